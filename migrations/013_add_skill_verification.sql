@@ -0,0 +1,12 @@
+-- Migration 013: Cache `ms verify` staleness results per skill
+CREATE TABLE IF NOT EXISTS skill_verification (
+    skill_id TEXT PRIMARY KEY,
+    content_hash TEXT NOT NULL,
+    score REAL NOT NULL,
+    commands_checked INTEGER NOT NULL,
+    references_checked INTEGER NOT NULL,
+    stale_json TEXT NOT NULL DEFAULT '[]',
+    verified_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_skill_verification_score ON skill_verification(score);
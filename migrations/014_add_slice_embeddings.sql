@@ -0,0 +1,13 @@
+-- Migration 014: Per-slice embeddings for slice-level retrieval
+CREATE TABLE IF NOT EXISTS slice_embeddings (
+    skill_id TEXT NOT NULL REFERENCES skills(id) ON DELETE CASCADE,
+    slice_id TEXT NOT NULL,
+    embedding BLOB NOT NULL,  -- f16 quantized
+    dims INTEGER NOT NULL,
+    embedder_type TEXT NOT NULL DEFAULT 'hash',
+    content_hash TEXT,
+    computed_at TEXT NOT NULL,
+    PRIMARY KEY (skill_id, slice_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_slice_embeddings_type ON slice_embeddings(embedder_type);
@@ -0,0 +1,15 @@
+-- Migration 015: Search impressions for learning-to-rank
+-- One row per result shown, with the feature snapshot used to rank it.
+CREATE TABLE IF NOT EXISTS search_impressions (
+    id TEXT PRIMARY KEY,
+    search_id TEXT NOT NULL,
+    query TEXT NOT NULL,
+    skill_id TEXT NOT NULL,
+    position INTEGER NOT NULL,     -- 0-indexed rank shown to the user
+    features_json TEXT NOT NULL,   -- RankFeatures
+    model_version INTEGER,         -- re-ranker version, NULL = RRF only
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_search_impressions_search ON search_impressions(search_id);
+CREATE INDEX IF NOT EXISTS idx_search_impressions_skill ON search_impressions(skill_id, created_at);
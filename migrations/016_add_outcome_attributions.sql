@@ -0,0 +1,17 @@
+-- Migration 016: Outcomes attributed from later CASS sessions
+-- Feedback now records where it came from and how sure we are.
+ALTER TABLE skill_feedback ADD COLUMN source TEXT NOT NULL DEFAULT 'manual';
+ALTER TABLE skill_feedback ADD COLUMN confidence REAL;
+
+-- One row per attributed skill load (skill_usage.id), so re-runs are idempotent.
+CREATE TABLE IF NOT EXISTS outcome_attributions (
+    usage_id INTEGER PRIMARY KEY,
+    skill_id TEXT NOT NULL,
+    session_id TEXT NOT NULL,      -- CASS session the verdict came from
+    success INTEGER NOT NULL,
+    confidence REAL NOT NULL,      -- 0.0 - 1.0
+    signals_json TEXT NOT NULL,    -- ["tests passed", "commit made", ...]
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_outcome_attributions_skill ON outcome_attributions(skill_id);
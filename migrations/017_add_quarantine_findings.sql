@@ -0,0 +1,3 @@
+-- Migration 017: Hidden-content findings on quarantine records
+-- JSON array of zero-width, bidi, homoglyph and encoded-payload findings.
+ALTER TABLE injection_quarantine ADD COLUMN findings_json TEXT NOT NULL DEFAULT '[]';
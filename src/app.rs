@@ -4,7 +4,8 @@ use std::sync::Arc;
 use crate::cli::OutputFormat;
 use crate::config::Config;
 use crate::error::{MsError, Result};
-use crate::search::SearchIndex;
+use crate::search::{SearchIndex, SliceIndex};
+use crate::security::PolicyEngine;
 use crate::storage::{Database, GitArchive};
 
 pub struct AppContext {
@@ -14,6 +15,10 @@ pub struct AppContext {
     pub db: Arc<Database>,
     pub git: Arc<GitArchive>,
     pub search: Arc<SearchIndex>,
+    /// Slice-level BM25 index (rules, pitfalls, commands, examples)
+    pub slices: Arc<SliceIndex>,
+    /// Organisation policy from every layer's `policy.toml`
+    pub policy: Arc<PolicyEngine>,
     /// Deprecated: use output_format instead
     pub robot_mode: bool,
     pub output_format: OutputFormat,
@@ -29,6 +34,8 @@ impl AppContext {
             .unwrap_or_else(|| default_config_path(&ms_root));
         let config = Config::load(cli.config.as_deref(), &ms_root)?;
 
+        let policy = Arc::new(PolicyEngine::load(&ms_root));
+
         Ok(Self {
             ms_root: ms_root.clone(),
             config_path,
@@ -36,6 +43,8 @@ impl AppContext {
             db: Arc::new(Database::open(ms_root.join("ms.db"))?),
             git: Arc::new(GitArchive::open(ms_root.join("archive"))?),
             search: Arc::new(SearchIndex::open(ms_root.join("index"))?),
+            slices: Arc::new(SliceIndex::open(ms_root.join("slice_index"))?),
+            policy,
             robot_mode: cli.robot,
             output_format: cli.output_format(),
             verbosity: cli.verbose,
@@ -0,0 +1,502 @@
+//! Security advisories for installed bundles.
+//!
+//! Advisories use a RustSec-like TOML format. A database is either a local
+//! directory, searched recursively for `*.toml` files, or a static URL that
+//! serves a single file. A file holds one advisory:
+//!
+//! ```toml
+//! [advisory]
+//! id = "MS-2026-0001"
+//! bundle = "community-devops"
+//! date = "2026-10-01"
+//! severity = "critical"
+//! title = "deploy-helper pipes a remote script into sh"
+//! skills = ["deploy-helper"]
+//! blobs = ["9f2c..."]
+//!
+//! [versions]
+//! patched = [">=1.4.2"]
+//! unaffected = ["<1.2.0"]
+//! ```
+//!
+//! or several as `[[advisories]]` tables, each with its own `versions`
+//! table, which is the form a URL mirror serves. A bundle version is affected
+//! unless it matches a `patched` or `unaffected` requirement. `blobs` lists
+//! skill blob hashes that are bad in any bundle at any version.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use walkdir::WalkDir;
+
+use crate::bundler::github::download_url;
+use crate::bundler::registry::{InstalledBundle, ParsedSource};
+use crate::error::{MsError, Result};
+
+/// Advisory directory checked under the ms root in addition to configured
+/// sources.
+pub const DEFAULT_ADVISORY_DIR: &str = "advisories";
+
+/// `[security.advisories]` configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdvisoryConfig {
+    /// Advisory databases: local directories or http(s) URLs
+    #[serde(default)]
+    pub sources: Vec<String>,
+    /// Refuse `ms bundle update` to a version with a known advisory
+    #[serde(default = "default_block_vulnerable_updates")]
+    pub block_vulnerable_updates: bool,
+}
+
+const fn default_block_vulnerable_updates() -> bool {
+    true
+}
+
+impl Default for AdvisoryConfig {
+    fn default() -> Self {
+        Self {
+            sources: Vec::new(),
+            block_vulnerable_updates: default_block_vulnerable_updates(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AdvisorySeverity {
+    Low,
+    #[default]
+    Medium,
+    High,
+    Critical,
+}
+
+/// Version ranges of an advisory.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AdvisoryVersions {
+    /// Versions that contain the fix
+    pub patched: Vec<VersionReq>,
+    /// Versions that never had the problem
+    pub unaffected: Vec<VersionReq>,
+}
+
+/// One advisory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Advisory {
+    pub id: String,
+    /// Affected bundle id; optional for blob-only advisories
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bundle: Option<String>,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    #[serde(default)]
+    pub severity: AdvisorySeverity,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    /// Affected skills; every skill in the bundle when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skills: Vec<String>,
+    /// Known-bad skill blob hashes
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blobs: Vec<String>,
+    /// Date the advisory was withdrawn; withdrawn advisories never match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub withdrawn: Option<String>,
+    #[serde(default)]
+    pub versions: AdvisoryVersions,
+}
+
+impl Advisory {
+    fn validate(&self) -> Result<()> {
+        if self.id.trim().is_empty() {
+            return Err(MsError::ValidationFailed(
+                "advisory is missing an id".to_string(),
+            ));
+        }
+        if self.bundle.is_none() && self.blobs.is_empty() {
+            return Err(MsError::ValidationFailed(format!(
+                "advisory {} names neither a bundle nor blobs",
+                self.id
+            )));
+        }
+        Ok(())
+    }
+
+    /// Whether a bundle version falls outside every patched and unaffected
+    /// range. Versions that are not semver are treated as affected.
+    #[must_use]
+    pub fn affects_version(&self, version: &str) -> bool {
+        let Ok(version) = Version::parse(version.trim_start_matches('v')) else {
+            return true;
+        };
+        !self
+            .versions
+            .patched
+            .iter()
+            .chain(&self.versions.unaffected)
+            .any(|req| req.matches(&version))
+    }
+
+    /// Whether a skill blob hash is listed as known-bad.
+    #[must_use]
+    pub fn matches_blob(&self, hash: &str) -> bool {
+        let hash = normalize_hash(hash);
+        self.blobs.iter().any(|blob| normalize_hash(blob) == hash)
+    }
+
+    /// Patched version requirements, for upgrade suggestions.
+    #[must_use]
+    pub fn fixed_versions(&self) -> Vec<String> {
+        self.versions
+            .patched
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+}
+
+/// An advisory that applies to a bundle version.
+#[derive(Debug, Clone, Serialize)]
+pub struct AdvisoryMatch {
+    pub bundle_id: String,
+    pub version: String,
+    pub advisory: Advisory,
+    /// The bundle version is in the affected range
+    pub version_affected: bool,
+    /// Skills whose blob hash is listed by the advisory
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub matched_blobs: Vec<String>,
+    pub affected_skills: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fixed_versions: Vec<String>,
+}
+
+/// Advisories loaded from every configured source.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AdvisoryDatabase {
+    pub sources: Vec<String>,
+    pub advisories: Vec<Advisory>,
+    /// Sources or files that could not be read
+    pub errors: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct AdvisoryDocument {
+    #[serde(default)]
+    advisory: Option<Advisory>,
+    #[serde(default)]
+    versions: Option<AdvisoryVersions>,
+    #[serde(default)]
+    advisories: Vec<Advisory>,
+}
+
+impl AdvisoryDatabase {
+    /// Load the configured sources plus `<ms_root>/advisories` when present.
+    /// URL sources are cached under `<ms_root>/cache/advisories`; with
+    /// `offline` only the cache is read.
+    #[must_use]
+    pub fn load(ms_root: &Path, config: &AdvisoryConfig, offline: bool) -> Self {
+        let mut sources = config.sources.clone();
+        let default_dir = ms_root.join(DEFAULT_ADVISORY_DIR);
+        if default_dir.is_dir() {
+            sources.push(default_dir.display().to_string());
+        }
+        Self::load_sources(&sources, &advisory_cache_dir(ms_root), offline)
+    }
+
+    /// Load advisories from directories and URLs.
+    #[must_use]
+    pub fn load_sources(sources: &[String], cache_dir: &Path, offline: bool) -> Self {
+        let mut db = Self::default();
+        for source in sources {
+            db.sources.push(source.clone());
+            if source.starts_with("http://") || source.starts_with("https://") {
+                db.load_url(source, cache_dir, offline);
+            } else {
+                db.load_dir(&ParsedSource::expand_path(source));
+            }
+        }
+        db
+    }
+
+    /// Parse one advisory file.
+    pub fn parse(raw: &str) -> Result<Vec<Advisory>> {
+        let document: AdvisoryDocument =
+            toml::from_str(raw).map_err(|err| MsError::Config(format!("parse advisory: {err}")))?;
+        let mut advisories = document.advisories;
+        if let Some(mut advisory) = document.advisory {
+            if let Some(versions) = document.versions {
+                advisory.versions = versions;
+            }
+            advisories.push(advisory);
+        }
+        for advisory in &advisories {
+            advisory.validate()?;
+        }
+        Ok(advisories)
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.advisories.is_empty()
+    }
+
+    /// Advisories that apply to one bundle version with the given skill
+    /// blobs (skill name to blob hash) and skill list.
+    #[must_use]
+    pub fn matches_for(
+        &self,
+        bundle_id: &str,
+        version: &str,
+        blobs: &BTreeMap<String, String>,
+        skills: &[String],
+    ) -> Vec<AdvisoryMatch> {
+        let mut matches = Vec::new();
+        for advisory in &self.advisories {
+            if advisory.withdrawn.is_some() {
+                continue;
+            }
+            let version_affected =
+                advisory.bundle.as_deref() == Some(bundle_id) && advisory.affects_version(version);
+            let matched_blobs: Vec<String> = blobs
+                .iter()
+                .filter(|(_, hash)| advisory.matches_blob(hash))
+                .map(|(skill, _)| skill.clone())
+                .collect();
+            if !version_affected && matched_blobs.is_empty() {
+                continue;
+            }
+
+            let mut affected_skills = matched_blobs.clone();
+            if version_affected {
+                for skill in skills {
+                    let listed = advisory.skills.is_empty() || advisory.skills.contains(skill);
+                    if listed && !affected_skills.contains(skill) {
+                        affected_skills.push(skill.clone());
+                    }
+                }
+            }
+            affected_skills.sort();
+
+            matches.push(AdvisoryMatch {
+                bundle_id: bundle_id.to_string(),
+                version: version.to_string(),
+                advisory: advisory.clone(),
+                version_affected,
+                matched_blobs,
+                affected_skills,
+                fixed_versions: advisory.fixed_versions(),
+            });
+        }
+        matches.sort_by_key(|found| std::cmp::Reverse(found.advisory.severity));
+        matches
+    }
+
+    /// Advisories that apply to installed bundles.
+    pub fn audit<'a>(
+        &self,
+        bundles: impl IntoIterator<Item = &'a InstalledBundle>,
+    ) -> Vec<AdvisoryMatch> {
+        bundles
+            .into_iter()
+            .flat_map(|bundle| {
+                self.matches_for(&bundle.id, &bundle.version, &bundle.blobs, &bundle.skills)
+            })
+            .collect()
+    }
+
+    fn load_dir(&mut self, dir: &Path) {
+        if !dir.is_dir() {
+            self.errors
+                .push(format!("{}: not a directory", dir.display()));
+            return;
+        }
+        let mut files: Vec<PathBuf> = WalkDir::new(dir)
+            .into_iter()
+            .filter_map(std::result::Result::ok)
+            .filter(|entry| entry.file_type().is_file())
+            .map(walkdir::DirEntry::into_path)
+            .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+            .collect();
+        files.sort();
+        for path in files {
+            let parsed = std::fs::read_to_string(&path)
+                .map_err(MsError::from)
+                .and_then(|raw| Self::parse(&raw));
+            match parsed {
+                Ok(advisories) => self.add(advisories),
+                Err(err) => self.errors.push(format!("{}: {err}", path.display())),
+            }
+        }
+    }
+
+    fn load_url(&mut self, url: &str, cache_dir: &Path, offline: bool) {
+        let cache_path = cache_dir.join(format!(
+            "{}.toml",
+            &hex::encode(Sha256::digest(url.as_bytes()))[..16]
+        ));
+        let fetched = if offline {
+            None
+        } else {
+            match download_url(url, None).and_then(|bytes| {
+                String::from_utf8(bytes).map_err(|err| {
+                    MsError::Config(format!("advisory database is not UTF-8: {err}"))
+                })
+            }) {
+                Ok(raw) => Some(raw),
+                Err(err) => {
+                    self.errors.push(format!("{url}: {err}"));
+                    None
+                }
+            }
+        };
+
+        let raw = match fetched {
+            Some(raw) => {
+                if std::fs::create_dir_all(cache_dir).is_ok() {
+                    let _ = std::fs::write(&cache_path, &raw);
+                }
+                raw
+            }
+            None => match std::fs::read_to_string(&cache_path) {
+                Ok(raw) => raw,
+                Err(_) => {
+                    if offline {
+                        self.errors.push(format!("{url}: no cached copy"));
+                    }
+                    return;
+                }
+            },
+        };
+        match Self::parse(&raw) {
+            Ok(advisories) => self.add(advisories),
+            Err(err) => self.errors.push(format!("{url}: {err}")),
+        }
+    }
+
+    /// Add advisories, keeping the first copy of each id.
+    fn add(&mut self, advisories: Vec<Advisory>) {
+        let mut seen: HashSet<String> = self.advisories.iter().map(|a| a.id.clone()).collect();
+        for advisory in advisories {
+            if seen.insert(advisory.id.clone()) {
+                self.advisories.push(advisory);
+            }
+        }
+    }
+}
+
+/// Where fetched URL databases are cached.
+#[must_use]
+pub fn advisory_cache_dir(ms_root: &Path) -> PathBuf {
+    ms_root.join("cache").join("advisories")
+}
+
+fn normalize_hash(hash: &str) -> String {
+    hash.trim()
+        .trim_start_matches("sha256:")
+        .to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundler::registry::InstallSource;
+
+    const SINGLE: &str = r#"
+[advisory]
+id = "MS-2026-0001"
+bundle = "community-devops"
+severity = "critical"
+title = "deploy-helper pipes a remote script into sh"
+skills = ["deploy-helper"]
+
+[versions]
+patched = [">=1.4.2"]
+unaffected = ["<1.2.0"]
+"#;
+
+    const COLLECTION: &str = r#"
+[[advisories]]
+id = "MS-2026-0002"
+title = "malicious curl | sh in shared blob"
+blobs = ["sha256:ABCDEF"]
+
+[[advisories]]
+id = "MS-2026-0003"
+bundle = "community-devops"
+title = "withdrawn"
+withdrawn = "2026-10-02"
+"#;
+
+    fn installed(version: &str) -> InstalledBundle {
+        InstalledBundle {
+            id: "community-devops".to_string(),
+            version: version.to_string(),
+            source: InstallSource::File {
+                path: "/tmp/devops.msb".to_string(),
+            },
+            installed_at: chrono::Utc::now(),
+            skills: vec!["deploy-helper".to_string(), "k8s-debug".to_string()],
+            checksum: None,
+            blobs: BTreeMap::from([
+                ("deploy-helper".to_string(), "1111".to_string()),
+                ("k8s-debug".to_string(), "abcdef".to_string()),
+            ]),
+        }
+    }
+
+    fn database() -> AdvisoryDatabase {
+        let mut db = AdvisoryDatabase::default();
+        db.add(AdvisoryDatabase::parse(SINGLE).unwrap());
+        db.add(AdvisoryDatabase::parse(COLLECTION).unwrap());
+        db
+    }
+
+    #[test]
+    fn version_ranges_and_blobs_match() {
+        let db = database();
+        assert_eq!(db.advisories.len(), 3);
+
+        let findings = db.audit([&installed("1.3.0")]);
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].advisory.id, "MS-2026-0001");
+        assert!(findings[0].version_affected);
+        assert_eq!(findings[0].affected_skills, vec!["deploy-helper"]);
+        assert_eq!(findings[0].fixed_versions, vec![">=1.4.2"]);
+        assert_eq!(findings[1].advisory.id, "MS-2026-0002");
+        assert_eq!(findings[1].matched_blobs, vec!["k8s-debug"]);
+
+        let patched = db.audit([&installed("1.4.2")]);
+        assert_eq!(patched.len(), 1);
+        assert_eq!(patched[0].advisory.id, "MS-2026-0002");
+        assert!(!patched[0].version_affected);
+        assert_eq!(db.audit([&installed("1.1.0")]).len(), 1);
+    }
+
+    #[test]
+    fn directory_sources_collect_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("community-devops");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(nested.join("MS-2026-0001.toml"), SINGLE).unwrap();
+        std::fs::write(dir.path().join("broken.toml"), "[advisory]\ntitle = 1\n").unwrap();
+
+        let db = AdvisoryDatabase::load_sources(
+            &[dir.path().display().to_string()],
+            &dir.path().join("cache"),
+            true,
+        );
+        assert_eq!(db.advisories.len(), 1);
+        assert_eq!(db.errors.len(), 1);
+        assert!(db.errors[0].contains("broken.toml"));
+    }
+}
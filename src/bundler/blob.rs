@@ -53,6 +53,45 @@ impl BlobStore {
         Ok(hash == hash_bytes(&data))
     }
 
+    /// Delete a blob, returning the number of bytes freed.
+    pub fn remove_blob(&self, hash: &str) -> Result<u64> {
+        let path = self.blob_path(hash)?;
+        let Ok(metadata) = fs::metadata(&path) else {
+            return Ok(0);
+        };
+        fs::remove_file(&path)
+            .map_err(|err| MsError::Config(format!("remove blob {}: {err}", path.display())))?;
+        Ok(metadata.len())
+    }
+
+    /// Hashes of every blob in the store (legacy flat and nested layouts).
+    pub fn blob_hashes(&self) -> Result<Vec<String>> {
+        let mut hashes = Vec::new();
+        let blobs = self.root.join("blobs");
+        for (dir, prefix) in [(blobs.clone(), ""), (blobs.join("sha256"), "sha256:")] {
+            if !dir.is_dir() {
+                continue;
+            }
+            for entry in fs::read_dir(&dir)
+                .map_err(|err| MsError::Config(format!("read dir {}: {err}", dir.display())))?
+            {
+                let entry = entry.map_err(|err| {
+                    MsError::Config(format!("read dir entry {}: {err}", dir.display()))
+                })?;
+                if !entry.path().is_file() {
+                    continue;
+                }
+                let hash = format!("{prefix}{}", entry.file_name().to_string_lossy());
+                if self.blob_path(&hash).is_ok() {
+                    hashes.push(hash);
+                }
+            }
+        }
+        hashes.sort();
+        hashes.dedup();
+        Ok(hashes)
+    }
+
     pub fn hash_path(path: &Path) -> Result<String> {
         if path.is_file() {
             let data = fs::read(path)
@@ -181,7 +220,7 @@ pub(crate) fn collect_files_for_bundle(
     Ok(())
 }
 
-fn hash_bytes(bytes: &[u8]) -> String {
+pub(crate) fn hash_bytes(bytes: &[u8]) -> String {
     let mut hasher = Sha256::new();
     hasher.update(bytes);
     let digest = hasher.finalize();
@@ -209,6 +248,22 @@ mod tests {
         assert!(store.verify_blob(&hash).unwrap());
     }
 
+    #[test]
+    fn list_and_remove_blobs() {
+        let dir = tempdir().unwrap();
+        let store = BlobStore::open(dir.path()).unwrap();
+        let first = store.write_blob(b"first").unwrap();
+        let second = store.write_blob(b"second").unwrap();
+        let mut expected = vec![first.clone(), second.clone()];
+        expected.sort();
+        assert_eq!(store.blob_hashes().unwrap(), expected);
+
+        assert_eq!(store.remove_blob(&first).unwrap(), 5);
+        assert!(!store.has_blob(&first));
+        assert_eq!(store.remove_blob(&first).unwrap(), 0);
+        assert_eq!(store.blob_hashes().unwrap(), vec![second]);
+    }
+
     #[test]
     fn blob_path_rejects_path_traversal() {
         let dir = tempdir().unwrap();
@@ -288,8 +288,7 @@ impl Ed25519Signer {
             ));
         }
 
-        // Generate key_id from public key (hex-encoded first 8 bytes)
-        let key_id = format!("ed25519:{}", hex::encode(&public_key_from_file[..8]));
+        let key_id = ed25519_key_id(&public_key_from_file);
 
         Ok(Self { keypair, key_id })
     }
@@ -318,6 +317,50 @@ impl Ed25519Signer {
     }
 }
 
+/// Key ID for an Ed25519 public key (hex-encoded first 8 bytes).
+#[must_use]
+pub fn ed25519_key_id(public_key: &[u8]) -> String {
+    let prefix = &public_key[..public_key.len().min(8)];
+    format!("ed25519:{}", hex::encode(prefix))
+}
+
+/// Parse an OpenSSH Ed25519 public key line (`ssh-ed25519 AAAA... comment`)
+/// and return (`key_id`, `public_key`).
+pub fn parse_openssh_ed25519_public_key(line: &str) -> Result<(String, Vec<u8>)> {
+    use base64::Engine;
+
+    let mut fields = line.split_whitespace();
+    let key_type = fields.next().unwrap_or_default();
+    if key_type != "ssh-ed25519" {
+        return Err(MsError::ValidationFailed(format!(
+            "expected ssh-ed25519 public key, found: {key_type}"
+        )));
+    }
+    let encoded = fields.next().ok_or_else(|| {
+        MsError::ValidationFailed("missing key data in SSH public key".to_string())
+    })?;
+    let data = base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .map_err(|err| MsError::ValidationFailed(format!("invalid base64 in SSH key: {err}")))?;
+
+    let mut cursor = 0;
+    let blob_type = read_openssh_string(&data, &mut cursor)?;
+    if blob_type != "ssh-ed25519" {
+        return Err(MsError::ValidationFailed(format!(
+            "expected ssh-ed25519 key, found: {blob_type}"
+        )));
+    }
+    let public_key = read_openssh_bytes(&data, &mut cursor)?;
+    if public_key.len() != 32 {
+        return Err(MsError::ValidationFailed(format!(
+            "invalid Ed25519 public key length: {} (expected 32)",
+            public_key.len()
+        )));
+    }
+
+    Ok((ed25519_key_id(public_key), public_key.to_vec()))
+}
+
 /// Parse an OpenSSH Ed25519 private key and return (seed, `public_key`).
 ///
 /// The OpenSSH format is documented at:
@@ -1005,6 +1048,29 @@ checksum = "sha256:abc123"
         assert_eq!(signer.public_key().len(), 32);
     }
 
+    #[test]
+    fn parses_openssh_public_key_line() {
+        use base64::Engine;
+
+        let (pem, public_key) = generate_test_openssh_key();
+        let signer = Ed25519Signer::from_openssh_str(&pem).unwrap();
+
+        let mut blob = Vec::new();
+        blob.extend_from_slice(&11u32.to_be_bytes());
+        blob.extend_from_slice(b"ssh-ed25519");
+        blob.extend_from_slice(&32u32.to_be_bytes());
+        blob.extend_from_slice(&public_key);
+        let line = format!(
+            "ssh-ed25519 {} alice@example",
+            base64::engine::general_purpose::STANDARD.encode(&blob)
+        );
+
+        let (key_id, parsed) = parse_openssh_ed25519_public_key(&line).unwrap();
+        assert_eq!(key_id, signer.key_id());
+        assert_eq!(parsed, public_key);
+        assert!(parse_openssh_ed25519_public_key("ssh-rsa AAAA").is_err());
+    }
+
     #[test]
     fn ed25519_signer_signs_data() {
         let (pem, _public_key) = generate_test_openssh_key();
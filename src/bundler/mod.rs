@@ -1,5 +1,6 @@
 //! Skill bundler for packaging and distribution
 
+pub mod advisory;
 pub mod blob;
 pub mod github;
 pub mod install;
@@ -8,6 +9,10 @@ pub mod manifest;
 pub mod package;
 pub mod registry;
 
+pub use advisory::{
+    Advisory, AdvisoryConfig, AdvisoryDatabase, AdvisoryMatch, AdvisorySeverity, AdvisoryVersions,
+    advisory_cache_dir,
+};
 pub use blob::BlobStore;
 pub use install::{InstallOptions, InstallReport, install, install_with_options};
 pub use local_safety::{
@@ -17,7 +22,9 @@ pub use local_safety::{
 };
 pub use manifest::{
     BundleDependency, BundleInfo, BundleManifest, BundleSignature, BundledSkill, Ed25519Signer,
-    Ed25519Verifier, SignatureVerifier,
+    Ed25519Verifier, SignatureVerifier, ed25519_key_id, parse_openssh_ed25519_public_key,
+};
+pub use package::{
+    Bundle, BundleBlob, BundlePackage, blob_entries, missing_blobs, skill_blob_hash,
 };
-pub use package::{Bundle, BundleBlob, BundlePackage, missing_blobs};
 pub use registry::{BundleRegistry, InstallSource, InstalledBundle, ParsedSource};
@@ -267,6 +267,35 @@ fn build_blob_bytes(path: &Path) -> Result<Vec<u8>> {
     Ok(out)
 }
 
+/// Blob hash of a skill path, as `Bundle::package` computes it.
+pub fn skill_blob_hash(path: &Path) -> Result<String> {
+    Ok(hash_bytes(&build_blob_bytes(path)?))
+}
+
+/// Split a directory blob back into its `(relative path, contents)` entries.
+pub fn blob_entries(bytes: &[u8]) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+    let mut cursor = 0usize;
+    let mut entries = Vec::new();
+    while cursor < bytes.len() {
+        let name_len = usize::try_from(read_u64(bytes, &mut cursor)?)
+            .map_err(|_| MsError::ValidationFailed("bundle entry too large".to_string()))?;
+        let name =
+            std::str::from_utf8(read_slice(bytes, &mut cursor, name_len)?).map_err(|_| {
+                MsError::ValidationFailed("bundle entry path is invalid UTF-8".to_string())
+            })?;
+        if name.is_empty() {
+            return Err(MsError::ValidationFailed(
+                "bundle entry has empty path".to_string(),
+            ));
+        }
+        let file_len = usize::try_from(read_u64(bytes, &mut cursor)?)
+            .map_err(|_| MsError::ValidationFailed("bundle entry too large".to_string()))?;
+        let data = read_slice(bytes, &mut cursor, file_len)?.to_vec();
+        entries.push((PathBuf::from(name), data));
+    }
+    Ok(entries)
+}
+
 fn bundle_checksum(manifest: &BundleManifest, blobs: &[BundleBlob]) -> Result<String> {
     let mut manifest = manifest.clone();
     manifest.checksum = None;
@@ -1,11 +1,14 @@
 //! Installed bundle registry for tracking bundle installations.
 
-use std::collections::HashMap;
-use std::path::{Path, PathBuf};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Component, Path, PathBuf};
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::bundler::blob::BlobStore;
+use crate::bundler::manifest::BundleManifest;
+use crate::bundler::package::{blob_entries, skill_blob_hash};
 use crate::error::{MsError, Result};
 
 /// Information about an installed bundle.
@@ -17,6 +20,89 @@ pub struct InstalledBundle {
     pub installed_at: DateTime<Utc>,
     pub skills: Vec<String>,
     pub checksum: Option<String>,
+    /// Blob hash of each bundled skill, keyed by skill name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub blobs: BTreeMap<String, String>,
+}
+
+impl InstalledBundle {
+    /// Blob hashes recorded in a bundle manifest, keyed by skill name.
+    #[must_use]
+    pub fn blobs_from_manifest(manifest: &BundleManifest) -> BTreeMap<String, String> {
+        manifest
+            .skills
+            .iter()
+            .filter_map(|skill| Some((skill.name.clone(), skill.hash.clone()?)))
+            .collect()
+    }
+
+    /// Blob hashes to audit, keyed by skill name.
+    ///
+    /// Installs registered before `blobs` was recorded have none, so each
+    /// installed skill is matched against the blobs kept in the archive's
+    /// bundle store, falling back to hashing the installed files.
+    #[must_use]
+    pub fn audit_blobs(&self, archive_root: &Path) -> BTreeMap<String, String> {
+        if !self.blobs.is_empty() {
+            return self.blobs.clone();
+        }
+        let stored = stored_blobs(archive_root);
+        self.skills
+            .iter()
+            .filter_map(|skill| {
+                let dir = installed_skill_dir(archive_root, skill)?;
+                let hash = stored
+                    .iter()
+                    .find(|(_, entries)| entries_match(&dir, entries))
+                    .map(|(hash, _)| hash.clone())
+                    .or_else(|| skill_blob_hash(&dir).ok())?;
+                Some((skill.clone(), hash))
+            })
+            .collect()
+    }
+}
+
+type BlobEntries = Vec<(PathBuf, Vec<u8>)>;
+
+/// Every directory blob in the archive's bundle store, decoded.
+fn stored_blobs(archive_root: &Path) -> Vec<(String, BlobEntries)> {
+    let root = archive_root.join("bundles");
+    if !root.is_dir() {
+        return Vec::new();
+    }
+    let Ok(store) = BlobStore::open(&root) else {
+        return Vec::new();
+    };
+    store
+        .blob_hashes()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|hash| {
+            let entries = blob_entries(&store.read_blob(&hash).ok()?).ok()?;
+            (!entries.is_empty()).then_some((hash, entries))
+        })
+        .collect()
+}
+
+fn installed_skill_dir(archive_root: &Path, skill: &str) -> Option<PathBuf> {
+    let mut components = Path::new(skill).components();
+    if !matches!(
+        (components.next(), components.next()),
+        (Some(Component::Normal(_)), None)
+    ) {
+        return None;
+    }
+    let dir = archive_root.join("skills").join("by-id").join(skill);
+    dir.is_dir().then_some(dir)
+}
+
+/// Whether every file in a blob is present in `dir` with the same contents.
+fn entries_match(dir: &Path, entries: &BlobEntries) -> bool {
+    entries.iter().all(|(rel, bytes)| {
+        rel.components()
+            .all(|component| matches!(component, Component::Normal(_)))
+            && std::fs::read(dir.join(rel)).is_ok_and(|data| data == *bytes)
+    })
 }
 
 /// Source from which a bundle was installed.
@@ -166,7 +252,8 @@ impl ParsedSource {
             || input.starts_with('/')
     }
 
-    fn expand_path(input: &str) -> PathBuf {
+    /// Expand a leading `~` to the home directory.
+    pub(crate) fn expand_path(input: &str) -> PathBuf {
         if input == "~" {
             if let Some(home) = dirs::home_dir() {
                 return home;
@@ -362,4 +449,65 @@ mod tests {
             _ => panic!("expected GitHub source for ambiguous path"),
         }
     }
+
+    #[test]
+    fn audit_blobs_recovers_hashes_for_old_installs() {
+        use crate::bundler::install::install;
+        use crate::bundler::manifest::{BundleInfo, BundledSkill};
+        use crate::bundler::package::Bundle;
+
+        let source = tempfile::tempdir().unwrap();
+        let skill_dir = source.path().join("skills/by-id/demo");
+        std::fs::create_dir_all(&skill_dir).unwrap();
+        std::fs::write(skill_dir.join("SKILL.md"), "# Demo\n").unwrap();
+        let manifest = BundleManifest {
+            bundle: BundleInfo {
+                id: "bundle".to_string(),
+                name: "Bundle".to_string(),
+                version: "1.0.0".to_string(),
+                description: None,
+                authors: vec![],
+                license: None,
+                repository: None,
+                keywords: vec![],
+                ms_version: None,
+            },
+            skills: vec![BundledSkill {
+                name: "demo".to_string(),
+                path: PathBuf::from("skills/by-id/demo"),
+                version: None,
+                hash: None,
+                optional: false,
+            }],
+            dependencies: vec![],
+            checksum: None,
+            signatures: vec![],
+        };
+        let package = Bundle::new(manifest, source.path()).package().unwrap();
+        let expected = InstalledBundle::blobs_from_manifest(&package.manifest);
+
+        let archive = tempfile::tempdir().unwrap();
+        install(&package, archive.path(), &[]).unwrap();
+        let old = InstalledBundle {
+            id: "bundle".to_string(),
+            version: "1.0.0".to_string(),
+            source: InstallSource::File {
+                path: "bundle.msb".to_string(),
+            },
+            installed_at: Utc::now(),
+            skills: vec!["demo".to_string()],
+            checksum: None,
+            blobs: BTreeMap::new(),
+        };
+
+        // Files added after install (e.g. by indexing) still match the stored blob.
+        let installed = archive.path().join("skills/by-id/demo");
+        std::fs::write(installed.join("skill.spec.json"), "{}").unwrap();
+        assert_eq!(old.audit_blobs(archive.path()), expected);
+
+        // Without the blob store, the installed files are hashed directly.
+        std::fs::remove_dir_all(archive.path().join("bundles")).unwrap();
+        std::fs::remove_file(installed.join("skill.spec.json")).unwrap();
+        assert_eq!(old.audit_blobs(archive.path()), expected);
+    }
 }
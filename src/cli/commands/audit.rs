@@ -0,0 +1,186 @@
+//! ms audit - Check installed bundles against advisory databases
+
+use clap::Args;
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::app::AppContext;
+use crate::bundler::{
+    AdvisoryDatabase, AdvisoryMatch, AdvisorySeverity, BundleRegistry, InstalledBundle,
+    advisory_cache_dir,
+};
+use crate::cli::output::OutputFormat;
+use crate::cli::output::emit_json;
+use crate::error::{MsError, Result};
+use crate::storage::sqlite::SkillProjectUsage;
+
+#[derive(Args, Debug)]
+pub struct AuditArgs {
+    /// Advisory database directory or URL (overrides security.advisories.sources)
+    #[arg(long = "db")]
+    pub db: Vec<String>,
+
+    /// Only audit this bundle
+    #[arg(long)]
+    pub bundle: Option<String>,
+
+    /// Use cached copies of URL databases instead of fetching
+    #[arg(long)]
+    pub offline: bool,
+}
+
+#[derive(Serialize)]
+struct AuditOutput {
+    sources: Vec<String>,
+    advisories: usize,
+    bundles: usize,
+    errors: Vec<String>,
+    findings: Vec<AuditFinding>,
+}
+
+#[derive(Serialize)]
+struct AuditFinding {
+    #[serde(flatten)]
+    found: AdvisoryMatch,
+    /// Where each affected skill has been loaded
+    usage: Vec<SkillUsage>,
+}
+
+#[derive(Serialize)]
+struct SkillUsage {
+    skill_id: String,
+    loads: u64,
+    projects: Vec<SkillProjectUsage>,
+}
+
+pub fn run(ctx: &AppContext, args: &AuditArgs) -> Result<()> {
+    let db = if args.db.is_empty() {
+        AdvisoryDatabase::load(&ctx.ms_root, &ctx.config.security.advisories, args.offline)
+    } else {
+        AdvisoryDatabase::load_sources(&args.db, &advisory_cache_dir(&ctx.ms_root), args.offline)
+    };
+
+    let registry = BundleRegistry::open(ctx.git.root())?;
+    let bundles: Vec<InstalledBundle> = registry
+        .list()
+        .filter(|bundle| args.bundle.as_deref().is_none_or(|id| bundle.id == id))
+        .map(|bundle| InstalledBundle {
+            blobs: bundle.audit_blobs(ctx.git.root()),
+            ..bundle.clone()
+        })
+        .collect();
+    if let Some(id) = &args.bundle {
+        if bundles.is_empty() {
+            return Err(MsError::NotFound(format!("bundle '{id}' is not installed")));
+        }
+    }
+
+    let mut findings = Vec::new();
+    for found in db.audit(&bundles) {
+        let mut usage = Vec::new();
+        for skill_id in &found.affected_skills {
+            let projects = ctx.db.list_skill_usage_by_project(skill_id)?;
+            usage.push(SkillUsage {
+                skill_id: skill_id.clone(),
+                loads: projects.iter().map(|project| project.loads).sum(),
+                projects,
+            });
+        }
+        findings.push(AuditFinding { found, usage });
+    }
+
+    let output = AuditOutput {
+        sources: db.sources.clone(),
+        advisories: db.advisories.len(),
+        bundles: bundles.len(),
+        errors: db.errors.clone(),
+        findings,
+    };
+    if ctx.output_format != OutputFormat::Human {
+        emit_json(&output)?;
+    } else {
+        print_audit(&output);
+    }
+
+    if output.findings.is_empty() {
+        Ok(())
+    } else {
+        Err(MsError::ValidationFailed(format!(
+            "{} advisory match(es) in installed bundles",
+            output.findings.len()
+        )))
+    }
+}
+
+fn print_audit(output: &AuditOutput) {
+    for error in &output.errors {
+        eprintln!("Warning: advisory database: {error}");
+    }
+    println!(
+        "Audited {} bundle(s) against {} advisory(ies) from {} source(s)",
+        output.bundles,
+        output.advisories,
+        output.sources.len()
+    );
+    if output.sources.is_empty() {
+        println!("No advisory sources: set security.advisories.sources or pass --db.");
+    }
+    if output.findings.is_empty() {
+        println!(
+            "{}",
+            "No known advisories affect installed bundles.".green()
+        );
+        return;
+    }
+
+    for finding in &output.findings {
+        let found = &finding.found;
+        let advisory = &found.advisory;
+        let severity = match advisory.severity {
+            AdvisorySeverity::Critical => "critical".red().bold(),
+            AdvisorySeverity::High => "high".red(),
+            AdvisorySeverity::Medium => "medium".yellow(),
+            AdvisorySeverity::Low => "low".normal(),
+        };
+        println!(
+            "\n{severity} {} {}@{}: {}",
+            advisory.id.bold(),
+            found.bundle_id,
+            found.version,
+            advisory.title
+        );
+        if !found.matched_blobs.is_empty() {
+            println!("  known-bad content: {}", found.matched_blobs.join(", "));
+        }
+        println!("  affected skills: {}", found.affected_skills.join(", "));
+        if found.version_affected {
+            if found.fixed_versions.is_empty() {
+                println!("  no fixed version: remove the bundle");
+            } else {
+                println!("  upgrade to: {}", found.fixed_versions.join(" or "));
+            }
+        }
+        if let Some(url) = &advisory.url {
+            println!("  {url}");
+        }
+        for usage in &finding.usage {
+            if usage.loads == 0 {
+                continue;
+            }
+            println!(
+                "  {} loaded {} time(s) in {} project(s):",
+                usage.skill_id,
+                usage.loads,
+                usage.projects.len()
+            );
+            for project in &usage.projects {
+                println!(
+                    "    {} ({} load(s), last {})",
+                    project.project_path.as_deref().unwrap_or("(no project)"),
+                    project.loads,
+                    project.last_used
+                );
+            }
+        }
+    }
+}
@@ -1,4 +1,8 @@
 //! ms backup - snapshot and restore ms state.
+//!
+//! New backups are content-addressed snapshots (see [`crate::storage::backup`]);
+//! full-copy backups made by earlier versions can still be listed, verified,
+//! and restored.
 
 use std::path::{Path, PathBuf};
 
@@ -9,6 +13,10 @@ use serde::{Deserialize, Serialize};
 use crate::app::AppContext;
 use crate::cli::output::OutputFormat;
 use crate::error::{MsError, Result};
+use crate::storage::backup::{
+    BLOB_DIR, BackupKey, BackupStore, RetentionPolicy, SnapshotManifest, SnapshotSource,
+    VerifyReport, restore_skill_rows, restore_table, user_tables,
+};
 use crate::storage::tx::GlobalLock;
 
 #[derive(Args, Debug)]
@@ -25,6 +33,10 @@ pub enum BackupCommand {
     List(BackupListArgs),
     /// Restore from a backup snapshot
     Restore(BackupRestoreArgs),
+    /// Check backup integrity
+    Verify(BackupVerifyArgs),
+    /// Delete backups outside a retention policy
+    Prune(BackupPruneArgs),
 }
 
 #[derive(Args, Debug)]
@@ -32,6 +44,10 @@ pub struct BackupCreateArgs {
     /// Backup ID (default: timestamp)
     #[arg(long)]
     pub id: Option<String>,
+
+    /// Encrypt the backup with the passphrase in this environment variable
+    #[arg(long)]
+    pub passphrase_env: Option<String>,
 }
 
 #[derive(Args, Debug)]
@@ -53,6 +69,52 @@ pub struct BackupRestoreArgs {
     /// Apply restore (required)
     #[arg(long)]
     pub approve: bool,
+
+    /// Restore only this skill (archive files and database rows)
+    #[arg(long, conflicts_with = "table")]
+    pub skill: Option<String>,
+
+    /// Restore only this database table (e.g. `skill_feedback` or `feedback`)
+    #[arg(long)]
+    pub table: Option<String>,
+
+    /// Environment variable holding the passphrase of an encrypted backup
+    #[arg(long)]
+    pub passphrase_env: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct BackupVerifyArgs {
+    /// Backup ID to verify (default: all backups)
+    pub id: Option<String>,
+
+    /// Environment variable holding the passphrase; without it encrypted
+    /// chunks are hash-checked but not decrypted
+    #[arg(long)]
+    pub passphrase_env: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct BackupPruneArgs {
+    /// Keep the N most recent backups
+    #[arg(long, default_value = "0")]
+    pub keep_last: usize,
+
+    /// Keep the newest backup of each of the last N days
+    #[arg(long, default_value = "0")]
+    pub keep_daily: usize,
+
+    /// Keep the newest backup of each of the last N weeks
+    #[arg(long, default_value = "0")]
+    pub keep_weekly: usize,
+
+    /// Keep the newest backup of each of the last N months
+    #[arg(long, default_value = "0")]
+    pub keep_monthly: usize,
+
+    /// Show what would be removed without deleting anything
+    #[arg(long)]
+    pub dry_run: bool,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -65,6 +127,18 @@ struct BackupManifest {
     total_bytes: u64,
 }
 
+#[derive(Serialize)]
+struct BackupSummary {
+    id: String,
+    created_at: String,
+    /// `incremental` (content-addressed) or `full` (legacy copy)
+    kind: &'static str,
+    total_bytes: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    new_bytes: Option<u64>,
+    encrypted: bool,
+}
+
 #[derive(Serialize, Deserialize)]
 struct BackupEntry {
     name: String,
@@ -79,109 +153,45 @@ pub fn run(ctx: &AppContext, args: &BackupArgs) -> Result<()> {
         BackupCommand::Create(create) => run_create(ctx, create),
         BackupCommand::List(list) => run_list(ctx, list),
         BackupCommand::Restore(restore) => run_restore(ctx, restore),
+        BackupCommand::Verify(verify) => run_verify(ctx, verify),
+        BackupCommand::Prune(prune) => run_prune(ctx, prune),
     }
 }
 
 fn run_create(ctx: &AppContext, args: &BackupCreateArgs) -> Result<()> {
     let _lock = GlobalLock::acquire(&ctx.ms_root)?;
-    let backup_root = backup_root(ctx);
     let backup_id = args.id.clone().unwrap_or_else(timestamp_id);
     validate_backup_id(&backup_id)?;
 
-    let backup_dir = backup_root.join(&backup_id);
-    if backup_dir.exists() {
+    let store = BackupStore::open(backup_root(ctx))?;
+    if store.root().join(&backup_id).exists() {
         return Err(MsError::ValidationFailed(format!(
             "backup {backup_id} already exists"
         )));
     }
-    std::fs::create_dir_all(&backup_dir)
-        .map_err(|err| MsError::Config(format!("create {}: {err}", backup_dir.display())))?;
-
-    let mut entries = Vec::new();
-    let mut total_bytes = 0u64;
-
-    let ms_db = ctx.ms_root.join("ms.db");
-    push_file_entry(
-        &mut entries,
-        &mut total_bytes,
-        "ms.db",
-        &ms_db,
-        &backup_dir.join("ms.db"),
-    )?;
-    let wal = ctx.ms_root.join("ms.db-wal");
-    push_file_entry(
-        &mut entries,
-        &mut total_bytes,
-        "ms.db-wal",
-        &wal,
-        &backup_dir.join("ms.db-wal"),
-    )?;
-    let shm = ctx.ms_root.join("ms.db-shm");
-    push_file_entry(
-        &mut entries,
-        &mut total_bytes,
-        "ms.db-shm",
-        &shm,
-        &backup_dir.join("ms.db-shm"),
-    )?;
-
-    let archive = ctx.ms_root.join("archive");
-    if archive.exists() {
-        let backup_path = backup_dir.join("archive");
-        let bytes = copy_dir_recursive(&archive, &backup_path)?;
-        total_bytes += bytes;
-        entries.push(BackupEntry {
-            name: "archive".to_string(),
-            source_path: archive.display().to_string(),
-            backup_path: backup_path.display().to_string(),
-            bytes,
-            is_dir: true,
-        });
-    }
-
-    let index = ctx.ms_root.join("index");
-    if index.exists() {
-        let backup_path = backup_dir.join("index");
-        let bytes = copy_dir_recursive(&index, &backup_path)?;
-        total_bytes += bytes;
-        entries.push(BackupEntry {
-            name: "index".to_string(),
-            source_path: index.display().to_string(),
-            backup_path: backup_path.display().to_string(),
-            bytes,
-            is_dir: true,
-        });
-    }
-
+    let key = unlock(&store, args.passphrase_env.as_deref())?;
+
+    let mut sources: Vec<SnapshotSource> = ["ms.db", "ms.db-wal", "ms.db-shm", "archive", "index"]
+        .iter()
+        .map(|name| SnapshotSource {
+            name: (*name).to_string(),
+            path: ctx.ms_root.join(name),
+        })
+        .collect();
     let config_path = if ctx.config_path.exists() {
-        let backup_path = backup_dir.join("config.toml");
-        let bytes = copy_file(&ctx.config_path, &backup_path)?;
-        total_bytes += bytes;
-        entries.push(BackupEntry {
-            name: "config".to_string(),
-            source_path: ctx.config_path.display().to_string(),
-            backup_path: backup_path.display().to_string(),
-            bytes,
-            is_dir: false,
+        sources.push(SnapshotSource {
+            name: "config.toml".to_string(),
+            path: ctx.config_path.clone(),
         });
         Some(ctx.config_path.display().to_string())
     } else {
         None
     };
 
-    let manifest = BackupManifest {
-        id: backup_id.clone(),
-        created_at: Utc::now().to_rfc3339(),
-        ms_root: ctx.ms_root.display().to_string(),
-        config_path,
-        entries,
-        total_bytes,
-    };
-    let manifest_path = backup_dir.join("manifest.json");
-    let manifest_json = serde_json::to_string_pretty(&manifest)
-        .map_err(|err| MsError::Config(format!("serialize manifest: {err}")))?;
-    std::fs::write(&manifest_path, manifest_json)
-        .map_err(|err| MsError::Config(format!("write {}: {err}", manifest_path.display())))?;
+    let mut manifest = store.snapshot(&backup_id, &sources, key.as_ref())?;
+    manifest.ms_root = ctx.ms_root.display().to_string();
+    manifest.config_path = config_path;
+    let backup_dir = store.save(&manifest)?;
 
     if ctx.output_format != OutputFormat::Human {
         return crate::cli::output::emit_json(&manifest);
@@ -189,8 +199,20 @@ fn run_create(ctx: &AppContext, args: &BackupCreateArgs) -> Result<()> {
 
     println!("Backup created: {backup_id}");
     println!("Path: {}", backup_dir.display());
-    println!("Entries: {}", manifest.entries.len());
+    println!("Files: {}", manifest.files.len());
     println!("Size: {} bytes", manifest.total_bytes);
+    println!(
+        "New data: {} bytes{}",
+        manifest.new_bytes,
+        manifest
+            .parent
+            .as_ref()
+            .map(|parent| format!(" (incremental on {parent})"))
+            .unwrap_or_default()
+    );
+    if manifest.encrypted {
+        println!("Encrypted: yes");
+    }
     Ok(())
 }
 
@@ -208,31 +230,34 @@ fn run_list(ctx: &AppContext, args: &BackupListArgs) -> Result<()> {
         return Ok(());
     }
 
+    let store = BackupStore::open(&backup_root)?;
     let mut backups = Vec::new();
-    let mut dirs = std::fs::read_dir(&backup_root)
-        .map_err(|err| MsError::Config(format!("read {}: {err}", backup_root.display())))?
-        .filter_map(std::result::Result::ok)
-        .filter(|entry| entry.path().is_dir())
-        .collect::<Vec<_>>();
-    dirs.sort_by_key(std::fs::DirEntry::file_name);
-    dirs.reverse();
-
-    for entry in dirs.into_iter().take(args.limit) {
-        let path = entry.path();
-        let manifest_path = path.join("manifest.json");
-        if let Ok(content) = std::fs::read_to_string(&manifest_path) {
-            if let Ok(manifest) = serde_json::from_str::<BackupManifest>(&content) {
-                backups.push(manifest);
-                continue;
-            }
+    for id in store.snapshot_ids()?.into_iter().rev().take(args.limit) {
+        if let Ok(Some(manifest)) = store.load(&id) {
+            backups.push(BackupSummary {
+                id,
+                created_at: manifest.created_at,
+                kind: "incremental",
+                total_bytes: manifest.total_bytes,
+                new_bytes: Some(manifest.new_bytes),
+                encrypted: manifest.encrypted,
+            });
+            continue;
         }
-        backups.push(BackupManifest {
-            id: entry.file_name().to_string_lossy().to_string(),
-            created_at: String::new(),
-            ms_root: ctx.ms_root.display().to_string(),
-            config_path: None,
-            entries: Vec::new(),
-            total_bytes: 0,
+        let manifest_path = backup_root.join(&id).join("manifest.json");
+        let legacy = std::fs::read_to_string(&manifest_path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<BackupManifest>(&content).ok());
+        backups.push(BackupSummary {
+            id,
+            created_at: legacy
+                .as_ref()
+                .map(|manifest| manifest.created_at.clone())
+                .unwrap_or_default(),
+            kind: "full",
+            total_bytes: legacy.map_or(0, |manifest| manifest.total_bytes),
+            new_bytes: None,
+            encrypted: false,
         });
     }
 
@@ -256,7 +281,15 @@ fn run_list(ctx: &AppContext, args: &BackupListArgs) -> Result<()> {
         } else {
             backup.created_at.clone()
         };
-        println!("  {}  {}  {} bytes", backup.id, created, backup.total_bytes);
+        let new_bytes = backup
+            .new_bytes
+            .map(|bytes| format!(" ({bytes} new)"))
+            .unwrap_or_default();
+        let encrypted = if backup.encrypted { "  encrypted" } else { "" };
+        println!(
+            "  {}  {}  {}  {} bytes{new_bytes}{encrypted}",
+            backup.id, created, backup.kind, backup.total_bytes
+        );
     }
     Ok(())
 }
@@ -289,6 +322,16 @@ fn run_restore(ctx: &AppContext, args: &BackupRestoreArgs) -> Result<()> {
     };
     validate_backup_id(&backup_id)?;
 
+    let store = BackupStore::open(&backup_root)?;
+    if let Some(manifest) = store.load(&backup_id)? {
+        return restore_snapshot(ctx, args, &store, &manifest);
+    }
+    if args.skill.is_some() || args.table.is_some() {
+        return Err(MsError::ValidationFailed(format!(
+            "backup {backup_id} is a full copy; partial restore needs a backup made with this version"
+        )));
+    }
+
     let backup_dir = backup_root.join(&backup_id);
     let manifest_path = backup_dir.join("manifest.json");
     if !manifest_path.exists() {
@@ -346,6 +389,368 @@ fn run_restore(ctx: &AppContext, args: &BackupRestoreArgs) -> Result<()> {
     Ok(())
 }
 
+fn restore_snapshot(
+    ctx: &AppContext,
+    args: &BackupRestoreArgs,
+    store: &BackupStore,
+    manifest: &SnapshotManifest,
+) -> Result<()> {
+    let key = if manifest.encrypted {
+        let key = unlock(store, args.passphrase_env.as_deref())?;
+        if key.is_none() {
+            return Err(MsError::ValidationFailed(format!(
+                "backup {} is encrypted; pass --passphrase-env",
+                manifest.id
+            )));
+        }
+        key
+    } else {
+        None
+    };
+    let backup_dir = store.root().join(&manifest.id);
+
+    if let Some(skill_id) = &args.skill {
+        return restore_snapshot_skill(ctx, store, manifest, key.as_ref(), skill_id);
+    }
+    if let Some(table) = &args.table {
+        return restore_snapshot_table(ctx, store, manifest, key.as_ref(), table);
+    }
+
+    let mut restored = 0usize;
+    let mut skipped = Vec::new();
+    for file in &manifest.files {
+        let Some(dest) = snapshot_restore_dest(&ctx.ms_root, &ctx.config_path, &file.path) else {
+            skipped.push(file.path.clone());
+            continue;
+        };
+        validate_restore_paths(
+            backup_dir.join(&file.path).to_string_lossy().as_ref(),
+            dest.to_string_lossy().as_ref(),
+            &backup_dir,
+            &ctx.ms_root,
+            ctx.config_path.as_path(),
+        )?;
+        store.restore_file(file, manifest.encrypted, key.as_ref(), &dest)?;
+        restored += 1;
+    }
+    // A WAL left over from the live database must not be replayed onto the
+    // restored one.
+    for name in ["ms.db-wal", "ms.db-shm"] {
+        if !manifest.files.iter().any(|file| file.path == name) {
+            let _ = std::fs::remove_file(ctx.ms_root.join(name));
+        }
+    }
+
+    if ctx.output_format != OutputFormat::Human {
+        return crate::cli::output::emit_json(&serde_json::json!({
+            "status": "ok",
+            "restored": manifest.id,
+            "entries": manifest.files.len(),
+            "restored_count": restored,
+            "skipped": skipped,
+        }));
+    }
+
+    println!("Restored backup: {}", manifest.id);
+    println!("Files restored: {restored}");
+    if !skipped.is_empty() {
+        println!("Files skipped: {}", skipped.join(", "));
+    }
+    Ok(())
+}
+
+fn restore_snapshot_skill(
+    ctx: &AppContext,
+    store: &BackupStore,
+    manifest: &SnapshotManifest,
+    key: Option<&BackupKey>,
+    skill_id: &str,
+) -> Result<()> {
+    let skill_dir = ctx
+        .git
+        .skill_path(skill_id)
+        .ok_or_else(|| MsError::ValidationFailed(format!("invalid skill id: {skill_id}")))?;
+    let prefix = format!("archive/skills/by-id/{skill_id}/");
+    let files: Vec<_> = manifest.files_under(&prefix).collect();
+
+    let scratch =
+        tempfile::tempdir().map_err(|err| MsError::Config(format!("create temp dir: {err}")))?;
+    let backup_db = materialize_database(store, manifest, key, scratch.path())?;
+    let rows = restore_skill_rows(ctx.db.conn(), &backup_db, skill_id)?;
+
+    if !files.is_empty() && skill_dir.exists() {
+        std::fs::remove_dir_all(&skill_dir)
+            .map_err(|err| MsError::Config(format!("remove {}: {err}", skill_dir.display())))?;
+    }
+    for file in &files {
+        let rel = &file.path[prefix.len()..];
+        let dest = skill_dir.join(rel);
+        if !normalize_path(&dest).starts_with(normalize_path(&skill_dir)) {
+            return Err(MsError::ValidationFailed(format!(
+                "restore destination outside skill directory: {}",
+                dest.display()
+            )));
+        }
+        store.restore_file(file, manifest.encrypted, key, &dest)?;
+    }
+
+    if ctx.output_format != OutputFormat::Human {
+        return crate::cli::output::emit_json(&serde_json::json!({
+            "status": "ok",
+            "restored": manifest.id,
+            "skill": skill_id,
+            "rows": rows,
+            "files": files.len(),
+        }));
+    }
+
+    println!("Restored skill {skill_id} from backup {}", manifest.id);
+    println!("Database rows: {rows}");
+    println!("Archive files: {}", files.len());
+    println!("Run `ms index` to refresh the search index.");
+    Ok(())
+}
+
+fn restore_snapshot_table(
+    ctx: &AppContext,
+    store: &BackupStore,
+    manifest: &SnapshotManifest,
+    key: Option<&BackupKey>,
+    table: &str,
+) -> Result<()> {
+    let tables = user_tables(ctx.db.conn())?;
+    let table = resolve_table(&tables, table)?;
+
+    let scratch =
+        tempfile::tempdir().map_err(|err| MsError::Config(format!("create temp dir: {err}")))?;
+    let backup_db = materialize_database(store, manifest, key, scratch.path())?;
+    let rows = restore_table(ctx.db.conn(), &backup_db, &table)?;
+
+    if ctx.output_format != OutputFormat::Human {
+        return crate::cli::output::emit_json(&serde_json::json!({
+            "status": "ok",
+            "restored": manifest.id,
+            "table": table,
+            "rows": rows,
+        }));
+    }
+
+    println!("Restored table {table} from backup {}", manifest.id);
+    println!("Rows: {rows}");
+    Ok(())
+}
+
+/// Accept a table name as-is or without its `skill_` prefix.
+fn resolve_table(tables: &[String], name: &str) -> Result<String> {
+    let prefixed = format!("skill_{name}");
+    tables
+        .iter()
+        .find(|table| *table == name)
+        .or_else(|| tables.iter().find(|table| **table == prefixed))
+        .cloned()
+        .ok_or_else(|| MsError::NotFound(format!("table {name}")))
+}
+
+/// Write the snapshot's database (and WAL) into `dir`.
+fn materialize_database(
+    store: &BackupStore,
+    manifest: &SnapshotManifest,
+    key: Option<&BackupKey>,
+    dir: &Path,
+) -> Result<PathBuf> {
+    let mut db_path = None;
+    for name in ["ms.db", "ms.db-wal"] {
+        if let Some(file) = manifest.files.iter().find(|file| file.path == name) {
+            let dest = dir.join(name);
+            store.restore_file(file, manifest.encrypted, key, &dest)?;
+            if name == "ms.db" {
+                db_path = Some(dest);
+            }
+        }
+    }
+    db_path.ok_or_else(|| MsError::NotFound(format!("backup {} has no database", manifest.id)))
+}
+
+fn run_verify(ctx: &AppContext, args: &BackupVerifyArgs) -> Result<()> {
+    let store = BackupStore::open(backup_root(ctx))?;
+    let ids = match &args.id {
+        Some(id) => {
+            validate_backup_id(id)?;
+            vec![id.clone()]
+        }
+        None => store.snapshot_ids()?,
+    };
+    let key = unlock(&store, args.passphrase_env.as_deref())?;
+
+    let mut reports = Vec::new();
+    for id in &ids {
+        let report = match store.load(id)? {
+            Some(manifest) => store.verify(&manifest, key.as_ref()),
+            None => verify_full_copy(&store.root().join(id))?,
+        };
+        reports.push(report);
+    }
+    let ok = reports.iter().all(VerifyReport::is_ok);
+
+    if ctx.output_format != OutputFormat::Human {
+        crate::cli::output::emit_json(&serde_json::json!({
+            "status": if ok { "ok" } else { "error" },
+            "verified": reports.len(),
+            "reports": reports,
+        }))?;
+    } else {
+        if reports.is_empty() {
+            println!("No backups found.");
+        }
+        for report in &reports {
+            let state = if report.is_ok() { "ok" } else { "FAILED" };
+            let mode = if report.decrypted { ", decrypted" } else { "" };
+            println!(
+                "  {}  {state}  {} files, {} chunks{mode}",
+                report.id, report.files, report.chunks
+            );
+            for hash in &report.missing {
+                println!("    missing: {hash}");
+            }
+            for item in &report.corrupt {
+                println!("    corrupt: {item}");
+            }
+        }
+    }
+
+    if ok {
+        Ok(())
+    } else {
+        Err(MsError::ValidationFailed(
+            "backup verification failed".to_string(),
+        ))
+    }
+}
+
+/// Check that every entry of a full-copy backup is present with its
+/// recorded size.
+fn verify_full_copy(backup_dir: &Path) -> Result<VerifyReport> {
+    let manifest_path = backup_dir.join("manifest.json");
+    let id = backup_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut report = VerifyReport {
+        id: id.clone(),
+        ..Default::default()
+    };
+    let Ok(content) = std::fs::read_to_string(&manifest_path) else {
+        report.missing.push("manifest.json".to_string());
+        return Ok(report);
+    };
+    let manifest: BackupManifest = serde_json::from_str(&content)?;
+    report.files = manifest.entries.len();
+    for entry in &manifest.entries {
+        let Some((source, _, is_dir)) =
+            restore_paths_for_entry(Path::new(""), Path::new(""), backup_dir, &entry.name)
+        else {
+            continue;
+        };
+        if !source.exists() {
+            report.missing.push(entry.name.clone());
+            continue;
+        }
+        let bytes = if is_dir {
+            dir_size(&source)?
+        } else {
+            std::fs::metadata(&source)?.len()
+        };
+        if bytes != entry.bytes {
+            report.corrupt.push(entry.name.clone());
+        }
+    }
+    Ok(report)
+}
+
+fn dir_size(path: &Path) -> Result<u64> {
+    let mut total = 0u64;
+    for entry in std::fs::read_dir(path)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        total += if metadata.is_dir() {
+            dir_size(&entry.path())?
+        } else {
+            metadata.len()
+        };
+    }
+    Ok(total)
+}
+
+fn run_prune(ctx: &AppContext, args: &BackupPruneArgs) -> Result<()> {
+    let policy = RetentionPolicy {
+        keep_last: args.keep_last,
+        keep_daily: args.keep_daily,
+        keep_weekly: args.keep_weekly,
+        keep_monthly: args.keep_monthly,
+    };
+    if policy.is_empty() {
+        return Err(MsError::ValidationFailed(
+            "prune requires at least one of --keep-last, --keep-daily, --keep-weekly, --keep-monthly"
+                .to_string(),
+        ));
+    }
+    let _lock = GlobalLock::acquire(&ctx.ms_root)?;
+    let store = BackupStore::open(backup_root(ctx))?;
+    let report = store.prune(&policy, args.dry_run)?;
+
+    if ctx.output_format != OutputFormat::Human {
+        return crate::cli::output::emit_json(&serde_json::json!({
+            "status": "ok",
+            "dry_run": args.dry_run,
+            "policy": policy,
+            "kept": report.kept,
+            "removed": report.removed,
+            "blobs_removed": report.blobs_removed,
+            "bytes_freed": report.bytes_freed,
+        }));
+    }
+
+    let verb = if args.dry_run {
+        "Would remove"
+    } else {
+        "Removed"
+    };
+    println!("Kept: {}", report.kept.len());
+    println!("{verb}: {}", report.removed.len());
+    for id in &report.removed {
+        println!("  {id}");
+    }
+    if !args.dry_run {
+        println!(
+            "Chunks removed: {} ({} bytes freed)",
+            report.blobs_removed, report.bytes_freed
+        );
+    }
+    Ok(())
+}
+
+/// Unlock the store key when a passphrase variable is given.
+fn unlock(store: &BackupStore, passphrase_env: Option<&str>) -> Result<Option<BackupKey>> {
+    let Some(var) = passphrase_env else {
+        return Ok(None);
+    };
+    let passphrase = std::env::var(var)
+        .map_err(|_| MsError::Config(format!("passphrase env var {var} is not set")))?;
+    store.unlock(&passphrase).map(Some)
+}
+
+/// Where a file of a content-addressed snapshot is restored to.
+fn snapshot_restore_dest(ms_root: &Path, config_path: &Path, path: &str) -> Option<PathBuf> {
+    match path {
+        "ms.db" | "ms.db-wal" | "ms.db-shm" => Some(ms_root.join(path)),
+        "config.toml" => Some(config_path.to_path_buf()),
+        _ => {
+            let (root, rest) = path.split_once('/')?;
+            matches!(root, "archive" | "index").then(|| ms_root.join(root).join(rest))
+        }
+    }
+}
+
 fn backup_root(ctx: &AppContext) -> PathBuf {
     ctx.ms_root.join("backups")
 }
@@ -363,6 +768,11 @@ fn validate_backup_id(id: &str) -> Result<()> {
             "backup id contains invalid path characters".to_string(),
         ));
     }
+    if id == BLOB_DIR {
+        return Err(MsError::ValidationFailed(format!(
+            "backup id {BLOB_DIR} is reserved"
+        )));
+    }
     Ok(())
 }
 
@@ -471,7 +881,7 @@ fn latest_backup_id(root: &Path) -> Result<String> {
     let mut dirs = std::fs::read_dir(root)
         .map_err(|err| MsError::Config(format!("read {}: {err}", root.display())))?
         .filter_map(std::result::Result::ok)
-        .filter(|entry| entry.path().is_dir())
+        .filter(|entry| entry.path().is_dir() && entry.file_name() != BLOB_DIR)
         .collect::<Vec<_>>();
     dirs.sort_by_key(std::fs::DirEntry::file_name);
     let latest = dirs
@@ -480,28 +890,6 @@ fn latest_backup_id(root: &Path) -> Result<String> {
     Ok(latest.file_name().to_string_lossy().to_string())
 }
 
-fn push_file_entry(
-    entries: &mut Vec<BackupEntry>,
-    total_bytes: &mut u64,
-    name: &str,
-    src: &Path,
-    dst: &Path,
-) -> Result<()> {
-    if !src.exists() {
-        return Ok(());
-    }
-    let bytes = copy_file(src, dst)?;
-    *total_bytes += bytes;
-    entries.push(BackupEntry {
-        name: name.to_string(),
-        source_path: src.display().to_string(),
-        backup_path: dst.display().to_string(),
-        bytes,
-        is_dir: false,
-    });
-    Ok(())
-}
-
 fn copy_file(src: &Path, dst: &Path) -> Result<u64> {
     if let Some(parent) = dst.parent() {
         std::fs::create_dir_all(parent)
@@ -547,6 +935,42 @@ mod tests {
         assert!(validate_backup_id("\0").is_err());
     }
 
+    #[test]
+    fn validate_backup_id_rejects_blob_dir() {
+        assert!(validate_backup_id(BLOB_DIR).is_err());
+    }
+
+    #[test]
+    fn snapshot_restore_dest_maps_known_paths() {
+        let ms_root = Path::new("/tmp/ms-root");
+        let config_path = Path::new("/tmp/ms-config/config.toml");
+
+        assert_eq!(
+            snapshot_restore_dest(ms_root, config_path, "ms.db"),
+            Some(ms_root.join("ms.db"))
+        );
+        assert_eq!(
+            snapshot_restore_dest(ms_root, config_path, "archive/skills/by-id/a/SKILL.md"),
+            Some(ms_root.join("archive").join("skills/by-id/a/SKILL.md"))
+        );
+        assert_eq!(
+            snapshot_restore_dest(ms_root, config_path, "config.toml"),
+            Some(config_path.to_path_buf())
+        );
+        assert_eq!(snapshot_restore_dest(ms_root, config_path, "other/x"), None);
+    }
+
+    #[test]
+    fn resolve_table_accepts_short_names() {
+        let tables = vec!["skill_feedback".to_string(), "skills".to_string()];
+        assert_eq!(
+            resolve_table(&tables, "feedback").unwrap(),
+            "skill_feedback"
+        );
+        assert_eq!(resolve_table(&tables, "skills").unwrap(), "skills");
+        assert!(resolve_table(&tables, "missing").is_err());
+    }
+
     #[test]
     fn latest_backup_id_picks_last_sorted() {
         let dir = tempfile::tempdir().unwrap();
@@ -1,11 +1,12 @@
 //! ms browse - Interactive skill browser TUI
 //!
 //! Provides a full-featured terminal interface for browsing, searching,
-//! and previewing skills interactively.
+//! and previewing skills interactively, with an in-place skill editor.
 
 use clap::Args;
 
 use crate::app::AppContext;
+use crate::cli::commands::edit::ArchiveEditorStore;
 use crate::cli::output::OutputFormat;
 use crate::error::{MsError, Result};
 use crate::tui::run_browse_tui;
@@ -63,7 +64,7 @@ pub fn run(ctx: &AppContext, args: &BrowseArgs) -> Result<()> {
     }
 
     // Run the TUI and capture any loaded skill
-    let result = run_browse_tui(&ctx.db)?;
+    let result = run_browse_tui(&ctx.db, &ArchiveEditorStore { ctx })?;
 
     // If a skill was selected, output its content
     if let Some(skill_id) = result {
@@ -31,6 +31,7 @@ use crate::cli::output::OutputFormat;
 use crate::cm::CmClient;
 use crate::core::recovery::Checkpoint;
 use crate::error::{MsError, Result};
+use crate::security::PolicyAction;
 use crate::tui::build_tui::run_build_tui;
 
 // =============================================================================
@@ -735,6 +736,16 @@ pub fn run(ctx: &AppContext, args: &BuildArgs) -> Result<()> {
         ));
     }
 
+    // Organisation policy may forbid the safety-bypassing flags outright
+    for (flag, set) in [
+        ("--no-redact", args.no_redact),
+        ("--no-injection-filter", args.no_injection_filter),
+    ] {
+        if set {
+            ctx.policy.enforce(&PolicyAction::BuildFlag { flag })?;
+        }
+    }
+
     // Warn about risky flags
     if (args.no_redact || args.no_injection_filter)
         && !args.auto
@@ -14,10 +14,13 @@ use crate::bundler::local_safety::{
     ModificationStatus, SkillModificationReport, backup_file, detect_modifications, hash_bytes,
 };
 use crate::bundler::registry::{BundleRegistry, InstallSource, InstalledBundle, ParsedSource};
-use crate::bundler::{Bundle, BundleInfo, BundleManifest, BundlePackage, BundledSkill};
+use crate::bundler::{
+    AdvisoryDatabase, Bundle, BundleInfo, BundleManifest, BundlePackage, BundledSkill, blob_entries,
+};
 use crate::cli::output::OutputFormat;
 use crate::cli::output::emit_json;
 use crate::error::{MsError, Result};
+use crate::security::PolicyAction;
 use crate::storage::GlobalLock;
 
 #[derive(Args, Debug)]
@@ -200,6 +203,10 @@ pub struct BundleUpdateArgs {
     /// Skip signature verification (checksum still enforced)
     #[arg(long)]
     pub no_verify: bool,
+
+    /// Update even when the new version has a known advisory
+    #[arg(long)]
+    pub allow_vulnerable: bool,
 }
 
 #[derive(Args, Debug)]
@@ -464,6 +471,12 @@ fn run_install(ctx: &AppContext, args: &BundleInstallArgs) -> Result<()> {
     let bundle_version = package.manifest.bundle.version.clone();
     let checksum = package.manifest.checksum.clone();
 
+    ctx.policy.enforce(&PolicyAction::InstallBundle {
+        bundle_id: &bundle_id,
+        signed: !package.manifest.signatures.is_empty(),
+        skip_verify: args.no_verify,
+    })?;
+
     // Check if already installed
     let mut registry = BundleRegistry::open(ctx.git.root())?;
     if registry.is_installed(&bundle_id) {
@@ -540,6 +553,7 @@ fn run_install(ctx: &AppContext, args: &BundleInstallArgs) -> Result<()> {
         installed_at: chrono::Utc::now(),
         skills: report.installed.clone(),
         checksum,
+        blobs: InstalledBundle::blobs_from_manifest(&package.manifest),
     };
     registry.register(installed)?;
 
@@ -643,6 +657,9 @@ struct BundleUpdateItem {
     conflicts: Vec<BundleConflictSummary>,
     skipped_reason: Option<String>,
     error: Option<String>,
+    /// Advisories that apply to the available version
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    advisories: Vec<String>,
 }
 
 #[derive(Serialize)]
@@ -696,10 +713,18 @@ fn run_update(ctx: &AppContext, args: &BundleUpdateArgs) -> Result<()> {
 
     let mut updates = Vec::new();
     let default_check = !args.check && !args.dry_run && !args.all && args.bundle_id.is_none();
+    let advisories = AdvisoryDatabase::load(&ctx.ms_root, &ctx.config.security.advisories, false);
+    if ctx.output_format == OutputFormat::Human {
+        for error in &advisories.errors {
+            eprintln!("Warning: advisory database: {error}");
+        }
+    }
 
     for installed in targets {
         let item = match fetch_update_candidate(ctx, args, &installed) {
-            Ok(candidate) => build_update_item(ctx, args, &installed, candidate, default_check)?,
+            Ok(candidate) => {
+                build_update_item(ctx, args, &installed, candidate, &advisories, default_check)?
+            }
             Err(err) => BundleUpdateItem {
                 bundle_id: installed.id.clone(),
                 current_version: installed.version.clone(),
@@ -710,6 +735,7 @@ fn run_update(ctx: &AppContext, args: &BundleUpdateArgs) -> Result<()> {
                 conflicts: Vec::new(),
                 skipped_reason: None,
                 error: Some(err.to_string()),
+                advisories: Vec::new(),
             },
         };
 
@@ -772,6 +798,11 @@ fn fetch_update_candidate(
 
 fn verify_bundle(ctx: &AppContext, args: &BundleUpdateArgs, package: &BundlePackage) -> Result<()> {
     package.verify()?;
+    ctx.policy.enforce(&PolicyAction::InstallBundle {
+        bundle_id: &package.manifest.bundle.id,
+        signed: !package.manifest.signatures.is_empty(),
+        skip_verify: args.no_verify,
+    })?;
 
     if args.no_verify {
         return Ok(());
@@ -799,10 +830,23 @@ fn build_update_item(
     args: &BundleUpdateArgs,
     installed: &InstalledBundle,
     candidate: UpdateCandidate,
+    advisories: &AdvisoryDatabase,
     default_check: bool,
 ) -> Result<BundleUpdateItem> {
     let new_version = candidate.package.manifest.bundle.version.clone();
     let update_available = is_newer_version(&installed.version, &new_version)?;
+    let manifest = &candidate.package.manifest;
+    let skill_names: Vec<String> = manifest.skills.iter().map(|s| s.name.clone()).collect();
+    let known_bad: Vec<String> = advisories
+        .matches_for(
+            &installed.id,
+            &new_version,
+            &InstalledBundle::blobs_from_manifest(manifest),
+            &skill_names,
+        )
+        .into_iter()
+        .map(|found| found.advisory.id)
+        .collect();
 
     let mut item = BundleUpdateItem {
         bundle_id: installed.id.clone(),
@@ -814,6 +858,7 @@ fn build_update_item(
         conflicts: Vec::new(),
         skipped_reason: None,
         error: None,
+        advisories: known_bad,
     };
 
     let check_only = args.check || args.dry_run || default_check;
@@ -827,6 +872,14 @@ fn build_update_item(
         return Ok(item);
     }
 
+    if !item.advisories.is_empty()
+        && ctx.config.security.advisories.block_vulnerable_updates
+        && !args.allow_vulnerable
+    {
+        item.skipped_reason = Some("known_vulnerable".to_string());
+        return Ok(item);
+    }
+
     let apply_result = apply_bundle_update(ctx, args, installed, &candidate)?;
     item.applied = apply_result.applied;
     item.conflicts = apply_result.conflicts;
@@ -925,6 +978,7 @@ fn apply_bundle_update(
             .map(|s| s.name.clone())
             .collect(),
         checksum: candidate.package.manifest.checksum.clone(),
+        blobs: InstalledBundle::blobs_from_manifest(&candidate.package.manifest),
     };
     BundleRegistry::open(ctx.git.root())?.register(installed)?;
 
@@ -959,7 +1013,7 @@ fn bundle_skill_entries(
             MsError::ValidationFailed(format!("bundle missing blob {} for {}", hash, skill.name))
         })?;
 
-    let entries = decode_blob_entries(&blob.bytes)?;
+    let entries = blob_entries(&blob.bytes)?;
     let mut expected_hashes = HashMap::new();
     for (path, bytes) in &entries {
         expected_hashes.insert(path.clone(), hash_bytes(bytes));
@@ -967,27 +1021,6 @@ fn bundle_skill_entries(
     Ok((entries, expected_hashes))
 }
 
-fn decode_blob_entries(input: &[u8]) -> Result<Vec<(PathBuf, Vec<u8>)>> {
-    let mut cursor = 0usize;
-    let mut entries = Vec::new();
-    while cursor < input.len() {
-        let name_len = read_u64(input, &mut cursor)? as usize;
-        if name_len == 0 {
-            return Err(MsError::ValidationFailed(
-                "bundle entry has empty path".to_string(),
-            ));
-        }
-        let name_bytes = read_slice(input, &mut cursor, name_len)?;
-        let name = std::str::from_utf8(name_bytes).map_err(|_| {
-            MsError::ValidationFailed("bundle entry path is invalid UTF-8".to_string())
-        })?;
-        let file_len = read_u64(input, &mut cursor)? as usize;
-        let file_bytes = read_slice(input, &mut cursor, file_len)?.to_vec();
-        entries.push((PathBuf::from(name), file_bytes));
-    }
-    Ok(entries)
-}
-
 fn write_bundle_files(target: &Path, entries: &[(PathBuf, Vec<u8>)]) -> Result<()> {
     for (rel, bytes) in entries {
         ensure_relative(rel)?;
@@ -1068,29 +1101,6 @@ fn ensure_relative(path: &Path) -> Result<()> {
     Ok(())
 }
 
-fn read_u64(input: &[u8], cursor: &mut usize) -> Result<u64> {
-    if *cursor + 8 > input.len() {
-        return Err(MsError::ValidationFailed(
-            "bundle data truncated".to_string(),
-        ));
-    }
-    let mut buf = [0u8; 8];
-    buf.copy_from_slice(&input[*cursor..*cursor + 8]);
-    *cursor += 8;
-    Ok(u64::from_be_bytes(buf))
-}
-
-fn read_slice<'a>(input: &'a [u8], cursor: &mut usize, len: usize) -> Result<&'a [u8]> {
-    if *cursor + len > input.len() {
-        return Err(MsError::ValidationFailed(
-            "bundle data truncated".to_string(),
-        ));
-    }
-    let slice = &input[*cursor..*cursor + len];
-    *cursor += len;
-    Ok(slice)
-}
-
 fn backup_root(ctx: &AppContext, bundle_id: &str) -> PathBuf {
     let timestamp = chrono::Utc::now().format("%Y%m%d%H%M%S").to_string();
     ctx.git
@@ -1117,6 +1127,9 @@ fn print_update_summary(updates: &[BundleUpdateItem]) {
         if let Some(ref available) = update.available_version {
             println!("  available: {available}");
         }
+        if !update.advisories.is_empty() {
+            println!("  advisories: {}", update.advisories.join(", "));
+        }
         if !update.conflicts.is_empty() {
             println!("  conflicts:");
             for conflict in &update.conflicts {
@@ -2091,6 +2104,7 @@ mod tests {
             assert!(!update.dry_run);
             assert!(!update.force);
             assert!(!update.no_verify);
+            assert!(!update.allow_vulnerable);
         } else {
             panic!("Expected Update command");
         }
@@ -2117,6 +2131,7 @@ mod tests {
             "--token",
             "ghp_123",
             "--no-verify",
+            "--allow-vulnerable",
         ]);
         if let BundleCommand::Update(update) = args.cmd {
             assert_eq!(update.bundle_id.as_deref(), Some("bundle-1"));
@@ -2126,6 +2141,7 @@ mod tests {
             assert!(update.force);
             assert_eq!(update.token.as_deref(), Some("ghp_123"));
             assert!(update.no_verify);
+            assert!(update.allow_vulnerable);
         } else {
             panic!("Expected Update command");
         }
@@ -104,7 +104,8 @@ fn resolve(ctx: &AppContext, args: &ConflictsResolveArgs) -> Result<()> {
             ctx.db.clone(),
             ctx.ms_root.clone(),
             ctx.config.ru.clone(),
-        );
+        )
+        .with_policy(ctx.policy.clone());
         let options = SyncOptions {
             force: true,
             ..Default::default()
@@ -12,7 +12,7 @@ use crate::error::Result;
 use crate::output::{
     OutputModeReport, is_agent_environment, is_ci_environment, is_ide_environment,
 };
-use crate::security::{SafetyGate, scan_secrets_summary};
+use crate::security::{DecisionSource, SafetyGate, ScanContext, SecretScanSummary, SecretScanner};
 use crate::storage::tx::GlobalLock;
 
 #[derive(Args, Debug)]
@@ -266,26 +266,39 @@ fn check_git_archive(ctx: &AppContext, verbose: bool) -> Result<usize> {
     }
 }
 
-/// Check command safety (DCG) availability
+/// Check command safety evaluator availability
 fn check_safety(ctx: &AppContext, verbose: bool) -> Result<usize> {
     print!("Checking command safety... ");
 
     let gate = SafetyGate::from_context(ctx);
     let status = gate.status();
 
-    if let Some(version) = status.dcg_version {
-        println!("{} dcg {}", "✓".green(), version);
-        if verbose {
-            println!("  dcg_bin: {}", status.dcg_bin.display());
-            if !status.packs.is_empty() {
-                println!("  packs: {}", status.packs.join(", "));
-            }
+    let mut issues = 0;
+    match (status.active, &status.dcg_version) {
+        (DecisionSource::Dcg, Some(version)) => println!("{} dcg {}", "✓".green(), version),
+        (DecisionSource::Dcg, None) => {
+            println!("{} dcg required but not available", "✗".red());
+            issues += 1;
         }
-        Ok(0)
-    } else {
-        println!("{} dcg not available", "!".yellow());
-        Ok(1)
+        (DecisionSource::Native, _) => println!(
+            "{} native evaluator ({} packs)",
+            "✓".green(),
+            status.native_packs.len()
+        ),
+    }
+    if let Some(err) = &status.rules_error {
+        println!("  {} custom rules not loaded: {err}", "!".yellow());
+        issues += 1;
     }
+    if verbose {
+        println!("  evaluator: {}", status.evaluator.as_str());
+        println!("  dcg_bin: {}", status.dcg_bin.display());
+        if !status.packs.is_empty() {
+            println!("  packs: {}", status.packs.join(", "));
+        }
+        println!("  native packs: {}", status.native_packs.join(", "));
+    }
+    Ok(issues)
 }
 
 /// Comprehensive security check
@@ -295,15 +308,23 @@ fn check_security(ctx: &AppContext, verbose: bool) -> Result<usize> {
 
     let mut issues = 0;
 
-    // 1. Check DCG availability
-    print!("  [1/5] Command safety (DCG)... ");
+    // 1. Check command safety evaluator
+    print!("  [1/5] Command safety... ");
     let gate = SafetyGate::from_context(ctx);
     let status = gate.status();
-    if let Some(version) = status.dcg_version {
-        println!("{} v{}", "✓".green(), version);
-    } else {
-        println!("{} not available", "!".yellow());
-        println!("        Commands will run without safety checks");
+    match (status.active, status.dcg_version) {
+        (DecisionSource::Dcg, Some(version)) => println!("{} dcg v{}", "✓".green(), version),
+        (DecisionSource::Dcg, None) => {
+            println!("{} dcg not available", "✗".red());
+            println!("        Commands will be blocked until DCG is installed");
+            issues += 1;
+        }
+        (DecisionSource::Native, _) => {
+            println!("{} native evaluator", "✓".green());
+        }
+    }
+    if let Some(err) = status.rules_error {
+        println!("        {} custom rules not loaded: {err}", "!".yellow());
         issues += 1;
     }
 
@@ -350,51 +371,66 @@ fn check_security(ctx: &AppContext, verbose: bool) -> Result<usize> {
     // 4. Scan evidence for secrets
     print!("  [4/5] Evidence secret scan... ");
     let evidence_dir = ctx.ms_root.join("archive").join("skills");
-    if evidence_dir.exists() {
-        let mut secrets_found = 0;
-        let mut files_scanned = 0;
-
-        // Scan a sample of evidence files
-        if let Ok(entries) = std::fs::read_dir(&evidence_dir) {
-            for entry in entries.take(50).flatten() {
-                let path = entry.path();
-                if path.is_file() && path.extension().is_some_and(|e| e == "json" || e == "md") {
-                    if let Ok(content) = std::fs::read_to_string(&path) {
-                        files_scanned += 1;
-                        let summary = scan_secrets_summary(&content);
-                        if summary.total_count > 0 {
-                            secrets_found += summary.total_count;
-                            if verbose {
-                                println!();
-                                println!(
-                                    "        {} potential secret(s) in {}",
-                                    summary.total_count,
-                                    path.display()
-                                );
+    match SecretScanner::from_config(&ctx.config.security.secrets) {
+        Err(e) => {
+            println!("{} invalid [security.secrets] config: {}", "✗".red(), e);
+            issues += 1;
+        }
+        Ok(scanner) if evidence_dir.exists() => {
+            let mut secrets_found = 0;
+            let mut files_scanned = 0;
+
+            // Scan a sample of evidence files
+            if let Ok(entries) = std::fs::read_dir(&evidence_dir) {
+                for entry in entries.take(50).flatten() {
+                    let path = entry.path();
+                    if path.is_file() && path.extension().is_some_and(|e| e == "json" || e == "md")
+                    {
+                        if let Ok(content) = std::fs::read_to_string(&path) {
+                            files_scanned += 1;
+                            let relative = path
+                                .strip_prefix(ctx.ms_root.join("archive"))
+                                .unwrap_or(&path)
+                                .to_string_lossy()
+                                .replace('\\', "/");
+                            let summary = SecretScanSummary::from_matches(&scanner.scan_path(
+                                &relative,
+                                &content,
+                                ScanContext::Evidence,
+                            ));
+                            if summary.total_count > 0 {
+                                secrets_found += summary.total_count;
+                                if verbose {
+                                    println!();
+                                    println!(
+                                        "        {} potential secret(s) in {}",
+                                        summary.total_count,
+                                        path.display()
+                                    );
+                                }
                             }
                         }
                     }
                 }
             }
-        }
 
-        if secrets_found > 0 {
-            println!(
-                "{} {} potential secret(s) found",
-                "!".yellow(),
-                secrets_found
-            );
-            println!("        Review evidence files for sensitive data");
-            issues += 1;
-        } else {
-            println!(
-                "{} {} files scanned, no secrets detected",
-                "✓".green(),
-                files_scanned
-            );
+            if secrets_found > 0 {
+                println!(
+                    "{} {} potential secret(s) found",
+                    "!".yellow(),
+                    secrets_found
+                );
+                println!("        Review evidence files for sensitive data");
+                issues += 1;
+            } else {
+                println!(
+                    "{} {} files scanned, no secrets detected",
+                    "✓".green(),
+                    files_scanned
+                );
+            }
         }
-    } else {
-        println!("{} no evidence directory", "-".dimmed());
+        Ok(_) => println!("{} no evidence directory", "-".dimmed()),
     }
 
     // 5. Check for .env files that shouldn't be tracked
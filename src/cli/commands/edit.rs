@@ -4,13 +4,19 @@ use clap::Args;
 
 use std::path::PathBuf;
 use std::process::Command;
+use std::time::Duration;
 
 use crate::app::AppContext;
 use crate::cli::commands::resolve_skill_markdown;
-use crate::core::SkillMetadata;
+use crate::cli::output::OutputFormat;
 use crate::core::spec_lens::{compile_markdown, parse_markdown};
-use crate::error::Result;
-use crate::security::SafetyGate;
+use crate::core::{SkillLayer, SkillMetadata, SkillSpec};
+use crate::error::{MsError, Result};
+use crate::lint::rules::all_rules;
+use crate::lint::{ValidationConfig, ValidationEngine, extend_with_custom_rules};
+use crate::security::{PolicyAction, SafetyGate};
+use crate::storage::{GlobalLock, TxManager};
+use crate::tui::{EditorStore, run_skill_editor};
 
 #[derive(Args, Debug)]
 pub struct EditArgs {
@@ -24,9 +30,17 @@ pub struct EditArgs {
     /// Edit metadata only
     #[arg(long)]
     pub meta: bool,
+
+    /// Edit in the interactive section/block editor instead of $EDITOR
+    #[arg(long, conflicts_with_all = ["editor", "meta"])]
+    pub tui: bool,
 }
 
 pub fn run(ctx: &AppContext, args: &EditArgs) -> Result<()> {
+    if args.tui {
+        return run_tui(ctx, &args.skill);
+    }
+
     let skill_md = resolve_skill_markdown(ctx, &args.skill)?;
     let skill_dir = skill_md
         .parent()
@@ -83,6 +97,92 @@ pub fn run(ctx: &AppContext, args: &EditArgs) -> Result<()> {
     Ok(())
 }
 
+fn run_tui(ctx: &AppContext, skill: &str) -> Result<()> {
+    if ctx.output_format != OutputFormat::Human {
+        return Err(MsError::ValidationFailed(
+            "edit --tui requires interactive terminal (cannot use --robot or --output-format)"
+                .to_string(),
+        ));
+    }
+    let record = ctx
+        .db
+        .get_skill(skill)?
+        .ok_or_else(|| MsError::SkillNotFound(skill.to_string()))?;
+    let store = ArchiveEditorStore { ctx };
+    if let Some(saved) = run_skill_editor(store.load(&record.id)?, &store)? {
+        println!("Committed {}", saved.metadata.id);
+    }
+    Ok(())
+}
+
+/// Loads skills from the archive for the TUI editor and commits edits
+/// through `TxManager`, keeping the source SKILL.md in step like `ms edit`.
+pub(crate) struct ArchiveEditorStore<'a> {
+    pub(crate) ctx: &'a AppContext,
+}
+
+impl EditorStore for ArchiveEditorStore<'_> {
+    fn load(&self, skill_id: &str) -> Result<SkillSpec> {
+        self.ctx.git.read_skill(skill_id)
+    }
+
+    fn validator(&self) -> Result<ValidationEngine> {
+        let mut engine = ValidationEngine::new(ValidationConfig::new());
+        let mut rules = all_rules();
+        extend_with_custom_rules(&mut rules, &self.ctx.ms_root)?;
+        for rule in rules {
+            engine.register(rule);
+        }
+        Ok(engine)
+    }
+
+    fn commit(&self, spec: &SkillSpec) -> Result<()> {
+        let ctx = self.ctx;
+        let skill_id = &spec.metadata.id;
+        let layer = ctx
+            .db
+            .get_skill(skill_id)?
+            .and_then(|record| layer_from_str(&record.source_layer))
+            .unwrap_or(SkillLayer::Project);
+        let corpus = ctx.policy.lint_corpus(&ctx.db, &ctx.git)?;
+        ctx.policy.enforce(&PolicyAction::IndexSkill {
+            spec,
+            layer,
+            corpus: &corpus,
+        })?;
+        let before = ctx.git.read_skill(skill_id).ok();
+
+        {
+            let _lock = GlobalLock::acquire_timeout(&ctx.ms_root, Duration::from_secs(30))?
+                .ok_or_else(|| {
+                    MsError::TransactionFailed("timeout waiting for global lock".to_string())
+                })?;
+            let tx_mgr = TxManager::new(ctx.db.clone(), ctx.git.clone(), ctx.ms_root.clone())?;
+            tx_mgr.write_skill_with_layer(spec, layer)?;
+        }
+
+        // Without this the next `ms index` would re-import the old source.
+        if let Ok(skill_md) = resolve_skill_markdown(ctx, skill_id) {
+            std::fs::write(&skill_md, compile_markdown(spec))
+                .map_err(|err| MsError::Config(format!("write {}: {err}", skill_md.display())))?;
+            if let (Some(skill_dir), Some(before)) = (skill_md.parent(), before) {
+                record_field_history(skill_dir, &before, spec)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn layer_from_str(raw: &str) -> Option<SkillLayer> {
+    match raw {
+        "base" => Some(SkillLayer::Base),
+        "org" => Some(SkillLayer::Org),
+        "project" => Some(SkillLayer::Project),
+        "user" => Some(SkillLayer::User),
+        _ => None,
+    }
+}
+
 fn edit_spec_path(skill_dir: &std::path::Path) -> PathBuf {
     skill_dir.join(".ms").join("spec_edit.yaml")
 }
@@ -18,6 +18,10 @@ use crate::cli::output::OutputFormat;
 use crate::cli::output::{HumanLayout, emit_json};
 use crate::error::{MsError, Result};
 use crate::storage::sqlite::ExperimentEventRecord;
+use crate::suggestions::experiment_stats::{
+    AnalysisConfig, Arm, Comparison, Direction, MetricKind, compare_to_control,
+    required_sample_size,
+};
 
 #[derive(Args, Debug)]
 pub struct ExperimentArgs {
@@ -71,6 +75,28 @@ pub struct ExperimentCreateArgs {
     /// Status for the experiment
     #[arg(long, default_value = "running")]
     pub status: String,
+
+    /// Control success rate, to plan the sample size of a pass/fail metric
+    #[arg(long, requires = "mde", conflicts_with = "baseline_sd")]
+    pub baseline_rate: Option<f64>,
+
+    /// Control standard deviation, to plan the sample size of a continuous
+    /// metric (latency, tokens)
+    #[arg(long, requires = "mde")]
+    pub baseline_sd: Option<f64>,
+
+    /// Minimum detectable effect: smallest absolute difference from control
+    /// worth detecting
+    #[arg(long)]
+    pub mde: Option<f64>,
+
+    /// Family-wise significance level used for planning
+    #[arg(long, default_value = "0.05")]
+    pub alpha: f64,
+
+    /// Desired power used for planning
+    #[arg(long, default_value = "0.8")]
+    pub power: f64,
 }
 
 #[derive(Args, Debug)]
@@ -96,6 +122,23 @@ pub struct ExperimentStatusArgs {
     /// Metric key to analyze (default: `task_success`)
     #[arg(long)]
     pub metric: Option<String>,
+
+    /// Metric type: auto, binary, or continuous
+    #[arg(long, default_value = "auto")]
+    pub metric_type: String,
+
+    /// Better direction of the metric: auto, higher, or lower
+    /// (auto picks lower for latency, duration, tokens, cost, errors)
+    #[arg(long, default_value = "auto")]
+    pub direction: String,
+
+    /// Control variant ID (default: first variant)
+    #[arg(long)]
+    pub control: Option<String>,
+
+    /// Family-wise significance level
+    #[arg(long)]
+    pub alpha: Option<f64>,
 }
 
 #[derive(Args, Debug)]
@@ -246,8 +289,15 @@ fn run_create(ctx: &AppContext, args: &ExperimentCreateArgs) -> Result<()> {
 
     let skill_id = resolve_skill_id(ctx, &args.skill)?;
 
-    let (variants_json, allocation_json) =
+    let (variants_json, mut allocation_json) =
         build_variants_payload(&args.variant, &strategy, &args.weight)?;
+    let plan = build_plan(args)?;
+    if let Some(plan) = &plan {
+        let mut allocation: serde_json::Value = serde_json::from_str(&allocation_json)?;
+        allocation["plan"] = serde_json::to_value(plan)
+            .map_err(|err| MsError::Serialization(format!("plan serialize: {err}")))?;
+        allocation_json = allocation.to_string();
+    }
 
     let record = ctx.db.create_skill_experiment(
         &skill_id,
@@ -262,6 +312,7 @@ fn run_create(ctx: &AppContext, args: &ExperimentCreateArgs) -> Result<()> {
         let payload = serde_json::json!({
             "status": "ok",
             "experiment": record,
+            "plan": plan,
         });
         return emit_json(&payload);
     }
@@ -276,10 +327,70 @@ fn run_create(ctx: &AppContext, args: &ExperimentCreateArgs) -> Result<()> {
         .kv("Status", &record.status)
         .kv("Started", &record.started_at)
         .kv("Variants", &record.variants_json);
+    if let Some(plan) = &plan {
+        layout.kv(
+            "Planned sample",
+            &format!(
+                "{} outcomes per variant ({} total; {} metric, MDE {}, alpha {}, power {})",
+                plan.per_variant,
+                plan.total,
+                metric_kind_label(plan.metric_type),
+                plan.mde,
+                plan.alpha,
+                plan.power
+            ),
+        );
+    }
     crate::cli::output::emit_human(layout);
     Ok(())
 }
 
+/// Sample-size plan from `--baseline-rate`/`--baseline-sd` and `--mde`.
+fn build_plan(args: &ExperimentCreateArgs) -> Result<Option<ExperimentPlan>> {
+    let (metric_type, baseline) = match (args.baseline_rate, args.baseline_sd) {
+        (Some(rate), None) => (MetricKind::Binary, rate),
+        (None, Some(sd)) => (MetricKind::Continuous, sd),
+        (None, None) => {
+            if args.mde.is_some() {
+                return Err(MsError::ValidationFailed(
+                    "--mde requires --baseline-rate or --baseline-sd".to_string(),
+                ));
+            }
+            return Ok(None);
+        }
+        (Some(_), Some(_)) => {
+            return Err(MsError::ValidationFailed(
+                "use either --baseline-rate or --baseline-sd".to_string(),
+            ));
+        }
+    };
+    let mde = args.mde.unwrap_or_default();
+    let variants = args.variant.len().max(2);
+    let per_variant = required_sample_size(
+        metric_type,
+        baseline,
+        mde,
+        args.alpha,
+        args.power,
+        variants - 1,
+    )
+    .ok_or_else(|| {
+        MsError::ValidationFailed(
+            "invalid plan: need 0 < alpha, power < 1, --mde > 0, a rate in [0, 1] or a positive sd"
+                .to_string(),
+        )
+    })?;
+    Ok(Some(ExperimentPlan {
+        metric_type,
+        baseline,
+        mde,
+        alpha: args.alpha,
+        power: args.power,
+        per_variant,
+        total: per_variant * variants as u64,
+    }))
+}
+
 fn run_list(ctx: &AppContext, args: &ExperimentListArgs) -> Result<()> {
     let skill_id = match &args.skill {
         Some(skill) => Some(resolve_skill_id(ctx, skill)?),
@@ -338,8 +449,32 @@ fn run_status(ctx: &AppContext, args: &ExperimentStatusArgs) -> Result<()> {
     let events = ctx.db.list_skill_experiment_events(&record.id)?;
     let metric = resolve_metric_key(args.metric.as_deref(), &events)
         .unwrap_or_else(|| "task_success".to_string());
-    let stats = compute_variant_stats(&variants, &events, &metric);
-    let analysis = compute_experiment_analysis(&stats);
+    let plan = parse_allocation_json(&record.allocation_json)
+        .ok()
+        .and_then(|allocation| allocation.plan);
+    let config = analysis_config(args, &metric, &events, plan.as_ref())?;
+    let arms = collect_arms(&variants, &events, &metric, config.kind);
+    let mut stats = compute_variant_stats(&variants, &events, &metric);
+    if config.kind == MetricKind::Continuous {
+        for (stat, arm) in stats.iter_mut().zip(&arms) {
+            let summary = arm.summary();
+            stat.outcomes = summary.n;
+            stat.mean = (summary.n > 0).then_some(summary.mean);
+            stat.std_dev = (summary.n > 1).then(|| summary.std_dev());
+        }
+    }
+    let control = match &args.control {
+        Some(control) => {
+            if !variants.iter().any(|variant| &variant.id == control) {
+                return Err(MsError::ValidationFailed(format!(
+                    "unknown control variant: {control}"
+                )));
+            }
+            control.clone()
+        }
+        None => variants.first().map(|v| v.id.clone()).unwrap_or_default(),
+    };
+    let analysis = compute_experiment_analysis(&arms, &control, &config, plan.as_ref());
 
     if ctx.output_format != OutputFormat::Human {
         let payload = serde_json::json!({
@@ -365,38 +500,71 @@ fn run_status(ctx: &AppContext, args: &ExperimentStatusArgs) -> Result<()> {
         .blank();
 
     for stat in &stats {
-        let rate = if stat.outcomes > 0 {
-            format!("{:.2}%", stat.success_rate * 100.0)
-        } else {
-            "-".to_string()
-        };
         layout
             .section(&stat.id)
             .kv("Name", stat.name.as_deref().unwrap_or("-"))
             .kv("Assignments", &stat.assignments.to_string())
-            .kv("Outcomes", &stat.outcomes.to_string())
-            .kv("Successes", &stat.successes.to_string())
-            .kv("Success rate", &rate)
-            .blank();
+            .kv("Outcomes", &stat.outcomes.to_string());
+        if config.kind == MetricKind::Continuous {
+            layout
+                .kv("Mean", &format_optional(stat.mean, 2))
+                .kv("Std dev", &format_optional(stat.std_dev, 2));
+        } else {
+            let rate = if stat.outcomes > 0 {
+                format!("{:.2}%", stat.success_rate * 100.0)
+            } else {
+                "-".to_string()
+            };
+            layout
+                .kv("Successes", &stat.successes.to_string())
+                .kv("Success rate", &rate);
+        }
+        layout.blank();
     }
 
-    if let Some(analysis) = analysis {
-        layout
-            .section("Analysis")
-            .kv(
-                "Significance",
-                &analysis
-                    .significance
-                    .map_or_else(|| "-".to_string(), |s| format!("{s:.2}")),
-            )
-            .kv(
-                "p-value",
-                &analysis
-                    .p_value
-                    .map_or_else(|| "-".to_string(), |p| format!("{p:.4}")),
-            )
-            .kv("Recommendation", &analysis.recommendation);
+    layout
+        .section("Analysis")
+        .kv(
+            "Metric type",
+            &format!(
+                "{} ({} is better)",
+                metric_kind_label(analysis.metric_type),
+                match analysis.direction {
+                    Direction::Higher => "higher",
+                    Direction::Lower => "lower",
+                }
+            ),
+        )
+        .kv("Control", analysis.control.as_deref().unwrap_or("-"));
+    for comparison in &analysis.comparisons {
+        layout.kv(
+            &format!("{} vs {}", comparison.variant, comparison.control),
+            &describe_comparison(comparison, analysis.metric_type),
+        );
+    }
+    if let Some(progress) = &analysis.plan {
+        layout.kv(
+            "Plan",
+            &format!(
+                "{}/{} outcomes per variant",
+                progress.min_outcomes, progress.per_variant
+            ),
+        );
     }
+    layout
+        .kv(
+            "Significance",
+            &analysis
+                .significance
+                .map_or_else(|| "-".to_string(), |s| format!("{s:.2}")),
+        )
+        .kv(
+            "p-value",
+            &analysis
+                .p_value
+                .map_or_else(|| "-".to_string(), |p| format!("{p:.4}")),
+        )
+        .kv("Recommendation", &analysis.recommendation);
 
     crate::cli::output::emit_human(layout);
     Ok(())
@@ -467,6 +635,9 @@ fn run_load(ctx: &AppContext, args: &ExperimentLoadArgs) -> Result<()> {
         deps: args.deps,
         experiment_id: Some(record.id.clone()),
         variant_id: Some(selection.variant.id.clone()),
+        reference: None,
+        offset: 0,
+        lines: None,
     };
 
     let load_result = load_skill(ctx, &load_args, &record.skill_id)?;
@@ -679,6 +850,20 @@ struct AllocationConfig {
     strategy: String,
     #[serde(default)]
     weights: HashMap<String, f64>,
+    #[serde(default)]
+    plan: Option<ExperimentPlan>,
+}
+
+/// Sample-size plan stored with the allocation at creation time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ExperimentPlan {
+    metric_type: MetricKind,
+    baseline: f64,
+    mde: f64,
+    alpha: f64,
+    power: f64,
+    per_variant: u64,
+    total: u64,
 }
 
 #[derive(Debug, Serialize)]
@@ -689,14 +874,36 @@ struct VariantStats {
     outcomes: u64,
     successes: u64,
     success_rate: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mean: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    std_dev: Option<f64>,
 }
 
 #[derive(Debug, Serialize)]
 struct ExperimentAnalysis {
+    /// Holm-adjusted p-value of the strongest comparison
     p_value: Option<f64>,
     significance: Option<f64>,
     confidence_interval: Option<[f64; 2]>,
     recommendation: String,
+    metric_type: MetricKind,
+    direction: Direction,
+    control: Option<String>,
+    alpha: f64,
+    comparisons: Vec<Comparison>,
+    /// The data already supports a decision (always-valid test or planned
+    /// sample size reached)
+    can_stop: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    plan: Option<PlanProgress>,
+}
+
+#[derive(Debug, Serialize)]
+struct PlanProgress {
+    per_variant: u64,
+    min_outcomes: u64,
+    complete: bool,
 }
 
 struct AssignmentSelection {
@@ -946,6 +1153,8 @@ fn compute_variant_stats(
             outcomes,
             successes,
             success_rate,
+            mean: None,
+            std_dev: None,
         });
     }
     stats
@@ -965,98 +1174,282 @@ fn metric_success(metrics: &serde_json::Value, key: &str) -> Option<bool> {
     }
 }
 
-fn compute_experiment_analysis(stats: &[VariantStats]) -> Option<ExperimentAnalysis> {
-    let mut candidates: Vec<&VariantStats> = stats.iter().filter(|s| s.outcomes > 0).collect();
-    if candidates.len() < 2 {
-        return Some(ExperimentAnalysis {
-            p_value: None,
-            significance: None,
-            confidence_interval: None,
-            recommendation: "Not enough outcome data yet.".to_string(),
-        });
+fn metric_number(metrics: &serde_json::Value, key: &str) -> Option<f64> {
+    match metrics.get(key)? {
+        serde_json::Value::Number(num) => num.as_f64(),
+        serde_json::Value::Bool(value) => Some(if *value { 1.0 } else { 0.0 }),
+        serde_json::Value::String(value) => value.trim().parse().ok(),
+        _ => None,
     }
+}
 
-    candidates.sort_by(|a, b| {
-        b.success_rate
-            .partial_cmp(&a.success_rate)
-            .unwrap_or(std::cmp::Ordering::Equal)
-            .then_with(|| b.outcomes.cmp(&a.outcomes))
-    });
+fn outcome_metrics(
+    events: &[ExperimentEventRecord],
+) -> impl Iterator<Item = (usize, &ExperimentEventRecord, serde_json::Value)> {
+    events
+        .iter()
+        .enumerate()
+        .filter(|(_, event)| event.event_type == "outcome")
+        .filter_map(|(seq, event)| {
+            let metrics = serde_json::from_str(event.metrics_json.as_deref()?).ok()?;
+            Some((seq, event, metrics))
+        })
+}
 
-    let best = candidates[0];
-    let runner_up = candidates[1];
-    let (p_value, ci) = two_proportion_test(
-        best.successes,
-        best.outcomes,
-        runner_up.successes,
-        runner_up.outcomes,
-    );
-
-    let (p_value, confidence_interval) = match (p_value, ci) {
-        (Some(p), Some(ci)) => (Some(p), Some(ci)),
-        _ => (None, None),
+fn analysis_config(
+    args: &ExperimentStatusArgs,
+    metric: &str,
+    events: &[ExperimentEventRecord],
+    plan: Option<&ExperimentPlan>,
+) -> Result<AnalysisConfig> {
+    let kind = match args.metric_type.to_lowercase().as_str() {
+        "auto" => plan.map_or_else(
+            || {
+                let values: Vec<serde_json::Value> = outcome_metrics(events)
+                    .filter_map(|(_, _, metrics)| metrics.get(metric).cloned())
+                    .collect();
+                MetricKind::infer(&values)
+            },
+            |plan| plan.metric_type,
+        ),
+        "binary" => MetricKind::Binary,
+        "continuous" => MetricKind::Continuous,
+        _ => {
+            return Err(MsError::ValidationFailed(
+                "metric type must be one of: auto, binary, continuous".to_string(),
+            ));
+        }
     };
+    let direction = match args.direction.to_lowercase().as_str() {
+        "auto" => Direction::infer(metric),
+        "higher" => Direction::Higher,
+        "lower" => Direction::Lower,
+        _ => {
+            return Err(MsError::ValidationFailed(
+                "direction must be one of: auto, higher, lower".to_string(),
+            ));
+        }
+    };
+    let alpha = args.alpha.or(plan.map(|plan| plan.alpha)).unwrap_or(0.05);
+    if !(alpha > 0.0 && alpha < 1.0) {
+        return Err(MsError::ValidationFailed(
+            "alpha must be between 0 and 1".to_string(),
+        ));
+    }
+    Ok(AnalysisConfig {
+        kind,
+        direction,
+        alpha,
+        ..AnalysisConfig::default()
+    })
+}
 
-    let significance = p_value.map(|p| (1.0 - p).clamp(0.0, 1.0));
-    let recommendation = if let Some(p) = p_value {
-        if p <= 0.05 {
-            format!("{} appears better than {}.", best.id, runner_up.id)
-        } else {
-            "No significant difference yet. Keep running the experiment.".to_string()
+/// Outcome values per variant, in recording order.
+fn collect_arms(
+    variants: &[ExperimentVariant],
+    events: &[ExperimentEventRecord],
+    metric: &str,
+    kind: MetricKind,
+) -> Vec<Arm> {
+    let mut arms: Vec<Arm> = variants.iter().map(|v| Arm::new(v.id.clone())).collect();
+    for (seq, event, metrics) in outcome_metrics(events) {
+        let value = match kind {
+            MetricKind::Binary => {
+                metric_success(&metrics, metric).map(|success| if success { 1.0 } else { 0.0 })
+            }
+            MetricKind::Continuous => metric_number(&metrics, metric),
+        };
+        if let (Some(value), Some(arm)) = (
+            value,
+            arms.iter_mut().find(|arm| arm.id == event.variant_id),
+        ) {
+            arm.observations.push((seq, value));
         }
+    }
+    arms
+}
+
+fn compute_experiment_analysis(
+    arms: &[Arm],
+    control: &str,
+    config: &AnalysisConfig,
+    plan: Option<&ExperimentPlan>,
+) -> ExperimentAnalysis {
+    let mut analysis = ExperimentAnalysis {
+        p_value: None,
+        significance: None,
+        confidence_interval: None,
+        recommendation: "Not enough outcome data yet.".to_string(),
+        metric_type: config.kind,
+        direction: config.direction,
+        control: None,
+        alpha: config.alpha,
+        comparisons: Vec::new(),
+        can_stop: false,
+        plan: None,
+    };
+    let Some(control_arm) = arms.iter().find(|arm| arm.id == control) else {
+        return analysis;
+    };
+    analysis.control = Some(control.to_string());
+    let others: Vec<Arm> = arms
+        .iter()
+        .filter(|arm| arm.id != control)
+        .cloned()
+        .collect();
+    analysis.comparisons = compare_to_control(control_arm, &others, config);
+
+    let min_outcomes = arms
+        .iter()
+        .map(|arm| arm.observations.len() as u64)
+        .min()
+        .unwrap_or(0);
+    analysis.plan = plan.map(|plan| PlanProgress {
+        per_variant: plan.per_variant,
+        min_outcomes,
+        complete: min_outcomes >= plan.per_variant,
+    });
+
+    if let Some(best) = analysis
+        .comparisons
+        .iter()
+        .filter(|c| c.adjusted_p_value.is_some())
+        .min_by(|a, b| {
+            a.adjusted_p_value
+                .unwrap_or(1.0)
+                .total_cmp(&b.adjusted_p_value.unwrap_or(1.0))
+        })
+    {
+        analysis.p_value = best.adjusted_p_value;
+        analysis.significance = best.adjusted_p_value.map(|p| (1.0 - p).clamp(0.0, 1.0));
+        analysis.confidence_interval = best.confidence_interval;
+    }
+
+    let comparisons = &analysis.comparisons;
+    if comparisons.is_empty()
+        || comparisons
+            .iter()
+            .all(|c| c.p_value.is_none() && c.sequential_p_value.is_none())
+    {
+        return analysis;
+    }
+
+    let best_by = |filter: &dyn Fn(&Comparison) -> bool| {
+        comparisons.iter().filter(|c| filter(c)).max_by(|a, b| {
+            a.prob_beat_control
+                .unwrap_or(0.0)
+                .total_cmp(&b.prob_beat_control.unwrap_or(0.0))
+        })
+    };
+    let plan_complete = analysis.plan.as_ref().map(|progress| progress.complete);
+
+    let (recommendation, can_stop) = if let Some(winner) =
+        best_by(&|c| c.sequential_significant && c.improves)
+    {
+        (
+            format!(
+                "Stop early: {} beats {} (always-valid p = {:.4}).",
+                winner.variant,
+                control,
+                winner.sequential_p_value.unwrap_or(1.0)
+            ),
+            true,
+        )
+    } else if comparisons
+        .iter()
+        .all(|c| c.sequential_significant && !c.improves)
+    {
+        (
+            format!("Stop early: no variant beats {control}; keep the control."),
+            true,
+        )
+    } else if plan_complete == Some(true) {
+        best_by(&|c| c.significant && c.improves).map_or_else(
+            || {
+                (
+                    format!("Planned sample size reached and no variant beats {control}; keep the control."),
+                    true,
+                )
+            },
+            |winner| {
+                (
+                    format!(
+                        "{} beats {} at the planned sample size (Holm-adjusted p = {:.4}).",
+                        winner.variant,
+                        control,
+                        winner.adjusted_p_value.unwrap_or(1.0)
+                    ),
+                    true,
+                )
+            },
+        )
+    } else if let Some(progress) = &analysis.plan {
+        (
+            format!(
+                "Keep running: {}/{} outcomes per variant.",
+                progress.min_outcomes, progress.per_variant
+            ),
+            false,
+        )
+    } else if let Some(winner) = best_by(&|c| c.significant && c.improves) {
+        (
+            format!(
+                "{} appears better than {} (Holm-adjusted p = {:.4}).",
+                winner.variant,
+                control,
+                winner.adjusted_p_value.unwrap_or(1.0)
+            ),
+            false,
+        )
     } else {
-        "Not enough outcome data yet.".to_string()
+        (
+            "No significant difference yet. Keep running the experiment.".to_string(),
+            false,
+        )
     };
+    analysis.recommendation = recommendation;
+    analysis.can_stop = can_stop;
+    analysis
+}
 
-    Some(ExperimentAnalysis {
-        p_value,
-        significance,
-        confidence_interval,
-        recommendation,
-    })
+fn describe_comparison(comparison: &Comparison, kind: MetricKind) -> String {
+    let diff = match kind {
+        MetricKind::Binary => format!("{:+.1} pts", comparison.diff * 100.0),
+        MetricKind::Continuous => format!("{:+.2}", comparison.diff),
+    };
+    let mut parts = vec![diff];
+    if let Some(lift) = comparison.relative_lift {
+        parts.push(format!("lift {:+.1}%", lift * 100.0));
+    }
+    let interval = comparison
+        .bootstrap_interval
+        .or(comparison.confidence_interval);
+    if let Some([lo, hi]) = interval {
+        parts.push(format!("CI [{lo:.3}, {hi:.3}]"));
+    }
+    parts.push(format!(
+        "p_adj {}",
+        format_optional(comparison.adjusted_p_value, 4)
+    ));
+    parts.push(format!(
+        "always-valid p {}",
+        format_optional(comparison.sequential_p_value, 4)
+    ));
+    if let Some(prob) = comparison.prob_beat_control {
+        parts.push(format!("P(beat) {:.1}%", prob * 100.0));
+    }
+    parts.join(" · ")
 }
 
-fn two_proportion_test(
-    succ_a: u64,
-    total_a: u64,
-    succ_b: u64,
-    total_b: u64,
-) -> (Option<f64>, Option<[f64; 2]>) {
-    if total_a == 0 || total_b == 0 {
-        return (None, None);
-    }
-    let a = succ_a as f64;
-    let b = succ_b as f64;
-    let n1 = total_a as f64;
-    let n2 = total_b as f64;
-    let p1 = a / n1;
-    let p2 = b / n2;
-    let pooled = (a + b) / (n1 + n2);
-    let se = (pooled * (1.0 - pooled) * (1.0 / n1 + 1.0 / n2)).sqrt();
-    if se == 0.0 {
-        return (None, None);
-    }
-    let z = (p1 - p2) / se;
-    let p_value = 2.0 * (1.0 - normal_cdf(z.abs()));
-
-    let se_diff = (p1 * (1.0 - p1) / n1 + p2 * (1.0 - p2) / n2).sqrt();
-    let diff = p1 - p2;
-    let ci = [
-        1.96f64.mul_add(-se_diff, diff),
-        1.96f64.mul_add(se_diff, diff),
-    ];
-    (Some(p_value), Some(ci))
+fn format_optional(value: Option<f64>, precision: usize) -> String {
+    value.map_or_else(|| "-".to_string(), |v| format!("{v:.precision$}"))
 }
 
-fn normal_cdf(z: f64) -> f64 {
-    // Abramowitz-Stegun approximation for standard normal CDF.
-    // Constants use standard mathematical notation with separators.
-    let t = 1.0 / 0.231_641_9f64.mul_add(z, 1.0);
-    let d = 0.398_942_3 * (-0.5 * z * z).exp();
-    let prob = d
-        * t
-        * (0.319_381_5 + t * (-0.356_563_8 + t * (1.781_478 + t * (-1.821_256 + t * 1.330_274))));
-    1.0 - prob
+const fn metric_kind_label(kind: MetricKind) -> &'static str {
+    match kind {
+        MetricKind::Binary => "binary",
+        MetricKind::Continuous => "continuous",
+    }
 }
 
 fn select_variant(
@@ -1320,6 +1713,113 @@ mod tests {
         }
     }
 
+    fn outcome(seq: usize, variant: &str, metrics: &str) -> ExperimentEventRecord {
+        ExperimentEventRecord {
+            id: format!("e{seq}"),
+            experiment_id: "exp-1".to_string(),
+            variant_id: variant.to_string(),
+            event_type: "outcome".to_string(),
+            metrics_json: Some(metrics.to_string()),
+            context_json: None,
+            session_id: None,
+            created_at: format!("2026-01-01T00:{:02}:{:02}Z", seq / 60, seq % 60),
+        }
+    }
+
+    #[test]
+    fn parse_experiment_create_plan_flags() {
+        let parsed = TestCli::parse_from([
+            "test",
+            "experiment",
+            "create",
+            "skill-1",
+            "--variant",
+            "control",
+            "--variant",
+            "a",
+            "--variant",
+            "b",
+            "--baseline-sd",
+            "300",
+            "--mde",
+            "100",
+        ]);
+        let TestCommand::Experiment(args) = parsed.cmd;
+        let ExperimentCommand::Create(create) = args.command else {
+            panic!("expected create");
+        };
+        let plan = build_plan(&create).unwrap().unwrap();
+        assert_eq!(plan.metric_type, MetricKind::Continuous);
+        // Bonferroni over two comparisons raises n above the single-test ~142
+        assert!(plan.per_variant > 142, "{}", plan.per_variant);
+        assert_eq!(plan.total, plan.per_variant * 3);
+
+        assert!(
+            TestCli::try_parse_from([
+                "test",
+                "experiment",
+                "create",
+                "skill-1",
+                "--variant",
+                "a",
+                "--baseline-rate",
+                "0.5",
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn multi_variant_latency_analysis() {
+        let mut events = Vec::new();
+        for i in 0..30 {
+            let jitter = (i % 5) * 10;
+            events.push(outcome(
+                i * 3,
+                "control",
+                &format!(r#"{{"latency_ms": {}}}"#, 1000 + jitter),
+            ));
+            events.push(outcome(
+                i * 3 + 1,
+                "fast",
+                &format!(r#"{{"latency_ms": {}}}"#, 700 + jitter),
+            ));
+            events.push(outcome(
+                i * 3 + 2,
+                "slow",
+                &format!(r#"{{"latency_ms": {}}}"#, 1010 + jitter),
+            ));
+        }
+        let variants: Vec<ExperimentVariant> = ["control", "fast", "slow"]
+            .iter()
+            .map(|id| ExperimentVariant {
+                id: (*id).to_string(),
+                name: None,
+                weight: None,
+            })
+            .collect();
+        let status = ExperimentStatusArgs {
+            experiment_id: "exp-1".to_string(),
+            metric: None,
+            metric_type: "auto".to_string(),
+            direction: "auto".to_string(),
+            control: None,
+            alpha: None,
+        };
+        let config = analysis_config(&status, "latency_ms", &events, None).unwrap();
+        assert_eq!(config.kind, MetricKind::Continuous);
+        assert_eq!(config.direction, Direction::Lower);
+
+        let arms = collect_arms(&variants, &events, "latency_ms", config.kind);
+        assert_eq!(arms[1].observations.len(), 30);
+        let analysis = compute_experiment_analysis(&arms, "control", &config, None);
+        assert_eq!(analysis.comparisons.len(), 2);
+        assert!(analysis.can_stop);
+        assert!(analysis.recommendation.contains("fast beats control"));
+        let slow = &analysis.comparisons[1];
+        assert!(!slow.improves);
+    }
+
     #[test]
     fn build_variants_payload_validation() {
         let empty: Vec<String> = Vec::new();
@@ -4,10 +4,15 @@ use std::path::PathBuf;
 
 use clap::{Args, Subcommand};
 
+use crate::antipatterns::detection::DefaultDetector;
 use crate::app::AppContext;
+use crate::cass::CassClient;
 use crate::cli::output::OutputFormat;
 use crate::cli::output::{HumanLayout, emit_json};
 use crate::error::{MsError, Result};
+use crate::suggestions::attribution::{
+    AssessedSession, AttributionConfig, attribute_usage, bandit_reward, same_project,
+};
 use crate::suggestions::bandit::{ContextFeatures, ContextualBandit, SkillFeedback};
 
 #[derive(Args, Debug)]
@@ -22,6 +27,8 @@ pub enum FeedbackCommand {
     Add(FeedbackAddArgs),
     /// List feedback records
     List(FeedbackListArgs),
+    /// Infer outcomes of recent skill loads from later CASS sessions
+    Attribute(FeedbackAttributeArgs),
 }
 
 #[derive(Args, Debug)]
@@ -61,10 +68,34 @@ pub struct FeedbackListArgs {
     pub offset: usize,
 }
 
+#[derive(Args, Debug)]
+pub struct FeedbackAttributeArgs {
+    /// Consider skill loads from the last N days
+    #[arg(long, default_value = "30")]
+    pub days: u32,
+
+    /// Sessions starting up to this long after a load count (e.g. 4h, 90m)
+    #[arg(long, default_value = "4h")]
+    pub window: String,
+
+    /// Drop attributions below this confidence (0.0-1.0)
+    #[arg(long, default_value = "0.4")]
+    pub min_confidence: f32,
+
+    /// Maximum number of CASS sessions to scan
+    #[arg(long, default_value = "500")]
+    pub sessions: usize,
+
+    /// Show what would be attributed without writing anything
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
 pub fn run(ctx: &AppContext, args: &FeedbackArgs) -> Result<()> {
     match &args.command {
         FeedbackCommand::Add(add) => run_add(ctx, add),
         FeedbackCommand::List(list) => run_list(ctx, list),
+        FeedbackCommand::Attribute(attribute) => run_attribute(ctx, attribute),
     }
 }
 
@@ -151,7 +182,131 @@ fn run_list(ctx: &AppContext, args: &FeedbackListArgs) -> Result<()> {
             .rating
             .map_or_else(|| "-".to_string(), |r| r.to_string());
         let comment = record.comment.unwrap_or_else(|| "-".to_string());
-        layout.kv(&label, &format!("rating {rating} · {comment}"));
+        let source = record.confidence.map_or_else(String::new, |confidence| {
+            format!(" · {} ({confidence:.2})", record.source)
+        });
+        layout.kv(&label, &format!("rating {rating} · {comment}{source}"));
+    }
+    crate::cli::output::emit_human(layout);
+    Ok(())
+}
+
+fn run_attribute(ctx: &AppContext, args: &FeedbackAttributeArgs) -> Result<()> {
+    if !(0.0..=1.0).contains(&args.min_confidence) {
+        return Err(MsError::ValidationFailed(
+            "--min-confidence must be between 0.0 and 1.0".to_string(),
+        ));
+    }
+    let window =
+        chrono::Duration::from_std(crate::cli::commands::build::parse_duration(&args.window)?)
+            .map_err(|err| MsError::Config(format!("invalid window: {err}")))?;
+    let config = AttributionConfig {
+        window,
+        min_confidence: args.min_confidence,
+    };
+
+    let since = chrono::Utc::now() - chrono::Duration::days(i64::from(args.days));
+    let pending: Vec<_> = ctx
+        .db
+        .list_unattributed_usage(&since.to_rfc3339())?
+        .into_iter()
+        .filter(|usage| usage.project_path.is_some())
+        .collect();
+
+    let mut sessions = Vec::new();
+    if !pending.is_empty() {
+        let cass = if let Some(ref cass_path) = ctx.config.cass.cass_path {
+            CassClient::with_binary(cass_path)
+        } else {
+            CassClient::new()
+        };
+        if !cass.is_available() {
+            return Err(MsError::CassUnavailable(
+                "cass is required for outcome attribution".to_string(),
+            ));
+        }
+        let detector = DefaultDetector::default();
+        for found in cass.incremental_sessions(args.sessions)? {
+            // Skip sessions from projects with no pending loads before
+            // fetching their full content.
+            if let Some(project) = found.project.as_deref() {
+                let relevant = pending.iter().any(|usage| {
+                    usage
+                        .project_path
+                        .as_deref()
+                        .is_some_and(|path| same_project(path, project))
+                });
+                if !relevant {
+                    continue;
+                }
+            }
+            match cass.get_session(&found.session_id) {
+                Ok(session) => {
+                    if let Some(assessed) =
+                        AssessedSession::from_session(&session, Some(&found), &detector)
+                    {
+                        sessions.push(assessed);
+                    }
+                }
+                Err(err) => tracing::debug!("skipping session {}: {err}", found.session_id),
+            }
+        }
+    }
+
+    let mut attributed = Vec::new();
+    for usage in &pending {
+        let Some(record) = attribute_usage(usage, &sessions, &config) else {
+            continue;
+        };
+        if !args.dry_run {
+            // Loads of skills that were since removed cannot take outcomes.
+            if ctx.db.get_skill(&record.skill_id)?.is_none()
+                || !ctx.db.record_outcome_attribution(&record)?
+            {
+                continue;
+            }
+            if let Err(e) = reward_contextual_bandit(&record.skill_id, bandit_reward(&record)) {
+                eprintln!("Warning: Failed to update bandit: {e}");
+            }
+        }
+        attributed.push(record);
+    }
+
+    if ctx.output_format != OutputFormat::Human {
+        let payload = serde_json::json!({
+            "status": "ok",
+            "dry_run": args.dry_run,
+            "loads_considered": pending.len(),
+            "sessions_assessed": sessions.len(),
+            "attributed": attributed,
+        });
+        return emit_json(&payload);
+    }
+
+    let mut layout = HumanLayout::new();
+    layout
+        .title(if args.dry_run {
+            "Outcome Attribution (dry run)"
+        } else {
+            "Outcome Attribution"
+        })
+        .kv("Loads considered", &pending.len().to_string())
+        .kv("Sessions assessed", &sessions.len().to_string())
+        .kv("Attributed", &attributed.len().to_string());
+    if !attributed.is_empty() {
+        layout.blank();
+    }
+    for record in &attributed {
+        layout.kv(
+            &record.skill_id,
+            &format!(
+                "{} ({:.2}) · session {} · {}",
+                if record.success { "success" } else { "failure" },
+                record.confidence,
+                record.session_id,
+                record.signals.join(", ")
+            ),
+        );
     }
     crate::cli::output::emit_human(layout);
     Ok(())
@@ -225,6 +380,15 @@ fn update_contextual_bandit(
     Ok(())
 }
 
+/// Feed an attributed outcome to the contextual bandit.
+fn reward_contextual_bandit(skill_id: &str, reward: f32) -> Result<()> {
+    let path = default_contextual_bandit_path();
+    let mut bandit = ContextualBandit::load(&path)?;
+    bandit.update_with_reward(skill_id, &ContextFeatures::default(), reward);
+    bandit.save(&path)?;
+    Ok(())
+}
+
 /// Default path for the contextual bandit state file.
 fn default_contextual_bandit_path() -> PathBuf {
     let base = dirs::data_dir().unwrap_or_else(|| PathBuf::from("."));
@@ -287,6 +451,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_feedback_attribute_defaults() {
+        let parsed = TestCli::parse_from(["test", "feedback", "attribute", "--dry-run"]);
+        let TestCommand::Feedback(args) = parsed.cmd;
+        match args.command {
+            FeedbackCommand::Attribute(attribute) => {
+                assert_eq!(attribute.days, 30);
+                assert_eq!(attribute.window, "4h");
+                assert!((attribute.min_confidence - 0.4).abs() < f32::EPSILON);
+                assert!(attribute.dry_run);
+            }
+            _ => panic!("expected attribute"),
+        }
+    }
+
     #[test]
     fn select_feedback_type_validation() {
         let base = FeedbackAddArgs {
@@ -2,9 +2,12 @@
 //!
 //! Provides a comprehensive import wizard for converting system prompts,
 //! documentation, and other unstructured text into well-formed SkillSpec files.
+//! With `--repo` it discovers every agent rule source in a repository (Cursor
+//! rules, CLAUDE.md hierarchies, Copilot/Windsurf instructions, skill folders)
+//! and writes one linked skill per topic.
 
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use clap::{Args, ValueEnum};
 use console::style;
@@ -18,9 +21,11 @@ use crate::core::spec_lens::compile_markdown;
 use crate::error::{MsError, Result};
 use crate::import::{
     ContentBlock, ContentBlockType, ContentParser, GeneratedSkill, GeneratorConfig, ImportHints,
-    ImportStats, SkillGenerator, Suggestion, UnknownHandling, Warning,
+    ImportStats, ImportedSkill, RuleSource, SkillGenerator, SourceKind, Suggestion,
+    UnknownHandling, Warning, apply_source_metadata, discover_sources, import_source,
 };
 use crate::lint::ValidationEngine;
+use crate::lint::extend_with_custom_rules;
 use crate::lint::rules::all_rules;
 
 // =============================================================================
@@ -41,6 +46,16 @@ pub enum InputFormat {
     AgentsMd,
     /// LLM system prompt
     SystemPrompt,
+    /// Cursor rule (`.cursor/rules/*.mdc` or `.cursorrules`)
+    Cursor,
+    /// CLAUDE.md project memory
+    ClaudeMd,
+    /// GitHub Copilot instructions
+    Copilot,
+    /// Windsurf rules (`.windsurfrules` or `.windsurf/rules/*.md`)
+    Windsurf,
+    /// Skill folder with SKILL.md, scripts/ and references/
+    SkillFolder,
 }
 
 /// Output skill format
@@ -65,6 +80,14 @@ pub struct ImportArgs {
     #[arg(long)]
     pub batch: bool,
 
+    /// Import every agent rule source found in a repository
+    #[arg(long, conflicts_with = "batch")]
+    pub repo: bool,
+
+    /// Keep multi-topic files as a single skill in repo mode
+    #[arg(long)]
+    pub no_split: bool,
+
     /// Output path (file or directory)
     #[arg(long, short)]
     pub output: Option<PathBuf>,
@@ -127,7 +150,9 @@ pub struct ImportArgs {
 // =============================================================================
 
 pub fn run(ctx: &AppContext, args: &ImportArgs) -> Result<()> {
-    if args.batch {
+    if args.repo {
+        run_repo(ctx, args)
+    } else if args.batch {
         run_batch(ctx, args)
     } else {
         run_single(ctx, args)
@@ -139,16 +164,46 @@ pub fn run(ctx: &AppContext, args: &ImportArgs) -> Result<()> {
 // =============================================================================
 
 fn run_single(ctx: &AppContext, args: &ImportArgs) -> Result<()> {
+    let kind = source_kind(args);
+    if kind == SourceKind::SkillFolder {
+        let skill_md = if args.path.is_dir() {
+            args.path.join("SKILL.md")
+        } else {
+            args.path.clone()
+        };
+        let root = skill_md.parent().unwrap_or(Path::new("."));
+        let source = RuleSource::load(&skill_md, kind, root)?;
+        let output_dir = args.output.clone().unwrap_or_else(|| PathBuf::from("."));
+        return run_sources(ctx, args, &args.path, &[source], &output_dir);
+    }
+
+    // Agent rule files have frontmatter mapped to skill metadata
+    let source = if kind == SourceKind::Markdown {
+        None
+    } else {
+        let root = args.path.parent().unwrap_or(Path::new("."));
+        Some(RuleSource::load(&args.path, kind, root)?)
+    };
+
     // Read input file
-    let content = std::fs::read_to_string(&args.path)
-        .map_err(|e| MsError::Config(format!("Failed to read {}: {e}", args.path.display())))?;
+    let content = match &source {
+        Some(source) => source.body.clone(),
+        None => std::fs::read_to_string(&args.path)
+            .map_err(|e| MsError::Config(format!("Failed to read {}: {e}", args.path.display())))?,
+    };
 
     // Parse content into blocks
     let parser = ContentParser::new();
     let blocks = parser.parse(&content);
 
     // Build hints from args
-    let hints = build_hints(args);
+    let mut hints = build_hints(args);
+    if let Some(source) = &source {
+        hints.suggested_id.get_or_insert_with(|| source.id.clone());
+        if hints.suggested_name.is_none() {
+            hints.suggested_name.clone_from(&source.name);
+        }
+    }
 
     // Run classification preview (always shown in human mode)
     if ctx.output_format == OutputFormat::Human {
@@ -176,6 +231,9 @@ fn run_single(ctx: &AppContext, args: &ImportArgs) -> Result<()> {
     // Generate skill
     let generator = SkillGenerator::with_config(gen_config);
     let mut generated = generator.generate(blocks, &hints);
+    if let Some(source) = &source {
+        apply_source_metadata(source, &mut generated.skill);
+    }
 
     // Determine output path
     let output_path = args.output.clone().unwrap_or_else(|| {
@@ -196,7 +254,9 @@ fn run_single(ctx: &AppContext, args: &ImportArgs) -> Result<()> {
     // Run linting if requested
     let lint_result = if args.lint {
         let mut engine = ValidationEngine::with_defaults();
-        for rule in all_rules() {
+        let mut rules = all_rules();
+        extend_with_custom_rules(&mut rules, &ctx.ms_root)?;
+        for rule in rules {
             engine.register(rule);
         }
         let result = engine.validate(&generated.skill);
@@ -460,10 +520,224 @@ fn import_single_for_batch(
     })
 }
 
+// =============================================================================
+// REPOSITORY IMPORT
+// =============================================================================
+
+fn run_repo(ctx: &AppContext, args: &ImportArgs) -> Result<()> {
+    if !args.path.is_dir() {
+        return Err(MsError::Config(format!(
+            "Repository import requires a directory, got: {}",
+            args.path.display()
+        )));
+    }
+
+    let sources = discover_sources(&args.path)?;
+    if sources.is_empty() {
+        return Err(MsError::Config(format!(
+            "No agent rule sources found in {}",
+            args.path.display()
+        )));
+    }
+
+    let output_dir = args
+        .output
+        .clone()
+        .unwrap_or_else(|| args.path.join(".ms").join("skills"));
+    run_sources(ctx, args, &args.path, &sources, &output_dir)
+}
+
+/// Generate skills from rule sources and write each one to
+/// `<output_dir>/<id>/` together with its asset files.
+fn run_sources(
+    ctx: &AppContext,
+    args: &ImportArgs,
+    root: &Path,
+    sources: &[RuleSource],
+    output_dir: &Path,
+) -> Result<()> {
+    let generator = SkillGenerator::with_config(GeneratorConfig {
+        min_confidence: args.min_confidence,
+        unknown_handling: UnknownHandling::AddToContext,
+        infer_metadata: true,
+        deduplicate: true,
+    });
+    let hints = ImportHints {
+        suggested_id: None,
+        suggested_name: None,
+        source_filename: None,
+        domain: args.domain.clone(),
+        tags: args.tags.clone().unwrap_or_default(),
+    };
+
+    let mut reports = Vec::new();
+    for source in sources {
+        if ctx.output_format == OutputFormat::Human {
+            println!(
+                "\n{} {}",
+                style(source.kind.as_str()).dim(),
+                style(source.path.display()).bold()
+            );
+        }
+
+        for mut skill in import_source(source, &generator, &hints, !args.no_split) {
+            let lint_passed = if args.lint {
+                Some(lint_generated(ctx, &mut skill.generated, args.fix)?)
+            } else {
+                None
+            };
+            let skill_dir = output_dir.join(&skill.generated.skill.metadata.id);
+            if !args.dry_run {
+                write_skill_dir(&skill, &skill_dir, args.skill_format)?;
+            }
+
+            let spec = &skill.generated.skill;
+            if ctx.output_format == OutputFormat::Human {
+                let extends = spec
+                    .extends
+                    .as_ref()
+                    .map(|parent| format!(", extends {parent}"))
+                    .unwrap_or_default();
+                println!(
+                    "  {} {} ({} rules, {} examples{extends})",
+                    style("✓").green(),
+                    style(&spec.metadata.id).cyan(),
+                    skill.generated.stats.rules_count,
+                    skill.generated.stats.examples_count,
+                );
+            }
+
+            reports.push(RepoSkillReport {
+                id: spec.metadata.id.clone(),
+                name: spec.metadata.name.clone(),
+                source: skill.source.display().to_string(),
+                kind: skill.kind,
+                extends: spec.extends.clone(),
+                file_patterns: spec.metadata.context.file_patterns.clone(),
+                output: (!args.dry_run).then(|| skill_dir.display().to_string()),
+                assets: skill.assets.scripts.len()
+                    + skill.assets.references.len()
+                    + skill.assets.tests.len(),
+                stats: skill.generated.stats.clone(),
+                lint_passed,
+            });
+        }
+    }
+
+    if ctx.output_format != OutputFormat::Human {
+        emit_json(&RepoReport {
+            root: root.display().to_string(),
+            output: output_dir.display().to_string(),
+            dry_run: args.dry_run,
+            sources: sources.len(),
+            skills: reports,
+        })?;
+    } else {
+        let mut layout = HumanLayout::new();
+        layout.section("Repository Import Summary");
+        layout.kv("Sources", &sources.len().to_string());
+        layout.kv("Skills", &reports.len().to_string());
+        layout.kv(
+            "Linked",
+            &reports
+                .iter()
+                .filter(|r| r.extends.is_some())
+                .count()
+                .to_string(),
+        );
+        if args.dry_run {
+            layout.kv("Output", "dry run - nothing written");
+        } else {
+            layout.kv("Output", &output_dir.display().to_string());
+        }
+        emit_human(layout);
+    }
+
+    Ok(())
+}
+
+/// Write a skill and its assets into its own directory.
+fn write_skill_dir(skill: &ImportedSkill, dir: &Path, format: SkillFormat) -> Result<()> {
+    std::fs::create_dir_all(dir)
+        .map_err(|e| MsError::Config(format!("Failed to create {}: {e}", dir.display())))?;
+    let file_name = match format {
+        SkillFormat::Markdown => "SKILL.md",
+        SkillFormat::Yaml => "skill.yaml",
+        SkillFormat::Toml => "skill.toml",
+    };
+    let path = dir.join(file_name);
+    std::fs::write(&path, format_skill(&skill.generated, format))
+        .map_err(|e| MsError::Config(format!("Failed to write {}: {e}", path.display())))?;
+
+    let Some(asset_root) = &skill.asset_root else {
+        return Ok(());
+    };
+    let asset_paths = skill
+        .assets
+        .scripts
+        .iter()
+        .map(|s| &s.path)
+        .chain(skill.assets.references.iter().map(|r| &r.path))
+        .chain(skill.assets.tests.iter().map(|t| &t.path));
+    for rel in asset_paths {
+        let target = dir.join(rel);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                MsError::Config(format!("Failed to create {}: {e}", parent.display()))
+            })?;
+        }
+        std::fs::copy(asset_root.join(rel), &target)
+            .map_err(|e| MsError::Config(format!("Failed to copy {}: {e}", rel.display())))?;
+    }
+    Ok(())
+}
+
 // =============================================================================
 // HELPERS
 // =============================================================================
 
+/// Source kind implied by `--format`, or detected from the path.
+fn source_kind(args: &ImportArgs) -> SourceKind {
+    match args.format {
+        InputFormat::Auto => {
+            if args.path.is_dir() {
+                SourceKind::SkillFolder
+            } else {
+                match SourceKind::detect(&args.path) {
+                    // Plain AGENTS.md keeps the generic import path
+                    Some(SourceKind::AgentsMd) | None => SourceKind::Markdown,
+                    Some(kind) => kind,
+                }
+            }
+        }
+        InputFormat::Markdown
+        | InputFormat::Plaintext
+        | InputFormat::AgentsMd
+        | InputFormat::SystemPrompt => SourceKind::Markdown,
+        InputFormat::Cursor => SourceKind::CursorRule,
+        InputFormat::ClaudeMd => SourceKind::ClaudeMd,
+        InputFormat::Copilot => SourceKind::CopilotInstructions,
+        InputFormat::Windsurf => SourceKind::WindsurfRules,
+        InputFormat::SkillFolder => SourceKind::SkillFolder,
+    }
+}
+
+/// Lint a generated skill, auto-fixing when requested. Returns whether
+/// validation passed.
+fn lint_generated(ctx: &AppContext, generated: &mut GeneratedSkill, fix: bool) -> Result<bool> {
+    let mut engine = ValidationEngine::with_defaults();
+    let mut rules = all_rules();
+    extend_with_custom_rules(&mut rules, &ctx.ms_root)?;
+    for rule in rules {
+        engine.register(rule);
+    }
+    let result = engine.validate(&generated.skill);
+    if fix && !result.passed {
+        let _fix_result = engine.auto_fix(&mut generated.skill)?;
+    }
+    Ok(result.passed)
+}
+
 fn build_hints(args: &ImportArgs) -> ImportHints {
     ImportHints {
         suggested_id: args.id.clone(),
@@ -664,6 +938,32 @@ struct ImportedMetadata {
     tags: Vec<String>,
 }
 
+#[derive(Serialize)]
+struct RepoReport {
+    root: String,
+    output: String,
+    dry_run: bool,
+    sources: usize,
+    skills: Vec<RepoSkillReport>,
+}
+
+#[derive(Serialize)]
+struct RepoSkillReport {
+    id: String,
+    name: String,
+    source: String,
+    kind: SourceKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    extends: Option<String>,
+    file_patterns: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output: Option<String>,
+    assets: usize,
+    stats: ImportStats,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lint_passed: Option<bool>,
+}
+
 #[derive(Serialize)]
 struct BatchReport {
     total: usize,
@@ -725,11 +1025,84 @@ mod tests {
         assert!(formatted.contains("context"));
     }
 
+    fn args_for(path: &str, format: InputFormat) -> ImportArgs {
+        ImportArgs {
+            path: PathBuf::from(path),
+            batch: false,
+            repo: false,
+            no_split: false,
+            output: None,
+            format,
+            skill_format: SkillFormat::Markdown,
+            non_interactive: true,
+            lint: false,
+            fix: false,
+            min_confidence: 0.3,
+            id: None,
+            name: None,
+            domain: None,
+            tags: None,
+            pattern: "*".to_string(),
+            verbose_signals: false,
+            dry_run: false,
+        }
+    }
+
+    #[test]
+    fn test_source_kind_detection() {
+        let kind = |path, format| source_kind(&args_for(path, format));
+        assert_eq!(
+            kind(".cursor/rules/rust.mdc", InputFormat::Auto),
+            SourceKind::CursorRule
+        );
+        assert_eq!(kind("CLAUDE.md", InputFormat::Auto), SourceKind::ClaudeMd);
+        assert_eq!(kind("AGENTS.md", InputFormat::Auto), SourceKind::Markdown);
+        assert_eq!(kind("notes.md", InputFormat::Auto), SourceKind::Markdown);
+        assert_eq!(
+            kind("CLAUDE.md", InputFormat::Markdown),
+            SourceKind::Markdown
+        );
+        assert_eq!(
+            kind("rules.md", InputFormat::Windsurf),
+            SourceKind::WindsurfRules
+        );
+    }
+
+    #[test]
+    fn test_write_skill_dir_copies_assets() {
+        let temp = tempfile::tempdir().unwrap();
+        let folder = temp.path().join("pdf-tools");
+        std::fs::create_dir_all(folder.join("scripts")).unwrap();
+        std::fs::create_dir_all(folder.join("references")).unwrap();
+        std::fs::write(
+            folder.join("SKILL.md"),
+            "---\nname: pdf-tools\ndescription: Work with PDFs\n---\n\n# PDF Tools\n\n- Always validate PDFs before merging.\n",
+        )
+        .unwrap();
+        std::fs::write(folder.join("scripts/merge.py"), "print('merge')\n").unwrap();
+        std::fs::write(folder.join("references/spec.md"), "# Spec\n").unwrap();
+
+        let source =
+            RuleSource::load(&folder.join("SKILL.md"), SourceKind::SkillFolder, &folder).unwrap();
+        let generator = SkillGenerator::new();
+        let skills = import_source(&source, &generator, &ImportHints::default(), true);
+        assert_eq!(skills.len(), 1);
+
+        let out = temp.path().join("out").join("pdf-tools");
+        write_skill_dir(&skills[0], &out, SkillFormat::Markdown).unwrap();
+        let skill_md = std::fs::read_to_string(out.join("SKILL.md")).unwrap();
+        assert!(skill_md.contains("Work with PDFs"));
+        assert!(out.join("scripts/merge.py").is_file());
+        assert!(out.join("references/spec.md").is_file());
+    }
+
     #[test]
     fn test_build_hints() {
         let args = ImportArgs {
             path: PathBuf::from("test.md"),
             batch: false,
+            repo: false,
+            no_split: false,
             output: None,
             format: InputFormat::Auto,
             skill_format: SkillFormat::Markdown,
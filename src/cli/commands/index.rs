@@ -1,6 +1,6 @@
 //! ms index - Index skills from configured paths
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
@@ -11,8 +11,17 @@ use walkdir::WalkDir;
 
 use crate::app::AppContext;
 use crate::cli::output::OutputFormat;
-use crate::core::{GitSkillRepository, ResolutionCache, SkillLayer, spec_lens::parse_markdown};
+use crate::core::references::{SkillReferences, reference_slices};
+use crate::core::skill::SkillAssets;
+use crate::core::{
+    GitSkillRepository, ResolutionCache, SkillLayer, SkillSlicer, SkillSpec,
+    spec_lens::parse_markdown,
+};
 use crate::error::{MsError, Result};
+use crate::search::slices::slice_embedding_text;
+use crate::search::{Embedder, build_embedder};
+use crate::security::PolicyAction;
+use crate::storage::sqlite::SliceEmbeddingRecord;
 use crate::storage::tx::GlobalLock;
 use crate::storage::{SkillRecord, TxManager};
 use crate::sync::ru::RuClient;
@@ -223,6 +232,8 @@ fn index_human(ctx: &AppContext, roots: &[SkillRoot], args: &IndexArgs) -> Resul
     // Create resolution cache and repository for resolving inherited/composed skills
     let resolution_cache = ResolutionCache::new();
     let repository = GitSkillRepository::new(&ctx.git);
+    let embedder = slice_embedder(ctx);
+    let force = force_reindex(ctx, args);
 
     for skill in &skill_files {
         pb.set_message(format!(
@@ -235,8 +246,9 @@ fn index_human(ctx: &AppContext, roots: &[SkillRoot], args: &IndexArgs) -> Resul
             &tx_mgr,
             &resolution_cache,
             &repository,
+            embedder.as_deref(),
             skill,
-            args.force,
+            force,
         ) {
             Ok(()) => indexed += 1,
             Err(e) => {
@@ -250,8 +262,9 @@ fn index_human(ctx: &AppContext, roots: &[SkillRoot], args: &IndexArgs) -> Resul
 
     pb.finish_and_clear();
 
-    // Commit Tantivy index
+    // Commit Tantivy indexes
     ctx.search.commit()?;
+    ctx.slices.commit()?;
 
     let elapsed = start.elapsed();
 
@@ -272,6 +285,12 @@ fn index_human(ctx: &AppContext, roots: &[SkillRoot], args: &IndexArgs) -> Resul
     Ok(())
 }
 
+/// A freshly created (or schema-rebuilt) search index has to be refilled even
+/// for skills whose content hash is unchanged.
+fn force_reindex(ctx: &AppContext, args: &IndexArgs) -> bool {
+    args.force || ctx.search.is_empty()
+}
+
 fn index_robot(ctx: &AppContext, roots: &[SkillRoot], args: &IndexArgs) -> Result<()> {
     let start = Instant::now();
     let mut indexed = 0;
@@ -290,6 +309,8 @@ fn index_robot(ctx: &AppContext, roots: &[SkillRoot], args: &IndexArgs) -> Resul
     // Create resolution cache and repository for resolving inherited/composed skills
     let resolution_cache = ResolutionCache::new();
     let repository = GitSkillRepository::new(&ctx.git);
+    let embedder = slice_embedder(ctx);
+    let force = force_reindex(ctx, args);
 
     for skill in &skill_files {
         match index_skill_file(
@@ -297,8 +318,9 @@ fn index_robot(ctx: &AppContext, roots: &[SkillRoot], args: &IndexArgs) -> Resul
             &tx_mgr,
             &resolution_cache,
             &repository,
+            embedder.as_deref(),
             skill,
-            args.force,
+            force,
         ) {
             Ok(()) => indexed += 1,
             Err(e) => {
@@ -310,8 +332,9 @@ fn index_robot(ctx: &AppContext, roots: &[SkillRoot], args: &IndexArgs) -> Resul
         }
     }
 
-    // Commit Tantivy index
+    // Commit Tantivy indexes
     ctx.search.commit()?;
+    ctx.slices.commit()?;
 
     let elapsed = start.elapsed();
 
@@ -358,6 +381,7 @@ fn index_skill_file(
     tx_mgr: &TxManager,
     resolution_cache: &ResolutionCache,
     repository: &GitSkillRepository<'_>,
+    embedder: Option<&dyn Embedder>,
     skill: &DiscoveredSkill,
     force: bool,
 ) -> Result<()> {
@@ -375,6 +399,22 @@ fn index_skill_file(
         )));
     }
 
+    let corpus = ctx.policy.lint_corpus(&ctx.db, &ctx.git)?;
+    ctx.policy.enforce(&PolicyAction::IndexSkill {
+        spec: &spec,
+        layer: skill.layer,
+        corpus: &corpus,
+    })?;
+
+    // Inventory scripts/, references/ and tests/ next to SKILL.md
+    let skill_dir = skill.path.parent().unwrap_or_else(|| Path::new("."));
+    let skill_dir = skill_dir
+        .canonicalize()
+        .unwrap_or_else(|_| skill_dir.to_path_buf());
+    let assets = SkillAssets::collect(&skill_dir)?;
+    let assets_json = serde_json::to_string(&assets)
+        .map_err(|e| MsError::InvalidSkill(format!("serialize assets: {e}")))?;
+
     // Check if already indexed (unless force)
     let new_hash = compute_spec_hash(&spec)?;
     if !force {
@@ -382,6 +422,10 @@ fn index_skill_file(
             // Check content hash to skip unchanged skills
             let same_layer = existing.source_layer == skill.layer.as_str();
             if existing.content_hash == new_hash && same_layer {
+                if existing.assets_json != assets_json {
+                    ctx.db
+                        .update_skill_assets(&spec.metadata.id, &assets_json)?;
+                }
                 return Ok(()); // Skip unchanged
             }
         }
@@ -389,10 +433,19 @@ fn index_skill_file(
 
     // Write using 2PC transaction manager (stores raw spec)
     tx_mgr.write_skill_with_layer(&spec, skill.layer)?;
+    ctx.db
+        .update_skill_assets(&spec.metadata.id, &assets_json)?;
 
     // Compute and persist quality score
     let scorer = crate::quality::QualityScorer::with_defaults();
-    let quality = scorer.score_spec(&spec, &crate::quality::QualityContext::default());
+    let mut quality_context = crate::quality::QualityContext::default();
+    crate::cli::commands::quality::apply_verification(
+        ctx,
+        &spec.metadata.id,
+        &new_hash,
+        &mut quality_context,
+    );
+    let quality = scorer.score_spec(&spec, &quality_context);
     ctx.db
         .update_skill_quality(&spec.metadata.id, f64::from(quality.overall))?;
 
@@ -426,16 +479,69 @@ fn index_skill_file(
         // Build a SkillRecord from the resolved spec for search indexing
         let resolved_record = build_skill_record_from_resolved(&resolved.spec, skill, &new_hash);
         ctx.search.index_skill(&resolved_record)?;
+        index_skill_slices(ctx, &resolved.spec, &assets, embedder)?;
     } else {
         // No resolution needed - index the raw spec directly
         if let Ok(Some(skill_record)) = ctx.db.get_skill(&spec.metadata.id) {
             ctx.search.index_skill(&skill_record)?;
         }
+        index_skill_slices(ctx, &spec, &assets, embedder)?;
     }
 
     Ok(())
 }
 
+/// Embedder for slice embeddings, if semantic search is enabled.
+///
+/// An embedder that cannot be built (e.g. missing API key) only disables
+/// slice embeddings; slices are still indexed for BM25.
+fn slice_embedder(ctx: &AppContext) -> Option<Box<dyn Embedder>> {
+    if !ctx.config.search.use_embeddings {
+        return None;
+    }
+    build_embedder(&ctx.config.search).ok()
+}
+
+/// Slice a skill and its reference files, persist the slices, and add them
+/// to the slice index (plus per-slice embeddings when an embedder is
+/// available).
+fn index_skill_slices(
+    ctx: &AppContext,
+    spec: &SkillSpec,
+    assets: &SkillAssets,
+    embedder: Option<&dyn Embedder>,
+) -> Result<()> {
+    let skill_id = &spec.metadata.id;
+    let mut slice_index = SkillSlicer::slice(spec);
+    if let Some(root) = &assets.root {
+        match SkillReferences::new(assets, root).chunks() {
+            Ok(chunks) => slice_index
+                .slices
+                .extend(reference_slices(&chunks, &spec.metadata.tags)),
+            Err(err) => tracing::warn!("skipping references of {skill_id}: {err}"),
+        }
+    }
+    ctx.db.upsert_skill_slices(skill_id, &slice_index)?;
+    ctx.slices.index_slices(skill_id, &slice_index.slices)?;
+
+    ctx.db.delete_slice_embeddings(skill_id)?;
+    if let Some(embedder) = embedder {
+        let computed_at = chrono::Utc::now().to_rfc3339();
+        for slice in &slice_index.slices {
+            ctx.db.upsert_slice_embedding(&SliceEmbeddingRecord {
+                skill_id: skill_id.clone(),
+                slice_id: slice.id.clone(),
+                embedding: embedder.embed(&slice_embedding_text(slice)),
+                dims: embedder.dims(),
+                embedder_type: embedder.name().to_string(),
+                content_hash: None,
+                computed_at: computed_at.clone(),
+            })?;
+        }
+    }
+    Ok(())
+}
+
 /// Build a SkillRecord from a resolved SkillSpec for search indexing
 fn build_skill_record_from_resolved(
     spec: &crate::core::SkillSpec,
@@ -484,7 +590,7 @@ fn build_skill_record_from_resolved(
     }
 }
 
-fn compute_spec_hash(spec: &crate::core::SkillSpec) -> Result<String> {
+pub(crate) fn compute_spec_hash(spec: &crate::core::SkillSpec) -> Result<String> {
     use sha2::{Digest, Sha256};
 
     let json = serde_json::to_string(spec)
@@ -250,6 +250,11 @@ format = "json"
 include_metadata = true
 
 [safety]
+# Command safety: "auto" uses DCG when installed, otherwise the built-in rules
+evaluator = "auto"
+# Built-in rule packs to enable (empty = all) and extra rule pack files
+native_packs = []
+rule_paths = []
 # Destructive Command Guard configuration
 dcg_bin = "dcg"
 dcg_packs = []
@@ -281,6 +286,11 @@ enabled = true
 # default_flags = []
 
 [safety]
+# Command safety: "auto" uses DCG when installed, otherwise the built-in rules
+evaluator = "auto"
+# Built-in rule packs to enable (empty = all) and extra rule pack files
+native_packs = []
+rule_paths = []
 # Destructive Command Guard configuration
 dcg_bin = "dcg"
 dcg_packs = []
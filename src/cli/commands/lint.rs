@@ -4,6 +4,7 @@
 //! multiple output formats, and auto-fix capabilities.
 
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use clap::{Args, ValueEnum};
 use console::style;
@@ -16,8 +17,12 @@ use crate::cli::output::{HumanLayout, emit_human, emit_json};
 use crate::core::spec_lens::parse_markdown;
 use crate::error::{MsError, Result};
 use crate::lint::diagnostic::{RuleCategory, Severity};
-use crate::lint::rules::all_rules;
-use crate::lint::{ValidationConfig, ValidationEngine, ValidationResult};
+use crate::lint::rules::{all_rules, corpus_rules, staleness_rules};
+use crate::lint::{
+    CorpusContext, LintBaseline, ValidationConfig, ValidationContext, ValidationEngine,
+    ValidationResult, default_baseline_path, extend_with_custom_rules, known_rules, load_corpus,
+};
+use crate::search::{Embedder, build_embedder};
 
 /// Output format for lint results
 #[derive(Debug, Clone, Copy, Default, ValueEnum)]
@@ -74,6 +79,31 @@ pub struct LintArgs {
     /// List all available rules
     #[arg(long)]
     pub list_rules: bool,
+
+    /// Also run staleness rules (binaries and paths on this machine)
+    #[arg(long)]
+    pub verify: bool,
+
+    /// With --verify, also check flags by running `<tool> --help` for
+    /// allowlisted tools
+    #[arg(long, requires = "verify")]
+    pub probe_flags: bool,
+
+    /// Also run corpus rules across all indexed skills (contradictions, gaps)
+    #[arg(long)]
+    pub corpus: bool,
+
+    /// Record current findings as the accepted baseline and exit successfully
+    #[arg(long, conflicts_with = "baseline")]
+    pub write_baseline: bool,
+
+    /// Only report findings that are not in the baseline
+    #[arg(long)]
+    pub baseline: bool,
+
+    /// Baseline file (default: <ms root>/lint-baseline.json)
+    #[arg(long, value_name = "PATH")]
+    pub baseline_file: Option<PathBuf>,
 }
 
 pub fn run(ctx: &AppContext, args: &LintArgs) -> Result<()> {
@@ -92,6 +122,8 @@ pub fn run(ctx: &AppContext, args: &LintArgs) -> Result<()> {
         discover_skill_markdowns(ctx)?
     } else if let Some(path) = &args.path {
         vec![resolve_skill_markdown(ctx, path)?]
+    } else if args.corpus {
+        Vec::new()
     } else {
         // Default to current directory
         let current = std::env::current_dir()
@@ -106,7 +138,7 @@ pub fn run(ctx: &AppContext, args: &LintArgs) -> Result<()> {
         }
     };
 
-    if paths.is_empty() {
+    if paths.is_empty() && !args.corpus {
         return Err(MsError::Config("No skills found to lint".into()));
     }
 
@@ -135,7 +167,15 @@ pub fn run(ctx: &AppContext, args: &LintArgs) -> Result<()> {
         .as_ref()
         .map(|r| r.iter().map(std::string::String::as_str).collect());
 
-    for rule in all_rules() {
+    let mut rules = all_rules();
+    if args.verify {
+        let verifier =
+            crate::cli::commands::verify::build_verifier(ctx, None, args.probe_flags, false)?;
+        rules.extend(staleness_rules(Arc::new(verifier)));
+    }
+    extend_with_custom_rules(&mut rules, &ctx.ms_root)?;
+
+    for rule in rules {
         if let Some(ref filter) = rules_filter {
             if !filter.contains(rule.id()) {
                 continue;
@@ -143,6 +183,27 @@ pub fn run(ctx: &AppContext, args: &LintArgs) -> Result<()> {
         }
         engine.register(rule);
     }
+    if args.corpus {
+        let embedder: Arc<dyn Embedder> = Arc::from(build_embedder(&ctx.config.search)?);
+        for rule in corpus_rules(embedder) {
+            if let Some(ref filter) = rules_filter {
+                if !filter.contains(rule.id()) {
+                    continue;
+                }
+            }
+            engine.register_corpus(rule);
+        }
+    }
+
+    let baseline_path = args
+        .baseline_file
+        .clone()
+        .unwrap_or_else(|| default_baseline_path(&ctx.ms_root));
+    let mut baseline_filter = if args.baseline {
+        Some(LintBaseline::load(&baseline_path)?.filter())
+    } else {
+        None
+    };
 
     // Lint all paths
     let mut all_results = Vec::new();
@@ -155,7 +216,20 @@ pub fn run(ctx: &AppContext, args: &LintArgs) -> Result<()> {
             .map_err(|e| MsError::Config(format!("read {}: {e}", path.display())))?;
 
         let mut spec = parse_markdown(&raw)?;
-        let result = engine.validate(&spec);
+        let validation = ValidationContext::new(&spec, engine.config())
+            .with_source(&raw)
+            .with_file_path(path);
+        let mut result = engine.validate_with_context(&validation);
+        let skill = if spec.metadata.id.is_empty() {
+            path.display().to_string()
+        } else {
+            spec.metadata.id.clone()
+        };
+
+        if let Some(filter) = baseline_filter.as_mut() {
+            filter.retain_new(&skill, &mut result.diagnostics);
+            result.passed = !result.truncated && result.error_count() == 0;
+        }
 
         total_errors += result.error_count();
         total_warnings += result.warning_count();
@@ -177,21 +251,86 @@ pub fn run(ctx: &AppContext, args: &LintArgs) -> Result<()> {
 
         all_results.push(LintFileResult {
             path: path.clone(),
+            skill,
             result,
             fixes_applied,
         });
     }
 
+    if args.corpus {
+        let corpus = load_corpus(&ctx.db, &ctx.git)?;
+        let findings = engine.validate_corpus(&CorpusContext::new(&corpus, engine.config()));
+        for finding in findings {
+            let idx = match all_results.iter().position(|r| r.skill == finding.skill_id) {
+                Some(idx) => idx,
+                None => {
+                    let path = corpus
+                        .iter()
+                        .find(|skill| skill.id() == finding.skill_id)
+                        .and_then(|skill| skill.path.clone())
+                        .unwrap_or_else(|| PathBuf::from(&finding.skill_id));
+                    all_results.push(LintFileResult {
+                        path,
+                        skill: finding.skill_id.clone(),
+                        result: ValidationResult::new(),
+                        fixes_applied: None,
+                    });
+                    all_results.len() - 1
+                }
+            };
+            let mut diagnostics = vec![finding.diagnostic];
+            if let Some(filter) = baseline_filter.as_mut() {
+                filter.retain_new(&finding.skill_id, &mut diagnostics);
+            }
+            let result = &mut all_results[idx].result;
+            for diag in diagnostics {
+                match diag.severity {
+                    Severity::Error => {
+                        total_errors += 1;
+                        result.passed = false;
+                    }
+                    Severity::Warning => total_warnings += 1,
+                    Severity::Info => {}
+                }
+                result.diagnostics.push(diag);
+            }
+        }
+    }
+
+    let baselined = baseline_filter.as_ref().map(|filter| filter.suppressed());
+
     // Output based on format
     match args.format {
-        LintFormat::Human => {
-            output_human(ctx, &all_results, total_errors, total_warnings, total_fixed)
-        }
-        LintFormat::Json => output_json(&all_results)?,
-        LintFormat::Sarif => output_sarif(&all_results)?,
+        LintFormat::Human => output_human(
+            ctx,
+            &all_results,
+            total_errors,
+            total_warnings,
+            total_fixed,
+            baselined,
+        ),
+        LintFormat::Json => output_json(&all_results, baselined)?,
+        LintFormat::Sarif => output_sarif(&all_results, &engine.list_rules())?,
         LintFormat::Junit => output_junit(&all_results)?,
     }
 
+    if args.write_baseline {
+        let baseline = LintBaseline::from_diagnostics(
+            all_results
+                .iter()
+                .map(|r| (r.skill.as_str(), r.result.diagnostics.as_slice())),
+        );
+        baseline.save(&baseline_path)?;
+        if matches!(args.format, LintFormat::Human) {
+            println!(
+                "Wrote {} finding(s) to {}",
+                baseline.total(),
+                baseline_path.display()
+            );
+        }
+        return Ok(());
+    }
+
     // Exit with appropriate code
     if total_errors > 0 {
         Err(MsError::ValidationFailed(format!(
@@ -204,37 +343,38 @@ pub fn run(ctx: &AppContext, args: &LintArgs) -> Result<()> {
 
 struct LintFileResult {
     path: PathBuf,
+    skill: String,
     result: ValidationResult,
     fixes_applied: Option<usize>,
 }
 
 fn explain_rule(ctx: &AppContext, rule_id: &str) -> Result<()> {
-    let rules = all_rules();
-    let rule = rules
-        .iter()
-        .find(|r| r.id() == rule_id)
+    let rule = known_rules(&ctx.ms_root)?
+        .list_rules()
+        .into_iter()
+        .find(|r| r.id == rule_id)
         .ok_or_else(|| MsError::NotFound(format!("Rule '{rule_id}' not found")))?;
 
     if ctx.output_format != OutputFormat::Human {
         let info = RuleInfo {
-            id: rule.id().to_string(),
-            name: rule.name().to_string(),
-            description: rule.description().to_string(),
-            category: format!("{}", rule.category()),
-            default_severity: format!("{}", rule.default_severity()),
-            can_fix: rule.can_fix(),
+            id: rule.id.clone(),
+            name: rule.name.clone(),
+            description: rule.description.clone(),
+            category: format!("{}", rule.category),
+            default_severity: format!("{}", rule.default_severity),
+            can_fix: rule.can_fix,
         };
         emit_json(&info)?;
     } else {
         let mut layout = HumanLayout::new();
-        layout.title(&format!("Rule: {}", rule.id()));
-        layout.kv("Name", rule.name());
-        layout.kv("Category", &format!("{}", rule.category()));
-        layout.kv("Severity", &format!("{}", rule.default_severity()));
-        layout.kv("Auto-fix", if rule.can_fix() { "Yes" } else { "No" });
+        layout.title(&format!("Rule: {}", rule.id));
+        layout.kv("Name", &rule.name);
+        layout.kv("Category", &format!("{}", rule.category));
+        layout.kv("Severity", &format!("{}", rule.default_severity));
+        layout.kv("Auto-fix", if rule.can_fix { "Yes" } else { "No" });
         layout.blank();
         layout.section("Description");
-        layout.push_line(rule.description());
+        layout.push_line(&rule.description);
         emit_human(layout);
     }
 
@@ -242,12 +382,7 @@ fn explain_rule(ctx: &AppContext, rule_id: &str) -> Result<()> {
 }
 
 fn list_rules(ctx: &AppContext) -> Result<()> {
-    let mut engine = ValidationEngine::with_defaults();
-    for rule in all_rules() {
-        engine.register(rule);
-    }
-
-    let rules = engine.list_rules();
+    let rules = known_rules(&ctx.ms_root)?.list_rules();
 
     if ctx.output_format != OutputFormat::Human {
         let infos: Vec<RuleInfo> = rules
@@ -273,6 +408,8 @@ fn list_rules(ctx: &AppContext) -> Result<()> {
             (RuleCategory::Security, "Security"),
             (RuleCategory::Quality, "Quality"),
             (RuleCategory::Performance, "Performance"),
+            (RuleCategory::Staleness, "Staleness (--verify)"),
+            (RuleCategory::Corpus, "Corpus (--corpus)"),
         ];
 
         for (cat, cat_name) in categories {
@@ -304,6 +441,7 @@ fn output_human(
     total_errors: usize,
     total_warnings: usize,
     total_fixed: usize,
+    baselined: Option<usize>,
 ) {
     let mut layout = HumanLayout::new();
 
@@ -364,11 +502,14 @@ fn output_human(
     if total_fixed > 0 {
         layout.kv("Fixed", &total_fixed.to_string());
     }
+    if let Some(baselined) = baselined {
+        layout.kv("Baselined", &baselined.to_string());
+    }
 
     emit_human(layout);
 }
 
-fn output_json(results: &[LintFileResult]) -> Result<()> {
+fn output_json(results: &[LintFileResult], baselined: Option<usize>) -> Result<()> {
     let report = JsonReport {
         files: results
             .iter()
@@ -404,13 +545,14 @@ fn output_json(results: &[LintFileResult]) -> Result<()> {
             total_errors: results.iter().map(|r| r.result.error_count()).sum(),
             total_warnings: results.iter().map(|r| r.result.warning_count()).sum(),
             passed: results.iter().all(|r| r.result.passed),
+            baselined,
         },
     };
 
     emit_json(&report)
 }
 
-fn output_sarif(results: &[LintFileResult]) -> Result<()> {
+fn output_sarif(results: &[LintFileResult], rules: &[crate::lint::RuleInfo]) -> Result<()> {
     let sarif = SarifReport {
         schema: "https://json.schemastore.org/sarif-2.1.0.json".to_string(),
         version: "2.1.0".to_string(),
@@ -420,16 +562,16 @@ fn output_sarif(results: &[LintFileResult]) -> Result<()> {
                     name: "ms lint".to_string(),
                     version: env!("CARGO_PKG_VERSION").to_string(),
                     information_uri: "https://github.com/anthropics/ms".to_string(),
-                    rules: all_rules()
+                    rules: rules
                         .iter()
                         .map(|r| SarifRule {
-                            id: r.id().to_string(),
-                            name: r.name().to_string(),
+                            id: r.id.clone(),
+                            name: r.name.clone(),
                             short_description: SarifMessage {
-                                text: r.description().to_string(),
+                                text: r.description.clone(),
                             },
                             default_configuration: SarifConfiguration {
-                                level: match r.default_severity() {
+                                level: match r.default_severity {
                                     Severity::Error => "error".to_string(),
                                     Severity::Warning => "warning".to_string(),
                                     Severity::Info => "note".to_string(),
@@ -598,6 +740,8 @@ struct JsonSummary {
     total_errors: usize,
     total_warnings: usize,
     passed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    baselined: Option<usize>,
 }
 
 // SARIF output types
@@ -2,7 +2,7 @@
 
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::io::{self, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use clap::{Args, ValueEnum};
 use colored::Colorize;
@@ -12,6 +12,7 @@ use crate::app::AppContext;
 use crate::cli::output::OutputFormat;
 use crate::context::collector::{CollectedContext, ContextCollector, ContextCollectorConfig};
 use crate::context::scoring::{RankedSkill, RelevanceScorer, WorkingContext};
+use crate::context::workspace::WorkspaceLayout;
 use crate::core::dependencies::{
     DependencyGraph, DependencyLoadMode, DependencyResolver, DisclosureLevel as DepDisclosure,
 };
@@ -21,11 +22,15 @@ use crate::core::disclosure::{
 use crate::core::pack_contracts::{
     PackContractPreset, custom_contracts_path, find_custom_contract,
 };
+use crate::core::references::{ReferenceAddress, SkillReferences};
 use crate::core::resolution::{DbSkillRepository, resolve_full};
-use crate::core::skill::{PackContract, SkillAssets, SkillMetadata};
-use crate::core::spec_lens::parse_markdown;
+use crate::core::skill::{PackContract, SkillAssets, SkillMetadata, SkillSpec};
+use crate::core::spec_lens::{compile_markdown, parse_markdown};
 use crate::error::{MsError, Result};
 use crate::meta_skills::{ConditionContext, MetaSkillManager, MetaSkillRegistry};
+use crate::security::{
+    AssetHashes, TrustAction, TrustDecision, signed_content_hash, spec_content_hash,
+};
 use crate::storage::sqlite::SkillRecord;
 use crate::suggestions::bandit::{
     ContextualBandit, DefaultFeatureExtractor, FeatureExtractor, SkillFeedback, UserHistory,
@@ -167,6 +172,18 @@ pub struct LoadArgs {
     /// Variant id for experiment attribution
     #[arg(long)]
     pub variant_id: Option<String>,
+
+    /// Load a reference file or one of its sections (`api.md#authentication`)
+    #[arg(long = "ref", value_name = "PATH[#HEADING]", conflicts_with = "auto")]
+    pub reference: Option<String>,
+
+    /// First line of the reference page (with --ref)
+    #[arg(long, default_value = "0", requires = "reference")]
+    pub offset: usize,
+
+    /// Lines per reference page (with --ref; default: whole section)
+    #[arg(long, requires = "reference")]
+    pub lines: Option<usize>,
 }
 
 /// Result of loading a skill
@@ -193,6 +210,10 @@ pub fn run(ctx: &AppContext, args: &LoadArgs) -> Result<()> {
         MsError::ValidationFailed("skill argument required when not using --auto".to_string())
     })?;
 
+    if let Some(reference) = &args.reference {
+        return run_reference(ctx, args, skill_ref, reference);
+    }
+
     // First try to load as meta-skill
     if let Some(meta_result) = try_load_meta_skill(ctx, args, skill_ref)? {
         return match ctx.output_format {
@@ -230,8 +251,10 @@ pub struct AutoLoadResult {
 #[derive(Debug, Clone)]
 pub struct ContextSummary {
     pub project_types: Vec<(String, f32)>,
+    pub dependencies_count: usize,
     pub recent_files_count: usize,
     pub tools: Vec<String>,
+    pub workspace: WorkspaceLayout,
 }
 
 /// Run auto-load: detect context, score skills, load relevant ones
@@ -397,9 +420,11 @@ fn run_auto_load(ctx: &AppContext, args: &LoadArgs) -> Result<()> {
 }
 
 /// Convert `CollectedContext` to `WorkingContext` for scoring
-fn convert_to_scoring_context(collected: &CollectedContext) -> WorkingContext {
+pub(crate) fn convert_to_scoring_context(collected: &CollectedContext) -> WorkingContext {
     WorkingContext::new()
         .with_projects(collected.detected_projects.clone())
+        .with_dependencies(collected.dependencies.clone())
+        .with_workspace(&collected.workspace)
         .with_recent_files(
             collected
                 .recent_files
@@ -526,8 +551,10 @@ fn summarize_context(collected: &CollectedContext) -> ContextSummary {
             .iter()
             .map(|p| (p.project_type.id().to_string(), p.confidence))
             .collect(),
+        dependencies_count: collected.dependencies.len(),
         recent_files_count: collected.recent_files.len(),
         tools: collected.detected_tools.iter().cloned().collect(),
+        workspace: collected.workspace.clone(),
     }
 }
 
@@ -553,7 +580,7 @@ fn get_all_skill_metadata(ctx: &AppContext) -> Result<Vec<SkillMetadata>> {
 }
 
 /// Convert `SkillRecord` to `SkillMetadata`
-fn skill_record_to_metadata(skill: &SkillRecord) -> SkillMetadata {
+pub(crate) fn skill_record_to_metadata(skill: &SkillRecord) -> SkillMetadata {
     let meta_json: serde_json::Value =
         serde_json::from_str(&skill.metadata_json).unwrap_or_default();
 
@@ -600,8 +627,32 @@ fn context_summary_to_json(summary: &ContextSummary) -> serde_json::Value {
         "project_types": summary.project_types.iter().map(|(t, c)| {
             serde_json::json!({"type": t, "confidence": c})
         }).collect::<Vec<_>>(),
+        "dependencies": summary.dependencies_count,
         "recent_files": summary.recent_files_count,
-        "tools": summary.tools
+        "tools": summary.tools,
+        "workspace": workspace_to_json(&summary.workspace)
+    })
+}
+
+fn workspace_to_json(workspace: &WorkspaceLayout) -> serde_json::Value {
+    if workspace.subprojects.is_empty() {
+        return serde_json::Value::Null;
+    }
+    serde_json::json!({
+        "root": workspace.root.display().to_string(),
+        "active_subproject": workspace.active().map(|s| s.name.clone()),
+        "subprojects": workspace.subprojects.iter().map(|s| {
+            serde_json::json!({
+                "name": s.name,
+                "path": s.root.display().to_string(),
+                "source": s.source,
+                "project_types": s.project_type_ids(),
+                "dependencies": s.dependencies.len(),
+                "cwd_distance": s.cwd_distance,
+                "recent_files": s.recent_files,
+                "weight": s.weight
+            })
+        }).collect::<Vec<_>>()
     })
 }
 
@@ -624,11 +675,13 @@ fn output_dry_run(
                             "skill_id": c.skill_id,
                             "name": c.skill_name,
                             "score": c.score,
+                            "subproject": c.subproject,
                             "breakdown": {
                                 "project_type": c.breakdown.project_type,
                                 "file_patterns": c.breakdown.file_patterns,
                                 "tools": c.breakdown.tools,
-                                "signals": c.breakdown.signals
+                                "signals": c.breakdown.signals,
+                                "dependencies": c.breakdown.dependencies
                             }
                         })
                     }).collect::<Vec<_>>(),
@@ -652,23 +705,34 @@ fn output_dry_run(
             }
         }
         OutputFormat::Human => {
+            if context_summary.workspace.is_monorepo() {
+                print_workspace_summary(&context_summary.workspace, ctx.verbosity > 0);
+                println!();
+            }
             println!("{}", "Would load skills:".bold());
             println!();
 
             for (i, candidate) in candidates.iter().enumerate() {
+                let via = candidate
+                    .subproject
+                    .as_ref()
+                    .map(|s| format!(" via {s}"))
+                    .unwrap_or_default();
                 println!(
-                    "  {}. {} (score: {:.2})",
+                    "  {}. {} (score: {:.2}){}",
                     i + 1,
                     candidate.skill_id.cyan(),
-                    candidate.score
+                    candidate.score,
+                    via.dimmed()
                 );
                 if ctx.verbosity > 0 {
                     println!(
-                        "     {} project: {:.2}, files: {:.2}, tools: {:.2}",
+                        "     {} project: {:.2}, files: {:.2}, tools: {:.2}, deps: {:.2}",
                         "├".dimmed(),
                         candidate.breakdown.project_type,
                         candidate.breakdown.file_patterns,
-                        candidate.breakdown.tools
+                        candidate.breakdown.tools,
+                        candidate.breakdown.dependencies
                     );
                 }
             }
@@ -686,6 +750,36 @@ fn output_dry_run(
     Ok(())
 }
 
+/// Print the active subproject (and, when verbose, all workspace members)
+fn print_workspace_summary(workspace: &WorkspaceLayout, verbose: bool) {
+    println!(
+        "{} {}",
+        "Workspace:".bold(),
+        workspace.root.display().to_string().dimmed()
+    );
+    if let Some(active) = workspace.active() {
+        println!(
+            "  Active subproject: {} [{}] (weight: {:.2}, distance: {}, recent files: {})",
+            active.name.cyan(),
+            active.project_type_ids().join(", "),
+            active.weight,
+            active.cwd_distance,
+            active.recent_files
+        );
+    }
+    if verbose {
+        for sub in workspace.subprojects.iter().skip(1) {
+            println!(
+                "     {} {} [{}] (weight: {:.2})",
+                "├".dimmed(),
+                sub.name,
+                sub.project_type_ids().join(", "),
+                sub.weight
+            );
+        }
+    }
+}
+
 /// Output auto-load results in human-readable format
 fn output_auto_human(ctx: &AppContext, result: &AutoLoadResult, _args: &LoadArgs) -> Result<()> {
     // Context summary
@@ -699,6 +793,24 @@ fn output_auto_human(ctx: &AppContext, result: &AutoLoadResult, _args: &LoadArgs
             );
         }
     }
+    if let Some(active) = result
+        .context_summary
+        .workspace
+        .active()
+        .filter(|_| result.context_summary.workspace.is_monorepo())
+    {
+        println!(
+            "  Active subproject: {} (weight: {:.2})",
+            active.name.cyan(),
+            active.weight
+        );
+    }
+    if result.context_summary.dependencies_count > 0 {
+        println!(
+            "  Dependencies: {}",
+            result.context_summary.dependencies_count
+        );
+    }
     println!(
         "  Recent files: {}",
         result.context_summary.recent_files_count
@@ -771,7 +883,8 @@ fn output_auto_robot(_ctx: &AppContext, result: &AutoLoadResult, args: &LoadArgs
                 serde_json::json!({
                     "skill_id": c.skill_id,
                     "name": c.skill_name,
-                    "score": c.score
+                    "score": c.score,
+                    "subproject": c.subproject
                 })
             }).collect::<Vec<_>>(),
             "loaded": result.loaded.iter().map(|l| {
@@ -814,8 +927,30 @@ pub(crate) fn load_skill(ctx: &AppContext, args: &LoadArgs, skill_ref: &str) ->
         args.variant_id.as_deref(),
     )?;
 
+    // Apply the signing trust policy before disclosing anything
+    let trust = check_trust(ctx, &skill)?;
+    let mut trust_warnings = Vec::new();
+    match trust.action {
+        TrustAction::Allow => {}
+        TrustAction::Warn => trust_warnings.push(trust.reason(&skill.id)),
+        TrustAction::Minimal => trust_warnings.push(format!(
+            "{}; limited to minimal disclosure by trust policy",
+            trust.reason(&skill.id)
+        )),
+        TrustAction::Deny => {
+            return Err(MsError::ValidationFailed(format!(
+                "{}; blocked by trust policy",
+                trust.reason(&skill.id)
+            )));
+        }
+    }
+
     // Determine disclosure plan
-    let disclosure_plan = determine_disclosure_plan(args, contract);
+    let disclosure_plan = if trust.action == TrustAction::Minimal {
+        DisclosurePlan::Level(DisclosureLevel::Minimal)
+    } else {
+        determine_disclosure_plan(args, contract)
+    };
 
     // Parse skill body into SkillSpec
     let spec = parse_markdown(&skill.body)
@@ -853,10 +988,9 @@ pub(crate) fn load_skill(ctx: &AppContext, args: &LoadArgs, skill_ref: &str) ->
         slices_included,
         inheritance_chain: resolved.inheritance_chain,
         included_from: resolved.included_from,
-        warnings: resolved
-            .warnings
-            .iter()
-            .map(|w| format!("{:?}", w))
+        warnings: trust_warnings
+            .into_iter()
+            .chain(resolved.warnings.iter().map(|w| format!("{:?}", w)))
             .collect(),
     };
 
@@ -871,6 +1005,141 @@ pub(crate) fn load_skill(ctx: &AppContext, args: &LoadArgs, skill_ref: &str) ->
     Ok(result)
 }
 
+/// Load one reference file (or section) of a skill on demand.
+fn run_reference(
+    ctx: &AppContext,
+    args: &LoadArgs,
+    skill_ref: &str,
+    reference: &str,
+) -> Result<()> {
+    let skill = resolve_skill(ctx, skill_ref)?;
+    let trust = check_trust(ctx, &skill)?;
+    if matches!(trust.action, TrustAction::Minimal | TrustAction::Deny) {
+        return Err(MsError::ValidationFailed(format!(
+            "{}; references blocked by trust policy",
+            trust.reason(&skill.id)
+        )));
+    }
+    let references = SkillReferences::from_record(&skill);
+    let section = references.section(&ReferenceAddress::parse(reference))?;
+    let page = section.page(args.offset, args.lines.unwrap_or(usize::MAX));
+
+    match ctx.output_format {
+        OutputFormat::Json | OutputFormat::Jsonl => {
+            let output = serde_json::json!({
+                "status": "ok",
+                "timestamp": chrono::Utc::now().to_rfc3339(),
+                "version": env!("CARGO_PKG_VERSION"),
+                "data": {
+                    "skill_id": skill.id,
+                    "reference": page,
+                },
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        OutputFormat::Plain | OutputFormat::Tsv => println!("{}", page.content),
+        OutputFormat::Human => {
+            let title = match &page.heading {
+                Some(heading) => format!("{}#{heading}", page.path),
+                None => page.path.clone(),
+            };
+            println!("{} {}", title.bold(), format!("({})", skill.id).dimmed());
+            println!();
+            println!("{}", page.content);
+            if let Some(next) = page.next_offset {
+                println!();
+                println!(
+                    "{}",
+                    format!(
+                        "lines {}-{} of {}; continue with --offset {next}",
+                        page.offset + 1,
+                        next,
+                        page.total_lines
+                    )
+                    .dimmed()
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Check a skill's signatures against `[security.signing]` and the `[load]` policy.
+pub(crate) fn check_trust(ctx: &AppContext, skill: &SkillRecord) -> Result<TrustDecision> {
+    check_trust_with_assets(ctx, skill, &skill_asset_hashes(skill)?)
+}
+
+/// [`check_trust`] against asset hashes the caller already took, so the
+/// same snapshot can be re-verified before a script runs.
+pub(crate) fn check_trust_with_assets(
+    ctx: &AppContext,
+    skill: &SkillRecord,
+    assets: &AssetHashes,
+) -> Result<TrustDecision> {
+    let signatures = ctx.git.read_signatures(&skill.id)?;
+    let content_hash = signed_skill_hash(ctx, skill, assets)?;
+    let mut decision = ctx.config.security.signing.evaluate(
+        &skill.id,
+        &skill.source_layer,
+        &content_hash,
+        &signatures,
+    )?;
+    if !decision.status.is_trusted() {
+        if let Some((action, source)) = ctx.policy.untrusted_action(&skill.id, &skill.source_layer)
+        {
+            if action > decision.action {
+                decision.action = action;
+                decision.policy = Some(source);
+            }
+        }
+    }
+    Ok(decision)
+}
+
+/// Hashes of the skill's asset files and declared scripts, read from its
+/// source directory.
+pub(crate) fn skill_asset_hashes(skill: &SkillRecord) -> Result<AssetHashes> {
+    let scripts = serde_json::from_str::<SkillMetadata>(&skill.metadata_json)
+        .map(|metadata| metadata.scripts)
+        .unwrap_or_default();
+    AssetHashes::collect(
+        &skill.skill_dir(),
+        scripts.iter().map(|script| Path::new(&script.path)),
+    )
+}
+
+/// Hash that signatures over this skill must cover: the disclosed body
+/// together with its asset files.
+pub(crate) fn signed_skill_hash(
+    ctx: &AppContext,
+    skill: &SkillRecord,
+    assets: &AssetHashes,
+) -> Result<String> {
+    Ok(signed_content_hash(
+        &disclosed_content_hash(ctx, skill)?,
+        assets,
+    ))
+}
+
+/// Content hash of the body that will actually be disclosed.
+///
+/// The stored `content_hash` is not trusted: an edited row or archive would
+/// keep a stale, validly signed hash. The body is credited with the archived
+/// spec's hash only while it still compiles from that spec; otherwise it is
+/// hashed as parsed, which no signature over the archived spec matches.
+fn disclosed_content_hash(ctx: &AppContext, skill: &SkillRecord) -> Result<String> {
+    body_content_hash(ctx.git.read_skill(&skill.id).ok().as_ref(), &skill.body)
+}
+
+fn body_content_hash(archived: Option<&SkillSpec>, body: &str) -> Result<String> {
+    if let Some(archived) = archived.filter(|spec| compile_markdown(spec) == body) {
+        return spec_content_hash(archived);
+    }
+    let spec = parse_markdown(body)
+        .map_err(|e| MsError::ValidationFailed(format!("failed to parse skill body: {e}")))?;
+    spec_content_hash(&spec)
+}
+
 fn resolve_skill(ctx: &AppContext, skill_ref: &str) -> Result<SkillRecord> {
     // Try direct ID lookup
     if let Some(skill) = ctx.db.get_skill(skill_ref)? {
@@ -1231,6 +1500,11 @@ fn merge_metadata(skill: &SkillRecord, parsed_meta: &SkillMetadata) -> SkillMeta
         author: skill.author.clone().or_else(|| parsed_meta.author.clone()),
         license: parsed_meta.license.clone(),
         context: parsed_meta.context.clone(),
+        scripts: db_meta
+            .get("scripts")
+            .cloned()
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_else(|| parsed_meta.scripts.clone()),
     }
 }
 
@@ -1633,4 +1907,33 @@ mod tests {
         let mode = CliPackMode::default();
         assert!(matches!(mode, CliPackMode::Balanced));
     }
+
+    // ==================== Trust Hash Tests ====================
+
+    #[test]
+    fn test_body_content_hash_tracks_disclosed_body() {
+        let spec = parse_markdown(
+            "---\nid: demo\nname: Demo\ndescription: Demo skill\n---\n\n# Demo\n\nDemo skill\n\n## Rules\n\nAlways run the tests.\n",
+        )
+        .unwrap();
+        let signed = spec_content_hash(&spec).unwrap();
+        let body = compile_markdown(&spec);
+        assert_eq!(body_content_hash(Some(&spec), &body).unwrap(), signed);
+
+        // Edited row, archive untouched: the stale signed hash is not reused.
+        let tampered = body.replace("Always run the tests.", "Skip the tests.");
+        assert_ne!(body_content_hash(Some(&spec), &tampered).unwrap(), signed);
+
+        // Edited archive, row untouched: the body is hashed as disclosed.
+        let mut edited = spec.clone();
+        edited.metadata.description = "Edited".to_string();
+        assert_eq!(
+            body_content_hash(Some(&edited), &body).unwrap(),
+            spec_content_hash(&parse_markdown(&body).unwrap()).unwrap()
+        );
+        assert_ne!(
+            body_content_hash(Some(&edited), &body).unwrap(),
+            spec_content_hash(&edited).unwrap()
+        );
+    }
 }
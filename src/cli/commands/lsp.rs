@@ -0,0 +1,930 @@
+//! ms lsp - Language server for SKILL.md authoring
+//!
+//! Speaks the Language Server Protocol over stdio (`Content-Length` framed
+//! JSON-RPC) so authors get `ms lint` feedback while they type:
+//!
+//! - diagnostics from every registered `ValidationRule`, placed at their
+//!   `SourceSpan` (or at the frontmatter key or quoted name they mention)
+//! - quick fixes that apply `ValidationRule::fix`
+//! - `ms fmt` formatting, also applied on save through `willSaveWaitUntil`
+//! - completion, go-to-definition and hover for skill ids in `extends`,
+//!   `includes` and `requires`; hovering the parent in `extends` previews
+//!   the sections inherited through `resolve_full`
+
+use std::collections::{HashMap, HashSet};
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use serde_json::{Value, json};
+
+use crate::app::AppContext;
+use crate::core::resolution::{DbSkillRepository, SkillRepository, resolve_full};
+use crate::core::skill::{SkillSection, SkillSpec};
+use crate::core::spec_lens::{compile_markdown, parse_markdown};
+use crate::error::{MsError, Result};
+use crate::lint::config::ValidationContext;
+use crate::lint::extend_with_custom_rules;
+use crate::lint::rules::all_rules;
+use crate::lint::rules::security::extract_all_content;
+use crate::lint::{Diagnostic, Severity, ValidationConfig, ValidationEngine, ValidationResult};
+
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const REQUEST_FAILED: i64 = -32803;
+
+/// LSP `CompletionItemKind.Reference`
+const COMPLETION_KIND_REFERENCE: u32 = 18;
+
+/// Lines of each inherited section shown on hover
+const HOVER_PREVIEW_LINES: usize = 6;
+
+#[derive(Args, Debug)]
+pub struct LspArgs {
+    /// Use the stdio transport (the default and only transport; accepted
+    /// because most editor configs pass it)
+    #[arg(long)]
+    pub stdio: bool,
+
+    /// Log protocol traffic to stderr
+    #[arg(long)]
+    pub debug: bool,
+}
+
+pub fn run(ctx: &AppContext, args: &LspArgs) -> Result<()> {
+    let stdin = io::stdin();
+    let mut reader = stdin.lock();
+    let mut stdout = io::stdout();
+    let mut server = LspServer::new(ctx);
+
+    while let Some(message) = read_message(&mut reader)? {
+        if args.debug {
+            eprintln!("[ms-lsp] <- {message}");
+        }
+        for outgoing in server.handle(&message) {
+            if args.debug {
+                eprintln!("[ms-lsp] -> {outgoing}");
+            }
+            write_message(&mut stdout, &outgoing)?;
+        }
+        if server.exit {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// Transport
+// ============================================================================
+
+/// Largest message body accepted, so a bogus header cannot force a huge
+/// allocation.
+const MAX_MESSAGE_BYTES: usize = 64 * 1024 * 1024;
+
+/// Read one `Content-Length` framed message. Returns `None` at end of input
+/// and `Value::Null` for a body that is not JSON. A header block without a
+/// valid `Content-Length`, or one above [`MAX_MESSAGE_BYTES`], is a protocol
+/// error.
+fn read_message(reader: &mut impl BufRead) -> Result<Option<Value>> {
+    let mut length = None;
+    let mut in_header = false;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            if in_header {
+                return Err(MsError::ValidationFailed(
+                    "lsp: end of input inside message header".to_string(),
+                ));
+            }
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        in_header = true;
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                let value = value.trim();
+                length = Some(value.parse::<usize>().map_err(|_| {
+                    MsError::ValidationFailed(format!("lsp: invalid Content-Length `{value}`"))
+                })?);
+            }
+        }
+    }
+    let length = length.ok_or_else(|| {
+        MsError::ValidationFailed("lsp: message header has no Content-Length".to_string())
+    })?;
+    if length > MAX_MESSAGE_BYTES {
+        return Err(MsError::ValidationFailed(format!(
+            "lsp: Content-Length {length} exceeds {MAX_MESSAGE_BYTES} bytes"
+        )));
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    Ok(Some(serde_json::from_slice(&body).unwrap_or(Value::Null)))
+}
+
+fn write_message(writer: &mut impl Write, message: &Value) -> Result<()> {
+    let body = serde_json::to_string(message)?;
+    write!(writer, "Content-Length: {}\r\n\r\n{body}", body.len())?;
+    writer.flush()?;
+    Ok(())
+}
+
+// ============================================================================
+// Server
+// ============================================================================
+
+struct LspServer<'a> {
+    ctx: &'a AppContext,
+    engine: ValidationEngine,
+    /// Open documents by URI (full text sync)
+    documents: HashMap<String, String>,
+    exit: bool,
+}
+
+impl<'a> LspServer<'a> {
+    fn new(ctx: &'a AppContext) -> Self {
+        let mut engine = ValidationEngine::new(ValidationConfig::new());
+        let mut rules = all_rules();
+        if let Err(err) = extend_with_custom_rules(&mut rules, &ctx.ms_root) {
+            tracing::warn!("skipping project lint rules: {err}");
+        }
+        for rule in rules {
+            engine.register(rule);
+        }
+        Self {
+            ctx,
+            engine,
+            documents: HashMap::new(),
+            exit: false,
+        }
+    }
+
+    /// Handle one incoming message, returning the responses and
+    /// notifications to send back.
+    fn handle(&mut self, message: &Value) -> Vec<Value> {
+        if message.is_null() {
+            return vec![error_response(Value::Null, PARSE_ERROR, "Parse error")];
+        }
+        let Some(method) = message.get("method").and_then(Value::as_str) else {
+            // Responses to server-initiated requests; we never send any
+            return Vec::new();
+        };
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+        match message.get("id").cloned() {
+            Some(id) => vec![match self.request(method, &params) {
+                None => {
+                    error_response(id, METHOD_NOT_FOUND, &format!("Method not found: {method}"))
+                }
+                Some(Ok(result)) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+                Some(Err(err)) => error_response(id, REQUEST_FAILED, &err.to_string()),
+            }],
+            None => self.notification(method, &params),
+        }
+    }
+
+    fn request(&mut self, method: &str, params: &Value) -> Option<Result<Value>> {
+        let result = match method {
+            "initialize" => Ok(initialize_result()),
+            "shutdown" => Ok(Value::Null),
+            "textDocument/formatting" | "textDocument/willSaveWaitUntil" => self.formatting(params),
+            "textDocument/codeAction" => self.code_actions(params),
+            "textDocument/completion" => self.completion(params),
+            "textDocument/definition" => self.definition(params),
+            "textDocument/hover" => self.hover(params),
+            _ => return None,
+        };
+        Some(result)
+    }
+
+    fn notification(&mut self, method: &str, params: &Value) -> Vec<Value> {
+        let Some(uri) = document_uri(params) else {
+            if method == "exit" {
+                self.exit = true;
+            }
+            return Vec::new();
+        };
+        let uri = uri.to_string();
+
+        match method {
+            "textDocument/didOpen" => {
+                let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+                self.documents.insert(uri.clone(), text.to_string());
+            }
+            "textDocument/didChange" => {
+                let changes = params["contentChanges"].as_array();
+                if let Some(text) = changes
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change["text"].as_str())
+                {
+                    self.documents.insert(uri.clone(), text.to_string());
+                }
+            }
+            "textDocument/didSave" => {
+                if let Some(text) = params["text"].as_str() {
+                    self.documents.insert(uri.clone(), text.to_string());
+                }
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(&uri);
+                return vec![publish_diagnostics(&uri, Vec::new())];
+            }
+            _ => return Vec::new(),
+        }
+
+        let diagnostics = self
+            .documents
+            .get(&uri)
+            .map(|text| self.diagnostics(text))
+            .unwrap_or_default();
+        vec![publish_diagnostics(&uri, diagnostics)]
+    }
+
+    /// Text of an open document, or the file on disk.
+    fn text(&self, params: &Value) -> Result<(String, String)> {
+        let uri = document_uri(params)
+            .ok_or_else(|| MsError::ValidationFailed("missing textDocument.uri".to_string()))?;
+        if let Some(text) = self.documents.get(uri) {
+            return Ok((uri.to_string(), text.clone()));
+        }
+        let path = uri_to_path(uri)
+            .ok_or_else(|| MsError::NotFound(format!("document not open: {uri}")))?;
+        let text = std::fs::read_to_string(&path)
+            .map_err(|err| MsError::NotFound(format!("read {}: {err}", path.display())))?;
+        Ok((uri.to_string(), text))
+    }
+
+    fn validate(&self, spec: &SkillSpec) -> ValidationResult {
+        let repository = DbSkillRepository::new(&self.ctx.db);
+        let ctx = ValidationContext::new(spec, self.engine.config()).with_repository(&repository);
+        self.engine.validate_with_context(&ctx)
+    }
+
+    fn diagnostics(&self, text: &str) -> Vec<Value> {
+        let spec = match parse_markdown(text) {
+            Ok(spec) => spec,
+            Err(err) => {
+                let range = LineRange::whole_line(&split_lines(text), 0);
+                return vec![json!({
+                    "range": range.to_lsp(text),
+                    "severity": 1,
+                    "code": "parse",
+                    "source": "ms",
+                    "message": err.to_string(),
+                })];
+            }
+        };
+        let scanned = extract_all_content(&spec);
+        self.validate(&spec)
+            .diagnostics
+            .iter()
+            .map(|diag| lsp_diagnostic(diag, text, &scanned))
+            .collect()
+    }
+
+    fn formatting(&self, params: &Value) -> Result<Value> {
+        let (_, text) = self.text(params)?;
+        let Ok(spec) = parse_markdown(&text) else {
+            return Ok(json!([]));
+        };
+        let formatted = compile_markdown(&spec);
+        if formatted == text {
+            Ok(json!([]))
+        } else {
+            Ok(json!([full_edit(&text, &formatted)]))
+        }
+    }
+
+    fn code_actions(&self, params: &Value) -> Result<Value> {
+        let (uri, text) = self.text(params)?;
+        let Ok(spec) = parse_markdown(&text) else {
+            return Ok(json!([]));
+        };
+        let first = params["range"]["start"]["line"].as_u64().unwrap_or(0) as usize;
+        let last = params["range"]["end"]["line"].as_u64().unwrap_or(0) as usize;
+        let requested: HashSet<&str> = params["context"]["diagnostics"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|diag| diag["code"].as_str())
+            .collect();
+
+        let scanned = extract_all_content(&spec);
+        let result = self.validate(&spec);
+        let fixable: Vec<&Diagnostic> = result
+            .diagnostics
+            .iter()
+            .filter(|diag| diag.fix_available)
+            .collect();
+
+        let mut actions = Vec::new();
+        for diag in &fixable {
+            let range = locate(diag, &text, &scanned);
+            if !(first..=last).contains(&range.line) && !requested.contains(diag.rule_id.as_str()) {
+                continue;
+            }
+            let Some(rule) = self
+                .engine
+                .rules()
+                .iter()
+                .find(|rule| rule.id() == diag.rule_id && rule.can_fix())
+            else {
+                continue;
+            };
+            let mut fixed = spec.clone();
+            if rule.fix(&mut fixed, diag).is_err() {
+                continue;
+            }
+            actions.push(json!({
+                "title": format!("Fix: {} ({})", diag.message, diag.rule_id),
+                "kind": "quickfix",
+                "diagnostics": [lsp_diagnostic(diag, &text, &scanned)],
+                "edit": document_edit(&uri, &text, &compile_markdown(&fixed)),
+            }));
+        }
+
+        if fixable.len() > 1 {
+            let mut fixed = spec.clone();
+            if self.engine.auto_fix(&mut fixed)?.fixed_count() > 0 {
+                actions.push(json!({
+                    "title": "Fix all auto-fixable issues",
+                    "kind": "source.fixAll",
+                    "edit": document_edit(&uri, &text, &compile_markdown(&fixed)),
+                }));
+            }
+        }
+
+        Ok(Value::Array(actions))
+    }
+
+    fn completion(&self, params: &Value) -> Result<Value> {
+        let (_, text) = self.text(params)?;
+        let lines = split_lines(&text);
+        let (line, _) = position(params, &lines);
+        if reference_field(&lines, line).is_none() {
+            return Ok(json!([]));
+        }
+
+        let current = parse_markdown(&text)
+            .map(|spec| spec.metadata.id)
+            .unwrap_or_default();
+        let items: Vec<Value> = self
+            .ctx
+            .db
+            .list_skills(10_000, 0)?
+            .into_iter()
+            .filter(|skill| skill.id != current)
+            .map(|skill| {
+                json!({
+                    "label": skill.id,
+                    "kind": COMPLETION_KIND_REFERENCE,
+                    "detail": skill.name,
+                    "documentation": skill.description,
+                })
+            })
+            .collect();
+        Ok(Value::Array(items))
+    }
+
+    fn definition(&self, params: &Value) -> Result<Value> {
+        let (_, text) = self.text(params)?;
+        let lines = split_lines(&text);
+        let (line, character) = position(params, &lines);
+        let Some((_, skill_id)) = reference_at(&lines, line, character) else {
+            return Ok(Value::Null);
+        };
+        let Some(skill) = self.ctx.db.get_skill(&skill_id)? else {
+            return Ok(Value::Null);
+        };
+
+        let target = [
+            skill.skill_dir().join("SKILL.md"),
+            PathBuf::from(&skill.source_path),
+        ]
+        .into_iter()
+        .find(|path| path.is_file());
+        Ok(target.map_or(Value::Null, |path| {
+            json!({
+                "uri": path_to_uri(&path),
+                "range": { "start": { "line": 0, "character": 0 }, "end": { "line": 0, "character": 0 } },
+            })
+        }))
+    }
+
+    fn hover(&self, params: &Value) -> Result<Value> {
+        let (_, text) = self.text(params)?;
+        let lines = split_lines(&text);
+        let (line, character) = position(params, &lines);
+        let Some((field, skill_id)) = reference_at(&lines, line, character) else {
+            return Ok(Value::Null);
+        };
+
+        let repository = DbSkillRepository::new(&self.ctx.db);
+        let value = match repository.get(&skill_id)? {
+            None => format!("Unknown skill `{skill_id}`"),
+            Some(target) => {
+                let resolved = resolve_full(&target, &repository)?;
+                let local: HashSet<String> = if field == RefField::Extends {
+                    parse_markdown(&text)
+                        .map(|spec| spec.sections.into_iter().map(|s| s.id).collect())
+                        .unwrap_or_default()
+                } else {
+                    HashSet::new()
+                };
+                hover_markdown(
+                    &target,
+                    field,
+                    &resolved.spec.sections,
+                    &local,
+                    &resolved.inheritance_chain,
+                )
+            }
+        };
+        Ok(json!({ "contents": { "kind": "markdown", "value": value } }))
+    }
+}
+
+fn initialize_result() -> Value {
+    json!({
+        "capabilities": {
+            "textDocumentSync": {
+                "openClose": true,
+                "change": 1,
+                "willSaveWaitUntil": true,
+                "save": { "includeText": false },
+            },
+            "completionProvider": { "triggerCharacters": [" ", ",", "["] },
+            "definitionProvider": true,
+            "hoverProvider": true,
+            "codeActionProvider": { "codeActionKinds": ["quickfix", "source.fixAll"] },
+            "documentFormattingProvider": true,
+        },
+        "serverInfo": { "name": "ms", "version": env!("CARGO_PKG_VERSION") },
+    })
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+fn publish_diagnostics(uri: &str, diagnostics: Vec<Value>) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": { "uri": uri, "diagnostics": diagnostics },
+    })
+}
+
+fn document_uri(params: &Value) -> Option<&str> {
+    params["textDocument"]["uri"].as_str()
+}
+
+/// Cursor position as (line, char index).
+fn position(params: &Value, lines: &[&str]) -> (usize, usize) {
+    let line = params["position"]["line"].as_u64().unwrap_or(0) as usize;
+    let utf16 = params["position"]["character"].as_u64().unwrap_or(0) as usize;
+    let character = lines.get(line).map_or(0, |text| utf16_to_char(text, utf16));
+    (line, character)
+}
+
+fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let path = uri.strip_prefix("file://")?;
+    urlencoding::decode(path)
+        .ok()
+        .map(|path| PathBuf::from(path.into_owned()))
+}
+
+fn path_to_uri(path: &Path) -> String {
+    let encoded: Vec<String> = path
+        .to_string_lossy()
+        .split('/')
+        .map(|segment| urlencoding::encode(segment).into_owned())
+        .collect();
+    format!("file://{}", encoded.join("/"))
+}
+
+// ============================================================================
+// Positions and diagnostics
+// ============================================================================
+
+/// A range on one line, in char columns (converted to UTF-16 for LSP).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct LineRange {
+    line: usize,
+    start: usize,
+    end: usize,
+}
+
+impl LineRange {
+    fn whole_line(lines: &[&str], line: usize) -> Self {
+        Self {
+            line,
+            start: 0,
+            end: lines.get(line).map_or(0, |text| text.chars().count()),
+        }
+    }
+
+    fn to_lsp(self, text: &str) -> Value {
+        let lines = split_lines(text);
+        let line_text = lines.get(self.line).copied().unwrap_or_default();
+        json!({
+            "start": { "line": self.line, "character": char_to_utf16(line_text, self.start) },
+            "end": { "line": self.line, "character": char_to_utf16(line_text, self.end) },
+        })
+    }
+}
+
+/// Lines without terminators, keeping a trailing empty line.
+fn split_lines(text: &str) -> Vec<&str> {
+    text.split('\n')
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+        .collect()
+}
+
+fn char_to_utf16(line: &str, chars: usize) -> usize {
+    line.chars().take(chars).map(char::len_utf16).sum()
+}
+
+fn utf16_to_char(line: &str, utf16: usize) -> usize {
+    let mut units = 0;
+    for (index, ch) in line.chars().enumerate() {
+        if units >= utf16 {
+            return index;
+        }
+        units += ch.len_utf16();
+    }
+    line.chars().count()
+}
+
+fn lsp_diagnostic(diag: &Diagnostic, text: &str, scanned: &str) -> Value {
+    let severity = match diag.severity {
+        Severity::Error => 1,
+        Severity::Warning => 2,
+        Severity::Info => 3,
+    };
+    let message = match &diag.suggestion {
+        Some(hint) => format!("{}\nhint: {hint}", diag.message),
+        None => diag.message.clone(),
+    };
+    json!({
+        "range": locate(diag, text, scanned).to_lsp(text),
+        "severity": severity,
+        "code": diag.rule_id,
+        "source": "ms",
+        "message": message,
+    })
+}
+
+/// Place a diagnostic in the document: its span (which indexes the text the
+/// rules scan, see `extract_all_content`) mapped back to the source, else a
+/// quoted name from the message, else a frontmatter key it mentions, else
+/// the first line.
+fn locate(diag: &Diagnostic, text: &str, scanned: &str) -> LineRange {
+    let lines = split_lines(text);
+    if let Some(span) = &diag.span {
+        let scanned_line = span
+            .start_line
+            .checked_sub(1)
+            .and_then(|index| scanned.lines().nth(index));
+        if let Some(scanned_line) = scanned_line {
+            let start = span.start_col.saturating_sub(1);
+            let end = if span.end_line == span.start_line {
+                span.end_col.saturating_sub(1)
+            } else {
+                scanned_line.chars().count()
+            };
+            let needle: String = scanned_line
+                .chars()
+                .skip(start)
+                .take(end.saturating_sub(start))
+                .collect();
+            let context = scanned_line.trim();
+            let in_context = lines.iter().enumerate().find_map(|(index, line)| {
+                if context.is_empty() || !line.contains(context) {
+                    return None;
+                }
+                find_in_line(index, line, &needle)
+            });
+            if let Some(found) = in_context.or_else(|| find_text(&lines, &needle)) {
+                return found;
+            }
+        }
+    }
+
+    if let Some(name) = quoted(&diag.message) {
+        if let Some(found) = find_text(&lines, name) {
+            return found;
+        }
+    }
+
+    if let Some(found) = frontmatter_key(&lines, &diag.message) {
+        return found;
+    }
+
+    LineRange::whole_line(&lines, 0)
+}
+
+fn find_in_line(index: usize, line: &str, needle: &str) -> Option<LineRange> {
+    if needle.trim().is_empty() {
+        return None;
+    }
+    let byte = line.find(needle)?;
+    let start = line[..byte].chars().count();
+    Some(LineRange {
+        line: index,
+        start,
+        end: start + needle.chars().count(),
+    })
+}
+
+fn find_text(lines: &[&str], needle: &str) -> Option<LineRange> {
+    lines
+        .iter()
+        .enumerate()
+        .find_map(|(index, line)| find_in_line(index, line, needle))
+}
+
+fn quoted(message: &str) -> Option<&str> {
+    let start = message.find('\'')? + 1;
+    let len = message[start..].find('\'')?;
+    (len > 0).then(|| &message[start..start + len])
+}
+
+/// Index of the closing `---` of the frontmatter.
+fn frontmatter_end(lines: &[&str]) -> Option<usize> {
+    if lines.first().map(|line| line.trim()) != Some("---") {
+        return None;
+    }
+    lines
+        .iter()
+        .skip(1)
+        .position(|line| line.trim() == "---")
+        .map(|index| index + 1)
+}
+
+/// The top-level frontmatter key named in a message ("Skill description is
+/// empty" lands on `description:`).
+fn frontmatter_key(lines: &[&str], message: &str) -> Option<LineRange> {
+    let end = frontmatter_end(lines)?;
+    let lower = message.to_lowercase();
+    let words: HashSet<&str> = lower
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .collect();
+    (1..end).find_map(|index| {
+        let line = lines[index];
+        let (key, _) = line.split_once(':')?;
+        (!line.starts_with([' ', '\t', '-']) && words.contains(key.trim()))
+            .then(|| LineRange::whole_line(lines, index))
+    })
+}
+
+// ============================================================================
+// Skill references in frontmatter
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RefField {
+    Extends,
+    Includes,
+    Requires,
+}
+
+impl RefField {
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Extends => "extends",
+            Self::Includes => "includes",
+            Self::Requires => "requires",
+        }
+    }
+}
+
+/// The frontmatter field (`extends`, `includes`, `requires`) whose value
+/// covers `line`, following indented and list continuation lines up to
+/// their key.
+fn reference_field(lines: &[&str], line: usize) -> Option<RefField> {
+    let end = frontmatter_end(lines)?;
+    if line == 0 || line >= end {
+        return None;
+    }
+    let owner = (1..=line)
+        .rev()
+        .map(|index| lines[index])
+        .find(|text| !text.trim().is_empty() && !text.starts_with([' ', '\t', '-']))?;
+    let (key, _) = owner.split_once(':')?;
+    match key.trim() {
+        "extends" => Some(RefField::Extends),
+        "includes" => Some(RefField::Includes),
+        "requires" => Some(RefField::Requires),
+        _ => None,
+    }
+}
+
+/// The skill id under the cursor, if it sits in a reference field.
+fn reference_at(lines: &[&str], line: usize, character: usize) -> Option<(RefField, String)> {
+    let field = reference_field(lines, line)?;
+    let chars: Vec<char> = lines[line].chars().collect();
+    let is_id = |c: char| c.is_alphanumeric() || matches!(c, '-' | '_' | '.' | '/');
+
+    let mut start = character.min(chars.len());
+    while start > 0 && is_id(chars[start - 1]) {
+        start -= 1;
+    }
+    let mut end = character.min(chars.len());
+    while end < chars.len() && is_id(chars[end]) {
+        end += 1;
+    }
+    if start == end {
+        return None;
+    }
+    // Keys (`extends:`, `skill:`) are not references
+    if chars[end..].iter().find(|c| !c.is_whitespace()) == Some(&':') {
+        return None;
+    }
+    Some((field, chars[start..end].iter().collect()))
+}
+
+fn hover_markdown(
+    target: &SkillSpec,
+    field: RefField,
+    sections: &[SkillSection],
+    local: &HashSet<String>,
+    chain: &[String],
+) -> String {
+    let mut out = format!("**{}** (`{}`)\n", target.metadata.name, target.metadata.id);
+    if !target.metadata.description.is_empty() {
+        out.push_str(&format!("\n{}\n", target.metadata.description));
+    }
+    if chain.len() > 1 {
+        out.push_str(&format!("\n_Inherits: {}_\n", chain.join(" → ")));
+    }
+
+    if field == RefField::Extends {
+        let inherited: Vec<&SkillSection> =
+            sections.iter().filter(|s| !local.contains(&s.id)).collect();
+        if inherited.is_empty() {
+            out.push_str("\nEvery parent section is overridden here.\n");
+        }
+        for section in inherited {
+            out.push_str(&format!(
+                "\n### {}\n\n{}\n",
+                section.title,
+                preview(section)
+            ));
+        }
+    } else if !sections.is_empty() {
+        out.push_str(&format!("\nSections ({}):\n", field.label()));
+        for section in sections {
+            out.push_str(&format!("- {}\n", section.title));
+        }
+    }
+    out
+}
+
+fn preview(section: &SkillSection) -> String {
+    let text = section
+        .blocks
+        .iter()
+        .map(|block| block.content.as_str())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let lines: Vec<&str> = text.lines().collect();
+    if lines.len() > HOVER_PREVIEW_LINES {
+        format!("{}\n…", lines[..HOVER_PREVIEW_LINES].join("\n"))
+    } else {
+        text
+    }
+}
+
+// ============================================================================
+// Edits
+// ============================================================================
+
+/// A `TextEdit` replacing the whole document.
+fn full_edit(old: &str, new: &str) -> Value {
+    let lines = split_lines(old);
+    let last = lines.len().saturating_sub(1);
+    let width = lines
+        .get(last)
+        .map_or(0, |line| char_to_utf16(line, line.chars().count()));
+    json!({
+        "range": {
+            "start": { "line": 0, "character": 0 },
+            "end": { "line": last, "character": width },
+        },
+        "newText": new,
+    })
+}
+
+fn document_edit(uri: &str, old: &str, new: &str) -> Value {
+    json!({ "changes": { uri: [full_edit(old, new)] } })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lint::SourceSpan;
+
+    const SKILL: &str = "---\nid: child\nname: Child\ndescription: Child skill\nextends: parent-skill\nrequires:\n  - rust-basics\n---\n\n# Child\n\n## Rules\n\n- Never commit api_key=abc\n";
+
+    #[test]
+    fn test_message_framing_roundtrip() {
+        let mut buf = Vec::new();
+        write_message(
+            &mut buf,
+            &json!({ "jsonrpc": "2.0", "method": "initialized" }),
+        )
+        .unwrap();
+        let mut reader = io::Cursor::new(buf);
+        let message = read_message(&mut reader).unwrap().unwrap();
+        assert_eq!(message["method"], "initialized");
+        assert!(read_message(&mut reader).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_message_framing_rejects_bad_headers() {
+        for input in [
+            "\r\n{}".to_string(),
+            "Content-Type: application/json\r\n\r\n{}".to_string(),
+            "Content-Length: abc\r\n\r\n{}".to_string(),
+            format!("Content-Length: {}\r\n\r\n", MAX_MESSAGE_BYTES + 1),
+            "Content-Length: 2\r\n".to_string(),
+        ] {
+            let mut reader = io::Cursor::new(input.as_bytes().to_vec());
+            assert!(read_message(&mut reader).is_err(), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn test_reference_field_detection() {
+        let lines = split_lines(SKILL);
+        assert_eq!(reference_field(&lines, 4), Some(RefField::Extends));
+        assert_eq!(reference_field(&lines, 6), Some(RefField::Requires));
+        assert_eq!(reference_field(&lines, 2), None);
+        assert_eq!(reference_field(&lines, 12), None);
+
+        let (field, id) = reference_at(&lines, 4, 12).unwrap();
+        assert_eq!(field, RefField::Extends);
+        assert_eq!(id, "parent-skill");
+        assert_eq!(reference_at(&lines, 6, 6).unwrap().1, "rust-basics");
+        // The key itself is not a reference
+        assert!(reference_at(&lines, 4, 2).is_none());
+    }
+
+    #[test]
+    fn test_locate_maps_scanned_spans_to_source() {
+        let spec = parse_markdown(SKILL).unwrap();
+        let scanned = extract_all_content(&spec);
+        let (index, line) = scanned
+            .lines()
+            .enumerate()
+            .find(|(_, line)| line.contains("api_key"))
+            .unwrap();
+        let col = line[..line.find("api_key").unwrap()].chars().count() + 1;
+        let diag = Diagnostic::error("no-secrets", "Potential secret detected").with_span(
+            SourceSpan::new(index + 1, col, index + 1, col + "api_key=abc".len()),
+        );
+
+        let range = locate(&diag, SKILL, &scanned);
+        assert_eq!(range.line, 13);
+        assert_eq!(range.start, "- Never commit ".len());
+        assert_eq!(range.end, range.start + "api_key=abc".len());
+    }
+
+    #[test]
+    fn test_locate_falls_back_to_frontmatter_key_and_quotes() {
+        let lines = split_lines(SKILL);
+        let diag = Diagnostic::warning("meaningful-description", "Description is too short");
+        assert_eq!(locate(&diag, SKILL, "").line, 3);
+
+        let diag = Diagnostic::error("valid-extends", "Parent skill 'parent-skill' not found");
+        let range = locate(&diag, SKILL, "");
+        assert_eq!(range.line, 4);
+        assert_eq!(range.start, "extends: ".len());
+
+        let diag = Diagnostic::info("other", "Something general");
+        assert_eq!(locate(&diag, SKILL, ""), LineRange::whole_line(&lines, 0));
+    }
+
+    #[test]
+    fn test_full_edit_covers_document() {
+        let edit = full_edit("a\nbé\n", "x");
+        assert_eq!(edit["range"]["end"]["line"], 2);
+        assert_eq!(edit["range"]["end"]["character"], 0);
+        let edit = full_edit("a\nbé", "x");
+        assert_eq!(edit["range"]["end"]["line"], 1);
+        assert_eq!(edit["range"]["end"]["character"], 2);
+    }
+
+    #[test]
+    fn test_utf16_conversion() {
+        assert_eq!(char_to_utf16("a😀b", 2), 3);
+        assert_eq!(utf16_to_char("a😀b", 3), 2);
+        assert_eq!(utf16_to_char("ab", 10), 2);
+    }
+}
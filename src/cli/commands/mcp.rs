@@ -4,6 +4,11 @@
 //! with AI coding agents. Supports stdio transport (primary) and optional
 //! TCP transport.
 //!
+//! Script entry points declared in skill frontmatter (`scripts:`) are exposed
+//! as additional tools named `<skill-id>__<script>`. Calls run in the
+//! simulation sandbox behind `SafetyGate` and are recorded in
+//! `skill_usage_events`.
+//!
 //! # Output Safety
 //!
 //! **CRITICAL**: MCP responses MUST always be valid JSON. This module enforces:
@@ -22,13 +27,21 @@ use serde_json::Value;
 use tracing::{debug, warn};
 
 use crate::app::AppContext;
+use crate::cli::commands::load::{check_trust, check_trust_with_assets, skill_asset_hashes};
 use crate::cli::output::OutputFormat;
 use crate::cli::output::emit_json;
 use crate::context::detector::ProjectDetector;
+use crate::core::references::{DEFAULT_PAGE_LINES, ReferenceAddress, SkillReferences};
+use crate::core::script_tools::{ScriptTool, script_tools};
 use crate::core::spec_lens::parse_markdown;
 use crate::error::{MsError, Result};
+use crate::lint::extend_with_custom_rules;
 use crate::lint::rules::all_rules;
-use crate::lint::{ValidationConfig, ValidationEngine};
+use crate::lint::{ValidationConfig, ValidationContext, ValidationEngine};
+use crate::search::SearchQuery;
+use crate::security::{PolicyAction, TrustAction, TrustDecision};
+use crate::simulation::{SimulationConfig, SimulationEngine};
+use crate::storage::sqlite::SkillRecord;
 
 /// MCP server protocol version
 const PROTOCOL_VERSION: &str = "2024-11-05";
@@ -392,18 +405,28 @@ fn define_tools() -> Vec<Tool> {
     vec![
         Tool {
             name: "search".to_string(),
-            description: "Search for skills using BM25 full-text search".to_string(),
+            description: "Search for skills using BM25 full-text search; each result includes the best-matching section with highlight offsets. The query accepts filters: tag:, author:, layer:, platform:, requires:, updated:>YYYY-MM-DD, quality:>0.7 (prefix - to exclude) and \"exact phrases\"".to_string(),
             input_schema: serde_json::json!({
                 "type": "object",
                 "properties": {
                     "query": {
                         "type": "string",
-                        "description": "Search query text"
+                        "description": "Search query text, e.g. 'tag:rust -tag:legacy quality:>0.7 error handling'"
                     },
                     "limit": {
                         "type": "integer",
                         "description": "Maximum number of results (default: 20)",
                         "default": 20
+                    },
+                    "include_deprecated": {
+                        "type": "boolean",
+                        "description": "Include deprecated skills (default: false)",
+                        "default": false
+                    },
+                    "snippets": {
+                        "type": "boolean",
+                        "description": "Include matched sections with snippets (default: true)",
+                        "default": true
                     }
                 },
                 "required": ["query"]
@@ -618,6 +641,37 @@ fn define_tools() -> Vec<Tool> {
                 }
             }),
         },
+        Tool {
+            name: "read_reference".to_string(),
+            description: "Read a skill's reference files on demand. Pass ref ('api.md' or 'api.md#heading') to fetch a page of a file or section, query to search the references, or neither to list files and their headings".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "skill": {
+                        "type": "string",
+                        "description": "Skill ID"
+                    },
+                    "ref": {
+                        "type": "string",
+                        "description": "Reference path, optionally with #heading"
+                    },
+                    "query": {
+                        "type": "string",
+                        "description": "Search terms within the skill's references"
+                    },
+                    "offset": {
+                        "type": "integer",
+                        "description": "First line of the page (default: 0)",
+                        "default": 0
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Lines per page, or hits for query (default: 200 lines, 10 hits)"
+                    }
+                },
+                "required": ["skill"]
+            }),
+        },
         Tool {
             name: "config".to_string(),
             description: "Get or set ms configuration values".to_string(),
@@ -644,6 +698,31 @@ fn define_tools() -> Vec<Tool> {
     ]
 }
 
+/// Tool definition for a skill script.
+fn script_tool_definition(tool: &ScriptTool) -> Tool {
+    Tool {
+        name: tool.name().to_string(),
+        description: tool.description(),
+        input_schema: tool.input_schema(),
+    }
+}
+
+/// Built-in tools followed by the script tools of indexed skills.
+fn all_tools(ctx: &AppContext) -> Vec<Tool> {
+    let mut tools = define_tools();
+    match script_tools(&ctx.db) {
+        Ok(scripts) => tools.extend(scripts.iter().map(script_tool_definition)),
+        Err(err) => warn!("failed to load skill script tools: {err}"),
+    }
+    // Tools the `[mcp]` policy forbids are never advertised.
+    tools.retain(|tool| {
+        ctx.policy
+            .check(&PolicyAction::McpTool { name: &tool.name })
+            .allowed
+    });
+    tools
+}
+
 // ============================================================================
 // MCP Server Implementation
 // ============================================================================
@@ -656,7 +735,7 @@ pub fn run(ctx: &AppContext, args: &McpArgs) -> Result<()> {
 }
 
 fn run_tools(ctx: &AppContext) -> Result<()> {
-    let tools = define_tools();
+    let tools = all_tools(ctx);
     if ctx.output_format != OutputFormat::Human {
         emit_json(&serde_json::json!({
             "tools": tools,
@@ -778,7 +857,7 @@ fn handle_request(ctx: &AppContext, line: &str, debug: bool) -> Option<JsonRpcRe
     match request.method.as_str() {
         "initialize" => Some(handle_initialize(request.id, &request.params)),
         "initialized" => handle_initialized(request.id),
-        "tools/list" => Some(handle_tools_list(request.id)),
+        "tools/list" => Some(handle_tools_list(ctx, request.id)),
         "tools/call" => Some(handle_tools_call(ctx, request.id, &request.params, debug)),
         "ping" => Some(handle_ping(request.id)),
         "shutdown" => Some(handle_shutdown(request.id)),
@@ -813,9 +892,9 @@ fn handle_initialized(id: Option<Value>) -> Option<JsonRpcResponse> {
     id.map(|id| JsonRpcResponse::success(Some(id), serde_json::json!({})))
 }
 
-fn handle_tools_list(id: Option<Value>) -> JsonRpcResponse {
+fn handle_tools_list(ctx: &AppContext, id: Option<Value>) -> JsonRpcResponse {
     let result = ToolsListResult {
-        tools: define_tools(),
+        tools: all_tools(ctx),
     };
     JsonRpcResponse::success(id, serde_json::to_value(result).unwrap())
 }
@@ -848,6 +927,11 @@ fn handle_tools_call(
         eprintln!("[ms-mcp] Calling tool: {name} with {arguments:?}");
     }
 
+    if let Err(err) = ctx.policy.enforce(&PolicyAction::McpTool { name }) {
+        let tool_result = ToolResult::error(err.to_string());
+        return JsonRpcResponse::success(id, serde_json::to_value(tool_result).unwrap());
+    }
+
     // Dispatch to tool handler
     let result = match name {
         "search" => handle_tool_search(ctx, &arguments),
@@ -862,7 +946,8 @@ fn handle_tools_call(
         "index" => handle_tool_index(ctx, &arguments),
         "validate" => handle_tool_validate(ctx, &arguments),
         "config" => handle_tool_config(ctx, &arguments),
-        _ => Err(MsError::ValidationFailed(format!("Unknown tool: {name}"))),
+        "read_reference" => handle_tool_read_reference(ctx, &arguments),
+        _ => handle_tool_script(ctx, name, &arguments),
     };
 
     match result {
@@ -915,17 +1000,36 @@ fn handle_tool_search(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
         .and_then(serde_json::Value::as_u64)
         .unwrap_or(20) as usize;
 
-    // Use BM25 search via Tantivy
-    let results = ctx.search.search(query, limit)?;
+    let snippets = args
+        .get("snippets")
+        .and_then(serde_json::Value::as_bool)
+        .unwrap_or(true);
+
+    let include_deprecated = args
+        .get("include_deprecated")
+        .and_then(serde_json::Value::as_bool)
+        .unwrap_or(false);
+
+    // Use BM25 search via Tantivy with the query's filters pushed down
+    let mut parsed = SearchQuery::parse(query)?;
+    parsed.filters = parsed.filters.include_deprecated(include_deprecated);
+    let results = ctx
+        .search
+        .search_filtered(&parsed.text, &parsed.filters, limit, snippets)?;
 
     let output = serde_json::json!({
         "query": query,
         "count": results.len(),
         "results": results.iter().map(|r| {
-            serde_json::json!({
+            let mut result = serde_json::json!({
                 "id": r.skill_id,
                 "score": r.score,
-            })
+            });
+            if !r.matched_sections.is_empty() {
+                result["snippet"] = serde_json::json!(r.matched_sections[0].label());
+                result["matched_sections"] = serde_json::json!(r.matched_sections);
+            }
+            result
         }).collect::<Vec<_>>()
     });
 
@@ -947,8 +1051,9 @@ fn handle_tool_load(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
         .db
         .get_skill(skill_id)?
         .ok_or_else(|| MsError::SkillNotFound(skill_id.to_string()))?;
+    let trust = enforce_trust(ctx, &skill)?;
 
-    let output = if full {
+    let mut output = if full && trust.action != TrustAction::Minimal {
         serde_json::json!({
             "skill_id": skill.id,
             "name": skill.name,
@@ -965,10 +1070,29 @@ fn handle_tool_load(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
             "layer": skill.source_layer,
         })
     };
+    if trust.action != TrustAction::Allow {
+        output["trust_warning"] = Value::String(trust.reason(&skill.id));
+    }
 
     Ok(ToolResult::text(serde_json::to_string_pretty(&output)?))
 }
 
+/// Apply the signing trust policy; denied skills are an error.
+fn enforce_trust(ctx: &AppContext, skill: &SkillRecord) -> Result<TrustDecision> {
+    refuse_denied(skill, check_trust(ctx, skill)?)
+}
+
+/// Turn a denying trust decision into an error.
+fn refuse_denied(skill: &SkillRecord, trust: TrustDecision) -> Result<TrustDecision> {
+    if trust.action == TrustAction::Deny {
+        return Err(MsError::ValidationFailed(format!(
+            "{}; blocked by trust policy",
+            trust.reason(&skill.id)
+        )));
+    }
+    Ok(trust)
+}
+
 fn handle_tool_evidence(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
     let skill_id = args.get("skill").and_then(|v| v.as_str()).ok_or_else(|| {
         MsError::ValidationFailed("Missing required parameter: skill".to_string())
@@ -1065,8 +1189,9 @@ fn handle_tool_show(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
         .db
         .get_skill(skill_id)?
         .ok_or_else(|| MsError::SkillNotFound(skill_id.to_string()))?;
+    let trust = enforce_trust(ctx, &skill)?;
 
-    let output = if full {
+    let output = if full && trust.action != TrustAction::Minimal {
         serde_json::json!({
             "id": skill.id,
             "name": skill.name,
@@ -1174,7 +1299,9 @@ fn handle_tool_lint(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
         .and_then(|v| v.as_array())
         .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect());
 
-    for rule in all_rules() {
+    let mut rules = all_rules();
+    extend_with_custom_rules(&mut rules, &ctx.ms_root)?;
+    for rule in rules {
         if let Some(ref filter) = rules_filter {
             if !filter.contains(rule.id()) {
                 continue;
@@ -1184,7 +1311,8 @@ fn handle_tool_lint(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
     }
 
     // Run validation
-    let result = engine.validate(&spec);
+    let validation = ValidationContext::new(&spec, engine.config()).with_source(&content);
+    let result = engine.validate_with_context(&validation);
 
     // Build output
     let output = serde_json::json!({
@@ -1351,7 +1479,7 @@ fn handle_tool_index(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
     Ok(ToolResult::text(serde_json::to_string_pretty(&output)?))
 }
 
-fn handle_tool_validate(_ctx: &AppContext, args: &Value) -> Result<ToolResult> {
+fn handle_tool_validate(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
     // Get content from either content param or path
     let (content, source) = if let Some(content) = args.get("content").and_then(|v| v.as_str()) {
         (content.to_string(), "inline".to_string())
@@ -1373,7 +1501,9 @@ fn handle_tool_validate(_ctx: &AppContext, args: &Value) -> Result<ToolResult> {
     let config = ValidationConfig::new();
     let mut engine = ValidationEngine::new(config);
 
-    for rule in all_rules() {
+    let mut rules = all_rules();
+    extend_with_custom_rules(&mut rules, &ctx.ms_root)?;
+    for rule in rules {
         engine.register(rule);
     }
 
@@ -1405,6 +1535,105 @@ fn handle_tool_validate(_ctx: &AppContext, args: &Value) -> Result<ToolResult> {
     Ok(ToolResult::text(serde_json::to_string_pretty(&output)?))
 }
 
+fn handle_tool_read_reference(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
+    let skill_id = args.get("skill").and_then(|v| v.as_str()).ok_or_else(|| {
+        MsError::ValidationFailed("Missing required parameter: skill".to_string())
+    })?;
+    let limit = args
+        .get("limit")
+        .and_then(serde_json::Value::as_u64)
+        .map(|v| v as usize);
+
+    let skill = ctx
+        .db
+        .get_skill(skill_id)?
+        .ok_or_else(|| MsError::SkillNotFound(skill_id.to_string()))?;
+    if enforce_trust(ctx, &skill)?.action == TrustAction::Minimal {
+        return Err(MsError::ValidationFailed(format!(
+            "references for {} are blocked by trust policy",
+            skill.id
+        )));
+    }
+    let references = SkillReferences::from_record(&skill);
+
+    let output = if let Some(reference) = args.get("ref").and_then(|v| v.as_str()) {
+        let offset = args
+            .get("offset")
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(0) as usize;
+        let section = references.section(&ReferenceAddress::parse(reference))?;
+        serde_json::to_value(section.page(offset, limit.unwrap_or(DEFAULT_PAGE_LINES)))?
+    } else if let Some(query) = args.get("query").and_then(|v| v.as_str()) {
+        serde_json::json!({
+            "skill_id": skill.id,
+            "query": query,
+            "hits": references.search(query, limit.unwrap_or(10))?,
+        })
+    } else {
+        serde_json::json!({
+            "skill_id": skill.id,
+            "references": references.outline()?,
+        })
+    };
+
+    Ok(ToolResult::text(serde_json::to_string_pretty(&output)?))
+}
+
+fn handle_tool_script(ctx: &AppContext, name: &str, args: &Value) -> Result<ToolResult> {
+    let tools = script_tools(&ctx.db)?;
+    let tool = tools
+        .iter()
+        .find(|tool| tool.name() == name)
+        .ok_or_else(|| MsError::ValidationFailed(format!("Unknown tool: {name}")))?;
+
+    // Scripts run the skill's own code, so they need the same trust as its
+    // text, over the script and reference files too. The hashes checked here
+    // are checked again against the copy the script runs from.
+    let skill = ctx
+        .db
+        .get_skill(&tool.skill_id)?
+        .ok_or_else(|| MsError::SkillNotFound(tool.skill_id.clone()))?;
+    let assets = skill_asset_hashes(&skill)?;
+    let trust = refuse_denied(&skill, check_trust_with_assets(ctx, &skill, &assets)?)?;
+    if trust.action == TrustAction::Minimal {
+        return Err(MsError::ValidationFailed(format!(
+            "scripts for {} are blocked by trust policy",
+            skill.id
+        )));
+    }
+
+    let engine = SimulationEngine::new(ctx);
+    let run = engine.run_script(tool, args, &assets, SimulationConfig::default());
+    let (success, details) = match &run {
+        Ok(run) => (
+            run.exit_code == 0,
+            serde_json::json!({
+                "tool": run.tool,
+                "exit_code": run.exit_code,
+                "duration_ms": run.duration_ms,
+            }),
+        ),
+        Err(err) => (
+            false,
+            serde_json::json!({ "tool": name, "error": err.to_string() }),
+        ),
+    };
+    if let Err(err) =
+        ctx.db
+            .record_script_invocation(&tool.skill_id, "mcp", &tool.entry.name, success, &details)
+    {
+        warn!("failed to record script invocation for {name}: {err}");
+    }
+
+    let run = run?;
+    let output = serde_json::to_string_pretty(&run)?;
+    if run.exit_code == 0 {
+        Ok(ToolResult::text(output))
+    } else {
+        Ok(ToolResult::error(output))
+    }
+}
+
 fn handle_tool_config(ctx: &AppContext, args: &Value) -> Result<ToolResult> {
     let action = args
         .get("action")
@@ -1574,6 +1803,18 @@ mod tests {
         assert!(props.get("rules").is_some());
     }
 
+    #[test]
+    fn test_read_reference_tool_schema() {
+        let tools = define_tools();
+        let tool = tools.iter().find(|t| t.name == "read_reference").unwrap();
+
+        let props = tool.input_schema.get("properties").unwrap();
+        assert!(props.get("ref").is_some());
+        assert!(props.get("query").is_some());
+        assert!(props.get("offset").is_some());
+        assert!(props.get("limit").is_some());
+    }
+
     #[test]
     fn test_define_tools_includes_suggest() {
         let tools = define_tools();
@@ -1694,6 +1935,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_script_tool_definition() {
+        use crate::core::skill::{ScriptArg, ScriptEntry};
+
+        let tool = ScriptTool::new(
+            "pdf-tools",
+            "/skills/pdf-tools",
+            ScriptEntry {
+                name: "merge".to_string(),
+                path: "scripts/merge.py".to_string(),
+                description: "Merge PDFs".to_string(),
+                args: vec![ScriptArg {
+                    name: "output".to_string(),
+                    required: true,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        );
+        let definition = script_tool_definition(&tool);
+        assert_eq!(definition.name, "pdf-tools__merge");
+        assert!(definition.description.contains("Merge PDFs"));
+        assert_eq!(definition.input_schema["required"][0], "output");
+        assert!(!define_tools().iter().any(|t| t.name == definition.name));
+    }
+
     #[test]
     fn test_tool_count() {
         let tools = define_tools();
@@ -15,6 +15,7 @@ use crate::error::Result;
 
 pub mod alias;
 pub mod antipatterns;
+pub mod audit;
 pub mod auth;
 pub mod backup;
 pub mod bandit;
@@ -46,16 +47,19 @@ pub mod install;
 pub mod lint;
 pub mod list;
 pub mod load;
+pub mod lsp;
 pub mod machine;
 pub mod mcp;
 pub mod meta;
 pub mod migrate;
 pub mod outcome;
 pub mod personalize;
+pub mod policy;
 pub mod pre_commit;
 pub mod preferences;
 pub mod prune;
 pub mod quality;
+pub mod rank;
 pub mod recommend;
 pub mod remote;
 pub mod requirements;
@@ -73,6 +77,7 @@ pub mod test;
 pub mod unhide;
 pub mod update;
 pub mod validate;
+pub mod verify;
 
 /// Dispatch a command to its handler
 pub fn run(ctx: &AppContext, command: &Commands) -> Result<()> {
@@ -105,6 +110,7 @@ pub fn run(ctx: &AppContext, command: &Commands) -> Result<()> {
         Commands::Experiment(args) => experiment::run(ctx, args),
         Commands::Build(args) => build::run(ctx, args),
         Commands::Bundle(args) => bundle::run(ctx, args),
+        Commands::Audit(args) => audit::run(ctx, args),
         Commands::Sync(args) => sync::run(ctx, args),
         Commands::Remote(args) => remote::run(ctx, args),
         Commands::Machine(args) => machine::run(ctx, args),
@@ -117,6 +123,7 @@ pub fn run(ctx: &AppContext, command: &Commands) -> Result<()> {
         Commands::Cm(args) => cm::run(ctx, args),
         Commands::Update(args) => update::run(ctx, args),
         Commands::Bandit(args) => bandit::run(ctx, args),
+        Commands::Rank(args) => rank::run(ctx, args),
         Commands::Backup(args) => backup::run(ctx, args),
         Commands::Browse(args) => browse::run(ctx, args),
         Commands::Doctor(args) => doctor::run(ctx, args),
@@ -124,10 +131,12 @@ pub fn run(ctx: &AppContext, command: &Commands) -> Result<()> {
         Commands::Prune(args) => prune::run(ctx, args),
         Commands::Config(args) => config::run(ctx, args),
         Commands::Security(args) => security::run(ctx, args),
+        Commands::Policy(args) => policy::run(ctx, args),
         Commands::Setup(args) => setup::run(ctx, args),
         Commands::Shell(args) => shell::run(ctx, args),
         Commands::Safety(args) => safety::run(ctx, args),
         Commands::Validate(args) => validate::run(ctx, args),
+        Commands::Verify(args) => verify::run(ctx, args),
         Commands::Test(args) => test::run(ctx, args),
         Commands::Unhide(args) => unhide::run(ctx, args),
         Commands::Simulate(args) => simulate::run(ctx, args),
@@ -135,6 +144,7 @@ pub fn run(ctx: &AppContext, command: &Commands) -> Result<()> {
         Commands::Recommend(args) => recommend::run(ctx, args),
         Commands::Evidence(args) => evidence::run(ctx, args),
         Commands::Mcp(args) => mcp::run(ctx, args),
+        Commands::Lsp(args) => lsp::run(ctx, args),
         Commands::Template(args) => template::run(ctx, args),
         Commands::Embed(args) => embed::run(ctx, args),
     }
@@ -0,0 +1,298 @@
+//! ms policy - Inspect, check and sign organisation policy files
+
+use clap::{Args, Subcommand};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::app::AppContext;
+use crate::bundler::{BundlePackage, Ed25519Signer};
+use crate::cli::output::OutputFormat;
+use crate::cli::output::emit_json;
+use crate::core::SkillLayer;
+use crate::core::spec_lens::parse_markdown;
+use crate::error::{MsError, Result};
+use crate::security::{
+    POLICY_FILE, PolicyAction, PolicyDecision, PolicyTrust, SYSTEM_POLICY_DIR, policy_content_hash,
+    policy_signature_path, policy_sources, read_policy_signatures, sign_content_hash,
+};
+
+#[derive(Args, Debug)]
+pub struct PolicyArgs {
+    #[command(subcommand)]
+    pub command: PolicyCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum PolicyCommand {
+    /// Show loaded policy layers and explain decisions for actions
+    Check(CheckArgs),
+    /// Write a detached signature next to a policy file
+    Sign(SignArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct CheckArgs {
+    /// Shell command to check against `[commands]`
+    #[arg(long = "command")]
+    pub commands: Vec<String>,
+    /// MCP tool name to check against `[mcp]`
+    #[arg(long = "mcp-tool")]
+    pub mcp_tools: Vec<String>,
+    /// `ms build` flag to check against `[build]` (e.g. --no-redact)
+    #[arg(long = "build-flag", allow_hyphen_values = true)]
+    pub build_flags: Vec<String>,
+    /// SKILL.md to check against `[index]`
+    #[arg(long)]
+    pub skill_file: Option<PathBuf>,
+    /// Layer the skill file would be indexed from
+    #[arg(long, default_value = "project")]
+    pub layer: String,
+    /// Bundle file to check against `[install]`
+    #[arg(long)]
+    pub bundle: Option<PathBuf>,
+    /// Check the bundle as if installed with --no-verify
+    #[arg(long, requires = "bundle")]
+    pub no_verify: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct SignArgs {
+    /// Policy file to sign (defaults to the project policy)
+    pub path: Option<PathBuf>,
+    /// OpenSSH Ed25519 private key (defaults to security.signing.key)
+    #[arg(long)]
+    pub key: Option<PathBuf>,
+    /// Signer name recorded with the signature (defaults to $USER)
+    #[arg(long)]
+    pub signer: Option<String>,
+}
+
+#[derive(Serialize)]
+struct SourceOutput {
+    layer: SkillLayer,
+    path: PathBuf,
+    present: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    signature: Option<String>,
+}
+
+#[derive(Serialize)]
+struct CheckOutput {
+    sources: Vec<SourceOutput>,
+    errors: Vec<String>,
+    fail_closed: bool,
+    decisions: Vec<PolicyDecision>,
+}
+
+#[derive(Serialize)]
+struct SignOutput {
+    path: PathBuf,
+    signature_path: PathBuf,
+    content_hash: String,
+    key_id: String,
+    signer: String,
+}
+
+pub fn run(ctx: &AppContext, args: &PolicyArgs) -> Result<()> {
+    match &args.command {
+        PolicyCommand::Check(check_args) => check(ctx, check_args),
+        PolicyCommand::Sign(sign_args) => sign(ctx, sign_args),
+    }
+}
+
+fn check(ctx: &AppContext, args: &CheckArgs) -> Result<()> {
+    let engine = &ctx.policy;
+    let system_dir = Path::new(SYSTEM_POLICY_DIR);
+    // An unreadable trust anchor is already among the engine's errors.
+    let trust = PolicyTrust::load(system_dir).unwrap_or_default();
+    let sources = policy_sources(system_dir, &trust, &ctx.ms_root)
+        .into_iter()
+        .map(|(layer, path)| {
+            let signature = engine
+                .layers
+                .iter()
+                .find(|loaded| loaded.path == path)
+                .map(|loaded| loaded.signature.label().to_string());
+            SourceOutput {
+                layer,
+                present: path.exists(),
+                path,
+                signature,
+            }
+        })
+        .collect();
+
+    let mut decisions = Vec::new();
+    for command in &args.commands {
+        decisions.push(engine.check(&PolicyAction::RunCommand { command }));
+    }
+    for name in &args.mcp_tools {
+        decisions.push(engine.check(&PolicyAction::McpTool { name }));
+    }
+    for flag in &args.build_flags {
+        decisions.push(engine.check(&PolicyAction::BuildFlag { flag }));
+    }
+    if let Some(path) = &args.skill_file {
+        let content = std::fs::read_to_string(path)?;
+        let spec = parse_markdown(&content)
+            .map_err(|err| MsError::InvalidSkill(format!("{}: {err}", path.display())))?;
+        let corpus = engine.lint_corpus(&ctx.db, &ctx.git)?;
+        decisions.push(engine.check(&PolicyAction::IndexSkill {
+            spec: &spec,
+            layer: parse_layer(&args.layer)?,
+            corpus: &corpus,
+        }));
+    }
+    if let Some(path) = &args.bundle {
+        let package = BundlePackage::from_bytes(&std::fs::read(path)?)?;
+        decisions.push(engine.check(&PolicyAction::InstallBundle {
+            bundle_id: &package.manifest.bundle.id,
+            signed: !package.manifest.signatures.is_empty(),
+            skip_verify: args.no_verify,
+        }));
+    }
+
+    let output = CheckOutput {
+        sources,
+        errors: engine.errors.clone(),
+        fail_closed: !engine.errors.is_empty(),
+        decisions,
+    };
+    if ctx.output_format != OutputFormat::Human {
+        return emit_json(&output);
+    }
+    print_check(&output);
+    Ok(())
+}
+
+fn print_check(output: &CheckOutput) {
+    println!("Policy layers:");
+    for source in &output.sources {
+        let state = match (&source.signature, source.present) {
+            (Some(signature), _) => format!("loaded, {signature}"),
+            (None, true) => "rejected".to_string(),
+            (None, false) => "absent".to_string(),
+        };
+        println!(
+            "  {:<8} {} ({state})",
+            source.layer.as_str(),
+            source.path.display()
+        );
+    }
+    for error in &output.errors {
+        println!("  error: {error}");
+    }
+    if output.fail_closed {
+        println!("\nPolicy is failing closed: every checked action is denied.");
+    }
+    if output.decisions.is_empty() {
+        return;
+    }
+    println!("\nDecisions:");
+    for decision in &output.decisions {
+        let verdict = if decision.allowed { "allow" } else { "deny" };
+        println!("  {verdict:<5} {} {}", decision.action, decision.subject);
+        if let Some(reason) = &decision.reason {
+            println!("        {reason}");
+        }
+        if let (Some(layer), Some(source)) = (&decision.layer, &decision.source) {
+            println!("        from {layer} policy {}", source.display());
+        }
+    }
+}
+
+fn sign(ctx: &AppContext, args: &SignArgs) -> Result<()> {
+    let path = args
+        .path
+        .clone()
+        .unwrap_or_else(|| ctx.ms_root.join(POLICY_FILE));
+    let raw = std::fs::read(&path)
+        .map_err(|err| MsError::Config(format!("read {}: {err}", path.display())))?;
+    let key_path = args
+        .key
+        .clone()
+        .or_else(|| ctx.config.security.signing.key.clone())
+        .ok_or_else(|| {
+            MsError::Config("no signing key: pass --key or set security.signing.key".to_string())
+        })?;
+    let signer = Ed25519Signer::from_openssh_file(&key_path)?;
+    let signer_name = args
+        .signer
+        .clone()
+        .or_else(|| std::env::var("USER").ok())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    let content_hash = policy_content_hash(&raw);
+    let mut signatures = read_policy_signatures(&path)?;
+    signatures.add(sign_content_hash(&content_hash, &signer, &signer_name));
+    let signature_path = policy_signature_path(&path);
+    let encoded = serde_json::to_string_pretty(&signatures)
+        .map_err(|err| MsError::Config(format!("serialize signatures: {err}")))?;
+    std::fs::write(&signature_path, encoded)?;
+
+    let output = SignOutput {
+        path,
+        signature_path,
+        content_hash,
+        key_id: signer.key_id().to_string(),
+        signer: signer_name,
+    };
+    if ctx.output_format != OutputFormat::Human {
+        return emit_json(&output);
+    }
+    println!(
+        "Signed {} ({}) as {}; wrote {}",
+        output.path.display(),
+        output.key_id,
+        output.signer,
+        output.signature_path.display()
+    );
+    Ok(())
+}
+
+fn parse_layer(raw: &str) -> Result<SkillLayer> {
+    match raw {
+        "base" => Ok(SkillLayer::Base),
+        "org" => Ok(SkillLayer::Org),
+        "project" => Ok(SkillLayer::Project),
+        "user" => Ok(SkillLayer::User),
+        other => Err(MsError::ValidationFailed(format!(
+            "unknown layer {other}: expected base, org, project or user"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct TestCli {
+        #[command(subcommand)]
+        command: PolicyCommand,
+    }
+
+    #[test]
+    fn check_accepts_hyphenated_build_flags() {
+        let cli = TestCli::parse_from([
+            "ms",
+            "check",
+            "--build-flag",
+            "--no-redact",
+            "--command",
+            "rm -rf /",
+        ]);
+        let PolicyCommand::Check(args) = cli.command else {
+            panic!("expected check");
+        };
+        assert_eq!(args.build_flags, vec!["--no-redact"]);
+        assert_eq!(args.commands, vec!["rm -rf /"]);
+    }
+
+    #[test]
+    fn parse_layer_rejects_unknown() {
+        assert_eq!(parse_layer("org").unwrap(), SkillLayer::Org);
+        assert!(parse_layer("team").is_err());
+    }
+}
@@ -2,8 +2,8 @@
 //!
 //! Tombstones are created when files are "deleted" within ms-managed directories.
 //! This command lists, purges, or restores tombstoned items. It also supports
-//! skill pruning analysis to surface low-usage, low-quality, and high-similarity
-//! candidates (proposal-first; no destructive actions).
+//! skill pruning analysis to surface low-usage, low-quality, stale (per `ms verify`)
+//! and high-similarity candidates (proposal-first; no destructive actions).
 
 use clap::{Args, Subcommand};
 use colored::Colorize;
@@ -204,6 +204,15 @@ struct ToolchainCandidate {
     missing_tools: Vec<String>,
 }
 
+#[derive(Debug, Clone, serde::Serialize)]
+struct StaleCandidate {
+    skill_id: String,
+    name: String,
+    verification_score: f32,
+    stale_references: Vec<String>,
+    verified_at: String,
+}
+
 #[derive(Debug, Clone, serde::Serialize)]
 struct DeprecateProposal {
     skill_id: String,
@@ -254,6 +263,7 @@ fn run_analyze(ctx: &AppContext, args: &AnalyzeArgs) -> Result<()> {
     let low_quality = analysis.low_quality;
     let similarity_pairs = analysis.similarity_pairs;
     let toolchain_mismatch = analysis.toolchain_mismatch;
+    let stale = analysis.stale;
 
     if ctx.output_format != OutputFormat::Human {
         let output = json!({
@@ -267,6 +277,7 @@ fn run_analyze(ctx: &AppContext, args: &AnalyzeArgs) -> Result<()> {
                 "low_quality": low_quality,
                 "high_similarity": similarity_pairs,
                 "toolchain_mismatch": toolchain_mismatch,
+                "stale": stale,
             },
         });
         println!("{}", serde_json::to_string_pretty(&output)?);
@@ -332,6 +343,20 @@ fn run_analyze(ctx: &AppContext, args: &AnalyzeArgs) -> Result<()> {
         }
     }
 
+    layout.section("Stale Commands");
+    if stale.is_empty() {
+        layout.bullet("None");
+    } else {
+        for candidate in &stale {
+            layout.bullet(&format!(
+                "{} ({}) - {}",
+                candidate.name,
+                candidate.skill_id,
+                candidate.stale_references.join("; ")
+            ));
+        }
+    }
+
     emit_human(layout);
     Ok(())
 }
@@ -371,6 +396,16 @@ fn build_proposals(ctx: &AppContext, args: &AnalyzeArgs, dry_run: bool) -> Resul
             ));
         candidate_ids.insert(candidate.skill_id.clone());
     }
+    for candidate in &analysis.stale {
+        rationale_map
+            .entry(candidate.skill_id.clone())
+            .or_default()
+            .push(format!(
+                "stale references: {}",
+                candidate.stale_references.join(", ")
+            ));
+        candidate_ids.insert(candidate.skill_id.clone());
+    }
 
     let mut deprecate = Vec::new();
     for skill in &analysis.skills {
@@ -1150,6 +1185,7 @@ struct PruneAnalysis {
     low_quality: Vec<QualityCandidate>,
     similarity_pairs: Vec<SimilarityCandidate>,
     toolchain_mismatch: Vec<ToolchainCandidate>,
+    stale: Vec<StaleCandidate>,
     split_proposals: Vec<SplitProposal>,
 }
 
@@ -1168,6 +1204,7 @@ fn analyze_candidates(ctx: &AppContext, args: &AnalyzeArgs) -> Result<PruneAnaly
     let mut low_usage = Vec::new();
     let mut low_quality = Vec::new();
     let mut toolchain_mismatch = Vec::new();
+    let mut stale = Vec::new();
 
     for skill in &skills {
         let uses = usage_since(ctx.db.as_ref(), &skill.id, &cutoff)?;
@@ -1206,6 +1243,19 @@ fn analyze_candidates(ctx: &AppContext, args: &AnalyzeArgs) -> Result<PruneAnaly
                 });
             }
         }
+
+        // Populated by `ms verify`
+        if let Some(record) = ctx.db.get_skill_verification(&skill.id)? {
+            if !record.stale.is_empty() {
+                stale.push(StaleCandidate {
+                    skill_id: skill.id.clone(),
+                    name: skill.name.clone(),
+                    verification_score: record.score,
+                    stale_references: record.stale.iter().map(|s| s.target.clone()).collect(),
+                    verified_at: record.verified_at,
+                });
+            }
+        }
     }
 
     low_usage.sort_by_key(|c| c.uses);
@@ -1221,6 +1271,12 @@ fn analyze_candidates(ctx: &AppContext, args: &AnalyzeArgs) -> Result<PruneAnaly
     let split_proposals = analyze_splits(ctx, &skills, args)?;
     toolchain_mismatch.sort_by(|a, b| a.skill_id.cmp(&b.skill_id));
     toolchain_mismatch.truncate(args.limit);
+    stale.sort_by(|a, b| {
+        a.verification_score
+            .partial_cmp(&b.verification_score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    stale.truncate(args.limit);
 
     Ok(PruneAnalysis {
         skills,
@@ -1231,6 +1287,7 @@ fn analyze_candidates(ctx: &AppContext, args: &AnalyzeArgs) -> Result<PruneAnaly
         low_quality,
         similarity_pairs,
         toolchain_mismatch,
+        stale,
         split_proposals,
     })
 }
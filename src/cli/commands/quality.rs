@@ -6,6 +6,7 @@ use serde::Serialize;
 use crate::app::AppContext;
 use crate::cli::output::OutputFormat;
 use crate::cli::output::{HumanLayout, emit_json};
+use crate::core::SkillSpec;
 use crate::core::spec_lens::parse_markdown;
 use crate::error::{MsError, Result};
 use crate::quality::{QualityContext, QualityScorer};
@@ -41,6 +42,10 @@ struct QualityBreakdownOutput {
     usage: f32,
     toolchain: f32,
     freshness: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    verification: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    outcomes: Option<f32>,
 }
 
 pub fn run(ctx: &AppContext, args: &QualityArgs) -> Result<()> {
@@ -66,23 +71,7 @@ pub fn run(ctx: &AppContext, args: &QualityArgs) -> Result<()> {
             .map_err(|err| MsError::InvalidSkill(format!("{}: {err}", skill_md.display())))?;
         let skill_id = spec.metadata.id.clone();
 
-        let (usage_count, evidence_count, modified_at) =
-            if let Ok(Some(record)) = ctx.db.get_skill(&skill_id) {
-                let usage = ctx.db.count_skill_usage(&skill_id).ok();
-                let evidence = ctx.db.count_skill_evidence(&skill_id).ok();
-                let modified = parse_modified_at(&record.modified_at);
-                (usage, evidence, modified)
-            } else {
-                (None, None, None)
-            };
-
-        let context = QualityContext {
-            usage_count,
-            evidence_count,
-            modified_at,
-            toolchain_match: true,
-        };
-
+        let context = quality_context(ctx, &spec)?;
         let score = scorer.score_spec(&spec, &context);
 
         if args.update {
@@ -100,6 +89,8 @@ pub fn run(ctx: &AppContext, args: &QualityArgs) -> Result<()> {
                 usage: score.breakdown.usage,
                 toolchain: score.breakdown.toolchain,
                 freshness: score.breakdown.freshness,
+                verification: score.breakdown.verification,
+                outcomes: score.breakdown.outcomes,
             },
             issues: score
                 .issues
@@ -130,8 +121,14 @@ pub fn run(ctx: &AppContext, args: &QualityArgs) -> Result<()> {
                 .kv("Evidence", &format!("{:.2}", output.breakdown.evidence))
                 .kv("Usage", &format!("{:.2}", output.breakdown.usage))
                 .kv("Toolchain", &format!("{:.2}", output.breakdown.toolchain))
-                .kv("Freshness", &format!("{:.2}", output.breakdown.freshness))
-                .blank();
+                .kv("Freshness", &format!("{:.2}", output.breakdown.freshness));
+            if let Some(verification) = output.breakdown.verification {
+                layout.kv("Verification", &format!("{verification:.2}"));
+            }
+            if let Some(outcomes) = output.breakdown.outcomes {
+                layout.kv("Outcomes", &format!("{outcomes:.2}"));
+            }
+            layout.blank();
             if !output.issues.is_empty() {
                 layout.bullet("Issues:");
                 for issue in &output.issues {
@@ -152,6 +149,54 @@ pub fn run(ctx: &AppContext, args: &QualityArgs) -> Result<()> {
     }
 }
 
+/// Build the scoring context for `spec` from stored usage, evidence,
+/// outcome and verification data.
+pub(crate) fn quality_context(ctx: &AppContext, spec: &SkillSpec) -> Result<QualityContext> {
+    let skill_id = &spec.metadata.id;
+    let (usage_count, evidence_count, modified_at) =
+        if let Ok(Some(record)) = ctx.db.get_skill(skill_id) {
+            let usage = ctx.db.count_skill_usage(skill_id).ok();
+            let evidence = ctx.db.count_skill_evidence(skill_id).ok();
+            let modified = parse_modified_at(&record.modified_at);
+            (usage, evidence, modified)
+        } else {
+            (None, None, None)
+        };
+
+    let mut context = QualityContext {
+        usage_count,
+        evidence_count,
+        modified_at,
+        toolchain_match: true,
+        outcome_rate: ctx
+            .db
+            .skill_outcome_rate(skill_id)
+            .ok()
+            .flatten()
+            .map(|(rate, _)| rate),
+        ..QualityContext::default()
+    };
+    let spec_hash = crate::cli::commands::index::compute_spec_hash(spec)?;
+    apply_verification(ctx, skill_id, &spec_hash, &mut context);
+    Ok(context)
+}
+
+/// Fold a cached `ms verify` result into `context` when it was computed for
+/// the current content of the skill.
+pub(crate) fn apply_verification(
+    ctx: &AppContext,
+    skill_id: &str,
+    spec_hash: &str,
+    context: &mut QualityContext,
+) {
+    if let Ok(Some(record)) = ctx.db.get_skill_verification(skill_id) {
+        if record.content_hash == spec_hash {
+            context.verification_score = Some(record.score);
+            context.stale_references = record.stale.len() as u64;
+        }
+    }
+}
+
 fn parse_modified_at(raw: &str) -> Option<chrono::DateTime<chrono::Utc>> {
     if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(raw) {
         return Some(dt.with_timezone(&chrono::Utc));
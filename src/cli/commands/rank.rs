@@ -0,0 +1,270 @@
+//! ms rank - Learned search re-ranker
+//!
+//! Trains a linear LambdaRank model from recorded search impressions and the
+//! loads and feedback that followed them. Each training run saves a new
+//! model version; the active version re-ranks `ms search` results after RRF.
+
+use clap::{Args, Subcommand};
+
+use crate::app::AppContext;
+use crate::cli::commands::search::rank_store;
+use crate::cli::output::{HumanLayout, OutputFormat, emit_json};
+use crate::error::Result;
+use crate::search::RankModel;
+use crate::search::rerank::{LabelWindows, TrainConfig, label_impressions, train};
+
+#[derive(Args, Debug)]
+pub struct RankArgs {
+    #[command(subcommand)]
+    pub command: RankCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum RankCommand {
+    /// Train a new model version from search impressions and feedback
+    Train(TrainArgs),
+
+    /// Show saved model versions and which one is active
+    Status,
+
+    /// Activate a saved model version
+    Activate(ActivateArgs),
+
+    /// Stop applying the learned model (plain RRF ranking)
+    Deactivate,
+}
+
+#[derive(Args, Debug)]
+pub struct TrainArgs {
+    /// Training epochs
+    #[arg(long, default_value = "50")]
+    pub epochs: usize,
+
+    /// Gradient step size
+    #[arg(long, default_value = "0.05")]
+    pub learning_rate: f32,
+
+    /// Minimum searches with a loaded result required to train
+    #[arg(long, default_value = "10")]
+    pub min_queries: usize,
+
+    /// A load within this many minutes after a search counts as a click
+    #[arg(long, default_value = "30")]
+    pub click_window: i64,
+
+    /// Activate the new version even if it does not beat RRF on held-out searches
+    #[arg(long)]
+    pub force: bool,
+
+    /// Save the new version without activating it
+    #[arg(long, conflicts_with = "force")]
+    pub no_activate: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct ActivateArgs {
+    /// Model version to activate
+    pub version: u32,
+}
+
+pub fn run(ctx: &AppContext, args: &RankArgs) -> Result<()> {
+    match &args.command {
+        RankCommand::Train(args) => run_train(ctx, args),
+        RankCommand::Status => run_status(ctx),
+        RankCommand::Activate(args) => run_activate(ctx, args),
+        RankCommand::Deactivate => run_deactivate(ctx),
+    }
+}
+
+fn run_train(ctx: &AppContext, args: &TrainArgs) -> Result<()> {
+    let windows = LabelWindows {
+        click: chrono::Duration::minutes(args.click_window.max(1)),
+        ..LabelWindows::default()
+    };
+    let impressions = ctx.db.list_search_impressions()?;
+    let usage = ctx.db.list_skill_usage_signals()?;
+    let feedback = ctx.db.list_skill_feedback(None, usize::MAX >> 1, 0)?;
+    let queries = label_impressions(&impressions, &usage, &feedback, windows);
+
+    let config = TrainConfig {
+        epochs: args.epochs,
+        learning_rate: args.learning_rate,
+        min_queries: args.min_queries,
+        ..TrainConfig::default()
+    };
+    let mut model = train(&queries, &config)?;
+
+    let store = rank_store(ctx);
+    let version = store.save(&mut model)?;
+    let activated = !args.no_activate && (args.force || model.metrics.improves());
+    if activated {
+        store.activate(version)?;
+    }
+
+    if ctx.output_format != OutputFormat::Human {
+        return emit_json(&serde_json::json!({
+            "status": "ok",
+            "version": version,
+            "activated": activated,
+            "searches": queries.len(),
+            "model": model,
+        }));
+    }
+
+    let mut layout = HumanLayout::new();
+    layout
+        .title("Rank Model Trained")
+        .kv("Version", &format!("v{version}"))
+        .kv("Searches logged", &queries.len().to_string());
+    push_metrics(&mut layout, &model);
+    layout.blank().kv(
+        "Active",
+        if activated {
+            "yes"
+        } else if args.no_activate {
+            "no (--no-activate)"
+        } else {
+            "no (does not beat RRF; use --force to activate anyway)"
+        },
+    );
+    layout.blank().section("Weights");
+    for (feature, weight) in model.features.iter().zip(&model.weights) {
+        layout.kv(feature, &format!("{weight:+.3}"));
+    }
+    crate::cli::output::emit_human(layout);
+    Ok(())
+}
+
+fn run_status(ctx: &AppContext) -> Result<()> {
+    let store = rank_store(ctx);
+    let models = store.list()?;
+    let active = store.active_version()?;
+
+    if ctx.output_format != OutputFormat::Human {
+        let versions: Vec<_> = models
+            .iter()
+            .map(|m| {
+                serde_json::json!({
+                    "version": m.version,
+                    "algorithm": m.algorithm,
+                    "trained_at": m.trained_at,
+                    "metrics": m.metrics,
+                    "active": Some(m.version) == active,
+                })
+            })
+            .collect();
+        return emit_json(&serde_json::json!({
+            "status": "ok",
+            "enabled": ctx.config.search.learned_rerank,
+            "active": active,
+            "models": versions,
+        }));
+    }
+
+    let mut layout = HumanLayout::new();
+    layout
+        .title("Learned Re-ranker")
+        .kv("Enabled", &ctx.config.search.learned_rerank.to_string())
+        .kv(
+            "Active",
+            &active.map_or_else(|| "none (RRF only)".to_string(), |v| format!("v{v}")),
+        );
+    if models.is_empty() {
+        layout
+            .blank()
+            .push_line("No models trained yet. Run: ms rank train");
+    }
+    for model in &models {
+        layout.blank().section(&format!(
+            "v{}{}",
+            model.version,
+            if Some(model.version) == active {
+                " (active)"
+            } else {
+                ""
+            }
+        ));
+        layout.kv("Trained", &model.trained_at);
+        push_metrics(&mut layout, model);
+    }
+    crate::cli::output::emit_human(layout);
+    Ok(())
+}
+
+fn run_activate(ctx: &AppContext, args: &ActivateArgs) -> Result<()> {
+    rank_store(ctx).activate(args.version)?;
+    if ctx.output_format != OutputFormat::Human {
+        return emit_json(&serde_json::json!({
+            "status": "ok",
+            "active": args.version,
+        }));
+    }
+    let mut layout = HumanLayout::new();
+    layout
+        .title("Rank Model Activated")
+        .kv("Active", &format!("v{}", args.version));
+    crate::cli::output::emit_human(layout);
+    Ok(())
+}
+
+fn run_deactivate(ctx: &AppContext) -> Result<()> {
+    let removed = rank_store(ctx).deactivate()?;
+    if ctx.output_format != OutputFormat::Human {
+        return emit_json(&serde_json::json!({
+            "status": "ok",
+            "deactivated": removed,
+        }));
+    }
+    let mut layout = HumanLayout::new();
+    layout
+        .title("Rank Model Deactivated")
+        .kv("Active", "none (RRF only)");
+    crate::cli::output::emit_human(layout);
+    Ok(())
+}
+
+fn push_metrics(layout: &mut HumanLayout, model: &RankModel) {
+    let metrics = &model.metrics;
+    layout
+        .kv("Training searches", &metrics.queries.to_string())
+        .kv("Held-out searches", &metrics.holdout_queries.to_string())
+        .kv("Preference pairs", &metrics.pairs.to_string())
+        .kv("NDCG@10 (RRF)", &format!("{:.3}", metrics.baseline_ndcg))
+        .kv("NDCG@10 (model)", &format!("{:.3}", metrics.model_ndcg));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct TestCli {
+        #[command(flatten)]
+        args: RankArgs,
+    }
+
+    #[test]
+    fn parse_train_defaults() {
+        let cli = TestCli::parse_from(["test", "train"]);
+        match cli.args.command {
+            RankCommand::Train(args) => {
+                assert_eq!(args.epochs, 50);
+                assert_eq!(args.min_queries, 10);
+                assert_eq!(args.click_window, 30);
+                assert!(!args.force);
+            }
+            _ => panic!("expected train"),
+        }
+    }
+
+    #[test]
+    fn parse_activate_version() {
+        let cli = TestCli::parse_from(["test", "activate", "3"]);
+        assert!(matches!(
+            cli.args.command,
+            RankCommand::Activate(ActivateArgs { version: 3 })
+        ));
+        assert!(TestCli::try_parse_from(["test", "train", "--force", "--no-activate"]).is_err());
+    }
+}
@@ -1,4 +1,4 @@
-//! Safety command - DCG safety gate status, logs, and command checking.
+//! Safety command - safety gate status, logs, and command checking.
 
 use clap::{Args, Subcommand};
 use colored::Colorize;
@@ -8,7 +8,7 @@ use crate::app::AppContext;
 use crate::cli::output::OutputFormat;
 use crate::core::safety::SafetyTier;
 use crate::error::Result;
-use crate::security::SafetyGate;
+use crate::security::{DecisionSource, SafetyGate};
 
 #[derive(Args, Debug)]
 pub struct SafetyArgs {
@@ -18,7 +18,7 @@ pub struct SafetyArgs {
 
 #[derive(Subcommand, Debug)]
 pub enum SafetyCommand {
-    /// Show DCG safety gate status
+    /// Show safety gate status
     Status,
 
     /// Show recent safety events log
@@ -65,13 +65,17 @@ pub fn run(ctx: &AppContext, args: &SafetyArgs) -> Result<()> {
     }
 }
 
-/// Show DCG safety gate status.
+/// Show safety gate status.
 fn run_status(ctx: &AppContext) -> Result<()> {
     let gate = SafetyGate::from_context(ctx);
     let status = gate.status();
 
     if ctx.output_format != OutputFormat::Human {
         let output = json!({
+            "evaluator": status.evaluator.as_str(),
+            "active_evaluator": status.active.as_str(),
+            "native_packs": status.native_packs,
+            "rules_error": status.rules_error,
             "dcg_available": status.dcg_version.is_some(),
             "dcg_version": status.dcg_version,
             "dcg_bin": status.dcg_bin.display().to_string(),
@@ -84,12 +88,30 @@ fn run_status(ctx: &AppContext) -> Result<()> {
         println!("{}", "─".repeat(30));
         println!();
 
+        println!(
+            "  Evaluator: {} (active: {})",
+            status.evaluator.as_str(),
+            status.active.as_str().cyan()
+        );
+        println!(
+            "    Native packs: {}",
+            status.native_packs.join(", ").dimmed()
+        );
+        if let Some(err) = &status.rules_error {
+            println!("    {} Custom rules not loaded: {err}", "!".yellow());
+        }
+        println!();
+
         if let Some(version) = &status.dcg_version {
             println!("  {} DCG Available", "✓".green());
             println!("    Version: {}", version.cyan());
         } else {
             println!("  {} DCG Not Available", "✗".red());
-            println!("    Commands will be allowed with warnings");
+            if status.active == DecisionSource::Native {
+                println!("    Commands are checked by the native evaluator");
+            } else {
+                println!("    Commands will be blocked until DCG is installed");
+            }
         }
         println!("    Binary: {}", status.dcg_bin.display());
 
@@ -195,47 +217,23 @@ fn run_log(ctx: &AppContext, args: &LogArgs) -> Result<()> {
 fn run_check(ctx: &AppContext, args: &CheckArgs) -> Result<()> {
     let gate = SafetyGate::from_context(ctx);
 
-    // Get DCG decision without logging
-    let status = gate.status();
-    if status.dcg_version.is_none() {
+    // Evaluate without logging; enforcement is left to the caller
+    let evaluation = gate.evaluate(&args.command);
+    if evaluation.unavailable {
         if ctx.output_format != OutputFormat::Human {
             let output = json!({
                 "error": true,
                 "code": "dcg_unavailable",
-                "message": "DCG is not available",
+                "message": evaluation.decision.reason,
             });
             println!("{}", serde_json::to_string_pretty(&output)?);
         } else {
-            println!("{} DCG is not available", "!".yellow());
-            println!("  Configure dcg_bin in your ms config");
+            println!("{} {}", "!".yellow(), evaluation.decision.reason);
+            println!("  Configure dcg_bin or set safety.evaluator = \"native\"");
         }
         return Ok(());
     }
-
-    // Use the core safety module directly to avoid logging on dry run
-    use crate::core::safety::DcgGuard;
-    let guard = DcgGuard::new(
-        ctx.config.safety.dcg_bin.clone(),
-        ctx.config.safety.dcg_packs.clone(),
-        ctx.config.safety.dcg_explain_format.clone(),
-    );
-
-    let decision = match guard.evaluate_command(&args.command) {
-        Ok(d) => d,
-        Err(e) => {
-            if ctx.output_format != OutputFormat::Human {
-                let output = json!({
-                    "error": true,
-                    "code": "dcg_error",
-                    "message": e.to_string(),
-                });
-                println!("{}", serde_json::to_string_pretty(&output)?);
-            } else {
-                println!("{} DCG error: {}", "✗".red(), e);
-            }
-            return Ok(());
-        }
-    };
+    let decision = evaluation.decision;
 
     // Determine if approval would be required
     let approval_required = !decision.allowed
@@ -251,6 +249,8 @@ fn run_check(ctx: &AppContext, args: &CheckArgs) -> Result<()> {
             "remediation": decision.remediation,
             "rule_id": decision.rule_id,
             "pack": decision.pack,
+            "evaluator": evaluation.source.as_str(),
+            "explanation": decision.explanation,
             "approval_required": approval_required,
             "approval_hint": if approval_required {
                 Some(format!("MS_APPROVE_COMMAND=\"{}\"", args.command))
@@ -282,6 +282,10 @@ fn run_check(ctx: &AppContext, args: &CheckArgs) -> Result<()> {
         if let Some(pack) = &decision.pack {
             println!("    Pack: {}", pack.dimmed());
         }
+        println!("    Evaluator: {}", evaluation.source.as_str().dimmed());
+        for line in &decision.explanation {
+            println!("    - {line}");
+        }
 
         if !decision.allowed {
             if let Some(remediation) = &decision.remediation {
@@ -1,22 +1,42 @@
 //! ms search - Search for skills
 //!
 //! Provides hybrid search combining BM25 full-text and semantic vector
-//! similarity via RRF fusion.
+//! similarity via RRF fusion. Queries accept the structured syntax from
+//! `search::query` (`tag:rust -tag:legacy author:alice quality:>0.7 ...`);
+//! filters are pushed into both backends. Fused results are re-ranked by the
+//! active learned model (`ms rank train`) and recorded as impressions.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
 
 use clap::Args;
 use console::style;
 
 use crate::app::AppContext;
+use crate::cli::commands::load::{
+    CliPackMode, convert_to_scoring_context, skill_record_to_metadata,
+};
 use crate::cli::formatters::SearchResults;
 use crate::cli::output::{Formattable, OutputFormat};
+use crate::context::collector::{ContextCollector, ContextCollectorConfig};
+use crate::context::scoring::RelevanceScorer;
+use crate::core::disclosure::render_packed_body;
+use crate::core::packing::{ConstrainedPacker, PackConstraints, PackError};
+use crate::core::skill::SliceType;
 use crate::error::{MsError, Result};
+use crate::search::rerank::{FeatureContribution, LabelWindows};
+use crate::search::slices::{parse_slice_type, slice_type_name};
 use crate::search::{
-    RrfConfig, SearchFilters, SearchLayer, VectorIndex, build_embedder, fuse_simple,
+    Embedder, HybridResult, RankCandidate, RankFeatures, RankModelStore, RankSignals, RrfConfig,
+    SearchFilters, SearchLayer, SearchQuery, SkillFacets, SliceHit, SnippetExtractor, VectorIndex,
+    build_embedder, extract_features, fuse_results, packable_slices, slice_key,
 };
+use crate::storage::sqlite::{SearchImpressionRecord, SkillRecord};
 
 #[derive(Args, Debug)]
 pub struct SearchArgs {
-    /// Search query
+    /// Search query; supports tag:, author:, layer:, platform:, requires:,
+    /// updated:>DATE, quality:>N filters (prefix - to exclude) and "phrases"
     pub query: String,
 
     /// Maximum number of results
@@ -43,9 +63,33 @@ pub struct SearchArgs {
     #[arg(long, default_value = "hybrid")]
     pub search_type: String,
 
-    /// Show snippets of matching content
+    /// Show the best-matching section of each result with highlighted terms
     #[arg(long)]
     pub snippets: bool,
+
+    /// Return the best slices (rules, pitfalls, commands, examples) from any skill
+    #[arg(long)]
+    pub slices: bool,
+
+    /// Only return these slice types (comma-separated, e.g. rule,pitfall)
+    #[arg(long, requires = "slices")]
+    pub slice_types: Option<String>,
+
+    /// Pack the matched slices into a token budget
+    #[arg(long, requires = "slices")]
+    pub pack: Option<usize>,
+
+    /// Pack mode when using --pack
+    #[arg(long, value_enum, default_value = "balanced")]
+    pub mode: CliPackMode,
+
+    /// Max slices per coverage group when using --pack
+    #[arg(long, default_value = "2")]
+    pub max_per_group: usize,
+
+    /// Show how the learned re-ranker scored each result
+    #[arg(long)]
+    pub explain_rank: bool,
 }
 
 pub fn run(ctx: &AppContext, args: &SearchArgs) -> Result<()> {
@@ -88,185 +132,529 @@ pub fn run(ctx: &AppContext, args: &SearchArgs) -> Result<()> {
 
     filters = filters.include_deprecated(args.include_deprecated);
 
+    // Facet filters written in the query itself (tag:rust -tag:legacy ...)
+    let mut query = SearchQuery::parse(&args.query)?;
+    query.filters = filters.merge(query.filters);
+
+    if args.slices {
+        return search_slices(ctx, args, &query);
+    }
+
     // Execute search
     match args.search_type.as_str() {
-        "bm25" => search_bm25(ctx, args, &filters),
+        "bm25" => search_bm25(ctx, args, &query),
         "semantic" => {
             if !ctx.config.search.use_embeddings {
                 return Err(MsError::Config(
                     "semantic search disabled (search.use_embeddings=false)".to_string(),
                 ));
             }
-            search_semantic(ctx, args, &filters)
+            if query.is_filter_only() {
+                return search_bm25(ctx, args, &query);
+            }
+            search_semantic(ctx, args, &query)
         }
         "hybrid" | _ => {
-            if !ctx.config.search.use_embeddings {
-                return search_bm25(ctx, args, &filters);
+            if !ctx.config.search.use_embeddings || query.is_filter_only() {
+                return search_bm25(ctx, args, &query);
             }
-            search_hybrid(ctx, args, &filters)
+            search_hybrid(ctx, args, &query)
         }
     }
 }
 
-fn search_hybrid(ctx: &AppContext, args: &SearchArgs, filters: &SearchFilters) -> Result<()> {
-    // Fetch enough results from both systems for fusion
-    // Increase limit to allow for filtering
-    let fetch_limit = args.limit * 50;
+fn search_hybrid(ctx: &AppContext, args: &SearchArgs, query: &SearchQuery) -> Result<()> {
+    // Filters are pushed into both backends, so a modest over-fetch is
+    // enough for fusion.
+    let fetch_limit = args.limit * 5;
 
-    // BM25 search using SQLite FTS
-    let bm25_candidates = ctx.db.search_fts(&args.query, fetch_limit)?;
+    // BM25 search using Tantivy, filtered in the index
+    let bm25_hits = ctx
+        .search
+        .search_filtered(&query.text, &query.filters, fetch_limit, false)?;
+    let bm25_results: Vec<(String, f32)> = bm25_hits
+        .iter()
+        .map(|hit| (hit.skill_id.clone(), hit.score))
+        .collect();
 
-    // Build semantic search using embeddings
+    // Semantic search restricted to skills that pass the filters
     let embedder = build_embedder(&ctx.config.search)?;
-    let query_embedding = embedder.embed(&args.query);
+    let semantic_results = filtered_vector_search(ctx, embedder.as_ref(), query, fetch_limit)?;
 
-    // Load embeddings from database
-    let mut vector_index = VectorIndex::new(embedder.dims());
-    let all_embeddings = ctx.db.get_all_embeddings()?;
+    // RRF fusion
+    let config = RrfConfig::with_weights(
+        ctx.config.search.bm25_weight,
+        ctx.config.search.semantic_weight,
+    );
+    let fused = fuse_results(&bm25_results, &semantic_results, &config);
+    let ranked = rank_results(ctx, args, fused)?;
 
-    for (id, embedding) in all_embeddings {
-        let _ = vector_index.insert(id, embedding);
-    }
+    display_results(ctx, &ranked, args, query, "hybrid", Some(embedder.as_ref()))
+}
 
-    // Semantic search
-    let semantic_results = vector_index.search(&query_embedding, fetch_limit);
+fn search_bm25(ctx: &AppContext, args: &SearchArgs, query: &SearchQuery) -> Result<()> {
+    let hits = ctx.search.search_filtered(
+        &query.text,
+        &query.filters,
+        candidate_limit(ctx, args),
+        false,
+    )?;
+    let scored: Vec<(String, f32)> = hits
+        .into_iter()
+        .map(|hit| (hit.skill_id, hit.score))
+        .collect();
 
-    // Convert to (id, score) format
-    let bm25_results: Vec<(String, f32)> = bm25_candidates
+    let ranked = rank_results(ctx, args, single_list(&scored, true))?;
+    display_results(ctx, &ranked, args, query, "bm25", None)
+}
+
+fn search_semantic(ctx: &AppContext, args: &SearchArgs, query: &SearchQuery) -> Result<()> {
+    let embedder = build_embedder(&ctx.config.search)?;
+    let scored = filtered_vector_search(ctx, embedder.as_ref(), query, candidate_limit(ctx, args))?;
+
+    let ranked = rank_results(ctx, args, single_list(&scored, false))?;
+    display_results(
+        ctx,
+        &ranked,
+        args,
+        query,
+        "semantic",
+        Some(embedder.as_ref()),
+    )
+}
+
+/// Single-backend searches over-fetch only when a learned re-ranker may
+/// reorder the candidates.
+fn candidate_limit(ctx: &AppContext, args: &SearchArgs) -> usize {
+    if ctx.config.search.learned_rerank {
+        args.limit * 5
+    } else {
+        args.limit
+    }
+}
+
+/// Wrap one backend's ranked list as fusion results
+fn single_list(scored: &[(String, f32)], lexical: bool) -> Vec<HybridResult> {
+    scored
         .iter()
         .enumerate()
-        .map(|(i, c)| (c.id.clone(), 1.0 / (i + 1) as f32)) // Convert rank to pseudo-score
+        .map(|(idx, (skill_id, score))| HybridResult {
+            skill_id: skill_id.clone(),
+            score: *score,
+            bm25_rank: lexical.then_some(idx + 1),
+            semantic_rank: (!lexical).then_some(idx + 1),
+            bm25_score: lexical.then_some(*score),
+            semantic_score: (!lexical).then_some(*score),
+        })
+        .collect()
+}
+
+/// Results after learned re-ranking
+struct RankedResults {
+    results: Vec<(SkillRecord, f32)>,
+    explanations: Vec<Vec<FeatureContribution>>,
+    model_version: Option<u32>,
+}
+
+/// Store of learned re-ranker versions for this registry
+pub(crate) fn rank_store(ctx: &AppContext) -> RankModelStore {
+    RankModelStore::new(ctx.ms_root.join("rank"))
+}
+
+/// Re-rank fused results with the active learned model (if any) and record
+/// what was shown, so `ms rank train` can learn from later loads and feedback.
+fn rank_results(
+    ctx: &AppContext,
+    args: &SearchArgs,
+    fused: Vec<HybridResult>,
+) -> Result<RankedResults> {
+    let mut candidates = Vec::new();
+    for result in fused {
+        if let Some(skill) = ctx.db.get_skill(&result.skill_id)? {
+            candidates.push((result, skill));
+        }
+    }
+
+    if !ctx.config.search.learned_rerank {
+        return Ok(RankedResults {
+            results: candidates
+                .into_iter()
+                .take(args.limit)
+                .map(|(result, skill)| (skill, result.score))
+                .collect(),
+            explanations: Vec::new(),
+            model_version: None,
+        });
+    }
+
+    let signals = RankSignals::load(&ctx.db, LabelWindows::default().click).unwrap_or_else(|err| {
+        tracing::debug!("rank signals unavailable: {err}");
+        RankSignals::default()
+    });
+    let context = context_scores(&candidates);
+    let rank_candidates: Vec<RankCandidate<'_>> = candidates
+        .iter()
+        .zip(&context)
+        .map(|((result, skill), &context)| RankCandidate {
+            result,
+            skill,
+            context,
+        })
+        .collect();
+    let features = extract_features(&rank_candidates, &signals, chrono::Utc::now());
+
+    let model = rank_store(ctx).active().unwrap_or_else(|err| {
+        tracing::warn!("ignoring learned re-ranker: {err}");
+        None
+    });
+    let shown: Vec<usize> = model
+        .as_ref()
+        .map_or_else(|| (0..features.len()).collect(), |m| m.rank(&features))
+        .into_iter()
+        .take(args.limit)
         .collect();
 
-    // RRF fusion
-    let config = RrfConfig::with_weights(
-        ctx.config.search.bm25_weight,
-        ctx.config.search.semantic_weight,
+    let model_version = model.as_ref().map(|m| m.version);
+    record_impressions(
+        ctx,
+        &args.query,
+        &candidates,
+        &features,
+        &shown,
+        model_version,
     );
-    let fused = fuse_simple(&bm25_results, &semantic_results, &config);
-
-    // Fetch full skill records and apply filters
-    let mut results = Vec::new();
-    for (skill_id, score) in fused {
-        // Optimization: Check metadata first
-        let candidate = if let Some(c) = bm25_candidates.iter().find(|c| c.id == skill_id) {
-            Some(c.clone())
-        } else {
-            ctx.db.get_skill_candidate(&skill_id)?
-        };
-
-        if let Some(candidate) = candidate {
-            let skill_tags = parse_tags_from_metadata(&candidate.metadata_json);
-
-            // Apply filters on metadata
-            if filters.matches(
-                &skill_tags,
-                &candidate.source_layer,
-                candidate.quality_score as f32,
-                candidate.is_deprecated,
-            ) {
-                // Only load full skill if it passes filters
-                if let Some(skill) = ctx.db.get_skill(&skill_id)? {
-                    results.push((skill, score));
-                }
-            }
+
+    let mut ranked = RankedResults {
+        results: Vec::with_capacity(shown.len()),
+        explanations: Vec::new(),
+        model_version,
+    };
+    for &idx in &shown {
+        let (result, skill) = &candidates[idx];
+        let score = model
+            .as_ref()
+            .map_or(result.score, |m| m.score(&features[idx]));
+        ranked.results.push((skill.clone(), score));
+        if let (Some(model), true) = (&model, args.explain_rank) {
+            ranked.explanations.push(model.explain(&features[idx]));
         }
+    }
+    Ok(ranked)
+}
+
+/// Context relevance of each candidate to the current working directory
+fn context_scores(candidates: &[(HybridResult, SkillRecord)]) -> Vec<f32> {
+    let working_dir = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let collector = ContextCollector::new(ContextCollectorConfig::default());
+    let Ok(collected) = collector.collect(&working_dir) else {
+        return vec![0.0; candidates.len()];
+    };
+    let working = convert_to_scoring_context(&collected);
+    let scorer = RelevanceScorer::default();
+    candidates
+        .iter()
+        .map(|(_, skill)| scorer.score(&skill_record_to_metadata(skill), &working))
+        .collect()
+}
 
-        if results.len() >= args.limit {
-            break;
+/// Impressions are best-effort: a failure never fails the search.
+fn record_impressions(
+    ctx: &AppContext,
+    query: &str,
+    candidates: &[(HybridResult, SkillRecord)],
+    features: &[RankFeatures],
+    shown: &[usize],
+    model_version: Option<u32>,
+) {
+    let search_id = uuid::Uuid::new_v4().to_string();
+    let created_at = chrono::Utc::now().to_rfc3339();
+    let impressions: Vec<SearchImpressionRecord> = shown
+        .iter()
+        .enumerate()
+        .map(|(position, &idx)| SearchImpressionRecord {
+            id: uuid::Uuid::new_v4().to_string(),
+            search_id: search_id.clone(),
+            query: query.to_string(),
+            skill_id: candidates[idx].1.id.clone(),
+            position,
+            features_json: serde_json::to_string(&features[idx]).unwrap_or_default(),
+            model_version,
+            created_at: created_at.clone(),
+        })
+        .collect();
+    if let Err(err) = ctx.db.record_search_impressions(&impressions) {
+        tracing::debug!("failed to record search impressions: {err}");
+    }
+}
+
+/// Ids of skills that pass the filters (the vector search candidate set)
+fn candidate_set(ctx: &AppContext, filters: &SearchFilters) -> Result<HashSet<String>> {
+    Ok(ctx
+        .db
+        .list_search_candidates()?
+        .into_iter()
+        .filter(|candidate| filters.matches_facets(&SkillFacets::from_candidate(candidate)))
+        .map(|candidate| candidate.id)
+        .collect())
+}
+
+/// Vector search over only the embeddings of skills that pass the filters
+fn filtered_vector_search(
+    ctx: &AppContext,
+    embedder: &dyn Embedder,
+    query: &SearchQuery,
+    limit: usize,
+) -> Result<Vec<(String, f32)>> {
+    let allowed = candidate_set(ctx, &query.filters)?;
+    let mut vector_index = VectorIndex::new(embedder.dims());
+    for (id, embedding) in ctx.db.get_all_embeddings()? {
+        if allowed.contains(&id) {
+            let _ = vector_index.insert(id, embedding);
         }
     }
 
-    display_results(ctx, &results, args, "hybrid")
+    let query_embedding = embedder.embed(&query.plain_text());
+    Ok(vector_index.search(&query_embedding, limit))
 }
 
-fn search_bm25(ctx: &AppContext, args: &SearchArgs, filters: &SearchFilters) -> Result<()> {
-    // Increase limit to allow for filtering
-    let candidates = ctx.db.search_fts(&args.query, args.limit * 50)?;
-
-    let mut results = Vec::new();
-    for (i, candidate) in candidates.iter().enumerate() {
-        let skill_tags = parse_tags_from_metadata(&candidate.metadata_json);
-
-        if filters.matches(
-            &skill_tags,
-            &candidate.source_layer,
-            candidate.quality_score as f32,
-            candidate.is_deprecated,
-        ) {
-            if let Some(skill) = ctx.db.get_skill(&candidate.id)? {
-                let score = 1.0 / (i + 1) as f32;
-                results.push((skill, score));
+fn search_slices(ctx: &AppContext, args: &SearchArgs, query: &SearchQuery) -> Result<()> {
+    let types = match &args.slice_types {
+        Some(raw) => parse_slice_types(raw)?,
+        None => Vec::new(),
+    };
+    let fetch_limit = args.pack.map_or(args.limit, |_| args.limit.max(50));
+    // Skill-level filters restrict which skills' slices may be returned
+    let allowed = candidate_set(ctx, &query.filters)?;
+
+    let use_semantic = ctx.config.search.use_embeddings && args.search_type != "bm25";
+    let (mut hits, search_type) = if use_semantic {
+        let embedder = build_embedder(&ctx.config.search)?;
+        let query_embedding = embedder.embed(&query.plain_text());
+        let mut vector_index = VectorIndex::new(embedder.dims());
+        for (skill_id, slice_id, embedding) in ctx.db.get_all_slice_embeddings()? {
+            if allowed.contains(&skill_id) {
+                let _ = vector_index.insert(slice_key(&skill_id, &slice_id), embedding);
             }
         }
-
-        if results.len() >= args.limit {
-            break;
+        let semantic = vector_index.search(&query_embedding, fetch_limit * 5);
+        if args.search_type == "semantic" {
+            let mut hits = Vec::new();
+            for (rank, (key, score)) in semantic.into_iter().enumerate() {
+                let Some(mut hit) = ctx.slices.get(&key)? else {
+                    continue;
+                };
+                if !types.is_empty() && !types.contains(&hit.slice.slice_type) {
+                    continue;
+                }
+                hit.score = score;
+                hit.semantic_rank = Some(rank + 1);
+                hits.push(hit);
+                if hits.len() == fetch_limit {
+                    break;
+                }
+            }
+            (hits, "semantic")
+        } else {
+            let hits = ctx.slices.search_hybrid(
+                &query.text,
+                &semantic,
+                &types,
+                &RrfConfig::default(),
+                fetch_limit * 5,
+            )?;
+            (hits, "hybrid")
         }
+    } else {
+        (
+            ctx.slices.search(&query.text, &types, fetch_limit * 5)?,
+            "bm25",
+        )
+    };
+    hits.retain(|hit| allowed.contains(&hit.skill_id));
+    hits.truncate(fetch_limit);
+
+    match args.pack {
+        Some(budget) => display_packed_slices(ctx, args, &hits, budget, search_type),
+        None => display_slices(ctx, args, &hits, search_type),
     }
-
-    display_results(ctx, &results, args, "bm25")
 }
 
-fn search_semantic(ctx: &AppContext, args: &SearchArgs, filters: &SearchFilters) -> Result<()> {
-    let embedder = build_embedder(&ctx.config.search)?;
-    let query_embedding = embedder.embed(&args.query);
+fn parse_slice_types(raw: &str) -> Result<Vec<SliceType>> {
+    raw.split(',')
+        .filter(|part| !part.trim().is_empty())
+        .map(|part| {
+            parse_slice_type(part).ok_or_else(|| {
+                MsError::Config(format!(
+                    "invalid slice type '{}'. Valid: rule, command, example, checklist, pitfall, overview, reference, policy",
+                    part.trim()
+                ))
+            })
+        })
+        .collect()
+}
 
-    // Load embeddings
-    let mut vector_index = VectorIndex::new(embedder.dims());
-    let all_embeddings = ctx.db.get_all_embeddings()?;
+fn slice_json(hit: &SliceHit) -> serde_json::Value {
+    serde_json::json!({
+        "key": hit.key(),
+        "skill_id": hit.skill_id,
+        "slice_id": hit.slice.id,
+        "slice_type": slice_type_name(hit.slice.slice_type),
+        "section_title": hit.slice.section_title,
+        "score": hit.score,
+        "bm25_rank": hit.bm25_rank,
+        "semantic_rank": hit.semantic_rank,
+        "token_estimate": hit.slice.token_estimate,
+        "content": hit.slice.content,
+    })
+}
 
-    for (id, embedding) in all_embeddings {
-        let _ = vector_index.insert(id, embedding);
+fn display_slices(
+    ctx: &AppContext,
+    args: &SearchArgs,
+    hits: &[SliceHit],
+    search_type: &str,
+) -> Result<()> {
+    if ctx.output_format != OutputFormat::Human {
+        let results: Vec<serde_json::Value> = hits.iter().map(slice_json).collect();
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "ok",
+                "query": args.query,
+                "search_type": search_type,
+                "count": results.len(),
+                "slices": results,
+            })
+        );
+        return Ok(());
     }
 
-    // Search more to allow filtering
-    let search_results = vector_index.search(&query_embedding, args.limit * 50);
-
-    let mut results = Vec::new();
-    for (skill_id, score) in search_results {
-        // Fetch metadata first
-        if let Some(candidate) = ctx.db.get_skill_candidate(&skill_id)? {
-            let skill_tags = parse_tags_from_metadata(&candidate.metadata_json);
+    if hits.is_empty() {
+        println!("{} No slices match '{}'", style("!").yellow(), args.query);
+        return Ok(());
+    }
 
-            if filters.matches(
-                &skill_tags,
-                &candidate.source_layer,
-                candidate.quality_score as f32,
-                candidate.is_deprecated,
-            ) {
-                if let Some(skill) = ctx.db.get_skill(&skill_id)? {
-                    results.push((skill, score));
-                }
-            }
+    println!(
+        "{} slices for '{}' ({})",
+        style(hits.len()).bold(),
+        args.query,
+        search_type
+    );
+    for (i, hit) in hits.iter().enumerate() {
+        println!();
+        println!(
+            "{}. {} {} {}",
+            i + 1,
+            style(hit.key()).cyan().bold(),
+            style(format!("[{}]", slice_type_name(hit.slice.slice_type))).dim(),
+            style(format!("{:.3}", hit.score)).dim()
+        );
+        if let Some(title) = &hit.slice.section_title {
+            println!("   {}", style(title).bold());
         }
-
-        if results.len() >= args.limit {
-            break;
+        for line in hit.slice.content.lines().take(6) {
+            println!("   {line}");
         }
     }
+    Ok(())
+}
 
-    display_results(ctx, &results, args, "semantic")
+fn display_packed_slices(
+    ctx: &AppContext,
+    args: &SearchArgs,
+    hits: &[SliceHit],
+    budget: usize,
+    search_type: &str,
+) -> Result<()> {
+    let slices = packable_slices(hits);
+    let constraints = PackConstraints::new(budget, args.max_per_group);
+    let packed = ConstrainedPacker
+        .pack(&slices, &constraints, args.mode.into())
+        .map_err(|err| match err {
+            PackError::MandatorySliceOmitted {
+                slice_id,
+                required_tokens,
+                available_tokens,
+            } => MsError::Config(format!(
+                "mandatory slice {slice_id} needs {required_tokens} tokens, budget is {available_tokens}"
+            )),
+            PackError::InsufficientBudget {
+                required,
+                available,
+            } => MsError::Config(format!(
+                "pack needs {required} tokens, budget is {available}"
+            )),
+        })?;
+    let body = render_packed_body(&packed.slices);
+
+    if ctx.output_format != OutputFormat::Human {
+        let keys: Vec<&str> = packed.slices.iter().map(|s| s.id.as_str()).collect();
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "ok",
+                "query": args.query,
+                "search_type": search_type,
+                "budget": budget,
+                "total_tokens": packed.total_tokens,
+                "coverage_satisfied": packed.coverage_satisfied,
+                "candidates": hits.len(),
+                "slices": keys,
+                "content": body,
+            })
+        );
+        return Ok(());
+    }
+
+    if packed.slices.is_empty() {
+        println!(
+            "{} No slices for '{}' fit in {} tokens",
+            style("!").yellow(),
+            args.query,
+            budget
+        );
+        return Ok(());
+    }
+
+    println!("{body}");
+    println!();
+    println!(
+        "{}",
+        style(format!(
+            "{} slices from {} candidates, {}/{} tokens",
+            packed.slices.len(),
+            hits.len(),
+            packed.total_tokens,
+            budget
+        ))
+        .dim()
+    );
+    Ok(())
 }
 
 fn display_results(
     ctx: &AppContext,
-    results: &[(crate::storage::sqlite::SkillRecord, f32)],
+    ranked: &RankedResults,
     args: &SearchArgs,
+    query: &SearchQuery,
     search_type: &str,
+    embedder: Option<&dyn Embedder>,
 ) -> Result<()> {
+    let results = &ranked.results;
+
     // Build SearchResults using the new formatter
     let mut search_results = SearchResults::from_tuples(&args.query, search_type, results);
+    search_results.rank_model = ranked.model_version;
+    for (i, explanation) in ranked.explanations.iter().enumerate() {
+        search_results.set_rank_explanation(i, explanation.clone());
+    }
 
-    // Add snippets if requested
+    // Add matched sections if requested
     if args.snippets {
+        let extractor = snippet_extractor(&query.plain_text(), embedder);
         for (i, (skill, _)) in results.iter().enumerate() {
             if !skill.body.is_empty() {
-                if let Some(snippet) = find_snippet(&skill.body, &args.query) {
-                    if i < search_results.results.len() {
-                        search_results.results[i].snippet = Some(snippet);
-                    }
-                }
+                search_results.set_matched_sections(i, extractor.extract(&skill.body));
             }
         }
     }
@@ -277,88 +665,13 @@ fn display_results(
     Ok(())
 }
 
-fn parse_tags_from_metadata(metadata_json: &str) -> Vec<String> {
-    if let Ok(meta) = serde_json::from_str::<serde_json::Value>(metadata_json) {
-        if let Some(tags) = meta.get("tags").and_then(|t| t.as_array()) {
-            return tags
-                .iter()
-                .filter_map(|v| v.as_str().map(str::to_lowercase))
-                .collect();
-        }
-    }
-    Vec::new()
-}
-
-fn find_snippet(body: &str, query: &str) -> Option<String> {
-    let query_lower = query.to_lowercase();
-    let body_chars: Vec<char> = body.chars().collect();
-    let total_chars = body_chars.len();
-
-    for word in query_lower.split_whitespace() {
-        for (char_idx, (byte_idx, _)) in body.char_indices().enumerate() {
-            if is_match_at(body, byte_idx, word) {
-                let source_len = count_source_chars_consumed(body, byte_idx, word);
-
-                let start_char = char_idx.saturating_sub(30);
-                let end_char = (char_idx + source_len + 50).min(total_chars);
-
-                // Find word boundaries (scan for whitespace)
-                let start_char = body_chars[..start_char]
-                    .iter()
-                    .rposition(|c| c.is_whitespace())
-                    .map_or(start_char, |p| p + 1);
-                let end_char = body_chars[end_char..]
-                    .iter()
-                    .position(|c| c.is_whitespace())
-                    .map_or(end_char, |p| end_char + p);
-
-                let snippet: String = body_chars[start_char..end_char].iter().collect();
-                let snippet = snippet.trim();
-                if !snippet.is_empty() {
-                    let prefix = if start_char > 0 { "..." } else { "" };
-                    let suffix = if end_char < total_chars { "..." } else { "" };
-                    return Some(format!("{prefix}{snippet}{suffix}"));
-                }
-            }
-        }
-    }
-    None
-}
-
-fn is_match_at(body: &str, start_byte: usize, word_lower: &str) -> bool {
-    let slice = &body[start_byte..];
-    let mut slice_chars = slice.chars().flat_map(char::to_lowercase);
-    let mut word_chars = word_lower.chars();
-
-    loop {
-        match (slice_chars.next(), word_chars.next()) {
-            (Some(sc), Some(wc)) => {
-                if sc != wc {
-                    return false;
-                }
-            }
-            (None, Some(_)) => return false, // slice ended before word
-            (_, None) => return true,        // word ended, match!
-        }
-    }
-}
-
-fn count_source_chars_consumed(body: &str, start_byte: usize, word_lower: &str) -> usize {
-    let slice = &body[start_byte..];
-    let mut slice_chars = slice.chars();
-    let mut consumed_count = 0;
-    let mut matched_lower_count = 0;
-    let target_count = word_lower.chars().count();
-
-    while matched_lower_count < target_count {
-        if let Some(c) = slice_chars.next() {
-            consumed_count += 1;
-            matched_lower_count += c.to_lowercase().count();
-        } else {
-            break;
-        }
+/// Lexical snippets always; semantic block matches when embeddings are in use.
+fn snippet_extractor<'a>(query: &str, embedder: Option<&'a dyn Embedder>) -> SnippetExtractor<'a> {
+    let extractor = SnippetExtractor::new(query);
+    match embedder {
+        Some(embedder) => extractor.with_embedder(embedder, query),
+        None => extractor,
     }
-    consumed_count
 }
 
 /// Truncate a string to a maximum number of characters (not bytes), safe for UTF-8
@@ -371,6 +684,15 @@ fn truncate_str(s: &str, max_chars: usize) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_slice_types() {
+        assert_eq!(
+            parse_slice_types("rule, pitfalls").unwrap(),
+            vec![SliceType::Rule, SliceType::Pitfall]
+        );
+        assert!(parse_slice_types("rule,bogus").is_err());
+    }
+
     // ==================== truncate_str Tests ====================
 
     #[test]
@@ -399,7 +721,20 @@ mod tests {
         assert_eq!(truncate_str(emoji_str, 2), "🦀🐍");
     }
 
-    // ==================== parse_tags_from_metadata Tests ====================
+    // ==================== metadata tag parsing Tests ====================
+
+    fn parse_tags_from_metadata(metadata_json: &str) -> Vec<String> {
+        SkillFacets::from_candidate(&crate::storage::sqlite::SkillSearchCandidate {
+            id: "s".to_string(),
+            source_layer: "project".to_string(),
+            metadata_json: metadata_json.to_string(),
+            quality_score: 0.5,
+            is_deprecated: false,
+            author: None,
+            modified_at: String::new(),
+        })
+        .tags
+    }
 
     #[test]
     fn test_parse_tags_valid_json() {
@@ -428,28 +763,39 @@ mod tests {
         assert!(tags.is_empty());
     }
 
-    // ==================== find_snippet Tests ====================
+    // ==================== snippet Tests ====================
 
     #[test]
-    fn test_find_snippet_simple_match() {
+    fn test_snippet_simple_match() {
         let body = "This is a test of the search functionality.";
-        let snippet = find_snippet(body, "search");
+        let snippet = snippet_extractor("search", None).best(body);
         assert!(snippet.is_some());
-        assert!(snippet.unwrap().contains("search"));
+        assert!(snippet.unwrap().text.contains("search"));
     }
 
     #[test]
-    fn test_find_snippet_no_match() {
+    fn test_snippet_no_match() {
         let body = "This is a test.";
-        let snippet = find_snippet(body, "notfound");
-        assert!(snippet.is_none());
+        assert!(snippet_extractor("notfound", None).best(body).is_none());
     }
 
     #[test]
-    fn test_find_snippet_case_insensitive() {
+    fn test_snippet_case_insensitive() {
         let body = "This is a TEST of Search functionality.";
-        let snippet = find_snippet(body, "search");
-        assert!(snippet.is_some());
+        let snippet = snippet_extractor("search", None).best(body).unwrap();
+        assert_eq!(
+            snippet.render("[", "]"),
+            "This is a TEST of [Search] functionality"
+        );
+    }
+
+    #[test]
+    fn test_snippet_with_embedder_adds_semantic_match() {
+        let embedder = crate::search::HashEmbedder::new(64);
+        let body = "## Install\n\nRun cargo install.\n\n## Errors\n\nPropagate errors with the question mark.\n";
+        let matches = snippet_extractor("cargo propagate errors", Some(&embedder)).extract(body);
+        assert!(!matches.is_empty());
+        assert!(matches.len() <= 2);
     }
 
     // ==================== Argument Parsing Tests ====================
@@ -493,6 +839,7 @@ mod tests {
             "0.5",
             "--include-deprecated",
             "--snippets",
+            "--explain-rank",
         ]);
 
         assert_eq!(parsed.args.limit, 10);
@@ -501,6 +848,7 @@ mod tests {
         assert_eq!(parsed.args.min_quality, Some(0.5));
         assert!(parsed.args.include_deprecated);
         assert!(parsed.args.snippets);
+        assert!(parsed.args.explain_rank);
     }
 
     #[test]
@@ -536,28 +884,17 @@ mod tests {
     }
 
     #[test]
-    fn test_find_snippet_unicode_expansion_bug() {
-        // "İ" (U+0130) lowercases to "i\u{307}" (U+0069 U+0307)
-        // Original: 1 char. Lower: 2 chars.
-
-        // Create a string with enough expanding characters to offset the index
-        // beyond the length of the original string.
+    fn test_snippet_unicode_expansion() {
+        // "İ" (U+0130) lowercases to two chars; offsets must stay on the
+        // original text.
         let mut body = String::new();
         for _ in 0..50 {
             body.push('İ');
         }
         body.push_str(" final");
 
-        // body len: 50 + 6 = 56 chars.
-        // body_lower len: 100 + 6 = 106 chars.
-
-        // "final" found at char index 101 in lower.
-        // But body only has 56 chars.
-        // This should panic if the bug exists.
-
-        let snippet = find_snippet(&body, "final");
-        assert!(snippet.is_some());
-        let s = snippet.unwrap();
-        assert!(s.contains("final"), "Should contain 'final', found {:?}", s);
+        let snippet = snippet_extractor("final", None).best(&body).unwrap();
+        let highlight = snippet.highlights[0];
+        assert_eq!(&snippet.text[highlight.start..highlight.end], "final");
     }
 }
@@ -1,15 +1,21 @@
-//! ms security - Prompt injection defense and quarantine controls
+//! ms security - Prompt injection defense, quarantine controls and skill signing
 
 use clap::{Args, Subcommand};
 use serde::Serialize;
 use std::path::PathBuf;
 
 use crate::app::AppContext;
+use crate::bundler::Ed25519Signer;
+use crate::cli::commands::load::{signed_skill_hash, skill_asset_hashes};
 use crate::cli::output::OutputFormat;
 use crate::cli::output::emit_json;
 use crate::error::{MsError, Result};
 use crate::security::acip::prompt_version;
-use crate::security::{AcipClassification, AcipEngine, ContentSource};
+use crate::security::{
+    AcipClassification, AcipEngine, ContentSource, HistoryRewrite, HistoryScan, SecretScanner,
+    TrustDecision, rewrite_history, scan_history, sign_content_hash, verify_signatures,
+};
+use crate::storage::sqlite::SkillRecord;
 
 #[derive(Args, Debug)]
 pub struct SecurityArgs {
@@ -33,10 +39,36 @@ pub enum SecurityCommand {
         #[arg(long, default_value = "user")]
         source: String,
     },
-    /// Scan content for injection attempts
+    /// Scan content for injection attempts, or archive history for secrets
     Scan(ScanArgs),
     /// Quarantine management
     Quarantine(QuarantineArgs),
+    /// Sign skills with an Ed25519 key
+    Sign(SignArgs),
+    /// Check skill signatures against the trust policy
+    Verify(VerifyArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct SignArgs {
+    /// Skills to sign
+    #[arg(required_unless_present = "all")]
+    pub skills: Vec<String>,
+    /// Sign every indexed skill
+    #[arg(long, conflicts_with = "skills")]
+    pub all: bool,
+    /// OpenSSH Ed25519 private key (defaults to security.signing.key)
+    #[arg(long)]
+    pub key: Option<PathBuf>,
+    /// Signer name recorded with the signature (defaults to $USER)
+    #[arg(long)]
+    pub signer: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct VerifyArgs {
+    /// Skills to check (all indexed skills when empty)
+    pub skills: Vec<String>,
 }
 
 #[derive(Args, Debug)]
@@ -65,6 +97,15 @@ pub struct ScanArgs {
     /// Content hash override
     #[arg(long)]
     pub content_hash: Option<String>,
+    /// Scan every commit in the skill archive for secrets instead of an input
+    #[arg(long, conflicts_with_all = ["input", "input_file"])]
+    pub history: bool,
+    /// Rewrite archive history with detected secrets redacted
+    #[arg(long, requires = "history")]
+    pub rewrite: bool,
+    /// Explicit acknowledgement for --rewrite
+    #[arg(long)]
+    pub i_understand_the_risks: bool,
 }
 
 #[derive(Args, Debug)]
@@ -150,6 +191,22 @@ struct ReplayOutput {
     note: String,
 }
 
+#[derive(Serialize)]
+struct SignOutput {
+    skill_id: String,
+    content_hash: String,
+    key_id: String,
+    signer: String,
+}
+
+#[derive(Serialize)]
+struct VerifyOutput {
+    skill_id: String,
+    layer: String,
+    #[serde(flatten)]
+    decision: TrustDecision,
+}
+
 #[derive(Serialize)]
 struct ScanOutput {
     classification: AcipClassification,
@@ -160,6 +217,15 @@ struct ScanOutput {
     content_hash: String,
 }
 
+#[derive(Serialize)]
+struct HistoryScanOutput {
+    #[serde(flatten)]
+    scan: HistoryScan,
+    removed: usize,
+    rewrite: Option<HistoryRewrite>,
+    note: Option<String>,
+}
+
 pub fn run(ctx: &AppContext, args: &SecurityArgs) -> Result<()> {
     match &args.command {
         SecurityCommand::Status => status(ctx),
@@ -168,6 +234,8 @@ pub fn run(ctx: &AppContext, args: &SecurityArgs) -> Result<()> {
         SecurityCommand::Test { input, source } => test(ctx, input, source),
         SecurityCommand::Scan(args) => scan(ctx, args),
         SecurityCommand::Quarantine(cmd) => quarantine(ctx, cmd),
+        SecurityCommand::Sign(args) => sign(ctx, args),
+        SecurityCommand::Verify(args) => verify(ctx, args),
     }
 }
 
@@ -219,6 +287,9 @@ fn test(ctx: &AppContext, input: &str, source: &str) -> Result<()> {
 }
 
 fn scan(ctx: &AppContext, args: &ScanArgs) -> Result<()> {
+    if args.history {
+        return scan_archive_history(ctx, args);
+    }
     let input = resolve_input(args)?;
     let mut cfg = ctx.config.security.acip.clone();
     if args.audit_mode {
@@ -266,6 +337,107 @@ fn scan(ctx: &AppContext, args: &ScanArgs) -> Result<()> {
     emit_output(ctx, &payload)
 }
 
+fn scan_archive_history(ctx: &AppContext, args: &ScanArgs) -> Result<()> {
+    if args.rewrite && !args.i_understand_the_risks {
+        return Err(MsError::ApprovalRequired(
+            "rewriting history requires --i-understand-the-risks".to_string(),
+        ));
+    }
+    let scanner = SecretScanner::from_config(&ctx.config.security.secrets)?;
+    let scan = scan_history(ctx.git.repo(), &scanner)?;
+
+    let mut rewrite = None;
+    let mut note = None;
+    if args.rewrite && !scan.findings.is_empty() {
+        let result = rewrite_history(ctx.git.repo(), &scanner)?;
+        if let Some(backup) = &result.backup_ref {
+            note = Some(format!(
+                "Old history is kept at {backup}. After checking the result, delete it with \
+                 `git update-ref -d {backup}`, run `git gc --prune=now` in {}, force-push any \
+                 remotes and run `ms index`.",
+                ctx.git.root().display()
+            ));
+        }
+        rewrite = Some(result);
+    } else if scan.removed_count() > 0 {
+        note = Some(
+            "Secrets removed at HEAD still exist in history; rotate them and re-run with \
+             --rewrite to redact them."
+                .to_string(),
+        );
+    }
+
+    let payload = HistoryScanOutput {
+        removed: scan.removed_count(),
+        scan,
+        rewrite,
+        note,
+    };
+    emit_output(ctx, &payload)
+}
+
+fn sign(ctx: &AppContext, args: &SignArgs) -> Result<()> {
+    let key_path = args
+        .key
+        .clone()
+        .or_else(|| ctx.config.security.signing.key.clone())
+        .ok_or_else(|| {
+            MsError::Config("no signing key: pass --key or set security.signing.key".to_string())
+        })?;
+    let signer = Ed25519Signer::from_openssh_file(&key_path)?;
+    let signer_name = args
+        .signer
+        .clone()
+        .or_else(|| std::env::var("USER").ok())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    let mut signed = Vec::new();
+    for skill in select_skills(ctx, &args.skills, args.all)? {
+        let content_hash = signed_skill_hash(ctx, &skill, &skill_asset_hashes(&skill)?)?;
+        let signature = sign_content_hash(&content_hash, &signer, &signer_name);
+        let mut signatures = ctx.git.read_signatures(&skill.id)?;
+        signatures.add(signature);
+        ctx.git.write_signatures(&skill.id, &signatures)?;
+        signed.push(SignOutput {
+            skill_id: skill.id,
+            content_hash,
+            key_id: signer.key_id().to_string(),
+            signer: signer_name.clone(),
+        });
+    }
+    emit_output(ctx, &signed)
+}
+
+fn verify(ctx: &AppContext, args: &VerifyArgs) -> Result<()> {
+    let signing = &ctx.config.security.signing;
+    let verifier = signing.verifier()?;
+    let mut results = Vec::new();
+    for skill in select_skills(ctx, &args.skills, args.skills.is_empty())? {
+        let signatures = ctx.git.read_signatures(&skill.id)?;
+        let content_hash = signed_skill_hash(ctx, &skill, &skill_asset_hashes(&skill)?)?;
+        let status = verify_signatures(&content_hash, &signatures, &verifier);
+        results.push(VerifyOutput {
+            decision: signing.decide(&skill.id, &skill.source_layer, status),
+            skill_id: skill.id,
+            layer: skill.source_layer,
+        });
+    }
+    emit_output(ctx, &results)
+}
+
+fn select_skills(ctx: &AppContext, ids: &[String], all: bool) -> Result<Vec<SkillRecord>> {
+    if all {
+        return ctx.db.list_skills(10_000, 0);
+    }
+    ids.iter()
+        .map(|id| {
+            ctx.db
+                .get_skill(id)?
+                .ok_or_else(|| MsError::SkillNotFound(id.clone()))
+        })
+        .collect()
+}
+
 fn quarantine(ctx: &AppContext, args: &QuarantineArgs) -> Result<()> {
     match &args.command {
         QuarantineCommand::List { limit, session_id } => {
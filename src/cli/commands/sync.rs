@@ -57,7 +57,8 @@ pub fn run(ctx: &AppContext, args: &SyncArgs) -> Result<()> {
         ctx.db.clone(),
         ctx.ms_root.clone(),
         ctx.config.ru.clone(),
-    );
+    )
+    .with_policy(ctx.policy.clone());
 
     let options = SyncOptions {
         push_only: args.push_only,
@@ -0,0 +1,268 @@
+//! ms verify - Detect stale commands, flags and paths in skills
+//!
+//! Parses command blocks and shell code fences, then checks that binaries
+//! resolve on PATH and that relative repo paths exist. With `--probe-flags`
+//! it also checks that flags are still documented by the tool's `--help`
+//! (cached per tool version under `<ms_root>/cache/help`); only allowlisted
+//! tools are run, and each probe must pass the safety gate. Results are
+//! stored per skill and feed `ms quality` and `ms prune proposals`.
+
+use std::path::PathBuf;
+
+use clap::Args;
+use serde::Serialize;
+
+use crate::app::AppContext;
+use crate::cli::commands::{discover_skill_markdowns, resolve_skill_markdown};
+use crate::cli::output::OutputFormat;
+use crate::cli::output::{HumanLayout, emit_human, emit_json};
+use crate::core::spec_lens::parse_markdown;
+use crate::error::{MsError, Result};
+use crate::quality::{HelpCache, QualityScorer, StaleReference, StalenessVerifier};
+use crate::storage::sqlite::SkillVerificationRecord;
+
+#[derive(Args, Debug)]
+pub struct VerifyArgs {
+    /// Skill ID or path to SKILL.md
+    pub skill: Option<String>,
+
+    /// Verify all skills
+    #[arg(long, conflicts_with = "skill")]
+    pub all: bool,
+
+    /// Repository root for path checks (defaults to the current directory)
+    #[arg(long)]
+    pub root: Option<PathBuf>,
+
+    /// Check flags by running `<tool> --help` for allowlisted tools
+    #[arg(long)]
+    pub probe_flags: bool,
+
+    /// Ignore cached `--help` output and probe tools again
+    #[arg(long, requires = "probe_flags")]
+    pub refresh: bool,
+
+    /// Recompute and store `quality_score` for indexed skills
+    #[arg(long)]
+    pub update: bool,
+
+    /// Exit with an error if any stale reference is found
+    #[arg(long)]
+    pub strict: bool,
+}
+
+#[derive(Serialize)]
+struct VerifyOutput {
+    skill_id: String,
+    path: String,
+    score: f32,
+    commands_checked: usize,
+    references_checked: usize,
+    flags_unverified: usize,
+    stale: Vec<StaleOutput>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    quality_score: Option<f32>,
+}
+
+#[derive(Serialize)]
+struct StaleOutput {
+    #[serde(flatten)]
+    reference: StaleReference,
+    message: String,
+}
+
+pub fn run(ctx: &AppContext, args: &VerifyArgs) -> Result<()> {
+    let skill_files = if args.all {
+        discover_skill_markdowns(ctx)?
+    } else if let Some(skill) = &args.skill {
+        vec![resolve_skill_markdown(ctx, skill)?]
+    } else {
+        return Err(MsError::Config("missing skill (or use --all)".to_string()));
+    };
+
+    let verifier = build_verifier(ctx, args.root.clone(), args.probe_flags, args.refresh)?;
+    let scorer = QualityScorer::with_defaults();
+    let verified_at = chrono::Utc::now().to_rfc3339();
+    let mut outputs = Vec::new();
+
+    for skill_md in skill_files {
+        let raw = std::fs::read_to_string(&skill_md)
+            .map_err(|err| MsError::Config(format!("read {}: {err}", skill_md.display())))?;
+        let spec = parse_markdown(&raw)
+            .map_err(|err| MsError::InvalidSkill(format!("{}: {err}", skill_md.display())))?;
+        let report = verifier.verify(&spec);
+
+        ctx.db.upsert_skill_verification(&SkillVerificationRecord {
+            skill_id: spec.metadata.id.clone(),
+            content_hash: crate::cli::commands::index::compute_spec_hash(&spec)?,
+            score: report.score(),
+            commands_checked: report.commands_checked as u64,
+            references_checked: report.references_checked as u64,
+            stale: report.stale.clone(),
+            verified_at: verified_at.clone(),
+        })?;
+
+        let quality_score = if args.update && ctx.db.get_skill(&spec.metadata.id)?.is_some() {
+            let context = crate::cli::commands::quality::quality_context(ctx, &spec)?;
+            let score = scorer.score_spec(&spec, &context).overall;
+            ctx.db
+                .update_skill_quality(&spec.metadata.id, f64::from(score))?;
+            Some(score)
+        } else {
+            None
+        };
+
+        outputs.push(VerifyOutput {
+            skill_id: report.skill_id.clone(),
+            path: skill_md.display().to_string(),
+            score: report.score(),
+            commands_checked: report.commands_checked,
+            references_checked: report.references_checked,
+            flags_unverified: report.flags_unverified,
+            stale: report
+                .stale
+                .iter()
+                .map(|reference| StaleOutput {
+                    message: reference.message(),
+                    reference: reference.clone(),
+                })
+                .collect(),
+            quality_score,
+        });
+    }
+
+    let stale_total: usize = outputs.iter().map(|o| o.stale.len()).sum();
+
+    if ctx.output_format == OutputFormat::Human {
+        emit_human(human_layout(&outputs, stale_total));
+    } else {
+        emit_json(&serde_json::json!({
+            "status": "ok",
+            "count": outputs.len(),
+            "stale_total": stale_total,
+            "results": outputs,
+        }))?;
+    }
+
+    if args.strict && stale_total > 0 {
+        return Err(MsError::ValidationFailed(format!(
+            "{stale_total} stale reference(s) found"
+        )));
+    }
+    Ok(())
+}
+
+/// Build a verifier rooted at `root` (or the current directory), optionally
+/// checking flags through the on-disk help cache behind the safety gate.
+pub(crate) fn build_verifier(
+    ctx: &AppContext,
+    root: Option<PathBuf>,
+    check_flags: bool,
+    refresh: bool,
+) -> Result<StalenessVerifier> {
+    let root = match root {
+        Some(root) => root,
+        None => std::env::current_dir()
+            .map_err(|err| MsError::Config(format!("cannot get current dir: {err}")))?,
+    };
+    let mut verifier = StalenessVerifier::new().with_repo_root(root);
+    if check_flags {
+        let cache = HelpCache::new(
+            ctx.ms_root.join("cache").join("help"),
+            ctx.config.safety.clone(),
+        )
+        .with_policy(ctx.policy.clone())
+        .with_refresh(refresh);
+        verifier = verifier.with_help_cache(cache);
+    }
+    Ok(verifier)
+}
+
+fn human_layout(outputs: &[VerifyOutput], stale_total: usize) -> HumanLayout {
+    let mut layout = HumanLayout::new();
+    layout.title("Skill Verification");
+    for output in outputs {
+        layout
+            .section(&output.skill_id)
+            .kv("Path", &output.path)
+            .kv("Score", &format!("{:.2}", output.score))
+            .kv("Commands", &output.commands_checked.to_string())
+            .kv("References", &output.references_checked.to_string());
+        if output.flags_unverified > 0 {
+            layout.kv(
+                "Unverified flags",
+                &format!("{} (no complete --help)", output.flags_unverified),
+            );
+        }
+        if let Some(quality) = output.quality_score {
+            layout.kv("Quality", &format!("{quality:.2}"));
+        }
+        for stale in &output.stale {
+            layout.bullet(&format!(
+                "[{}] {} ({})",
+                stale.reference.kind.as_str(),
+                stale.message,
+                stale.reference.command
+            ));
+        }
+        layout.blank();
+    }
+    layout.section("Summary");
+    if stale_total == 0 {
+        layout.bullet("No stale references");
+    } else {
+        layout.bullet(&format!("{stale_total} stale reference(s)"));
+    }
+    layout
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser, Debug)]
+    struct TestCli {
+        #[command(flatten)]
+        verify: VerifyArgs,
+    }
+
+    #[test]
+    fn parse_verify_defaults() {
+        let cli = TestCli::try_parse_from(["test", "rust-errors"]).unwrap();
+        assert_eq!(cli.verify.skill.as_deref(), Some("rust-errors"));
+        assert!(!cli.verify.all);
+        assert!(!cli.verify.probe_flags);
+        assert!(!cli.verify.strict);
+    }
+
+    #[test]
+    fn parse_verify_all_with_flags() {
+        let cli = TestCli::try_parse_from([
+            "test",
+            "--all",
+            "--root",
+            "/tmp/repo",
+            "--probe-flags",
+            "--update",
+            "--strict",
+        ])
+        .unwrap();
+        assert!(cli.verify.all);
+        assert_eq!(cli.verify.root, Some(PathBuf::from("/tmp/repo")));
+        assert!(cli.verify.probe_flags);
+        assert!(cli.verify.update);
+        assert!(cli.verify.strict);
+    }
+
+    #[test]
+    fn parse_verify_refresh_requires_probe_flags() {
+        assert!(TestCli::try_parse_from(["test", "a", "--refresh"]).is_err());
+        assert!(TestCli::try_parse_from(["test", "a", "--probe-flags", "--refresh"]).is_ok());
+    }
+
+    #[test]
+    fn parse_verify_all_conflicts_with_skill() {
+        assert!(TestCli::try_parse_from(["test", "a", "--all"]).is_err());
+    }
+}
@@ -4,6 +4,8 @@ use console::style;
 use serde::Serialize;
 
 use crate::cli::output::{Formattable, OutputFormat};
+use crate::search::MatchedSection;
+use crate::search::rerank::FeatureContribution;
 use crate::storage::sqlite::SkillRecord;
 
 /// Search result item with score
@@ -15,6 +17,10 @@ pub struct SearchResultItem {
     pub score: f32,
     /// Optional snippet of matching content
     pub snippet: Option<String>,
+    /// Sections that explain the match, best first
+    pub matched_sections: Vec<MatchedSection>,
+    /// Learned re-ranker feature contributions (with --explain-rank)
+    pub rank_explanation: Vec<FeatureContribution>,
 }
 
 /// Search results collection for formatted display
@@ -28,6 +34,8 @@ pub struct SearchResults {
     pub results: Vec<SearchResultItem>,
     /// Search duration in milliseconds
     pub duration_ms: Option<u64>,
+    /// Version of the learned re-ranker applied, if any
+    pub rank_model: Option<u32>,
 }
 
 /// Serializable search result for JSON output
@@ -42,6 +50,10 @@ struct SearchResultJson {
     is_deprecated: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     snippet: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    matched_sections: Vec<MatchedSection>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    rank_explanation: Vec<FeatureContribution>,
 }
 
 /// Serializable search response for JSON output
@@ -53,6 +65,8 @@ struct SearchResponseJson {
     count: usize,
     #[serde(skip_serializing_if = "Option::is_none")]
     duration_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rank_model: Option<u32>,
     results: Vec<SearchResultJson>,
 }
 
@@ -64,6 +78,7 @@ impl SearchResults {
             search_type: search_type.into(),
             results: Vec::new(),
             duration_ms: None,
+            rank_model: None,
         }
     }
 
@@ -73,6 +88,8 @@ impl SearchResults {
             skill,
             score,
             snippet: None,
+            matched_sections: Vec::new(),
+            rank_explanation: Vec::new(),
         });
     }
 
@@ -87,9 +104,27 @@ impl SearchResults {
             skill,
             score,
             snippet: Some(snippet.into()),
+            matched_sections: Vec::new(),
+            rank_explanation: Vec::new(),
         });
     }
 
+    /// Attach matched sections to the result at `index`; the best one also
+    /// becomes the plain-text snippet.
+    pub fn set_matched_sections(&mut self, index: usize, sections: Vec<MatchedSection>) {
+        if let Some(result) = self.results.get_mut(index) {
+            result.snippet = sections.first().map(MatchedSection::label);
+            result.matched_sections = sections;
+        }
+    }
+
+    /// Attach the re-ranker explanation to the result at `index`
+    pub fn set_rank_explanation(&mut self, index: usize, explanation: Vec<FeatureContribution>) {
+        if let Some(result) = self.results.get_mut(index) {
+            result.rank_explanation = explanation;
+        }
+    }
+
     /// Set the search duration
     #[must_use]
     pub const fn with_duration(mut self, duration_ms: u64) -> Self {
@@ -117,6 +152,7 @@ impl SearchResults {
             search_type: self.search_type.clone(),
             count: self.results.len(),
             duration_ms: self.duration_ms,
+            rank_model: self.rank_model,
             results: self
                 .results
                 .iter()
@@ -129,6 +165,8 @@ impl SearchResults {
                     quality: r.skill.quality_score,
                     is_deprecated: r.skill.is_deprecated,
                     snippet: r.snippet.clone(),
+                    matched_sections: r.matched_sections.clone(),
+                    rank_explanation: r.rank_explanation.clone(),
                 })
                 .collect(),
         }
@@ -158,6 +196,9 @@ impl SearchResults {
         if let Some(ms) = self.duration_ms {
             out.push_str(&format!(" in {ms}ms"));
         }
+        if let Some(version) = self.rank_model {
+            out.push_str(&format!(", re-ranked by model v{version}"));
+        }
         out.push_str(":\n\n");
 
         for (i, result) in self.results.iter().enumerate() {
@@ -185,9 +226,40 @@ impl SearchResults {
                 out.push_str(&format!("   {}\n", result.skill.description));
             }
 
-            // Snippet if available
-            if let Some(ref snippet) = result.snippet {
-                out.push_str(&format!("   {}\n", style(snippet).dim()));
+            // Matched sections with highlighted terms, else the plain snippet
+            if result.matched_sections.is_empty() {
+                if let Some(ref snippet) = result.snippet {
+                    out.push_str(&format!("   {}\n", style(snippet).dim()));
+                }
+            }
+            for matched in &result.matched_sections {
+                out.push_str("   ");
+                if let Some(ref title) = matched.section_title {
+                    out.push_str(&format!("{} ", style(format!("§ {title}:")).dim()));
+                }
+                for (text, highlighted) in matched.segments() {
+                    let text = text.replace('\n', " ");
+                    if highlighted {
+                        out.push_str(&style(text).yellow().bold().to_string());
+                    } else {
+                        out.push_str(&style(text).dim().to_string());
+                    }
+                }
+                out.push('\n');
+            }
+
+            // Top re-ranker contributions
+            if !result.rank_explanation.is_empty() {
+                let parts: Vec<String> = result
+                    .rank_explanation
+                    .iter()
+                    .take(4)
+                    .map(|c| format!("{} {:+.2}", c.feature, c.contribution))
+                    .collect();
+                out.push_str(&format!(
+                    "   {}\n",
+                    style(format!("rank: {}", parts.join(", "))).dim()
+                ));
             }
 
             out.push('\n');
@@ -248,6 +320,8 @@ impl SearchResults {
                     quality: r.skill.quality_score,
                     is_deprecated: r.skill.is_deprecated,
                     snippet: r.snippet.clone(),
+                    matched_sections: r.matched_sections.clone(),
+                    rank_explanation: r.rank_explanation.clone(),
                 })
                 .ok()
             })
@@ -374,6 +448,25 @@ mod tests {
         assert!(lines.len() >= 2);
     }
 
+    #[test]
+    fn search_results_json_includes_matched_sections() {
+        let mut results = SearchResults::new("rustup", "bm25");
+        results.add_result(test_skill("skill-1"), 0.95);
+        let body = "## Setup\n\nInstall rustup first.\n";
+        let sections = crate::search::SnippetExtractor::new("rustup").extract(body);
+        results.set_matched_sections(0, sections);
+
+        let output = results.format(OutputFormat::Json);
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let result = &parsed["results"][0];
+        assert_eq!(result["snippet"], "Setup: Install rustup first");
+        let matched = &result["matched_sections"][0];
+        assert_eq!(matched["section_id"], "setup");
+        assert_eq!(matched["kind"], "lexical");
+        assert_eq!(matched["highlights"][0]["start"], 8);
+        assert_eq!(matched["highlights"][0]["end"], 14);
+    }
+
     #[test]
     fn search_results_with_duration() {
         let results = SearchResults::new("test", "hybrid").with_duration(42);
@@ -212,6 +212,9 @@ pub enum Commands {
     /// Manage skill bundles
     Bundle(commands::bundle::BundleArgs),
 
+    /// Check installed bundles against security advisories
+    Audit(commands::audit::AuditArgs),
+
     /// Synchronize skills across machines
     Sync(commands::sync::SyncArgs),
 
@@ -248,6 +251,9 @@ pub enum Commands {
     /// Suggestion bandit controls
     Bandit(commands::bandit::BanditArgs),
 
+    /// Train and manage the learned search re-ranker
+    Rank(commands::rank::RankArgs),
+
     /// Backup and restore ms state
     Backup(commands::backup::BackupArgs),
 
@@ -266,6 +272,9 @@ pub enum Commands {
     /// Security and prompt-injection defenses
     Security(commands::security::SecurityArgs),
 
+    /// Organisation policy: inspect, explain decisions and sign
+    Policy(commands::policy::PolicyArgs),
+
     /// Setup ms integration for AI coding agents
     Setup(commands::setup::SetupArgs),
 
@@ -278,6 +287,9 @@ pub enum Commands {
     /// Validate skill specs
     Validate(commands::validate::ValidateArgs),
 
+    /// Detect stale commands, flags and paths in skills
+    Verify(commands::verify::VerifyArgs),
+
     /// Run skill tests
     Test(commands::test::TestArgs),
 
@@ -302,6 +314,9 @@ pub enum Commands {
     /// Run as MCP (Model Context Protocol) server
     Mcp(commands::mcp::McpArgs),
 
+    /// Run a language server (LSP over stdio) for SKILL.md authoring
+    Lsp(commands::lsp::LspArgs),
+
     /// Test embedding backends
     Embed(commands::embed::EmbedArgs),
 }
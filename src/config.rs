@@ -3,8 +3,13 @@ use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 
+use crate::bundler::AdvisoryConfig;
+use crate::core::safety::SafetyEvaluator;
 use crate::error::{MsError, Result};
-use crate::security::{AcipConfig, TrustLevel};
+use crate::security::{
+    AcipConfig, CustomSecretPattern, SecretAllowlist, SecretScanConfig, SigningConfig, TrustLevel,
+    TrustRule,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Config {
@@ -200,6 +205,9 @@ impl Config {
             validate_weight("MS_SEARCH_SEMANTIC_WEIGHT", value)?;
             self.search.semantic_weight = value;
         }
+        if let Some(value) = env_bool("MS_SEARCH_LEARNED_RERANK")? {
+            self.search.learned_rerank = value;
+        }
 
         if let Some(value) = env_bool("MS_CASS_AUTO_DETECT")? {
             self.cass.auto_detect = value;
@@ -287,6 +295,9 @@ impl Config {
         if let Some(value) = env_string("MS_SECURITY_ACIP_TRUST_FILE_CONTENTS") {
             self.security.acip.trust.file_contents = parse_trust_level(&value)?;
         }
+        if let Some(value) = env_string("MS_SAFETY_EVALUATOR") {
+            self.safety.evaluator = parse_safety_evaluator(&value)?;
+        }
         if let Some(value) = env_string("MS_SAFETY_DCG_BIN") {
             self.safety.dcg_bin = PathBuf::from(value);
         }
@@ -299,6 +310,12 @@ impl Config {
         if let Some(value) = env_bool("MS_SAFETY_REQUIRE_VERBATIM_APPROVAL")? {
             self.safety.require_verbatim_approval = value;
         }
+        if let Some(values) = env_list("MS_SAFETY_NATIVE_PACKS")? {
+            self.safety.native_packs = values;
+        }
+        if let Some(values) = env_list("MS_SAFETY_RULE_PATHS")? {
+            self.safety.rule_paths = values.into_iter().map(PathBuf::from).collect();
+        }
 
         // Auto-load learning config
         if let Some(value) = env_bool("MS_AUTO_LOAD_LEARNING_ENABLED")? {
@@ -466,6 +483,13 @@ pub struct SearchConfig {
     /// Environment variable containing API key
     #[serde(default)]
     pub api_key_env: String,
+    /// Record search impressions and apply the active learned re-ranker
+    #[serde(default = "default_learned_rerank")]
+    pub learned_rerank: bool,
+}
+
+const fn default_learned_rerank() -> bool {
+    true
 }
 
 impl Default for SearchConfig {
@@ -479,6 +503,7 @@ impl Default for SearchConfig {
             api_endpoint: "https://api.openai.com/v1/embeddings".to_string(),
             api_model: "text-embedding-3-small".to_string(),
             api_key_env: "OPENAI_API_KEY".to_string(),
+            learned_rerank: default_learned_rerank(),
         }
     }
 }
@@ -509,6 +534,9 @@ impl SearchConfig {
         if let Some(value) = patch.api_key_env {
             self.api_key_env = value;
         }
+        if let Some(value) = patch.learned_rerank {
+            self.learned_rerank = value;
+        }
     }
 }
 
@@ -788,6 +816,15 @@ impl AgentMailConfig {
 pub struct SecurityConfig {
     #[serde(default)]
     pub acip: AcipConfig,
+    /// Skill signatures and load-time trust policy
+    #[serde(default)]
+    pub signing: SigningConfig,
+    /// Secret scanner patterns, entropy thresholds and allowlists
+    #[serde(default)]
+    pub secrets: SecretScanConfig,
+    /// Bundle advisory databases for `ms audit` and `ms bundle update`
+    #[serde(default)]
+    pub advisories: AdvisoryConfig,
 }
 
 impl SecurityConfig {
@@ -795,11 +832,63 @@ impl SecurityConfig {
         if let Some(patch) = patch.acip {
             self.acip.merge(patch);
         }
+        if let Some(patch) = patch.signing {
+            if let Some(value) = patch.trusted_keys {
+                self.signing.trusted_keys = value;
+            }
+            if let Some(value) = patch.key {
+                self.signing.key = Some(value);
+            }
+            if let Some(value) = patch.policy {
+                self.signing.policy = value;
+            }
+        }
+        if let Some(patch) = patch.secrets {
+            if let Some(value) = patch.patterns {
+                self.secrets.patterns = value;
+            }
+            if let Some(entropy) = patch.entropy {
+                let target = &mut self.secrets.entropy;
+                if let Some(value) = entropy.enabled {
+                    target.enabled = value;
+                }
+                if let Some(value) = entropy.threshold {
+                    target.threshold = value;
+                }
+                if let Some(value) = entropy.min_length {
+                    target.min_length = value;
+                }
+                for (slot, value) in [
+                    (&mut target.input, entropy.input),
+                    (&mut target.skill, entropy.skill),
+                    (&mut target.evidence, entropy.evidence),
+                    (&mut target.session, entropy.session),
+                    (&mut target.history, entropy.history),
+                ] {
+                    if value.is_some() {
+                        *slot = value;
+                    }
+                }
+            }
+            if let Some(value) = patch.allowlist {
+                self.secrets.allowlist = value;
+            }
+        }
+        if let Some(patch) = patch.advisories {
+            if let Some(value) = patch.sources {
+                self.advisories.sources = value;
+            }
+            if let Some(value) = patch.block_vulnerable_updates {
+                self.advisories.block_vulnerable_updates = value;
+            }
+        }
     }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SafetyConfig {
+    #[serde(default)]
+    pub evaluator: SafetyEvaluator,
     #[serde(default)]
     pub dcg_bin: PathBuf,
     #[serde(default)]
@@ -808,21 +897,33 @@ pub struct SafetyConfig {
     pub dcg_explain_format: String,
     #[serde(default)]
     pub require_verbatim_approval: bool,
+    /// Built-in rule packs to enable (all when empty)
+    #[serde(default)]
+    pub native_packs: Vec<String>,
+    /// Extra rule pack files or directories of `*.toml` packs
+    #[serde(default)]
+    pub rule_paths: Vec<PathBuf>,
 }
 
 impl Default for SafetyConfig {
     fn default() -> Self {
         Self {
+            evaluator: SafetyEvaluator::Auto,
             dcg_bin: PathBuf::from("dcg"),
             dcg_packs: Vec::new(),
             dcg_explain_format: "json".to_string(),
             require_verbatim_approval: true,
+            native_packs: Vec::new(),
+            rule_paths: Vec::new(),
         }
     }
 }
 
 impl SafetyConfig {
     fn merge(&mut self, patch: SafetyPatch) {
+        if let Some(value) = patch.evaluator {
+            self.evaluator = value;
+        }
         if let Some(value) = patch.dcg_bin {
             self.dcg_bin = value;
         }
@@ -835,6 +936,12 @@ impl SafetyConfig {
         if let Some(value) = patch.require_verbatim_approval {
             self.require_verbatim_approval = value;
         }
+        if let Some(values) = patch.native_packs {
+            self.native_packs = values;
+        }
+        if let Some(values) = patch.rule_paths {
+            self.rule_paths = values;
+        }
     }
 }
 
@@ -1052,6 +1159,7 @@ struct SearchPatch {
     pub api_endpoint: Option<String>,
     pub api_model: Option<String>,
     pub api_key_env: Option<String>,
+    pub learned_rerank: Option<bool>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -1109,14 +1217,52 @@ struct AgentMailPatch {
 #[derive(Debug, Clone, Default, Deserialize)]
 struct SecurityPatch {
     pub acip: Option<AcipPatch>,
+    pub signing: Option<SigningPatch>,
+    pub secrets: Option<SecretsPatch>,
+    pub advisories: Option<AdvisoriesPatch>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct AdvisoriesPatch {
+    pub sources: Option<Vec<String>>,
+    pub block_vulnerable_updates: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct SecretsPatch {
+    pub patterns: Option<Vec<CustomSecretPattern>>,
+    pub entropy: Option<EntropyPatch>,
+    pub allowlist: Option<SecretAllowlist>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct EntropyPatch {
+    pub enabled: Option<bool>,
+    pub threshold: Option<f64>,
+    pub min_length: Option<usize>,
+    pub input: Option<f64>,
+    pub skill: Option<f64>,
+    pub evidence: Option<f64>,
+    pub session: Option<f64>,
+    pub history: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct SigningPatch {
+    pub trusted_keys: Option<Vec<String>>,
+    pub key: Option<PathBuf>,
+    pub policy: Option<Vec<TrustRule>>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
 struct SafetyPatch {
+    pub evaluator: Option<SafetyEvaluator>,
     pub dcg_bin: Option<PathBuf>,
     pub dcg_packs: Option<Vec<String>>,
     pub dcg_explain_format: Option<String>,
     pub require_verbatim_approval: Option<bool>,
+    pub native_packs: Option<Vec<String>>,
+    pub rule_paths: Option<Vec<PathBuf>>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -1199,6 +1345,17 @@ fn parse_trust_level(value: &str) -> Result<TrustLevel> {
     }
 }
 
+fn parse_safety_evaluator(value: &str) -> Result<SafetyEvaluator> {
+    match value.to_lowercase().as_str() {
+        "auto" => Ok(SafetyEvaluator::Auto),
+        "native" => Ok(SafetyEvaluator::Native),
+        "dcg" => Ok(SafetyEvaluator::Dcg),
+        _ => Err(MsError::Config(format!(
+            "invalid safety evaluator {value} (expected auto|native|dcg)"
+        ))),
+    }
+}
+
 fn env_string(key: &str) -> Option<String> {
     std::env::var(key).ok()
 }
@@ -12,6 +12,8 @@ use std::num::NonZeroUsize;
 use std::sync::Mutex;
 
 use crate::context::detector::{DefaultDetector, DetectedProject, ProjectDetector};
+use crate::context::manifest::{DetectedDependency, detect_dependencies};
+use crate::context::workspace::{WorkspaceDetector, WorkspaceLayout};
 use crate::error::Result;
 
 /// Configuration for context collection
@@ -68,9 +70,13 @@ pub struct CollectorFingerprint(pub u64);
 pub struct CollectedContext {
     pub cwd: PathBuf,
     pub detected_projects: Vec<DetectedProject>,
+    #[serde(default)]
+    pub dependencies: Vec<DetectedDependency>,
     pub recent_files: Vec<RecentFile>,
     pub detected_tools: HashSet<String>,
     pub git_context: Option<GitContext>,
+    #[serde(default)]
+    pub workspace: WorkspaceLayout,
     pub env_signals: HashMap<String, String>,
     pub collected_at: DateTime<Utc>,
     pub fingerprint: CollectorFingerprint,
@@ -90,6 +96,12 @@ impl CollectedContext {
             project.project_type.hash(&mut hasher);
         }
 
+        // Hash dependency set (changes only when manifests change)
+        for dep in &self.dependencies {
+            dep.key().hash(&mut hasher);
+            dep.version.hash(&mut hasher);
+        }
+
         // Hash recent file paths and extensions (stable-ish)
         for file in &self.recent_files {
             file.path.hash(&mut hasher);
@@ -103,6 +115,11 @@ impl CollectedContext {
             tool.hash(&mut hasher);
         }
 
+        // Hash active subproject (changes when moving between monorepo members)
+        if let Some(active) = self.workspace.active() {
+            active.root.hash(&mut hasher);
+        }
+
         // Hash git branch (semi-stable)
         if let Some(git) = &self.git_context {
             git.branch.hash(&mut hasher);
@@ -143,8 +160,22 @@ impl ContextCollector {
             }
         }
 
-        let detected_projects = self.project_detector.detect(cwd);
         let recent_files = self.collect_recent_files(cwd);
+        let recent_paths: Vec<PathBuf> = recent_files.iter().map(|f| f.path.clone()).collect();
+        let workspace =
+            WorkspaceDetector::new(self.config.scan_depth, self.config.ignore_patterns.clone())
+                .discover(cwd, &recent_paths, self.project_detector.as_ref());
+
+        // In a workspace, projects are weighted by subproject relevance and
+        // dependencies come from the active subproject.
+        let (detected_projects, dependencies) = if workspace.subprojects.is_empty() {
+            (self.project_detector.detect(cwd), detect_dependencies(cwd))
+        } else {
+            (
+                workspace.weighted_projects(),
+                workspace.active_dependencies(),
+            )
+        };
         let detected_tools = self.detect_tools();
         let git_context = self.collect_git_context(cwd);
         let env_signals = self.collect_env_signals();
@@ -152,9 +183,11 @@ impl ContextCollector {
         let mut ctx = CollectedContext {
             cwd: cwd.to_path_buf(),
             detected_projects,
+            dependencies,
             recent_files,
             detected_tools,
             git_context,
+            workspace,
             env_signals,
             collected_at: Utc::now(),
             fingerprint: CollectorFingerprint(0), // Placeholder
@@ -327,9 +360,11 @@ mod tests {
         let ctx1 = CollectedContext {
             cwd: PathBuf::from("/tmp/test"),
             detected_projects: vec![],
+            dependencies: vec![],
             recent_files: vec![],
             detected_tools: HashSet::new(),
             git_context: None,
+            workspace: WorkspaceLayout::default(),
             env_signals: HashMap::new(),
             collected_at: Utc::now(),
             fingerprint: CollectorFingerprint(0),
@@ -0,0 +1,868 @@
+//! Dependency detection from project manifests and lockfiles.
+//!
+//! Parses the manifests of the common package ecosystems (`Cargo.toml`,
+//! `package.json`, `pyproject.toml`, `go.mod`, `Gemfile`, ...) and their
+//! lockfiles into a flat set of `(ecosystem, package, version)` entries.
+//! Manifests contribute the declared requirement of direct dependencies;
+//! lockfiles contribute the exact resolved version of every package.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Package ecosystems understood by the manifest parsers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Ecosystem {
+    Cargo,
+    Npm,
+    PyPI,
+    Go,
+    RubyGems,
+}
+
+impl Ecosystem {
+    /// Get a lowercase identifier for the ecosystem.
+    #[must_use]
+    pub const fn id(&self) -> &'static str {
+        match self {
+            Self::Cargo => "cargo",
+            Self::Npm => "npm",
+            Self::PyPI => "pypi",
+            Self::Go => "go",
+            Self::RubyGems => "rubygems",
+        }
+    }
+
+    /// Resolve an ecosystem from its id or a common alias
+    /// (e.g. "rust" → `Cargo`, "node" → `Npm`, "pip" → `PyPI`).
+    #[must_use]
+    pub fn from_alias(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "cargo" | "rust" | "crates" | "crates.io" => Some(Self::Cargo),
+            "npm" | "node" | "js" | "javascript" | "typescript" | "yarn" | "pnpm" => {
+                Some(Self::Npm)
+            }
+            "pypi" | "python" | "pip" | "poetry" | "uv" => Some(Self::PyPI),
+            "go" | "golang" => Some(Self::Go),
+            "rubygems" | "ruby" | "gem" | "bundler" => Some(Self::RubyGems),
+            _ => None,
+        }
+    }
+
+    /// Normalize a package name so that equivalent spellings compare equal.
+    ///
+    /// `PyPI` names are case-insensitive and treat `-`, `_` and `.` as the
+    /// same character; Go module paths are compared verbatim.
+    #[must_use]
+    pub fn normalize_name(&self, name: &str) -> String {
+        match self {
+            Self::PyPI => name.trim().to_lowercase().replace(['_', '.'], "-"),
+            Self::Go => name.trim().to_string(),
+            Self::Cargo | Self::Npm | Self::RubyGems => name.trim().to_lowercase(),
+        }
+    }
+}
+
+/// A dependency detected from a manifest or lockfile.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DetectedDependency {
+    /// Ecosystem the package belongs to.
+    pub ecosystem: Ecosystem,
+    /// Package name as written in the manifest.
+    pub name: String,
+    /// Exact resolved version (from a lockfile or an `==` pin).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Declared version requirement (from the manifest).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requirement: Option<String>,
+    /// Whether the project depends on the package directly.
+    pub direct: bool,
+    /// Manifest or lockfile the dependency was found in.
+    pub source: PathBuf,
+}
+
+impl DetectedDependency {
+    fn new(ecosystem: Ecosystem, name: &str, source: &Path) -> Self {
+        Self {
+            ecosystem,
+            name: name.trim().to_string(),
+            version: None,
+            requirement: None,
+            direct: true,
+            source: source.to_path_buf(),
+        }
+    }
+
+    fn with_requirement(mut self, requirement: Option<&str>) -> Self {
+        self.requirement = requirement
+            .map(str::trim)
+            .filter(|r| !r.is_empty() && *r != "*")
+            .map(String::from);
+        self
+    }
+
+    fn resolved(mut self, version: &str) -> Self {
+        let version = version.trim().trim_start_matches('v');
+        if !version.is_empty() {
+            self.version = Some(version.to_string());
+        }
+        self.direct = false;
+        self
+    }
+
+    /// Normalized lookup key for this dependency.
+    #[must_use]
+    pub fn key(&self) -> (Ecosystem, String) {
+        (self.ecosystem, self.ecosystem.normalize_name(&self.name))
+    }
+
+    /// Best-effort semantic version for range matching.
+    ///
+    /// Uses the resolved version when known, otherwise the lowest version
+    /// mentioned by the declared requirement.
+    #[must_use]
+    pub fn effective_version(&self) -> Option<semver::Version> {
+        self.version
+            .as_deref()
+            .and_then(lenient_version)
+            .or_else(|| self.requirement.as_deref().and_then(lenient_version))
+    }
+}
+
+/// Manifest and lockfile names recognized by [`parse_manifest`].
+pub const MANIFEST_FILES: &[&str] = &[
+    "Cargo.toml",
+    "Cargo.lock",
+    "package.json",
+    "package-lock.json",
+    "npm-shrinkwrap.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "pyproject.toml",
+    "requirements.txt",
+    "requirements-dev.txt",
+    "poetry.lock",
+    "uv.lock",
+    "Pipfile.lock",
+    "go.mod",
+    "Gemfile",
+    "Gemfile.lock",
+];
+
+/// Detect all dependencies declared by manifests in `dir`.
+///
+/// Entries from manifests and lockfiles are merged per package: the result
+/// carries the declared requirement, the resolved version, and is marked
+/// direct if any manifest lists it directly.
+#[must_use]
+pub fn detect_dependencies(dir: &Path) -> Vec<DetectedDependency> {
+    let mut merged: HashMap<(Ecosystem, String), DetectedDependency> = HashMap::new();
+    let mut order = Vec::new();
+
+    for file in MANIFEST_FILES {
+        let path = dir.join(file);
+        if !path.is_file() {
+            continue;
+        }
+        for dep in parse_manifest(&path).unwrap_or_default() {
+            let key = dep.key();
+            if let Some(existing) = merged.get_mut(&key) {
+                existing.direct |= dep.direct;
+                if existing.version.is_none() {
+                    existing.version = dep.version;
+                }
+                if existing.requirement.is_none() {
+                    existing.requirement = dep.requirement;
+                }
+            } else {
+                order.push(key.clone());
+                merged.insert(key, dep);
+            }
+        }
+    }
+
+    order
+        .into_iter()
+        .filter_map(|key| merged.remove(&key))
+        .collect()
+}
+
+/// Parse a single manifest or lockfile, dispatching on its file name.
+///
+/// Returns `None` for unrecognized files or files that cannot be read or
+/// parsed.
+#[must_use]
+pub fn parse_manifest(path: &Path) -> Option<Vec<DetectedDependency>> {
+    let file_name = path.file_name()?.to_str()?;
+    let content = std::fs::read_to_string(path).ok()?;
+
+    let deps = match file_name {
+        "Cargo.toml" => parse_cargo_toml(&content, path)?,
+        "Cargo.lock" | "poetry.lock" | "uv.lock" => {
+            let ecosystem = if file_name == "Cargo.lock" {
+                Ecosystem::Cargo
+            } else {
+                Ecosystem::PyPI
+            };
+            parse_toml_lock(&content, path, ecosystem)?
+        }
+        "package.json" => parse_package_json(&content, path)?,
+        "package-lock.json" | "npm-shrinkwrap.json" => parse_package_lock(&content, path)?,
+        "yarn.lock" => parse_yarn_lock(&content, path),
+        "pnpm-lock.yaml" => parse_pnpm_lock(&content, path)?,
+        "pyproject.toml" => parse_pyproject(&content, path)?,
+        "Pipfile.lock" => parse_pipfile_lock(&content, path)?,
+        "go.mod" => parse_go_mod(&content, path),
+        "Gemfile" => parse_gemfile(&content, path),
+        "Gemfile.lock" => parse_gemfile_lock(&content, path),
+        name if name.starts_with("requirements")
+            && Path::new(name)
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("txt")) =>
+        {
+            parse_requirements_txt(&content, path)
+        }
+        _ => return None,
+    };
+
+    Some(deps)
+}
+
+// =============================================================================
+// Rust
+// =============================================================================
+
+fn parse_cargo_toml(content: &str, source: &Path) -> Option<Vec<DetectedDependency>> {
+    let value: toml::Value = toml::from_str(content).ok()?;
+    let mut deps = Vec::new();
+
+    let mut tables: Vec<&toml::Value> = Vec::new();
+    for key in ["dependencies", "dev-dependencies", "build-dependencies"] {
+        tables.extend(value.get(key));
+    }
+    if let Some(workspace_deps) = value.get("workspace").and_then(|w| w.get("dependencies")) {
+        tables.push(workspace_deps);
+    }
+    if let Some(targets) = value.get("target").and_then(toml::Value::as_table) {
+        for target in targets.values() {
+            for key in ["dependencies", "dev-dependencies", "build-dependencies"] {
+                tables.extend(target.get(key));
+            }
+        }
+    }
+
+    for table in tables.into_iter().filter_map(toml::Value::as_table) {
+        for (key, spec) in table {
+            let (name, requirement) = match spec {
+                toml::Value::String(req) => (key.as_str(), Some(req.as_str())),
+                toml::Value::Table(t) => (
+                    t.get("package")
+                        .and_then(toml::Value::as_str)
+                        .unwrap_or(key.as_str()),
+                    t.get("version").and_then(toml::Value::as_str),
+                ),
+                _ => continue,
+            };
+            deps.push(
+                DetectedDependency::new(Ecosystem::Cargo, name, source)
+                    .with_requirement(requirement),
+            );
+        }
+    }
+
+    Some(deps)
+}
+
+/// Parse lockfiles made of `[[package]]` tables with `name` and `version`
+/// (`Cargo.lock`, `poetry.lock`, `uv.lock`).
+fn parse_toml_lock(
+    content: &str,
+    source: &Path,
+    ecosystem: Ecosystem,
+) -> Option<Vec<DetectedDependency>> {
+    let value: toml::Value = toml::from_str(content).ok()?;
+    let packages = value.get("package")?.as_array()?;
+
+    Some(
+        packages
+            .iter()
+            .filter_map(|pkg| {
+                let name = pkg.get("name")?.as_str()?;
+                let version = pkg.get("version")?.as_str()?;
+                Some(DetectedDependency::new(ecosystem, name, source).resolved(version))
+            })
+            .collect(),
+    )
+}
+
+// =============================================================================
+// JavaScript
+// =============================================================================
+
+fn parse_package_json(content: &str, source: &Path) -> Option<Vec<DetectedDependency>> {
+    let value: serde_json::Value = serde_json::from_str(content).ok()?;
+    let mut deps = Vec::new();
+
+    for key in [
+        "dependencies",
+        "devDependencies",
+        "peerDependencies",
+        "optionalDependencies",
+    ] {
+        let Some(map) = value.get(key).and_then(|v| v.as_object()) else {
+            continue;
+        };
+        for (name, requirement) in map {
+            deps.push(
+                DetectedDependency::new(Ecosystem::Npm, name, source)
+                    .with_requirement(requirement.as_str()),
+            );
+        }
+    }
+
+    Some(deps)
+}
+
+fn parse_package_lock(content: &str, source: &Path) -> Option<Vec<DetectedDependency>> {
+    let value: serde_json::Value = serde_json::from_str(content).ok()?;
+    let mut deps = Vec::new();
+
+    // lockfileVersion 2/3: "packages" keyed by install path
+    if let Some(packages) = value.get("packages").and_then(|v| v.as_object()) {
+        for (path, pkg) in packages {
+            let Some(idx) = path.rfind("node_modules/") else {
+                continue;
+            };
+            let name = &path[idx + "node_modules/".len()..];
+            if let Some(version) = pkg.get("version").and_then(|v| v.as_str()) {
+                deps.push(DetectedDependency::new(Ecosystem::Npm, name, source).resolved(version));
+            }
+        }
+        return Some(deps);
+    }
+
+    // lockfileVersion 1: nested "dependencies" keyed by name
+    if let Some(packages) = value.get("dependencies").and_then(|v| v.as_object()) {
+        for (name, pkg) in packages {
+            if let Some(version) = pkg.get("version").and_then(|v| v.as_str()) {
+                deps.push(DetectedDependency::new(Ecosystem::Npm, name, source).resolved(version));
+            }
+        }
+    }
+
+    Some(deps)
+}
+
+fn parse_yarn_lock(content: &str, source: &Path) -> Vec<DetectedDependency> {
+    let mut deps = Vec::new();
+    let mut current: Option<String> = None;
+
+    for line in content.lines() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if !line.starts_with(' ') {
+            // Entry header: `"@scope/pkg@^1.0.0", "@scope/pkg@^1.1.0":`
+            let first_spec = line
+                .trim_end_matches(':')
+                .split(',')
+                .next()
+                .unwrap_or("")
+                .trim()
+                .trim_matches('"');
+            current = npm_spec_name(first_spec).map(String::from);
+            continue;
+        }
+
+        let Some(name) = current.as_deref() else {
+            continue;
+        };
+        let trimmed = line.trim();
+        let version = trimmed
+            .strip_prefix("version ")
+            .or_else(|| trimmed.strip_prefix("version: "));
+        if let Some(version) = version {
+            let version = version.trim().trim_matches('"');
+            deps.push(DetectedDependency::new(Ecosystem::Npm, name, source).resolved(version));
+            current = None;
+        }
+    }
+
+    deps
+}
+
+fn parse_pnpm_lock(content: &str, source: &Path) -> Option<Vec<DetectedDependency>> {
+    let value: serde_yaml::Value = serde_yaml::from_str(content).ok()?;
+    let packages = value.get("packages")?.as_mapping()?;
+    let mut deps = Vec::new();
+
+    for key in packages.keys().filter_map(serde_yaml::Value::as_str) {
+        // v9: `name@1.2.3`, v6: `/name@1.2.3`, v5: `/name/1.2.3`;
+        // peer suffixes look like `(react@18.2.0)`.
+        let key = key.trim_start_matches('/');
+        let key = key.split('(').next().unwrap_or(key);
+        let split = key.get(1..).and_then(|rest| rest.rfind('@')).map_or_else(
+            || key.rfind('/').map(|idx| (&key[..idx], &key[idx + 1..])),
+            |idx| Some((&key[..=idx], &key[idx + 2..])),
+        );
+        if let Some((name, version)) = split {
+            deps.push(DetectedDependency::new(Ecosystem::Npm, name, source).resolved(version));
+        }
+    }
+
+    Some(deps)
+}
+
+/// Extract the package name from an npm spec such as `@scope/pkg@^1.0.0`
+/// or `pkg@npm:^2.0.0`.
+fn npm_spec_name(spec: &str) -> Option<&str> {
+    if spec.is_empty() || spec.starts_with("__metadata") {
+        return None;
+    }
+    let (idx, _) = spec.char_indices().skip(1).find(|&(_, c)| c == '@')?;
+    Some(&spec[..idx])
+}
+
+// =============================================================================
+// Python
+// =============================================================================
+
+fn parse_pyproject(content: &str, source: &Path) -> Option<Vec<DetectedDependency>> {
+    let value: toml::Value = toml::from_str(content).ok()?;
+    let mut deps = Vec::new();
+
+    // PEP 621
+    if let Some(project) = value.get("project") {
+        let mut specs: Vec<&str> = project
+            .get("dependencies")
+            .and_then(toml::Value::as_array)
+            .map(|a| a.iter().filter_map(toml::Value::as_str).collect())
+            .unwrap_or_default();
+        if let Some(optional) = project
+            .get("optional-dependencies")
+            .and_then(toml::Value::as_table)
+        {
+            for group in optional.values().filter_map(toml::Value::as_array) {
+                specs.extend(group.iter().filter_map(toml::Value::as_str));
+            }
+        }
+        deps.extend(specs.into_iter().filter_map(|s| parse_pep508(s, source)));
+    }
+
+    // Poetry
+    if let Some(poetry) = value.get("tool").and_then(|t| t.get("poetry")) {
+        let mut tables: Vec<&toml::Value> = Vec::new();
+        tables.extend(poetry.get("dependencies"));
+        tables.extend(poetry.get("dev-dependencies"));
+        if let Some(groups) = poetry.get("group").and_then(toml::Value::as_table) {
+            tables.extend(groups.values().filter_map(|g| g.get("dependencies")));
+        }
+        for table in tables.into_iter().filter_map(toml::Value::as_table) {
+            for (name, spec) in table {
+                if name.eq_ignore_ascii_case("python") {
+                    continue;
+                }
+                let requirement = match spec {
+                    toml::Value::String(req) => Some(req.as_str()),
+                    toml::Value::Table(t) => t.get("version").and_then(toml::Value::as_str),
+                    _ => None,
+                };
+                deps.push(
+                    DetectedDependency::new(Ecosystem::PyPI, name, source)
+                        .with_requirement(requirement),
+                );
+            }
+        }
+    }
+
+    Some(deps)
+}
+
+fn parse_requirements_txt(content: &str, source: &Path) -> Vec<DetectedDependency> {
+    content
+        .lines()
+        .map(|line| line.split(" #").next().unwrap_or(line).trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with('-'))
+        .filter_map(|line| parse_pep508(line, source))
+        .collect()
+}
+
+fn parse_pipfile_lock(content: &str, source: &Path) -> Option<Vec<DetectedDependency>> {
+    let value: serde_json::Value = serde_json::from_str(content).ok()?;
+    let mut deps = Vec::new();
+
+    for section in ["default", "develop"] {
+        let Some(map) = value.get(section).and_then(|v| v.as_object()) else {
+            continue;
+        };
+        for (name, pkg) in map {
+            if let Some(version) = pkg.get("version").and_then(|v| v.as_str()) {
+                let version = version.trim_start_matches("==");
+                deps.push(DetectedDependency::new(Ecosystem::PyPI, name, source).resolved(version));
+            }
+        }
+    }
+
+    Some(deps)
+}
+
+static PEP508_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^([A-Za-z0-9][A-Za-z0-9._-]*)\s*(?:\[[^\]]*\])?\s*([^;@]*)").unwrap()
+});
+
+/// Parse a PEP 508 requirement such as `requests[socks]>=2.0; python_version<"3.8"`.
+fn parse_pep508(spec: &str, source: &Path) -> Option<DetectedDependency> {
+    let caps = PEP508_RE.captures(spec.trim())?;
+    let name = caps.get(1)?.as_str();
+    let requirement = caps.get(2).map(|m| m.as_str().trim());
+    let mut dep =
+        DetectedDependency::new(Ecosystem::PyPI, name, source).with_requirement(requirement);
+    if let Some(pinned) = requirement.and_then(|r| r.strip_prefix("==")) {
+        if !pinned.contains(',') {
+            dep.version = Some(pinned.trim().to_string());
+        }
+    }
+    Some(dep)
+}
+
+// =============================================================================
+// Go
+// =============================================================================
+
+fn parse_go_mod(content: &str, source: &Path) -> Vec<DetectedDependency> {
+    let mut deps = Vec::new();
+    let mut in_block = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        let entry = if in_block {
+            if trimmed.starts_with(')') {
+                in_block = false;
+                continue;
+            }
+            trimmed
+        } else if trimmed == "require (" || trimmed == "require(" {
+            in_block = true;
+            continue;
+        } else if let Some(rest) = trimmed.strip_prefix("require ") {
+            rest.trim()
+        } else {
+            continue;
+        };
+
+        let (entry, comment) = entry
+            .split_once("//")
+            .map_or((entry, ""), |(e, c)| (e.trim(), c.trim()));
+        let mut parts = entry.split_whitespace();
+        let (Some(module), Some(version)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        let mut dep = DetectedDependency::new(Ecosystem::Go, module, source).resolved(version);
+        dep.direct = comment != "indirect";
+        deps.push(dep);
+    }
+
+    deps
+}
+
+// =============================================================================
+// Ruby
+// =============================================================================
+
+static GEMFILE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"^\s*gem\s+['"]([^'"]+)['"](?:\s*,\s*['"]([^'"]+)['"])?"#).unwrap()
+});
+
+static GEMFILE_LOCK_SPEC_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^    ([A-Za-z0-9_.-]+) \(([^)]+)\)$").unwrap());
+
+fn parse_gemfile(content: &str, source: &Path) -> Vec<DetectedDependency> {
+    content
+        .lines()
+        .filter_map(|line| GEMFILE_RE.captures(line))
+        .filter_map(|caps| {
+            let name = caps.get(1)?.as_str();
+            Some(
+                DetectedDependency::new(Ecosystem::RubyGems, name, source)
+                    .with_requirement(caps.get(2).map(|m| m.as_str())),
+            )
+        })
+        .collect()
+}
+
+fn parse_gemfile_lock(content: &str, source: &Path) -> Vec<DetectedDependency> {
+    content
+        .lines()
+        .filter_map(|line| GEMFILE_LOCK_SPEC_RE.captures(line))
+        .filter_map(|caps| {
+            let name = caps.get(1)?.as_str();
+            let version = caps.get(2)?.as_str();
+            Some(DetectedDependency::new(Ecosystem::RubyGems, name, source).resolved(version))
+        })
+        .collect()
+}
+
+// =============================================================================
+// Versions
+// =============================================================================
+
+static VERSION_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(\d+)(?:\.(\d+))?(?:\.(\d+))?([-+][0-9A-Za-z.+-]+)?").unwrap());
+
+/// Parse a version leniently: accepts `v` prefixes, missing minor/patch
+/// components and leading requirement operators (`^1.2`, `>=2.0`, `~> 3.1`).
+#[must_use]
+pub fn lenient_version(value: &str) -> Option<semver::Version> {
+    let value = value.trim().trim_start_matches('v');
+    if let Ok(version) = semver::Version::parse(value) {
+        return Some(version);
+    }
+    let caps = VERSION_RE.captures(value)?;
+    let part = |i: usize| {
+        caps.get(i)
+            .and_then(|m| m.as_str().parse::<u64>().ok())
+            .unwrap_or(0)
+    };
+    let mut version = semver::Version::new(part(1), part(2), part(3));
+    if let Some(pre) = caps
+        .get(4)
+        .and_then(|m| m.as_str().strip_prefix('-'))
+        .and_then(|p| semver::Prerelease::new(p).ok())
+    {
+        version.pre = pre;
+    }
+    Some(version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn find<'a>(deps: &'a [DetectedDependency], name: &str) -> &'a DetectedDependency {
+        deps.iter()
+            .find(|d| d.name == name)
+            .unwrap_or_else(|| panic!("dependency {name} not found"))
+    }
+
+    #[test]
+    fn parses_cargo_toml_and_lock() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            r#"
+[package]
+name = "demo"
+
+[dependencies]
+tokio = { version = "1.35", features = ["full"] }
+serde = "1.0"
+renamed = { package = "bevy", version = "0.12" }
+
+[dev-dependencies]
+tempfile = "3"
+"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("Cargo.lock"),
+            r#"
+[[package]]
+name = "tokio"
+version = "1.36.0"
+
+[[package]]
+name = "mio"
+version = "0.8.10"
+"#,
+        )
+        .unwrap();
+
+        let deps = detect_dependencies(dir.path());
+        let tokio = find(&deps, "tokio");
+        assert_eq!(tokio.ecosystem, Ecosystem::Cargo);
+        assert_eq!(tokio.requirement.as_deref(), Some("1.35"));
+        assert_eq!(tokio.version.as_deref(), Some("1.36.0"));
+        assert!(tokio.direct);
+        assert!(!find(&deps, "mio").direct);
+        assert!(deps.iter().any(|d| d.name == "bevy"));
+        assert!(deps.iter().any(|d| d.name == "tempfile"));
+    }
+
+    #[test]
+    fn parses_package_json_and_lockfiles() {
+        let dir = TempDir::new().unwrap();
+        let manifest = dir.path().join("package.json");
+        fs::write(
+            &manifest,
+            r#"{"dependencies": {"react": "^18.2.0"}, "devDependencies": {"@types/node": "20.x"}}"#,
+        )
+        .unwrap();
+        let deps = parse_manifest(&manifest).unwrap();
+        assert_eq!(find(&deps, "react").requirement.as_deref(), Some("^18.2.0"));
+        assert!(deps.iter().any(|d| d.name == "@types/node"));
+
+        let lock = dir.path().join("package-lock.json");
+        fs::write(
+            &lock,
+            r#"{"lockfileVersion": 3, "packages": {
+                "": {"name": "demo"},
+                "node_modules/react": {"version": "18.2.0"},
+                "node_modules/a/node_modules/@scope/b": {"version": "1.0.0"}
+            }}"#,
+        )
+        .unwrap();
+        let deps = parse_manifest(&lock).unwrap();
+        assert_eq!(find(&deps, "react").version.as_deref(), Some("18.2.0"));
+        assert_eq!(find(&deps, "@scope/b").version.as_deref(), Some("1.0.0"));
+
+        let yarn = dir.path().join("yarn.lock");
+        fs::write(
+            &yarn,
+            "# yarn lockfile v1\n\n\"@babel/core@^7.0.0\", \"@babel/core@^7.1.0\":\n  version \"7.23.0\"\n\nlodash@^4.17.21:\n  version \"4.17.21\"\n",
+        )
+        .unwrap();
+        let deps = parse_manifest(&yarn).unwrap();
+        assert_eq!(
+            find(&deps, "@babel/core").version.as_deref(),
+            Some("7.23.0")
+        );
+        assert_eq!(find(&deps, "lodash").version.as_deref(), Some("4.17.21"));
+
+        fs::write(
+            &yarn,
+            "\"é-utils@^1.0.0\":\n  version \"1.0.2\"\n\n\"ü\":\n  version \"0.1.0\"\n",
+        )
+        .unwrap();
+        let deps = parse_manifest(&yarn).unwrap();
+        assert_eq!(find(&deps, "é-utils").version.as_deref(), Some("1.0.2"));
+        assert_eq!(deps.len(), 1);
+
+        let pnpm = dir.path().join("pnpm-lock.yaml");
+        fs::write(
+            &pnpm,
+            "lockfileVersion: '6.0'\npackages:\n  /vite@5.0.0(@types/node@20.0.0):\n    resolution: {}\n  /@scope/pkg@1.2.3:\n    resolution: {}\n",
+        )
+        .unwrap();
+        let deps = parse_manifest(&pnpm).unwrap();
+        assert_eq!(find(&deps, "vite").version.as_deref(), Some("5.0.0"));
+        assert_eq!(find(&deps, "@scope/pkg").version.as_deref(), Some("1.2.3"));
+    }
+
+    #[test]
+    fn parses_python_manifests() {
+        let dir = TempDir::new().unwrap();
+        let pyproject = dir.path().join("pyproject.toml");
+        fs::write(
+            &pyproject,
+            r#"
+[project]
+dependencies = ["requests[socks]>=2.31; python_version >= '3.8'", "Django==4.2.7"]
+
+[tool.poetry.dependencies]
+python = "^3.11"
+fastapi = { version = "^0.110" }
+"#,
+        )
+        .unwrap();
+        let deps = parse_manifest(&pyproject).unwrap();
+        assert_eq!(
+            find(&deps, "requests").requirement.as_deref(),
+            Some(">=2.31")
+        );
+        assert_eq!(find(&deps, "Django").version.as_deref(), Some("4.2.7"));
+        assert_eq!(
+            find(&deps, "fastapi").requirement.as_deref(),
+            Some("^0.110")
+        );
+        assert!(!deps.iter().any(|d| d.name == "python"));
+
+        let requirements = dir.path().join("requirements.txt");
+        fs::write(
+            &requirements,
+            "# comment\n-r base.txt\nnumpy==1.26.0  # pinned\nPyYAML\n",
+        )
+        .unwrap();
+        let deps = parse_manifest(&requirements).unwrap();
+        assert_eq!(deps.len(), 2);
+        assert_eq!(find(&deps, "numpy").version.as_deref(), Some("1.26.0"));
+        assert_eq!(
+            find(&deps, "PyYAML").key(),
+            (Ecosystem::PyPI, "pyyaml".to_string())
+        );
+    }
+
+    #[test]
+    fn parses_go_mod() {
+        let dir = TempDir::new().unwrap();
+        let go_mod = dir.path().join("go.mod");
+        fs::write(
+            &go_mod,
+            "module example.com/app\n\ngo 1.22\n\nrequire github.com/spf13/cobra v1.8.0\n\nrequire (\n\tgithub.com/gin-gonic/gin v1.9.1\n\tgolang.org/x/sys v0.15.0 // indirect\n)\n",
+        )
+        .unwrap();
+        let deps = parse_manifest(&go_mod).unwrap();
+        assert_eq!(deps.len(), 3);
+        let gin = find(&deps, "github.com/gin-gonic/gin");
+        assert_eq!(gin.version.as_deref(), Some("1.9.1"));
+        assert!(gin.direct);
+        assert!(!find(&deps, "golang.org/x/sys").direct);
+    }
+
+    #[test]
+    fn parses_gemfile_and_lock() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("Gemfile"),
+            "source 'https://rubygems.org'\ngem 'rails', '~> 7.1'\ngem \"puma\"\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("Gemfile.lock"),
+            "GEM\n  remote: https://rubygems.org/\n  specs:\n    rails (7.1.2)\n      actionpack (= 7.1.2)\n    puma (6.4.0)\n",
+        )
+        .unwrap();
+        let deps = detect_dependencies(dir.path());
+        let rails = find(&deps, "rails");
+        assert_eq!(rails.requirement.as_deref(), Some("~> 7.1"));
+        assert_eq!(rails.version.as_deref(), Some("7.1.2"));
+        assert!(rails.direct);
+        assert!(!deps.iter().any(|d| d.name == "actionpack"));
+    }
+
+    #[test]
+    fn lenient_version_parsing() {
+        assert_eq!(
+            lenient_version("1.2.3"),
+            Some(semver::Version::new(1, 2, 3))
+        );
+        assert_eq!(
+            lenient_version("v0.15"),
+            Some(semver::Version::new(0, 15, 0))
+        );
+        assert_eq!(
+            lenient_version("^18.2"),
+            Some(semver::Version::new(18, 2, 0))
+        );
+        assert_eq!(
+            lenient_version("~> 7.1"),
+            Some(semver::Version::new(7, 1, 0))
+        );
+        assert_eq!(lenient_version("latest"), None);
+    }
+
+    #[test]
+    fn ecosystem_aliases() {
+        assert_eq!(Ecosystem::from_alias("rust"), Some(Ecosystem::Cargo));
+        assert_eq!(Ecosystem::from_alias("Node"), Some(Ecosystem::Npm));
+        assert_eq!(Ecosystem::from_alias("pip"), Some(Ecosystem::PyPI));
+        assert_eq!(Ecosystem::from_alias("cobol"), None);
+    }
+}
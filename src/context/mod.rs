@@ -4,7 +4,9 @@ pub mod capture;
 pub mod collector;
 pub mod detector;
 pub mod fingerprint;
+pub mod manifest;
 pub mod scoring;
+pub mod workspace;
 
 pub use capture::{CaptureError, ContextCapture};
 pub use collector::{
@@ -13,6 +15,8 @@ pub use collector::{
 };
 pub use detector::{DefaultDetector, DetectedProject, ProjectDetector, ProjectMarker, ProjectType};
 pub use fingerprint::{ChangeSignificance, ContextFingerprint};
+pub use manifest::{DetectedDependency, Ecosystem, detect_dependencies, parse_manifest};
 pub use scoring::{
     RankedSkill, RelevanceScorer, ScoreBreakdown, ScoringWeights, WorkingContext as ScoringContext,
 };
+pub use workspace::{MemberSource, Subproject, WorkspaceDetector, WorkspaceLayout};
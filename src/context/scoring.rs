@@ -5,9 +5,11 @@
 
 use std::collections::HashSet;
 
-use crate::core::skill::{ContextTags, SkillMetadata};
+use crate::core::skill::{ContextTags, DependencyTag, SkillMetadata};
 
 use super::detector::{DetectedProject, ProjectType};
+use super::manifest::{DetectedDependency, Ecosystem};
+use super::workspace::{Subproject, WorkspaceLayout};
 
 /// Working context captured from the current environment.
 ///
@@ -23,6 +25,12 @@ pub struct WorkingContext {
     pub detected_tools: HashSet<String>,
     /// Content snippets for signal matching (file content, command history, etc.).
     pub content_snippets: Vec<String>,
+    /// Dependencies parsed from project manifests and lockfiles.
+    pub dependencies: Vec<DetectedDependency>,
+    /// Workspace members, sorted by relevance weight (monorepos).
+    pub subprojects: Vec<Subproject>,
+    /// The subproject the user is most likely working in.
+    pub active_subproject: Option<Subproject>,
 }
 
 impl WorkingContext {
@@ -59,6 +67,56 @@ impl WorkingContext {
         self
     }
 
+    /// Add dependencies detected from project manifests.
+    #[must_use]
+    pub fn with_dependencies(mut self, dependencies: Vec<DetectedDependency>) -> Self {
+        self.dependencies = dependencies;
+        self
+    }
+
+    /// Add workspace members and the active subproject from a workspace layout.
+    #[must_use]
+    pub fn with_workspace(mut self, workspace: &WorkspaceLayout) -> Self {
+        self.subprojects.clone_from(&workspace.subprojects);
+        self.active_subproject = workspace.active().cloned();
+        self
+    }
+
+    /// Find the most relevant subproject whose project types or dependencies
+    /// match a skill's context tags.
+    #[must_use]
+    pub fn matching_subproject(&self, tags: &ContextTags) -> Option<&Subproject> {
+        self.subprojects.iter().find(|sub| {
+            let type_match = sub.projects.iter().any(|p| {
+                tags.project_types
+                    .iter()
+                    .any(|pt| pt.eq_ignore_ascii_case(p.project_type.id()))
+            });
+            let dep_match = tags.dependencies.iter().any(|tag| {
+                sub.dependencies
+                    .iter()
+                    .any(|dep| dep.ecosystem.normalize_name(&tag.name) == dep.key().1)
+            });
+            type_match || dep_match
+        })
+    }
+
+    /// Find the detected dependency matching a skill's dependency tag by
+    /// name and (if given) ecosystem.
+    #[must_use]
+    pub fn find_dependency(&self, tag: &DependencyTag) -> Option<&DetectedDependency> {
+        let ecosystem = tag.ecosystem.as_deref().map(Ecosystem::from_alias);
+        self.dependencies.iter().find(|dep| {
+            let ecosystem_ok = match ecosystem {
+                None => true,
+                Some(Some(eco)) => eco == dep.ecosystem,
+                // Unknown ecosystem names never match
+                Some(None) => false,
+            };
+            ecosystem_ok && dep.ecosystem.normalize_name(&tag.name) == dep.key().1
+        })
+    }
+
     /// Check if any content matches a signal pattern.
     #[must_use]
     pub fn matches_signal(&self, pattern: &str) -> bool {
@@ -96,22 +154,27 @@ pub struct ScoringWeights {
     pub signals: f32,
     /// Weight for historical affinity (default: 0.05).
     pub historical: f32,
+    /// Weight for manifest dependency matching (default: 0.15).
+    pub dependencies: f32,
 }
 
 impl Default for ScoringWeights {
     fn default() -> Self {
         Self {
-            project_type: 0.40,
-            file_patterns: 0.25,
-            tools: 0.20,
+            project_type: 0.35,
+            file_patterns: 0.20,
+            tools: 0.15,
             signals: 0.10,
             historical: 0.05,
+            dependencies: 0.15,
         }
     }
 }
 
 impl ScoringWeights {
     /// Create weights with custom values.
+    ///
+    /// The dependency weight starts at zero; set it with [`Self::with_dependencies`].
     #[must_use]
     pub const fn new(
         project_type: f32,
@@ -126,14 +189,26 @@ impl ScoringWeights {
             tools,
             signals,
             historical,
+            dependencies: 0.0,
         }
     }
 
+    /// Set the weight for manifest dependency matching.
+    #[must_use]
+    pub const fn with_dependencies(mut self, dependencies: f32) -> Self {
+        self.dependencies = dependencies;
+        self
+    }
+
     /// Normalize weights to sum to 1.0.
     #[must_use]
     pub fn normalized(&self) -> Self {
-        let sum =
-            self.project_type + self.file_patterns + self.tools + self.signals + self.historical;
+        let sum = self.project_type
+            + self.file_patterns
+            + self.tools
+            + self.signals
+            + self.historical
+            + self.dependencies;
         if sum == 0.0 {
             return self.clone();
         }
@@ -143,6 +218,7 @@ impl ScoringWeights {
             tools: self.tools / sum,
             signals: self.signals / sum,
             historical: self.historical / sum,
+            dependencies: self.dependencies / sum,
         }
     }
 }
@@ -160,6 +236,10 @@ pub struct ScoreBreakdown {
     pub signals: f32,
     /// Historical affinity score (0.0-1.0).
     pub historical: f32,
+    /// Dependency match score (0.0-1.0).
+    pub dependencies: f32,
+    /// Skill declares dependencies but none of them are used by the project.
+    pub dependency_mismatch: bool,
 }
 
 /// A skill with its relevance score and breakdown.
@@ -173,12 +253,16 @@ pub struct RankedSkill {
     pub score: f32,
     /// Breakdown of individual components.
     pub breakdown: ScoreBreakdown,
+    /// Workspace member that matched the skill (monorepos only).
+    pub subproject: Option<String>,
 }
 
 /// Relevance scorer for ranking skills by context match.
 #[derive(Debug, Clone)]
 pub struct RelevanceScorer {
     weights: ScoringWeights,
+    /// Zero the score of library skills whose dependencies the project doesn't use.
+    dependency_gate: bool,
 }
 
 impl Default for RelevanceScorer {
@@ -193,9 +277,20 @@ impl RelevanceScorer {
     pub fn new(weights: ScoringWeights) -> Self {
         Self {
             weights: weights.normalized(),
+            dependency_gate: true,
         }
     }
 
+    /// Enable or disable dependency gating (enabled by default).
+    ///
+    /// When enabled, a skill that declares `context.dependencies` scores zero
+    /// unless the project actually depends on at least one of them.
+    #[must_use]
+    pub const fn with_dependency_gate(mut self, enabled: bool) -> Self {
+        self.dependency_gate = enabled;
+        self
+    }
+
     /// Get the scoring weights.
     #[must_use]
     pub const fn weights(&self) -> &ScoringWeights {
@@ -212,26 +307,35 @@ impl RelevanceScorer {
     /// Get the score breakdown for a skill.
     #[must_use]
     pub fn breakdown(&self, metadata: &SkillMetadata, context: &WorkingContext) -> ScoreBreakdown {
+        let dependencies = self.dependency_match(&metadata.context, context);
         ScoreBreakdown {
             project_type: self.project_type_match(&metadata.context, context),
             file_patterns: self.file_pattern_match(&metadata.context, context),
             tools: self.tool_match(&metadata.context, context),
             signals: self.signal_match(&metadata.context, context),
             historical: 0.0, // TODO: Connect to recommendation engine
+            dependencies,
+            dependency_mismatch: !metadata.context.dependencies.is_empty() && dependencies == 0.0,
         }
     }
 
     /// Compute weighted score from breakdown.
     fn weighted_score(&self, breakdown: &ScoreBreakdown) -> f32 {
-        self.weights.historical.mul_add(
-            breakdown.historical,
-            self.weights.signals.mul_add(
-                breakdown.signals,
-                self.weights.tools.mul_add(
-                    breakdown.tools,
-                    self.weights.project_type.mul_add(
-                        breakdown.project_type,
-                        self.weights.file_patterns * breakdown.file_patterns,
+        if self.dependency_gate && breakdown.dependency_mismatch {
+            return 0.0;
+        }
+        self.weights.dependencies.mul_add(
+            breakdown.dependencies,
+            self.weights.historical.mul_add(
+                breakdown.historical,
+                self.weights.signals.mul_add(
+                    breakdown.signals,
+                    self.weights.tools.mul_add(
+                        breakdown.tools,
+                        self.weights.project_type.mul_add(
+                            breakdown.project_type,
+                            self.weights.file_patterns * breakdown.file_patterns,
+                        ),
                     ),
                 ),
             ),
@@ -246,11 +350,19 @@ impl RelevanceScorer {
             .map(|s| {
                 let breakdown = self.breakdown(s, context);
                 let score = self.weighted_score(&breakdown);
+                let subproject = if context.subprojects.len() > 1 && score > 0.0 {
+                    context
+                        .matching_subproject(&s.context)
+                        .map(|sub| sub.name.clone())
+                } else {
+                    None
+                };
                 RankedSkill {
                     skill_id: s.id.clone(),
                     skill_name: s.name.clone(),
                     score,
                     breakdown,
+                    subproject,
                 }
             })
             .collect();
@@ -378,6 +490,46 @@ impl RelevanceScorer {
             (weighted_sum / max_possible).min(1.0)
         }
     }
+
+    /// Match skill's dependency tags against dependencies parsed from manifests.
+    ///
+    /// Each tag contributes 1.0 when the project uses the package in a version
+    /// satisfying the tag's range, 0.75 when the version can't be determined,
+    /// and nothing when the version falls outside the range. Transitive-only
+    /// dependencies count half.
+    fn dependency_match(&self, skill_context: &ContextTags, context: &WorkingContext) -> f32 {
+        if skill_context.dependencies.is_empty() {
+            return 0.0;
+        }
+
+        let total: f32 = skill_context
+            .dependencies
+            .iter()
+            .map(|tag| {
+                let Some(dep) = context.find_dependency(tag) else {
+                    return 0.0;
+                };
+                let version_score = match (tag.version_req(), dep.effective_version()) {
+                    (None, _) => 1.0,
+                    (Some(req), Some(version)) => {
+                        if req.matches(&version) {
+                            1.0
+                        } else {
+                            0.0
+                        }
+                    }
+                    (Some(_), None) => 0.75,
+                };
+                if dep.direct {
+                    version_score
+                } else {
+                    version_score * 0.5
+                }
+            })
+            .sum();
+
+        (total / skill_context.dependencies.len() as f32).min(1.0)
+    }
 }
 
 #[cfg(test)]
@@ -386,6 +538,34 @@ mod tests {
     use crate::core::skill::ContextSignal;
     use std::path::PathBuf;
 
+    fn sample_tokio_skill() -> SkillMetadata {
+        SkillMetadata {
+            id: "tokio-runtime".to_string(),
+            name: "Tokio Runtime".to_string(),
+            context: ContextTags {
+                project_types: vec!["rust".to_string()],
+                dependencies: vec![
+                    DependencyTag::new("tokio")
+                        .in_ecosystem("cargo")
+                        .with_version(">=1.0"),
+                ],
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn cargo_dependency(name: &str, version: Option<&str>, direct: bool) -> DetectedDependency {
+        DetectedDependency {
+            ecosystem: Ecosystem::Cargo,
+            name: name.to_string(),
+            version: version.map(String::from),
+            requirement: None,
+            direct,
+            source: PathBuf::from("Cargo.lock"),
+        }
+    }
+
     fn sample_rust_skill() -> SkillMetadata {
         SkillMetadata {
             id: "rust-errors".to_string(),
@@ -395,6 +575,7 @@ mod tests {
                 file_patterns: vec!["*.rs".to_string(), "Cargo.toml".to_string()],
                 tools: vec!["cargo".to_string(), "rustc".to_string()],
                 signals: vec![ContextSignal::new("thiserror", "use.*thiserror", 0.8)],
+                dependencies: vec![],
             },
             ..Default::default()
         }
@@ -409,6 +590,7 @@ mod tests {
                 file_patterns: vec!["*.ts".to_string(), "*.js".to_string()],
                 tools: vec!["npm".to_string(), "node".to_string()],
                 signals: vec![],
+                dependencies: vec![],
             },
             ..Default::default()
         }
@@ -457,7 +639,8 @@ mod tests {
         let scorer = RelevanceScorer::default();
         let w = scorer.weights();
         // Weights should sum to approximately 1.0 after normalization
-        let sum = w.project_type + w.file_patterns + w.tools + w.signals + w.historical;
+        let sum =
+            w.project_type + w.file_patterns + w.tools + w.signals + w.historical + w.dependencies;
         assert!((sum - 1.0).abs() < 0.001);
     }
 
@@ -648,4 +831,129 @@ mod tests {
         assert!(breakdown.tools > 0.0);
         assert!(breakdown.signals > 0.0);
     }
+
+    #[test]
+    fn test_dependency_match_in_range() {
+        let scorer = RelevanceScorer::default();
+        let skill = sample_tokio_skill();
+        let context =
+            rust_context().with_dependencies(vec![cargo_dependency("tokio", Some("1.36.0"), true)]);
+
+        let breakdown = scorer.breakdown(&skill, &context);
+        assert!((breakdown.dependencies - 1.0).abs() < 0.001);
+        assert!(!breakdown.dependency_mismatch);
+        assert!(scorer.score(&skill, &context) > 0.4);
+    }
+
+    #[test]
+    fn test_dependency_match_out_of_range() {
+        let scorer = RelevanceScorer::default();
+        let skill = sample_tokio_skill();
+        let context =
+            rust_context().with_dependencies(vec![cargo_dependency("tokio", Some("0.2.25"), true)]);
+
+        let breakdown = scorer.breakdown(&skill, &context);
+        assert!(breakdown.dependencies < 0.001);
+        assert!(breakdown.dependency_mismatch);
+    }
+
+    #[test]
+    fn test_dependency_gate_blocks_unused_library_skill() {
+        let scorer = RelevanceScorer::default();
+        let skill = sample_tokio_skill();
+        // A Rust project that doesn't use tokio
+        let context =
+            rust_context().with_dependencies(vec![cargo_dependency("serde", Some("1.0.0"), true)]);
+
+        assert!(scorer.score(&skill, &context) < 0.001);
+
+        let ungated = RelevanceScorer::default().with_dependency_gate(false);
+        assert!(ungated.score(&skill, &context) > 0.3);
+    }
+
+    #[test]
+    fn test_dependency_match_transitive_and_unknown_version() {
+        let scorer = RelevanceScorer::default();
+        let skill = sample_tokio_skill();
+
+        let transitive =
+            rust_context().with_dependencies(vec![cargo_dependency("tokio", Some("1.0.0"), false)]);
+        let breakdown = scorer.breakdown(&skill, &transitive);
+        assert!((breakdown.dependencies - 0.5).abs() < 0.001);
+
+        let unknown = rust_context().with_dependencies(vec![cargo_dependency("tokio", None, true)]);
+        let breakdown = scorer.breakdown(&skill, &unknown);
+        assert!((breakdown.dependencies - 0.75).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_dependency_ecosystem_must_match() {
+        let context = WorkingContext::new().with_dependencies(vec![DetectedDependency {
+            ecosystem: Ecosystem::Npm,
+            name: "tokio".to_string(),
+            version: None,
+            requirement: None,
+            direct: true,
+            source: PathBuf::from("package.json"),
+        }]);
+
+        assert!(
+            context
+                .find_dependency(&DependencyTag::new("tokio"))
+                .is_some()
+        );
+        assert!(
+            context
+                .find_dependency(&DependencyTag::new("tokio").in_ecosystem("rust"))
+                .is_none()
+        );
+        assert!(
+            context
+                .find_dependency(&DependencyTag::new("tokio").in_ecosystem("node"))
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_rank_attributes_monorepo_subproject() {
+        use crate::context::workspace::MemberSource;
+
+        let subproject = |name: &str, project_type: ProjectType, weight: f32| Subproject {
+            name: name.to_string(),
+            root: PathBuf::from("/repo").join(name),
+            source: MemberSource::Nested,
+            projects: vec![DetectedProject {
+                project_type,
+                confidence: 1.0,
+                marker_path: PathBuf::from("/repo").join(name),
+                marker_pattern: "marker".to_string(),
+            }],
+            dependencies: vec![],
+            cwd_distance: 0,
+            recent_files: 0,
+            weight,
+        };
+        let layout = WorkspaceLayout {
+            root: PathBuf::from("/repo"),
+            subprojects: vec![
+                subproject("services/api", ProjectType::Rust, 1.0),
+                subproject("apps/web", ProjectType::Node, 0.3),
+            ],
+        };
+        let context = WorkingContext::new()
+            .with_projects(layout.weighted_projects())
+            .with_workspace(&layout);
+
+        assert_eq!(
+            context.active_subproject.as_ref().map(|s| s.name.as_str()),
+            Some("services/api")
+        );
+
+        let scorer = RelevanceScorer::default();
+        let ranked = scorer.rank(&[sample_rust_skill(), sample_node_skill()], &context);
+        assert_eq!(ranked[0].skill_id, "rust-errors");
+        assert_eq!(ranked[0].subproject.as_deref(), Some("services/api"));
+        assert_eq!(ranked[1].subproject.as_deref(), Some("apps/web"));
+        assert!(ranked[0].breakdown.project_type > ranked[1].breakdown.project_type);
+    }
 }
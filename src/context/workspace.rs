@@ -0,0 +1,651 @@
+//! Monorepo-aware detection of workspace members.
+//!
+//! A single cwd rarely tells the whole story in a monorepo: a Go service, a
+//! TypeScript frontend and Python tooling can all live under one git root.
+//! This module finds the workspace root, enumerates its members (Cargo
+//! workspaces, npm/yarn/pnpm workspaces, `go.work`, and any nested directory
+//! with project markers), and weights each member by its distance to the cwd
+//! and by how many recently edited files it contains.
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::detector::{DetectedProject, ProjectDetector, ProjectType};
+use super::manifest::{DetectedDependency, detect_dependencies};
+
+/// How a subproject was discovered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemberSource {
+    /// The workspace root itself.
+    Root,
+    /// `[workspace] members` in `Cargo.toml`.
+    CargoWorkspace,
+    /// `workspaces` in `package.json` (npm / yarn).
+    NpmWorkspace,
+    /// `packages` in `pnpm-workspace.yaml`.
+    PnpmWorkspace,
+    /// `use` directives in `go.work`.
+    GoWork,
+    /// A nested directory containing project markers.
+    Nested,
+}
+
+/// A project inside a (possibly multi-project) workspace.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Subproject {
+    /// Display name: path relative to the workspace root ("." for the root).
+    pub name: String,
+    /// Absolute path of the subproject directory.
+    pub root: PathBuf,
+    /// How this subproject was discovered.
+    pub source: MemberSource,
+    /// Project types detected in the subproject directory.
+    pub projects: Vec<DetectedProject>,
+    /// Dependencies declared by the subproject's manifests.
+    #[serde(default)]
+    pub dependencies: Vec<DetectedDependency>,
+    /// Tree distance (directory hops) between the cwd and the subproject root.
+    pub cwd_distance: usize,
+    /// Number of recently edited files that belong to this subproject.
+    pub recent_files: usize,
+    /// Relevance weight (0.0-1.0) combining cwd distance and recent edits.
+    pub weight: f32,
+}
+
+impl Subproject {
+    /// Check whether a path lies inside this subproject.
+    #[must_use]
+    pub fn contains(&self, path: &Path) -> bool {
+        path.starts_with(&self.root)
+    }
+
+    /// Project type ids detected in this subproject (deduplicated).
+    #[must_use]
+    pub fn project_type_ids(&self) -> Vec<&'static str> {
+        let ids: BTreeSet<&'static str> =
+            self.projects.iter().map(|p| p.project_type.id()).collect();
+        ids.into_iter().collect()
+    }
+}
+
+/// The workspace around the cwd and its weighted members.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorkspaceLayout {
+    /// Workspace root (repository root or outermost workspace manifest).
+    pub root: PathBuf,
+    /// All subprojects, sorted by weight descending.
+    pub subprojects: Vec<Subproject>,
+}
+
+impl WorkspaceLayout {
+    /// The subproject the user is most likely working in.
+    #[must_use]
+    pub fn active(&self) -> Option<&Subproject> {
+        self.subprojects.first()
+    }
+
+    /// Whether the workspace contains more than one project.
+    #[must_use]
+    pub fn is_monorepo(&self) -> bool {
+        self.subprojects.len() > 1
+    }
+
+    /// Detected projects across all subprojects, with confidence scaled by
+    /// the subproject weight and deduplicated by project type.
+    #[must_use]
+    pub fn weighted_projects(&self) -> Vec<DetectedProject> {
+        let mut best: HashMap<ProjectType, DetectedProject> = HashMap::new();
+        for sub in &self.subprojects {
+            for project in &sub.projects {
+                let confidence = project.confidence * sub.weight;
+                let replace = best
+                    .get(&project.project_type)
+                    .is_none_or(|existing| confidence > existing.confidence);
+                if replace {
+                    best.insert(
+                        project.project_type,
+                        DetectedProject {
+                            confidence,
+                            ..project.clone()
+                        },
+                    );
+                }
+            }
+        }
+
+        let mut projects: Vec<DetectedProject> = best.into_values().collect();
+        projects.sort_by(|a, b| {
+            b.confidence
+                .partial_cmp(&a.confidence)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        projects
+    }
+
+    /// Dependencies relevant to the active subproject: its own manifests
+    /// plus the workspace root (where shared lockfiles usually live).
+    #[must_use]
+    pub fn active_dependencies(&self) -> Vec<DetectedDependency> {
+        let Some(active) = self.active() else {
+            return Vec::new();
+        };
+        let mut deps = active.dependencies.clone();
+        if let Some(root) = self
+            .subprojects
+            .iter()
+            .find(|s| s.source == MemberSource::Root && s.root != active.root)
+        {
+            for dep in &root.dependencies {
+                if let Some(existing) = deps.iter_mut().find(|d| d.key() == dep.key()) {
+                    if existing.version.is_none() {
+                        existing.version.clone_from(&dep.version);
+                    }
+                } else {
+                    let mut shared = dep.clone();
+                    // Root lockfiles list every member's packages; only the
+                    // active member's manifest makes them direct.
+                    shared.direct = false;
+                    deps.push(shared);
+                }
+            }
+        }
+        deps
+    }
+}
+
+/// Discovers workspace members around a working directory.
+#[derive(Debug, Clone)]
+pub struct WorkspaceDetector {
+    /// Maximum directory depth (below the workspace root) to scan for nested projects.
+    pub max_depth: usize,
+    /// Path fragments to skip while scanning.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl WorkspaceDetector {
+    /// Create a detector with the given scan depth and ignore patterns.
+    #[must_use]
+    pub const fn new(max_depth: usize, ignore_patterns: Vec<String>) -> Self {
+        Self {
+            max_depth,
+            ignore_patterns,
+        }
+    }
+
+    /// Discover the workspace containing `cwd` and weight its members.
+    ///
+    /// The workspace root is the outermost directory between `cwd` and the
+    /// enclosing git repository root that declares a workspace (Cargo,
+    /// npm/yarn, pnpm, `go.work`). Without a declaration the git root is used,
+    /// and outside of a git repository only `cwd` itself is scanned.
+    #[must_use]
+    pub fn discover(
+        &self,
+        cwd: &Path,
+        recent_files: &[PathBuf],
+        detector: &dyn ProjectDetector,
+    ) -> WorkspaceLayout {
+        let cwd = cwd.canonicalize().unwrap_or_else(|_| cwd.to_path_buf());
+        let root = find_workspace_root(&cwd);
+
+        // Collect member directories, keeping the most specific source.
+        let mut members: Vec<(PathBuf, MemberSource)> = vec![(root.clone(), MemberSource::Root)];
+        for (dir, source) in declared_members(&root) {
+            if !members.iter().any(|(d, _)| *d == dir) {
+                members.push((dir, source));
+            }
+        }
+        for dir in self.nested_project_dirs(&root, detector) {
+            if !members.iter().any(|(d, _)| *d == dir) {
+                members.push((dir, MemberSource::Nested));
+            }
+        }
+
+        let mut subprojects: Vec<Subproject> = members
+            .into_iter()
+            .filter_map(|(dir, source)| {
+                let projects = detector.detect(&dir);
+                if projects.is_empty() && source != MemberSource::Root {
+                    return None;
+                }
+                let name = dir
+                    .strip_prefix(&root)
+                    .ok()
+                    .map(|rel| rel.to_string_lossy().to_string())
+                    .filter(|rel| !rel.is_empty())
+                    .unwrap_or_else(|| ".".to_string());
+                Some(Subproject {
+                    name,
+                    cwd_distance: tree_distance(&cwd, &dir),
+                    dependencies: detect_dependencies(&dir),
+                    root: dir,
+                    source,
+                    projects,
+                    recent_files: 0,
+                    weight: 0.0,
+                })
+            })
+            .collect();
+
+        // Drop an empty root when real members exist.
+        if subprojects.len() > 1 {
+            subprojects.retain(|s| s.source != MemberSource::Root || !s.projects.is_empty());
+        }
+        if subprojects.iter().all(|s| s.projects.is_empty()) {
+            subprojects.clear();
+        }
+
+        // Attribute each recent file to the deepest subproject containing it.
+        let recent: Vec<PathBuf> = recent_files
+            .iter()
+            .map(|f| f.canonicalize().unwrap_or_else(|_| f.clone()))
+            .collect();
+        for file in &recent {
+            if let Some(owner) = subprojects
+                .iter_mut()
+                .filter(|s| file.starts_with(&s.root))
+                .max_by_key(|s| s.root.components().count())
+            {
+                owner.recent_files += 1;
+            }
+        }
+
+        for sub in &mut subprojects {
+            sub.weight = subproject_weight(sub.cwd_distance, sub.recent_files, recent.len());
+        }
+        subprojects.sort_by(|a, b| {
+            b.weight
+                .partial_cmp(&a.weight)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.cwd_distance.cmp(&b.cwd_distance))
+        });
+
+        WorkspaceLayout { root, subprojects }
+    }
+
+    fn nested_project_dirs(&self, root: &Path, detector: &dyn ProjectDetector) -> Vec<PathBuf> {
+        walkdir::WalkDir::new(root)
+            .min_depth(1)
+            .max_depth(self.max_depth)
+            .follow_links(false)
+            .into_iter()
+            .filter_entry(|e| {
+                let path = e.path().to_string_lossy();
+                !self
+                    .ignore_patterns
+                    .iter()
+                    .any(|p| path.contains(p.as_str()))
+            })
+            .filter_map(std::result::Result::ok)
+            .filter(|e| e.file_type().is_dir())
+            .filter(|e| !detector.detect(e.path()).is_empty())
+            .map(walkdir::DirEntry::into_path)
+            .collect()
+    }
+}
+
+/// Weight a subproject: half proximity to the cwd, half share of recent edits
+/// (proximity only when there are no recent edits).
+fn subproject_weight(cwd_distance: usize, recent_hits: usize, recent_total: usize) -> f32 {
+    let proximity = 1.0 / (1.0 + cwd_distance as f32);
+    if recent_total == 0 {
+        return proximity;
+    }
+    let activity = recent_hits as f32 / recent_total as f32;
+    0.5 * (proximity + activity)
+}
+
+/// Number of directory hops between two paths (up to the common ancestor,
+/// then down).
+fn tree_distance(from: &Path, to: &Path) -> usize {
+    let a: Vec<Component<'_>> = from.components().collect();
+    let b: Vec<Component<'_>> = to.components().collect();
+    let common = a.iter().zip(&b).take_while(|(x, y)| x == y).count();
+    (a.len() - common) + (b.len() - common)
+}
+
+/// Find the outermost workspace declaration between `cwd` and its git root.
+fn find_workspace_root(cwd: &Path) -> PathBuf {
+    let Some(repo_root) = git2::Repository::discover(cwd)
+        .ok()
+        .and_then(|repo| repo.workdir().map(Path::to_path_buf))
+        .map(|p| p.canonicalize().unwrap_or(p))
+    else {
+        return cwd.to_path_buf();
+    };
+
+    let mut outermost = None;
+    for dir in cwd.ancestors() {
+        if !dir.starts_with(&repo_root) {
+            break;
+        }
+        if declares_workspace(dir) {
+            outermost = Some(dir.to_path_buf());
+        }
+    }
+    outermost.unwrap_or(repo_root)
+}
+
+fn declares_workspace(dir: &Path) -> bool {
+    if dir.join("pnpm-workspace.yaml").is_file() || dir.join("go.work").is_file() {
+        return true;
+    }
+    let cargo_workspace = std::fs::read_to_string(dir.join("Cargo.toml"))
+        .ok()
+        .and_then(|c| toml::from_str::<toml::Value>(&c).ok())
+        .is_some_and(|v| v.get("workspace").is_some());
+    cargo_workspace || !npm_workspace_patterns(dir).is_empty()
+}
+
+/// Members declared by workspace manifests in `root`.
+fn declared_members(root: &Path) -> Vec<(PathBuf, MemberSource)> {
+    let mut members = Vec::new();
+
+    if let Some(workspace) = std::fs::read_to_string(root.join("Cargo.toml"))
+        .ok()
+        .and_then(|c| toml::from_str::<toml::Value>(&c).ok())
+        .and_then(|v| v.get("workspace").cloned())
+    {
+        let patterns = string_array(workspace.get("members"));
+        let excludes = string_array(workspace.get("exclude"));
+        for dir in expand_patterns(root, &patterns, &excludes) {
+            members.push((dir, MemberSource::CargoWorkspace));
+        }
+    }
+
+    let npm = npm_workspace_patterns(root);
+    if !npm.is_empty() {
+        for dir in expand_patterns(root, &npm, &[]) {
+            members.push((dir, MemberSource::NpmWorkspace));
+        }
+    }
+
+    if let Some(packages) = std::fs::read_to_string(root.join("pnpm-workspace.yaml"))
+        .ok()
+        .and_then(|c| serde_yaml::from_str::<serde_yaml::Value>(&c).ok())
+        .and_then(|v| v.get("packages").cloned())
+    {
+        let patterns: Vec<String> = packages
+            .as_sequence()
+            .map(|seq| {
+                seq.iter()
+                    .filter_map(serde_yaml::Value::as_str)
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let (excludes, includes): (Vec<String>, Vec<String>) =
+            patterns.into_iter().partition(|p| p.starts_with('!'));
+        let excludes: Vec<String> = excludes
+            .iter()
+            .map(|p| p.trim_start_matches('!').to_string())
+            .collect();
+        for dir in expand_patterns(root, &includes, &excludes) {
+            members.push((dir, MemberSource::PnpmWorkspace));
+        }
+    }
+
+    if let Ok(go_work) = std::fs::read_to_string(root.join("go.work")) {
+        for dir in parse_go_work(&go_work) {
+            let path = root.join(dir);
+            if path.is_dir() {
+                members.push((path.canonicalize().unwrap_or(path), MemberSource::GoWork));
+            }
+        }
+    }
+
+    members
+}
+
+fn npm_workspace_patterns(dir: &Path) -> Vec<String> {
+    let Some(value) = std::fs::read_to_string(dir.join("package.json"))
+        .ok()
+        .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+    else {
+        return Vec::new();
+    };
+    let workspaces = match value.get("workspaces") {
+        Some(serde_json::Value::Object(obj)) => obj.get("packages"),
+        other => other,
+    };
+    workspaces
+        .and_then(|w| w.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn parse_go_work(content: &str) -> Vec<String> {
+    let mut dirs = Vec::new();
+    let mut in_block = false;
+    for line in content.lines() {
+        let line = line.split("//").next().unwrap_or("").trim();
+        if in_block {
+            if line.starts_with(')') {
+                in_block = false;
+            } else if !line.is_empty() {
+                dirs.push(line.to_string());
+            }
+        } else if line == "use (" || line == "use(" {
+            in_block = true;
+        } else if let Some(dir) = line.strip_prefix("use ") {
+            dirs.push(dir.trim().to_string());
+        }
+    }
+    dirs
+}
+
+fn string_array(value: Option<&toml::Value>) -> Vec<String> {
+    value
+        .and_then(toml::Value::as_array)
+        .map(|arr| {
+            arr.iter()
+                .filter_map(toml::Value::as_str)
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Expand workspace member globs relative to `root` into existing directories.
+fn expand_patterns(root: &Path, patterns: &[String], excludes: &[String]) -> Vec<PathBuf> {
+    let excluded: Vec<glob::Pattern> = excludes
+        .iter()
+        .filter_map(|p| glob::Pattern::new(&root.join(p).to_string_lossy()).ok())
+        .collect();
+
+    let mut dirs = Vec::new();
+    for pattern in patterns {
+        let full = root.join(pattern.trim_start_matches("./"));
+        let Ok(paths) = glob::glob(&full.to_string_lossy()) else {
+            continue;
+        };
+        for path in paths.filter_map(std::result::Result::ok) {
+            if path.is_dir() && !excluded.iter().any(|e| e.matches_path(&path)) {
+                let path = path.canonicalize().unwrap_or(path);
+                if !dirs.contains(&path) {
+                    dirs.push(path);
+                }
+            }
+        }
+    }
+    dirs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::detector::DefaultDetector;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn write(root: &Path, rel: &str, content: &str) {
+        let path = root.join(rel);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn monorepo() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        git2::Repository::init(dir.path()).unwrap();
+        write(
+            dir.path(),
+            "package.json",
+            r#"{"private": true, "workspaces": ["apps/*"]}"#,
+        );
+        write(
+            dir.path(),
+            "apps/web/package.json",
+            r#"{"dependencies": {"react": "^18.2.0"}}"#,
+        );
+        write(
+            dir.path(),
+            "go.work",
+            "go 1.22\n\nuse (\n\t./services/api\n)\n",
+        );
+        write(
+            dir.path(),
+            "services/api/go.mod",
+            "module example.com/api\n\nrequire github.com/gin-gonic/gin v1.9.1\n",
+        );
+        write(
+            dir.path(),
+            "tools/pyproject.toml",
+            "[project]\nname = \"tools\"\n",
+        );
+        dir
+    }
+
+    fn detector() -> WorkspaceDetector {
+        WorkspaceDetector::new(3, vec!["node_modules".to_string(), ".git".to_string()])
+    }
+
+    #[test]
+    fn discovers_declared_and_nested_members() {
+        let dir = monorepo();
+        let cwd = dir.path().join("services/api");
+        let layout = detector().discover(&cwd, &[], &DefaultDetector::new());
+
+        assert_eq!(layout.root, dir.path().canonicalize().unwrap());
+        let find = |name: &str| layout.subprojects.iter().find(|s| s.name == name);
+        assert_eq!(find("apps/web").unwrap().source, MemberSource::NpmWorkspace);
+        assert_eq!(find("services/api").unwrap().source, MemberSource::GoWork);
+        assert_eq!(find("tools").unwrap().source, MemberSource::Nested);
+        assert!(layout.is_monorepo());
+    }
+
+    #[test]
+    fn active_subproject_follows_cwd() {
+        let dir = monorepo();
+        let layout = detector().discover(
+            &dir.path().join("services/api"),
+            &[],
+            &DefaultDetector::new(),
+        );
+
+        let active = layout.active().unwrap();
+        assert_eq!(active.name, "services/api");
+        assert_eq!(active.cwd_distance, 0);
+        assert_eq!(active.project_type_ids(), vec!["go"]);
+
+        let projects = layout.weighted_projects();
+        assert_eq!(projects[0].project_type, ProjectType::Go);
+        let node = projects
+            .iter()
+            .find(|p| p.project_type == ProjectType::Node)
+            .unwrap();
+        assert!(node.confidence < projects[0].confidence);
+    }
+
+    #[test]
+    fn recent_files_shift_weight() {
+        let dir = monorepo();
+        let web_file = dir.path().join("apps/web/index.ts");
+        fs::write(&web_file, "export {}").unwrap();
+
+        // cwd at the root, but all recent edits are in the web app
+        let layout = detector().discover(
+            dir.path(),
+            &[web_file.clone(), web_file],
+            &DefaultDetector::new(),
+        );
+        let active = layout.active().unwrap();
+        assert_eq!(active.name, "apps/web");
+        assert_eq!(active.recent_files, 2);
+    }
+
+    #[test]
+    fn active_dependencies_come_from_active_member() {
+        let dir = monorepo();
+        let layout =
+            detector().discover(&dir.path().join("apps/web"), &[], &DefaultDetector::new());
+        let deps = layout.active_dependencies();
+        assert!(deps.iter().any(|d| d.name == "react"));
+        assert!(!deps.iter().any(|d| d.name == "github.com/gin-gonic/gin"));
+    }
+
+    #[test]
+    fn single_project_outside_git() {
+        let dir = TempDir::new().unwrap();
+        write(dir.path(), "Cargo.toml", "[package]\nname = \"demo\"\n");
+        let layout = detector().discover(dir.path(), &[], &DefaultDetector::new());
+
+        assert!(!layout.is_monorepo());
+        let active = layout.active().unwrap();
+        assert_eq!(active.name, ".");
+        assert!((active.weight - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn cargo_workspace_members_respect_exclude() {
+        let dir = TempDir::new().unwrap();
+        git2::Repository::init(dir.path()).unwrap();
+        write(
+            dir.path(),
+            "Cargo.toml",
+            "[workspace]\nmembers = [\"crates/*\"]\nexclude = [\"crates/legacy\"]\n",
+        );
+        write(
+            dir.path(),
+            "crates/core/Cargo.toml",
+            "[package]\nname = \"core\"\n",
+        );
+        write(
+            dir.path(),
+            "crates/legacy/Cargo.toml",
+            "[package]\nname = \"legacy\"\n",
+        );
+
+        let layout = detector().discover(
+            &dir.path().join("crates/core"),
+            &[],
+            &DefaultDetector::new(),
+        );
+        let core = layout
+            .subprojects
+            .iter()
+            .find(|s| s.name == "crates/core")
+            .unwrap();
+        assert_eq!(core.source, MemberSource::CargoWorkspace);
+        // Excluded from the workspace, but still found as a nested project
+        let legacy = layout
+            .subprojects
+            .iter()
+            .find(|s| s.name == "crates/legacy")
+            .unwrap();
+        assert_eq!(legacy.source, MemberSource::Nested);
+    }
+
+    #[test]
+    fn tree_distance_counts_hops() {
+        assert_eq!(tree_distance(Path::new("/r/a/b"), Path::new("/r/a/b")), 0);
+        assert_eq!(tree_distance(Path::new("/r/a/b"), Path::new("/r/a")), 1);
+        assert_eq!(tree_distance(Path::new("/r/a/b"), Path::new("/r/c")), 3);
+    }
+}
@@ -463,7 +463,9 @@ fn disclose_packed(
     }
 }
 
-fn render_packed_body(slices: &[crate::core::skill::SkillSlice]) -> String {
+/// Render packed slices as markdown, emitting each section header once.
+#[must_use]
+pub fn render_packed_body(slices: &[crate::core::skill::SkillSlice]) -> String {
     let mut out = String::new();
     let mut last_section = None;
 
@@ -689,6 +689,7 @@ mod tests {
                     id: bid.to_string(),
                     block_type: BlockType::Text,
                     content: content.to_string(),
+                    suppressions: Vec::new(),
                 })
                 .collect(),
         }
@@ -7,10 +7,13 @@ pub mod overlay;
 pub mod pack_contracts;
 pub mod packing;
 pub mod recovery;
+pub mod references;
 pub mod requirements;
 pub mod resolution;
 pub mod resolution_cache;
 pub mod safety;
+pub mod script_tools;
+pub mod shell;
 pub mod skill;
 pub mod slicing;
 pub mod spec_lens;
@@ -34,6 +37,7 @@ pub use recovery::{
     Checkpoint, FailureMode, RecoveryIssue, RecoveryManager, RecoveryReport, RetryConfig,
     with_retry, with_retry_if,
 };
+pub use references::{ReferenceAddress, SkillReferences};
 pub use resolution::{
     CycleDetectionResult, GitSkillRepository, MAX_INHERITANCE_DEPTH, ResolutionWarning,
     ResolvedSkillSpec, SkillRepository, detect_inheritance_cycle, get_inheritance_chain,
@@ -43,6 +47,7 @@ pub use resolution_cache::{
     CacheKey, CacheStats, CachedResolvedSkill, DependencyGraph as ResolutionDependencyGraph,
     ResolutionCache,
 };
+pub use script_tools::ScriptTool;
 pub use skill::{
     BlockType, EvidenceCoverage, EvidenceLevel, EvidenceRef, Skill, SkillBlock, SkillEvidenceIndex,
     SkillLayer, SkillMetadata, SkillSection, SkillSpec,
@@ -0,0 +1,595 @@
+//! Skill reference files (`references/`).
+//!
+//! References are read lazily from the skill directory instead of being
+//! stored with the skill. They are addressed as `<path>#<heading>`, chunked
+//! by heading into [`SliceType::Reference`] slices for the slice index, and
+//! served in line-based pages so large API references never have to be
+//! loaded whole.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use super::skill::{ReferenceFile, SkillAssets, SkillSlice, SliceType};
+use crate::error::{MsError, Result};
+use crate::storage::sqlite::SkillRecord;
+
+/// Chunks above this estimate are split further at blank lines.
+pub const MAX_CHUNK_TOKENS: usize = 600;
+
+/// Lines per page when the caller does not ask for a size.
+pub const DEFAULT_PAGE_LINES: usize = 200;
+
+const REFERENCE_UTILITY: f32 = 0.4;
+
+/// Address of a reference file or one of its sections
+/// (`api.md#authentication`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReferenceAddress {
+    pub path: String,
+    pub heading: Option<String>,
+}
+
+impl ReferenceAddress {
+    #[must_use]
+    pub fn parse(input: &str) -> Self {
+        let (path, heading) = match input.split_once('#') {
+            Some((path, heading)) => (path, Some(heading.trim())),
+            None => (input, None),
+        };
+        Self {
+            path: path.trim().to_string(),
+            heading: heading.filter(|h| !h.is_empty()).map(str::to_string),
+        }
+    }
+}
+
+/// A heading-delimited chunk of a reference file.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReferenceChunk {
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub heading: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub anchor: Option<String>,
+    /// Heading level (0 for text before the first heading)
+    pub level: usize,
+    /// First line of the chunk (1-based)
+    pub start_line: usize,
+    pub content: String,
+}
+
+/// A reference file or section, resolved to its text.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReferenceSection {
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub heading: Option<String>,
+    pub content: String,
+}
+
+/// One page of a reference section.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReferencePage {
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub heading: Option<String>,
+    /// First line of the page within the section (0-based)
+    pub offset: usize,
+    pub total_lines: usize,
+    /// Offset of the next page, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_offset: Option<usize>,
+    pub token_estimate: usize,
+    pub content: String,
+}
+
+/// Headings of one reference file.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReferenceOutline {
+    pub path: String,
+    pub file_type: String,
+    pub token_estimate: usize,
+    pub headings: Vec<OutlineHeading>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OutlineHeading {
+    pub level: usize,
+    pub heading: String,
+    pub anchor: String,
+}
+
+/// A search match inside a skill's references.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReferenceHit {
+    /// Address to fetch the matching section (`path#anchor`)
+    pub address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub heading: Option<String>,
+    pub score: f32,
+    pub snippet: String,
+}
+
+/// The reference files of one skill, read on demand.
+#[derive(Debug, Clone)]
+pub struct SkillReferences {
+    root: PathBuf,
+    files: Vec<ReferenceFile>,
+}
+
+impl SkillReferences {
+    /// References listed in `assets`, relative to the asset root or
+    /// `skill_dir` when the inventory has none.
+    #[must_use]
+    pub fn new(assets: &SkillAssets, skill_dir: &Path) -> Self {
+        Self {
+            root: assets
+                .root
+                .clone()
+                .unwrap_or_else(|| skill_dir.to_path_buf()),
+            files: assets.references.clone(),
+        }
+    }
+
+    /// References recorded for an indexed skill.
+    #[must_use]
+    pub fn from_record(skill: &SkillRecord) -> Self {
+        let assets: SkillAssets = serde_json::from_str(&skill.assets_json).unwrap_or_default();
+        Self::new(&assets, &skill.skill_dir())
+    }
+
+    #[must_use]
+    pub fn files(&self) -> &[ReferenceFile] {
+        &self.files
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Find a listed reference by path, with or without the `references/`
+    /// prefix.
+    pub fn find(&self, path: &str) -> Result<&ReferenceFile> {
+        let wanted = path.trim_start_matches("./");
+        self.files
+            .iter()
+            .find(|file| {
+                let listed = display_path(&file.path);
+                listed == wanted || listed.strip_prefix("references/") == Some(wanted)
+            })
+            .ok_or_else(|| {
+                let available: Vec<String> =
+                    self.files.iter().map(|f| display_path(&f.path)).collect();
+                MsError::NotFound(if available.is_empty() {
+                    format!("reference not found: {path} (skill has no references)")
+                } else {
+                    format!(
+                        "reference not found: {path} (available: {})",
+                        available.join(", ")
+                    )
+                })
+            })
+    }
+
+    /// Read a reference file from disk.
+    pub fn read(&self, file: &ReferenceFile) -> Result<String> {
+        let path = self.root.join(&file.path);
+        std::fs::read_to_string(&path)
+            .map_err(|err| MsError::NotFound(format!("read reference {}: {err}", path.display())))
+    }
+
+    /// Resolve an address to the whole file or the named section.
+    pub fn section(&self, address: &ReferenceAddress) -> Result<ReferenceSection> {
+        let file = self.find(&address.path)?;
+        let content = self.read(file)?;
+        let path = display_path(&file.path);
+        let Some(wanted) = &address.heading else {
+            return Ok(ReferenceSection {
+                path,
+                heading: None,
+                content,
+            });
+        };
+        let (heading, content) = find_section(&content, wanted)
+            .ok_or_else(|| MsError::NotFound(format!("heading not found in {path}: #{wanted}")))?;
+        Ok(ReferenceSection {
+            path,
+            heading: Some(heading),
+            content,
+        })
+    }
+
+    /// Chunks of every readable reference file.
+    pub fn chunks(&self) -> Result<Vec<ReferenceChunk>> {
+        let mut chunks = Vec::new();
+        for file in &self.files {
+            chunks.extend(chunk_reference(
+                &display_path(&file.path),
+                &self.read(file)?,
+            ));
+        }
+        Ok(chunks)
+    }
+
+    /// Headings of every reference file.
+    pub fn outline(&self) -> Result<Vec<ReferenceOutline>> {
+        self.files
+            .iter()
+            .map(|file| {
+                let content = self.read(file)?;
+                let path = display_path(&file.path);
+                let headings = headings(&content)
+                    .into_iter()
+                    .map(|(_, level, heading)| OutlineHeading {
+                        level,
+                        anchor: anchor(&heading),
+                        heading,
+                    })
+                    .collect();
+                Ok(ReferenceOutline {
+                    path,
+                    file_type: file.file_type.clone(),
+                    token_estimate: estimate_tokens(&content),
+                    headings,
+                })
+            })
+            .collect()
+    }
+
+    /// Rank reference chunks by how many query terms they contain.
+    pub fn search(&self, query: &str, limit: usize) -> Result<Vec<ReferenceHit>> {
+        let terms: Vec<String> = query
+            .split_whitespace()
+            .map(str::to_lowercase)
+            .filter(|t| !t.is_empty())
+            .collect();
+        if terms.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut hits = Vec::new();
+        for chunk in self.chunks()? {
+            let haystack = chunk.content.to_lowercase();
+            let title = chunk.heading.as_deref().unwrap_or_default().to_lowercase();
+            let mut score = 0.0_f32;
+            for term in &terms {
+                let count = haystack.matches(term.as_str()).count();
+                if count > 0 {
+                    score += 1.0 + (count as f32).ln();
+                }
+                if title.contains(term.as_str()) {
+                    score += 2.0;
+                }
+            }
+            if score <= 0.0 {
+                continue;
+            }
+            let address = match &chunk.anchor {
+                Some(anchor) => format!("{}#{anchor}", chunk.path),
+                None => chunk.path.clone(),
+            };
+            hits.push(ReferenceHit {
+                address,
+                heading: chunk.heading.clone(),
+                score,
+                snippet: snippet(&chunk.content, &terms),
+            });
+        }
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        hits.truncate(limit);
+        Ok(hits)
+    }
+}
+
+impl ReferenceSection {
+    /// Page through the section by lines.
+    #[must_use]
+    pub fn page(&self, offset: usize, limit: usize) -> ReferencePage {
+        let lines: Vec<&str> = self.content.lines().collect();
+        let total_lines = lines.len();
+        let start = offset.min(total_lines);
+        let end = start.saturating_add(limit.max(1)).min(total_lines);
+        let content = lines[start..end].join("\n");
+        ReferencePage {
+            path: self.path.clone(),
+            heading: self.heading.clone(),
+            offset: start,
+            total_lines,
+            next_offset: (end < total_lines).then_some(end),
+            token_estimate: estimate_tokens(&content),
+            content,
+        }
+    }
+}
+
+/// Split a reference file into heading-delimited chunks. Markdown files
+/// split at every heading; oversized chunks (and other file types) split
+/// at blank lines.
+#[must_use]
+pub fn chunk_reference(path: &str, content: &str) -> Vec<ReferenceChunk> {
+    let mut sections: Vec<ReferenceChunk> = Vec::new();
+    let heading_lines = if is_markdown(path) {
+        headings(content)
+    } else {
+        Vec::new()
+    };
+    let lines: Vec<&str> = content.lines().collect();
+    let mut boundaries: Vec<(usize, usize, Option<String>)> = heading_lines
+        .into_iter()
+        .map(|(line, level, heading)| (line, level, Some(heading)))
+        .collect();
+    if boundaries.first().is_none_or(|(line, _, _)| *line > 0) {
+        boundaries.insert(0, (0, 0, None));
+    }
+
+    for (i, (start, level, heading)) in boundaries.iter().enumerate() {
+        let end = boundaries
+            .get(i + 1)
+            .map_or(lines.len(), |(line, _, _)| *line);
+        let text = lines[*start..end].join("\n");
+        if text.trim().is_empty() {
+            continue;
+        }
+        for (offset, part) in split_oversized(&lines[*start..end]) {
+            sections.push(ReferenceChunk {
+                path: path.to_string(),
+                heading: heading.clone(),
+                anchor: heading.as_deref().map(anchor),
+                level: *level,
+                start_line: start + offset + 1,
+                content: part,
+            });
+        }
+    }
+    sections
+}
+
+/// Turn reference chunks into slices for the slice index and packer.
+#[must_use]
+pub fn reference_slices(chunks: &[ReferenceChunk], tags: &[String]) -> Vec<SkillSlice> {
+    let mut seen = HashSet::new();
+    chunks
+        .iter()
+        .map(|chunk| {
+            let base = match &chunk.anchor {
+                Some(anchor) => format!("{}#{anchor}", chunk.path),
+                None => chunk.path.clone(),
+            };
+            let mut id = base.clone();
+            let mut n = 1;
+            while !seen.insert(id.clone()) {
+                n += 1;
+                id = format!("{base}-{n}");
+            }
+            let mut slice_tags = tags.to_vec();
+            slice_tags.push("reference".to_string());
+            SkillSlice {
+                id,
+                slice_type: SliceType::Reference,
+                token_estimate: estimate_tokens(&chunk.content),
+                utility_score: REFERENCE_UTILITY,
+                coverage_group: Some("reference".to_string()),
+                tags: slice_tags,
+                requires: Vec::new(),
+                condition: None,
+                section_title: Some(match &chunk.heading {
+                    Some(heading) => format!("{heading} ({})", chunk.path),
+                    None => chunk.path.clone(),
+                }),
+                content: chunk.content.clone(),
+            }
+        })
+        .collect()
+}
+
+/// GitHub-style heading anchor.
+#[must_use]
+pub fn anchor(heading: &str) -> String {
+    let mut out = String::with_capacity(heading.len());
+    for ch in heading.trim().to_lowercase().chars() {
+        if ch.is_alphanumeric() || ch == '-' || ch == '_' {
+            out.push(ch);
+        } else if ch.is_whitespace() {
+            out.push('-');
+        }
+    }
+    out
+}
+
+fn display_path(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+fn is_markdown(path: &str) -> bool {
+    let lower = path.to_lowercase();
+    lower.ends_with(".md") || lower.ends_with(".markdown") || lower.ends_with(".mdx")
+}
+
+/// ATX headings outside fenced code: (line index, level, text).
+fn headings(content: &str) -> Vec<(usize, usize, String)> {
+    let mut out = Vec::new();
+    let mut in_fence = false;
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        let level = trimmed.chars().take_while(|c| *c == '#').count();
+        if (1..=6).contains(&level) && trimmed[level..].starts_with(' ') {
+            let text = trimmed[level..].trim().trim_end_matches('#').trim();
+            if !text.is_empty() {
+                out.push((index, level, text.to_string()));
+            }
+        }
+    }
+    out
+}
+
+/// The section under `wanted` (matched by anchor or text), up to the next
+/// heading of the same or a higher level.
+fn find_section(content: &str, wanted: &str) -> Option<(String, String)> {
+    let wanted_anchor = anchor(wanted);
+    let all = headings(content);
+    let position = all.iter().position(|(_, _, heading)| {
+        anchor(heading) == wanted_anchor || heading.eq_ignore_ascii_case(wanted)
+    })?;
+    let (start, level, heading) = &all[position];
+    let lines: Vec<&str> = content.lines().collect();
+    let end = all[position + 1..]
+        .iter()
+        .find(|(_, l, _)| l <= level)
+        .map_or(lines.len(), |(line, _, _)| *line);
+    Some((heading.clone(), lines[*start..end].join("\n")))
+}
+
+/// Split lines into parts under `MAX_CHUNK_TOKENS`, breaking at blank
+/// lines. Returns (line offset, text) pairs.
+fn split_oversized(lines: &[&str]) -> Vec<(usize, String)> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut current: Vec<&str> = Vec::new();
+    let mut tokens = 0;
+    for (index, line) in lines.iter().enumerate() {
+        let line_tokens = estimate_tokens(line);
+        if line.trim().is_empty() && tokens >= MAX_CHUNK_TOKENS {
+            push_part(&mut parts, start, &current);
+            current.clear();
+            tokens = 0;
+            start = index + 1;
+            continue;
+        }
+        current.push(line);
+        tokens += line_tokens;
+    }
+    push_part(&mut parts, start, &current);
+    parts
+}
+
+fn push_part(parts: &mut Vec<(usize, String)>, start: usize, lines: &[&str]) {
+    let text = lines.join("\n").trim_end().to_string();
+    if !text.trim().is_empty() {
+        parts.push((start, text));
+    }
+}
+
+fn snippet(content: &str, terms: &[String]) -> String {
+    let line = content
+        .lines()
+        .find(|line| {
+            let lower = line.to_lowercase();
+            !line.trim_start().starts_with('#') && terms.iter().any(|t| lower.contains(t.as_str()))
+        })
+        .or_else(|| content.lines().find(|line| !line.trim().is_empty()))
+        .unwrap_or_default()
+        .trim();
+    if line.chars().count() > 160 {
+        format!("{}…", line.chars().take(160).collect::<String>())
+    } else {
+        line.to_string()
+    }
+}
+
+fn estimate_tokens(content: &str) -> usize {
+    content.chars().count().div_ceil(4).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const API: &str = "# API Reference\n\nIntro text.\n\n## Authentication\n\nUse a bearer token.\n\n### Token refresh\n\nRefresh before expiry.\n\n```bash\n# not a heading\ncurl /refresh\n```\n\n## Pagination\n\nUse cursor parameters.\n";
+
+    fn skill_with_reference() -> (tempfile::TempDir, SkillReferences) {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("references")).unwrap();
+        std::fs::write(dir.path().join("references/api.md"), API).unwrap();
+        let assets = SkillAssets::collect(dir.path()).unwrap();
+        let refs = SkillReferences::new(&assets, dir.path());
+        (dir, refs)
+    }
+
+    #[test]
+    fn parses_addresses() {
+        assert_eq!(
+            ReferenceAddress::parse("api.md#Token refresh"),
+            ReferenceAddress {
+                path: "api.md".to_string(),
+                heading: Some("Token refresh".to_string()),
+            }
+        );
+        assert_eq!(ReferenceAddress::parse("api.md#").heading, None);
+    }
+
+    #[test]
+    fn chunks_by_heading_and_skips_fenced_hashes() {
+        let chunks = chunk_reference("references/api.md", API);
+        let headings: Vec<_> = chunks.iter().map(|c| c.heading.as_deref()).collect();
+        assert_eq!(
+            headings,
+            vec![
+                Some("API Reference"),
+                Some("Authentication"),
+                Some("Token refresh"),
+                Some("Pagination"),
+            ]
+        );
+        assert_eq!(chunks[2].anchor.as_deref(), Some("token-refresh"));
+        assert_eq!(chunks[1].start_line, 5);
+
+        let slices = reference_slices(&chunks, &["api".to_string()]);
+        assert!(slices.iter().all(|s| s.slice_type == SliceType::Reference));
+        assert_eq!(slices[1].id, "references/api.md#authentication");
+        assert!(slices[1].tags.contains(&"reference".to_string()));
+    }
+
+    #[test]
+    fn oversized_chunks_split_at_blank_lines() {
+        let paragraph = "word ".repeat(400);
+        let content = format!("## Big\n\n{paragraph}\n\n{paragraph}\n\n{paragraph}\n");
+        let chunks = chunk_reference("big.md", &content);
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|c| c.heading.as_deref() == Some("Big")));
+    }
+
+    #[test]
+    fn resolves_sections_and_pages() {
+        let (_dir, refs) = skill_with_reference();
+        let section = refs
+            .section(&ReferenceAddress::parse("api.md#authentication"))
+            .unwrap();
+        assert_eq!(section.path, "references/api.md");
+        assert!(section.content.contains("Refresh before expiry"));
+        assert!(!section.content.contains("Pagination"));
+
+        let page = section.page(0, 3);
+        assert_eq!(page.offset, 0);
+        assert_eq!(page.next_offset, Some(3));
+        let last = section.page(page.next_offset.unwrap(), 100);
+        assert_eq!(last.next_offset, None);
+
+        assert!(
+            refs.section(&ReferenceAddress::parse("api.md#missing"))
+                .is_err()
+        );
+        let err = refs.find("nope.md").unwrap_err().to_string();
+        assert!(err.contains("references/api.md"));
+    }
+
+    #[test]
+    fn searches_and_outlines_references() {
+        let (_dir, refs) = skill_with_reference();
+        let hits = refs.search("cursor", 5).unwrap();
+        assert_eq!(hits[0].address, "references/api.md#pagination");
+
+        let outline = refs.outline().unwrap();
+        assert_eq!(outline.len(), 1);
+        assert_eq!(outline[0].headings.len(), 4);
+    }
+}
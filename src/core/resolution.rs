@@ -705,6 +705,7 @@ mod tests {
                 id: "intro-1".to_string(),
                 block_type: BlockType::Text,
                 content: "Parent intro".to_string(),
+                suppressions: Vec::new(),
             }],
         });
         repo.add(parent);
@@ -814,6 +815,7 @@ mod tests {
                 id: "rule-1".to_string(),
                 block_type: BlockType::Rule,
                 content: "Parent rule".to_string(),
+                suppressions: Vec::new(),
             }],
         });
         repo.add(parent);
@@ -826,6 +828,7 @@ mod tests {
                 id: "rule-2".to_string(),
                 block_type: BlockType::Rule,
                 content: "Child rule".to_string(),
+                suppressions: Vec::new(),
             }],
         });
 
@@ -850,6 +853,7 @@ mod tests {
                 id: "rule-1".to_string(),
                 block_type: BlockType::Rule,
                 content: "Parent rule".to_string(),
+                suppressions: Vec::new(),
             }],
         });
         repo.add(parent);
@@ -863,6 +867,7 @@ mod tests {
                 id: "rule-2".to_string(),
                 block_type: BlockType::Rule,
                 content: "Child rule only".to_string(),
+                suppressions: Vec::new(),
             }],
         });
 
@@ -951,6 +956,7 @@ mod tests {
                     id: format!("rule-{}", i + 1),
                     block_type: BlockType::Rule,
                     content: content.to_string(),
+                    suppressions: Vec::new(),
                 })
                 .collect(),
         });
@@ -985,6 +991,7 @@ mod tests {
                 id: "main-rule-1".to_string(),
                 block_type: BlockType::Rule,
                 content: "Main rule".to_string(),
+                suppressions: Vec::new(),
             }],
         });
 
@@ -1287,6 +1294,7 @@ mod tests {
                 id: "rule-1".to_string(),
                 block_type: BlockType::Rule,
                 content: "Source rule".to_string(),
+                suppressions: Vec::new(),
             }],
         });
         source_skill.sections.push(SkillSection {
@@ -1296,6 +1304,7 @@ mod tests {
                 id: "pitfall-1".to_string(),
                 block_type: BlockType::Pitfall,
                 content: "Source pitfall".to_string(),
+                suppressions: Vec::new(),
             }],
         });
         repo.add(source_skill);
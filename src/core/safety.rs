@@ -1,4 +1,7 @@
 //! Safety invariants and DCG integration
+//!
+//! The built-in evaluator lives in [`crate::security::command_rules`]; DCG
+//! is an optional override selected by [`SafetyEvaluator`].
 
 use std::path::PathBuf;
 use std::process::Command;
@@ -16,6 +19,30 @@ pub enum SafetyTier {
     Critical,
 }
 
+/// Which evaluator classifies commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SafetyEvaluator {
+    /// DCG when it is installed, otherwise the native evaluator.
+    #[default]
+    Auto,
+    /// Always the built-in rule packs.
+    Native,
+    /// Always DCG; commands are blocked when it is unavailable.
+    Dcg,
+}
+
+impl SafetyEvaluator {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Native => "native",
+            Self::Dcg => "dcg",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DcgDecision {
     pub allowed: bool,
@@ -29,6 +56,9 @@ pub struct DcgDecision {
     pub pack: Option<String>,
     #[serde(default)]
     pub approved: bool,
+    /// Every rule that matched, highest tier first (native evaluator).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub explanation: Vec<String>,
 }
 
 impl DcgDecision {
@@ -41,6 +71,7 @@ impl DcgDecision {
             rule_id: None,
             pack: None,
             approved: false,
+            explanation: Vec::new(),
         }
     }
 
@@ -60,6 +91,24 @@ impl DcgDecision {
             rule_id: None,
             pack: None,
             approved: false,
+            explanation: Vec::new(),
+        }
+    }
+
+    /// Decision for a command blocked by organisation policy. Not approvable.
+    #[must_use]
+    pub fn policy_denied(reason: String) -> Self {
+        Self {
+            allowed: false,
+            tier: SafetyTier::Critical,
+            reason,
+            remediation: Some(
+                "Blocked by policy.toml; see `ms policy check --command`".to_string(),
+            ),
+            rule_id: None,
+            pack: Some("policy".to_string()),
+            approved: false,
+            explanation: Vec::new(),
         }
     }
 }
@@ -152,6 +201,7 @@ impl DcgGuard {
             rule_id,
             pack,
             approved: false,
+            explanation: Vec::new(),
         })
     }
 }
@@ -229,6 +279,15 @@ mod tests {
         assert_eq!(tier, SafetyTier::Danger);
     }
 
+    #[test]
+    fn safety_evaluator_serialization() {
+        assert_eq!(SafetyEvaluator::default(), SafetyEvaluator::Auto);
+        let json = serde_json::to_string(&SafetyEvaluator::Native).unwrap();
+        assert_eq!(json, "\"native\"");
+        let evaluator: SafetyEvaluator = serde_json::from_str("\"dcg\"").unwrap();
+        assert_eq!(evaluator, SafetyEvaluator::Dcg);
+    }
+
     // =========================================================================
     // DcgDecision tests
     // =========================================================================
@@ -268,6 +327,7 @@ mod tests {
             rule_id: Some("RULE-001".to_string()),
             pack: Some("default".to_string()),
             approved: false,
+            explanation: Vec::new(),
         };
 
         let json = serde_json::to_string(&decision).unwrap();
@@ -287,6 +347,7 @@ mod tests {
         assert!(!json.contains("remediation"));
         assert!(!json.contains("rule_id"));
         assert!(!json.contains("pack"));
+        assert!(!json.contains("explanation"));
     }
 
     // =========================================================================
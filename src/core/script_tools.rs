@@ -0,0 +1,570 @@
+//! Skill scripts exposed as tools.
+//!
+//! Skills declare script entry points in their frontmatter (`scripts:`); each
+//! entry becomes a tool named `<skill-id>__<script>` whose input schema is
+//! built from the declared arguments. Names that collide after sanitizing get
+//! a hash suffix (see [`script_tools`]). Execution lives in
+//! [`crate::simulation::SimulationEngine::run_script`].
+
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use tracing::warn;
+
+use crate::core::skill::{ScriptArg, ScriptArgType, ScriptEntry, SkillMetadata};
+use crate::error::{MsError, Result};
+use crate::storage::Database;
+
+/// Separator between skill ID and script name in tool names.
+pub const TOOL_SEPARATOR: &str = "__";
+
+/// Longest tool name MCP clients accept.
+const MAX_TOOL_NAME_LEN: usize = 64;
+
+/// Hex digits of the hash suffix added to colliding tool names.
+const NAME_HASH_LEN: usize = 8;
+
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+const MAX_TIMEOUT_SECS: u64 = 600;
+
+/// A script entry point of an indexed skill.
+#[derive(Debug, Clone)]
+pub struct ScriptTool {
+    pub skill_id: String,
+    /// Directory containing the skill's SKILL.md
+    pub skill_dir: PathBuf,
+    pub entry: ScriptEntry,
+    /// Tool name exposed to clients
+    name: String,
+}
+
+impl ScriptTool {
+    #[must_use]
+    pub fn new(
+        skill_id: impl Into<String>,
+        skill_dir: impl Into<PathBuf>,
+        entry: ScriptEntry,
+    ) -> Self {
+        let skill_id = skill_id.into();
+        let name = base_name(&skill_id, &entry.name, MAX_TOOL_NAME_LEN);
+        Self {
+            skill_id,
+            skill_dir: skill_dir.into(),
+            entry,
+            name,
+        }
+    }
+
+    /// Tool name: `<skill-id>__<script>`, restricted to `[A-Za-z0-9_-]` and
+    /// 64 characters, with a hash suffix if that collides with another tool.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Replace the name with one ending in a hash of the skill ID and
+    /// script name, which tell colliding tools apart.
+    fn disambiguate(&mut self) {
+        let digest = hex::encode(Sha256::digest(format!(
+            "{}\0{}",
+            self.skill_id, self.entry.name
+        )));
+        let prefix = base_name(
+            &self.skill_id,
+            &self.entry.name,
+            MAX_TOOL_NAME_LEN - NAME_HASH_LEN - 1,
+        );
+        self.name = format!("{prefix}-{}", &digest[..NAME_HASH_LEN]);
+    }
+
+    #[must_use]
+    pub fn description(&self) -> String {
+        let description = self.entry.description.trim();
+        if description.is_empty() {
+            format!("Run {} from skill {}", self.entry.path, self.skill_id)
+        } else {
+            format!("{description} (script from skill {})", self.skill_id)
+        }
+    }
+
+    /// JSON schema for the tool's arguments.
+    #[must_use]
+    pub fn input_schema(&self) -> Value {
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        for arg in &self.entry.args {
+            let mut schema = serde_json::json!({ "type": arg.arg_type.as_str() });
+            if arg.arg_type == ScriptArgType::Array {
+                schema["items"] = serde_json::json!({ "type": "string" });
+            }
+            if !arg.description.is_empty() {
+                schema["description"] = Value::String(arg.description.clone());
+            }
+            if let Some(default) = &arg.default {
+                schema["default"] = default.clone();
+            }
+            if arg.required {
+                required.push(Value::String(arg.name.clone()));
+            }
+            properties.insert(arg.name.clone(), schema);
+        }
+        serde_json::json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+            "additionalProperties": false,
+        })
+    }
+
+    #[must_use]
+    pub fn timeout(&self) -> Duration {
+        let secs = self
+            .entry
+            .timeout_secs
+            .unwrap_or(DEFAULT_TIMEOUT_SECS)
+            .clamp(1, MAX_TIMEOUT_SECS);
+        Duration::from_secs(secs)
+    }
+
+    /// Interpreter for the script, from the entry or the file extension.
+    /// `None` means the script is executed directly. A declared interpreter
+    /// must be a bare program name (`[A-Za-z0-9._-]+`), not a command line.
+    pub fn interpreter(&self) -> Result<Option<String>> {
+        if let Some(interpreter) = &self.entry.interpreter {
+            let bare = !interpreter.is_empty()
+                && interpreter
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "._-".contains(c));
+            if !bare {
+                return Err(MsError::ValidationFailed(format!(
+                    "script interpreter must be a bare program name: {interpreter}"
+                )));
+            }
+            return Ok(Some(interpreter.clone()));
+        }
+        let Some(ext) = Path::new(&self.entry.path)
+            .extension()
+            .and_then(std::ffi::OsStr::to_str)
+        else {
+            return Ok(None);
+        };
+        let interpreter = match ext {
+            "py" => "python3",
+            "js" | "mjs" | "cjs" => "node",
+            "sh" | "bash" => "bash",
+            "rb" => "ruby",
+            "pl" => "perl",
+            _ => return Ok(None),
+        };
+        Ok(Some(interpreter.to_string()))
+    }
+
+    /// Script path relative to the skill directory; rejects paths that
+    /// escape it, including through symlinks.
+    pub fn relative_path(&self) -> Result<&Path> {
+        let rel = Path::new(&self.entry.path);
+        let escapes = rel.is_absolute()
+            || rel
+                .components()
+                .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir));
+        if escapes || self.entry.path.is_empty() {
+            return Err(MsError::ValidationFailed(format!(
+                "script path must stay inside the skill directory: {}",
+                self.entry.path
+            )));
+        }
+        if !self.skill_dir.join(rel).is_file() {
+            return Err(MsError::NotFound(format!(
+                "script not found: {}",
+                self.skill_dir.join(rel).display()
+            )));
+        }
+        let resolved = self.skill_dir.join(rel).canonicalize()?;
+        if !resolved.starts_with(self.skill_dir.canonicalize()?) {
+            return Err(MsError::ValidationFailed(format!(
+                "script resolves outside the skill directory: {}",
+                self.entry.path
+            )));
+        }
+        Ok(rel)
+    }
+
+    /// Convert tool call arguments into command-line arguments, checking
+    /// them against the declared types.
+    pub fn build_args(&self, arguments: &Value) -> Result<Vec<String>> {
+        let empty = Map::new();
+        let provided = match arguments {
+            Value::Null => &empty,
+            Value::Object(map) => map,
+            _ => {
+                return Err(MsError::ValidationFailed(
+                    "script arguments must be an object".to_string(),
+                ));
+            }
+        };
+        if let Some(unknown) = provided
+            .keys()
+            .find(|key| !self.entry.args.iter().any(|arg| &arg.name == *key))
+        {
+            return Err(MsError::ValidationFailed(format!(
+                "unknown argument `{unknown}` for {}",
+                self.name()
+            )));
+        }
+
+        let mut argv = Vec::new();
+        for arg in &self.entry.args {
+            let value = provided
+                .get(&arg.name)
+                .filter(|v| !v.is_null())
+                .or(arg.default.as_ref());
+            let Some(value) = value else {
+                if arg.required {
+                    return Err(MsError::ValidationFailed(format!(
+                        "missing required argument `{}`",
+                        arg.name
+                    )));
+                }
+                continue;
+            };
+
+            let values = render_values(arg, value)?;
+            if arg.positional {
+                argv.extend(values);
+            } else if arg.arg_type == ScriptArgType::Boolean {
+                if value.as_bool() == Some(true) {
+                    argv.push(format!("--{}", arg.name));
+                }
+            } else {
+                for rendered in values {
+                    argv.push(format!("--{}", arg.name));
+                    argv.push(rendered);
+                }
+            }
+        }
+        Ok(argv)
+    }
+}
+
+/// `<skill-id>__<script>` with characters outside `[A-Za-z0-9_-]` replaced,
+/// cut to `max` characters.
+fn base_name(skill_id: &str, script: &str, max: usize) -> String {
+    format!("{skill_id}{TOOL_SEPARATOR}{script}")
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(max)
+        .collect()
+}
+
+fn render_values(arg: &ScriptArg, value: &Value) -> Result<Vec<String>> {
+    let scalar = |value: &Value| match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    };
+    let rendered = match (arg.arg_type, value) {
+        (ScriptArgType::String, Value::String(s)) => Some(vec![s.clone()]),
+        (ScriptArgType::Integer, Value::Number(n)) if n.is_i64() || n.is_u64() => {
+            Some(vec![n.to_string()])
+        }
+        (ScriptArgType::Number, Value::Number(n)) => Some(vec![n.to_string()]),
+        (ScriptArgType::Boolean, Value::Bool(b)) => Some(vec![b.to_string()]),
+        (ScriptArgType::Array, Value::Array(items)) => items.iter().map(scalar).collect(),
+        _ => None,
+    };
+    rendered.ok_or_else(|| {
+        MsError::ValidationFailed(format!(
+            "argument `{}` must be of type {}",
+            arg.name,
+            arg.arg_type.as_str()
+        ))
+    })
+}
+
+/// Script tools declared by all indexed, non-deprecated skills, with unique
+/// names (see [`unique_names`]).
+pub fn script_tools(db: &Database) -> Result<Vec<ScriptTool>> {
+    let mut tools: Vec<ScriptTool> = Vec::new();
+    for record in db.list_skills(10_000, 0)? {
+        if record.is_deprecated {
+            continue;
+        }
+        let Ok(metadata) = serde_json::from_str::<SkillMetadata>(&record.metadata_json) else {
+            continue;
+        };
+        let skill_dir = record.skill_dir();
+        for entry in metadata.scripts {
+            tools.push(ScriptTool::new(record.id.clone(), skill_dir.clone(), entry));
+        }
+    }
+    Ok(unique_names(tools))
+}
+
+/// Give every tool whose name collides a hash-suffixed name, so no caller
+/// can reach the wrong script by name. Tools that still collide (the same
+/// script declared twice by one skill) are dropped.
+fn unique_names(mut tools: Vec<ScriptTool>) -> Vec<ScriptTool> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for tool in &tools {
+        *counts.entry(tool.name.clone()).or_default() += 1;
+    }
+    for tool in &mut tools {
+        if counts[&tool.name] > 1 {
+            tool.disambiguate();
+        }
+    }
+
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for tool in &tools {
+        *counts.entry(tool.name.clone()).or_default() += 1;
+    }
+    tools.retain(|tool| {
+        let unique = counts[&tool.name] == 1;
+        if !unique {
+            warn!(
+                "script tool {} is declared more than once by {}; not listing it",
+                tool.name, tool.skill_id
+            );
+        }
+        unique
+    });
+    tools
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool(args: Vec<ScriptArg>) -> ScriptTool {
+        ScriptTool::new(
+            "pdf-tools",
+            "/skills/pdf-tools",
+            ScriptEntry {
+                name: "merge".to_string(),
+                path: "scripts/merge.py".to_string(),
+                description: "Merge PDFs".to_string(),
+                args,
+                ..Default::default()
+            },
+        )
+    }
+
+    fn arg(name: &str, arg_type: ScriptArgType) -> ScriptArg {
+        ScriptArg {
+            name: name.to_string(),
+            arg_type,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn tool_name_and_schema() {
+        let mut output = arg("output", ScriptArgType::String);
+        output.required = true;
+        let tool = tool(vec![output, arg("inputs", ScriptArgType::Array)]);
+        assert_eq!(tool.name(), "pdf-tools__merge");
+        assert_eq!(tool.interpreter().unwrap().as_deref(), Some("python3"));
+
+        let schema = tool.input_schema();
+        assert_eq!(schema["properties"]["output"]["type"], "string");
+        assert_eq!(schema["properties"]["inputs"]["items"]["type"], "string");
+        assert_eq!(schema["required"], serde_json::json!(["output"]));
+
+        let dotted = ScriptTool::new("a.b c", "/skills/a.b c", tool.entry.clone());
+        assert_eq!(dotted.name(), "a_b_c__merge");
+    }
+
+    #[test]
+    fn colliding_names_get_hash_suffixes() {
+        let entry = tool(Vec::new()).entry;
+        let tools = unique_names(vec![
+            ScriptTool::new("a.b", "/skills/a.b", entry.clone()),
+            ScriptTool::new("a_b", "/skills/a_b", entry.clone()),
+            ScriptTool::new("pdf-tools", "/skills/pdf-tools", entry.clone()),
+            ScriptTool::new("twice", "/skills/twice", entry.clone()),
+            ScriptTool::new("twice", "/skills/twice", entry.clone()),
+        ]);
+        let names: Vec<&str> = tools.iter().map(ScriptTool::name).collect();
+        assert_eq!(names.len(), 3, "{names:?}");
+        assert!(names[0].starts_with("a_b__merge-"));
+        assert!(names[1].starts_with("a_b__merge-"));
+        assert_ne!(names[0], names[1]);
+        assert_eq!(names[2], "pdf-tools__merge");
+
+        let long = "x".repeat(80);
+        let tools = unique_names(vec![
+            ScriptTool::new(format!("{long}.a"), "/skills/a", entry.clone()),
+            ScriptTool::new(format!("{long}.b"), "/skills/b", entry),
+        ]);
+        assert_eq!(tools.len(), 2);
+        assert_ne!(tools[0].name(), tools[1].name());
+        assert!(
+            tools
+                .iter()
+                .all(|tool| tool.name().len() == MAX_TOOL_NAME_LEN)
+        );
+    }
+
+    #[test]
+    fn build_args_checks_types() {
+        let mut output = arg("output", ScriptArgType::String);
+        output.required = true;
+        let mut inputs = arg("inputs", ScriptArgType::Array);
+        inputs.positional = true;
+        let mut pages = arg("pages", ScriptArgType::Integer);
+        pages.default = Some(serde_json::json!(10));
+        let tool = tool(vec![
+            output,
+            arg("verbose", ScriptArgType::Boolean),
+            pages,
+            inputs,
+        ]);
+
+        let argv = tool
+            .build_args(&serde_json::json!({
+                "output": "out.pdf",
+                "verbose": true,
+                "inputs": ["a.pdf", "b.pdf"],
+            }))
+            .unwrap();
+        assert_eq!(
+            argv,
+            vec![
+                "--output",
+                "out.pdf",
+                "--verbose",
+                "--pages",
+                "10",
+                "a.pdf",
+                "b.pdf"
+            ]
+        );
+
+        assert!(tool.build_args(&serde_json::json!({})).is_err());
+        assert!(
+            tool.build_args(&serde_json::json!({"output": "o", "pages": "ten"}))
+                .is_err()
+        );
+        assert!(
+            tool.build_args(&serde_json::json!({"output": "o", "extra": 1}))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn discovers_tools_from_index_and_records_invocations() {
+        use crate::storage::SkillRecord;
+
+        let temp = tempfile::tempdir().unwrap();
+        let db = Database::open(temp.path().join("ms.db")).unwrap();
+        let metadata = SkillMetadata {
+            id: "pdf-tools".to_string(),
+            name: "PDF Tools".to_string(),
+            scripts: vec![tool(Vec::new()).entry],
+            ..Default::default()
+        };
+        db.upsert_skill(&SkillRecord {
+            id: "pdf-tools".to_string(),
+            name: "PDF Tools".to_string(),
+            description: String::new(),
+            version: None,
+            author: None,
+            source_path: "/archive/skills/by-id/pdf-tools".to_string(),
+            source_layer: "project".to_string(),
+            git_remote: None,
+            git_commit: None,
+            content_hash: "abc".to_string(),
+            body: String::new(),
+            metadata_json: serde_json::to_string(&metadata).unwrap(),
+            assets_json: r#"{"root":"/skills/pdf-tools"}"#.to_string(),
+            token_count: 0,
+            quality_score: 0.0,
+            indexed_at: "2026-01-01T00:00:00Z".to_string(),
+            modified_at: "2026-01-01T00:00:00Z".to_string(),
+            is_deprecated: false,
+            deprecation_reason: None,
+        })
+        .unwrap();
+
+        let tools = script_tools(&db).unwrap();
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].name(), "pdf-tools__merge");
+        assert_eq!(tools[0].skill_dir, PathBuf::from("/skills/pdf-tools"));
+
+        db.record_script_invocation(
+            "pdf-tools",
+            "mcp",
+            "merge",
+            true,
+            &serde_json::json!({"exit_code": 0}),
+        )
+        .unwrap();
+        let (level, outcome): (String, String) = db
+            .conn()
+            .query_row(
+                "SELECT disclosure_level, outcome FROM skill_usage_events WHERE skill_id = 'pdf-tools'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(level, "script");
+        assert_eq!(outcome, "success");
+    }
+
+    #[test]
+    fn script_path_must_stay_in_skill_dir() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(temp.path().join("scripts")).unwrap();
+        std::fs::write(temp.path().join("scripts/merge.py"), "print(1)\n").unwrap();
+
+        let mut tool = tool(Vec::new());
+        tool.skill_dir = temp.path().to_path_buf();
+        assert!(tool.relative_path().is_ok());
+
+        tool.entry.path = "../outside.sh".to_string();
+        assert!(tool.relative_path().is_err());
+        tool.entry.path = "/bin/sh".to_string();
+        assert!(tool.relative_path().is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn script_symlink_must_stay_in_skill_dir() {
+        let temp = tempfile::tempdir().unwrap();
+        let skill_dir = temp.path().join("skill");
+        std::fs::create_dir_all(skill_dir.join("scripts")).unwrap();
+        std::fs::write(temp.path().join("outside.sh"), "echo hi\n").unwrap();
+        std::os::unix::fs::symlink(
+            temp.path().join("outside.sh"),
+            skill_dir.join("scripts/merge.sh"),
+        )
+        .unwrap();
+
+        let mut tool = tool(Vec::new());
+        tool.skill_dir = skill_dir;
+        tool.entry.path = "scripts/merge.sh".to_string();
+        assert!(tool.relative_path().is_err());
+    }
+
+    #[test]
+    fn declared_interpreter_must_be_bare_name() {
+        let mut tool = tool(Vec::new());
+        tool.entry.interpreter = Some("python3.12".to_string());
+        assert_eq!(tool.interpreter().unwrap().as_deref(), Some("python3.12"));
+
+        for bad in ["python3 -c 'import os'", "sh;rm -rf ~", "/usr/bin/env", ""] {
+            tool.entry.interpreter = Some(bad.to_string());
+            assert!(tool.interpreter().is_err(), "{bad}");
+        }
+    }
+}
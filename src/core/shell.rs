@@ -0,0 +1,1425 @@
+//! Shell command parsing
+//!
+//! A small POSIX-style parser used by the native command-safety evaluator.
+//! It understands lists (`;`, `&&`, `||`, `&`, newlines), pipelines,
+//! subshells and brace groups, function definitions, the common control-flow
+//! keywords, redirections (including heredocs and here-strings), quoting, and
+//! command / process substitution, which is parsed recursively.
+//!
+//! Parameters and globs are never expanded: words keep their literal text and
+//! record whether they contained an expansion, so callers can treat dynamic
+//! parts conservatively.
+//!
+//! [`parse`] accepts fragments, since commands under evaluation are often
+//! partial. [`parse_strict`] additionally requires a complete script.
+
+/// Maximum nesting of substitutions, subshells and groups.
+pub const MAX_DEPTH: usize = 16;
+
+/// Error produced when a command cannot be parsed.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{message} (at offset {offset})")]
+pub struct ShellParseError {
+    pub message: String,
+    /// Character (not byte) offset into the parsed source
+    pub offset: usize,
+}
+
+impl ShellParseError {
+    fn new(message: impl Into<String>, offset: usize) -> Self {
+        Self {
+            message: message.into(),
+            offset,
+        }
+    }
+}
+
+type ParseResult<T> = std::result::Result<T, ShellParseError>;
+
+/// A parsed command list.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Script {
+    pub pipelines: Vec<Pipeline>,
+}
+
+impl Script {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.pipelines.is_empty()
+    }
+
+    /// All simple commands in this script, including those nested in groups,
+    /// subshells, function bodies and substitutions.
+    #[must_use]
+    pub fn simple_commands(&self) -> Vec<&SimpleCommand> {
+        let mut out = Vec::new();
+        for pipeline in &self.pipelines {
+            for command in &pipeline.commands {
+                command.collect_simple(&mut out);
+            }
+        }
+        out
+    }
+}
+
+/// Commands connected by `|` or `|&`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Pipeline {
+    pub negated: bool,
+    pub commands: Vec<Command>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Simple(SimpleCommand),
+    /// `( ... )`
+    Subshell {
+        body: Script,
+        redirects: Vec<Redirect>,
+    },
+    /// `{ ...; }`, also used for the arms of `case`
+    Group {
+        body: Script,
+        redirects: Vec<Redirect>,
+    },
+    /// `name() body`; the body runs whenever the function is called.
+    Function {
+        name: String,
+        body: Box<Command>,
+    },
+    /// Header words of `for`, `select` and `case`: expanded but not run.
+    Clause(Vec<Word>),
+}
+
+impl Command {
+    fn collect_simple<'a>(&'a self, out: &mut Vec<&'a SimpleCommand>) {
+        let nested = |words: &'a [Word], out: &mut Vec<&'a SimpleCommand>| {
+            for word in words {
+                for script in &word.substitutions {
+                    out.extend(script.simple_commands());
+                }
+            }
+        };
+        match self {
+            Self::Simple(simple) => {
+                out.push(simple);
+                nested(&simple.assignments, out);
+                nested(&simple.words, out);
+                for redirect in &simple.redirects {
+                    nested(std::slice::from_ref(&redirect.target), out);
+                }
+            }
+            Self::Subshell { body, .. } | Self::Group { body, .. } => {
+                out.extend(body.simple_commands());
+            }
+            Self::Function { body, .. } => body.collect_simple(out),
+            Self::Clause(words) => nested(words, out),
+        }
+    }
+}
+
+/// A command with its leading assignments, words and redirections.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SimpleCommand {
+    pub assignments: Vec<Word>,
+    pub words: Vec<Word>,
+    pub redirects: Vec<Redirect>,
+}
+
+impl SimpleCommand {
+    /// The command name, if any.
+    #[must_use]
+    pub fn program(&self) -> Option<&Word> {
+        self.words.first()
+    }
+
+    /// Arguments after the command name.
+    #[must_use]
+    pub fn args(&self) -> &[Word] {
+        self.words.get(1..).unwrap_or_default()
+    }
+}
+
+/// A shell word after quote removal.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Word {
+    /// Text with quotes removed; expansions are kept verbatim (`$HOME`).
+    pub text: String,
+    /// Whether any part of the word was quoted.
+    pub quoted: bool,
+    /// Whether the word contains a parameter, arithmetic or command expansion.
+    pub expanded: bool,
+    /// Command and process substitutions, parsed recursively.
+    pub substitutions: Vec<Script>,
+}
+
+impl Word {
+    /// A plain word with no quoting or expansions.
+    #[must_use]
+    pub fn literal(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            ..Self::default()
+        }
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        !self.quoted && !self.expanded && self.text == keyword
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedirectOp {
+    /// `<`
+    Read,
+    /// `>`
+    Write,
+    /// `>>`
+    Append,
+    /// `>|`
+    Clobber,
+    /// `<>`
+    ReadWrite,
+    /// `<&`
+    DupIn,
+    /// `>&`
+    DupOut,
+    /// `&>`
+    WriteAll,
+    /// `&>>`
+    AppendAll,
+    /// `<<` and `<<-`
+    Heredoc,
+    /// `<<<`
+    HereString,
+}
+
+impl RedirectOp {
+    /// Whether the redirection writes to its target.
+    #[must_use]
+    pub const fn writes(self) -> bool {
+        matches!(
+            self,
+            Self::Write
+                | Self::Append
+                | Self::Clobber
+                | Self::ReadWrite
+                | Self::WriteAll
+                | Self::AppendAll
+        )
+    }
+
+    /// Whether the redirection feeds the command's standard input.
+    #[must_use]
+    pub const fn feeds_stdin(self) -> bool {
+        matches!(self, Self::Read | Self::Heredoc | Self::HereString)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Redirect {
+    pub fd: Option<u32>,
+    pub op: RedirectOp,
+    /// Target file, fd, here-string, or heredoc delimiter.
+    pub target: Word,
+    /// Heredoc body, with substitutions recorded on `target` when unquoted.
+    pub heredoc: Option<String>,
+}
+
+/// Parse a shell command string.
+pub fn parse(src: &str) -> ParseResult<Script> {
+    parse_at_depth(src, 0, false)
+}
+
+/// Parse a complete shell script.
+///
+/// On top of [`parse`], this rejects `if` and `do` blocks that are never
+/// closed or are closed by the wrong keyword, `&&` or `||` with no command
+/// after them, and heredocs that are never terminated.
+pub fn parse_strict(src: &str) -> ParseResult<Script> {
+    parse_at_depth(src, 0, true)
+}
+
+fn parse_at_depth(src: &str, depth: usize, strict: bool) -> ParseResult<Script> {
+    if depth > MAX_DEPTH {
+        return Err(ShellParseError::new("nesting too deep", 0));
+    }
+    let mut lexer = Lexer::new(src, depth, strict);
+    lexer.lex()?;
+    if strict {
+        if let Some(heredoc) = lexer.heredocs.iter().find(|heredoc| !heredoc.terminated) {
+            return Err(ShellParseError::new(
+                format!("heredoc `{}` is never terminated", heredoc.delimiter),
+                heredoc.offset,
+            ));
+        }
+    }
+    let mut parser = Parser {
+        tokens: lexer.tokens,
+        heredocs: lexer.heredocs,
+        pos: 0,
+        depth,
+        len: src.chars().count(),
+        strict,
+        blocks: Vec::new(),
+        base: 0,
+    };
+    parser.parse_script(End::Eof, 0)
+}
+
+// =============================================================================
+// Lexer
+// =============================================================================
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Word(Word),
+    Op(Op),
+    Redirect {
+        fd: Option<u32>,
+        op: RedirectOp,
+        heredoc: Option<usize>,
+    },
+    Newline,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Pipe,
+    And,
+    Or,
+    Semi,
+    CaseEnd,
+    Background,
+    LParen,
+    RParen,
+}
+
+#[derive(Debug, Default)]
+struct Heredoc {
+    delimiter: String,
+    strip_tabs: bool,
+    quoted: bool,
+    body: String,
+    offset: usize,
+    terminated: bool,
+}
+
+struct Lexer {
+    chars: Vec<char>,
+    pos: usize,
+    depth: usize,
+    strict: bool,
+    tokens: Vec<(Token, usize)>,
+    heredocs: Vec<Heredoc>,
+    pending: Vec<usize>,
+}
+
+impl Lexer {
+    fn new(src: &str, depth: usize, strict: bool) -> Self {
+        Self {
+            chars: src.chars().collect(),
+            pos: 0,
+            depth,
+            strict,
+            tokens: Vec::new(),
+            heredocs: Vec::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn push(&mut self, token: Token, start: usize) {
+        self.tokens.push((token, start));
+    }
+
+    fn lex(&mut self) -> ParseResult<()> {
+        while let Some(c) = self.peek() {
+            let start = self.pos;
+            match c {
+                ' ' | '\t' | '\r' => self.pos += 1,
+                '\\' if self.peek_at(1) == Some('\n') => self.pos += 2,
+                '\n' => {
+                    self.pos += 1;
+                    self.push(Token::Newline, start);
+                    self.read_heredoc_bodies();
+                }
+                '#' => {
+                    while self.peek().is_some_and(|c| c != '\n') {
+                        self.pos += 1;
+                    }
+                }
+                '|' => {
+                    self.pos += 1;
+                    let op = match self.peek() {
+                        Some('|') => {
+                            self.pos += 1;
+                            Op::Or
+                        }
+                        Some('&') => {
+                            self.pos += 1;
+                            Op::Pipe
+                        }
+                        _ => Op::Pipe,
+                    };
+                    self.push(Token::Op(op), start);
+                }
+                '&' => match self.peek_at(1) {
+                    Some('&') => {
+                        self.pos += 2;
+                        self.push(Token::Op(Op::And), start);
+                    }
+                    Some('>') => self.lex_redirect(None)?,
+                    _ => {
+                        self.pos += 1;
+                        self.push(Token::Op(Op::Background), start);
+                    }
+                },
+                ';' => {
+                    self.pos += 1;
+                    let op = match self.peek() {
+                        Some(';') => {
+                            self.pos += 1;
+                            if self.peek() == Some('&') {
+                                self.pos += 1;
+                            }
+                            Op::CaseEnd
+                        }
+                        Some('&') => {
+                            self.pos += 1;
+                            Op::CaseEnd
+                        }
+                        _ => Op::Semi,
+                    };
+                    self.push(Token::Op(op), start);
+                }
+                '(' => {
+                    self.pos += 1;
+                    self.push(Token::Op(Op::LParen), start);
+                }
+                ')' => {
+                    self.pos += 1;
+                    self.push(Token::Op(Op::RParen), start);
+                }
+                '<' | '>' if self.peek_at(1) != Some('(') => self.lex_redirect(None)?,
+                _ => {
+                    let word = self.lex_word()?;
+                    let is_fd = !word.quoted
+                        && !word.expanded
+                        && !word.text.is_empty()
+                        && word.text.chars().all(|c| c.is_ascii_digit())
+                        && matches!(self.peek(), Some('<' | '>'))
+                        && self.peek_at(1) != Some('(');
+                    if is_fd {
+                        self.lex_redirect(word.text.parse().ok())?;
+                    } else {
+                        self.push(Token::Word(word), start);
+                    }
+                }
+            }
+        }
+        // Heredocs whose body runs to the end of input.
+        self.read_heredoc_bodies();
+        Ok(())
+    }
+
+    fn lex_redirect(&mut self, fd: Option<u32>) -> ParseResult<()> {
+        let start = self.pos;
+        let rest: String = self.chars[self.pos..].iter().take(4).collect();
+        let (op, len) = if rest.starts_with("&>>") {
+            (RedirectOp::AppendAll, 3)
+        } else if rest.starts_with("&>") {
+            (RedirectOp::WriteAll, 2)
+        } else if rest.starts_with("<<<") {
+            (RedirectOp::HereString, 3)
+        } else if rest.starts_with("<<") {
+            (RedirectOp::Heredoc, 2)
+        } else if rest.starts_with("<>") {
+            (RedirectOp::ReadWrite, 2)
+        } else if rest.starts_with("<&") {
+            (RedirectOp::DupIn, 2)
+        } else if rest.starts_with('<') {
+            (RedirectOp::Read, 1)
+        } else if rest.starts_with(">>") {
+            (RedirectOp::Append, 2)
+        } else if rest.starts_with(">|") {
+            (RedirectOp::Clobber, 2)
+        } else if rest.starts_with(">&") {
+            (RedirectOp::DupOut, 2)
+        } else {
+            (RedirectOp::Write, 1)
+        };
+        self.pos += len;
+
+        if op != RedirectOp::Heredoc {
+            self.push(
+                Token::Redirect {
+                    fd,
+                    op,
+                    heredoc: None,
+                },
+                start,
+            );
+            return Ok(());
+        }
+
+        let strip_tabs = self.peek() == Some('-');
+        if strip_tabs {
+            self.pos += 1;
+        }
+        while matches!(self.peek(), Some(' ' | '\t')) {
+            self.pos += 1;
+        }
+        let delimiter_start = self.pos;
+        let delimiter = self.lex_word()?;
+        if delimiter.text.is_empty() && !delimiter.quoted {
+            return Err(ShellParseError::new("missing heredoc delimiter", start));
+        }
+        let index = self.heredocs.len();
+        self.heredocs.push(Heredoc {
+            delimiter: delimiter.text.clone(),
+            strip_tabs,
+            quoted: delimiter.quoted,
+            body: String::new(),
+            offset: start,
+            terminated: false,
+        });
+        self.pending.push(index);
+        self.push(
+            Token::Redirect {
+                fd,
+                op,
+                heredoc: Some(index),
+            },
+            start,
+        );
+        self.push(Token::Word(delimiter), delimiter_start);
+        Ok(())
+    }
+
+    fn read_heredoc_bodies(&mut self) {
+        for index in std::mem::take(&mut self.pending) {
+            let mut body = String::new();
+            while self.pos < self.chars.len() {
+                let line_end = self.chars[self.pos..]
+                    .iter()
+                    .position(|&c| c == '\n')
+                    .map_or(self.chars.len(), |offset| self.pos + offset);
+                let line: String = self.chars[self.pos..line_end].iter().collect();
+                self.pos = (line_end + 1).min(self.chars.len());
+                let heredoc = &self.heredocs[index];
+                let line = if heredoc.strip_tabs {
+                    line.trim_start_matches('\t').to_string()
+                } else {
+                    line
+                };
+                if line == heredoc.delimiter {
+                    self.heredocs[index].terminated = true;
+                    break;
+                }
+                body.push_str(&line);
+                body.push('\n');
+            }
+            self.heredocs[index].body = body;
+        }
+    }
+
+    fn lex_word(&mut self) -> ParseResult<Word> {
+        let mut word = Word::default();
+        while let Some(c) = self.peek() {
+            match c {
+                ' ' | '\t' | '\r' | '\n' | '|' | '&' | ';' | '(' | ')' => break,
+                '<' | '>' => {
+                    if self.peek_at(1) != Some('(') {
+                        break;
+                    }
+                    // Process substitution: `<(cmd)` / `>(cmd)`.
+                    let start = self.pos;
+                    self.pos += 2;
+                    let inner = self.take_until_close_paren(start)?;
+                    word.text.push(c);
+                    word.text.push('(');
+                    word.text.push_str(&inner);
+                    word.text.push(')');
+                    word.expanded = true;
+                    word.substitutions.push(self.nested(&inner, start)?);
+                }
+                '\'' => {
+                    let start = self.pos;
+                    self.pos += 1;
+                    word.quoted = true;
+                    loop {
+                        match self.peek() {
+                            Some('\'') => {
+                                self.pos += 1;
+                                break;
+                            }
+                            Some(c) => {
+                                word.text.push(c);
+                                self.pos += 1;
+                            }
+                            None => {
+                                return Err(ShellParseError::new(
+                                    "unterminated single quote",
+                                    start,
+                                ));
+                            }
+                        }
+                    }
+                }
+                '"' => {
+                    let start = self.pos;
+                    self.pos += 1;
+                    word.quoted = true;
+                    self.lex_double_quoted(&mut word, Some('"'))
+                        .map_err(|_| ShellParseError::new("unterminated double quote", start))?;
+                }
+                '\\' => {
+                    self.pos += 1;
+                    match self.peek() {
+                        Some('\n') => self.pos += 1,
+                        Some(c) => {
+                            word.text.push(c);
+                            word.quoted = true;
+                            self.pos += 1;
+                        }
+                        None => {}
+                    }
+                }
+                '$' => self.lex_dollar(&mut word)?,
+                '`' => self.lex_backtick(&mut word)?,
+                _ => {
+                    word.text.push(c);
+                    self.pos += 1;
+                }
+            }
+        }
+        Ok(word)
+    }
+
+    /// Lex double-quoted content up to `terminator` (or end of input when
+    /// `None`, as for unquoted heredoc bodies).
+    fn lex_double_quoted(&mut self, word: &mut Word, terminator: Option<char>) -> ParseResult<()> {
+        loop {
+            let Some(c) = self.peek() else {
+                return match terminator {
+                    Some(_) => Err(ShellParseError::new("unterminated quote", self.pos)),
+                    None => Ok(()),
+                };
+            };
+            if Some(c) == terminator {
+                self.pos += 1;
+                return Ok(());
+            }
+            match c {
+                '\\' => {
+                    self.pos += 1;
+                    match self.peek() {
+                        Some('\n') => self.pos += 1,
+                        Some(next @ ('$' | '`' | '"' | '\\')) => {
+                            word.text.push(next);
+                            self.pos += 1;
+                        }
+                        Some(next) => {
+                            word.text.push('\\');
+                            word.text.push(next);
+                            self.pos += 1;
+                        }
+                        None => word.text.push('\\'),
+                    }
+                }
+                '$' => self.lex_dollar(word)?,
+                '`' => self.lex_backtick(word)?,
+                _ => {
+                    word.text.push(c);
+                    self.pos += 1;
+                }
+            }
+        }
+    }
+
+    fn lex_dollar(&mut self, word: &mut Word) -> ParseResult<()> {
+        let start = self.pos;
+        match self.peek_at(1) {
+            Some('(') if self.peek_at(2) == Some('(') => {
+                // Arithmetic expansion: keep verbatim.
+                self.pos += 3;
+                let inner = self.take_until_close_paren(start)?;
+                if self.peek() != Some(')') {
+                    return Err(ShellParseError::new(
+                        "unterminated arithmetic expansion",
+                        start,
+                    ));
+                }
+                self.pos += 1;
+                word.text.push_str("$((");
+                word.text.push_str(&inner);
+                word.text.push_str("))");
+                word.expanded = true;
+            }
+            Some('(') => {
+                self.pos += 2;
+                let inner = self.take_until_close_paren(start)?;
+                word.text.push_str("$(");
+                word.text.push_str(&inner);
+                word.text.push(')');
+                word.expanded = true;
+                word.substitutions.push(self.nested(&inner, start)?);
+            }
+            Some('{') => {
+                self.pos += 2;
+                let inner = self.take_until_close_brace(start)?;
+                word.text.push_str("${");
+                word.text.push_str(&inner);
+                word.text.push('}');
+                word.expanded = true;
+                // `${x:-$(cmd)}` runs `cmd`.
+                let mut nested = Lexer::new(&inner, self.depth + 1, self.strict);
+                let mut inner_word = Word::default();
+                nested.lex_double_quoted(&mut inner_word, None)?;
+                word.substitutions.extend(inner_word.substitutions);
+            }
+            Some('\'') => {
+                // ANSI-C quoting: `$'...'`.
+                self.pos += 2;
+                word.quoted = true;
+                loop {
+                    match self.peek() {
+                        Some('\\') => {
+                            word.text.push('\\');
+                            if let Some(next) = self.peek_at(1) {
+                                word.text.push(next);
+                            }
+                            self.pos += 2;
+                        }
+                        Some('\'') => {
+                            self.pos += 1;
+                            break;
+                        }
+                        Some(c) => {
+                            word.text.push(c);
+                            self.pos += 1;
+                        }
+                        None => {
+                            return Err(ShellParseError::new("unterminated $'...' quote", start));
+                        }
+                    }
+                }
+            }
+            Some(c) if c.is_ascii_alphanumeric() || "_@*#?$!-".contains(c) => {
+                word.text.push('$');
+                self.pos += 1;
+                if c.is_ascii_alphabetic() || c == '_' {
+                    while self
+                        .peek()
+                        .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_')
+                    {
+                        word.text.push(self.chars[self.pos]);
+                        self.pos += 1;
+                    }
+                } else {
+                    word.text.push(c);
+                    self.pos += 1;
+                }
+                word.expanded = true;
+            }
+            _ => {
+                word.text.push('$');
+                self.pos += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn lex_backtick(&mut self, word: &mut Word) -> ParseResult<()> {
+        let start = self.pos;
+        self.pos += 1;
+        let mut inner = String::new();
+        loop {
+            match self.peek() {
+                Some('`') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some('\\') => {
+                    if let Some(next) = self.peek_at(1) {
+                        if !matches!(next, '`' | '\\' | '$') {
+                            inner.push('\\');
+                        }
+                        inner.push(next);
+                    }
+                    self.pos += 2;
+                }
+                Some(c) => {
+                    inner.push(c);
+                    self.pos += 1;
+                }
+                None => return Err(ShellParseError::new("unterminated backtick", start)),
+            }
+        }
+        word.text.push('`');
+        word.text.push_str(&inner);
+        word.text.push('`');
+        word.expanded = true;
+        word.substitutions.push(self.nested(&inner, start)?);
+        Ok(())
+    }
+
+    fn nested(&self, src: &str, offset: usize) -> ParseResult<Script> {
+        parse_at_depth(src, self.depth + 1, self.strict)
+            .map_err(|err| ShellParseError::new(err.message, offset))
+    }
+
+    /// Consume up to (and including) the `)` matching an already consumed
+    /// `(`, returning the text in between.
+    fn take_until_close_paren(&mut self, start: usize) -> ParseResult<String> {
+        self.take_balanced('(', ')', start)
+    }
+
+    fn take_until_close_brace(&mut self, start: usize) -> ParseResult<String> {
+        self.take_balanced('{', '}', start)
+    }
+
+    fn take_balanced(&mut self, open: char, close: char, start: usize) -> ParseResult<String> {
+        let mut depth = 1usize;
+        let mut quote: Option<char> = None;
+        let begin = self.pos;
+        while let Some(c) = self.peek() {
+            self.pos += 1;
+            if let Some(q) = quote {
+                if c == '\\' && q != '\'' {
+                    self.pos += 1;
+                } else if c == q {
+                    quote = None;
+                }
+                continue;
+            }
+            match c {
+                '\\' => self.pos += 1,
+                '\'' | '"' | '`' => quote = Some(c),
+                c if c == open => depth += 1,
+                c if c == close => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok(self.chars[begin..self.pos - 1].iter().collect());
+                    }
+                }
+                _ => {}
+            }
+        }
+        Err(ShellParseError::new(
+            format!("unterminated substitution (missing `{close}`)"),
+            start,
+        ))
+    }
+}
+
+// =============================================================================
+// Parser
+// =============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum End {
+    Eof,
+    RParen,
+    Brace,
+    CaseArm,
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    heredocs: Vec<Heredoc>,
+    pos: usize,
+    depth: usize,
+    len: usize,
+    strict: bool,
+    /// Open `if` / `do` keywords and their offsets (strict mode only)
+    blocks: Vec<(&'static str, usize)>,
+    /// Blocks below this index belong to an enclosing list
+    base: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(token, _)| token)
+    }
+
+    fn offset(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .map_or(self.len, |(_, offset)| *offset)
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Word(word)) if word.is_keyword(keyword))
+    }
+
+    fn error(&self, message: impl Into<String>) -> ShellParseError {
+        ShellParseError::new(message, self.offset())
+    }
+
+    fn skip_newlines(&mut self) {
+        while matches!(self.peek(), Some(Token::Newline)) {
+            self.pos += 1;
+        }
+    }
+
+    fn parse_script(&mut self, end: End, nesting: usize) -> ParseResult<Script> {
+        let outer = std::mem::replace(&mut self.base, self.blocks.len());
+        let script = self.parse_list(end, nesting)?;
+        if let Some(&(opener, offset)) = self.blocks.get(self.base) {
+            return Err(ShellParseError::new(
+                format!("`{opener}` is never closed with `{}`", block_closer(opener)),
+                offset,
+            ));
+        }
+        self.base = outer;
+        Ok(script)
+    }
+
+    /// Open a block, or check that `keyword` closes the innermost one.
+    fn track_block(&mut self, keyword: &str) -> ParseResult<()> {
+        if !self.strict {
+            return Ok(());
+        }
+        let open = self.blocks[self.base..].last().copied();
+        match keyword {
+            "if" => self.blocks.push(("if", self.offset())),
+            "do" => self.blocks.push(("do", self.offset())),
+            "then" | "elif" | "else" if open.is_some_and(|(opener, _)| opener == "if") => {}
+            "fi" | "done" if open.is_some_and(|(opener, _)| block_closer(opener) == keyword) => {
+                self.blocks.pop();
+            }
+            "then" | "elif" | "else" | "fi" | "done" => {
+                return Err(self.error(match open {
+                    Some((opener, _)) => format!(
+                        "unexpected `{keyword}` (expected `{}` for `{opener}`)",
+                        block_closer(opener)
+                    ),
+                    None => format!("unexpected `{keyword}`"),
+                }));
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn parse_list(&mut self, end: End, nesting: usize) -> ParseResult<Script> {
+        if self.depth + nesting > MAX_DEPTH {
+            return Err(self.error("nesting too deep"));
+        }
+        let mut script = Script::default();
+        loop {
+            while matches!(
+                self.peek(),
+                Some(Token::Newline | Token::Op(Op::Semi | Op::Background))
+            ) {
+                self.pos += 1;
+            }
+            match (self.peek(), end) {
+                (None, End::Eof) => return Ok(script),
+                (None, _) => return Err(self.error("unexpected end of input")),
+                (Some(Token::Op(Op::RParen)), End::RParen) => {
+                    self.pos += 1;
+                    return Ok(script);
+                }
+                (Some(Token::Op(Op::RParen)), _) => return Err(self.error("unexpected `)`")),
+                (Some(Token::Op(Op::CaseEnd)), End::CaseArm) => {
+                    self.pos += 1;
+                    return Ok(script);
+                }
+                (Some(Token::Word(word)), End::Brace) if word.is_keyword("}") => {
+                    self.pos += 1;
+                    return Ok(script);
+                }
+                (Some(Token::Word(word)), End::CaseArm) if word.is_keyword("esac") => {
+                    return Ok(script);
+                }
+                _ => {}
+            }
+
+            let pipeline = self.parse_pipeline(nesting)?;
+            if !pipeline.commands.is_empty() {
+                script.pipelines.push(pipeline);
+            }
+
+            match self.peek() {
+                Some(Token::Op(Op::And | Op::Or)) => {
+                    let offset = self.offset();
+                    let op = if matches!(self.peek(), Some(Token::Op(Op::And))) {
+                        "&&"
+                    } else {
+                        "||"
+                    };
+                    self.pos += 1;
+                    self.skip_newlines();
+                    let dangling = match self.peek() {
+                        None => true,
+                        Some(Token::Op(next)) => *next != Op::LParen,
+                        Some(_) => false,
+                    };
+                    if self.strict && dangling {
+                        return Err(ShellParseError::new(
+                            format!("missing command after `{op}`"),
+                            offset,
+                        ));
+                    }
+                }
+                Some(
+                    Token::Op(Op::Semi | Op::Background | Op::RParen | Op::CaseEnd)
+                    | Token::Newline,
+                )
+                | None => {}
+                Some(Token::Word(word)) if word.is_keyword("}") || word.is_keyword("esac") => {}
+                Some(_) => return Err(self.error("unexpected token")),
+            }
+        }
+    }
+
+    fn parse_pipeline(&mut self, nesting: usize) -> ParseResult<Pipeline> {
+        let mut pipeline = Pipeline::default();
+        if self.peek_keyword("!") {
+            self.pos += 1;
+            pipeline.negated = true;
+        }
+        if let Some(command) = self.parse_command(nesting)? {
+            pipeline.commands.push(command);
+        }
+        while matches!(self.peek(), Some(Token::Op(Op::Pipe))) {
+            self.pos += 1;
+            self.skip_newlines();
+            match self.parse_command(nesting)? {
+                Some(command) => pipeline.commands.push(command),
+                None => return Err(self.error("missing command after `|`")),
+            }
+        }
+        Ok(pipeline)
+    }
+
+    fn parse_command(&mut self, nesting: usize) -> ParseResult<Option<Command>> {
+        loop {
+            match self.peek() {
+                Some(Token::Op(Op::LParen)) => {
+                    self.pos += 1;
+                    let body = self.parse_script(End::RParen, nesting + 1)?;
+                    let redirects = self.parse_redirects()?;
+                    return Ok(Some(Command::Subshell { body, redirects }));
+                }
+                Some(Token::Word(word)) if word.is_keyword("{") => {
+                    self.pos += 1;
+                    let body = self.parse_script(End::Brace, nesting + 1)?;
+                    let redirects = self.parse_redirects()?;
+                    return Ok(Some(Command::Group { body, redirects }));
+                }
+                Some(Token::Word(word)) => {
+                    let keyword = if word.quoted || word.expanded {
+                        String::new()
+                    } else {
+                        word.text.clone()
+                    };
+                    match keyword.as_str() {
+                        // Prefixes: what follows is an ordinary command.
+                        "if" | "then" | "elif" | "else" | "do" | "while" | "until" | "!" => {
+                            self.track_block(&keyword)?;
+                            self.pos += 1;
+                        }
+                        // Terminators of compound commands; keep trailing
+                        // redirections such as `done < input`.
+                        "fi" | "done" => {
+                            self.track_block(&keyword)?;
+                            self.pos += 1;
+                            let redirects = self.parse_redirects()?;
+                            if redirects.is_empty() {
+                                return Ok(None);
+                            }
+                            return Ok(Some(Command::Group {
+                                body: Script::default(),
+                                redirects,
+                            }));
+                        }
+                        "function" => {
+                            self.pos += 1;
+                            return self.parse_function_keyword(nesting).map(Some);
+                        }
+                        "for" | "select" => {
+                            self.pos += 1;
+                            return Ok(Some(Command::Clause(self.parse_clause_words())));
+                        }
+                        "case" => {
+                            self.pos += 1;
+                            return self.parse_case(nesting).map(Some);
+                        }
+                        "}" if self.strict => return Err(self.error("unexpected `}`")),
+                        _ => {
+                            if let Some(function) = self.parse_function(nesting)? {
+                                return Ok(Some(function));
+                            }
+                            return self.parse_simple();
+                        }
+                    }
+                }
+                Some(Token::Redirect { .. }) => return self.parse_simple(),
+                _ => return Ok(None),
+            }
+        }
+    }
+
+    fn parse_clause_words(&mut self) -> Vec<Word> {
+        let mut words = Vec::new();
+        while let Some(Token::Word(word)) = self.peek() {
+            if word.is_keyword("do") {
+                break;
+            }
+            words.push(word.clone());
+            self.pos += 1;
+        }
+        words
+    }
+
+    fn parse_case(&mut self, nesting: usize) -> ParseResult<Command> {
+        let Some(Token::Word(subject)) = self.peek().cloned() else {
+            return Err(self.error("missing `case` subject"));
+        };
+        self.pos += 1;
+        self.skip_newlines();
+        if !self.peek_keyword("in") {
+            return Err(self.error("expected `in`"));
+        }
+        self.pos += 1;
+
+        let mut body = Script::default();
+        body.pipelines.push(Pipeline {
+            negated: false,
+            commands: vec![Command::Clause(vec![subject])],
+        });
+        loop {
+            self.skip_newlines();
+            if self.peek_keyword("esac") {
+                self.pos += 1;
+                break;
+            }
+            if matches!(self.peek(), Some(Token::Op(Op::LParen))) {
+                self.pos += 1;
+            }
+            // Patterns up to `)`, separated by `|`.
+            loop {
+                match self.peek() {
+                    Some(Token::Word(_) | Token::Op(Op::Pipe)) => self.pos += 1,
+                    Some(Token::Op(Op::RParen)) => {
+                        self.pos += 1;
+                        break;
+                    }
+                    _ => return Err(self.error("malformed `case` pattern")),
+                }
+            }
+            let arm = self.parse_script(End::CaseArm, nesting + 1)?;
+            body.pipelines.extend(arm.pipelines);
+        }
+        let redirects = self.parse_redirects()?;
+        Ok(Command::Group { body, redirects })
+    }
+
+    fn parse_function(&mut self, nesting: usize) -> ParseResult<Option<Command>> {
+        let is_function = matches!(
+            (
+                self.tokens.get(self.pos + 1).map(|(t, _)| t),
+                self.tokens.get(self.pos + 2).map(|(t, _)| t),
+            ),
+            (Some(Token::Op(Op::LParen)), Some(Token::Op(Op::RParen)))
+        );
+        if !is_function {
+            return Ok(None);
+        }
+        let Some(Token::Word(name)) = self.peek().cloned() else {
+            return Ok(None);
+        };
+        self.pos += 3;
+        self.skip_newlines();
+        let Some(body) = self.parse_command(nesting + 1)? else {
+            return Err(self.error("missing function body"));
+        };
+        Ok(Some(Command::Function {
+            name: name.text,
+            body: Box::new(body),
+        }))
+    }
+
+    /// `function name [()] body`
+    fn parse_function_keyword(&mut self, nesting: usize) -> ParseResult<Command> {
+        let Some(Token::Word(name)) = self.peek().cloned() else {
+            return Err(self.error("missing function name"));
+        };
+        self.pos += 1;
+        if matches!(self.peek(), Some(Token::Op(Op::LParen))) {
+            self.pos += 1;
+            if !matches!(self.peek(), Some(Token::Op(Op::RParen))) {
+                return Err(self.error("expected `)`"));
+            }
+            self.pos += 1;
+        }
+        self.skip_newlines();
+        let Some(body) = self.parse_command(nesting + 1)? else {
+            return Err(self.error("missing function body"));
+        };
+        Ok(Command::Function {
+            name: name.text,
+            body: Box::new(body),
+        })
+    }
+
+    fn parse_simple(&mut self) -> ParseResult<Option<Command>> {
+        let mut simple = SimpleCommand::default();
+        loop {
+            match self.peek() {
+                Some(Token::Word(word)) => {
+                    let word = word.clone();
+                    self.pos += 1;
+                    if simple.words.is_empty() && is_assignment(&word) {
+                        simple.assignments.push(word);
+                    } else {
+                        simple.words.push(word);
+                    }
+                }
+                Some(Token::Redirect { .. }) => {
+                    let redirect = self.parse_redirect()?;
+                    simple.redirects.push(redirect);
+                }
+                _ => break,
+            }
+        }
+        if simple.words.is_empty() && simple.assignments.is_empty() && simple.redirects.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(Command::Simple(simple)))
+    }
+
+    fn parse_redirects(&mut self) -> ParseResult<Vec<Redirect>> {
+        let mut redirects = Vec::new();
+        while matches!(self.peek(), Some(Token::Redirect { .. })) {
+            redirects.push(self.parse_redirect()?);
+        }
+        Ok(redirects)
+    }
+
+    fn parse_redirect(&mut self) -> ParseResult<Redirect> {
+        let Some(Token::Redirect { fd, op, heredoc }) = self.peek().cloned() else {
+            return Err(self.error("expected redirection"));
+        };
+        self.pos += 1;
+        let Some(Token::Word(mut target)) = self.peek().cloned() else {
+            return Err(self.error("missing redirection target"));
+        };
+        self.pos += 1;
+
+        let body = match heredoc {
+            Some(index) => {
+                let heredoc = &self.heredocs[index];
+                if !heredoc.quoted {
+                    let mut lexer = Lexer::new(&heredoc.body, self.depth + 1, self.strict);
+                    let mut expanded = Word::default();
+                    lexer.lex_double_quoted(&mut expanded, None)?;
+                    target.expanded |= expanded.expanded;
+                    target.substitutions.extend(expanded.substitutions);
+                }
+                Some(heredoc.body.clone())
+            }
+            None => None,
+        };
+        Ok(Redirect {
+            fd,
+            op,
+            target,
+            heredoc: body,
+        })
+    }
+}
+
+/// Keyword that closes a block opened by `opener`.
+fn block_closer(opener: &str) -> &'static str {
+    if opener == "if" { "fi" } else { "done" }
+}
+
+/// `NAME=value` (or `NAME+=value`) with an unquoted, valid name.
+fn is_assignment(word: &Word) -> bool {
+    let Some((name, _)) = word.text.split_once('=') else {
+        return false;
+    };
+    let name = name.strip_suffix('+').unwrap_or(name);
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn programs(script: &Script) -> Vec<String> {
+        script
+            .simple_commands()
+            .iter()
+            .filter_map(|cmd| cmd.program().map(|w| w.text.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn parses_lists_and_pipelines() {
+        let script = parse("cd /tmp && ls -la | grep foo; echo done &").unwrap();
+        assert_eq!(script.pipelines.len(), 3);
+        assert_eq!(script.pipelines[1].commands.len(), 2);
+        assert_eq!(programs(&script), vec!["cd", "ls", "grep", "echo"]);
+    }
+
+    #[test]
+    fn removes_quotes_and_keeps_expansions() {
+        let script = parse(r#"echo 'a b' "c $HOME" d\ e"#).unwrap();
+        let cmd = script.simple_commands()[0];
+        let args: Vec<&str> = cmd.args().iter().map(|w| w.text.as_str()).collect();
+        assert_eq!(args, vec!["a b", "c $HOME", "d e"]);
+        assert!(cmd.args()[1].expanded);
+        assert!(!cmd.args()[0].expanded);
+    }
+
+    #[test]
+    fn parses_substitutions_recursively() {
+        let script = parse("echo $(cat `which rm`) <(ls)").unwrap();
+        assert_eq!(programs(&script), vec!["echo", "cat", "which", "ls"]);
+    }
+
+    #[test]
+    fn parses_subshells_groups_and_functions() {
+        let script = parse("(cd x && make) > log; { rm a; }; f() { git push; }").unwrap();
+        assert_eq!(programs(&script), vec!["cd", "make", "rm", "git"]);
+        assert!(matches!(
+            &script.pipelines[0].commands[0],
+            Command::Subshell { redirects, .. } if redirects[0].op == RedirectOp::Write
+        ));
+
+        let script =
+            parse("function f { rm -r x; }; while read l; do echo $l; done < list").unwrap();
+        assert_eq!(programs(&script), vec!["rm", "read", "echo"]);
+    }
+
+    #[test]
+    fn parses_control_flow() {
+        let script = parse(
+            "if test -f x; then rm x; else touch x; fi\nfor f in $(ls); do echo $f; done\ncase $1 in a|b) rm -r a ;; *) true ;; esac",
+        )
+        .unwrap();
+        assert_eq!(
+            programs(&script),
+            vec!["test", "rm", "touch", "ls", "echo", "rm", "true"]
+        );
+    }
+
+    #[test]
+    fn parses_redirections_and_fds() {
+        let script = parse("cmd 2>&1 >>out.log < in &>/dev/null").unwrap();
+        let cmd = script.simple_commands()[0];
+        assert_eq!(cmd.words.len(), 1);
+        let ops: Vec<RedirectOp> = cmd.redirects.iter().map(|r| r.op).collect();
+        assert_eq!(
+            ops,
+            vec![
+                RedirectOp::DupOut,
+                RedirectOp::Append,
+                RedirectOp::Read,
+                RedirectOp::WriteAll
+            ]
+        );
+        assert_eq!(cmd.redirects[0].fd, Some(2));
+        assert_eq!(cmd.redirects[1].target.text, "out.log");
+    }
+
+    #[test]
+    fn parses_heredocs() {
+        let script = parse("bash <<'EOF'\nrm -rf /\nEOF\necho after").unwrap();
+        let cmd = script.simple_commands()[0];
+        assert_eq!(cmd.redirects[0].op, RedirectOp::Heredoc);
+        assert_eq!(cmd.redirects[0].heredoc.as_deref(), Some("rm -rf /\n"));
+        assert_eq!(programs(&script), vec!["bash", "echo"]);
+
+        let script = parse("cat <<EOF\n$(whoami)\nEOF").unwrap();
+        assert_eq!(programs(&script), vec!["cat", "whoami"]);
+    }
+
+    #[test]
+    fn records_assignments() {
+        let script = parse("FOO=1 BAR=$(id) make install").unwrap();
+        let cmd = script.simple_commands()[0];
+        assert_eq!(cmd.assignments.len(), 2);
+        assert_eq!(cmd.program().unwrap().text, "make");
+        assert_eq!(programs(&script), vec!["make", "id"]);
+    }
+
+    #[test]
+    fn comments_and_continuations() {
+        let script = parse("ls \\\n  -la # list files\n").unwrap();
+        let cmd = script.simple_commands()[0];
+        assert_eq!(cmd.args().len(), 1);
+        assert_eq!(cmd.args()[0].text, "-la");
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        assert!(parse("echo 'unterminated").is_err());
+        assert!(parse("echo $(ls").is_err());
+        assert!(parse("(cd x").is_err());
+        assert!(parse("ls |").is_err());
+        assert!(parse("echo >").is_err());
+    }
+
+    #[test]
+    fn strict_requires_complete_scripts() {
+        for src in [
+            "if a; then b; elif c; then d; else e; fi",
+            "for i in 1 2\ndo\n  echo $i\ndone < list",
+            "case $x in a) if y; then z; fi;; esac",
+            "cat <<-EOF\n\tbody\n\tEOF\necho done fi",
+        ] {
+            assert!(parse_strict(src).is_ok(), "{src}");
+        }
+
+        for (src, message, offset) in [
+            (
+                "if true; then\n  echo hi\n",
+                "`if` is never closed with `fi`",
+                0,
+            ),
+            (
+                "for x in a; do echo; fi",
+                "unexpected `fi` (expected `done` for `do`)",
+                21,
+            ),
+            ("{ if x; then y; }", "`if` is never closed with `fi`", 2),
+            ("ls &&", "missing command after `&&`", 3),
+            ("cat <<EOF\nbody", "heredoc `EOF` is never terminated", 4),
+            ("x=$(if a; then b)", "`if` is never closed with `fi`", 2),
+        ] {
+            assert!(parse(src).is_ok(), "{src}");
+            assert_eq!(
+                parse_strict(src),
+                Err(ShellParseError::new(message, offset)),
+                "{src}"
+            );
+        }
+    }
+
+    #[test]
+    fn limits_nesting_depth() {
+        let mut src = "ls".to_string();
+        for _ in 0..=MAX_DEPTH + 1 {
+            src = format!("echo $({src})");
+        }
+        assert!(parse(&src).is_err());
+    }
+}
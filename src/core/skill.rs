@@ -1,9 +1,12 @@
 //! Skill data structure
 
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 
+use crate::error::{MsError, Result};
+
 fn default_format_version() -> String {
     SkillSpec::FORMAT_VERSION.to_string()
 }
@@ -203,6 +206,9 @@ pub struct SkillMetadata {
     /// Context tags for auto-loading relevance matching.
     #[serde(default, skip_serializing_if = "ContextTags::is_empty")]
     pub context: ContextTags,
+    /// Script entry points exposed as tools (e.g. by `ms mcp serve`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scripts: Vec<ScriptEntry>,
 }
 
 /// A section in a skill
@@ -225,6 +231,20 @@ pub struct SkillBlock {
     pub block_type: BlockType,
     /// Block content
     pub content: String,
+    /// Lint rules silenced for this block by inline markers
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suppressions: Vec<LintSuppression>,
+}
+
+/// Lint rules silenced for the following block, written in SKILL.md as
+/// `<!-- ms-lint-disable-next-block rule-a, rule-b: reason -->`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintSuppression {
+    /// Rule IDs to silence
+    pub rules: Vec<String>,
+    /// Why the findings are acceptable
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
 }
 
 /// Block type
@@ -357,13 +377,96 @@ pub struct SkillAssets {
     /// Test files in tests/ directory
     #[serde(default)]
     pub tests: Vec<TestFile>,
+    /// Skill directory the asset paths are relative to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub root: Option<PathBuf>,
+}
+
+impl SkillAssets {
+    /// Inventory `scripts/`, `references/`, and `tests/` of a skill directory.
+    pub fn collect(dir: &Path) -> Result<Self> {
+        let mut assets = Self {
+            root: Some(dir.to_path_buf()),
+            ..Self::default()
+        };
+        for path in asset_files(dir, "scripts")? {
+            let ext = extension_of(&path);
+            assets.scripts.push(ScriptFile {
+                path,
+                language: script_language(&ext),
+                description: None,
+            });
+        }
+        for path in asset_files(dir, "references")? {
+            let ext = extension_of(&path);
+            assets.references.push(ReferenceFile {
+                path,
+                file_type: if ext.is_empty() {
+                    "text".to_string()
+                } else {
+                    ext
+                },
+            });
+        }
+        for path in asset_files(dir, "tests")? {
+            assets.tests.push(TestFile {
+                path,
+                framework: None,
+            });
+        }
+        Ok(assets)
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.scripts.is_empty() && self.references.is_empty() && self.tests.is_empty()
+    }
+}
+
+fn asset_files(dir: &Path, sub: &str) -> Result<Vec<PathBuf>> {
+    let root = dir.join(sub);
+    if !root.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut files = Vec::new();
+    for entry in walkdir::WalkDir::new(&root).sort_by_file_name() {
+        let entry = entry
+            .map_err(|e| MsError::Config(format!("Failed to walk {}: {e}", root.display())))?;
+        if entry.file_type().is_file() {
+            if let Ok(rel) = entry.path().strip_prefix(dir) {
+                files.push(rel.to_path_buf());
+            }
+        }
+    }
+    Ok(files)
+}
+
+fn extension_of(path: &Path) -> String {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+fn script_language(ext: &str) -> String {
+    match ext {
+        "sh" | "bash" => "bash",
+        "py" => "python",
+        "js" | "mjs" | "cjs" => "javascript",
+        "ts" => "typescript",
+        "rb" => "ruby",
+        "ps1" => "powershell",
+        "" => "text",
+        other => other,
+    }
+    .to_string()
 }
 
 /// A script file associated with a skill
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScriptFile {
     /// Relative path from skill directory
-    pub path: std::path::PathBuf,
+    pub path: PathBuf,
     /// Script language (bash, python, etc.)
     pub language: String,
     /// Brief description
@@ -375,7 +478,7 @@ pub struct ScriptFile {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReferenceFile {
     /// Relative path from skill directory
-    pub path: std::path::PathBuf,
+    pub path: PathBuf,
     /// File type
     pub file_type: String,
 }
@@ -384,12 +487,86 @@ pub struct ReferenceFile {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TestFile {
     /// Relative path from skill directory
-    pub path: std::path::PathBuf,
+    pub path: PathBuf,
     /// Test framework (if applicable)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub framework: Option<String>,
 }
 
+/// A script entry point declared in skill metadata.
+///
+/// ```yaml
+/// scripts:
+///   - name: merge
+///     path: scripts/merge.py
+///     description: Merge PDFs into one file
+///     args:
+///       - { name: output, type: string, required: true }
+///       - { name: inputs, type: array, positional: true }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScriptEntry {
+    /// Tool name, unique within the skill
+    pub name: String,
+    /// Relative path from skill directory
+    pub path: String,
+    /// What the script does
+    #[serde(default)]
+    pub description: String,
+    /// Interpreter (inferred from the file extension when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interpreter: Option<String>,
+    /// Arguments, passed to the script in declaration order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<ScriptArg>,
+    /// Maximum run time in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+}
+
+/// A typed script argument.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScriptArg {
+    pub name: String,
+    #[serde(rename = "type", default)]
+    pub arg_type: ScriptArgType,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub required: bool,
+    /// Pass the value positionally instead of as `--name value`
+    #[serde(default)]
+    pub positional: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<serde_json::Value>,
+}
+
+/// JSON type of a script argument.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScriptArgType {
+    #[default]
+    String,
+    Integer,
+    Number,
+    Boolean,
+    /// List of strings, passed as repeated values
+    Array,
+}
+
+impl ScriptArgType {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::String => "string",
+            Self::Integer => "integer",
+            Self::Number => "number",
+            Self::Boolean => "boolean",
+            Self::Array => "array",
+        }
+    }
+}
+
 // =============================================================================
 // TRIGGERS AND REQUIREMENTS
 // =============================================================================
@@ -484,6 +661,11 @@ pub struct ContextTags {
     /// Advanced signal patterns for fine-grained relevance matching.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub signals: Vec<ContextSignal>,
+
+    /// Packages this skill is about (e.g., tokio, react, django).
+    /// Matched against dependencies parsed from project manifests and lockfiles.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<DependencyTag>,
 }
 
 impl ContextTags {
@@ -494,6 +676,7 @@ impl ContextTags {
             && self.file_patterns.is_empty()
             && self.tools.is_empty()
             && self.signals.is_empty()
+            && self.dependencies.is_empty()
     }
 
     /// Check if a project type matches this context.
@@ -530,6 +713,58 @@ impl ContextTags {
     }
 }
 
+/// A package dependency that indicates skill relevance.
+///
+/// Matches when the project depends on the named package, optionally
+/// restricted to an ecosystem and a semver range.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DependencyTag {
+    /// Package name (e.g., "tokio", "@tanstack/react-query", "github.com/gin-gonic/gin").
+    pub name: String,
+
+    /// Ecosystem the package belongs to (e.g., "cargo", "npm", "pypi", "go", "rubygems").
+    /// Matches any ecosystem when omitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ecosystem: Option<String>,
+
+    /// Semver range the project's version must satisfy (e.g., ">=1.0, <2").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+impl DependencyTag {
+    /// Create a dependency tag matching any version in any ecosystem.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ecosystem: None,
+            version: None,
+        }
+    }
+
+    /// Restrict the tag to an ecosystem.
+    #[must_use]
+    pub fn in_ecosystem(mut self, ecosystem: impl Into<String>) -> Self {
+        self.ecosystem = Some(ecosystem.into());
+        self
+    }
+
+    /// Restrict the tag to a semver range.
+    #[must_use]
+    pub fn with_version(mut self, range: impl Into<String>) -> Self {
+        self.version = Some(range.into());
+        self
+    }
+
+    /// Parse the version range (returns None if absent or invalid).
+    #[must_use]
+    pub fn version_req(&self) -> Option<semver::VersionReq> {
+        self.version
+            .as_deref()
+            .and_then(|v| semver::VersionReq::parse(v).ok())
+    }
+}
+
 /// A contextual signal pattern for relevance matching.
 ///
 /// Signals are advanced patterns that look for specific code constructs
@@ -987,6 +1222,7 @@ mod tests {
             file_patterns: vec!["*.rs".to_string()],
             tools: vec!["cargo".to_string()],
             signals: vec![ContextSignal::new("test", "pattern", 0.5)],
+            dependencies: vec![DependencyTag::new("tokio").with_version("^1")],
         };
         let json = serde_json::to_string(&ctx).unwrap();
         let parsed: ContextTags = serde_json::from_str(&json).unwrap();
@@ -994,6 +1230,7 @@ mod tests {
         assert_eq!(parsed.file_patterns, ctx.file_patterns);
         assert_eq!(parsed.tools, ctx.tools);
         assert_eq!(parsed.signals.len(), 1);
+        assert_eq!(parsed.dependencies, ctx.dependencies);
     }
 
     #[test]
@@ -199,6 +199,7 @@ mod tests {
                     id: "rule-1".to_string(),
                     block_type: BlockType::Rule,
                     content: "Always sanitize input.".to_string(),
+                    suppressions: Vec::new(),
                 }],
             }],
             ..Default::default()
@@ -228,6 +229,7 @@ mod tests {
                     id: "policy-1".to_string(),
                     block_type: BlockType::Rule,
                     content: "Never run destructive commands.".to_string(),
+                    suppressions: Vec::new(),
                 }],
             }],
             ..Default::default()
@@ -1,8 +1,11 @@
 //! Round-trip spec <-> markdown mapping
 
+use serde::Deserialize;
 use serde_json::Value as JsonValue;
 
-use super::skill::{BlockType, SkillBlock, SkillMetadata, SkillSection, SkillSpec};
+use super::skill::{
+    BlockType, LintSuppression, SkillBlock, SkillMetadata, SkillSection, SkillSpec,
+};
 use crate::error::{MsError, Result};
 
 /// Bidirectional mapping between `SkillSpec` and SKILL.md.
@@ -33,18 +36,68 @@ impl SpecLens {
     }
 }
 
+/// Spec fields carried in SKILL.md frontmatter next to the metadata.
+#[derive(Deserialize)]
+struct FrontmatterSpecFields {
+    #[serde(default)]
+    extends: Option<String>,
+}
+
+/// HTML comment marker silencing lint rules for the next block.
+const SUPPRESSION_MARKER: &str = "ms-lint-disable-next-block";
+
+/// Parse `<!-- ms-lint-disable-next-block rule-a, rule-b: reason -->`.
+pub(crate) fn parse_suppression_marker(line: &str) -> Option<LintSuppression> {
+    let inner = line
+        .trim()
+        .strip_prefix("<!--")?
+        .strip_suffix("-->")?
+        .trim();
+    let rest = inner.strip_prefix(SUPPRESSION_MARKER)?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let (rules, reason) = match rest.split_once(':') {
+        Some((rules, reason)) => (rules, Some(reason.trim().to_string())),
+        None => (rest, None),
+    };
+    Some(LintSuppression {
+        rules: rules
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|rule| !rule.is_empty())
+            .map(String::from)
+            .collect(),
+        reason: reason.filter(|reason| !reason.is_empty()),
+    })
+}
+
+fn suppression_marker(suppression: &LintSuppression) -> String {
+    let reason = suppression
+        .reason
+        .as_deref()
+        .map(|reason| format!(": {reason}"))
+        .unwrap_or_default();
+    format!(
+        "<!-- {SUPPRESSION_MARKER} {}{reason} -->",
+        suppression.rules.join(", ")
+    )
+}
+
 /// Parse a SKILL.md file into a `SkillSpec`.
 pub fn parse_markdown(content: &str) -> Result<SkillSpec> {
     let mut name = String::new();
     let mut description_lines = Vec::new();
     let mut sections: Vec<SkillSection> = Vec::new();
     let mut metadata = SkillMetadata::default();
+    let mut extends = None;
 
     let mut current_section: Option<SkillSection> = None;
     let mut in_description = false;
     let mut in_code_block = false;
     let mut code_lines: Vec<String> = Vec::new();
     let mut paragraph_lines: Vec<String> = Vec::new();
+    // Suppression markers waiting for the block they precede
+    let mut pending_suppressions: Vec<LintSuppression> = Vec::new();
     let mut in_frontmatter = false;
     let mut frontmatter_lines: Vec<String> = Vec::new();
     let mut lines_iter = content.lines().peekable();
@@ -57,7 +110,9 @@ pub fn parse_markdown(content: &str) -> Result<SkillSpec> {
         }
     }
 
-    let flush_paragraph = |section: &mut SkillSection, lines: &mut Vec<String>| {
+    let flush_paragraph = |section: &mut SkillSection,
+                           lines: &mut Vec<String>,
+                           suppressions: &mut Vec<LintSuppression>| {
         if lines.is_empty() {
             return;
         }
@@ -70,6 +125,7 @@ pub fn parse_markdown(content: &str) -> Result<SkillSpec> {
             id: format!("{}-block-{}", section.id, section.blocks.len() + 1),
             block_type: BlockType::Text,
             content,
+            suppressions: std::mem::take(suppressions),
         });
     };
 
@@ -82,6 +138,9 @@ pub fn parse_markdown(content: &str) -> Result<SkillSpec> {
                     Ok(meta) => metadata = meta,
                     Err(e) => eprintln!("Failed to parse frontmatter: {e}\nYAML:\n{yaml}"),
                 }
+                extends = serde_yaml::from_str::<FrontmatterSpecFields>(&yaml)
+                    .ok()
+                    .and_then(|fields| fields.extends);
                 continue;
             }
             frontmatter_lines.push(line.to_string());
@@ -96,7 +155,7 @@ pub fn parse_markdown(content: &str) -> Result<SkillSpec> {
 
         if let Some(title) = line.strip_prefix("## ") {
             if let Some(section) = current_section.as_mut() {
-                flush_paragraph(section, &mut paragraph_lines);
+                flush_paragraph(section, &mut paragraph_lines, &mut pending_suppressions);
             }
             if let Some(section) = current_section.take() {
                 sections.push(section);
@@ -131,14 +190,15 @@ pub fn parse_markdown(content: &str) -> Result<SkillSpec> {
                 let content = code_lines.join("\n");
                 code_lines.clear();
                 in_code_block = false;
-                flush_paragraph(section, &mut paragraph_lines);
+                flush_paragraph(section, &mut paragraph_lines, &mut pending_suppressions);
                 section.blocks.push(SkillBlock {
                     id: format!("{}-block-{}", section.id, section.blocks.len() + 1),
                     block_type: BlockType::Code,
                     content,
+                    suppressions: std::mem::take(&mut pending_suppressions),
                 });
             } else {
-                flush_paragraph(section, &mut paragraph_lines);
+                flush_paragraph(section, &mut paragraph_lines, &mut pending_suppressions);
                 in_code_block = true;
                 code_lines.push(line.to_string());
             }
@@ -150,15 +210,21 @@ pub fn parse_markdown(content: &str) -> Result<SkillSpec> {
             continue;
         }
 
+        if let Some(suppression) = parse_suppression_marker(line) {
+            flush_paragraph(section, &mut paragraph_lines, &mut pending_suppressions);
+            pending_suppressions.push(suppression);
+            continue;
+        }
+
         if line.trim().is_empty() {
-            flush_paragraph(section, &mut paragraph_lines);
+            flush_paragraph(section, &mut paragraph_lines, &mut pending_suppressions);
         } else {
             paragraph_lines.push(line.trim_end().to_string());
         }
     }
 
     if let Some(section) = current_section.as_mut() {
-        flush_paragraph(section, &mut paragraph_lines);
+        flush_paragraph(section, &mut paragraph_lines, &mut pending_suppressions);
     }
     if in_code_block && !code_lines.is_empty() {
         if let Some(section) = current_section.as_mut() {
@@ -166,6 +232,7 @@ pub fn parse_markdown(content: &str) -> Result<SkillSpec> {
                 id: format!("{}-block-{}", section.id, section.blocks.len() + 1),
                 block_type: BlockType::Code,
                 content: code_lines.join("\n"),
+                suppressions: std::mem::take(&mut pending_suppressions),
             });
         }
     }
@@ -202,8 +269,8 @@ pub fn parse_markdown(content: &str) -> Result<SkillSpec> {
         format_version: SkillSpec::FORMAT_VERSION.to_string(),
         metadata,
         sections,
-        // Inheritance fields - not parsed from markdown (use YAML frontmatter)
-        extends: None,
+        // Inheritance: only `extends` is carried in the frontmatter
+        extends,
         replace_rules: false,
         replace_examples: false,
         replace_pitfalls: false,
@@ -222,6 +289,9 @@ pub fn compile_markdown(spec: &SkillSpec) -> String {
     if let Ok(yaml) = serde_yaml::to_string(&spec.metadata) {
         output.push_str("---\n");
         output.push_str(yaml.trim());
+        if let Some(parent) = &spec.extends {
+            output.push_str(&format!("\nextends: {parent}"));
+        }
         output.push_str("\n---\n\n");
     }
 
@@ -235,6 +305,10 @@ pub fn compile_markdown(spec: &SkillSpec) -> String {
     for section in &spec.sections {
         output.push_str(&format!("## {}\n\n", section.title));
         for block in &section.blocks {
+            for suppression in &block.suppressions {
+                output.push_str(&suppression_marker(suppression));
+                output.push('\n');
+            }
             if block.block_type == BlockType::Code {
                 let content = block.content.trim_end();
                 if content.starts_with("```") {
@@ -255,7 +329,7 @@ pub fn compile_markdown(spec: &SkillSpec) -> String {
     output.trim_end().to_string() + "\n"
 }
 
-fn slugify(input: &str) -> String {
+pub(crate) fn slugify(input: &str) -> String {
     let lowered = input.trim().to_lowercase();
     let mut out = String::with_capacity(lowered.len());
     let mut last_was_dash = false;
@@ -303,6 +377,35 @@ mod tests {
 
         assert_eq!(compiled, expected);
     }
+    #[test]
+    fn suppression_markers_attach_to_next_block_and_roundtrip() {
+        let md = "# Sample\n\nDescription.\n\n## Usage\n\nFirst.\n\n<!-- ms-lint-disable-next-block no-secrets, shell-syntax: test fixture -->\n```bash\nexport TOKEN=x\n```\n\n<!-- ms-lint-disable-next-block token-budget -->\nSecond.\n";
+        let parsed = parse_markdown(md).expect("parse");
+        let blocks = &parsed.sections[0].blocks;
+        assert_eq!(blocks.len(), 3);
+        assert!(blocks[0].suppressions.is_empty());
+        assert_eq!(
+            blocks[1].suppressions[0].rules,
+            vec!["no-secrets", "shell-syntax"]
+        );
+        assert_eq!(
+            blocks[1].suppressions[0].reason.as_deref(),
+            Some("test fixture")
+        );
+        assert_eq!(blocks[2].suppressions[0].rules, vec!["token-budget"]);
+        assert_eq!(blocks[2].content, "Second.");
+
+        let reparsed = parse_markdown(&compile_markdown(&parsed)).expect("reparse");
+        assert_eq!(
+            reparsed.sections[0].blocks[1].suppressions,
+            blocks[1].suppressions
+        );
+        assert_eq!(
+            reparsed.sections[0].blocks[2].suppressions,
+            blocks[2].suppressions
+        );
+    }
+
     #[test]
     fn parse_frontmatter_tags() {
         let md = "---\nid: tagged-skill\nname: Tagged Skill\nversion: 0.1.0\ndescription: A test skill\ntags: [rust, backend]\nrequires: []\nprovides: []\nplatforms: []\n---\n\n# Tagged Skill\n\nDescription.\n";
@@ -310,4 +413,20 @@ mod tests {
         assert_eq!(parsed.metadata.name, "Tagged Skill");
         assert_eq!(parsed.metadata.tags, vec!["rust", "backend"]);
     }
+
+    #[test]
+    fn extends_roundtrips_through_frontmatter() {
+        let md = "---\nid: child\nname: Child\nextends: parent\n---\n\n# Child\n\nA child skill.\n";
+        let parsed = parse_markdown(md).expect("parse");
+        assert_eq!(parsed.extends.as_deref(), Some("parent"));
+        let compiled = compile_markdown(&parsed);
+        assert!(compiled.contains("\nextends: parent\n---"));
+        assert_eq!(
+            parse_markdown(&compiled)
+                .expect("reparse")
+                .extends
+                .as_deref(),
+            Some("parent")
+        );
+    }
 }
@@ -81,6 +81,9 @@ pub enum MsError {
     #[error("Destructive operation blocked: {0}")]
     DestructiveBlocked(String),
 
+    #[error("Blocked by policy: {0}")]
+    PolicyViolation(String),
+
     #[error("ACIP error: {0}")]
     AcipError(String),
 
@@ -145,6 +148,7 @@ impl MsError {
             Self::TwoPhaseCommitFailed { .. } => ErrorCode::TwoPhaseCommitFailed,
             Self::ApprovalRequired(_) => ErrorCode::ApprovalRequired,
             Self::DestructiveBlocked(_) => ErrorCode::DestructiveBlocked,
+            Self::PolicyViolation(_) => ErrorCode::SecurityViolation,
             Self::AcipError(_) => ErrorCode::AcipBlocked,
             Self::LockTimeout(_) => ErrorCode::LockTimeout,
             Self::LockFailed(_) => ErrorCode::LockFailed,
@@ -103,6 +103,11 @@ pub fn format_rule(content: &str) -> String {
 static CODE_FENCE_REGEX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"```(\w*)\n([\s\S]*?)```").unwrap());
 
+/// Markdown fence regex: indentation, info string and code. Fences open and
+/// close at the start of a line.
+static MARKDOWN_FENCE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^([ \t]*)```([^`\n]*)\n([\s\S]*?)^[ \t]*```").unwrap());
+
 /// A code block extracted from content.
 #[derive(Debug, Clone)]
 pub struct ExtractedCode {
@@ -110,6 +115,12 @@ pub struct ExtractedCode {
     pub code: String,
     /// Detected or specified language
     pub language: Option<String>,
+    /// Full info string after the opening fence, e.g. `rust ignore`
+    pub info: String,
+    /// Indentation of the opening fence
+    pub indent: usize,
+    /// Byte offset of the first code line in the content
+    pub offset: usize,
 }
 
 /// Extract code blocks from content.
@@ -122,11 +133,42 @@ pub fn extract_code_blocks(content: &str) -> Vec<ExtractedCode> {
         .captures_iter(content)
         .map(|cap| {
             let lang = cap.get(1).map(|m| m.as_str()).filter(|s| !s.is_empty());
-            let code = cap.get(2).map(|m| m.as_str()).unwrap_or("");
+            let code = cap.get(2);
+
+            ExtractedCode {
+                code: code.map_or("", |m| m.as_str()).trim_end().to_string(),
+                language: lang.map(String::from),
+                info: lang.unwrap_or_default().to_string(),
+                indent: 0,
+                offset: code.map_or(content.len(), |m| m.start()),
+            }
+        })
+        .collect()
+}
+
+/// Extract Markdown fenced code blocks from content.
+///
+/// Unlike [`extract_code_blocks`], fences must start a line (optionally
+/// indented) and the full info string is kept, so the result maps back to
+/// source positions.
+#[must_use]
+pub fn extract_fenced_blocks(content: &str) -> Vec<ExtractedCode> {
+    MARKDOWN_FENCE_REGEX
+        .captures_iter(content)
+        .map(|cap| {
+            let info = cap.get(2).map_or("", |m| m.as_str()).trim();
+            let lang = info
+                .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .next()
+                .filter(|s| !s.is_empty());
+            let code = cap.get(3);
 
             ExtractedCode {
-                code: code.trim_end().to_string(),
+                code: code.map_or("", |m| m.as_str()).trim_end().to_string(),
                 language: lang.map(String::from),
+                info: info.to_string(),
+                indent: cap.get(1).map_or(0, |m| m.len()),
+                offset: code.map_or(content.len(), |m| m.start()),
             }
         })
         .collect()
@@ -627,6 +669,34 @@ mod tests {
         assert_eq!(blocks[1].code, "print('hi')");
     }
 
+    #[test]
+    fn test_extract_fenced_blocks_info_and_position() {
+        let content = "Intro\n  ```rust ignore\n  fn broken(\n  ```\n";
+        let blocks = extract_fenced_blocks(content);
+
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].language, Some("rust".to_string()));
+        assert_eq!(blocks[0].info, "rust ignore");
+        assert_eq!(blocks[0].indent, 2);
+        assert_eq!(&content[blocks[0].offset..], "  fn broken(\n  ```\n");
+    }
+
+    #[test]
+    fn test_extract_code_blocks_matches_mid_line_fences() {
+        // The import path also accepts fences that do not start a line.
+        let content = "Run ```bash\nls -la``` then\n    ```\n    indented\n    ```";
+        let blocks = extract_code_blocks(content);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].code, "ls -la");
+        assert_eq!(blocks[1].code, "    indented");
+        assert_eq!(extract_description(content), "Run  then");
+
+        let fenced = extract_fenced_blocks(content);
+        assert_eq!(fenced.len(), 1);
+        assert_eq!(fenced[0].code, "    indented");
+        assert_eq!(fenced[0].indent, 4);
+    }
+
     #[test]
     fn test_extract_code_blocks_no_language() {
         let content = "```\nsome code\n```";
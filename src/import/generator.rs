@@ -261,6 +261,7 @@ impl SkillGenerator {
                         id: format!("rule-{}", rule_counter),
                         block_type: BlockType::Rule,
                         content: formatted,
+                        suppressions: Vec::new(),
                     });
                     stats.rules_count += 1;
                 }
@@ -272,6 +273,7 @@ impl SkillGenerator {
                         id: format!("example-{}", example_counter),
                         block_type: BlockType::Code,
                         content: formatted,
+                        suppressions: Vec::new(),
                     });
                     stats.examples_count += 1;
                 }
@@ -283,6 +285,7 @@ impl SkillGenerator {
                         id: format!("pitfall-{}", pitfall_counter),
                         block_type: BlockType::Pitfall,
                         content: formatted,
+                        suppressions: Vec::new(),
                     });
                     stats.pitfalls_count += 1;
                 }
@@ -295,6 +298,7 @@ impl SkillGenerator {
                             id: format!("checklist-{}", checklist_counter),
                             block_type: BlockType::Checklist,
                             content: item.text,
+                            suppressions: Vec::new(),
                         });
                         stats.checklist_count += 1;
                     }
@@ -306,6 +310,7 @@ impl SkillGenerator {
                         id: format!("context-{}", context_counter),
                         block_type: BlockType::Text,
                         content: block.content.trim().to_string(),
+                        suppressions: Vec::new(),
                     });
                     stats.context_count += 1;
                 }
@@ -323,6 +328,7 @@ impl SkillGenerator {
                                 id: format!("context-{}", context_counter),
                                 block_type: BlockType::Text,
                                 content: block.content.trim().to_string(),
+                                suppressions: Vec::new(),
                             });
                             stats.context_count += 1;
                         }
@@ -11,6 +11,10 @@
 //! 2. **Block Classifiers** - Classify each block by type
 //! 3. **Skill Generator** - Transform classified blocks into SkillSpec
 //!
+//! **Rule Sources** recognize other agents' rule files (Cursor `.mdc`,
+//! `CLAUDE.md`, Copilot and Windsurf instructions, skill folders), discover
+//! them across a repository, and split multi-topic files into linked skills.
+//!
 //! # Example
 //!
 //! ```ignore
@@ -32,9 +36,11 @@ mod classifiers;
 pub mod formatting;
 mod generator;
 mod parser;
+mod sources;
 mod types;
 
 pub use classifiers::*;
 pub use generator::*;
 pub use parser::*;
+pub use sources::*;
 pub use types::*;
@@ -0,0 +1,743 @@
+//! Agent rule sources and repository-wide discovery.
+//!
+//! Recognizes the rule files other agents use and maps their metadata onto
+//! skills:
+//! - Cursor rules (`.cursor/rules/*.mdc`, `.cursorrules`): `globs` become
+//!   `ContextTags::file_patterns`
+//! - `CLAUDE.md` and `AGENTS.md` hierarchies: nested files are scoped to
+//!   their directory and extend the nearest ancestor
+//! - Copilot instructions (`.github/copilot-instructions.md`,
+//!   `.github/instructions/*.instructions.md` with `applyTo`)
+//! - Windsurf rules (`.windsurfrules`, `.windsurf/rules/*.md`)
+//! - Skill folders (`SKILL.md` with `scripts/`, `references/`, `tests/`),
+//!   mapped into `SkillAssets`
+//!
+//! Files that cover several topics are split into one skill per topic with
+//! the block classifiers, each extending a base skill built from the rest.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use super::formatting::slugify;
+use super::generator::{GeneratedSkill, ImportHints, SkillGenerator};
+use super::parser::ContentParser;
+use super::types::ContentBlockType;
+use crate::core::skill::{SkillAssets, SkillSpec};
+use crate::error::{MsError, Result};
+
+/// Directories never searched during discovery.
+const SKIP_DIRS: &[&str] = &[
+    ".git",
+    ".hg",
+    ".svn",
+    ".ms",
+    "node_modules",
+    "target",
+    "vendor",
+    "dist",
+    "build",
+    ".venv",
+    "__pycache__",
+];
+
+/// Classified rule/example/pitfall/checklist blocks a section needs to
+/// become its own skill.
+const MIN_TOPIC_BLOCKS: usize = 3;
+
+/// Kind of agent rule source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceKind {
+    Markdown,
+    AgentsMd,
+    CursorRule,
+    ClaudeMd,
+    CopilotInstructions,
+    WindsurfRules,
+    SkillFolder,
+}
+
+impl SourceKind {
+    /// Detect the source kind from a file path; `None` for files that are
+    /// not agent rules.
+    #[must_use]
+    pub fn detect(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?;
+        let parent = path
+            .parent()
+            .map(|p| p.to_string_lossy().replace('\\', "/"))
+            .unwrap_or_default();
+        let kind = match name {
+            "CLAUDE.md" => Self::ClaudeMd,
+            "AGENTS.md" => Self::AgentsMd,
+            "SKILL.md" => Self::SkillFolder,
+            ".cursorrules" => Self::CursorRule,
+            ".windsurfrules" => Self::WindsurfRules,
+            "copilot-instructions.md" => Self::CopilotInstructions,
+            _ if name.ends_with(".mdc") => Self::CursorRule,
+            _ if name.ends_with(".instructions.md") => Self::CopilotInstructions,
+            _ if name.ends_with(".md") && parent.ends_with(".windsurf/rules") => {
+                Self::WindsurfRules
+            }
+            _ => return None,
+        };
+        Some(kind)
+    }
+
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Markdown => "markdown",
+            Self::AgentsMd => "agents_md",
+            Self::CursorRule => "cursor_rule",
+            Self::ClaudeMd => "claude_md",
+            Self::CopilotInstructions => "copilot_instructions",
+            Self::WindsurfRules => "windsurf_rules",
+            Self::SkillFolder => "skill_folder",
+        }
+    }
+
+    /// Tag added to skills imported from this kind of source.
+    #[must_use]
+    pub const fn tag(&self) -> Option<&'static str> {
+        match self {
+            Self::Markdown | Self::SkillFolder => None,
+            Self::AgentsMd => Some("agents-md"),
+            Self::CursorRule => Some("cursor"),
+            Self::ClaudeMd => Some("claude-md"),
+            Self::CopilotInstructions => Some("copilot"),
+            Self::WindsurfRules => Some("windsurf"),
+        }
+    }
+
+    /// Nested files of this kind refine the ones in parent directories.
+    const fn is_hierarchical(self) -> bool {
+        matches!(self, Self::ClaudeMd | Self::AgentsMd)
+    }
+}
+
+/// One agent rule file, with its metadata mapped to skill fields.
+#[derive(Debug, Clone)]
+pub struct RuleSource {
+    pub kind: SourceKind,
+    /// The rule file (`SKILL.md` for skill folders)
+    pub path: PathBuf,
+    /// Content without frontmatter
+    pub body: String,
+    pub id: String,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub file_patterns: Vec<String>,
+    pub assets: SkillAssets,
+    /// Directory asset paths are relative to
+    pub asset_root: Option<PathBuf>,
+    /// Skill this source extends (set for nested hierarchy files)
+    pub extends: Option<String>,
+}
+
+impl RuleSource {
+    /// Load a rule file. `root` is the repository root used to scope
+    /// hierarchical files; pass the file's directory for standalone imports.
+    pub fn load(path: &Path, kind: SourceKind, root: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| MsError::Config(format!("Failed to read {}: {e}", path.display())))?;
+        let (frontmatter, body) = split_frontmatter(&content);
+        let stem = path
+            .file_name()
+            .and_then(|s| s.to_str())
+            .unwrap_or("imported")
+            .trim_start_matches('.')
+            .trim_end_matches(".instructions.md")
+            .trim_end_matches(".mdc")
+            .trim_end_matches(".md")
+            .to_string();
+        let rel_dir = path
+            .parent()
+            .and_then(|dir| dir.strip_prefix(root).ok())
+            .map(|dir| dir.to_string_lossy().replace('\\', "/"))
+            .unwrap_or_default();
+        // `.claude/CLAUDE.md` applies to the whole repository.
+        let scope_dir = if rel_dir == ".claude" {
+            String::new()
+        } else {
+            rel_dir
+        };
+
+        let mut source = Self {
+            kind,
+            path: path.to_path_buf(),
+            body: body.to_string(),
+            id: slugify(&stem),
+            name: first_value(&frontmatter, "name"),
+            description: first_value(&frontmatter, "description"),
+            file_patterns: Vec::new(),
+            assets: SkillAssets::default(),
+            asset_root: None,
+            extends: None,
+        };
+
+        match kind {
+            SourceKind::CursorRule => {
+                source.file_patterns = list_value(&frontmatter, "globs");
+                if stem == "cursorrules" {
+                    source.id = "cursor-rules".to_string();
+                }
+            }
+            SourceKind::CopilotInstructions => {
+                source.file_patterns = list_value(&frontmatter, "applyTo")
+                    .into_iter()
+                    .filter(|glob| glob != "**" && glob != "**/*")
+                    .collect();
+            }
+            SourceKind::WindsurfRules => {
+                source.file_patterns = list_value(&frontmatter, "globs");
+                if stem == "windsurfrules" {
+                    source.id = "windsurf-rules".to_string();
+                }
+            }
+            SourceKind::ClaudeMd | SourceKind::AgentsMd => {
+                let base = if kind == SourceKind::ClaudeMd {
+                    "claude-md"
+                } else {
+                    "agents-md"
+                };
+                if scope_dir.is_empty() {
+                    source.id = base.to_string();
+                } else {
+                    source.id = format!("{base}-{}", slugify(&scope_dir));
+                    source.file_patterns = vec![format!("{scope_dir}/**")];
+                }
+            }
+            SourceKind::SkillFolder => {
+                let dir = path.parent().unwrap_or(root);
+                let dir_name = dir
+                    .file_name()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("skill")
+                    .to_string();
+                source.id = slugify(source.name.as_deref().unwrap_or(&dir_name));
+                source.assets = SkillAssets::collect(dir)?;
+                source.asset_root = Some(dir.to_path_buf());
+            }
+            SourceKind::Markdown => {}
+        }
+        if source.id.is_empty() {
+            source.id = "imported-skill".to_string();
+        }
+        Ok(source)
+    }
+}
+
+/// Find every agent rule source under `root`, with unique IDs and
+/// hierarchy links resolved.
+pub fn discover_sources(root: &Path) -> Result<Vec<RuleSource>> {
+    let mut sources = Vec::new();
+    let walker = walkdir::WalkDir::new(root)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() == 0
+                || !entry.file_type().is_dir()
+                || !SKIP_DIRS.contains(&entry.file_name().to_string_lossy().as_ref())
+        });
+    for entry in walker {
+        let entry = entry
+            .map_err(|e| MsError::Config(format!("Failed to walk {}: {e}", root.display())))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let Some(kind) = SourceKind::detect(entry.path()) else {
+            continue;
+        };
+        // `.mdc` files only count inside `.cursor/rules`.
+        if kind == SourceKind::CursorRule
+            && entry.file_name() != ".cursorrules"
+            && !entry
+                .path()
+                .to_string_lossy()
+                .replace('\\', "/")
+                .contains(".cursor/rules/")
+        {
+            continue;
+        }
+        sources.push(RuleSource::load(entry.path(), kind, root)?);
+    }
+
+    let mut seen = HashSet::new();
+    for source in &mut sources {
+        let base = source.id.clone();
+        let mut n = 2;
+        while !seen.insert(source.id.clone()) {
+            source.id = format!("{base}-{n}");
+            n += 1;
+        }
+    }
+    link_hierarchies(&mut sources);
+    Ok(sources)
+}
+
+/// Point each nested `CLAUDE.md`/`AGENTS.md` at the nearest ancestor of the
+/// same kind.
+fn link_hierarchies(sources: &mut [RuleSource]) {
+    let scopes: HashMap<(SourceKind, PathBuf), String> = sources
+        .iter()
+        .filter(|source| source.kind.is_hierarchical())
+        .map(|source| ((source.kind, scope_of(source)), source.id.clone()))
+        .collect();
+    for source in sources.iter_mut() {
+        if !source.kind.is_hierarchical() {
+            continue;
+        }
+        let scope = scope_of(source);
+        source.extends = scope
+            .ancestors()
+            .skip(1)
+            .find_map(|dir| scopes.get(&(source.kind, dir.to_path_buf())).cloned());
+    }
+}
+
+fn scope_of(source: &RuleSource) -> PathBuf {
+    let dir = source.path.parent().unwrap_or(Path::new(""));
+    if dir.file_name().is_some_and(|name| name == ".claude") {
+        dir.parent().unwrap_or(dir).to_path_buf()
+    } else {
+        dir.to_path_buf()
+    }
+}
+
+/// A slice of a rule file that becomes one skill.
+#[derive(Debug, Clone)]
+pub struct Topic {
+    pub title: Option<String>,
+    pub body: String,
+}
+
+/// Split a document into a base (preamble and minor sections) followed by
+/// one topic per heading section that the classifiers find substantial.
+/// Returns a single base topic when fewer than two sections qualify.
+#[must_use]
+pub fn split_topics(body: &str, parser: &ContentParser) -> Vec<Topic> {
+    let whole = || {
+        vec![Topic {
+            title: None,
+            body: body.to_string(),
+        }]
+    };
+    let level = if heading_lines(body, "# ").count() > 1 {
+        "# "
+    } else {
+        "## "
+    };
+    let mut sections: Vec<(Option<String>, String)> = vec![(None, String::new())];
+    let mut in_fence = false;
+    for line in body.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+        if !in_fence {
+            if let Some(title) = line.strip_prefix(level) {
+                sections.push((Some(title.trim().to_string()), String::new()));
+            }
+        }
+        let current = &mut sections.last_mut().expect("at least one section").1;
+        current.push_str(line);
+        current.push('\n');
+    }
+
+    let is_topic = |text: &str| {
+        parser
+            .parse(text)
+            .iter()
+            .filter(|block| {
+                matches!(
+                    block.block_type,
+                    ContentBlockType::Rule
+                        | ContentBlockType::Pitfall
+                        | ContentBlockType::Example
+                        | ContentBlockType::Checklist
+                )
+            })
+            .count()
+            >= MIN_TOPIC_BLOCKS
+    };
+    let qualifying: Vec<bool> = sections
+        .iter()
+        .map(|(title, text)| title.is_some() && is_topic(text))
+        .collect();
+    if qualifying.iter().filter(|q| **q).count() < 2 {
+        return whole();
+    }
+
+    let mut base = String::new();
+    let mut topics = Vec::new();
+    for ((title, text), topic) in sections.into_iter().zip(qualifying) {
+        if topic {
+            topics.push(Topic { title, body: text });
+        } else {
+            base.push_str(&text);
+        }
+    }
+    let mut out = vec![Topic {
+        title: None,
+        body: base,
+    }];
+    out.extend(topics);
+    out
+}
+
+fn heading_lines<'a>(body: &'a str, prefix: &'a str) -> impl Iterator<Item = &'a str> {
+    let mut in_fence = false;
+    body.lines().filter(move |line| {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+        !in_fence && line.starts_with(prefix)
+    })
+}
+
+/// A skill generated from (part of) a rule source.
+#[derive(Debug, Clone)]
+pub struct ImportedSkill {
+    pub source: PathBuf,
+    pub kind: SourceKind,
+    pub generated: GeneratedSkill,
+    pub assets: SkillAssets,
+    pub asset_root: Option<PathBuf>,
+}
+
+/// Generate skills from a source, splitting multi-topic files when `split`
+/// is set. Topic skills are named `<source-id>-<topic>` and extend the base.
+#[must_use]
+pub fn import_source(
+    source: &RuleSource,
+    generator: &SkillGenerator,
+    hints: &ImportHints,
+    split: bool,
+) -> Vec<ImportedSkill> {
+    let parser = ContentParser::new();
+    let topics = if split {
+        split_topics(&source.body, &parser)
+    } else {
+        vec![Topic {
+            title: None,
+            body: source.body.clone(),
+        }]
+    };
+    let (base, rest) = topics.split_first().expect("split_topics is never empty");
+    let has_base = rest.is_empty() || !parser.parse(&base.body).is_empty();
+
+    let mut skills = Vec::new();
+    let mut assets = Some(source.assets.clone());
+    let mut generate = |body: &str, id: String, name: Option<String>, extends: Option<String>| {
+        let topic_hints = ImportHints {
+            suggested_id: Some(id),
+            suggested_name: name,
+            source_filename: hints.source_filename.clone(),
+            domain: hints.domain.clone(),
+            tags: hints.tags.clone(),
+        };
+        let mut generated = generator.generate(parser.parse(body), &topic_hints);
+        apply_source_metadata(source, &mut generated.skill);
+        generated.skill.extends = extends;
+        skills.push(ImportedSkill {
+            source: source.path.clone(),
+            kind: source.kind,
+            generated,
+            assets: assets.take().unwrap_or_default(),
+            asset_root: source.asset_root.clone(),
+        });
+    };
+
+    if has_base {
+        generate(
+            &base.body,
+            source.id.clone(),
+            source.name.clone().or_else(|| hints.suggested_name.clone()),
+            source.extends.clone(),
+        );
+    }
+    let topic_parent = if has_base {
+        Some(source.id.clone())
+    } else {
+        source.extends.clone()
+    };
+    for topic in rest {
+        let title = topic.title.clone().unwrap_or_default();
+        generate(
+            &topic.body,
+            format!("{}-{}", source.id, slugify(&title)),
+            Some(title),
+            topic_parent.clone(),
+        );
+    }
+    skills
+}
+
+/// Copy source metadata (description, file patterns, kind tag) onto a
+/// generated skill.
+pub fn apply_source_metadata(source: &RuleSource, skill: &mut SkillSpec) {
+    let metadata = &mut skill.metadata;
+    if let Some(description) = &source.description {
+        metadata.description.clone_from(description);
+    }
+    for pattern in &source.file_patterns {
+        if !metadata.context.file_patterns.contains(pattern) {
+            metadata.context.file_patterns.push(pattern.clone());
+        }
+    }
+    if let Some(tag) = source.kind.tag() {
+        if !metadata.tags.iter().any(|t| t == tag) {
+            metadata.tags.push(tag.to_string());
+        }
+    }
+    metadata.tags.sort();
+}
+
+/// Split `---` frontmatter into loosely parsed keys and the body. Values
+/// are kept as strings because rule files often hold invalid YAML such as
+/// `globs: *.ts`.
+fn split_frontmatter(content: &str) -> (BTreeMap<String, Vec<String>>, &str) {
+    let mut map = BTreeMap::new();
+    let Some(rest) = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))
+    else {
+        return (map, content);
+    };
+    let Some(end) = rest.find("\n---") else {
+        return (map, content);
+    };
+    let after = &rest[end + 4..];
+    let body = after
+        .find('\n')
+        .map_or("", |nl| &after[nl + 1..])
+        .trim_start_matches(['\r', '\n']);
+
+    let mut current: Option<String> = None;
+    for line in rest[..end].lines() {
+        let trimmed = line.trim();
+        if let Some(item) = trimmed.strip_prefix("- ") {
+            if let Some(key) = &current {
+                map.entry(key.clone())
+                    .or_insert_with(Vec::new)
+                    .push(unquote(item));
+            }
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        if line.starts_with(char::is_whitespace) {
+            continue;
+        }
+        let key = key.trim().to_string();
+        let value = value.trim();
+        let values = map.entry(key.clone()).or_insert_with(Vec::new);
+        if let Some(list) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+            values.extend(list.split(',').map(unquote).filter(|v| !v.is_empty()));
+        } else if !value.is_empty() {
+            values.push(unquote(value));
+        }
+        current = Some(key);
+    }
+    (map, body)
+}
+
+fn unquote(value: &str) -> String {
+    value
+        .trim()
+        .trim_matches(|c| c == '"' || c == '\'')
+        .trim()
+        .to_string()
+}
+
+fn first_value(map: &BTreeMap<String, Vec<String>>, key: &str) -> Option<String> {
+    map.get(key)
+        .and_then(|values| values.first())
+        .filter(|value| !value.is_empty())
+        .cloned()
+}
+
+/// Values of a list key, also splitting comma-separated scalars.
+fn list_value(map: &BTreeMap<String, Vec<String>>, key: &str) -> Vec<String> {
+    map.get(key)
+        .into_iter()
+        .flatten()
+        .flat_map(|value| value.split(','))
+        .map(unquote)
+        .filter(|value| !value.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn write(root: &Path, rel: &str, content: &str) {
+        let path = root.join(rel);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn detects_source_kinds() {
+        let kind = |p: &str| SourceKind::detect(Path::new(p));
+        assert_eq!(
+            kind(".cursor/rules/react.mdc"),
+            Some(SourceKind::CursorRule)
+        );
+        assert_eq!(kind("svc/CLAUDE.md"), Some(SourceKind::ClaudeMd));
+        assert_eq!(
+            kind(".github/copilot-instructions.md"),
+            Some(SourceKind::CopilotInstructions)
+        );
+        assert_eq!(
+            kind(".github/instructions/ts.instructions.md"),
+            Some(SourceKind::CopilotInstructions)
+        );
+        assert_eq!(kind(".windsurfrules"), Some(SourceKind::WindsurfRules));
+        assert_eq!(
+            kind(".windsurf/rules/style.md"),
+            Some(SourceKind::WindsurfRules)
+        );
+        assert_eq!(kind("skills/pdf/SKILL.md"), Some(SourceKind::SkillFolder));
+        assert_eq!(kind("README.md"), None);
+    }
+
+    #[test]
+    fn cursor_frontmatter_maps_globs() {
+        let dir = tempdir().unwrap();
+        write(
+            dir.path(),
+            ".cursor/rules/react-hooks.mdc",
+            "---\ndescription: React hook rules\nglobs: *.tsx, src/**/*.ts\nalwaysApply: false\n---\n\nAlways call hooks at the top level.\n",
+        );
+        let path = dir.path().join(".cursor/rules/react-hooks.mdc");
+        let source = RuleSource::load(&path, SourceKind::CursorRule, dir.path()).unwrap();
+        assert_eq!(source.id, "react-hooks");
+        assert_eq!(source.description.as_deref(), Some("React hook rules"));
+        assert_eq!(source.file_patterns, vec!["*.tsx", "src/**/*.ts"]);
+        assert!(source.body.starts_with("Always call hooks"));
+
+        let (map, _) = split_frontmatter("---\napplyTo:\n  - \"**/*.py\"\n---\nbody");
+        assert_eq!(list_value(&map, "applyTo"), vec!["**/*.py"]);
+    }
+
+    #[test]
+    fn discovers_repository_sources_and_hierarchy() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        write(root, "CLAUDE.md", "# Project\n\nUse cargo.\n");
+        write(
+            root,
+            "services/api/CLAUDE.md",
+            "# API\n\nAlways validate input.\n",
+        );
+        write(
+            root,
+            ".github/copilot-instructions.md",
+            "Prefer small functions.\n",
+        );
+        write(root, ".windsurfrules", "Never commit secrets.\n");
+        write(root, "docs/notes.mdc", "not a cursor rule location\n");
+        write(root, "node_modules/pkg/CLAUDE.md", "ignored\n");
+        write(
+            root,
+            ".claude/skills/pdf/SKILL.md",
+            "---\nname: pdf-tools\ndescription: Work with PDFs\n---\n\n# PDF\n",
+        );
+        write(root, ".claude/skills/pdf/scripts/extract.py", "print()\n");
+        write(root, ".claude/skills/pdf/references/forms.md", "# Forms\n");
+
+        let sources = discover_sources(root).unwrap();
+        let ids: Vec<&str> = sources.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "pdf-tools",
+                "copilot-instructions",
+                "windsurf-rules",
+                "claude-md",
+                "claude-md-services-api"
+            ]
+        );
+
+        let api = sources
+            .iter()
+            .find(|s| s.id == "claude-md-services-api")
+            .unwrap();
+        assert_eq!(api.extends.as_deref(), Some("claude-md"));
+        assert_eq!(api.file_patterns, vec!["services/api/**"]);
+
+        let pdf = &sources[0];
+        assert_eq!(pdf.assets.scripts.len(), 1);
+        assert_eq!(pdf.assets.scripts[0].language, "python");
+        assert_eq!(
+            pdf.assets.scripts[0].path,
+            PathBuf::from("scripts/extract.py")
+        );
+        assert_eq!(pdf.assets.references[0].file_type, "md");
+    }
+
+    #[test]
+    fn splits_multi_topic_files_into_linked_skills() {
+        let body = "# Team rules\n\nGeneral guidance for the repository.\n\n\
+## Testing\n\nAlways run tests before pushing.\n\nNever skip flaky tests silently.\n\n\
+- [ ] Add a regression test\n- [ ] Run the full suite\n\n\
+Always keep tests deterministic.\n\n\
+## Security\n\nNever log secrets.\n\nAlways validate user input.\n\n\
+Avoid shelling out with untrusted arguments.\n\nMust rotate credentials quarterly.\n\n\
+## History\n\nThis repo was started in 2019.\n";
+        let source = RuleSource {
+            kind: SourceKind::ClaudeMd,
+            path: PathBuf::from("CLAUDE.md"),
+            body: body.to_string(),
+            id: "claude-md".to_string(),
+            name: None,
+            description: None,
+            file_patterns: vec![],
+            assets: SkillAssets::default(),
+            asset_root: None,
+            extends: None,
+        };
+        let skills = import_source(
+            &source,
+            &SkillGenerator::new(),
+            &ImportHints::default(),
+            true,
+        );
+        let ids: Vec<&str> = skills
+            .iter()
+            .map(|s| s.generated.skill.metadata.id.as_str())
+            .collect();
+        assert_eq!(
+            ids,
+            vec!["claude-md", "claude-md-testing", "claude-md-security"]
+        );
+        assert_eq!(skills[0].generated.skill.extends, None);
+        assert_eq!(
+            skills[1].generated.skill.extends.as_deref(),
+            Some("claude-md")
+        );
+        assert!(skills[1].generated.stats.rules_count > 0);
+        assert!(
+            skills[0]
+                .generated
+                .skill
+                .metadata
+                .tags
+                .contains(&"claude-md".to_string())
+        );
+
+        let single = import_source(
+            &source,
+            &SkillGenerator::new(),
+            &ImportHints::default(),
+            false,
+        );
+        assert_eq!(single.len(), 1);
+    }
+}
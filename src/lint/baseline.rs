@@ -0,0 +1,240 @@
+//! Lint baselines.
+//!
+//! A baseline records the diagnostics a project already has so that CI can
+//! fail only on new ones. Entries are fingerprinted by skill, rule and
+//! message (not by line), so unrelated edits that shift a finding around do
+//! not resurface it. Each fingerprint carries a count: adding a second copy
+//! of an already-baselined finding still reports the extra one.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::diagnostic::Diagnostic;
+use crate::error::{MsError, Result};
+
+/// Current baseline file format version.
+pub const BASELINE_VERSION: u32 = 1;
+
+/// Default baseline location inside the ms root.
+#[must_use]
+pub fn default_baseline_path(ms_root: &Path) -> PathBuf {
+    ms_root.join("lint-baseline.json")
+}
+
+/// A set of accepted diagnostics.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintBaseline {
+    /// Format version
+    pub version: u32,
+    /// Accepted findings, sorted by skill then rule
+    #[serde(default)]
+    pub entries: Vec<BaselineEntry>,
+}
+
+/// One accepted finding.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    /// Skill the finding belongs to
+    pub skill: String,
+    /// Rule that produced it
+    pub rule_id: String,
+    /// Diagnostic message
+    pub message: String,
+    /// Hash of skill, rule and message
+    pub fingerprint: String,
+    /// How many identical findings are accepted
+    #[serde(default = "default_count")]
+    pub count: usize,
+}
+
+const fn default_count() -> usize {
+    1
+}
+
+/// Fingerprint a diagnostic for `skill`.
+#[must_use]
+pub fn fingerprint(skill: &str, diagnostic: &Diagnostic) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(skill.as_bytes());
+    hasher.update([0]);
+    hasher.update(diagnostic.rule_id.as_bytes());
+    hasher.update([0]);
+    hasher.update(diagnostic.message.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+impl Default for LintBaseline {
+    fn default() -> Self {
+        Self {
+            version: BASELINE_VERSION,
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl LintBaseline {
+    /// Build a baseline from `(skill, diagnostics)` pairs.
+    pub fn from_diagnostics<'a>(
+        results: impl IntoIterator<Item = (&'a str, &'a [Diagnostic])>,
+    ) -> Self {
+        let mut grouped: BTreeMap<(String, String, String), BaselineEntry> = BTreeMap::new();
+        for (skill, diagnostics) in results {
+            for diagnostic in diagnostics {
+                let key = (
+                    skill.to_string(),
+                    diagnostic.rule_id.clone(),
+                    diagnostic.message.clone(),
+                );
+                grouped
+                    .entry(key)
+                    .and_modify(|entry| entry.count += 1)
+                    .or_insert_with(|| BaselineEntry {
+                        skill: skill.to_string(),
+                        rule_id: diagnostic.rule_id.clone(),
+                        message: diagnostic.message.clone(),
+                        fingerprint: fingerprint(skill, diagnostic),
+                        count: 1,
+                    });
+            }
+        }
+        Self {
+            version: BASELINE_VERSION,
+            entries: grouped.into_values().collect(),
+        }
+    }
+
+    /// Load a baseline file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw = std::fs::read_to_string(path)
+            .map_err(|e| MsError::Config(format!("read lint baseline {}: {e}", path.display())))?;
+        let baseline: Self = serde_json::from_str(&raw)
+            .map_err(|e| MsError::Config(format!("parse lint baseline {}: {e}", path.display())))?;
+        if baseline.version > BASELINE_VERSION {
+            return Err(MsError::Config(format!(
+                "lint baseline {} has unsupported version {}",
+                path.display(),
+                baseline.version
+            )));
+        }
+        Ok(baseline)
+    }
+
+    /// Write the baseline as pretty-printed JSON.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, format!("{json}\n"))
+            .map_err(|e| MsError::Config(format!("write lint baseline {}: {e}", path.display())))
+    }
+
+    /// Total number of accepted findings.
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.entries.iter().map(|entry| entry.count).sum()
+    }
+
+    /// Start matching diagnostics against this baseline.
+    #[must_use]
+    pub fn filter(&self) -> BaselineFilter {
+        let mut remaining = HashMap::new();
+        for entry in &self.entries {
+            *remaining.entry(entry.fingerprint.clone()).or_insert(0) += entry.count;
+        }
+        BaselineFilter {
+            remaining,
+            suppressed: 0,
+        }
+    }
+}
+
+/// Consumes baseline counts as diagnostics are matched.
+#[derive(Debug, Clone)]
+pub struct BaselineFilter {
+    remaining: HashMap<String, usize>,
+    suppressed: usize,
+}
+
+impl BaselineFilter {
+    /// Drop diagnostics already accepted by the baseline, keeping new ones.
+    pub fn retain_new(&mut self, skill: &str, diagnostics: &mut Vec<Diagnostic>) {
+        diagnostics.retain(|diagnostic| {
+            let Some(left) = self.remaining.get_mut(&fingerprint(skill, diagnostic)) else {
+                return true;
+            };
+            if *left == 0 {
+                return true;
+            }
+            *left -= 1;
+            self.suppressed += 1;
+            false
+        });
+    }
+
+    /// Number of diagnostics hidden so far.
+    #[must_use]
+    pub const fn suppressed(&self) -> usize {
+        self.suppressed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diags() -> Vec<Diagnostic> {
+        vec![
+            Diagnostic::warning("no-secrets", "Possible API key"),
+            Diagnostic::warning("no-secrets", "Possible API key"),
+            Diagnostic::error("required-metadata", "Missing description"),
+        ]
+    }
+
+    #[test]
+    fn baseline_groups_identical_findings() {
+        let diagnostics = diags();
+        let baseline = LintBaseline::from_diagnostics([("demo", diagnostics.as_slice())]);
+        assert_eq!(baseline.entries.len(), 2);
+        assert_eq!(baseline.total(), 3);
+        let secrets = baseline
+            .entries
+            .iter()
+            .find(|entry| entry.rule_id == "no-secrets")
+            .unwrap();
+        assert_eq!(secrets.count, 2);
+        assert_eq!(secrets.fingerprint, fingerprint("demo", &diagnostics[0]));
+    }
+
+    #[test]
+    fn filter_reports_only_new_findings() {
+        let diagnostics = diags();
+        let baseline = LintBaseline::from_diagnostics([("demo", &diagnostics[..2])]);
+
+        let mut current = diags();
+        current.push(Diagnostic::warning("no-secrets", "Possible API key"));
+        let mut filter = baseline.filter();
+        filter.retain_new("demo", &mut current);
+        let ids: Vec<&str> = current.iter().map(|d| d.rule_id.as_str()).collect();
+        assert_eq!(ids, vec!["required-metadata", "no-secrets"]);
+        assert_eq!(filter.suppressed(), 2);
+
+        // The same finding in another skill is new.
+        let mut other = diags();
+        baseline.filter().retain_new("other", &mut other);
+        assert_eq!(other.len(), 3);
+    }
+
+    #[test]
+    fn baseline_roundtrips_through_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = default_baseline_path(dir.path());
+        let diagnostics = diags();
+        let baseline = LintBaseline::from_diagnostics([("demo", diagnostics.as_slice())]);
+        baseline.save(&path).unwrap();
+        assert_eq!(LintBaseline::load(&path).unwrap(), baseline);
+    }
+}
@@ -1,13 +1,16 @@
 //! Validation configuration.
 
 use std::collections::{HashMap, HashSet};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 
 use super::diagnostic::Severity;
 use crate::core::resolution::SkillRepository;
 use crate::core::skill::SkillSpec;
+use crate::core::spec_lens::parse_markdown;
+use crate::error::Result;
+use crate::storage::{Database, GitArchive};
 
 /// Configuration for validation
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -141,6 +144,135 @@ impl<'a> ValidationContext<'a> {
     }
 }
 
+/// One indexed skill as seen by corpus rules
+#[derive(Debug, Clone)]
+pub struct CorpusSkill {
+    /// The skill as written (inheritance and includes unresolved)
+    pub spec: SkillSpec,
+    /// Layer the skill was indexed from (e.g. "org", "project")
+    pub layer: Option<String>,
+    /// Path to the skill file
+    pub path: Option<PathBuf>,
+    /// Original source text for span calculation
+    pub source: Option<String>,
+    /// Deprecation reason, if the skill is deprecated
+    pub deprecated: Option<String>,
+}
+
+impl CorpusSkill {
+    /// Create a corpus entry for a spec
+    #[must_use]
+    pub const fn new(spec: SkillSpec) -> Self {
+        Self {
+            spec,
+            layer: None,
+            path: None,
+            source: None,
+            deprecated: None,
+        }
+    }
+
+    /// Set the layer
+    #[must_use]
+    pub fn with_layer(mut self, layer: impl Into<String>) -> Self {
+        self.layer = Some(layer.into());
+        self
+    }
+
+    /// Set the file path
+    #[must_use]
+    pub fn with_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Set the source text
+    #[must_use]
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Mark the skill deprecated
+    #[must_use]
+    pub fn with_deprecated(mut self, reason: impl Into<String>) -> Self {
+        self.deprecated = Some(reason.into());
+        self
+    }
+
+    /// Skill ID
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.spec.metadata.id
+    }
+}
+
+/// Every indexed skill, for corpus rules. The archived spec is preferred
+/// (it keeps `includes`); the source file supplies spans.
+pub fn load_corpus(db: &Database, git: &GitArchive) -> Result<Vec<CorpusSkill>> {
+    let mut corpus = Vec::new();
+    for record in db.list_skills(10_000, 0)? {
+        let mut path = PathBuf::from(&record.source_path);
+        if path.is_dir() {
+            path.push("SKILL.md");
+        }
+        let source = if path.is_file() {
+            std::fs::read_to_string(&path).ok()
+        } else {
+            None
+        };
+        let archived = if git.skill_exists(&record.id) {
+            git.read_skill(&record.id).ok()
+        } else {
+            None
+        };
+        let spec = match (archived, source.as_deref().map(parse_markdown)) {
+            (Some(spec), _) | (None, Some(Ok(spec))) => spec,
+            _ => continue,
+        };
+        let mut skill = CorpusSkill::new(spec)
+            .with_layer(record.source_layer.clone())
+            .with_path(path);
+        if let Some(source) = source {
+            skill = skill.with_source(source);
+        }
+        if record.is_deprecated {
+            skill = skill.with_deprecated(record.deprecation_reason.unwrap_or_default());
+        }
+        corpus.push(skill);
+    }
+    Ok(corpus)
+}
+
+/// Context provided to corpus rules: every indexed skill at once
+pub struct CorpusContext<'a> {
+    /// All skills in the corpus
+    pub skills: &'a [CorpusSkill],
+
+    /// Configuration for validation
+    pub config: &'a ValidationConfig,
+}
+
+impl<'a> CorpusContext<'a> {
+    /// Create a corpus context
+    #[must_use]
+    pub const fn new(skills: &'a [CorpusSkill], config: &'a ValidationConfig) -> Self {
+        Self { skills, config }
+    }
+
+    /// Look up a skill by ID
+    #[must_use]
+    pub fn get(&self, skill_id: &str) -> Option<&'a CorpusSkill> {
+        self.skills.iter().find(|skill| skill.id() == skill_id)
+    }
+}
+
+impl SkillRepository for CorpusContext<'_> {
+    fn get(&self, skill_id: &str) -> Result<Option<SkillSpec>> {
+        Ok(CorpusContext::get(self, skill_id).map(|skill| skill.spec.clone()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,1023 @@
+//! Declarative lint rules loaded from the project (`.ms/lint/*.toml`).
+//!
+//! Each file holds one or more `[[rule]]` tables. A rule selects part of the
+//! `SkillSpec` (a metadata field, sections, or blocks, optionally limited to
+//! skills with certain tags), asserts something about it, and may carry a
+//! templated fix:
+//!
+//! ```toml
+//! [[rule]]
+//! id = "house-examples"
+//! message = "Rust skills need an Examples section with code"
+//! severity = "error"
+//!
+//! [rule.select]
+//! tags = ["rust"]
+//! section = "Examples"
+//! block_type = "code"
+//!
+//! [rule.assert]
+//! required_sections = ["Examples"]
+//! min_count = 1
+//!
+//! [rule.fix]
+//! content = "TODO: add an example for {name}"
+//! ```
+//!
+//! Templates may use `{id}`, `{name}` and `{section}`.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use regex::Regex;
+use serde::Deserialize;
+
+use super::config::ValidationContext;
+use super::diagnostic::{Diagnostic, RuleCategory, Severity, SourceSpan};
+use super::engine::ValidationEngine;
+use super::rule::{BoxedRule, ValidationRule};
+use super::rules::{all_rules, corpus_rules, staleness_rules};
+use crate::core::skill::{BlockType, SkillBlock, SkillSection, SkillSpec};
+use crate::core::spec_lens::slugify;
+use crate::error::{MsError, Result};
+use crate::quality::StalenessVerifier;
+use crate::search::HashEmbedder;
+
+/// Directory holding project lint rules.
+#[must_use]
+pub fn custom_rules_dir(ms_root: &Path) -> PathBuf {
+    ms_root.join("lint")
+}
+
+/// Load and compile every rule in `<ms_root>/lint/*.toml`, sorted by file
+/// name. Rules may not reuse an id from another rule or from `reserved`
+/// (the built-in rule ids).
+pub fn load_custom_rules(ms_root: &Path, reserved: &[&str]) -> Result<Vec<CustomRule>> {
+    let dir = custom_rules_dir(ms_root);
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut files: Vec<PathBuf> = std::fs::read_dir(&dir)
+        .map_err(|err| MsError::Config(format!("read {}: {err}", dir.display())))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    files.sort();
+
+    let mut rules: Vec<CustomRule> = Vec::new();
+    for path in files {
+        let raw = std::fs::read_to_string(&path)
+            .map_err(|err| MsError::Config(format!("read {}: {err}", path.display())))?;
+        let file: CustomRuleFile = toml::from_str(&raw)
+            .map_err(|err| MsError::Config(format!("parse {}: {err}", path.display())))?;
+        for spec in file.rules {
+            let rule = CustomRule::compile(spec, &path)?;
+            if reserved.contains(&rule.id()) || rules.iter().any(|r| r.id() == rule.id()) {
+                return Err(MsError::Config(format!(
+                    "{}: duplicate lint rule id '{}'",
+                    path.display(),
+                    rule.id()
+                )));
+            }
+            rules.push(rule);
+        }
+    }
+    Ok(rules)
+}
+
+/// Project rules boxed for `ValidationEngine::register`.
+pub fn custom_rules(ms_root: &Path, reserved: &[&str]) -> Result<Vec<BoxedRule>> {
+    Ok(load_custom_rules(ms_root, reserved)?
+        .into_iter()
+        .map(|rule| Box::new(rule) as BoxedRule)
+        .collect())
+}
+
+/// Append the project rules to `rules`, rejecting ids already in use.
+pub fn extend_with_custom_rules(rules: &mut Vec<BoxedRule>, ms_root: &Path) -> Result<()> {
+    let reserved: Vec<&str> = rules.iter().map(|rule| rule.id()).collect();
+    let custom = custom_rules(ms_root, &reserved)?;
+    rules.extend(custom);
+    Ok(())
+}
+
+/// Engine holding every rule `ms lint` can run, including opt-in staleness
+/// and corpus rules and the project's `.ms/lint/*.toml` rules.
+pub fn known_rules(ms_root: &Path) -> Result<ValidationEngine> {
+    let mut rules = all_rules();
+    rules.extend(staleness_rules(Arc::new(StalenessVerifier::new())));
+    extend_with_custom_rules(&mut rules, ms_root)?;
+    let mut engine = ValidationEngine::with_defaults();
+    for rule in rules {
+        engine.register(rule);
+    }
+    for rule in corpus_rules(Arc::new(HashEmbedder::default())) {
+        engine.register_corpus(rule);
+    }
+    Ok(engine)
+}
+
+/// A `.ms/lint/*.toml` file.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomRuleFile {
+    #[serde(default, rename = "rule")]
+    pub rules: Vec<CustomRuleSpec>,
+}
+
+/// One declarative rule as written in TOML.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomRuleSpec {
+    pub id: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default = "default_severity")]
+    pub severity: Severity,
+    #[serde(default = "default_category")]
+    pub category: RuleCategory,
+    pub message: String,
+    #[serde(default)]
+    pub suggestion: Option<String>,
+    #[serde(default)]
+    pub select: Selector,
+    #[serde(rename = "assert")]
+    pub assertion: Assertion,
+    #[serde(default)]
+    pub fix: Option<FixTemplate>,
+}
+
+/// What part of the skill a rule looks at.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Selector {
+    /// Section title or id (case-insensitive)
+    #[serde(default)]
+    pub section: Option<String>,
+    /// Only blocks of this type
+    #[serde(default)]
+    pub block_type: Option<BlockType>,
+    /// A metadata field instead of the body
+    #[serde(default)]
+    pub field: Option<MetadataField>,
+    /// Only skills carrying at least one of these tags
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetadataField {
+    Id,
+    Name,
+    Version,
+    Description,
+    Author,
+    License,
+    Tags,
+    Requires,
+    Provides,
+    Platforms,
+}
+
+impl MetadataField {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Id => "id",
+            Self::Name => "name",
+            Self::Version => "version",
+            Self::Description => "description",
+            Self::Author => "author",
+            Self::License => "license",
+            Self::Tags => "tags",
+            Self::Requires => "requires",
+            Self::Provides => "provides",
+            Self::Platforms => "platforms",
+        }
+    }
+
+    /// Values of the field: one per list entry, none for an unset option.
+    fn values(self, skill: &SkillSpec) -> Vec<String> {
+        let meta = &skill.metadata;
+        match self {
+            Self::Id => vec![meta.id.clone()],
+            Self::Name => vec![meta.name.clone()],
+            Self::Version => vec![meta.version.clone()],
+            Self::Description => vec![meta.description.clone()],
+            Self::Author => meta.author.iter().cloned().collect(),
+            Self::License => meta.license.iter().cloned().collect(),
+            Self::Tags => meta.tags.clone(),
+            Self::Requires => meta.requires.clone(),
+            Self::Provides => meta.provides.clone(),
+            Self::Platforms => meta.platforms.clone(),
+        }
+    }
+
+    /// Mutable access to a list field.
+    fn list_mut(self, skill: &mut SkillSpec) -> Option<&mut Vec<String>> {
+        let meta = &mut skill.metadata;
+        match self {
+            Self::Tags => Some(&mut meta.tags),
+            Self::Requires => Some(&mut meta.requires),
+            Self::Provides => Some(&mut meta.provides),
+            Self::Platforms => Some(&mut meta.platforms),
+            _ => None,
+        }
+    }
+
+    fn set(self, skill: &mut SkillSpec, value: String) {
+        let meta = &mut skill.metadata;
+        match self {
+            Self::Id => meta.id = value,
+            Self::Name => meta.name = value,
+            Self::Version => meta.version = value,
+            Self::Description => meta.description = value,
+            Self::Author => meta.author = Some(value),
+            Self::License => meta.license = Some(value),
+            Self::Tags | Self::Requires | Self::Provides | Self::Platforms => {
+                if let Some(list) = self.list_mut(skill) {
+                    list.push(value);
+                }
+            }
+        }
+    }
+}
+
+/// What must hold for the selection.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Assertion {
+    /// Every selected item must match
+    #[serde(default)]
+    pub must_match: Option<String>,
+    /// No selected item may match
+    #[serde(default)]
+    pub must_not_match: Option<String>,
+    /// Minimum number of selected items
+    #[serde(default)]
+    pub min_count: Option<usize>,
+    /// Maximum number of selected items
+    #[serde(default)]
+    pub max_count: Option<usize>,
+    /// Token budget per selected item
+    #[serde(default)]
+    pub max_tokens: Option<usize>,
+    /// Sections (title or id) that must exist
+    #[serde(default)]
+    pub required_sections: Vec<String>,
+}
+
+impl Assertion {
+    fn is_empty(&self) -> bool {
+        self.must_match.is_none()
+            && self.must_not_match.is_none()
+            && self.min_count.is_none()
+            && self.max_count.is_none()
+            && self.max_tokens.is_none()
+            && self.required_sections.is_empty()
+    }
+}
+
+/// Templated fix.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FixTemplate {
+    /// Replacement for `must_not_match` matches (`$1` refers to captures)
+    #[serde(default)]
+    pub replace: Option<String>,
+    /// Text to add: the body of a missing section, a block appended to reach
+    /// `min_count`, or the value of an empty field
+    #[serde(default)]
+    pub content: Option<String>,
+}
+
+fn default_severity() -> Severity {
+    Severity::Warning
+}
+
+fn default_category() -> RuleCategory {
+    RuleCategory::Quality
+}
+
+/// A compiled declarative rule.
+#[derive(Debug, Clone)]
+pub struct CustomRule {
+    spec: CustomRuleSpec,
+    name: String,
+    must_match: Option<Regex>,
+    must_not_match: Option<Regex>,
+}
+
+/// A selected piece of the skill.
+struct Item {
+    label: String,
+    text: String,
+    /// Span of the item's first line in the text the rules scan (see
+    /// `extract_all_content`)
+    span: Option<SourceSpan>,
+    /// Scanned line where `text` starts
+    text_line: Option<usize>,
+    /// Block the item is, when blocks are selected
+    block_id: Option<String>,
+}
+
+impl Item {
+    fn new(label: String, text: String, line: Option<usize>) -> Self {
+        let width = text.lines().next().unwrap_or_default().chars().count();
+        Self {
+            label,
+            span: line.map(|line| SourceSpan::new(line, 1, line, width + 1)),
+            text_line: line,
+            text,
+            block_id: None,
+        }
+    }
+}
+
+impl CustomRule {
+    /// Validate and compile a rule read from `source`.
+    pub fn compile(spec: CustomRuleSpec, source: &Path) -> Result<Self> {
+        let invalid = |detail: String| {
+            MsError::Config(format!(
+                "{}: rule '{}': {detail}",
+                source.display(),
+                spec.id
+            ))
+        };
+        if spec.id.trim().is_empty() {
+            return Err(MsError::Config(format!(
+                "{}: lint rule without an id",
+                source.display()
+            )));
+        }
+        if spec.assertion.is_empty() {
+            return Err(invalid("[rule.assert] has no assertions".to_string()));
+        }
+        if spec.select.field.is_some()
+            && (spec.select.section.is_some() || spec.select.block_type.is_some())
+        {
+            return Err(invalid(
+                "select.field cannot be combined with section or block_type".to_string(),
+            ));
+        }
+        let compile = |pattern: &Option<String>, key: &str| {
+            pattern
+                .as_deref()
+                .map(Regex::new)
+                .transpose()
+                .map_err(|err| invalid(format!("invalid {key}: {err}")))
+        };
+        let must_match = compile(&spec.assertion.must_match, "must_match")?;
+        let must_not_match = compile(&spec.assertion.must_not_match, "must_not_match")?;
+
+        Ok(Self {
+            name: spec.name.clone().unwrap_or_else(|| spec.id.clone()),
+            spec,
+            must_match,
+            must_not_match,
+        })
+    }
+
+    fn applies_to(&self, skill: &SkillSpec) -> bool {
+        let wanted = &self.spec.select.tags;
+        wanted.is_empty()
+            || skill
+                .metadata
+                .tags
+                .iter()
+                .any(|tag| wanted.iter().any(|w| w.eq_ignore_ascii_case(tag)))
+    }
+
+    fn section_selected(&self, section: &SkillSection) -> bool {
+        self.spec
+            .select
+            .section
+            .as_deref()
+            .is_none_or(|wanted| section_matches(section, wanted))
+    }
+
+    fn block_selected(&self, block: &SkillBlock) -> bool {
+        self.spec
+            .select
+            .block_type
+            .as_ref()
+            .is_none_or(|wanted| &block.block_type == wanted)
+    }
+
+    /// Sections are the items when a section is selected without a block
+    /// type; otherwise blocks are.
+    fn selects_sections(&self) -> bool {
+        self.spec.select.section.is_some() && self.spec.select.block_type.is_none()
+    }
+
+    fn items(&self, skill: &SkillSpec) -> Vec<Item> {
+        if let Some(field) = self.spec.select.field {
+            let line = match field {
+                MetadataField::Name => Some(1),
+                MetadataField::Description => Some(2),
+                _ => None,
+            };
+            return field
+                .values(skill)
+                .into_iter()
+                .map(|text| Item::new(field.as_str().to_string(), text, line))
+                .collect();
+        }
+
+        let mut items = Vec::new();
+        let mut line =
+            1 + line_count(&skill.metadata.name) + line_count(&skill.metadata.description);
+        for section in &skill.sections {
+            let title_line = line;
+            line += line_count(&section.title);
+            let selected = self.section_selected(section);
+            if selected && self.selects_sections() {
+                let width = section.title.chars().count();
+                items.push(Item {
+                    label: format!("section '{}'", section.title),
+                    text: section_text(section),
+                    span: Some(SourceSpan::new(title_line, 1, title_line, width + 1)),
+                    text_line: Some(line),
+                    block_id: None,
+                });
+            }
+            for block in &section.blocks {
+                if selected && !self.selects_sections() && self.block_selected(block) {
+                    let label = format!(
+                        "{} block in '{}'",
+                        block_type_name(&block.block_type),
+                        section.title
+                    );
+                    items.push(Item {
+                        block_id: Some(block.id.clone()),
+                        ..Item::new(label, block.content.clone(), Some(line))
+                    });
+                }
+                line += line_count(&block.content);
+            }
+        }
+        items
+    }
+
+    fn diagnostic(&self, skill: &SkillSpec, section: Option<&str>, detail: &str) -> Diagnostic {
+        let message = render(&self.spec.message, skill, section);
+        let mut diag = Diagnostic::new(
+            &self.spec.id,
+            self.spec.severity,
+            format!("{message} ({detail})"),
+        )
+        .with_category(self.spec.category);
+        if let Some(suggestion) = &self.spec.suggestion {
+            diag = diag.with_suggestion(render(suggestion, skill, section));
+        }
+        if self.can_fix() {
+            diag = diag.with_fix();
+        }
+        diag
+    }
+
+    fn missing_sections<'a>(&'a self, skill: &SkillSpec) -> Vec<&'a str> {
+        self.spec
+            .assertion
+            .required_sections
+            .iter()
+            .map(String::as_str)
+            .filter(|wanted| !skill.sections.iter().any(|s| section_matches(s, wanted)))
+            .collect()
+    }
+}
+
+impl ValidationRule for CustomRule {
+    fn id(&self) -> &str {
+        &self.spec.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        if self.spec.description.is_empty() {
+            &self.spec.message
+        } else {
+            &self.spec.description
+        }
+    }
+
+    fn category(&self) -> RuleCategory {
+        self.spec.category
+    }
+
+    fn default_severity(&self) -> Severity {
+        self.spec.severity
+    }
+
+    fn validate(&self, ctx: &ValidationContext<'_>) -> Vec<Diagnostic> {
+        let skill = ctx.skill;
+        if !self.applies_to(skill) {
+            return Vec::new();
+        }
+        let assertion = &self.spec.assertion;
+        let mut diagnostics = Vec::new();
+
+        for missing in self.missing_sections(skill) {
+            diagnostics.push(self.diagnostic(
+                skill,
+                Some(missing),
+                &format!("missing section '{missing}'"),
+            ));
+        }
+
+        let items = self.items(skill);
+        let section = self.spec.select.section.as_deref();
+        let what = self
+            .spec
+            .select
+            .field
+            .map_or("items", MetadataField::as_str);
+        if let Some(min) = assertion.min_count {
+            if items.len() < min {
+                diagnostics.push(self.diagnostic(
+                    skill,
+                    section,
+                    &format!("found {} {what}, expected at least {min}", items.len()),
+                ));
+            }
+        }
+        if let Some(max) = assertion.max_count {
+            if items.len() > max {
+                diagnostics.push(self.diagnostic(
+                    skill,
+                    section,
+                    &format!("found {} {what}, expected at most {max}", items.len()),
+                ));
+            }
+        }
+
+        for item in &items {
+            let span = item.span.clone();
+            let mut push = |mut diag: Diagnostic, span: Option<SourceSpan>| {
+                diag.span = span;
+                diag.block_id.clone_from(&item.block_id);
+                diagnostics.push(diag);
+            };
+
+            if let Some(re) = &self.must_match {
+                if !re.is_match(&item.text) {
+                    push(
+                        self.diagnostic(
+                            skill,
+                            section,
+                            &format!("{} does not match /{re}/", item.label),
+                        ),
+                        span.clone(),
+                    );
+                }
+            }
+            if let Some(re) = &self.must_not_match {
+                for found in re.find_iter(&item.text) {
+                    let span = item
+                        .text_line
+                        .map(|line| offset_span(&item.text, line, found.start(), found.end()));
+                    push(
+                        self.diagnostic(
+                            skill,
+                            section,
+                            &format!("'{}' in {}", found.as_str(), item.label),
+                        ),
+                        span,
+                    );
+                }
+            }
+            if let Some(max) = assertion.max_tokens {
+                let tokens = estimate_tokens(&item.text);
+                if tokens > max {
+                    push(
+                        self.diagnostic(
+                            skill,
+                            section,
+                            &format!("{} is ~{tokens} tokens, max {max}", item.label),
+                        ),
+                        span,
+                    );
+                }
+            }
+        }
+
+        diagnostics
+    }
+
+    fn can_fix(&self) -> bool {
+        self.spec.fix.as_ref().is_some_and(|fix| {
+            (fix.replace.is_some() && self.must_not_match.is_some())
+                || (fix.content.is_some()
+                    && (!self.spec.assertion.required_sections.is_empty()
+                        || self.spec.assertion.min_count.is_some()
+                        || self.spec.select.field.is_some()))
+        })
+    }
+
+    /// Fixes every violation of the rule at once, so repeated calls for the
+    /// remaining diagnostics are no-ops.
+    fn fix(&self, skill: &mut SkillSpec, _diagnostic: &Diagnostic) -> Result<()> {
+        let Some(fix) = self.spec.fix.clone().filter(|_| self.can_fix()) else {
+            return Err(MsError::NotImplemented(format!(
+                "auto-fix not configured for rule '{}'",
+                self.spec.id
+            )));
+        };
+        if !self.applies_to(skill) {
+            return Ok(());
+        }
+
+        if let (Some(re), Some(replace)) = (&self.must_not_match, &fix.replace) {
+            self.replace_matches(skill, re, replace);
+        }
+
+        let Some(content) = &fix.content else {
+            return Ok(());
+        };
+
+        for title in self.missing_sections(skill) {
+            let body = render(content, skill, Some(title));
+            skill.sections.push(new_section(title, &body));
+        }
+
+        if let Some(field) = self.spec.select.field {
+            let values = field.values(skill);
+            let short = self
+                .spec
+                .assertion
+                .min_count
+                .is_some_and(|min| values.len() < min);
+            let empty = values.iter().all(|v| v.trim().is_empty());
+            if short || (empty && self.must_match.is_some()) {
+                let value = render(content, skill, None);
+                if field.list_mut(skill).is_none() || short {
+                    field.set(skill, value);
+                }
+            }
+            return Ok(());
+        }
+
+        if let Some(min) = self.spec.assertion.min_count {
+            let missing = min.saturating_sub(self.items(skill).len());
+            if missing > 0 {
+                self.add_items(skill, content, missing);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl CustomRule {
+    fn replace_matches(&self, skill: &mut SkillSpec, re: &Regex, template: &str) {
+        if let Some(field) = self.spec.select.field {
+            let replacement = render(template, skill, None);
+            match field.list_mut(skill) {
+                Some(list) => {
+                    for value in list.iter_mut() {
+                        *value = re.replace_all(value, replacement.as_str()).into_owned();
+                    }
+                }
+                None => {
+                    if let Some(value) = field.values(skill).into_iter().next() {
+                        let replaced = re.replace_all(&value, replacement.as_str()).into_owned();
+                        field.set(skill, replaced);
+                    }
+                }
+            }
+            return;
+        }
+
+        let rendered: Vec<String> = skill
+            .sections
+            .iter()
+            .map(|section| render(template, skill, Some(&section.title)))
+            .collect();
+        for (section, replacement) in skill.sections.iter_mut().zip(rendered) {
+            if !self.section_selected(section) {
+                continue;
+            }
+            let whole_section = self.selects_sections();
+            for block in &mut section.blocks {
+                if whole_section || self.block_selected(block) {
+                    block.content = re
+                        .replace_all(&block.content, replacement.as_str())
+                        .into_owned();
+                }
+            }
+        }
+    }
+
+    /// Append `count` sections or blocks rendered from `content`.
+    fn add_items(&self, skill: &mut SkillSpec, content: &str, count: usize) {
+        let wanted = self.spec.select.section.clone();
+        if self.selects_sections() {
+            let title = wanted.unwrap_or_default();
+            for _ in 0..count {
+                let body = render(content, skill, Some(&title));
+                skill.sections.push(new_section(&title, &body));
+            }
+            return;
+        }
+
+        let index = match &wanted {
+            Some(title) => match skill
+                .sections
+                .iter()
+                .position(|s| section_matches(s, title))
+            {
+                Some(index) => index,
+                None => {
+                    skill.sections.push(new_section(title, ""));
+                    skill.sections.len() - 1
+                }
+            },
+            None => match skill.sections.len() {
+                0 => return,
+                len => len - 1,
+            },
+        };
+        let title = skill.sections[index].title.clone();
+        let body = render(content, skill, Some(&title));
+        let block_type = self.spec.select.block_type.clone().unwrap_or_default();
+        let section = &mut skill.sections[index];
+        for _ in 0..count {
+            section.blocks.push(SkillBlock {
+                id: format!("{}-block-{}", section.id, section.blocks.len() + 1),
+                block_type: block_type.clone(),
+                content: body.clone(),
+                suppressions: Vec::new(),
+            });
+        }
+    }
+}
+
+fn section_matches(section: &SkillSection, wanted: &str) -> bool {
+    section.title.eq_ignore_ascii_case(wanted) || section.id.eq_ignore_ascii_case(wanted)
+}
+
+fn section_text(section: &SkillSection) -> String {
+    section
+        .blocks
+        .iter()
+        .map(|block| block.content.as_str())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn new_section(title: &str, body: &str) -> SkillSection {
+    let id = slugify(title);
+    let blocks = if body.trim().is_empty() {
+        Vec::new()
+    } else {
+        vec![SkillBlock {
+            id: format!("{id}-block-1"),
+            block_type: BlockType::Text,
+            content: body.to_string(),
+            suppressions: Vec::new(),
+        }]
+    };
+    SkillSection {
+        id,
+        title: title.to_string(),
+        blocks,
+    }
+}
+
+const fn block_type_name(block_type: &BlockType) -> &'static str {
+    match block_type {
+        BlockType::Text => "text",
+        BlockType::Code => "code",
+        BlockType::Rule => "rule",
+        BlockType::Pitfall => "pitfall",
+        BlockType::Command => "command",
+        BlockType::Checklist => "checklist",
+    }
+}
+
+fn render(template: &str, skill: &SkillSpec, section: Option<&str>) -> String {
+    template
+        .replace("{id}", &skill.metadata.id)
+        .replace("{name}", &skill.metadata.name)
+        .replace("{section}", section.unwrap_or_default())
+}
+
+/// Lines a value occupies in the scanned text (always at least one).
+fn line_count(text: &str) -> usize {
+    text.matches('\n').count() + 1
+}
+
+/// Span of `text[start..end]` for an item starting at scanned `line`.
+fn offset_span(text: &str, line: usize, start: usize, end: usize) -> SourceSpan {
+    let position = |offset: usize| {
+        let before = &text[..offset];
+        let line_offset = before.matches('\n').count();
+        let col = before
+            .rsplit('\n')
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .count();
+        (line + line_offset, col + 1)
+    };
+    let (start_line, start_col) = position(start);
+    let (end_line, end_col) = position(end);
+    SourceSpan::new(start_line, start_col, end_line, end_col)
+}
+
+fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::spec_lens::parse_markdown;
+    use crate::lint::config::ValidationConfig;
+    use crate::lint::engine::ValidationEngine;
+
+    const SKILL: &str = "---\nid: rusty\nname: Rusty\ndescription: Rust error handling\ntags: [rust]\n---\n\n# Rusty\n\nRust error handling\n\n## Rules\n\nUse anyhow. TODO: expand.\n\nNever unwrap.\n";
+
+    fn rule(toml: &str) -> CustomRule {
+        let file: CustomRuleFile = toml::from_str(toml).unwrap();
+        CustomRule::compile(
+            file.rules.into_iter().next().unwrap(),
+            Path::new("test.toml"),
+        )
+        .unwrap()
+    }
+
+    fn run(rule: &CustomRule, skill: &SkillSpec) -> Vec<Diagnostic> {
+        let config = ValidationConfig::new();
+        rule.validate(&ValidationContext::new(skill, &config))
+    }
+
+    #[test]
+    fn required_section_per_tag_with_fix() {
+        let rule = rule(
+            r#"
+            [[rule]]
+            id = "house-examples"
+            message = "Rust skills need examples"
+            severity = "error"
+            [rule.select]
+            tags = ["rust"]
+            [rule.assert]
+            required_sections = ["Examples"]
+            [rule.fix]
+            content = "TODO: add an example for {name} ({section})"
+            "#,
+        );
+        let mut skill = parse_markdown(SKILL).unwrap();
+        let diagnostics = run(&rule, &skill);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert!(
+            diagnostics[0]
+                .message
+                .contains("missing section 'Examples'")
+        );
+        assert!(diagnostics[0].fix_available);
+
+        rule.fix(&mut skill, &diagnostics[0]).unwrap();
+        let added = skill.sections.last().unwrap();
+        assert_eq!(added.title, "Examples");
+        assert_eq!(
+            added.blocks[0].content,
+            "TODO: add an example for Rusty (Examples)"
+        );
+        assert!(run(&rule, &skill).is_empty());
+
+        skill.metadata.tags = vec!["python".to_string()];
+        skill.sections.pop();
+        assert!(run(&rule, &skill).is_empty());
+    }
+
+    #[test]
+    fn must_not_match_reports_spans_and_replaces() {
+        let rule = rule(
+            r#"
+            [[rule]]
+            id = "no-todo"
+            message = "No TODOs in {section}"
+            [rule.select]
+            section = "rules"
+            [rule.assert]
+            must_not_match = "TODO: (\\w+)"
+            [rule.fix]
+            replace = "Note: $1"
+            "#,
+        );
+        let mut skill = parse_markdown(SKILL).unwrap();
+        let diagnostics = run(&rule, &skill);
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].message.starts_with("No TODOs in rules"));
+        let span = diagnostics[0].span.clone().unwrap();
+        let scanned = crate::lint::rules::security::extract_all_content(&skill);
+        let line = scanned.lines().nth(span.start_line - 1).unwrap();
+        assert_eq!(&line[span.start_col - 1..span.end_col - 1], "TODO: expand");
+
+        rule.fix(&mut skill, &diagnostics[0]).unwrap();
+        assert!(skill.sections[0].blocks[0].content.contains("Note: expand"));
+        assert!(run(&rule, &skill).is_empty());
+    }
+
+    #[test]
+    fn counts_tokens_and_fields() {
+        let rule_count = rule(
+            r#"
+            [[rule]]
+            id = "max-rule-blocks"
+            message = "Too many blocks"
+            [rule.select]
+            section = "Rules"
+            block_type = "text"
+            [rule.assert]
+            max_count = 1
+            max_tokens = 3
+            "#,
+        );
+        let skill = parse_markdown(SKILL).unwrap();
+        let diagnostics = run(&rule_count, &skill);
+        assert!(
+            diagnostics
+                .iter()
+                .any(|d| d.message.contains("found 2 items, expected at most 1"))
+        );
+        assert!(
+            diagnostics
+                .iter()
+                .any(|d| d.message.contains("tokens, max 3"))
+        );
+
+        let rule_tags = rule(
+            r#"
+            [[rule]]
+            id = "kebab-tags"
+            message = "Tags must be kebab-case"
+            [rule.select]
+            field = "tags"
+            [rule.assert]
+            must_match = "^[a-z0-9-]+$"
+            min_count = 2
+            [rule.fix]
+            content = "needs-triage"
+            "#,
+        );
+        let mut skill = skill;
+        skill.metadata.tags.push("Bad Tag".to_string());
+        let diagnostics = run(&rule_tags, &skill);
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].message.contains("tags does not match"));
+
+        skill.metadata.tags.pop();
+        let diagnostics = run(&rule_tags, &skill);
+        assert!(diagnostics[0].message.contains("found 1 tags"));
+        rule_tags.fix(&mut skill, &diagnostics[0]).unwrap();
+        assert_eq!(skill.metadata.tags, vec!["rust", "needs-triage"]);
+    }
+
+    #[test]
+    fn loads_rules_from_ms_root() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(custom_rules_dir(dir.path())).unwrap();
+        std::fs::write(
+            custom_rules_dir(dir.path()).join("house.toml"),
+            "[[rule]]\nid = \"house-desc\"\nmessage = \"Describe it\"\n[rule.select]\nfield = \"description\"\n[rule.assert]\nmust_match = \"\\\\S\"\n",
+        )
+        .unwrap();
+
+        let rules = custom_rules(dir.path(), &[]).unwrap();
+        assert_eq!(rules.len(), 1);
+        let mut engine = ValidationEngine::with_defaults();
+        for rule in rules {
+            engine.register(rule);
+        }
+        let result = engine.validate(&SkillSpec::new("x", "X"));
+        assert_eq!(result.warning_count(), 1);
+
+        let Err(err) = custom_rules(dir.path(), &["house-desc"]) else {
+            panic!("duplicate id accepted");
+        };
+        assert!(err.to_string().contains("duplicate lint rule id"));
+    }
+
+    #[test]
+    fn rejects_invalid_rules() {
+        let file: CustomRuleFile = toml::from_str(
+            "[[rule]]\nid = \"bad\"\nmessage = \"m\"\n[rule.assert]\nmust_match = \"(\"\n",
+        )
+        .unwrap();
+        let err = CustomRule::compile(file.rules[0].clone(), Path::new("x.toml")).unwrap_err();
+        assert!(err.to_string().contains("invalid must_match"));
+
+        let file: CustomRuleFile =
+            toml::from_str("[[rule]]\nid = \"empty\"\nmessage = \"m\"\n[rule.assert]\n").unwrap();
+        assert!(CustomRule::compile(file.rules[0].clone(), Path::new("x.toml")).is_err());
+    }
+}
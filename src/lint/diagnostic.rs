@@ -18,6 +18,10 @@ pub enum RuleCategory {
     Security,
     /// Performance hints (token budget, etc.)
     Performance,
+    /// Stale commands, flags and paths (checked against the local machine)
+    Staleness,
+    /// Consistency across all indexed skills (contradictions, gaps)
+    Corpus,
 }
 
 impl fmt::Display for RuleCategory {
@@ -28,6 +32,8 @@ impl fmt::Display for RuleCategory {
             Self::Quality => write!(f, "quality"),
             Self::Security => write!(f, "security"),
             Self::Performance => write!(f, "performance"),
+            Self::Staleness => write!(f, "staleness"),
+            Self::Corpus => write!(f, "corpus"),
         }
     }
 }
@@ -89,6 +95,43 @@ impl SourceSpan {
             end_col: 1,
         }
     }
+
+    /// Create a span from a byte range in `content` (columns count chars).
+    #[must_use]
+    pub fn from_byte_range(content: &str, start: usize, end: usize) -> Self {
+        let mut line = 1;
+        let mut col = 1;
+        let mut start_line = 1;
+        let mut start_col = 1;
+        let mut end_line = 1;
+        let mut end_col = 1;
+
+        for (i, c) in content.char_indices() {
+            if i == start {
+                start_line = line;
+                start_col = col;
+            }
+            if i == end {
+                end_line = line;
+                end_col = col;
+                break;
+            }
+            if c == '\n' {
+                line += 1;
+                col = 1;
+            } else {
+                col += 1;
+            }
+        }
+
+        // Handle case where end is at or past content length
+        if end >= content.len() {
+            end_line = line;
+            end_col = col;
+        }
+
+        Self::new(start_line, start_col, end_line, end_col)
+    }
 }
 
 impl fmt::Display for SourceSpan {
@@ -122,6 +165,9 @@ pub struct Diagnostic {
     pub fix_available: bool,
     /// Category of the rule
     pub category: RuleCategory,
+    /// Block the finding belongs to, if it is about a single block
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_id: Option<String>,
 }
 
 impl Diagnostic {
@@ -135,6 +181,7 @@ impl Diagnostic {
             suggestion: None,
             fix_available: false,
             category: RuleCategory::Structure,
+            block_id: None,
         }
     }
 
@@ -179,6 +226,12 @@ impl Diagnostic {
         self.category = category;
         self
     }
+
+    /// Attribute the finding to a block, so block suppressions can silence it
+    pub fn with_block(mut self, block_id: impl Into<String>) -> Self {
+        self.block_id = Some(block_id.into());
+        self
+    }
 }
 
 impl fmt::Display for Diagnostic {
@@ -194,6 +247,25 @@ impl fmt::Display for Diagnostic {
     }
 }
 
+/// A diagnostic from a corpus rule, attributed to one skill
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorpusDiagnostic {
+    /// Skill the finding is reported on
+    pub skill_id: String,
+    /// The diagnostic itself
+    pub diagnostic: Diagnostic,
+}
+
+impl CorpusDiagnostic {
+    /// Create a corpus diagnostic for a skill
+    pub fn new(skill_id: impl Into<String>, diagnostic: Diagnostic) -> Self {
+        Self {
+            skill_id: skill_id.into(),
+            diagnostic,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
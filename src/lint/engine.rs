@@ -1,11 +1,15 @@
 //! Validation engine for running rules against skills.
 
-use crate::core::skill::SkillSpec;
+use crate::core::skill::{SkillBlock, SkillSpec};
+use crate::core::spec_lens::parse_suppression_marker;
 use crate::error::{MsError, Result};
 
-use super::config::{ValidationConfig, ValidationContext};
-use super::diagnostic::{Diagnostic, RuleCategory, Severity};
-use super::rule::BoxedRule;
+use super::config::{CorpusContext, ValidationConfig, ValidationContext};
+use super::diagnostic::{CorpusDiagnostic, Diagnostic, RuleCategory, Severity, SourceSpan};
+use super::rule::{BoxedCorpusRule, BoxedRule};
+
+/// Rule ID reported for suppression markers that silence nothing.
+pub const UNUSED_SUPPRESSION_RULE: &str = "unused-suppression";
 
 /// Result of validation
 #[derive(Debug, Clone)]
@@ -123,6 +127,7 @@ impl Default for FixResult {
 /// The validation engine that manages and runs rules
 pub struct ValidationEngine {
     rules: Vec<BoxedRule>,
+    corpus_rules: Vec<BoxedCorpusRule>,
     config: ValidationConfig,
 }
 
@@ -132,6 +137,7 @@ impl ValidationEngine {
     pub fn new(config: ValidationConfig) -> Self {
         Self {
             rules: Vec::new(),
+            corpus_rules: Vec::new(),
             config,
         }
     }
@@ -160,6 +166,24 @@ impl ValidationEngine {
         &self.rules
     }
 
+    /// Register a corpus rule
+    pub fn register_corpus(&mut self, rule: BoxedCorpusRule) {
+        self.corpus_rules.push(rule);
+    }
+
+    /// Register a corpus rule (builder pattern)
+    #[must_use]
+    pub fn with_corpus_rule(mut self, rule: BoxedCorpusRule) -> Self {
+        self.register_corpus(rule);
+        self
+    }
+
+    /// Get registered corpus rules
+    #[must_use]
+    pub fn corpus_rules(&self) -> &[BoxedCorpusRule] {
+        &self.corpus_rules
+    }
+
     /// Get the config
     #[must_use]
     pub const fn config(&self) -> &ValidationConfig {
@@ -190,9 +214,13 @@ impl ValidationEngine {
                 continue;
             }
 
-            let rule_diagnostics = rule.validate(ctx);
+            let (rule_diagnostics, unused) = self.run_rule(rule, ctx);
+            let unused = unused
+                .into_iter()
+                .filter(|_| !self.config.is_rule_disabled(UNUSED_SUPPRESSION_RULE))
+                .map(|(nth, block)| unused_suppression(ctx, rule.id(), nth, block));
 
-            for mut diag in rule_diagnostics {
+            for mut diag in rule_diagnostics.into_iter().chain(unused) {
                 // Apply severity override and strict mode
                 diag.severity = self.config.effective_severity(&diag.rule_id, diag.severity);
 
@@ -217,6 +245,24 @@ impl ValidationEngine {
         result
     }
 
+    /// Run all enabled corpus rules over every skill in the corpus
+    #[must_use]
+    pub fn validate_corpus(&self, ctx: &CorpusContext<'_>) -> Vec<CorpusDiagnostic> {
+        let mut findings = Vec::new();
+        for rule in &self.corpus_rules {
+            if self.config.is_rule_disabled(rule.id()) {
+                continue;
+            }
+            for mut finding in rule.validate(ctx) {
+                finding.diagnostic.severity = self
+                    .config
+                    .effective_severity(&finding.diagnostic.rule_id, finding.diagnostic.severity);
+                findings.push(finding);
+            }
+        }
+        findings
+    }
+
     /// Apply auto-fixes to a skill
     pub fn auto_fix(&self, skill: &mut SkillSpec) -> Result<FixResult> {
         let mut result = FixResult::new();
@@ -228,7 +274,8 @@ impl ValidationEngine {
             .iter()
             .filter(|r| r.can_fix() && !self.config.is_rule_disabled(r.id()))
             .flat_map(|r| {
-                r.validate(&ctx)
+                self.run_rule(r, &ctx)
+                    .0
                     .into_iter()
                     .filter(|d| d.fix_available)
                     .map(|d| (r.id().to_string(), d))
@@ -252,22 +299,100 @@ impl ValidationEngine {
         Ok(result)
     }
 
+    /// Run one rule, dropping findings silenced by block suppressions.
+    ///
+    /// A finding is silenced when it is attributed to a suppressed block;
+    /// skill-level findings are never silenced by a block marker. Also
+    /// returns suppressions of this rule that silenced nothing, with their
+    /// position among the rule's markers.
+    fn run_rule<'s>(
+        &self,
+        rule: &BoxedRule,
+        ctx: &ValidationContext<'s>,
+    ) -> (Vec<Diagnostic>, Vec<(usize, &'s SkillBlock)>) {
+        let mut diagnostics = rule.validate(ctx);
+        let mut unused = Vec::new();
+
+        let suppressed = ctx
+            .skill
+            .sections
+            .iter()
+            .flat_map(|section| &section.blocks)
+            .filter(|block| {
+                block
+                    .suppressions
+                    .iter()
+                    .any(|suppression| suppression.rules.iter().any(|id| id == rule.id()))
+            });
+        for (nth, block) in suppressed.enumerate() {
+            let before = diagnostics.len();
+            diagnostics.retain(|diag| diag.block_id.as_deref() != Some(block.id.as_str()));
+            if diagnostics.len() == before {
+                unused.push((nth, block));
+            }
+        }
+
+        (diagnostics, unused)
+    }
+
     /// List all registered rules
     #[must_use]
     pub fn list_rules(&self) -> Vec<RuleInfo> {
-        self.rules
-            .iter()
-            .map(|r| RuleInfo {
-                id: r.id().to_string(),
-                name: r.name().to_string(),
-                description: r.description().to_string(),
-                category: r.category(),
-                default_severity: r.default_severity(),
-                can_fix: r.can_fix(),
-                disabled: self.config.is_rule_disabled(r.id()),
+        let rules = self.rules.iter().map(|r| RuleInfo {
+            id: r.id().to_string(),
+            name: r.name().to_string(),
+            description: r.description().to_string(),
+            category: r.category(),
+            default_severity: r.default_severity(),
+            can_fix: r.can_fix(),
+            disabled: self.config.is_rule_disabled(r.id()),
+        });
+        let corpus_rules = self.corpus_rules.iter().map(|r| RuleInfo {
+            id: r.id().to_string(),
+            name: r.name().to_string(),
+            description: r.description().to_string(),
+            category: r.category(),
+            default_severity: r.default_severity(),
+            can_fix: false,
+            disabled: self.config.is_rule_disabled(r.id()),
+        });
+        rules.chain(corpus_rules).collect()
+    }
+}
+
+/// Warning for the `nth` marker suppressing `rule_id` (on `block`) that
+/// silenced nothing, placed on the marker when the source is known.
+fn unused_suppression(
+    ctx: &ValidationContext<'_>,
+    rule_id: &str,
+    nth: usize,
+    block: &SkillBlock,
+) -> Diagnostic {
+    let mut diag = Diagnostic::warning(
+        UNUSED_SUPPRESSION_RULE,
+        format!(
+            "Suppression of '{rule_id}' on block '{}' is unused",
+            block.id
+        ),
+    )
+    .with_suggestion("Remove the rule from the ms-lint-disable-next-block marker")
+    .with_category(RuleCategory::Quality);
+
+    let marker_line = ctx.source.and_then(|source| {
+        source
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| {
+                parse_suppression_marker(line)
+                    .is_some_and(|suppression| suppression.rules.iter().any(|id| id == rule_id))
             })
-            .collect()
+            .nth(nth)
+            .map(|(index, line)| (index + 1, line.chars().count()))
+    });
+    if let Some((line, width)) = marker_line {
+        diag = diag.with_span(SourceSpan::new(line, 1, line, width + 1));
     }
+    diag
 }
 
 /// Information about a registered rule
@@ -356,6 +481,112 @@ mod tests {
         }
     }
 
+    /// Flags every block mentioning TODO.
+    struct TodoRule;
+
+    impl ValidationRule for TodoRule {
+        fn id(&self) -> &str {
+            "no-todo"
+        }
+        fn name(&self) -> &str {
+            "No TODO"
+        }
+        fn description(&self) -> &str {
+            "Blocks should not contain TODO"
+        }
+        fn category(&self) -> RuleCategory {
+            RuleCategory::Quality
+        }
+        fn default_severity(&self) -> Severity {
+            Severity::Warning
+        }
+        fn validate(&self, ctx: &ValidationContext<'_>) -> Vec<Diagnostic> {
+            ctx.skill
+                .sections
+                .iter()
+                .flat_map(|section| &section.blocks)
+                .filter(|block| block.content.contains("TODO"))
+                .map(|block| {
+                    Diagnostic::warning(self.id(), "TODO left in skill").with_block(&block.id)
+                })
+                .collect()
+        }
+    }
+
+    /// Reports a single skill-level count of TODOs.
+    struct TodoCountRule;
+
+    impl ValidationRule for TodoCountRule {
+        fn id(&self) -> &str {
+            "todo-count"
+        }
+        fn name(&self) -> &str {
+            "TODO Count"
+        }
+        fn description(&self) -> &str {
+            "Skills should not contain TODOs"
+        }
+        fn category(&self) -> RuleCategory {
+            RuleCategory::Quality
+        }
+        fn default_severity(&self) -> Severity {
+            Severity::Warning
+        }
+        fn validate(&self, ctx: &ValidationContext<'_>) -> Vec<Diagnostic> {
+            let count = ctx
+                .skill
+                .sections
+                .iter()
+                .flat_map(|section| &section.blocks)
+                .filter(|block| block.content.contains("TODO"))
+                .count();
+            (count > 0)
+                .then(|| Diagnostic::warning(self.id(), format!("{count} TODOs in skill")))
+                .into_iter()
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_engine_suppressions() {
+        let source = "# Demo\n\nDemo skill.\n\n## Notes\n\nTODO: one\n\n<!-- ms-lint-disable-next-block no-todo: tracked upstream -->\nTODO: two\n\n<!-- ms-lint-disable-next-block no-todo -->\nAll done.\n";
+        let skill = crate::core::spec_lens::parse_markdown(source).unwrap();
+        let engine = ValidationEngine::with_defaults().with_rule(Box::new(TodoRule));
+
+        let ctx = ValidationContext::new(&skill, engine.config()).with_source(source);
+        let result = engine.validate_with_context(&ctx);
+        let ids: Vec<&str> = result
+            .diagnostics
+            .iter()
+            .map(|d| d.rule_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["no-todo", UNUSED_SUPPRESSION_RULE]);
+        assert_eq!(
+            result.diagnostics[1].span,
+            Some(SourceSpan::new(12, 1, 12, 44))
+        );
+
+        let quiet =
+            ValidationEngine::new(ValidationConfig::new().disable_rule(UNUSED_SUPPRESSION_RULE))
+                .with_rule(Box::new(TodoRule));
+        assert_eq!(quiet.validate(&skill).total_count(), 1);
+    }
+
+    #[test]
+    fn test_block_suppression_keeps_skill_level_findings() {
+        let source = "# Demo\n\nDemo skill.\n\n## Notes\n\n<!-- ms-lint-disable-next-block todo-count -->\nTODO: one\n";
+        let skill = crate::core::spec_lens::parse_markdown(source).unwrap();
+        let engine = ValidationEngine::with_defaults().with_rule(Box::new(TodoCountRule));
+
+        let result = engine.validate(&skill);
+        let ids: Vec<&str> = result
+            .diagnostics
+            .iter()
+            .map(|d| d.rule_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["todo-count", UNUSED_SUPPRESSION_RULE]);
+    }
+
     #[test]
     fn test_engine_register_rules() {
         let mut engine = ValidationEngine::with_defaults();
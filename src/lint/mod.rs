@@ -3,9 +3,12 @@
 //! This module provides a flexible validation framework for skills, including:
 //!
 //! - `ValidationRule` trait for implementing custom validation rules
+//! - `CorpusRule` trait for checks across all indexed skills
 //! - `ValidationEngine` for running rules against skills
 //! - `Diagnostic` types for reporting issues with spans and suggestions
 //! - `ValidationConfig` for configuring rule behavior
+//! - `custom` for declarative project rules in `.ms/lint/*.toml`
+//! - `baseline` for accepting existing diagnostics so only new ones fail CI
 //!
 //! # Example
 //!
@@ -43,19 +46,29 @@
 //! }
 //! ```
 
+pub mod baseline;
 pub mod config;
+pub mod custom;
 pub mod diagnostic;
 pub mod engine;
 pub mod rule;
 pub mod rules;
 
 // Re-export main types for convenience
-pub use config::{ValidationConfig, ValidationContext};
-pub use diagnostic::{Diagnostic, RuleCategory, Severity, SourceSpan};
-pub use engine::{FixResult, RuleInfo, ValidationEngine, ValidationResult};
-pub use rule::{BoxedRule, ValidationRule};
+pub use baseline::{BaselineFilter, LintBaseline, default_baseline_path};
+pub use config::{CorpusContext, CorpusSkill, ValidationConfig, ValidationContext, load_corpus};
+pub use custom::{
+    CustomRule, custom_rules, custom_rules_dir, extend_with_custom_rules, known_rules,
+    load_custom_rules,
+};
+pub use diagnostic::{CorpusDiagnostic, Diagnostic, RuleCategory, Severity, SourceSpan};
+pub use engine::{
+    FixResult, RuleInfo, UNUSED_SUPPRESSION_RULE, ValidationEngine, ValidationResult,
+};
+pub use rule::{BoxedCorpusRule, BoxedRule, CorpusRule, ValidationRule};
 
 // Re-export rule collection functions
 pub use rules::{
-    all_rules, performance_rules, quality_rules, reference_rules, security_rules, structural_rules,
+    all_rules, corpus_rules, performance_rules, quality_rules, reference_rules, security_rules,
+    staleness_rules, structural_rules, syntax_rules,
 };
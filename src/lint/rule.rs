@@ -3,8 +3,8 @@
 use crate::core::skill::SkillSpec;
 use crate::error::Result;
 
-use super::config::ValidationContext;
-use super::diagnostic::{Diagnostic, RuleCategory, Severity};
+use super::config::{CorpusContext, ValidationContext};
+use super::diagnostic::{CorpusDiagnostic, Diagnostic, RuleCategory, Severity};
 
 /// A validation rule that checks skills for issues.
 ///
@@ -47,6 +47,35 @@ pub trait ValidationRule: Send + Sync {
 /// A boxed validation rule for dynamic dispatch
 pub type BoxedRule = Box<dyn ValidationRule>;
 
+/// A rule that checks all indexed skills together (e.g. contradictions
+/// between skills, requirements nobody provides).
+///
+/// Corpus rules cannot auto-fix: a finding usually spans several skills.
+pub trait CorpusRule: Send + Sync {
+    /// Unique identifier for this rule (e.g., "unprovided-requirement")
+    fn id(&self) -> &str;
+
+    /// Human-readable name
+    fn name(&self) -> &str;
+
+    /// Detailed description of what this rule checks
+    fn description(&self) -> &str;
+
+    /// Category this rule belongs to
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Corpus
+    }
+
+    /// Default severity level
+    fn default_severity(&self) -> Severity;
+
+    /// Run the check over the whole corpus
+    fn validate(&self, ctx: &CorpusContext<'_>) -> Vec<CorpusDiagnostic>;
+}
+
+/// A boxed corpus rule for dynamic dispatch
+pub type BoxedCorpusRule = Box<dyn CorpusRule>;
+
 /// Helper macro to simplify rule implementation
 #[macro_export]
 macro_rules! impl_rule {
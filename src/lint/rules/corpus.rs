@@ -0,0 +1,657 @@
+//! Corpus-level validation rules.
+//!
+//! These rules look at every indexed skill at once rather than one skill at
+//! a time: guidance that contradicts guidance in another skill, `requires`
+//! that nothing `provides`, parents that are deprecated, block ids that
+//! collide once includes are applied, and tags nobody else uses. They run
+//! under `ms lint --corpus`.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::core::resolution::resolve_full;
+use crate::core::skill::{BlockType, SkillLayer};
+use crate::dedup::cosine_similarity;
+use crate::lint::config::{CorpusContext, CorpusSkill};
+use crate::lint::diagnostic::{CorpusDiagnostic, Diagnostic, RuleCategory, Severity, SourceSpan};
+use crate::lint::rule::{BoxedCorpusRule, CorpusRule};
+use crate::search::Embedder;
+
+/// Default cosine similarity above which opposite-polarity statements are
+/// considered to talk about the same thing.
+pub const DEFAULT_CONTRADICTION_THRESHOLD: f32 = 0.85;
+
+/// Words that flip the polarity of a statement.
+const NEGATIONS: &[&str] = &[
+    "never",
+    "not",
+    "no",
+    "dont",
+    "avoid",
+    "without",
+    "cannot",
+    "disable",
+    "disallow",
+    "forbid",
+    "forbidden",
+    "prohibit",
+    "prohibited",
+];
+
+/// Directive words carrying no topic of their own; stripped before
+/// comparing statements so "Always use X" and "Never use X" match.
+const DIRECTIVES: &[&str] = &[
+    "always", "must", "should", "shall", "need", "needs", "ensure", "prefer", "use", "using",
+    "enable", "do", "does", "please", "make", "sure",
+];
+
+/// Filler words ignored when comparing statements.
+const STOPWORDS: &[&str] = &[
+    "a", "an", "the", "to", "be", "is", "are", "it", "this", "that", "and", "or", "of", "in", "on",
+    "for", "with", "when", "you", "your", "we", "our",
+];
+
+/// Guidance that contradicts guidance in another skill.
+pub struct ContradictionRule {
+    embedder: Arc<dyn Embedder>,
+    threshold: f32,
+}
+
+impl ContradictionRule {
+    #[must_use]
+    pub fn new(embedder: Arc<dyn Embedder>) -> Self {
+        Self {
+            embedder,
+            threshold: DEFAULT_CONTRADICTION_THRESHOLD,
+        }
+    }
+
+    /// Set the similarity threshold (0.0-1.0)
+    #[must_use]
+    pub const fn with_threshold(mut self, threshold: f32) -> Self {
+        self.threshold = threshold;
+        self
+    }
+}
+
+/// A directive line from a skill's guidance blocks.
+struct Statement<'a> {
+    skill: &'a CorpusSkill,
+    text: String,
+    negated: bool,
+    topic: Vec<String>,
+}
+
+impl CorpusRule for ContradictionRule {
+    fn id(&self) -> &'static str {
+        "contradictory-guidance"
+    }
+
+    fn name(&self) -> &'static str {
+        "No Contradictory Guidance"
+    }
+
+    fn description(&self) -> &'static str {
+        "Rules in different skills should not tell agents to do opposite things"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn validate(&self, ctx: &CorpusContext<'_>) -> Vec<CorpusDiagnostic> {
+        let statements: Vec<Statement<'_>> = ctx
+            .skills
+            .iter()
+            .filter(|skill| skill.deprecated.is_none())
+            .flat_map(statements)
+            .collect();
+        let embeddings: Vec<Vec<f32>> = statements
+            .iter()
+            .map(|statement| self.embedder.embed(&statement.topic.join(" ")))
+            .collect();
+
+        // Only compare statements that share at least one topic word.
+        let mut by_word: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (idx, statement) in statements.iter().enumerate() {
+            for word in &statement.topic {
+                by_word.entry(word.as_str()).or_default().push(idx);
+            }
+        }
+        let mut candidates: Vec<(usize, usize)> = by_word
+            .values()
+            .flat_map(|indices| {
+                indices.iter().enumerate().flat_map(move |(pos, &a)| {
+                    indices[pos + 1..]
+                        .iter()
+                        .map(move |&b| (a.min(b), a.max(b)))
+                })
+            })
+            .collect();
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        let mut findings = Vec::new();
+        for (a, b) in candidates {
+            let (left, right) = (&statements[a], &statements[b]);
+            if left.negated == right.negated || left.skill.id() == right.skill.id() {
+                continue;
+            }
+            if cosine_similarity(&embeddings[a], &embeddings[b]) < self.threshold {
+                continue;
+            }
+            // Report on the more specific layer: that is usually the skill
+            // deviating from shared guidance.
+            let (here, there) = if layer_rank(right.skill.layer.as_deref())
+                > layer_rank(left.skill.layer.as_deref())
+            {
+                (right, left)
+            } else {
+                (left, right)
+            };
+            let layer = there
+                .skill
+                .layer
+                .as_deref()
+                .map(|layer| format!(" ({layer})"))
+                .unwrap_or_default();
+            let mut diag = Diagnostic::warning(
+                self.id(),
+                format!(
+                    "\"{}\" contradicts \"{}\" in '{}'{layer}",
+                    here.text,
+                    there.text,
+                    there.skill.id()
+                ),
+            )
+            .with_suggestion("Reconcile the two rules, or scope one of them explicitly")
+            .with_category(RuleCategory::Corpus);
+            if let Some(span) = locate(here.skill, &here.text) {
+                diag = diag.with_span(span);
+            }
+            findings.push(CorpusDiagnostic::new(here.skill.id(), diag));
+        }
+        findings
+    }
+}
+
+/// `requires` entries that no skill id or `provides` capability satisfies.
+pub struct UnprovidedRequirementRule;
+
+impl CorpusRule for UnprovidedRequirementRule {
+    fn id(&self) -> &'static str {
+        "unprovided-requirement"
+    }
+
+    fn name(&self) -> &'static str {
+        "Requirements Are Provided"
+    }
+
+    fn description(&self) -> &'static str {
+        "Every required capability should be provided by some indexed skill"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn validate(&self, ctx: &CorpusContext<'_>) -> Vec<CorpusDiagnostic> {
+        let mut provided: HashSet<String> = HashSet::new();
+        for skill in ctx.skills {
+            provided.insert(skill.id().to_lowercase());
+            provided.extend(
+                skill
+                    .spec
+                    .metadata
+                    .provides
+                    .iter()
+                    .map(|cap| cap.to_lowercase()),
+            );
+        }
+
+        let mut findings = Vec::new();
+        for skill in ctx.skills {
+            for requirement in &skill.spec.metadata.requires {
+                if provided.contains(&requirement.to_lowercase()) {
+                    continue;
+                }
+                let mut diag = Diagnostic::warning(
+                    self.id(),
+                    format!("Requires '{requirement}', which no indexed skill provides"),
+                )
+                .with_suggestion(format!(
+                    "Index a skill that lists '{requirement}' under provides, or drop the requirement"
+                ))
+                .with_category(RuleCategory::Corpus);
+                if let Some(span) = locate(skill, requirement) {
+                    diag = diag.with_span(span);
+                }
+                findings.push(CorpusDiagnostic::new(skill.id(), diag));
+            }
+        }
+        findings
+    }
+}
+
+/// Skills extending a deprecated parent.
+pub struct DeprecatedParentRule;
+
+impl CorpusRule for DeprecatedParentRule {
+    fn id(&self) -> &'static str {
+        "deprecated-parent"
+    }
+
+    fn name(&self) -> &'static str {
+        "Parent Not Deprecated"
+    }
+
+    fn description(&self) -> &'static str {
+        "Skills should not extend a deprecated skill"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn validate(&self, ctx: &CorpusContext<'_>) -> Vec<CorpusDiagnostic> {
+        let mut findings = Vec::new();
+        for skill in ctx.skills {
+            let Some(parent_id) = &skill.spec.extends else {
+                continue;
+            };
+            let Some(reason) = ctx
+                .get(parent_id)
+                .and_then(|parent| parent.deprecated.as_deref())
+            else {
+                continue;
+            };
+            let mut message = format!("Extends deprecated skill '{parent_id}'");
+            if !reason.is_empty() {
+                message.push_str(&format!(": {reason}"));
+            }
+            let mut diag = Diagnostic::warning(self.id(), message)
+                .with_suggestion("Extend the skill that replaced it")
+                .with_category(RuleCategory::Corpus);
+            if let Some(span) = locate(skill, parent_id) {
+                diag = diag.with_span(span);
+            }
+            findings.push(CorpusDiagnostic::new(skill.id(), diag));
+        }
+        findings
+    }
+}
+
+/// Block ids that collide once a skill's includes are applied.
+pub struct DuplicateIncludedBlockRule;
+
+impl CorpusRule for DuplicateIncludedBlockRule {
+    fn id(&self) -> &'static str {
+        "duplicate-included-block"
+    }
+
+    fn name(&self) -> &'static str {
+        "Unique Block IDs Across Includes"
+    }
+
+    fn description(&self) -> &'static str {
+        "Block IDs should stay unique after includes are merged in"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn validate(&self, ctx: &CorpusContext<'_>) -> Vec<CorpusDiagnostic> {
+        let mut findings = Vec::new();
+        for skill in ctx.skills {
+            if skill.spec.includes.is_empty() {
+                continue;
+            }
+            // Resolution errors (cycles, missing parents) are reported by
+            // the per-skill reference rules.
+            let Ok(resolved) = resolve_full(&skill.spec, ctx) else {
+                continue;
+            };
+            let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+            for block in resolved.spec.sections.iter().flat_map(|s| &s.blocks) {
+                *counts.entry(block.id.as_str()).or_default() += 1;
+            }
+            for (block_id, count) in counts {
+                if count < 2 {
+                    continue;
+                }
+                let sources: Vec<&str> = std::iter::once(skill.id())
+                    .chain(skill.spec.includes.iter().map(|inc| inc.skill.as_str()))
+                    .filter(|id| {
+                        ctx.get(id).is_some_and(|source| {
+                            source
+                                .spec
+                                .sections
+                                .iter()
+                                .flat_map(|s| &s.blocks)
+                                .any(|block| block.id == block_id)
+                        })
+                    })
+                    .collect();
+                let diag = Diagnostic::warning(
+                    self.id(),
+                    format!(
+                        "Block id '{block_id}' appears {count} times after includes (from {})",
+                        sources.join(", ")
+                    ),
+                )
+                .with_suggestion("Rename the block so overlays can target it unambiguously")
+                .with_category(RuleCategory::Corpus);
+                findings.push(CorpusDiagnostic::new(skill.id(), diag));
+            }
+        }
+        findings
+    }
+}
+
+/// Tags that only one skill uses.
+pub struct SingletonTagRule;
+
+impl CorpusRule for SingletonTagRule {
+    fn id(&self) -> &'static str {
+        "singleton-tag"
+    }
+
+    fn name(&self) -> &'static str {
+        "Shared Tags"
+    }
+
+    fn description(&self) -> &'static str {
+        "Tags used by only one skill rarely help search or filtering"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+
+    fn validate(&self, ctx: &CorpusContext<'_>) -> Vec<CorpusDiagnostic> {
+        if ctx.skills.len() < 2 {
+            return Vec::new();
+        }
+        let mut users: HashMap<String, HashSet<&str>> = HashMap::new();
+        for skill in ctx.skills {
+            for tag in &skill.spec.metadata.tags {
+                users
+                    .entry(tag.to_lowercase())
+                    .or_default()
+                    .insert(skill.id());
+            }
+        }
+
+        let mut findings = Vec::new();
+        for skill in ctx.skills {
+            for tag in &skill.spec.metadata.tags {
+                if users.get(&tag.to_lowercase()).map_or(0, HashSet::len) > 1 {
+                    continue;
+                }
+                let mut diag = Diagnostic::new(
+                    self.id(),
+                    Severity::Info,
+                    format!("Tag '{tag}' is not used by any other skill"),
+                )
+                .with_suggestion("Reuse an existing tag, or drop it")
+                .with_category(RuleCategory::Corpus);
+                if let Some(span) = locate(skill, tag) {
+                    diag = diag.with_span(span);
+                }
+                findings.push(CorpusDiagnostic::new(skill.id(), diag));
+            }
+        }
+        findings
+    }
+}
+
+/// Directive statements in a skill's guidance blocks.
+fn statements(skill: &CorpusSkill) -> Vec<Statement<'_>> {
+    let mut out = Vec::new();
+    for block in skill.spec.sections.iter().flat_map(|s| &s.blocks) {
+        let implicit = match block.block_type {
+            BlockType::Rule | BlockType::Checklist => true,
+            BlockType::Text | BlockType::Pitfall => false,
+            BlockType::Code | BlockType::Command => continue,
+        };
+        for line in block.content.lines() {
+            let text = strip_list_marker(line);
+            let words = words(text);
+            let negations = words.iter().filter(|word| is_negation(word)).count();
+            let directive = words.iter().any(|word| DIRECTIVES.contains(&word.as_str()));
+            if negations == 0 && !directive && !implicit {
+                continue;
+            }
+            let topic: Vec<String> = words
+                .into_iter()
+                .filter(|word| {
+                    !is_negation(word)
+                        && !DIRECTIVES.contains(&word.as_str())
+                        && !STOPWORDS.contains(&word.as_str())
+                })
+                .collect();
+            if topic.len() < 2 {
+                continue;
+            }
+            out.push(Statement {
+                skill,
+                text: text.to_string(),
+                negated: negations % 2 == 1,
+                topic,
+            });
+        }
+    }
+    out
+}
+
+fn is_negation(word: &str) -> bool {
+    NEGATIONS.contains(&word)
+        || matches!(
+            word,
+            "doesnt" | "isnt" | "arent" | "cant" | "wont" | "shouldnt" | "mustnt" | "didnt"
+        )
+}
+
+/// Lowercase words with apostrophes dropped ("don't" -> "dont").
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !(c.is_alphanumeric() || c == '\'' || c == '\u{2019}'))
+        .map(|word| {
+            word.chars()
+                .filter(|c| c.is_alphanumeric())
+                .flat_map(char::to_lowercase)
+                .collect::<String>()
+        })
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+fn strip_list_marker(line: &str) -> &str {
+    let line = line.trim();
+    let line = line
+        .strip_prefix("- ")
+        .or_else(|| line.strip_prefix("* "))
+        .or_else(|| line.strip_prefix("+ "))
+        .unwrap_or_else(|| {
+            let digits = line.chars().take_while(char::is_ascii_digit).count();
+            if digits > 0 {
+                line[digits..]
+                    .strip_prefix(". ")
+                    .or_else(|| line[digits..].strip_prefix(") "))
+                    .unwrap_or(line)
+            } else {
+                line
+            }
+        });
+    let line = line
+        .strip_prefix("[ ] ")
+        .or_else(|| line.strip_prefix("[x] "))
+        .unwrap_or(line);
+    line.trim()
+}
+
+/// Priority of a layer name; unknown layers rank lowest.
+fn layer_rank(layer: Option<&str>) -> usize {
+    [
+        SkillLayer::Base,
+        SkillLayer::Org,
+        SkillLayer::Project,
+        SkillLayer::User,
+    ]
+    .iter()
+    .position(|known| Some(known.as_str()) == layer)
+    .map_or(0, |pos| pos + 1)
+}
+
+/// Span of the first source line containing `needle`.
+fn locate(skill: &CorpusSkill, needle: &str) -> Option<SourceSpan> {
+    let source = skill.source.as_deref()?;
+    source.lines().enumerate().find_map(|(idx, line)| {
+        line.find(needle).map(|col| {
+            let start = line[..col].chars().count() + 1;
+            SourceSpan::new(idx + 1, start, idx + 1, start + needle.chars().count())
+        })
+    })
+}
+
+/// All corpus rules, using `embedder` to compare guidance across skills.
+#[must_use]
+pub fn corpus_rules(embedder: Arc<dyn Embedder>) -> Vec<BoxedCorpusRule> {
+    vec![
+        Box::new(ContradictionRule::new(embedder)),
+        Box::new(UnprovidedRequirementRule),
+        Box::new(DeprecatedParentRule),
+        Box::new(DuplicateIncludedBlockRule),
+        Box::new(SingletonTagRule),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::skill::{
+        IncludePosition, IncludeTarget, SkillBlock, SkillInclude, SkillSection, SkillSpec,
+    };
+    use crate::lint::config::ValidationConfig;
+    use crate::search::HashEmbedder;
+
+    fn skill(id: &str, rules: &[&str]) -> SkillSpec {
+        let mut spec = SkillSpec::new(id, id);
+        spec.sections.push(SkillSection {
+            id: "rules".to_string(),
+            title: "Rules".to_string(),
+            blocks: rules
+                .iter()
+                .enumerate()
+                .map(|(idx, content)| SkillBlock {
+                    id: format!("{id}-rule-{idx}"),
+                    block_type: BlockType::Rule,
+                    content: (*content).to_string(),
+                    suppressions: Vec::new(),
+                })
+                .collect(),
+        });
+        spec
+    }
+
+    fn run(rule: &dyn CorpusRule, skills: &[CorpusSkill]) -> Vec<CorpusDiagnostic> {
+        let config = ValidationConfig::new();
+        rule.validate(&CorpusContext::new(skills, &config))
+    }
+
+    #[test]
+    fn contradiction_between_org_and_project_skill() {
+        let skills = vec![
+            CorpusSkill::new(skill(
+                "org-style",
+                &[
+                    "Always use tabs for indentation",
+                    "Run the formatter before committing",
+                ],
+            ))
+            .with_layer("org"),
+            CorpusSkill::new(skill(
+                "project-style",
+                &["- Never use tabs for indentation", "Keep commits small"],
+            ))
+            .with_layer("project")
+            .with_source("# Project\n\n- Never use tabs for indentation\n"),
+        ];
+        let rule = ContradictionRule::new(Arc::new(HashEmbedder::default()));
+        let findings = run(&rule, &skills);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].skill_id, "project-style");
+        assert!(findings[0].diagnostic.message.contains("'org-style' (org)"));
+        assert_eq!(
+            findings[0].diagnostic.span,
+            Some(SourceSpan::new(3, 3, 3, 33))
+        );
+
+        // Agreement and unrelated rules are not contradictions.
+        let agreeing = vec![
+            CorpusSkill::new(skill("a", &["Never commit secrets to git"])),
+            CorpusSkill::new(skill(
+                "b",
+                &["Do not commit secrets to git", "Always commit lockfiles"],
+            )),
+        ];
+        assert!(run(&rule, &agreeing).is_empty());
+    }
+
+    #[test]
+    fn requirements_parents_and_tags() {
+        let mut base = skill("base", &[]);
+        base.metadata.provides = vec!["Git".to_string()];
+        base.metadata.tags = vec!["vcs".to_string()];
+        let mut child = skill("child", &[]);
+        child.extends = Some("base".to_string());
+        child.metadata.requires = vec!["git".to_string(), "docker".to_string()];
+        child.metadata.tags = vec!["VCS".to_string(), "one-off".to_string()];
+        let skills = vec![
+            CorpusSkill::new(base).with_deprecated("use base-v2"),
+            CorpusSkill::new(child),
+        ];
+
+        let missing = run(&UnprovidedRequirementRule, &skills);
+        assert_eq!(missing.len(), 1);
+        assert!(missing[0].diagnostic.message.contains("'docker'"));
+
+        let parents = run(&DeprecatedParentRule, &skills);
+        assert_eq!(parents.len(), 1);
+        assert_eq!(
+            parents[0].diagnostic.message,
+            "Extends deprecated skill 'base': use base-v2"
+        );
+
+        let tags = run(&SingletonTagRule, &skills);
+        assert_eq!(tags.len(), 1);
+        assert!(tags[0].diagnostic.message.contains("'one-off'"));
+        assert_eq!(tags[0].diagnostic.severity, Severity::Info);
+    }
+
+    #[test]
+    fn duplicate_block_ids_across_includes() {
+        let mut shared = skill("shared", &["Check errors"]);
+        shared.sections[0].blocks[0].id = "errors".to_string();
+        let mut host = skill("host", &["Log errors"]);
+        host.sections[0].blocks[0].id = "errors".to_string();
+        host.includes.push(SkillInclude {
+            skill: "shared".to_string(),
+            into: IncludeTarget::Rules,
+            prefix: None,
+            sections: None,
+            position: IncludePosition::Append,
+        });
+        let skills = vec![CorpusSkill::new(shared), CorpusSkill::new(host)];
+
+        let findings = run(&DuplicateIncludedBlockRule, &skills);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].skill_id, "host");
+        assert!(
+            findings[0]
+                .diagnostic
+                .message
+                .contains("(from host, shared)")
+        );
+    }
+}
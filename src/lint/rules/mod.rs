@@ -7,6 +7,11 @@
 //! - **Security rules** (`security`): Detect secrets, injection, and unsafe patterns
 //! - **Quality rules** (`quality`): Check content quality (descriptions, rules, examples)
 //! - **Performance rules** (`quality`): Token budget and embedding quality hints
+//! - **Syntax rules** (`syntax`): Fenced code blocks must parse in their language
+//! - **Staleness rules** (`staleness`): Commands, flags and paths checked against
+//!   the local machine (opt-in, not part of `all_rules`)
+//! - **Corpus rules** (`corpus`): Checks across all indexed skills, such as
+//!   contradictory guidance (`CorpusRule`, run by `ms lint --corpus`)
 //!
 //! # Usage
 //!
@@ -20,24 +25,33 @@
 //! }
 //! ```
 
+pub mod corpus;
 pub mod quality;
 pub mod reference;
 pub mod security;
+pub mod staleness;
 pub mod structural;
+pub mod syntax;
 
 use crate::lint::rule::BoxedRule;
 
 // Re-export individual rules for direct use
+pub use corpus::{
+    ContradictionRule, DeprecatedParentRule, DuplicateIncludedBlockRule, SingletonTagRule,
+    UnprovidedRequirementRule, corpus_rules,
+};
 pub use quality::{
     ActionableRulesRule, BalancedContentRule, EmbeddingQualityRule, ExamplesHaveCodeRule,
     MeaningfulDescriptionRule, TokenBudgetRule,
 };
 pub use reference::{DeepInheritanceRule, FormatVersionRule, NoCycleRule, ValidExtendsRule};
 pub use security::{InputSanitizationRule, NoPromptInjectionRule, NoSecretsRule, SafePathsRule};
+pub use staleness::{StalenessRule, staleness_rules};
 pub use structural::{
     NonEmptyBlocksRule, RequiredMetadataRule, UniqueBlockIdsRule, UniqueSectionIdsRule,
     ValidVersionRule,
 };
+pub use syntax::{CodeLanguage, CodeSyntaxRule, SyntaxError, check_syntax};
 
 /// Returns all structural validation rules.
 #[must_use]
@@ -69,6 +83,12 @@ pub fn performance_rules() -> Vec<BoxedRule> {
     quality::performance_rules()
 }
 
+/// Returns all code block syntax rules.
+#[must_use]
+pub fn syntax_rules() -> Vec<BoxedRule> {
+    syntax::syntax_rules()
+}
+
 /// Returns all built-in validation rules.
 ///
 /// This is a convenience function that combines all rule categories.
@@ -79,6 +99,7 @@ pub fn all_rules() -> Vec<BoxedRule> {
     rules.extend(security_rules());
     rules.extend(quality_rules());
     rules.extend(performance_rules());
+    rules.extend(syntax_rules());
     rules
 }
 
@@ -118,12 +139,24 @@ mod tests {
         assert_eq!(rules.len(), 4);
     }
 
+    #[test]
+    fn test_corpus_rules_count() {
+        let rules = corpus_rules(std::sync::Arc::new(crate::search::HashEmbedder::default()));
+        assert_eq!(rules.len(), 5);
+    }
+
     #[test]
     fn test_performance_rules_count() {
         let rules = performance_rules();
         assert_eq!(rules.len(), 2);
     }
 
+    #[test]
+    fn test_syntax_rules_count() {
+        let rules = syntax_rules();
+        assert_eq!(rules.len(), 7);
+    }
+
     #[test]
     fn test_rule_ids_unique() {
         let rules = all_rules();
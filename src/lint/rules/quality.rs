@@ -318,7 +318,8 @@ impl ValidationRule for ActionableRulesRule {
                             .with_suggestion(
                                 "Start rules with action verbs like 'Use', 'Avoid', 'Always'",
                             )
-                            .with_category(RuleCategory::Quality),
+                            .with_category(RuleCategory::Quality)
+                            .with_block(&block.id),
                         );
                     }
                 }
@@ -685,6 +686,7 @@ mod tests {
                 id: "block-1".to_string(),
                 block_type: BlockType::Text,
                 content: content.to_string(),
+                suppressions: Vec::new(),
             }],
         });
         skill
@@ -764,11 +766,13 @@ mod tests {
                     id: "rule-1".to_string(),
                     block_type: BlockType::Rule,
                     content: "Always validate user input before processing.".to_string(),
+                    suppressions: Vec::new(),
                 },
                 SkillBlock {
                     id: "rule-2".to_string(),
                     block_type: BlockType::Rule,
                     content: "Use error handling for all async operations.".to_string(),
+                    suppressions: Vec::new(),
                 },
             ],
         });
@@ -791,6 +795,7 @@ mod tests {
                 id: "rule-1".to_string(),
                 block_type: BlockType::Rule,
                 content: "Input validation is important for security.".to_string(),
+                suppressions: Vec::new(),
             }],
         });
         let ctx = make_context(&skill, &config);
@@ -815,6 +820,7 @@ mod tests {
                 id: "example-1".to_string(),
                 block_type: BlockType::Code,
                 content: "let x = 42;".to_string(),
+                suppressions: Vec::new(),
             }],
         });
         let ctx = make_context(&skill, &config);
@@ -836,6 +842,7 @@ mod tests {
                 id: "example-1".to_string(),
                 block_type: BlockType::Text,
                 content: "Here is a long description without any code examples at all, which is not ideal for an example section that should demonstrate usage.".to_string(),
+                suppressions: Vec::new(),
             }],
         });
         let ctx = make_context(&skill, &config);
@@ -155,25 +155,23 @@ impl DeepInheritanceRule {
     }
 
     /// Calculate inheritance depth by walking the chain.
+    ///
+    /// Starts from the skill being validated rather than its indexed copy,
+    /// so unsaved or not-yet-indexed edits are measured too.
     fn calculate_depth(&self, ctx: &ValidationContext<'_>) -> Option<usize> {
         let repository = ctx.repository?;
         let mut depth = 0;
-        let mut current_id = ctx.skill.metadata.id.clone();
-
-        loop {
-            let skill = repository.get(&current_id).ok()??;
-            match &skill.extends {
-                Some(parent_id) => {
-                    depth += 1;
-                    current_id = parent_id.clone();
-                    // Safety limit to prevent infinite loops
-                    if depth > 100 {
-                        return Some(depth);
-                    }
-                }
-                None => return Some(depth),
+        let mut next = ctx.skill.extends.clone();
+
+        while let Some(parent_id) = next {
+            depth += 1;
+            // Safety limit to prevent infinite loops
+            if depth > 100 {
+                return Some(depth);
             }
+            next = repository.get(&parent_id).ok()??.extends;
         }
+        Some(depth)
     }
 }
 
@@ -454,6 +452,28 @@ mod tests {
         assert!(diagnostics.is_empty());
     }
 
+    #[test]
+    fn test_deep_inheritance_counts_unindexed_skill() {
+        let rule = DeepInheritanceRule::with_max_depth(1);
+        let config = ValidationConfig::new();
+
+        let mut repo = TestRepository::new();
+        let skill_c = SkillSpec::new("skill-c", "Skill C");
+        let mut skill_b = SkillSpec::new("skill-b", "Skill B");
+        skill_b.extends = Some("skill-c".to_string());
+        repo.add(skill_c);
+        repo.add(skill_b);
+
+        // skill-a is being edited and is not in the repository yet
+        let mut skill_a = SkillSpec::new("skill-a", "Skill A");
+        skill_a.extends = Some("skill-b".to_string());
+
+        let ctx = make_context(&skill_a, &config, Some(&repo));
+        let diagnostics = rule.validate(&ctx);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+    }
+
     #[test]
     fn test_format_version_valid() {
         let rule = FormatVersionRule;
@@ -0,0 +1,181 @@
+//! Staleness validation rules for skills.
+//!
+//! These rules check command blocks against the local machine: binaries must
+//! resolve on `PATH`, flags must still be documented by the tool's `--help`
+//! (only with `--probe-flags`), and relative repo paths must exist. Because results depend on the
+//! environment they are not part of [`all_rules`](super::all_rules); register
+//! them explicitly (`ms lint --verify`).
+
+use std::sync::Arc;
+
+use crate::lint::config::ValidationContext;
+use crate::lint::diagnostic::{Diagnostic, RuleCategory, Severity, SourceSpan};
+use crate::lint::rule::{BoxedRule, ValidationRule};
+use crate::quality::staleness::{StaleKind, StaleReference, StalenessVerifier};
+
+/// Rule reporting one kind of stale reference found by a shared verifier.
+pub struct StalenessRule {
+    kind: StaleKind,
+    verifier: Arc<StalenessVerifier>,
+}
+
+impl StalenessRule {
+    #[must_use]
+    pub const fn new(kind: StaleKind, verifier: Arc<StalenessVerifier>) -> Self {
+        Self { kind, verifier }
+    }
+}
+
+impl ValidationRule for StalenessRule {
+    fn id(&self) -> &'static str {
+        match self.kind {
+            StaleKind::MissingBinary => "stale-binary",
+            StaleKind::UnknownFlag => "stale-flag",
+            StaleKind::MissingPath => "stale-path",
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self.kind {
+            StaleKind::MissingBinary => "Command Binary Exists",
+            StaleKind::UnknownFlag => "Command Flags Documented",
+            StaleKind::MissingPath => "Referenced Paths Exist",
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self.kind {
+            StaleKind::MissingBinary => "Commands in command blocks must resolve on PATH",
+            StaleKind::UnknownFlag => {
+                "Flags used in commands must appear in the tool's --help output"
+            }
+            StaleKind::MissingPath => "Relative repo paths passed to commands must exist",
+        }
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Staleness
+    }
+
+    fn default_severity(&self) -> Severity {
+        match self.kind {
+            StaleKind::MissingBinary => Severity::Info,
+            StaleKind::UnknownFlag | StaleKind::MissingPath => Severity::Warning,
+        }
+    }
+
+    fn validate(&self, ctx: &ValidationContext<'_>) -> Vec<Diagnostic> {
+        let report = self.verifier.verify(ctx.skill);
+        report
+            .of_kind(self.kind)
+            .map(|stale| {
+                let mut diag = Diagnostic::new(self.id(), self.default_severity(), stale.message())
+                    .with_suggestion(suggestion(stale))
+                    .with_category(RuleCategory::Staleness)
+                    .with_block(&stale.block_id);
+                if let Some(line) = ctx.source.and_then(|src| find_line(src, stale)) {
+                    diag = diag.with_span(SourceSpan::line(line));
+                }
+                diag
+            })
+            .collect()
+    }
+}
+
+fn suggestion(stale: &StaleReference) -> String {
+    match stale.kind {
+        StaleKind::MissingBinary => format!(
+            "Install `{}` or document it as a prerequisite",
+            stale.target
+        ),
+        StaleKind::UnknownFlag => format!(
+            "Check `{}` against the current --help and update the command",
+            stale.command
+        ),
+        StaleKind::MissingPath => "Update the path or remove the command".to_string(),
+    }
+}
+
+/// First source line mentioning both the command's program and the target.
+fn find_line(source: &str, stale: &StaleReference) -> Option<usize> {
+    let program = stale.command.split_whitespace().next()?;
+    source
+        .lines()
+        .position(|line| line.contains(program) && line.contains(&stale.target))
+        .map(|idx| idx + 1)
+}
+
+/// Returns the staleness rules, sharing one verifier.
+#[must_use]
+pub fn staleness_rules(verifier: Arc<StalenessVerifier>) -> Vec<BoxedRule> {
+    vec![
+        Box::new(StalenessRule::new(
+            StaleKind::MissingBinary,
+            verifier.clone(),
+        )),
+        Box::new(StalenessRule::new(StaleKind::UnknownFlag, verifier.clone())),
+        Box::new(StalenessRule::new(StaleKind::MissingPath, verifier)),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::skill::{BlockType, SkillBlock, SkillSection, SkillSpec};
+    use crate::lint::config::ValidationConfig;
+    use tempfile::TempDir;
+
+    fn skill_with_command(command: &str) -> SkillSpec {
+        let mut skill = SkillSpec::new("test", "Test Skill");
+        skill.sections.push(SkillSection {
+            id: "usage".to_string(),
+            title: "Usage".to_string(),
+            blocks: vec![SkillBlock {
+                id: "usage-block-1".to_string(),
+                block_type: BlockType::Command,
+                content: command.to_string(),
+                suppressions: Vec::new(),
+            }],
+        });
+        skill
+    }
+
+    #[test]
+    fn test_rule_ids_unique() {
+        let rules = staleness_rules(Arc::new(StalenessVerifier::new()));
+        let ids: Vec<&str> = rules.iter().map(|r| r.id()).collect();
+        assert_eq!(ids, vec!["stale-binary", "stale-flag", "stale-path"]);
+        assert!(
+            rules
+                .iter()
+                .all(|r| r.category() == RuleCategory::Staleness)
+        );
+    }
+
+    #[test]
+    fn test_missing_path_reported_with_span() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("docs")).unwrap();
+        let verifier = Arc::new(StalenessVerifier::new().with_repo_root(dir.path()));
+        let rule = StalenessRule::new(StaleKind::MissingPath, verifier);
+
+        let skill = skill_with_command("cat docs/removed.md");
+        let source = "# Test Skill\n\n## Usage\n\ncat docs/removed.md\n";
+        let config = ValidationConfig::new();
+        let ctx = ValidationContext::new(&skill, &config).with_source(source);
+
+        let diagnostics = rule.validate(&ctx);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule_id, "stale-path");
+        assert_eq!(diagnostics[0].span, Some(SourceSpan::line(5)));
+    }
+
+    #[test]
+    fn test_fresh_skill_has_no_diagnostics() {
+        let rule = StalenessRule::new(StaleKind::MissingBinary, Arc::new(StalenessVerifier::new()));
+        let skill = skill_with_command("cd somewhere");
+        let config = ValidationConfig::new();
+        let ctx = ValidationContext::new(&skill, &config);
+        assert!(rule.validate(&ctx).is_empty());
+    }
+}
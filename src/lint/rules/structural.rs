@@ -221,7 +221,8 @@ impl ValidationRule for UniqueBlockIdsRule {
                             ),
                         )
                         .with_suggestion("Each block must have a unique ID within its section")
-                        .with_category(RuleCategory::Structure),
+                        .with_category(RuleCategory::Structure)
+                        .with_block(&block.id),
                     );
                 }
             }
@@ -270,7 +271,8 @@ impl ValidationRule for NonEmptyBlocksRule {
                             ),
                         )
                         .with_suggestion("Add meaningful content or remove the empty block")
-                        .with_category(RuleCategory::Structure),
+                        .with_category(RuleCategory::Structure)
+                        .with_block(&block.id),
                     );
                 }
             }
@@ -413,11 +415,13 @@ mod tests {
                     id: "block-1".to_string(),
                     block_type: Default::default(),
                     content: "Content".to_string(),
+                    suppressions: Vec::new(),
                 },
                 SkillBlock {
                     id: "block-1".to_string(), // Duplicate!
                     block_type: Default::default(),
                     content: "More content".to_string(),
+                    suppressions: Vec::new(),
                 },
             ],
         }];
@@ -440,6 +444,7 @@ mod tests {
                 id: "block-1".to_string(),
                 block_type: Default::default(),
                 content: "   ".to_string(), // Empty!
+                suppressions: Vec::new(),
             }],
         }];
         let ctx = make_context(&skill, &config);
@@ -0,0 +1,1011 @@
+//! Syntax validation rules for fenced code blocks.
+//!
+//! Every fenced block whose info string names a supported language is parsed:
+//! JSON, YAML and TOML with the same crates `ms` reads its own files with,
+//! Rust with `syn`, shell with the command-safety parser in strict mode
+//! (`core::shell::parse_strict`), and Python and JavaScript with small
+//! built-in scanners that catch unterminated strings, unbalanced brackets and
+//! indentation (no full grammars are bundled for those).
+//!
+//! Blocks are skipped when their info string carries `ignore` or
+//! `compile_fail`, or when they elide code with a bare `...` line.
+//!
+//! Spans point into SKILL.md when the context carries the source text, and
+//! into the scanned content (see `extract_all_content`) otherwise.
+
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::core::shell;
+use crate::core::skill::SkillSpec;
+use crate::import::formatting::extract_fenced_blocks;
+use crate::lint::config::ValidationContext;
+use crate::lint::diagnostic::{Diagnostic, RuleCategory, Severity, SourceSpan};
+use crate::lint::rule::{BoxedRule, ValidationRule};
+
+/// Position suffix that `serde_json` and `serde_yaml` append to messages.
+static PARSER_POSITION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r" at line \d+ column \d+").unwrap());
+
+// =============================================================================
+// LANGUAGES AND CHECKERS
+// =============================================================================
+
+/// Languages checked by the syntax rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodeLanguage {
+    Json,
+    Yaml,
+    Toml,
+    Shell,
+    Rust,
+    Python,
+    JavaScript,
+}
+
+impl CodeLanguage {
+    pub const ALL: [Self; 7] = [
+        Self::Json,
+        Self::Yaml,
+        Self::Toml,
+        Self::Shell,
+        Self::Rust,
+        Self::Python,
+        Self::JavaScript,
+    ];
+
+    /// Language for a fence tag such as `json` or `bash`, if it is checked.
+    #[must_use]
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        match tag.to_ascii_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "yaml" | "yml" => Some(Self::Yaml),
+            "toml" => Some(Self::Toml),
+            "sh" | "bash" | "shell" | "zsh" => Some(Self::Shell),
+            "rust" | "rs" => Some(Self::Rust),
+            "python" | "py" | "python3" => Some(Self::Python),
+            "js" | "javascript" | "mjs" | "cjs" | "ts" | "typescript" => Some(Self::JavaScript),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Json => "JSON",
+            Self::Yaml => "YAML",
+            Self::Toml => "TOML",
+            Self::Shell => "shell",
+            Self::Rust => "Rust",
+            Self::Python => "Python",
+            Self::JavaScript => "JavaScript",
+        }
+    }
+}
+
+/// A syntax error, positioned within the code block (1-indexed line and
+/// character column).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl SyntaxError {
+    fn new(position: (usize, usize), message: impl Into<String>) -> Self {
+        Self {
+            line: position.0.max(1),
+            column: position.1.max(1),
+            message: message.into(),
+        }
+    }
+
+    fn at_offset(code: &str, offset: usize, message: impl Into<String>) -> Self {
+        let offset = offset.min(code.len());
+        let before = code.get(..offset).unwrap_or(code);
+        let line = before.matches('\n').count() + 1;
+        let column = before
+            .rsplit('\n')
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .count()
+            + 1;
+        Self::new((line, column), message)
+    }
+}
+
+/// Parse `code` as `language`.
+pub fn check_syntax(language: CodeLanguage, code: &str) -> Result<(), SyntaxError> {
+    match language {
+        CodeLanguage::Json => check_json(code),
+        CodeLanguage::Yaml => check_yaml(code),
+        CodeLanguage::Toml => check_toml(code),
+        CodeLanguage::Shell => check_shell(code),
+        CodeLanguage::Rust => check_rust(code),
+        CodeLanguage::Python => check_python(code),
+        CodeLanguage::JavaScript => check_javascript(code),
+    }
+}
+
+fn parser_message(message: &str) -> String {
+    PARSER_POSITION.replace_all(message, "").into_owned()
+}
+
+fn check_json(code: &str) -> Result<(), SyntaxError> {
+    serde_json::from_str::<serde_json::Value>(code)
+        .map(|_| ())
+        .map_err(|err| {
+            SyntaxError::new((err.line(), err.column()), parser_message(&err.to_string()))
+        })
+}
+
+fn check_yaml(code: &str) -> Result<(), SyntaxError> {
+    for document in serde_yaml::Deserializer::from_str(code) {
+        if let Err(err) = serde_yaml::Value::deserialize(document) {
+            let position = err
+                .location()
+                .map_or((1, 1), |location| (location.line(), location.column()));
+            return Err(SyntaxError::new(position, parser_message(&err.to_string())));
+        }
+    }
+    Ok(())
+}
+
+fn check_toml(code: &str) -> Result<(), SyntaxError> {
+    toml::from_str::<toml::Table>(code)
+        .map(|_| ())
+        .map_err(|err| {
+            let offset = err.span().map_or(0, |span| span.start);
+            SyntaxError::at_offset(code, offset, err.message().trim())
+        })
+}
+
+/// Parse as a file, then as the body of a block so statement snippets pass;
+/// report whichever parse got further.
+fn check_rust(code: &str) -> Result<(), SyntaxError> {
+    // Rustdoc hides setup lines behind `# `.
+    let code: String = code
+        .lines()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            if trimmed == "#" {
+                ""
+            } else {
+                trimmed.strip_prefix("# ").unwrap_or(line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let file_err = match syn::parse_file(&code) {
+        Ok(_) => return Ok(()),
+        Err(err) => rust_error(&err, 0),
+    };
+    let block_err = match syn::parse_str::<syn::Block>(&format!("{{\n{code}\n}}")) {
+        Ok(_) => return Ok(()),
+        Err(err) => rust_error(&err, 1),
+    };
+    Err(std::cmp::max_by_key(file_err, block_err, |err| {
+        (err.line, err.column)
+    }))
+}
+
+fn rust_error(err: &syn::Error, line_offset: usize) -> SyntaxError {
+    let start = err.span().start();
+    SyntaxError::new(
+        (start.line.saturating_sub(line_offset), start.column + 1),
+        err.to_string(),
+    )
+}
+
+/// Character cursor tracking 1-indexed line and column.
+struct Cursor {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+    column: usize,
+}
+
+impl Cursor {
+    fn new(code: &str) -> Self {
+        Self {
+            chars: code.chars().collect(),
+            pos: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, ahead: usize) -> Option<char> {
+        self.chars.get(self.pos + ahead).copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    const fn here(&self) -> (usize, usize) {
+        (self.line, self.column)
+    }
+
+    fn skip_line(&mut self) {
+        while self.peek().is_some_and(|c| c != '\n') {
+            self.bump();
+        }
+    }
+}
+
+/// Shell blocks written as terminal sessions: keep only `$ ` command lines
+/// (and their continuations), blanking output so line numbers stay put.
+fn strip_prompts(code: &str) -> String {
+    if !code.lines().any(|line| line.starts_with("$ ")) {
+        return code.to_string();
+    }
+    let mut continued = false;
+    code.lines()
+        .map(|line| {
+            let kept = if let Some(command) = line.strip_prefix("$ ") {
+                format!("  {command}")
+            } else if continued {
+                line.to_string()
+            } else {
+                String::new()
+            };
+            continued = !kept.is_empty() && kept.ends_with('\\');
+            kept
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn check_shell(code: &str) -> Result<(), SyntaxError> {
+    let code = strip_prompts(code);
+    shell::parse_strict(&code).map(|_| ()).map_err(|err| {
+        // Parser offsets count characters.
+        let offset = code
+            .char_indices()
+            .nth(err.offset)
+            .map_or(code.len(), |(index, _)| index);
+        SyntaxError::at_offset(&code, offset, err.message)
+    })
+}
+
+const fn closing_for(open: char) -> char {
+    match open {
+        '(' => ')',
+        '[' => ']',
+        _ => '}',
+    }
+}
+
+fn check_python(code: &str) -> Result<(), SyntaxError> {
+    let mut cur = Cursor::new(code);
+    let mut brackets: Vec<(char, (usize, usize))> = Vec::new();
+    let mut indents: Vec<usize> = vec![0];
+    let mut expect_indent: Option<(usize, usize)> = None;
+
+    while cur.peek().is_some() {
+        // Start of a logical line: measure indentation.
+        let mut indent = 0;
+        while let Some(c) = cur.peek() {
+            match c {
+                ' ' => indent += 1,
+                '\t' => indent = (indent / 8 + 1) * 8,
+                _ => break,
+            }
+            cur.bump();
+        }
+        match cur.peek() {
+            Some('\n' | '\r') => {
+                cur.bump();
+                continue;
+            }
+            Some('#') => {
+                cur.skip_line();
+                continue;
+            }
+            None => break,
+            Some(_) => {}
+        }
+
+        let at = cur.here();
+        let current = *indents.last().unwrap_or(&0);
+        if expect_indent.take().is_some() {
+            if indent <= current {
+                return Err(SyntaxError::new(at, "expected an indented block"));
+            }
+            indents.push(indent);
+        } else if indent > current {
+            return Err(SyntaxError::new(at, "unexpected indent"));
+        } else {
+            while indent < *indents.last().unwrap_or(&0) {
+                indents.pop();
+            }
+            if indent != *indents.last().unwrap_or(&0) {
+                return Err(SyntaxError::new(
+                    at,
+                    "unindent does not match any outer indentation level",
+                ));
+            }
+        }
+
+        // Rest of the logical line.
+        let mut last = ' ';
+        while let Some(c) = cur.peek() {
+            let at = cur.here();
+            match c {
+                '\n' => {
+                    cur.bump();
+                    if brackets.is_empty() {
+                        break;
+                    }
+                }
+                '#' => cur.skip_line(),
+                '\\' if cur.peek_at(1) == Some('\n') => {
+                    cur.bump();
+                    cur.bump();
+                }
+                '\'' | '"' => {
+                    python_string(&mut cur, c)?;
+                    last = c;
+                }
+                '(' | '[' | '{' => {
+                    cur.bump();
+                    brackets.push((c, at));
+                    last = c;
+                }
+                ')' | ']' | '}' => {
+                    cur.bump();
+                    match brackets.pop() {
+                        Some((open, _)) if closing_for(open) == c => {}
+                        Some((open, _)) => {
+                            return Err(SyntaxError::new(
+                                at,
+                                format!(
+                                    "closing parenthesis '{c}' does not match opening parenthesis '{open}'"
+                                ),
+                            ));
+                        }
+                        None => return Err(SyntaxError::new(at, format!("unmatched '{c}'"))),
+                    }
+                    last = c;
+                }
+                c if c.is_whitespace() => {
+                    cur.bump();
+                }
+                c => {
+                    cur.bump();
+                    last = c;
+                }
+            }
+        }
+        if last == ':' {
+            expect_indent = Some(cur.here());
+        }
+    }
+
+    if let Some((open, at)) = brackets.first() {
+        return Err(SyntaxError::new(*at, format!("'{open}' was never closed")));
+    }
+    if expect_indent.is_some() {
+        return Err(SyntaxError::new(cur.here(), "expected an indented block"));
+    }
+    Ok(())
+}
+
+fn python_string(cur: &mut Cursor, quote: char) -> Result<(), SyntaxError> {
+    let at = cur.here();
+    let triple = cur.peek_at(1) == Some(quote) && cur.peek_at(2) == Some(quote);
+    let opening = if triple { 3 } else { 1 };
+    for _ in 0..opening {
+        cur.bump();
+    }
+    loop {
+        match cur.peek() {
+            None => {
+                let kind = if triple {
+                    "triple-quoted string"
+                } else {
+                    "string"
+                };
+                return Err(SyntaxError::new(at, format!("unterminated {kind} literal")));
+            }
+            Some('\\') => {
+                cur.bump();
+                cur.bump();
+            }
+            Some('\n') if !triple => {
+                return Err(SyntaxError::new(at, "unterminated string literal"));
+            }
+            Some(c) if c == quote => {
+                if !triple {
+                    cur.bump();
+                    return Ok(());
+                }
+                if cur.peek_at(1) == Some(quote) && cur.peek_at(2) == Some(quote) {
+                    cur.bump();
+                    cur.bump();
+                    cur.bump();
+                    return Ok(());
+                }
+                cur.bump();
+            }
+            Some(_) => {
+                cur.bump();
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum JsScope {
+    Bracket(char),
+    Template,
+    TemplateExpr,
+}
+
+/// Keywords after which `/` starts a regular expression.
+const JS_REGEX_KEYWORDS: &[&str] = &[
+    "return",
+    "typeof",
+    "case",
+    "do",
+    "else",
+    "in",
+    "instanceof",
+    "new",
+    "delete",
+    "void",
+    "throw",
+    "yield",
+    "await",
+    "of",
+];
+
+fn check_javascript(code: &str) -> Result<(), SyntaxError> {
+    let mut cur = Cursor::new(code);
+    if cur.peek() == Some('#') && cur.peek_at(1) == Some('!') {
+        cur.skip_line();
+    }
+    let mut scopes: Vec<(JsScope, (usize, usize))> = Vec::new();
+    let mut regex_allowed = true;
+
+    while let Some(c) = cur.peek() {
+        let at = cur.here();
+        if scopes.last().map(|(scope, _)| *scope) == Some(JsScope::Template) {
+            cur.bump();
+            match c {
+                '\\' => {
+                    cur.bump();
+                }
+                '`' => {
+                    scopes.pop();
+                    regex_allowed = false;
+                }
+                '$' if cur.peek() == Some('{') => {
+                    cur.bump();
+                    scopes.push((JsScope::TemplateExpr, at));
+                    regex_allowed = true;
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        match c {
+            c if c.is_whitespace() => {
+                cur.bump();
+            }
+            '/' if cur.peek_at(1) == Some('/') => cur.skip_line(),
+            '/' if cur.peek_at(1) == Some('*') => {
+                cur.bump();
+                cur.bump();
+                loop {
+                    match cur.bump() {
+                        Some('*') if cur.peek() == Some('/') => {
+                            cur.bump();
+                            break;
+                        }
+                        Some(_) => {}
+                        None => return Err(SyntaxError::new(at, "unterminated comment")),
+                    }
+                }
+            }
+            '/' if regex_allowed => {
+                cur.bump();
+                let mut in_class = false;
+                loop {
+                    match cur.bump() {
+                        Some('\\') => {
+                            cur.bump();
+                        }
+                        Some('[') => in_class = true,
+                        Some(']') => in_class = false,
+                        Some('/') if !in_class => break,
+                        Some('\n') | None => {
+                            return Err(SyntaxError::new(at, "unterminated regular expression"));
+                        }
+                        Some(_) => {}
+                    }
+                }
+                while cur.peek().is_some_and(char::is_alphabetic) {
+                    cur.bump();
+                }
+                regex_allowed = false;
+            }
+            '\'' | '"' => {
+                cur.bump();
+                loop {
+                    match cur.bump() {
+                        Some('\\') => {
+                            cur.bump();
+                        }
+                        Some(q) if q == c => break,
+                        Some('\n') | None => {
+                            return Err(SyntaxError::new(at, "unterminated string literal"));
+                        }
+                        Some(_) => {}
+                    }
+                }
+                regex_allowed = false;
+            }
+            '`' => {
+                cur.bump();
+                scopes.push((JsScope::Template, at));
+            }
+            '(' | '[' | '{' => {
+                cur.bump();
+                scopes.push((JsScope::Bracket(c), at));
+                regex_allowed = true;
+            }
+            ')' | ']' | '}' => {
+                cur.bump();
+                match scopes.pop() {
+                    Some((JsScope::Bracket(open), _)) if closing_for(open) == c => {}
+                    Some((JsScope::TemplateExpr, _)) if c == '}' => {}
+                    Some((JsScope::Bracket(open), opened)) => {
+                        return Err(SyntaxError::new(
+                            at,
+                            format!(
+                                "unexpected '{c}' (expected '{}' for '{open}' at line {})",
+                                closing_for(open),
+                                opened.0
+                            ),
+                        ));
+                    }
+                    _ => return Err(SyntaxError::new(at, format!("unexpected '{c}'"))),
+                }
+                regex_allowed = false;
+            }
+            c if c.is_alphanumeric() || c == '_' || c == '$' => {
+                let mut word = String::new();
+                while let Some(c) = cur.peek() {
+                    if !(c.is_alphanumeric() || c == '_' || c == '$') {
+                        break;
+                    }
+                    word.push(c);
+                    cur.bump();
+                }
+                regex_allowed = JS_REGEX_KEYWORDS.contains(&word.as_str());
+            }
+            _ => {
+                cur.bump();
+                regex_allowed = true;
+            }
+        }
+    }
+
+    match scopes.last() {
+        Some((JsScope::Template, at)) => {
+            Err(SyntaxError::new(*at, "unterminated template literal"))
+        }
+        Some((JsScope::TemplateExpr, at)) => Err(SyntaxError::new(*at, "'${' is never closed")),
+        Some((JsScope::Bracket(open), at)) => {
+            Err(SyntaxError::new(*at, format!("'{open}' is never closed")))
+        }
+        None => Ok(()),
+    }
+}
+
+// =============================================================================
+// FENCED BLOCKS
+// =============================================================================
+
+/// A fenced code block found in a skill.
+struct Fence<'a> {
+    section: &'a str,
+    block_id: &'a str,
+    info: String,
+    code: String,
+    /// Indentation removed from the fence and its code lines
+    indent: usize,
+    /// Scanned-content line of the first code line
+    scanned_line: usize,
+    /// SKILL.md line of the first code line, when the source is known
+    source_line: Option<usize>,
+}
+
+impl Fence<'_> {
+    /// The language to check, unless the info string opts out.
+    fn language(&self) -> Option<CodeLanguage> {
+        let mut words = self
+            .info
+            .split(|c: char| c == ',' || c == '{' || c.is_whitespace())
+            .filter(|word| !word.is_empty());
+        let language = CodeLanguage::from_tag(words.next()?)?;
+        if words.any(|word| matches!(word, "ignore" | "compile_fail")) {
+            return None;
+        }
+        // YAML's `...` ends a document and Python's is an expression.
+        let elided = !matches!(language, CodeLanguage::Yaml | CodeLanguage::Python)
+            && self
+                .code
+                .lines()
+                .any(|line| matches!(line.trim(), "..." | "…"));
+        let interactive = language == CodeLanguage::Python
+            && self
+                .code
+                .lines()
+                .any(|line| line.trim_start().starts_with(">>>"));
+        if elided || interactive || self.code.trim().is_empty() {
+            return None;
+        }
+        Some(language)
+    }
+}
+
+fn line_count(text: &str) -> usize {
+    text.matches('\n').count() + 1
+}
+
+fn fences<'a>(skill: &'a SkillSpec, source: Option<&str>) -> Vec<Fence<'a>> {
+    let mut fences = Vec::new();
+    let mut scanned_line =
+        1 + line_count(&skill.metadata.name) + line_count(&skill.metadata.description);
+    let mut source_cursor = 0;
+
+    for section in &skill.sections {
+        scanned_line += line_count(&section.title);
+        for block in &section.blocks {
+            let block_line = scanned_line;
+            scanned_line += line_count(&block.content);
+            if !block.content.contains("```") {
+                continue;
+            }
+
+            let source_line = source.and_then(|source| {
+                let found = source.get(source_cursor..)?.find(&block.content)? + source_cursor;
+                source_cursor = found + block.content.len();
+                Some(source[..found].matches('\n').count() + 1)
+            });
+
+            for extracted in extract_fenced_blocks(&block.content) {
+                // Lines before the first code line within the block.
+                let first = block.content[..extracted.offset].matches('\n').count();
+                let code = extracted
+                    .code
+                    .lines()
+                    .map(|line| {
+                        let strip = line.len() - line.trim_start().len();
+                        &line[strip.min(extracted.indent)..]
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                fences.push(Fence {
+                    section: &section.title,
+                    block_id: &block.id,
+                    info: extracted.info,
+                    code,
+                    indent: extracted.indent,
+                    scanned_line: block_line + first,
+                    source_line: source_line.map(|line| line + first),
+                });
+            }
+        }
+    }
+    fences
+}
+
+// =============================================================================
+// RULES
+// =============================================================================
+
+/// Rule that parses fenced code blocks of one language.
+pub struct CodeSyntaxRule {
+    language: CodeLanguage,
+}
+
+impl CodeSyntaxRule {
+    #[must_use]
+    pub const fn new(language: CodeLanguage) -> Self {
+        Self { language }
+    }
+}
+
+impl ValidationRule for CodeSyntaxRule {
+    fn id(&self) -> &'static str {
+        match self.language {
+            CodeLanguage::Json => "json-syntax",
+            CodeLanguage::Yaml => "yaml-syntax",
+            CodeLanguage::Toml => "toml-syntax",
+            CodeLanguage::Shell => "shell-syntax",
+            CodeLanguage::Rust => "rust-syntax",
+            CodeLanguage::Python => "python-syntax",
+            CodeLanguage::JavaScript => "js-syntax",
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self.language {
+            CodeLanguage::Json => "Valid JSON Blocks",
+            CodeLanguage::Yaml => "Valid YAML Blocks",
+            CodeLanguage::Toml => "Valid TOML Blocks",
+            CodeLanguage::Shell => "Valid Shell Blocks",
+            CodeLanguage::Rust => "Valid Rust Blocks",
+            CodeLanguage::Python => "Valid Python Blocks",
+            CodeLanguage::JavaScript => "Valid JavaScript Blocks",
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self.language {
+            CodeLanguage::Json => "Fenced json blocks must parse as JSON",
+            CodeLanguage::Yaml => "Fenced yaml blocks must parse as YAML",
+            CodeLanguage::Toml => "Fenced toml blocks must parse as TOML",
+            CodeLanguage::Shell => {
+                "Fenced shell blocks must have balanced quotes, brackets and keywords"
+            }
+            CodeLanguage::Rust => "Fenced rust blocks must parse as Rust items or statements",
+            CodeLanguage::Python => {
+                "Fenced python blocks must have valid strings, brackets and indentation"
+            }
+            CodeLanguage::JavaScript => {
+                "Fenced js/ts blocks must have balanced strings, comments and brackets"
+            }
+        }
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Quality
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn validate(&self, ctx: &ValidationContext<'_>) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for fence in fences(ctx.skill, ctx.source) {
+            if fence.language() != Some(self.language) {
+                continue;
+            }
+            let Err(err) = check_syntax(self.language, &fence.code) else {
+                continue;
+            };
+
+            let line = err.line.min(line_count(&fence.code));
+            let text = fence.code.lines().nth(line - 1).unwrap_or_default();
+            let width = text.chars().count();
+            let column = err.column.min(width + 1);
+
+            let mut diag = Diagnostic::warning(
+                self.id(),
+                format!(
+                    "{} syntax error in '{}' at line {line}, column {column} of the block: {}",
+                    self.language.label(),
+                    fence.section,
+                    err.message
+                ),
+            )
+            .with_suggestion(format!(
+                "Fix the example, or mark intentionally partial code with ```{} ignore",
+                fence.info.split_whitespace().next().unwrap_or_default()
+            ))
+            .with_category(RuleCategory::Quality)
+            .with_block(fence.block_id);
+
+            let first_line = match ctx.source {
+                Some(_) => fence.source_line,
+                None => Some(fence.scanned_line),
+            };
+            if let Some(first_line) = first_line {
+                let start = fence.indent + column;
+                let end = (fence.indent + width + 1).max(start + 1);
+                let line = first_line + line - 1;
+                diag = diag.with_span(SourceSpan::new(line, start, line, end));
+            }
+            diagnostics.push(diag);
+        }
+
+        diagnostics
+    }
+}
+
+/// Returns the code block syntax rules, one per language.
+#[must_use]
+pub fn syntax_rules() -> Vec<BoxedRule> {
+    CodeLanguage::ALL
+        .into_iter()
+        .map(|language| Box::new(CodeSyntaxRule::new(language)) as BoxedRule)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::spec_lens::parse_markdown;
+    use crate::lint::config::ValidationConfig;
+    use crate::lint::rules::security::extract_all_content;
+
+    fn error(language: CodeLanguage, code: &str) -> SyntaxError {
+        check_syntax(language, code).expect_err(code)
+    }
+
+    #[test]
+    fn test_data_formats() {
+        assert!(check_syntax(CodeLanguage::Json, "{\"a\": [1, 2]}").is_ok());
+        let err = error(CodeLanguage::Json, "{\n  \"a\": 1\n  \"b\": 2\n}");
+        assert_eq!((err.line, err.column), (3, 3));
+        assert!(!err.message.contains("at line"));
+
+        assert!(check_syntax(CodeLanguage::Yaml, "a: 1\n---\nb: [1, 2]\n...\n").is_ok());
+        let err = error(CodeLanguage::Yaml, "a: 1\nb: [1, 2\nc: 3");
+        assert!(err.line >= 2);
+
+        assert!(check_syntax(CodeLanguage::Toml, "[server]\nport = 80").is_ok());
+        let err = error(CodeLanguage::Toml, "[server]\nport = ");
+        assert_eq!(err.line, 2);
+    }
+
+    #[test]
+    fn test_rust_files_and_snippets() {
+        assert!(check_syntax(CodeLanguage::Rust, "fn main() {\n    println!(\"hi\");\n}").is_ok());
+        assert!(check_syntax(CodeLanguage::Rust, "let x = foo()?;\nx.bar()").is_ok());
+        assert!(check_syntax(CodeLanguage::Rust, "# use std::io;\nlet _ = io::stdin();").is_ok());
+
+        let err = error(CodeLanguage::Rust, "fn main() {\n    let x = ;\n}");
+        assert_eq!((err.line, err.column), (2, 13));
+    }
+
+    #[test]
+    fn test_shell() {
+        let ok = [
+            "cargo build --release && ./target/release/ms index",
+            "if [ -f x ]; then\n  echo \"$(date) ${HOME}\"\nfi",
+            "for f in *.md; do\n  case \"$f\" in\n    a*) echo a ;;\n    *) echo b ;;\n  esac\ndone",
+            "cat <<'EOF' > out.txt\nif (unbalanced\nEOF\necho done",
+            "$ ms init\nInitialized (in .ms)\n$ ms index",
+            "echo 'it''s' # it's fine",
+            "ls |\n  grep md",
+        ];
+        for code in ok {
+            assert!(check_syntax(CodeLanguage::Shell, code).is_ok(), "{code}");
+        }
+
+        let err = error(CodeLanguage::Shell, "if true; then\n  echo hi\n");
+        assert_eq!((err.line, err.column), (1, 1));
+        assert!(err.message.contains("`if` is never closed"));
+        assert_eq!(error(CodeLanguage::Shell, "echo 'oops").column, 6);
+        assert!(
+            error(CodeLanguage::Shell, "for x in a; do echo; fi")
+                .message
+                .contains("expected `done`")
+        );
+        assert!(
+            error(CodeLanguage::Shell, "ls &&")
+                .message
+                .contains("after `&&`")
+        );
+        assert!(
+            error(CodeLanguage::Shell, "cat <<EOF\nbody")
+                .message
+                .contains("never terminated")
+        );
+    }
+
+    #[test]
+    fn test_python_and_javascript() {
+        let python =
+            "def f(x):\n    if x:\n        return {'a': [1, 2]}\n    return \"\"\"doc\n:\"\"\"\n";
+        assert!(check_syntax(CodeLanguage::Python, python).is_ok());
+        assert_eq!(error(CodeLanguage::Python, "def f():\nreturn 1").line, 2);
+        assert!(
+            error(CodeLanguage::Python, "x = 1\n  y = 2")
+                .message
+                .contains("unexpected indent")
+        );
+        assert!(
+            error(CodeLanguage::Python, "print(f(x)")
+                .message
+                .contains("never closed")
+        );
+        assert!(
+            error(CodeLanguage::Python, "s = 'abc\n")
+                .message
+                .contains("unterminated")
+        );
+
+        let js = "const re = /[/]+/g;\nconst s = `a ${fn({ b: '}' })} c`;\n// (\n/* { */\nif (a / b > 1) { run(); }";
+        assert!(check_syntax(CodeLanguage::JavaScript, js).is_ok());
+        let err = error(
+            CodeLanguage::JavaScript,
+            "function f() {\n  return [1, 2);\n}",
+        );
+        assert_eq!((err.line, err.column), (2, 15));
+        assert!(
+            error(CodeLanguage::JavaScript, "const s = 'abc")
+                .message
+                .contains("unterminated")
+        );
+    }
+
+    const SKILL: &str = "---\nid: demo\nname: Demo\ndescription: Demo skill\n---\n\n# Demo\n\nDemo skill\n\n## Config\n\n```json\n{\n  \"a\": 1,\n}\n```\n\n```rust ignore\nfn broken(\n```\n\n```rust\nfn main() {\n    ...\n}\n```\n";
+
+    #[test]
+    fn test_rule_spans_map_to_source() {
+        let skill = parse_markdown(SKILL).unwrap();
+        let config = ValidationConfig::new();
+        let rule = CodeSyntaxRule::new(CodeLanguage::Json);
+
+        let ctx = ValidationContext::new(&skill, &config).with_source(SKILL);
+        let diagnostics = rule.validate(&ctx);
+        assert_eq!(diagnostics.len(), 1);
+        assert!(
+            diagnostics[0]
+                .message
+                .starts_with("JSON syntax error in 'Config'")
+        );
+        let span = diagnostics[0].span.clone().unwrap();
+        assert_eq!(SKILL.lines().nth(span.start_line - 1), Some("}"));
+
+        let diagnostics = rule.validate(&ValidationContext::new(&skill, &config));
+        let span = diagnostics[0].span.clone().unwrap();
+        let scanned = extract_all_content(&skill);
+        assert_eq!(scanned.lines().nth(span.start_line - 1), Some("}"));
+    }
+
+    #[test]
+    fn test_shell_offsets_map_to_source() {
+        let source = "---\nid: demo\nname: Demo\ndescription: Demo skill\n---\n\n# Demo\n\nDemo skill\n\n## Run\n\n```bash\necho ok\necho é 'oops\n```\n";
+        let skill = parse_markdown(source).unwrap();
+        let config = ValidationConfig::new();
+        let ctx = ValidationContext::new(&skill, &config).with_source(source);
+        let diagnostics = CodeSyntaxRule::new(CodeLanguage::Shell).validate(&ctx);
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].message.contains("unterminated single quote"));
+
+        let span = diagnostics[0].span.clone().unwrap();
+        let line = source.lines().nth(span.start_line - 1).unwrap();
+        assert_eq!(line, "echo é 'oops");
+        assert_eq!(line.chars().nth(span.start_col - 1), Some('\''));
+    }
+
+    #[test]
+    fn test_ignored_and_elided_blocks_skipped() {
+        let skill = parse_markdown(SKILL).unwrap();
+        let config = ValidationConfig::new();
+        let ctx = ValidationContext::new(&skill, &config).with_source(SKILL);
+        assert!(
+            CodeSyntaxRule::new(CodeLanguage::Rust)
+                .validate(&ctx)
+                .is_empty()
+        );
+    }
+}
@@ -23,6 +23,7 @@ fn main() -> ExitCode {
                 let (code, message) = match &e {
                     ms::MsError::ApprovalRequired(msg) => ("approval_required", msg.clone()),
                     ms::MsError::DestructiveBlocked(msg) => ("destructive_blocked", msg.clone()),
+                    ms::MsError::PolicyViolation(msg) => ("policy_violation", msg.clone()),
                     _ => ("error", e.to_string()),
                 };
                 let error_json = serde_json::json!({
@@ -1,8 +1,13 @@
 //! Quality tooling integrations.
 
 pub mod skill;
+pub mod staleness;
 pub mod ubs;
 
 pub use skill::{
     QualityBreakdown, QualityContext, QualityIssue, QualityScore, QualityScorer, QualityWeights,
 };
+pub use staleness::{
+    HelpCache, HelpText, StaleKind, StaleReference, StalenessReport, StalenessVerifier,
+    extract_commands,
+};
@@ -29,14 +29,26 @@ impl QualityScorer {
         let toolchain = if context.toolchain_match { 1.0 } else { 0.4 };
         let freshness = score_freshness(context.modified_at);
 
-        let overall = weighted_average(&[
+        let verification = context.verification_score.map(|v| v.clamp(0.0, 1.0));
+        let outcomes = context.outcome_rate.map(|v| v.clamp(0.0, 1.0));
+
+        let mut factors = vec![
             (structure, self.weights.structure_weight),
             (content, self.weights.content_weight),
             (evidence, self.weights.evidence_weight),
             (usage, self.weights.usage_weight),
             (toolchain, self.weights.toolchain_weight),
             (freshness, self.weights.freshness_weight),
-        ]);
+        ];
+        // Unverified skills are scored as before rather than penalized.
+        if let Some(verification) = verification {
+            factors.push((verification, self.weights.verification_weight));
+        }
+        // Likewise, skills without recorded outcomes are not penalized.
+        if let Some(outcomes) = outcomes {
+            factors.push((outcomes, self.weights.outcome_weight));
+        }
+        let overall = weighted_average(&factors);
 
         let (issues, suggestions) =
             collect_issues(spec, context, structure, content, evidence, usage);
@@ -50,6 +62,8 @@ impl QualityScorer {
                 usage,
                 toolchain,
                 freshness,
+                verification,
+                outcomes,
             },
             issues,
             suggestions,
@@ -69,6 +83,13 @@ pub struct QualityContext {
     pub evidence_count: Option<u64>,
     pub modified_at: Option<DateTime<Utc>>,
     pub toolchain_match: bool,
+    /// Share of command references still valid, from `ms verify`
+    pub verification_score: Option<f32>,
+    /// Number of stale command references found by `ms verify`
+    pub stale_references: u64,
+    /// Confidence-weighted success rate of loads with a manual or
+    /// attributed outcome
+    pub outcome_rate: Option<f32>,
 }
 
 impl Default for QualityContext {
@@ -78,6 +99,9 @@ impl Default for QualityContext {
             evidence_count: None,
             modified_at: None,
             toolchain_match: true,
+            verification_score: None,
+            stale_references: 0,
+            outcome_rate: None,
         }
     }
 }
@@ -90,6 +114,10 @@ pub struct QualityWeights {
     pub usage_weight: f32,
     pub toolchain_weight: f32,
     pub freshness_weight: f32,
+    /// Only applied to skills that have been through `ms verify`
+    pub verification_weight: f32,
+    /// Only applied to skills with recorded outcomes
+    pub outcome_weight: f32,
 }
 
 impl Default for QualityWeights {
@@ -101,6 +129,8 @@ impl Default for QualityWeights {
             usage_weight: 0.20,
             toolchain_weight: 0.10,
             freshness_weight: 0.10,
+            verification_weight: 0.15,
+            outcome_weight: 0.15,
         }
     }
 }
@@ -121,6 +151,8 @@ pub struct QualityBreakdown {
     pub usage: f32,
     pub toolchain: f32,
     pub freshness: f32,
+    pub verification: Option<f32>,
+    pub outcomes: Option<f32>,
 }
 
 #[derive(Debug, Clone)]
@@ -131,6 +163,7 @@ pub enum QualityIssue {
     LowEvidence(u64),
     LowUsage(u64),
     NoTags,
+    StaleReferences(u64),
 }
 
 fn score_structure(spec: &SkillSpec) -> f32 {
@@ -267,6 +300,11 @@ fn collect_issues(
         issues.push(QualityIssue::LowUsage(count));
     }
 
+    if context.stale_references > 0 {
+        issues.push(QualityIssue::StaleReferences(context.stale_references));
+        suggestions.push("Update commands, flags or paths flagged by `ms verify`".to_string());
+    }
+
     if spec.metadata.tags.is_empty() {
         issues.push(QualityIssue::NoTags);
         suggestions.push("Add tags for discoverability".to_string());
@@ -295,6 +333,7 @@ mod tests {
                     id: "b1".to_string(),
                     block_type: BlockType::Text,
                     content: "Short".to_string(),
+                    suppressions: Vec::new(),
                 }],
             }],
             ..Default::default()
@@ -307,4 +346,43 @@ mod tests {
         let score = scorer.score_spec(&minimal_spec(), &QualityContext::default());
         assert!(score.overall >= 0.0 && score.overall <= 1.0);
     }
+
+    #[test]
+    fn stale_verification_lowers_score() {
+        let scorer = QualityScorer::with_defaults();
+        let baseline = scorer.score_spec(&minimal_spec(), &QualityContext::default());
+        assert!(baseline.breakdown.verification.is_none());
+
+        let stale = QualityContext {
+            verification_score: Some(0.0),
+            stale_references: 3,
+            ..QualityContext::default()
+        };
+        let score = scorer.score_spec(&minimal_spec(), &stale);
+        assert!(score.overall < baseline.overall);
+        assert!(
+            score
+                .issues
+                .iter()
+                .any(|issue| matches!(issue, QualityIssue::StaleReferences(3)))
+        );
+    }
+
+    #[test]
+    fn outcome_rate_moves_score() {
+        let scorer = QualityScorer::with_defaults();
+        let spec = minimal_spec();
+        let failing = QualityContext {
+            outcome_rate: Some(0.0),
+            ..QualityContext::default()
+        };
+        let succeeding = QualityContext {
+            outcome_rate: Some(1.0),
+            ..QualityContext::default()
+        };
+        let low = scorer.score_spec(&spec, &failing);
+        let high = scorer.score_spec(&spec, &succeeding);
+        assert!(high.overall > low.overall);
+        assert_eq!(high.breakdown.outcomes, Some(1.0));
+    }
 }
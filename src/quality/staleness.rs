@@ -0,0 +1,969 @@
+//! Staleness verification for command blocks.
+//!
+//! Skills embed shell commands (`BlockType::Command` blocks and shell code
+//! fences) that rot as tools evolve. The verifier extracts those commands and
+//! checks that:
+//!
+//! - the binary still resolves on `PATH`,
+//! - every flag still appears in the tool's `--help` output (captured locally
+//!   and cached per tool version), and
+//! - relative repo paths passed as arguments still exist.
+//!
+//! Flag checks run the named tools, so they are opt-in and limited to
+//! `KNOWN_CLIS`; every probe is also checked by the `SafetyGate` first.
+
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{Arc, LazyLock};
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::config::SafetyConfig;
+use crate::core::{BlockType, SkillSpec};
+use crate::security::{PolicyEngine, SafetyGate};
+
+/// Shell languages whose fenced code blocks are treated as commands.
+const SHELL_LANGUAGES: &[&str] = &["bash", "sh", "shell", "zsh", "console", "shell-session"];
+
+/// Builtins and keywords that never resolve on `PATH`.
+const SHELL_BUILTINS: &[&str] = &[
+    ".", ":", "[", "[[", "alias", "bg", "break", "case", "cd", "continue", "declare", "do", "done",
+    "echo", "elif", "else", "esac", "eval", "exec", "exit", "export", "false", "fg", "fi", "for",
+    "function", "if", "jobs", "local", "popd", "printf", "pushd", "pwd", "read", "readonly",
+    "return", "set", "shift", "source", "test", "then", "trap", "true", "type", "ulimit", "umask",
+    "unalias", "unset", "until", "wait", "while",
+];
+
+/// Wrappers whose first non-option argument is the real command.
+const COMMAND_WRAPPERS: &[&str] = &["sudo", "env", "time", "nohup", "nice", "command", "xargs"];
+
+/// Tools whose `--help`/`--version` output may be probed. Anything else a
+/// skill names is only checked for presence on `PATH`. Interpreters and
+/// package runners are left out: their first word is a script to run.
+pub const KNOWN_CLIS: &[&str] = &[
+    "apt", "apt-get", "aws", "awk", "az", "brew", "bundle", "cargo", "cat", "clang", "cmake",
+    "composer", "cp", "curl", "docker", "dotnet", "fd", "find", "gcc", "gcloud", "gem", "gh",
+    "git", "go", "gradle", "grep", "head", "helm", "jq", "kubectl", "ln", "ls", "make", "mkdir",
+    "ms", "mv", "mvn", "npm", "pip", "pip3", "poetry", "pytest", "rg", "rustc", "rustup", "sed",
+    "sort", "tail", "tar", "tr", "uv", "wc", "wget",
+];
+
+/// How long a single `--help`/`--version` probe may run.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+static HEREDOC: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"<<-?\s*['"]?([A-Za-z_][A-Za-z0-9_]*)['"]?"#).unwrap());
+
+static SUBCOMMAND: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[a-z][a-z0-9-]*$").unwrap());
+
+/// What kind of reference went stale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StaleKind {
+    /// The binary no longer resolves on `PATH`
+    MissingBinary,
+    /// The flag is not mentioned in the tool's `--help` output
+    UnknownFlag,
+    /// A relative repo path passed as an argument does not exist
+    MissingPath,
+}
+
+impl StaleKind {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::MissingBinary => "missing_binary",
+            Self::UnknownFlag => "unknown_flag",
+            Self::MissingPath => "missing_path",
+        }
+    }
+}
+
+/// A single stale reference found in a skill.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StaleReference {
+    pub kind: StaleKind,
+    /// Block the command came from
+    pub block_id: String,
+    /// The command line as written in the skill
+    pub command: String,
+    /// The binary, flag or path that went stale
+    pub target: String,
+}
+
+impl StaleReference {
+    /// Human-readable description of the problem.
+    #[must_use]
+    pub fn message(&self) -> String {
+        match self.kind {
+            StaleKind::MissingBinary => format!("`{}` does not resolve on PATH", self.target),
+            StaleKind::UnknownFlag => {
+                let program = self.command.split_whitespace().next().unwrap_or_default();
+                format!("`{program}` no longer documents `{}`", self.target)
+            }
+            StaleKind::MissingPath => format!("path `{}` does not exist", self.target),
+        }
+    }
+}
+
+/// A shell command extracted from a skill block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandInvocation {
+    pub block_id: String,
+    /// The simple command as written (wrappers and assignments stripped)
+    pub line: String,
+    pub program: String,
+    /// Leading bare word that looks like a subcommand (`git commit`)
+    pub subcommand: Option<String>,
+    /// Flags in `--name` / `-x` form, values stripped
+    pub flags: Vec<String>,
+    /// Positional arguments that are not redirection targets
+    pub args: Vec<String>,
+}
+
+/// Result of verifying one skill.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StalenessReport {
+    pub skill_id: String,
+    pub commands_checked: usize,
+    /// Binaries, flags and paths that were checked
+    pub references_checked: usize,
+    /// Flags that could not be checked (tool not probed or no usable `--help`)
+    pub flags_unverified: usize,
+    pub stale: Vec<StaleReference>,
+}
+
+impl StalenessReport {
+    /// Fraction of checked references that are still valid (1.0 when nothing
+    /// was checked).
+    #[must_use]
+    pub fn score(&self) -> f32 {
+        if self.references_checked == 0 {
+            return 1.0;
+        }
+        let valid = self.references_checked.saturating_sub(self.stale.len());
+        valid as f32 / self.references_checked as f32
+    }
+
+    #[must_use]
+    pub fn is_fresh(&self) -> bool {
+        self.stale.is_empty()
+    }
+
+    /// Stale references of one kind.
+    pub fn of_kind(&self, kind: StaleKind) -> impl Iterator<Item = &StaleReference> {
+        self.stale.iter().filter(move |s| s.kind == kind)
+    }
+}
+
+/// Captures `--help` output per tool version and caches it on disk.
+///
+/// Cache entries live at `<dir>/<program>/<key>.txt`, where the key hashes the
+/// resolved binary path, its `--version` output and the subcommand, so an
+/// upgraded tool is probed again automatically. Only allowlisted tools are
+/// run, and only when the safety gate allows the exact probe command.
+#[derive(Debug)]
+pub struct HelpCache {
+    dir: Option<PathBuf>,
+    refresh: bool,
+    /// Settings the gate is built from for each probe; a `SafetyGate` holds
+    /// a database handle, which would keep lint rules from being `Sync`.
+    safety: SafetyConfig,
+    policy: Option<Arc<PolicyEngine>>,
+    allowlist: Vec<String>,
+    memo: Mutex<HashMap<(String, Option<String>), Option<HelpText>>>,
+}
+
+impl HelpCache {
+    /// Create a cache persisted under `dir`.
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>, safety: SafetyConfig) -> Self {
+        Self {
+            dir: Some(dir.into()),
+            ..Self::in_memory(safety)
+        }
+    }
+
+    /// Create a cache that only memoizes for the lifetime of the process.
+    #[must_use]
+    pub fn in_memory(safety: SafetyConfig) -> Self {
+        Self {
+            dir: None,
+            refresh: false,
+            safety,
+            policy: None,
+            allowlist: KNOWN_CLIS.iter().map(|cli| (*cli).to_string()).collect(),
+            memo: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Also check probes against organisation policy.
+    #[must_use]
+    pub fn with_policy(mut self, policy: Arc<PolicyEngine>) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    /// Replace the tools that may be probed (defaults to `KNOWN_CLIS`).
+    #[must_use]
+    pub fn with_allowlist<I, S>(mut self, allowlist: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.allowlist = allowlist.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Whether `program args...` may be run: the tool is allowlisted and the
+    /// safety gate allows the command.
+    fn may_probe(&self, program: &str, args: &[&str]) -> bool {
+        if !self.allowlist.iter().any(|allowed| allowed == program) {
+            return false;
+        }
+        let command = std::iter::once(program)
+            .chain(args.iter().copied())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let mut gate = SafetyGate::from_config(&self.safety, None);
+        if let Some(policy) = &self.policy {
+            gate = gate.with_policy(policy.clone());
+        }
+        gate.evaluate(&command).decision.allowed
+    }
+
+    /// Ignore existing cache entries and probe tools again.
+    #[must_use]
+    pub const fn with_refresh(mut self, refresh: bool) -> Self {
+        self.refresh = refresh;
+        self
+    }
+
+    /// Help text for `program [subcommand]`, if the tool offers any.
+    pub fn help_for(&self, program: &str, subcommand: Option<&str>) -> Option<HelpText> {
+        let key = (program.to_string(), subcommand.map(str::to_string));
+        if let Some(cached) = self.memo.lock().get(&key) {
+            return cached.clone();
+        }
+        let help = self.load_or_probe(program, subcommand);
+        self.memo.lock().insert(key, help.clone());
+        help
+    }
+
+    fn load_or_probe(&self, program: &str, subcommand: Option<&str>) -> Option<HelpText> {
+        // No bare `-h` fallback: for tools like `shutdown` it is not help.
+        let args: Vec<&str> = subcommand.into_iter().chain(["--help"]).collect();
+        if !self.may_probe(program, &args) {
+            return None;
+        }
+        let resolved = which::which(program).ok()?;
+        let version = if self.may_probe(program, &["--version"]) {
+            probe(&resolved, &["--version"])
+                .and_then(|out| out.lines().next().map(str::to_string))
+                .unwrap_or_default()
+        } else {
+            String::new()
+        };
+
+        self.load_or_probe_args(&resolved, &version, program, subcommand, &args)
+            .map(|text| HelpText { text })
+    }
+
+    fn load_or_probe_args(
+        &self,
+        resolved: &Path,
+        version: &str,
+        program: &str,
+        subcommand: Option<&str>,
+        args: &[&str],
+    ) -> Option<String> {
+        let path = self.dir.as_ref().map(|dir| {
+            let mut hasher = Sha256::new();
+            for part in [
+                resolved.to_string_lossy().as_ref(),
+                version,
+                subcommand.unwrap_or_default(),
+                "--help",
+            ] {
+                hasher.update(part.as_bytes());
+                hasher.update([0]);
+            }
+            let key = hex::encode(&hasher.finalize()[..8]);
+            dir.join(sanitize(program)).join(format!("{key}.txt"))
+        });
+
+        if !self.refresh {
+            if let Some(text) = path.as_ref().and_then(|p| std::fs::read_to_string(p).ok()) {
+                return looks_like_help(&text).then_some(text);
+            }
+        }
+
+        let text = probe(resolved, args).unwrap_or_default();
+        if let Some(path) = &path {
+            if let Some(parent) = path.parent() {
+                let _ = std::fs::create_dir_all(parent);
+            }
+            let _ = std::fs::write(path, &text);
+        }
+        looks_like_help(&text).then_some(text)
+    }
+}
+
+/// Captured `--help` output for a tool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HelpText {
+    pub text: String,
+}
+
+/// Verifies command blocks in skills against the local environment.
+#[derive(Debug, Default)]
+pub struct StalenessVerifier {
+    repo_root: Option<PathBuf>,
+    help: Option<HelpCache>,
+}
+
+impl StalenessVerifier {
+    /// Create a verifier that only checks binaries.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check relative paths against `root`.
+    #[must_use]
+    pub fn with_repo_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.repo_root = Some(root.into());
+        self
+    }
+
+    /// Check flags against `--help` output captured through `cache`. This runs
+    /// allowlisted tools, so callers only enable it on explicit request.
+    #[must_use]
+    pub fn with_help_cache(mut self, cache: HelpCache) -> Self {
+        self.help = Some(cache);
+        self
+    }
+
+    /// Verify every command in `spec`.
+    #[must_use]
+    pub fn verify(&self, spec: &SkillSpec) -> StalenessReport {
+        let mut report = StalenessReport {
+            skill_id: spec.metadata.id.clone(),
+            ..StalenessReport::default()
+        };
+
+        for invocation in extract_commands(spec) {
+            report.commands_checked += 1;
+            let stale = |kind, target: &str| StaleReference {
+                kind,
+                block_id: invocation.block_id.clone(),
+                command: invocation.line.clone(),
+                target: target.to_string(),
+            };
+
+            if invocation.program.contains('/') {
+                // Repo-local script: a path check, never executed.
+                if let Some(root) = &self.repo_root {
+                    report.references_checked += 1;
+                    if !root.join(&invocation.program).exists() {
+                        report
+                            .stale
+                            .push(stale(StaleKind::MissingPath, &invocation.program));
+                    }
+                }
+                continue;
+            }
+
+            report.references_checked += 1;
+            if which::which(&invocation.program).is_err() {
+                report
+                    .stale
+                    .push(stale(StaleKind::MissingBinary, &invocation.program));
+                continue;
+            }
+
+            if let Some(cache) = &self.help {
+                if !invocation.flags.is_empty() {
+                    let top = cache.help_for(&invocation.program, None);
+                    // Only probe `tool sub --help` for subcommands the tool
+                    // documents; otherwise the word may be a script or file
+                    // that `tool` would run.
+                    let help: Vec<HelpText> = match invocation
+                        .subcommand
+                        .as_deref()
+                        .filter(|sub| top.as_ref().is_some_and(|t| help_mentions(&t.text, sub)))
+                    {
+                        // Subcommand flags are not in the top-level help.
+                        Some(sub) => cache
+                            .help_for(&invocation.program, Some(sub))
+                            .map(|sub_help| std::iter::once(sub_help).chain(top).collect())
+                            .unwrap_or_default(),
+                        None => top.into_iter().collect(),
+                    };
+                    for flag in &invocation.flags {
+                        if help.iter().any(|h| help_mentions(&h.text, flag)) {
+                            report.references_checked += 1;
+                        } else if !help.is_empty() {
+                            report.references_checked += 1;
+                            report.stale.push(stale(StaleKind::UnknownFlag, flag));
+                        } else {
+                            report.flags_unverified += 1;
+                        }
+                    }
+                }
+            }
+
+            if let Some(root) = &self.repo_root {
+                for arg in &invocation.args {
+                    if !is_repo_path(root, arg) {
+                        continue;
+                    }
+                    report.references_checked += 1;
+                    if !root.join(arg).exists() {
+                        report.stale.push(stale(StaleKind::MissingPath, arg));
+                    }
+                }
+            }
+        }
+
+        report
+    }
+}
+
+/// Extract the simple commands from command blocks and shell fences.
+#[must_use]
+pub fn extract_commands(spec: &SkillSpec) -> Vec<CommandInvocation> {
+    let mut commands = Vec::new();
+    for section in &spec.sections {
+        for block in &section.blocks {
+            let Some((console, body)) = shell_body(&block.block_type, &block.content) else {
+                continue;
+            };
+            for line in logical_lines(&body, console) {
+                for words in split_commands(&line) {
+                    if let Some(invocation) = parse_invocation(&block.id, &words) {
+                        commands.push(invocation);
+                    }
+                }
+            }
+        }
+    }
+    commands
+}
+
+/// Return the shell body of a block and whether it is a console transcript.
+fn shell_body(block_type: &BlockType, content: &str) -> Option<(bool, String)> {
+    let trimmed = content.trim();
+    if let Some(rest) = trimmed.strip_prefix("```") {
+        let (info, body) = rest.split_once('\n').unwrap_or((rest, ""));
+        let lang = info
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        let shell = SHELL_LANGUAGES.contains(&lang.as_str())
+            || (lang.is_empty() && *block_type == BlockType::Command);
+        if !shell || !matches!(block_type, BlockType::Code | BlockType::Command) {
+            return None;
+        }
+        let body = body.trim_end().strip_suffix("```").unwrap_or(body);
+        let console = matches!(lang.as_str(), "console" | "shell-session");
+        return Some((console, body.to_string()));
+    }
+    (*block_type == BlockType::Command).then(|| (false, trimmed.to_string()))
+}
+
+/// Join continuation lines, drop comments, prompts, output and heredoc bodies.
+fn logical_lines(body: &str, console: bool) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut pending = String::new();
+    let mut heredoc: Option<String> = None;
+
+    for raw in body.lines() {
+        if let Some(end) = &heredoc {
+            if raw.trim() == end {
+                heredoc = None;
+            }
+            continue;
+        }
+
+        let mut line = raw.trim();
+        if pending.is_empty() {
+            if let Some(rest) = line.strip_prefix("$ ") {
+                line = rest;
+            } else if console {
+                // Console transcripts: anything without a prompt is output.
+                continue;
+            }
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+        }
+
+        if let Some(cont) = line.strip_suffix('\\') {
+            pending.push_str(cont);
+            pending.push(' ');
+            continue;
+        }
+        pending.push_str(line);
+        let full = std::mem::take(&mut pending);
+        if let Some(caps) = HEREDOC.captures(&full).filter(|_| !full.contains("<<<")) {
+            heredoc = Some(caps[1].to_string());
+        }
+        lines.push(full);
+    }
+    if !pending.trim().is_empty() {
+        lines.push(pending);
+    }
+    lines
+}
+
+/// Split a line into simple commands (on `|`, `&&`, `||`, `;`, `&`), each a
+/// list of words with quotes removed. Redirection operators are kept as
+/// separate words so their targets can be skipped.
+fn split_commands(line: &str) -> Vec<Vec<String>> {
+    let mut commands = Vec::new();
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut chars = line.chars().peekable();
+    let mut quote: Option<char> = None;
+    let mut quoted = false;
+
+    let flush_word = |word: &mut String, quoted: &mut bool, words: &mut Vec<String>| {
+        if !word.is_empty() || *quoted {
+            words.push(std::mem::take(word));
+        }
+        *quoted = false;
+    };
+
+    while let Some(c) = chars.next() {
+        if let Some(q) = quote {
+            if c == q {
+                quote = None;
+            } else if c == '\\' && q == '"' {
+                if let Some(next) = chars.next() {
+                    word.push(next);
+                }
+            } else {
+                word.push(c);
+            }
+            continue;
+        }
+        match c {
+            '\'' | '"' => {
+                quote = Some(c);
+                quoted = true;
+            }
+            '\\' => {
+                if let Some(next) = chars.next() {
+                    word.push(next);
+                }
+            }
+            '#' if word.is_empty() => break,
+            ' ' | '\t' => flush_word(&mut word, &mut quoted, &mut words),
+            '|' | '&' | ';' => {
+                // `2>&1`, `&>file` and `>&2` are redirections, not separators.
+                if c == '&' && (word.ends_with('>') || chars.peek() == Some(&'>')) {
+                    word.push(c);
+                    continue;
+                }
+                flush_word(&mut word, &mut quoted, &mut words);
+                if chars.peek() == Some(&c) {
+                    chars.next();
+                }
+                if !words.is_empty() {
+                    commands.push(std::mem::take(&mut words));
+                }
+            }
+            '(' | ')' | '{' | '}' if word.is_empty() => {
+                flush_word(&mut word, &mut quoted, &mut words);
+            }
+            _ => word.push(c),
+        }
+    }
+    flush_word(&mut word, &mut quoted, &mut words);
+    if !words.is_empty() {
+        commands.push(words);
+    }
+    commands
+}
+
+fn parse_invocation(block_id: &str, words: &[String]) -> Option<CommandInvocation> {
+    let mut rest = words;
+
+    // Skip assignments and wrappers (with their options).
+    loop {
+        let first = rest.first()?;
+        if is_assignment(first) {
+            rest = &rest[1..];
+        } else if COMMAND_WRAPPERS.contains(&first.as_str()) {
+            rest = &rest[1..];
+            while rest
+                .first()
+                .is_some_and(|w| w.starts_with('-') || is_assignment(w))
+            {
+                rest = &rest[1..];
+            }
+        } else {
+            break;
+        }
+    }
+
+    let program = rest.first()?;
+    if SHELL_BUILTINS.contains(&program.as_str()) || is_placeholder(program) {
+        return None;
+    }
+
+    let mut flags = Vec::new();
+    let mut args = Vec::new();
+    let mut subcommand = None;
+    let mut end_of_options = false;
+    let mut skip_next = false;
+
+    for (idx, word) in rest.iter().enumerate().skip(1) {
+        if skip_next {
+            skip_next = false;
+            continue;
+        }
+        if let Some(inline_target) = redirection_target(word) {
+            // `> file` takes the next word; `>file` and `2>&1` carry it inline.
+            skip_next = !inline_target;
+            continue;
+        }
+        if is_placeholder(word) {
+            continue;
+        }
+        if !end_of_options && word == "--" {
+            end_of_options = true;
+            continue;
+        }
+        if !end_of_options && word.starts_with('-') && word.len() > 1 {
+            if let Some(flag) = normalize_flag(word) {
+                if !flags.contains(&flag) {
+                    flags.push(flag);
+                }
+            }
+            continue;
+        }
+        if idx == 1 && SUBCOMMAND.is_match(word) {
+            subcommand = Some(word.clone());
+            continue;
+        }
+        args.push(word.clone());
+    }
+
+    Some(CommandInvocation {
+        block_id: block_id.to_string(),
+        line: rest.join(" "),
+        program: program.clone(),
+        subcommand,
+        flags,
+        args,
+    })
+}
+
+fn is_assignment(word: &str) -> bool {
+    word.split_once('=').is_some_and(|(name, _)| {
+        !name.is_empty()
+            && !name.starts_with('-')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+/// Template placeholders and shell expansions we cannot reason about.
+fn is_placeholder(word: &str) -> bool {
+    word.contains('$')
+        || word.contains('`')
+        || word.contains("...")
+        || (word.starts_with('<') && word.ends_with('>'))
+        || (word.starts_with('[') && word.ends_with(']'))
+        || (word.starts_with('{') && word.ends_with('}'))
+}
+
+/// For a redirection word, whether its target is inline (`>out`, `2>&1`)
+/// rather than the following word (`>`, `2>>`, `&>`).
+fn redirection_target(word: &str) -> Option<bool> {
+    let op = word.trim_start_matches(char::is_numeric);
+    let op = op
+        .strip_prefix('&')
+        .filter(|o| o.starts_with('>'))
+        .unwrap_or(op);
+    if !(op.starts_with('>') || op.starts_with('<')) {
+        return None;
+    }
+    Some(!op.trim_start_matches(['>', '<']).is_empty())
+}
+
+/// Normalize `--flag=value` to `--flag` and keep single-letter short flags.
+/// Bundled short flags (`-rf`) and single-dash long options (`-name`) are
+/// ambiguous and skipped, as are negative numbers.
+fn normalize_flag(word: &str) -> Option<String> {
+    if let Some(long) = word.strip_prefix("--") {
+        let name = long.split('=').next().unwrap_or_default();
+        let valid = name.len() > 1
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        return valid.then(|| format!("--{name}"));
+    }
+    let short = word.strip_prefix('-')?;
+    let mut chars = short.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_ascii_alphabetic() => Some(format!("-{c}")),
+        _ => None,
+    }
+}
+
+/// Whether help text documents `flag`, including `--[no-]flag` spellings.
+fn help_mentions(help: &str, flag: &str) -> bool {
+    let mentions = |needle: &str| {
+        help.match_indices(needle).any(|(idx, _)| {
+            let before = help[..idx].chars().next_back();
+            let after = help[idx + needle.len()..].chars().next();
+            !before.is_some_and(|c| c.is_ascii_alphanumeric() || c == '-')
+                && !after.is_some_and(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        })
+    };
+    if mentions(flag) {
+        return true;
+    }
+    flag.strip_prefix("--no-").is_some_and(|base| {
+        mentions(&format!("--{base}")) || help.contains(&format!("--[no-]{base}"))
+    }) || flag
+        .strip_prefix("--")
+        .is_some_and(|base| help.contains(&format!("--[no-]{base}")))
+}
+
+/// A relative argument is treated as a repo path when it starts with `./` or
+/// its first component exists under `root` (so `origin/main` or
+/// `@scope/pkg` are not mistaken for paths).
+fn is_repo_path(root: &Path, arg: &str) -> bool {
+    if arg.contains("://")
+        || arg.starts_with('/')
+        || arg.starts_with('~')
+        || arg.starts_with("../")
+        || arg.contains(['*', '?', '{', '}', ':', '='])
+    {
+        return false;
+    }
+    if let Some(rest) = arg.strip_prefix("./") {
+        return !rest.is_empty();
+    }
+    let Some((first, rest)) = arg.split_once('/') else {
+        return false;
+    };
+    !rest.is_empty() && !first.is_empty() && root.join(first).exists()
+}
+
+fn looks_like_help(text: &str) -> bool {
+    text.contains("--") || text.lines().any(|l| l.trim_start().starts_with('-'))
+}
+
+fn sanitize(program: &str) -> String {
+    program
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Run `program args...` with pagers disabled, returning combined output.
+fn probe(program: &Path, args: &[&str]) -> Option<String> {
+    let mut child = Command::new(program)
+        .args(args)
+        .env("PAGER", "cat")
+        .env("GIT_PAGER", "cat")
+        .env("MANPAGER", "cat")
+        .env("NO_COLOR", "1")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .ok()?;
+
+    let mut stdout = child.stdout.take()?;
+    let mut stderr = child.stderr.take()?;
+    let out_handle = std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        stdout.read_to_end(&mut buf).map(|_| buf)
+    });
+    let err_handle = std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        stderr.read_to_end(&mut buf).map(|_| buf)
+    });
+
+    let start = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => break,
+            Ok(None) if start.elapsed() > PROBE_TIMEOUT => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(20)),
+            Err(_) => return None,
+        }
+    }
+
+    let stdout = out_handle.join().ok()?.unwrap_or_default();
+    let stderr = err_handle.join().ok()?.unwrap_or_default();
+    let mut text = String::from_utf8_lossy(&stdout).into_owned();
+    if !stderr.is_empty() {
+        text.push('\n');
+        text.push_str(&String::from_utf8_lossy(&stderr));
+    }
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{SkillBlock, SkillSection};
+    use tempfile::TempDir;
+
+    fn spec_with(block_type: BlockType, content: &str) -> SkillSpec {
+        let mut spec = SkillSpec::new("demo", "Demo");
+        spec.sections.push(SkillSection {
+            id: "usage".to_string(),
+            title: "Usage".to_string(),
+            blocks: vec![SkillBlock {
+                id: "usage-block-1".to_string(),
+                block_type,
+                content: content.to_string(),
+                suppressions: Vec::new(),
+            }],
+        });
+        spec
+    }
+
+    #[test]
+    fn extracts_commands_from_shell_fences() {
+        let spec = spec_with(
+            BlockType::Code,
+            "```bash\n# build it\nRUST_LOG=debug cargo build --release \\\n  -p ms && ls ./target | wc -l\n```",
+        );
+        let commands = extract_commands(&spec);
+        let programs: Vec<&str> = commands.iter().map(|c| c.program.as_str()).collect();
+        assert_eq!(programs, vec!["cargo", "ls", "wc"]);
+        assert_eq!(commands[0].subcommand.as_deref(), Some("build"));
+        assert_eq!(commands[0].flags, vec!["--release", "-p"]);
+        assert_eq!(commands[1].args, vec!["./target"]);
+    }
+
+    #[test]
+    fn ignores_non_shell_fences_and_console_output() {
+        let rust = spec_with(BlockType::Code, "```rust\nfn main() {}\n```");
+        assert!(extract_commands(&rust).is_empty());
+
+        let console = spec_with(
+            BlockType::Code,
+            "```console\n$ git status --short\n M src/lib.rs\n```",
+        );
+        let commands = extract_commands(&console);
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].program, "git");
+    }
+
+    #[test]
+    fn skips_builtins_wrappers_placeholders_and_heredocs() {
+        let spec = spec_with(
+            BlockType::Command,
+            "cd repo\nsudo -E apt-get install <package>\ncat <<EOF > out.txt\nnot-a-command --x\nEOF\n$EDITOR notes.md",
+        );
+        let commands = extract_commands(&spec);
+        let programs: Vec<&str> = commands.iter().map(|c| c.program.as_str()).collect();
+        assert_eq!(programs, vec!["apt-get", "cat"]);
+        assert!(commands[1].args.is_empty(), "redirect target is not an arg");
+    }
+
+    #[test]
+    fn normalizes_flags() {
+        assert_eq!(normalize_flag("--out=dir").as_deref(), Some("--out"));
+        assert_eq!(normalize_flag("-v").as_deref(), Some("-v"));
+        assert_eq!(normalize_flag("-rf"), None);
+        assert_eq!(normalize_flag("-1"), None);
+    }
+
+    #[test]
+    fn help_mentions_respects_word_boundaries() {
+        let help = "  --color <WHEN>\n  --[no-]verify\n  -q, --quiet";
+        assert!(help_mentions(help, "--color"));
+        assert!(help_mentions(help, "-q"));
+        assert!(help_mentions(help, "--no-verify"));
+        assert!(help_mentions(help, "--verify"));
+        assert!(!help_mentions(help, "--col"));
+        assert!(!help_mentions(help, "--colorize"));
+    }
+
+    #[test]
+    fn repo_paths_require_known_first_component() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        assert!(is_repo_path(dir.path(), "src/old.rs"));
+        assert!(is_repo_path(dir.path(), "./scripts/run.sh"));
+        assert!(!is_repo_path(dir.path(), "origin/main"));
+        assert!(!is_repo_path(dir.path(), "https://example.com/x"));
+        assert!(!is_repo_path(dir.path(), "src/*.rs"));
+    }
+
+    #[test]
+    fn verify_reports_missing_binary_and_path() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/lib.rs"), "").unwrap();
+
+        let spec = spec_with(
+            BlockType::Command,
+            "ms-definitely-not-installed --flag\nls src/lib.rs src/gone.rs",
+        );
+        let report = StalenessVerifier::new()
+            .with_repo_root(dir.path())
+            .verify(&spec);
+
+        assert_eq!(report.commands_checked, 2);
+        let kinds: Vec<(StaleKind, &str)> = report
+            .stale
+            .iter()
+            .map(|s| (s.kind, s.target.as_str()))
+            .collect();
+        assert!(kinds.contains(&(StaleKind::MissingBinary, "ms-definitely-not-installed")));
+        assert!(kinds.contains(&(StaleKind::MissingPath, "src/gone.rs")));
+        assert!(!kinds.contains(&(StaleKind::MissingPath, "src/lib.rs")));
+        assert!(report.score() < 1.0);
+    }
+
+    #[test]
+    fn probes_only_allowlisted_commands_the_gate_allows() {
+        let config = crate::config::SafetyConfig {
+            evaluator: crate::core::safety::SafetyEvaluator::Native,
+            ..crate::config::SafetyConfig::default()
+        };
+        let cache = HelpCache::in_memory(config);
+        assert!(cache.may_probe("git", &["status", "--help"]));
+        assert!(!cache.may_probe("shutdown", &["--help"]));
+        assert!(cache.help_for("shutdown", None).is_none());
+        assert!(!cache.may_probe("git", &["push", "--force", "origin", "main"]));
+
+        let cache = cache.with_allowlist(["cargo"]);
+        assert!(!cache.may_probe("git", &["--help"]));
+    }
+
+    #[test]
+    fn empty_report_scores_fresh() {
+        let report = StalenessReport::default();
+        assert!((report.score() - 1.0).abs() < f32::EPSILON);
+        assert!(report.is_fresh());
+    }
+}
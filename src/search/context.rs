@@ -3,8 +3,11 @@
 //! Provides `SearchContext` for personalized ranking and `SearchFilters`
 //! for structured result filtering.
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::storage::sqlite::{SkillRecord, SkillSearchCandidate};
+
 /// Context for personalized search ranking
 #[derive(Debug, Clone, Default)]
 pub struct SearchContext {
@@ -61,10 +64,172 @@ impl std::fmt::Display for SearchLayer {
     }
 }
 
+/// Skill attribute addressable from the query syntax (`tag:rust`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Facet {
+    Tag,
+    Author,
+    Layer,
+    Platform,
+    Requires,
+}
+
+impl Facet {
+    /// Parse a query-syntax key
+    #[must_use]
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key.to_lowercase().as_str() {
+            "tag" | "tags" => Some(Self::Tag),
+            "author" => Some(Self::Author),
+            "layer" => Some(Self::Layer),
+            "platform" | "platforms" => Some(Self::Platform),
+            "requires" | "require" => Some(Self::Requires),
+            _ => None,
+        }
+    }
+
+    /// Query-syntax key
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Tag => "tag",
+            Self::Author => "author",
+            Self::Layer => "layer",
+            Self::Platform => "platform",
+            Self::Requires => "requires",
+        }
+    }
+}
+
+/// A single facet constraint such as `tag:rust` or `-author:bob`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FacetClause {
+    pub facet: Facet,
+    /// Lowercased value (layers are normalized to base/org/project/user)
+    pub value: String,
+    /// Exclude skills that have this value
+    #[serde(default)]
+    pub negated: bool,
+}
+
+impl FacetClause {
+    #[must_use]
+    pub fn new(facet: Facet, value: &str, negated: bool) -> Self {
+        let value = match facet {
+            Facet::Layer => normalize_layer(value),
+            _ => value.trim().to_lowercase(),
+        };
+        Self {
+            facet,
+            value,
+            negated,
+        }
+    }
+}
+
+/// The attributes of a skill that filters are evaluated against
+#[derive(Debug, Clone, Default)]
+pub struct SkillFacets {
+    pub tags: Vec<String>,
+    pub author: Option<String>,
+    pub layer: String,
+    pub platforms: Vec<String>,
+    pub requires: Vec<String>,
+    pub quality: f32,
+    pub deprecated: bool,
+    pub updated: Option<DateTime<Utc>>,
+}
+
+impl SkillFacets {
+    /// Facets of an indexed skill record
+    #[must_use]
+    pub fn from_record(skill: &SkillRecord) -> Self {
+        Self::from_parts(
+            &skill.metadata_json,
+            skill.author.as_deref(),
+            &skill.source_layer,
+            skill.quality_score as f32,
+            skill.is_deprecated,
+            &skill.modified_at,
+        )
+    }
+
+    /// Facets of a search candidate row
+    #[must_use]
+    pub fn from_candidate(candidate: &SkillSearchCandidate) -> Self {
+        Self::from_parts(
+            &candidate.metadata_json,
+            candidate.author.as_deref(),
+            &candidate.source_layer,
+            candidate.quality_score as f32,
+            candidate.is_deprecated,
+            &candidate.modified_at,
+        )
+    }
+
+    fn from_parts(
+        metadata_json: &str,
+        author: Option<&str>,
+        layer: &str,
+        quality: f32,
+        deprecated: bool,
+        modified_at: &str,
+    ) -> Self {
+        let meta = serde_json::from_str::<serde_json::Value>(metadata_json).unwrap_or_default();
+        let list = |key: &str| -> Vec<String> {
+            meta.get(key)
+                .and_then(|v| v.as_array())
+                .map(|values| {
+                    values
+                        .iter()
+                        .filter_map(|v| v.as_str().map(str::to_lowercase))
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        let author = author
+            .or_else(|| meta.get("author").and_then(|v| v.as_str()))
+            .map(str::to_lowercase);
+
+        Self {
+            tags: list("tags"),
+            author,
+            layer: normalize_layer(layer),
+            platforms: list("platforms"),
+            requires: list("requires"),
+            quality,
+            deprecated,
+            updated: DateTime::parse_from_rfc3339(modified_at)
+                .ok()
+                .map(|dt| dt.with_timezone(&Utc)),
+        }
+    }
+
+    fn values(&self, facet: Facet) -> &[String] {
+        match facet {
+            Facet::Tag => &self.tags,
+            Facet::Author => self.author.as_slice(),
+            Facet::Layer => std::slice::from_ref(&self.layer),
+            Facet::Platform => &self.platforms,
+            Facet::Requires => &self.requires,
+        }
+    }
+}
+
+/// Normalize layer aliases to base/org/project/user
+fn normalize_layer(layer: &str) -> String {
+    SearchLayer::from_str(layer.trim()).map_or_else(
+        || layer.trim().to_lowercase(),
+        |layer| layer.as_str().to_string(),
+    )
+}
+
 /// Filters for narrowing search results
 ///
-/// Filters are applied post-fusion (after BM25 + vector RRF) to the
-/// merged result set. They can be combined for fine-grained control.
+/// Search backends push filters down where they can (Tantivy clauses over
+/// fast fields, the vector candidate set) so narrow filters still fill the
+/// result limit; `matches_facets` is the reference semantics.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SearchFilters {
     /// Filter by tags (any-match: result passes if it has ANY of these tags)
@@ -82,6 +247,22 @@ pub struct SearchFilters {
     /// Include deprecated skills (default: false)
     #[serde(default)]
     pub include_deprecated: bool,
+
+    /// Maximum quality score (0.0 - 1.0)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_quality: Option<f32>,
+
+    /// Only skills modified at or after this instant
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_after: Option<DateTime<Utc>>,
+
+    /// Only skills modified before this instant
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_before: Option<DateTime<Utc>>,
+
+    /// Facet clauses from the query syntax (all must hold)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub facets: Vec<FacetClause>,
 }
 
 impl SearchFilters {
@@ -146,16 +327,50 @@ impl SearchFilters {
         self
     }
 
+    /// Builder: add a facet clause
+    #[must_use]
+    pub fn facet(mut self, facet: Facet, value: &str, negated: bool) -> Self {
+        self.facets.push(FacetClause::new(facet, value, negated));
+        self
+    }
+
+    /// Merge another set of filters into this one (clauses accumulate,
+    /// bounds narrow).
+    #[must_use]
+    pub fn merge(mut self, other: Self) -> Self {
+        self.tags.extend(other.tags);
+        self.layer = other.layer.or(self.layer);
+        self.min_quality = max_option(self.min_quality, other.min_quality);
+        self.max_quality = match (self.max_quality, other.max_quality) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        self.updated_after = max_option(self.updated_after, other.updated_after);
+        self.updated_before = match (self.updated_before, other.updated_before) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        self.include_deprecated |= other.include_deprecated;
+        self.facets.extend(other.facets);
+        self
+    }
+
     /// Check if filters are empty (no filtering will occur)
     #[must_use]
     pub fn is_empty(&self) -> bool {
         self.tags.is_empty()
             && self.layer.is_none()
             && self.min_quality.is_none()
+            && self.max_quality.is_none()
+            && self.updated_after.is_none()
+            && self.updated_before.is_none()
+            && self.facets.is_empty()
             && self.include_deprecated // if true, no deprecation filtering; if false, filtering occurs
     }
 
-    /// Check if a skill passes all filters
+    /// Check if a skill passes the tag, layer, quality and deprecation
+    /// filters. Clauses on other facets never match here; use
+    /// `matches_facets` when the full record is available.
     ///
     /// # Arguments
     /// * `skill_tags` - Tags from the skill
@@ -170,42 +385,65 @@ impl SearchFilters {
         skill_quality: f32,
         is_deprecated: bool,
     ) -> bool {
+        self.matches_facets(&SkillFacets {
+            tags: skill_tags.to_vec(),
+            layer: normalize_layer(skill_layer),
+            quality: skill_quality,
+            deprecated: is_deprecated,
+            ..SkillFacets::default()
+        })
+    }
+
+    /// Check if a skill passes all filters
+    #[must_use]
+    pub fn matches_facets(&self, skill: &SkillFacets) -> bool {
         // Check deprecated filter (exclude deprecated by default)
-        if is_deprecated && !self.include_deprecated {
+        if skill.deprecated && !self.include_deprecated {
             return false;
         }
 
         // Check layer filter
         if let Some(ref layer) = self.layer {
-            let layer_lc = skill_layer.to_lowercase();
-            let normalized_layer = match layer_lc.as_str() {
-                "base" | "system" => "base",
-                "org" | "global" => "org",
-                "project" => "project",
-                "user" | "local" => "user",
-                _ => layer_lc.as_str(),
-            };
-            if normalized_layer != layer.as_str() {
+            if skill.layer != layer.as_str() {
                 return false;
             }
         }
 
-        // Check quality filter
-        if let Some(min_q) = self.min_quality {
-            if skill_quality < min_q {
+        // Check quality bounds
+        if self.min_quality.is_some_and(|min_q| skill.quality < min_q)
+            || self.max_quality.is_some_and(|max_q| skill.quality > max_q)
+        {
+            return false;
+        }
+
+        // Check modification time bounds (skills without a timestamp fail)
+        if self.updated_after.is_some() || self.updated_before.is_some() {
+            let Some(updated) = skill.updated else {
+                return false;
+            };
+            if self.updated_after.is_some_and(|after| updated < after)
+                || self.updated_before.is_some_and(|before| updated >= before)
+            {
                 return false;
             }
         }
 
         // Check tags filter (any-match)
         if !self.tags.is_empty() {
-            let has_matching_tag = self.tags.iter().any(|t| skill_tags.contains(t));
+            let has_matching_tag = self.tags.iter().any(|t| skill.tags.contains(t));
             if !has_matching_tag {
                 return false;
             }
         }
 
-        true
+        // Check facet clauses (all must hold)
+        self.facets.iter().all(|clause| {
+            let present = skill
+                .values(clause.facet)
+                .iter()
+                .any(|value| value.eq_ignore_ascii_case(&clause.value));
+            present != clause.negated
+        })
     }
 
     /// Parse tags from comma-separated string
@@ -219,6 +457,13 @@ impl SearchFilters {
     }
 }
 
+fn max_option<T: PartialOrd>(a: Option<T>, b: Option<T>) -> Option<T> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(if b > a { b } else { a }),
+        (a, b) => a.or(b),
+    }
+}
+
 /// Result of applying filters (for audit/debug)
 #[derive(Debug, Clone, Serialize)]
 pub struct FilterResult {
@@ -430,6 +675,95 @@ mod tests {
         assert!(!filters.is_empty());
     }
 
+    #[test]
+    fn test_matches_facet_clauses() {
+        let skill = SkillFacets {
+            tags: vec!["rust".to_string(), "cli".to_string()],
+            author: Some("alice".to_string()),
+            layer: "project".to_string(),
+            platforms: vec!["linux".to_string()],
+            requires: vec!["git".to_string()],
+            quality: 0.8,
+            deprecated: false,
+            updated: Some("2026-02-01T00:00:00Z".parse().unwrap()),
+        };
+
+        let filters = SearchFilters::new()
+            .facet(Facet::Tag, "Rust", false)
+            .facet(Facet::Tag, "legacy", true)
+            .facet(Facet::Author, "alice", false)
+            .facet(Facet::Layer, "local", true)
+            .facet(Facet::Platform, "linux", false)
+            .facet(Facet::Requires, "git", false);
+        assert!(filters.matches_facets(&skill));
+
+        let filters = SearchFilters::new().facet(Facet::Tag, "cli", true);
+        assert!(!filters.matches_facets(&skill));
+
+        let filters = SearchFilters::new().facet(Facet::Author, "bob", false);
+        assert!(!filters.matches_facets(&skill));
+    }
+
+    #[test]
+    fn test_matches_ranges() {
+        let skill = SkillFacets {
+            layer: "project".to_string(),
+            quality: 0.8,
+            updated: Some("2026-02-01T00:00:00Z".parse().unwrap()),
+            ..SkillFacets::default()
+        };
+
+        let mut filters = SearchFilters::new();
+        filters.max_quality = Some(0.9);
+        filters.updated_after = Some("2026-01-01T00:00:00Z".parse().unwrap());
+        assert!(filters.matches_facets(&skill));
+
+        filters.updated_before = Some("2026-02-01T00:00:00Z".parse().unwrap());
+        assert!(!filters.matches_facets(&skill));
+
+        let mut filters = SearchFilters::new();
+        filters.max_quality = Some(0.5);
+        assert!(!filters.matches_facets(&skill));
+
+        let mut filters = SearchFilters::new();
+        filters.updated_after = Some("2026-01-01T00:00:00Z".parse().unwrap());
+        assert!(!filters.matches_facets(&SkillFacets::default()));
+    }
+
+    #[test]
+    fn test_facets_from_record_metadata() {
+        let record = SkillRecord {
+            id: "demo".to_string(),
+            name: "Demo".to_string(),
+            description: String::new(),
+            version: None,
+            author: None,
+            source_path: "/demo".to_string(),
+            source_layer: "global".to_string(),
+            git_remote: None,
+            git_commit: None,
+            content_hash: "hash".to_string(),
+            body: String::new(),
+            metadata_json:
+                r#"{"tags":["Rust"],"platforms":["linux"],"requires":["git"],"author":"Alice"}"#
+                    .to_string(),
+            assets_json: "{}".to_string(),
+            token_count: 0,
+            quality_score: 0.5,
+            indexed_at: String::new(),
+            modified_at: "2026-01-02T03:04:05Z".to_string(),
+            is_deprecated: false,
+            deprecation_reason: None,
+        };
+        let facets = SkillFacets::from_record(&record);
+        assert_eq!(facets.tags, vec!["rust"]);
+        assert_eq!(facets.author.as_deref(), Some("alice"));
+        assert_eq!(facets.layer, "org");
+        assert_eq!(facets.platforms, vec!["linux"]);
+        assert_eq!(facets.requires, vec!["git"]);
+        assert!(facets.updated.is_some());
+    }
+
     #[test]
     fn test_filter_result() {
         let filters = SearchFilters::with_tags(vec!["git".to_string()]);
@@ -6,19 +6,13 @@
 
 use crate::storage::sqlite::SkillRecord;
 
-use super::context::SearchFilters;
+use super::context::{SearchFilters, SkillFacets};
 use super::hybrid::HybridResult;
 
 /// Check if a skill record matches the given filters
 #[must_use]
 pub fn matches_skill_record(filters: &SearchFilters, skill: &SkillRecord) -> bool {
-    let skill_tags = parse_tags_from_metadata(&skill.metadata_json);
-    filters.matches(
-        &skill_tags,
-        &skill.source_layer,
-        skill.quality_score as f32,
-        skill.is_deprecated,
-    )
+    filters.matches_facets(&SkillFacets::from_record(skill))
 }
 
 /// Filter a list of skill IDs based on a lookup function
@@ -57,19 +51,6 @@ pub fn filter_hybrid_results(
         .collect()
 }
 
-/// Parse tags from metadata JSON
-fn parse_tags_from_metadata(metadata_json: &str) -> Vec<String> {
-    if let Ok(meta) = serde_json::from_str::<serde_json::Value>(metadata_json) {
-        if let Some(tags) = meta.get("tags").and_then(|t| t.as_array()) {
-            return tags
-                .iter()
-                .filter_map(|v| v.as_str().map(str::to_lowercase))
-                .collect();
-        }
-    }
-    vec![]
-}
-
 #[cfg(test)]
 mod tests {
     use super::super::context::SearchLayer;
@@ -194,25 +175,40 @@ mod tests {
         assert!(!matches_skill_record(&filters, &low_quality));
     }
 
+    fn tags_of(metadata_json: &str) -> Vec<String> {
+        let mut skill = make_skill("s", "project", 0.5, false, &[]);
+        skill.metadata_json = metadata_json.to_string();
+        SkillFacets::from_record(&skill).tags
+    }
+
     #[test]
     fn test_parse_tags_from_metadata() {
-        let json = r#"{"tags": ["rust", "cli", "search"]}"#;
-        let tags = parse_tags_from_metadata(json);
+        let tags = tags_of(r#"{"tags": ["rust", "cli", "search"]}"#);
         assert_eq!(tags, vec!["rust", "cli", "search"]);
     }
 
     #[test]
     fn test_parse_tags_empty_metadata() {
-        let tags = parse_tags_from_metadata("{}");
+        let tags = tags_of("{}");
         assert!(tags.is_empty());
     }
 
     #[test]
     fn test_parse_tags_invalid_json() {
-        let tags = parse_tags_from_metadata("not json");
+        let tags = tags_of("not json");
         assert!(tags.is_empty());
     }
 
+    #[test]
+    fn test_facet_filters_use_full_record() {
+        let mut skill = make_skill("s", "project", 0.8, false, &["rust"]);
+        skill.author = Some("Alice".to_string());
+        let filters = SearchFilters::new().facet(crate::search::Facet::Author, "alice", false);
+        assert!(matches_skill_record(&filters, &skill));
+        skill.author = None;
+        assert!(!matches_skill_record(&filters, &skill));
+    }
+
     #[test]
     fn test_filter_skill_ids() {
         let skills = vec![
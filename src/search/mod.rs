@@ -25,6 +25,33 @@
 //!                     Combined ranked results
 //! ```
 //!
+//! ## Query syntax
+//!
+//! The `query` module parses `tag:rust -tag:legacy author:alice
+//! updated:>2026-01-01 quality:>0.7 "exact phrase"` into free text and
+//! `SearchFilters`. Filters are pushed down: into Tantivy as term and range
+//! clauses over raw facet fields, and into the vector search candidate set.
+//!
+//! ## Snippets
+//!
+//! The `snippets` module explains each hit: the best-matching section and
+//! block (lexical via Tantivy's snippet generator, semantic via per-block
+//! embeddings) with highlight offsets.
+//!
+//! ## Slice retrieval
+//!
+//! The `slices` module keeps a second index whose documents are individual
+//! skill slices, each with its own BM25 and embedding entries. Slice queries
+//! return the best rules, pitfalls, commands and examples from any skill and
+//! feed `ConstrainedPacker` directly.
+//!
+//! ## Learned re-ranking
+//!
+//! The `rerank` module logs each search's results with a feature snapshot,
+//! trains a linear LambdaRank model from later loads and feedback
+//! (`ms rank train`), and re-orders fused results with the active model
+//! version.
+//!
 //! ## Caching
 //!
 //! The `cache` module provides LRU caching for query results and embeddings
@@ -36,15 +63,27 @@ pub mod embeddings;
 pub mod embeddings_local;
 pub mod filters;
 pub mod hybrid;
+pub mod query;
+pub mod rerank;
+pub mod slices;
+pub mod snippets;
 pub mod tantivy;
 pub mod tantivy_index;
 
 // Re-export main types
 pub use cache::{CacheLayer, CacheStats, CachedQueryResult, SessionFingerprint};
-pub use context::{FilterResult, SearchContext, SearchFilters, SearchLayer};
+pub use context::{
+    Facet, FacetClause, FilterResult, SearchContext, SearchFilters, SearchLayer, SkillFacets,
+};
 pub use embeddings::{ApiEmbedder, Embedder, HashEmbedder, VectorIndex, build_embedder};
 pub use embeddings_local::LocalEmbedder;
 pub use filters::{filter_hybrid_results, filter_skill_ids, matches_skill_record};
 pub use hybrid::{HybridResult, RrfConfig, fuse_results, fuse_simple, fuse_with_limit};
+pub use query::SearchQuery;
+pub use rerank::{
+    RankCandidate, RankFeatures, RankModel, RankModelStore, RankSignals, extract_features,
+};
+pub use slices::{SliceHit, SliceIndex, packable_slices, slice_key};
+pub use snippets::{DEFAULT_SNIPPET_CHARS, Highlight, MatchKind, MatchedSection, SnippetExtractor};
 pub use tantivy::{Bm25Index, Bm25Result};
 pub use tantivy_index::SearchIndex;
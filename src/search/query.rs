@@ -0,0 +1,324 @@
+//! Structured search query syntax
+//!
+//! Splits a query such as
+//! `tag:rust -tag:legacy author:alice updated:>2026-01-01 quality:>0.7 "exact phrase" errors`
+//! into free text (passed to BM25 and the embedder) and `SearchFilters`
+//! (pushed down into the backends). The same syntax is used by `ms search`,
+//! the MCP `search` tool and the TUI.
+//!
+//! | Syntax | Meaning |
+//! |--------|---------|
+//! | `tag:x`, `author:x`, `layer:x`, `platform:x`, `requires:x` | skill must have the value |
+//! | `-tag:x` (any facet) | skill must not have the value |
+//! | `quality:>0.7`, `quality:<=0.9` | quality bounds (inclusive) |
+//! | `updated:>2026-01-01`, `updated:<=2026-02-01`, `updated:2026-01-15` | modification date |
+//! | `"exact phrase"` | phrase query |
+//!
+//! Values may be quoted (`author:"Jane Doe"`). Tokens whose key is not one of
+//! the above (e.g. `std::io`) stay in the free text.
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+
+use super::context::{Facet, FacetClause, SearchFilters};
+use crate::error::{MsError, Result};
+
+/// A parsed search query
+#[derive(Debug, Clone, Default)]
+pub struct SearchQuery {
+    /// Free text including quoted phrases, in query-parser syntax
+    pub text: String,
+    /// Quoted phrases (without quotes)
+    pub phrases: Vec<String>,
+    /// Filters extracted from the query
+    pub filters: SearchFilters,
+}
+
+impl SearchQuery {
+    /// Parse the query syntax. Filters start from `SearchFilters::default()`
+    /// (deprecated skills excluded).
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut query = Self::default();
+        let mut text_parts: Vec<String> = Vec::new();
+
+        for token in tokenize(input)? {
+            match token {
+                Token::Phrase(phrase) => {
+                    text_parts.push(format!("\"{phrase}\""));
+                    query.phrases.push(phrase);
+                }
+                Token::Word(word) => {
+                    if !apply_filter(&mut query.filters, &word)? {
+                        text_parts.push(word);
+                    }
+                }
+            }
+        }
+
+        query.text = text_parts.join(" ");
+        Ok(query)
+    }
+
+    /// Free text with phrase quotes removed (for substring matching and
+    /// embedding)
+    #[must_use]
+    pub fn plain_text(&self) -> String {
+        self.text.replace('"', "")
+    }
+
+    /// True when the query has no free text (filters only)
+    #[must_use]
+    pub fn is_filter_only(&self) -> bool {
+        self.text.trim().is_empty()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Token {
+    Word(String),
+    Phrase(String),
+}
+
+/// Split on whitespace, keeping quoted phrases and quoted filter values
+/// (`author:"Jane Doe"`) together.
+fn tokenize(input: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        if c == '"' {
+            chars.next();
+            let phrase = read_until_quote(&mut chars, input)?;
+            if !phrase.trim().is_empty() {
+                tokens.push(Token::Phrase(phrase));
+            }
+            continue;
+        }
+
+        let mut word = String::new();
+        while let Some(&c) = chars.peek() {
+            if c.is_whitespace() {
+                break;
+            }
+            chars.next();
+            if c == '"' && word.ends_with(':') {
+                word.push_str(&read_until_quote(&mut chars, input)?);
+                break;
+            }
+            word.push(c);
+        }
+        tokens.push(Token::Word(word));
+    }
+
+    Ok(tokens)
+}
+
+fn read_until_quote(
+    chars: &mut std::iter::Peekable<std::str::Chars<'_>>,
+    input: &str,
+) -> Result<String> {
+    let mut out = String::new();
+    for c in chars.by_ref() {
+        if c == '"' {
+            return Ok(out);
+        }
+        out.push(c);
+    }
+    Err(MsError::QueryParse(format!(
+        "unterminated quote in query: {input}"
+    )))
+}
+
+/// Apply a `key:value` token to the filters. Returns false when the token is
+/// not a filter and belongs to the free text.
+fn apply_filter(filters: &mut SearchFilters, word: &str) -> Result<bool> {
+    let (negated, body) = match word.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, word),
+    };
+    let Some((key, value)) = body.split_once(':') else {
+        return Ok(false);
+    };
+    if value.is_empty() {
+        return Ok(false);
+    }
+
+    if let Some(facet) = Facet::from_key(key) {
+        if facet == Facet::Layer && super::context::SearchLayer::from_str(value).is_none() {
+            return Err(MsError::QueryParse(format!(
+                "invalid layer '{value}'. Valid: base, org, project, user"
+            )));
+        }
+        filters.facets.push(FacetClause::new(facet, value, negated));
+        return Ok(true);
+    }
+
+    match key.to_lowercase().as_str() {
+        "quality" => {
+            reject_negation(negated, key)?;
+            let (op, raw) = split_comparator(value);
+            let bound: f32 = raw
+                .parse()
+                .map_err(|_| MsError::QueryParse(format!("invalid quality '{raw}'")))?;
+            let bound = bound.clamp(0.0, 1.0);
+            match op {
+                Comparator::Greater | Comparator::GreaterEq | Comparator::Eq => {
+                    filters.min_quality = Some(bound);
+                }
+                Comparator::Less | Comparator::LessEq => filters.max_quality = Some(bound),
+            }
+            Ok(true)
+        }
+        "updated" => {
+            reject_negation(negated, key)?;
+            let (op, raw) = split_comparator(value);
+            let (start, end) = parse_instant(raw)?;
+            match op {
+                Comparator::Greater => filters.updated_after = Some(end),
+                Comparator::GreaterEq => filters.updated_after = Some(start),
+                Comparator::Less => filters.updated_before = Some(start),
+                Comparator::LessEq => filters.updated_before = Some(end),
+                Comparator::Eq => {
+                    filters.updated_after = Some(start);
+                    filters.updated_before = Some(end);
+                }
+            }
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
+}
+
+fn reject_negation(negated: bool, key: &str) -> Result<()> {
+    if negated {
+        return Err(MsError::QueryParse(format!(
+            "'{key}:' is a range filter and cannot be negated; use < or > instead"
+        )));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Comparator {
+    Greater,
+    GreaterEq,
+    Less,
+    LessEq,
+    Eq,
+}
+
+fn split_comparator(value: &str) -> (Comparator, &str) {
+    if let Some(rest) = value.strip_prefix(">=") {
+        (Comparator::GreaterEq, rest)
+    } else if let Some(rest) = value.strip_prefix("<=") {
+        (Comparator::LessEq, rest)
+    } else if let Some(rest) = value.strip_prefix('>') {
+        (Comparator::Greater, rest)
+    } else if let Some(rest) = value.strip_prefix('<') {
+        (Comparator::Less, rest)
+    } else {
+        (Comparator::Eq, value.strip_prefix('=').unwrap_or(value))
+    }
+}
+
+/// Parse a date (`2026-01-01`, covering the whole UTC day) or an RFC 3339
+/// timestamp (a single instant). Returns the half-open span `[start, end)`.
+fn parse_instant(raw: &str) -> Result<(DateTime<Utc>, DateTime<Utc>)> {
+    if let Ok(date) = NaiveDate::parse_from_str(raw, "%Y-%m-%d") {
+        let start = date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc();
+        return Ok((start, start + Duration::days(1)));
+    }
+    if let Ok(instant) = DateTime::parse_from_rfc3339(raw) {
+        let instant = instant.with_timezone(&Utc);
+        return Ok((instant, instant + Duration::seconds(1)));
+    }
+    Err(MsError::QueryParse(format!(
+        "invalid date '{raw}' (expected YYYY-MM-DD or RFC 3339)"
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::context::SkillFacets;
+
+    #[test]
+    fn parses_full_example() {
+        let query = SearchQuery::parse(
+            r#"tag:rust -tag:legacy author:alice layer:project platform:linux requires:git updated:>2026-01-01 quality:>0.7 "exact phrase" errors"#,
+        )
+        .unwrap();
+
+        assert_eq!(query.text, r#""exact phrase" errors"#);
+        assert_eq!(query.plain_text(), "exact phrase errors");
+        assert_eq!(query.phrases, vec!["exact phrase"]);
+
+        let facets = &query.filters.facets;
+        assert_eq!(facets.len(), 6);
+        assert_eq!(facets[0], FacetClause::new(Facet::Tag, "rust", false));
+        assert_eq!(facets[1], FacetClause::new(Facet::Tag, "legacy", true));
+        assert_eq!(facets[3].value, "project");
+        assert_eq!(query.filters.min_quality, Some(0.7));
+        assert_eq!(
+            query.filters.updated_after,
+            Some("2026-01-02T00:00:00Z".parse().unwrap())
+        );
+        assert!(!query.filters.include_deprecated);
+    }
+
+    #[test]
+    fn date_comparators_cover_whole_days() {
+        let filters = SearchQuery::parse("updated:2026-01-15").unwrap().filters;
+        assert_eq!(
+            filters.updated_after,
+            Some("2026-01-15T00:00:00Z".parse().unwrap())
+        );
+        assert_eq!(
+            filters.updated_before,
+            Some("2026-01-16T00:00:00Z".parse().unwrap())
+        );
+
+        let filters = SearchQuery::parse("updated:<=2026-01-15").unwrap().filters;
+        assert_eq!(
+            filters.updated_before,
+            Some("2026-01-16T00:00:00Z".parse().unwrap())
+        );
+
+        let skill = SkillFacets {
+            layer: "project".to_string(),
+            updated: Some("2026-01-15T12:00:00Z".parse().unwrap()),
+            ..SkillFacets::default()
+        };
+        assert!(filters.matches_facets(&skill));
+        let filters = SearchQuery::parse("updated:<2026-01-15").unwrap().filters;
+        assert!(!filters.matches_facets(&skill));
+    }
+
+    #[test]
+    fn quoted_filter_values_and_unknown_keys() {
+        let query = SearchQuery::parse(r#"author:"Jane Doe" std::io quality:<=0.9"#).unwrap();
+        assert_eq!(query.filters.facets[0].value, "jane doe");
+        assert_eq!(query.text, "std::io");
+        assert_eq!(query.filters.max_quality, Some(0.9));
+    }
+
+    #[test]
+    fn filter_only_query() {
+        let query = SearchQuery::parse("tag:rust layer:local").unwrap();
+        assert!(query.is_filter_only());
+        assert_eq!(query.filters.facets[1].value, "user");
+    }
+
+    #[test]
+    fn rejects_malformed_filters() {
+        assert!(SearchQuery::parse("quality:>high").is_err());
+        assert!(SearchQuery::parse("updated:>yesterday").is_err());
+        assert!(SearchQuery::parse("-quality:>0.5").is_err());
+        assert!(SearchQuery::parse("layer:moon").is_err());
+        assert!(SearchQuery::parse(r#""unterminated"#).is_err());
+    }
+}
@@ -0,0 +1,977 @@
+//! Learned re-ranking from local feedback and usage
+//!
+//! Every search records an impression per shown result together with a
+//! snapshot of its ranking features (`search_impressions`). `ms rank train`
+//! labels those impressions from what happened next and fits a linear
+//! LambdaRank model, which `ms search` applies after RRF fusion.
+//!
+//! ## Labels
+//!
+//! | Label | Meaning |
+//! |-------|---------|
+//! | 2 | loaded within the click window and it worked (success signal or positive feedback) |
+//! | 1 | loaded within the click window |
+//! | 0 | shown but not loaded, or negative feedback after loading |
+//!
+//! ## Model
+//!
+//! Features are standardized with the training mean/scale and scored with a
+//! linear function. Pairwise logistic gradients are weighted by the change in
+//! NDCG from swapping the pair (LambdaRank). Models are versioned JSON files
+//! under `<ms_root>/rank/models/`; one version at a time is active.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::hybrid::HybridResult;
+use crate::error::{MsError, Result};
+use crate::storage::Database;
+use crate::storage::sqlite::{
+    SearchImpressionRecord, SkillFeedbackRecord, SkillRecord, SkillUsageSignal,
+};
+
+/// Feature names, in `RankFeatures::to_array` order
+pub const RANK_FEATURES: [&str; 11] = [
+    "bm25",
+    "semantic",
+    "rrf",
+    "quality",
+    "recency",
+    "context",
+    "favorite",
+    "hidden",
+    "click_through",
+    "feedback",
+    "success_rate",
+];
+
+/// Days for the recency feature to decay to 1/e
+const RECENCY_DAYS: f32 = 90.0;
+
+/// NDCG cutoff used for training gradients and metrics
+const NDCG_K: usize = 10;
+
+/// Ranking features for one (query, skill) pair, each roughly in [0, 1]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RankFeatures {
+    /// BM25 score relative to the best BM25 score for the query
+    pub bm25: f32,
+    /// Cosine similarity of query and skill embeddings
+    pub semantic: f32,
+    /// Fused (RRF) score relative to the best fused score for the query
+    pub rrf: f32,
+    /// Skill quality score
+    pub quality: f32,
+    /// exp(-age / 90 days) since the skill was last modified
+    pub recency: f32,
+    /// Relevance to the working context (project type, files, tools)
+    pub context: f32,
+    /// 1 when the skill is a favorite
+    pub favorite: f32,
+    /// 1 when the skill is hidden
+    pub hidden: f32,
+    /// Smoothed share of past impressions that led to a load
+    pub click_through: f32,
+    /// Mean explicit feedback mapped to [0, 1] (0.5 = neutral)
+    pub feedback: f32,
+    /// Smoothed share of loads with a success signal
+    pub success_rate: f32,
+}
+
+impl RankFeatures {
+    /// Feature values in `RANK_FEATURES` order
+    #[must_use]
+    pub const fn to_array(&self) -> [f32; RANK_FEATURES.len()] {
+        [
+            self.bm25,
+            self.semantic,
+            self.rrf,
+            self.quality,
+            self.recency,
+            self.context,
+            self.favorite,
+            self.hidden,
+            self.click_through,
+            self.feedback,
+            self.success_rate,
+        ]
+    }
+
+    /// Value of a feature by name
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<f32> {
+        RANK_FEATURES
+            .iter()
+            .position(|feature| *feature == name)
+            .map(|idx| self.to_array()[idx])
+    }
+}
+
+/// A fused search result to be re-ranked
+#[derive(Debug, Clone, Copy)]
+pub struct RankCandidate<'a> {
+    pub result: &'a HybridResult,
+    pub skill: &'a SkillRecord,
+    /// Context relevance score (0.0 when unknown)
+    pub context: f32,
+}
+
+/// Per-skill behavioural signals, aggregated from the database
+#[derive(Debug, Clone, Default)]
+pub struct RankSignals {
+    pub favorites: HashSet<String>,
+    pub hidden: HashSet<String>,
+    /// (impressions, clicks) per skill
+    pub clicks: HashMap<String, (u64, u64)>,
+    /// (sum, count) of feedback scores in [-1, 1] per skill
+    pub feedback: HashMap<String, (f32, u32)>,
+    /// (successes, loads with a success signal) per skill
+    pub outcomes: HashMap<String, (u32, u32)>,
+}
+
+impl RankSignals {
+    /// Aggregate signals from the database
+    pub fn load(db: &Database, click_window: Duration) -> Result<Self> {
+        let mut signals = Self {
+            clicks: db.search_click_stats(click_window.num_minutes())?,
+            ..Self::default()
+        };
+        for (kind, set) in [
+            ("favorite", &mut signals.favorites),
+            ("hidden", &mut signals.hidden),
+        ] {
+            set.extend(
+                db.list_user_preferences(kind, usize::MAX >> 1, 0)?
+                    .into_iter()
+                    .map(|pref| pref.skill_id),
+            );
+        }
+        for record in db.list_skill_feedback(None, usize::MAX >> 1, 0)? {
+            if let Some(score) = feedback_score(&record) {
+                let entry = signals.feedback.entry(record.skill_id).or_default();
+                entry.0 += score;
+                entry.1 += 1;
+            }
+        }
+        for usage in db.list_skill_usage_signals()? {
+            if let Some(success) = usage.success {
+                let entry = signals.outcomes.entry(usage.skill_id).or_default();
+                entry.0 += u32::from(success);
+                entry.1 += 1;
+            }
+        }
+        Ok(signals)
+    }
+
+    fn click_through(&self, skill_id: &str) -> f32 {
+        let (shown, clicked) = self.clicks.get(skill_id).copied().unwrap_or_default();
+        (clicked as f32 + 1.0) / (shown as f32 + 2.0)
+    }
+
+    fn feedback(&self, skill_id: &str) -> f32 {
+        self.feedback
+            .get(skill_id)
+            .filter(|(_, count)| *count > 0)
+            .map_or(0.5, |(sum, count)| (sum / *count as f32).mul_add(0.5, 0.5))
+    }
+
+    fn success_rate(&self, skill_id: &str) -> f32 {
+        let (successes, total) = self.outcomes.get(skill_id).copied().unwrap_or_default();
+        (successes as f32 + 1.0) / (total as f32 + 2.0)
+    }
+}
+
+/// Map a feedback record to [-1, 1], scaled by the confidence of
+/// attributed outcomes
+fn feedback_score(record: &SkillFeedbackRecord) -> Option<f32> {
+    let score = match record.feedback_type.as_str() {
+        "positive" => Some(1.0),
+        "negative" => Some(-1.0),
+        "rating" => record
+            .rating
+            .map(|stars| ((stars.clamp(1, 5) - 3) as f32) / 2.0),
+        _ => None,
+    }?;
+    Some(score * record.confidence.unwrap_or(1.0).clamp(0.0, 1.0))
+}
+
+/// Compute features for fused candidates of one query
+#[must_use]
+pub fn extract_features(
+    candidates: &[RankCandidate<'_>],
+    signals: &RankSignals,
+    now: DateTime<Utc>,
+) -> Vec<RankFeatures> {
+    let max_bm25 = candidates
+        .iter()
+        .filter_map(|c| c.result.bm25_score)
+        .fold(0.0_f32, f32::max);
+    let max_fused = candidates
+        .iter()
+        .map(|c| c.result.score)
+        .fold(0.0_f32, f32::max);
+
+    candidates
+        .iter()
+        .map(|candidate| {
+            let id = candidate.skill.id.as_str();
+            let age_days = DateTime::parse_from_rfc3339(&candidate.skill.modified_at)
+                .map(|modified| (now - modified.with_timezone(&Utc)).num_hours() as f32 / 24.0)
+                .unwrap_or(RECENCY_DAYS * 4.0)
+                .max(0.0);
+            RankFeatures {
+                bm25: ratio(candidate.result.bm25_score.unwrap_or(0.0), max_bm25),
+                semantic: candidate
+                    .result
+                    .semantic_score
+                    .unwrap_or(0.0)
+                    .clamp(0.0, 1.0),
+                rrf: ratio(candidate.result.score, max_fused),
+                quality: (candidate.skill.quality_score as f32).clamp(0.0, 1.0),
+                recency: (-age_days / RECENCY_DAYS).exp(),
+                context: candidate.context.clamp(0.0, 1.0),
+                favorite: f32::from(u8::from(signals.favorites.contains(id))),
+                hidden: f32::from(u8::from(signals.hidden.contains(id))),
+                click_through: signals.click_through(id),
+                feedback: signals.feedback(id),
+                success_rate: signals.success_rate(id),
+            }
+        })
+        .collect()
+}
+
+fn ratio(value: f32, max: f32) -> f32 {
+    if max > 0.0 {
+        (value / max).clamp(0.0, 1.0)
+    } else {
+        0.0
+    }
+}
+
+// =============================================================================
+// Training data
+// =============================================================================
+
+/// Labeled impressions of one search
+#[derive(Debug, Clone, Default)]
+pub struct TrainingQuery {
+    pub search_id: String,
+    pub features: Vec<RankFeatures>,
+    pub labels: Vec<u8>,
+}
+
+impl TrainingQuery {
+    /// Pairs with differing labels carry training signal
+    #[must_use]
+    pub fn is_informative(&self) -> bool {
+        self.labels.iter().any(|&l| l > 0) && self.labels.contains(&0)
+    }
+}
+
+/// Windows used to attribute later events to an impression
+#[derive(Debug, Clone, Copy)]
+pub struct LabelWindows {
+    /// A load within this window after an impression counts as a click
+    pub click: Duration,
+    /// Feedback within this window after an impression refines the label
+    pub feedback: Duration,
+}
+
+impl Default for LabelWindows {
+    fn default() -> Self {
+        Self {
+            click: Duration::minutes(30),
+            feedback: Duration::days(7),
+        }
+    }
+}
+
+/// Group impressions by search and label them from later loads and feedback
+#[must_use]
+pub fn label_impressions(
+    impressions: &[SearchImpressionRecord],
+    usage: &[SkillUsageSignal],
+    feedback: &[SkillFeedbackRecord],
+    windows: LabelWindows,
+) -> Vec<TrainingQuery> {
+    let mut loads: HashMap<&str, Vec<LoadEvent>> = HashMap::new();
+    for signal in usage {
+        if let Some(at) = parse_time(&signal.used_at) {
+            loads
+                .entry(signal.skill_id.as_str())
+                .or_default()
+                .push((at, signal.success));
+        }
+    }
+    let mut scores: HashMap<&str, Vec<(DateTime<Utc>, f32)>> = HashMap::new();
+    for record in feedback {
+        if let (Some(at), Some(score)) = (parse_time(&record.created_at), feedback_score(record)) {
+            scores
+                .entry(record.skill_id.as_str())
+                .or_default()
+                .push((at, score));
+        }
+    }
+
+    let mut order: Vec<&str> = Vec::new();
+    let mut groups: HashMap<&str, TrainingQuery> = HashMap::new();
+    for impression in impressions {
+        let (Some(shown_at), Ok(features)) = (
+            parse_time(&impression.created_at),
+            serde_json::from_str::<RankFeatures>(&impression.features_json),
+        ) else {
+            continue;
+        };
+
+        let skill = impression.skill_id.as_str();
+        let click = loads.get(skill).and_then(|events| {
+            events
+                .iter()
+                .find(|(at, _)| *at >= shown_at && *at <= shown_at + windows.click)
+        });
+        let opinion: f32 = scores.get(skill).map_or(0.0, |events| {
+            events
+                .iter()
+                .filter(|(at, _)| *at >= shown_at && *at <= shown_at + windows.feedback)
+                .map(|(_, score)| score)
+                .sum()
+        });
+        let label = match click {
+            None => 0,
+            Some(_) if opinion < 0.0 => 0,
+            Some((_, Some(true))) => 2,
+            Some(_) if opinion > 0.0 => 2,
+            Some(_) => 1,
+        };
+
+        let group = groups
+            .entry(impression.search_id.as_str())
+            .or_insert_with(|| {
+                order.push(impression.search_id.as_str());
+                TrainingQuery {
+                    search_id: impression.search_id.clone(),
+                    ..TrainingQuery::default()
+                }
+            });
+        group.features.push(features);
+        group.labels.push(label);
+    }
+
+    order
+        .into_iter()
+        .filter_map(|id| groups.remove(id))
+        .collect()
+}
+
+/// (loaded at, success signal)
+type LoadEvent = (DateTime<Utc>, Option<bool>);
+
+fn parse_time(raw: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(raw)
+        .ok()
+        .map(|at| at.with_timezone(&Utc))
+}
+
+// =============================================================================
+// Model
+// =============================================================================
+
+/// Training hyper-parameters
+#[derive(Debug, Clone)]
+pub struct TrainConfig {
+    pub epochs: usize,
+    pub learning_rate: f32,
+    /// L2 regularization strength
+    pub l2: f32,
+    /// Share of (most recent) queries held out for evaluation
+    pub holdout: f32,
+    /// Minimum informative queries required to train
+    pub min_queries: usize,
+}
+
+impl Default for TrainConfig {
+    fn default() -> Self {
+        Self {
+            epochs: 50,
+            learning_rate: 0.05,
+            l2: 0.001,
+            holdout: 0.2,
+            min_queries: 10,
+        }
+    }
+}
+
+/// Evaluation of a trained model
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TrainingMetrics {
+    /// Informative queries used for training
+    pub queries: usize,
+    /// Queries held out for evaluation
+    pub holdout_queries: usize,
+    /// Labeled impressions in all informative queries
+    pub impressions: usize,
+    /// Preference pairs seen per epoch
+    pub pairs: usize,
+    /// NDCG@10 of the RRF order on the holdout set
+    pub baseline_ndcg: f32,
+    /// NDCG@10 of the model order on the holdout set
+    pub model_ndcg: f32,
+}
+
+impl TrainingMetrics {
+    /// True when the model ranks the holdout set at least as well as RRF
+    #[must_use]
+    pub fn improves(&self) -> bool {
+        self.model_ndcg >= self.baseline_ndcg
+    }
+}
+
+/// A versioned linear ranking model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RankModel {
+    /// Version assigned by `RankModelStore::save` (0 = unsaved)
+    pub version: u32,
+    pub algorithm: String,
+    pub trained_at: String,
+    /// Feature names the weights apply to
+    pub features: Vec<String>,
+    pub weights: Vec<f32>,
+    pub means: Vec<f32>,
+    pub scales: Vec<f32>,
+    pub metrics: TrainingMetrics,
+}
+
+/// Contribution of one feature to a model score
+#[derive(Debug, Clone, Serialize)]
+pub struct FeatureContribution {
+    pub feature: String,
+    pub value: f32,
+    pub contribution: f32,
+}
+
+impl RankModel {
+    /// Model score for a candidate
+    #[must_use]
+    pub fn score(&self, features: &RankFeatures) -> f32 {
+        self.contributions(features).map(|(_, _, c)| c).sum()
+    }
+
+    /// Per-feature contributions, largest magnitude first
+    #[must_use]
+    pub fn explain(&self, features: &RankFeatures) -> Vec<FeatureContribution> {
+        let mut out: Vec<FeatureContribution> = self
+            .contributions(features)
+            .map(|(feature, value, contribution)| FeatureContribution {
+                feature: feature.to_string(),
+                value,
+                contribution,
+            })
+            .collect();
+        out.sort_by(|a, b| {
+            b.contribution
+                .abs()
+                .partial_cmp(&a.contribution.abs())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        out
+    }
+
+    /// Features are matched by name so models survive feature additions.
+    fn contributions<'a>(
+        &'a self,
+        features: &'a RankFeatures,
+    ) -> impl Iterator<Item = (&'a str, f32, f32)> + 'a {
+        self.features.iter().enumerate().map(move |(idx, name)| {
+            let value = features.get(name).unwrap_or(0.0);
+            let weight = self.weights.get(idx).copied().unwrap_or(0.0);
+            let mean = self.means.get(idx).copied().unwrap_or(0.0);
+            let scale = self.scales.get(idx).copied().unwrap_or(1.0);
+            (name.as_str(), value, weight * (value - mean) / scale)
+        })
+    }
+
+    /// Indices of `features` in model order (stable for ties)
+    #[must_use]
+    pub fn rank(&self, features: &[RankFeatures]) -> Vec<usize> {
+        let scores: Vec<f32> = features.iter().map(|f| self.score(f)).collect();
+        order_by(&scores)
+    }
+}
+
+/// Train a linear LambdaRank model on labeled queries
+pub fn train(queries: &[TrainingQuery], config: &TrainConfig) -> Result<RankModel> {
+    let informative: Vec<&TrainingQuery> = queries.iter().filter(|q| q.is_informative()).collect();
+    if informative.len() < config.min_queries.max(2) {
+        return Err(MsError::ValidationFailed(format!(
+            "not enough feedback to train: {} searches with a loaded result (need {})",
+            informative.len(),
+            config.min_queries.max(2)
+        )));
+    }
+
+    // Most recent queries are held out (impressions are listed oldest first)
+    let holdout_len = ((informative.len() as f32 * config.holdout).round() as usize)
+        .clamp(1, informative.len() - 1);
+    let (train_set, holdout_set) = informative.split_at(informative.len() - holdout_len);
+
+    let dims = RANK_FEATURES.len();
+    let rows: Vec<[f32; RANK_FEATURES.len()]> = train_set
+        .iter()
+        .flat_map(|q| q.features.iter().map(RankFeatures::to_array))
+        .collect();
+    let mut means = vec![0.0_f32; dims];
+    let mut scales = vec![1.0_f32; dims];
+    for d in 0..dims {
+        let n = rows.len() as f32;
+        let mean = rows.iter().map(|r| r[d]).sum::<f32>() / n;
+        let var = rows.iter().map(|r| (r[d] - mean).powi(2)).sum::<f32>() / n;
+        means[d] = mean;
+        scales[d] = if var > 1e-6 { var.sqrt() } else { 1.0 };
+    }
+
+    let standardize = |features: &RankFeatures| -> Vec<f32> {
+        features
+            .to_array()
+            .iter()
+            .enumerate()
+            .map(|(d, v)| (v - means[d]) / scales[d])
+            .collect()
+    };
+    let prepared: Vec<(Vec<Vec<f32>>, &[u8])> = train_set
+        .iter()
+        .map(|q| {
+            (
+                q.features.iter().map(standardize).collect(),
+                q.labels.as_slice(),
+            )
+        })
+        .collect();
+
+    // Start from the RRF order so the model only learns corrections to it
+    let mut weights = vec![0.0_f32; dims];
+    if let Some(rrf) = RANK_FEATURES.iter().position(|f| *f == "rrf") {
+        weights[rrf] = 1.0;
+    }
+
+    let mut pairs = 0;
+    for _ in 0..config.epochs {
+        pairs = 0;
+        let mut gradient = vec![0.0_f32; dims];
+        for (rows, labels) in &prepared {
+            let scores: Vec<f32> = rows.iter().map(|x| dot(&weights, x)).collect();
+            let order = order_by(&scores);
+            let mut rank_of = vec![0; order.len()];
+            for (rank, &idx) in order.iter().enumerate() {
+                rank_of[idx] = rank;
+            }
+            let ideal = ideal_dcg(labels, NDCG_K);
+            if ideal <= 0.0 {
+                continue;
+            }
+            for i in 0..labels.len() {
+                for j in 0..labels.len() {
+                    if labels[i] <= labels[j] {
+                        continue;
+                    }
+                    pairs += 1;
+                    let delta = (gain(labels[i]) - gain(labels[j]))
+                        * (discount(rank_of[i]) - discount(rank_of[j]));
+                    let lambda = (delta.abs() / ideal) / (1.0 + (scores[i] - scores[j]).exp());
+                    for d in 0..dims {
+                        gradient[d] += lambda * (rows[i][d] - rows[j][d]);
+                    }
+                }
+            }
+        }
+        let norm = prepared.len().max(1) as f32;
+        for d in 0..dims {
+            weights[d] += config.learning_rate * (gradient[d] / norm - config.l2 * weights[d]);
+        }
+    }
+
+    let mut model = RankModel {
+        version: 0,
+        algorithm: "linear-lambdarank".to_string(),
+        trained_at: Utc::now().to_rfc3339(),
+        features: RANK_FEATURES.iter().map(ToString::to_string).collect(),
+        weights,
+        means,
+        scales,
+        metrics: TrainingMetrics::default(),
+    };
+
+    let (mut baseline, mut learned) = (0.0, 0.0);
+    for query in holdout_set {
+        let rrf: Vec<f32> = query.features.iter().map(|f| f.rrf).collect();
+        baseline += ndcg_at(&order_by(&rrf), &query.labels, NDCG_K);
+        learned += ndcg_at(&model.rank(&query.features), &query.labels, NDCG_K);
+    }
+    model.metrics = TrainingMetrics {
+        queries: train_set.len(),
+        holdout_queries: holdout_set.len(),
+        impressions: informative.iter().map(|q| q.labels.len()).sum(),
+        pairs,
+        baseline_ndcg: baseline / holdout_set.len() as f32,
+        model_ndcg: learned / holdout_set.len() as f32,
+    };
+    Ok(model)
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// Indices sorted by descending score, ties kept in input order
+fn order_by(scores: &[f32]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..scores.len()).collect();
+    order.sort_by(|&a, &b| {
+        scores[b]
+            .partial_cmp(&scores[a])
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    order
+}
+
+fn gain(label: u8) -> f32 {
+    f32::from(label).exp2() - 1.0
+}
+
+fn discount(rank: usize) -> f32 {
+    if rank < NDCG_K {
+        1.0 / (rank as f32 + 2.0).log2()
+    } else {
+        0.0
+    }
+}
+
+fn ideal_dcg(labels: &[u8], k: usize) -> f32 {
+    let mut sorted = labels.to_vec();
+    sorted.sort_unstable_by(|a, b| b.cmp(a));
+    sorted
+        .iter()
+        .take(k)
+        .enumerate()
+        .map(|(rank, &label)| gain(label) / (rank as f32 + 2.0).log2())
+        .sum()
+}
+
+/// NDCG@k of a ranking (indices into `labels`)
+#[must_use]
+pub fn ndcg_at(order: &[usize], labels: &[u8], k: usize) -> f32 {
+    let ideal = ideal_dcg(labels, k);
+    if ideal <= 0.0 {
+        return 0.0;
+    }
+    let dcg: f32 = order
+        .iter()
+        .take(k)
+        .enumerate()
+        .map(|(rank, &idx)| gain(labels[idx]) / (rank as f32 + 2.0).log2())
+        .sum();
+    dcg / ideal
+}
+
+// =============================================================================
+// Model store
+// =============================================================================
+
+/// Versioned model files with an active-version pointer
+#[derive(Debug, Clone)]
+pub struct RankModelStore {
+    root: PathBuf,
+}
+
+impl RankModelStore {
+    /// Store rooted at `root` (usually `<ms_root>/rank`)
+    #[must_use]
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn models_dir(&self) -> PathBuf {
+        self.root.join("models")
+    }
+
+    fn model_path(&self, version: u32) -> PathBuf {
+        self.models_dir().join(format!("v{version:04}.json"))
+    }
+
+    fn active_path(&self) -> PathBuf {
+        self.root.join("ACTIVE")
+    }
+
+    /// Save a model under the next version number and return it
+    pub fn save(&self, model: &mut RankModel) -> Result<u32> {
+        std::fs::create_dir_all(self.models_dir()).map_err(MsError::Io)?;
+        model.version = self.list()?.last().map_or(1, |m| m.version + 1);
+        let json = serde_json::to_string_pretty(model)?;
+        write_atomic(&self.model_path(model.version), &json)?;
+        Ok(model.version)
+    }
+
+    /// Load a specific version
+    pub fn load(&self, version: u32) -> Result<RankModel> {
+        let path = self.model_path(version);
+        if !path.exists() {
+            return Err(MsError::NotFound(format!("rank model v{version}")));
+        }
+        let contents = std::fs::read_to_string(&path).map_err(MsError::Io)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// All saved models, oldest first
+    pub fn list(&self) -> Result<Vec<RankModel>> {
+        let dir = self.models_dir();
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut models = Vec::new();
+        for entry in std::fs::read_dir(&dir).map_err(MsError::Io)? {
+            let path = entry.map_err(MsError::Io)?.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                let contents = std::fs::read_to_string(&path).map_err(MsError::Io)?;
+                models.push(serde_json::from_str::<RankModel>(&contents)?);
+            }
+        }
+        models.sort_by_key(|m| m.version);
+        Ok(models)
+    }
+
+    /// Active version, if any
+    pub fn active_version(&self) -> Result<Option<u32>> {
+        let path = self.active_path();
+        if !path.exists() {
+            return Ok(None);
+        }
+        let raw = std::fs::read_to_string(&path).map_err(MsError::Io)?;
+        raw.trim()
+            .parse()
+            .map(Some)
+            .map_err(|_| MsError::Config(format!("invalid rank model pointer: {raw}")))
+    }
+
+    /// The active model, if any
+    pub fn active(&self) -> Result<Option<RankModel>> {
+        self.active_version()?
+            .map(|version| self.load(version))
+            .transpose()
+    }
+
+    /// Make `version` the active model
+    pub fn activate(&self, version: u32) -> Result<()> {
+        self.load(version)?;
+        write_atomic(&self.active_path(), &version.to_string())
+    }
+
+    /// Stop applying a learned model (search falls back to RRF)
+    pub fn deactivate(&self) -> Result<bool> {
+        let path = self.active_path();
+        if !path.exists() {
+            return Ok(false);
+        }
+        std::fs::remove_file(path).map_err(MsError::Io)?;
+        Ok(true)
+    }
+}
+
+fn write_atomic(path: &Path, contents: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(MsError::Io)?;
+    }
+    let temp_path = path.with_extension("tmp");
+    std::fs::write(&temp_path, contents).map_err(MsError::Io)?;
+    std::fs::rename(&temp_path, path).map_err(MsError::Io)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn features(rrf: f32, click_through: f32) -> RankFeatures {
+        RankFeatures {
+            rrf,
+            click_through,
+            ..RankFeatures::default()
+        }
+    }
+
+    /// RRF puts an unused skill first; users always load the second one.
+    fn clicked_second_queries(n: usize) -> Vec<TrainingQuery> {
+        (0..n)
+            .map(|i| TrainingQuery {
+                search_id: format!("s{i}"),
+                features: vec![features(1.0, 0.1), features(0.8, 0.9), features(0.5, 0.2)],
+                labels: vec![0, 2, 0],
+            })
+            .collect()
+    }
+
+    #[test]
+    fn ndcg_prefers_relevant_first() {
+        let labels = [0, 2, 1];
+        assert!((ndcg_at(&[1, 2, 0], &labels, 10) - 1.0).abs() < 1e-6);
+        assert!(ndcg_at(&[0, 2, 1], &labels, 10) < ndcg_at(&[1, 0, 2], &labels, 10));
+        assert_eq!(ndcg_at(&[0, 1], &[0, 0], 10), 0.0);
+    }
+
+    #[test]
+    fn train_learns_click_through_over_rrf() {
+        let queries = clicked_second_queries(20);
+        let model = train(&queries, &TrainConfig::default()).unwrap();
+
+        assert_eq!(model.rank(&queries[0].features)[0], 1);
+        assert!(model.metrics.improves());
+        assert!(model.metrics.model_ndcg > model.metrics.baseline_ndcg);
+        assert_eq!(model.metrics.holdout_queries, 4);
+
+        let explanation = model.explain(&queries[0].features[1]);
+        assert_eq!(explanation.len(), RANK_FEATURES.len());
+        let total: f32 = explanation.iter().map(|c| c.contribution).sum();
+        assert!((total - model.score(&queries[0].features[1])).abs() < 1e-4);
+    }
+
+    #[test]
+    fn train_requires_informative_queries() {
+        let mut queries = clicked_second_queries(3);
+        queries[0].labels = vec![0, 0, 0];
+        let err = train(&queries, &TrainConfig::default()).unwrap_err();
+        assert!(err.to_string().contains("not enough feedback"));
+    }
+
+    #[test]
+    fn labels_follow_loads_and_feedback() {
+        let shown = "2026-03-01T10:00:00+00:00";
+        let impression = |skill: &str, position| SearchImpressionRecord {
+            id: format!("{skill}-{position}"),
+            search_id: "search-1".to_string(),
+            query: "errors".to_string(),
+            skill_id: skill.to_string(),
+            position,
+            features_json: serde_json::to_string(&features(0.5, 0.5)).unwrap(),
+            model_version: None,
+            created_at: shown.to_string(),
+        };
+        let impressions = vec![
+            impression("ignored", 0),
+            impression("loaded", 1),
+            impression("worked", 2),
+            impression("disliked", 3),
+            impression("late", 4),
+        ];
+        let load = |skill: &str, at: &str, success| SkillUsageSignal {
+            skill_id: skill.to_string(),
+            used_at: at.to_string(),
+            success,
+        };
+        let usage = vec![
+            load("loaded", "2026-03-01T10:05:00+00:00", None),
+            load("worked", "2026-03-01T10:01:00+00:00", Some(true)),
+            load("disliked", "2026-03-01T10:02:00+00:00", None),
+            load("late", "2026-03-01T12:00:00+00:00", Some(true)),
+        ];
+        let feedback = vec![SkillFeedbackRecord {
+            id: "f1".to_string(),
+            skill_id: "disliked".to_string(),
+            feedback_type: "negative".to_string(),
+            rating: None,
+            comment: None,
+            source: "manual".to_string(),
+            confidence: None,
+            created_at: "2026-03-01T11:00:00+00:00".to_string(),
+        }];
+
+        let queries = label_impressions(&impressions, &usage, &feedback, LabelWindows::default());
+        assert_eq!(queries.len(), 1);
+        assert_eq!(queries[0].labels, vec![0, 1, 2, 0, 0]);
+        assert!(queries[0].is_informative());
+    }
+
+    #[test]
+    fn features_normalize_scores_and_signals() {
+        let skill = |id: &str| SkillRecord {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            version: None,
+            author: None,
+            source_path: String::new(),
+            source_layer: "project".to_string(),
+            git_remote: None,
+            git_commit: None,
+            content_hash: String::new(),
+            body: String::new(),
+            metadata_json: "{}".to_string(),
+            assets_json: "[]".to_string(),
+            token_count: 0,
+            quality_score: 0.7,
+            indexed_at: String::new(),
+            modified_at: "2026-03-01T00:00:00Z".to_string(),
+            is_deprecated: false,
+            deprecation_reason: None,
+        };
+        let result = |id: &str, score, bm25| HybridResult {
+            skill_id: id.to_string(),
+            score,
+            bm25_rank: Some(1),
+            semantic_rank: None,
+            bm25_score: Some(bm25),
+            semantic_score: None,
+        };
+        let (a, b) = (skill("a"), skill("b"));
+        let (ra, rb) = (result("a", 0.04, 10.0), result("b", 0.02, 5.0));
+        let mut signals = RankSignals::default();
+        signals.favorites.insert("b".to_string());
+        signals.clicks.insert("b".to_string(), (8, 8));
+
+        let now: DateTime<Utc> = "2026-03-01T00:00:00Z".parse().unwrap();
+        let features = extract_features(
+            &[
+                RankCandidate {
+                    result: &ra,
+                    skill: &a,
+                    context: 0.0,
+                },
+                RankCandidate {
+                    result: &rb,
+                    skill: &b,
+                    context: 0.4,
+                },
+            ],
+            &signals,
+            now,
+        );
+
+        assert_eq!(features[0].bm25, 1.0);
+        assert_eq!(features[1].rrf, 0.5);
+        assert_eq!(features[1].favorite, 1.0);
+        assert_eq!(features[1].context, 0.4);
+        assert!((features[0].recency - 1.0).abs() < 1e-6);
+        assert!(features[1].click_through > features[0].click_through);
+        assert_eq!(features[0].feedback, 0.5);
+    }
+
+    #[test]
+    fn store_versions_and_activation() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = RankModelStore::new(dir.path().join("rank"));
+        assert!(store.active().unwrap().is_none());
+
+        let queries = clicked_second_queries(10);
+        let mut first = train(&queries, &TrainConfig::default()).unwrap();
+        let mut second = first.clone();
+        assert_eq!(store.save(&mut first).unwrap(), 1);
+        assert_eq!(store.save(&mut second).unwrap(), 2);
+        assert_eq!(store.list().unwrap().len(), 2);
+
+        store.activate(1).unwrap();
+        assert_eq!(store.active().unwrap().unwrap().version, 1);
+        assert!(store.activate(7).is_err());
+
+        assert!(store.deactivate().unwrap());
+        assert!(store.active_version().unwrap().is_none());
+    }
+}
@@ -0,0 +1,593 @@
+//! Slice-level retrieval
+//!
+//! A second Tantivy index whose documents are individual `SkillSlice`s
+//! (rules, pitfalls, commands, examples) rather than whole skills. Queries
+//! return the best slices from any skill, optionally fused with per-slice
+//! embeddings, and the hits convert straight into input for
+//! `ConstrainedPacker`.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::RwLock;
+
+use tantivy::collector::TopDocs;
+use tantivy::query::{QueryParser, TermQuery};
+use tantivy::schema::{
+    Field, IndexRecordOption, STORED, STRING, Schema, TextFieldIndexing, TextOptions, Value,
+};
+use tantivy::{Index, IndexReader, IndexWriter, ReloadPolicy, TantivyDocument, Term};
+
+use super::hybrid::{RrfConfig, fuse_results};
+use crate::core::skill::{SkillSlice, SliceType};
+use crate::error::{MsError, Result};
+
+/// Separator between skill id and slice id in slice keys
+pub const SLICE_KEY_SEPARATOR: &str = "::";
+
+/// Key identifying a slice across skills (`skill-id::rule-1`)
+#[must_use]
+pub fn slice_key(skill_id: &str, slice_id: &str) -> String {
+    format!("{skill_id}{SLICE_KEY_SEPARATOR}{slice_id}")
+}
+
+/// Text embedded for a slice: its section title followed by its content.
+#[must_use]
+pub fn slice_embedding_text(slice: &SkillSlice) -> String {
+    match &slice.section_title {
+        Some(title) => format!("{title}\n\n{}", slice.content),
+        None => slice.content.clone(),
+    }
+}
+
+/// BM25 index over skill slices
+pub struct SliceIndex {
+    index: Index,
+    reader: IndexReader,
+    writer: RwLock<IndexWriter>,
+    fields: SliceFields,
+}
+
+#[derive(Clone)]
+struct SliceFields {
+    key: Field,
+    skill_id: Field,
+    slice_type: Field,
+    section_title: Field,
+    content: Field,
+    tags: Field,
+    slice_json: Field,
+}
+
+/// A slice returned by a slice query
+#[derive(Debug, Clone)]
+pub struct SliceHit {
+    /// Skill the slice belongs to
+    pub skill_id: String,
+    /// The slice as sliced at index time
+    pub slice: SkillSlice,
+    /// Retrieval score (BM25, or RRF when fused)
+    pub score: f32,
+    /// Rank in the BM25 results (1-indexed)
+    pub bm25_rank: Option<usize>,
+    /// Rank in the semantic results (1-indexed)
+    pub semantic_rank: Option<usize>,
+}
+
+impl SliceHit {
+    /// Key identifying this slice across skills
+    #[must_use]
+    pub fn key(&self) -> String {
+        slice_key(&self.skill_id, &self.slice.id)
+    }
+}
+
+impl SliceIndex {
+    /// Open or create a slice index at the given path
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        std::fs::create_dir_all(path)?;
+
+        let schema = build_schema();
+        let fields = extract_fields(&schema)?;
+
+        let index = if path.join("meta.json").exists() {
+            Index::open_in_dir(path)?
+        } else {
+            Index::create_in_dir(path, schema)?
+        };
+        let reader = index
+            .reader_builder()
+            .reload_policy(ReloadPolicy::Manual)
+            .try_into()?;
+        let writer = index.writer(50_000_000)?;
+
+        Ok(Self {
+            index,
+            reader,
+            writer: RwLock::new(writer),
+            fields,
+        })
+    }
+
+    /// Open an in-memory index (for testing)
+    pub fn open_in_memory() -> Result<Self> {
+        let schema = build_schema();
+        let fields = extract_fields(&schema)?;
+
+        let index = Index::create_in_ram(schema);
+        let reader = index
+            .reader_builder()
+            .reload_policy(ReloadPolicy::Manual)
+            .try_into()?;
+        let writer = index.writer(15_000_000)?;
+
+        Ok(Self {
+            index,
+            reader,
+            writer: RwLock::new(writer),
+            fields,
+        })
+    }
+
+    /// Replace all slices of a skill. Changes are visible after `commit`.
+    pub fn index_slices(&self, skill_id: &str, slices: &[SkillSlice]) -> Result<usize> {
+        let writer = self.write_lock()?;
+        writer.delete_term(Term::from_field_text(self.fields.skill_id, skill_id));
+
+        for slice in slices {
+            let slice_json = serde_json::to_string(slice)
+                .map_err(|err| MsError::Serialization(format!("encode slice: {err}")))?;
+
+            let mut doc = TantivyDocument::new();
+            doc.add_text(self.fields.key, slice_key(skill_id, &slice.id));
+            doc.add_text(self.fields.skill_id, skill_id);
+            doc.add_text(self.fields.slice_type, slice_type_name(slice.slice_type));
+            doc.add_text(
+                self.fields.section_title,
+                slice.section_title.as_deref().unwrap_or_default(),
+            );
+            doc.add_text(self.fields.content, &slice.content);
+            doc.add_text(self.fields.tags, slice.tags.join(" ").to_lowercase());
+            doc.add_text(self.fields.slice_json, slice_json);
+            writer.add_document(doc)?;
+        }
+
+        Ok(slices.len())
+    }
+
+    /// Remove all slices of a skill
+    pub fn delete_skill(&self, skill_id: &str) -> Result<()> {
+        let writer = self.write_lock()?;
+        writer.delete_term(Term::from_field_text(self.fields.skill_id, skill_id));
+        Ok(())
+    }
+
+    /// Commit pending changes and reload the reader
+    pub fn commit(&self) -> Result<()> {
+        let mut writer = self.write_lock()?;
+        writer.commit()?;
+        drop(writer);
+
+        self.reader.reload()?;
+        Ok(())
+    }
+
+    /// Clear the entire index
+    pub fn clear(&self) -> Result<()> {
+        let mut writer = self.write_lock()?;
+        writer.delete_all_documents()?;
+        writer.commit()?;
+        drop(writer);
+
+        self.reader.reload()?;
+        Ok(())
+    }
+
+    /// Best slices from any skill for a query, optionally restricted to
+    /// some slice types.
+    pub fn search(&self, query: &str, types: &[SliceType], limit: usize) -> Result<Vec<SliceHit>> {
+        let searcher = self.reader.searcher();
+        let parser = QueryParser::for_index(
+            &self.index,
+            vec![
+                self.fields.content,
+                self.fields.section_title,
+                self.fields.tags,
+            ],
+        );
+        let parsed_query = parser
+            .parse_query(query)
+            .map_err(|e| MsError::QueryParse(format!("Failed to parse query: {e}")))?;
+
+        // Over-fetch when filtering by type so the limit still fills up.
+        let fetch = if types.is_empty() { limit } else { limit * 10 };
+        let top_docs = searcher.search(&parsed_query, &TopDocs::with_limit(fetch))?;
+
+        let mut hits = Vec::with_capacity(top_docs.len().min(limit));
+        for (rank, (score, address)) in top_docs.into_iter().enumerate() {
+            let doc: TantivyDocument = searcher.doc(address)?;
+            let Some(mut hit) = self.hit_from_doc(&doc, score)? else {
+                continue;
+            };
+            if !types.is_empty() && !types.contains(&hit.slice.slice_type) {
+                continue;
+            }
+            hit.bm25_rank = Some(rank + 1);
+            hits.push(hit);
+            if hits.len() == limit {
+                break;
+            }
+        }
+        Ok(hits)
+    }
+
+    /// Fuse BM25 slice hits with semantic results (keyed by `slice_key`)
+    /// using RRF.
+    pub fn search_hybrid(
+        &self,
+        query: &str,
+        semantic: &[(String, f32)],
+        types: &[SliceType],
+        config: &RrfConfig,
+        limit: usize,
+    ) -> Result<Vec<SliceHit>> {
+        let bm25_hits = self.search(query, types, limit * 5)?;
+        let bm25: Vec<(String, f32)> = bm25_hits.iter().map(|h| (h.key(), h.score)).collect();
+        let mut by_key: HashMap<String, SliceHit> =
+            bm25_hits.into_iter().map(|h| (h.key(), h)).collect();
+
+        let mut hits = Vec::with_capacity(limit);
+        for fused in fuse_results(&bm25, semantic, config) {
+            let hit = match by_key.remove(&fused.skill_id) {
+                Some(hit) => Some(hit),
+                None => self.get(&fused.skill_id)?,
+            };
+            let Some(mut hit) = hit else {
+                continue;
+            };
+            if !types.is_empty() && !types.contains(&hit.slice.slice_type) {
+                continue;
+            }
+            hit.score = fused.score;
+            hit.bm25_rank = fused.bm25_rank;
+            hit.semantic_rank = fused.semantic_rank;
+            hits.push(hit);
+            if hits.len() == limit {
+                break;
+            }
+        }
+        Ok(hits)
+    }
+
+    /// Look up a slice by its `slice_key`
+    pub fn get(&self, key: &str) -> Result<Option<SliceHit>> {
+        let searcher = self.reader.searcher();
+        let query = TermQuery::new(
+            Term::from_field_text(self.fields.key, key),
+            IndexRecordOption::Basic,
+        );
+        let top_docs = searcher.search(&query, &TopDocs::with_limit(1))?;
+        match top_docs.first() {
+            Some((_, address)) => {
+                let doc: TantivyDocument = searcher.doc(*address)?;
+                self.hit_from_doc(&doc, 0.0)
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Get total number of indexed slices
+    pub fn num_docs(&self) -> u64 {
+        self.reader.searcher().num_docs()
+    }
+
+    /// Check if index is empty
+    pub fn is_empty(&self) -> bool {
+        self.num_docs() == 0
+    }
+
+    fn hit_from_doc(&self, doc: &TantivyDocument, score: f32) -> Result<Option<SliceHit>> {
+        let skill_id = doc
+            .get_first(self.fields.skill_id)
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string();
+        let Some(slice_json) = doc
+            .get_first(self.fields.slice_json)
+            .and_then(|v| v.as_str())
+        else {
+            return Ok(None);
+        };
+        let slice: SkillSlice = serde_json::from_str(slice_json)
+            .map_err(|err| MsError::Serialization(format!("decode slice: {err}")))?;
+        Ok(Some(SliceHit {
+            skill_id,
+            slice,
+            score,
+            bm25_rank: None,
+            semantic_rank: None,
+        }))
+    }
+
+    fn write_lock(&self) -> Result<std::sync::RwLockWriteGuard<'_, IndexWriter>> {
+        self.writer.write().map_err(|e| {
+            MsError::SearchIndex(tantivy::TantivyError::InternalError(format!(
+                "Failed to acquire write lock: {e}"
+            )))
+        })
+    }
+}
+
+/// Turn slice hits into packer input.
+///
+/// Slice ids (and `requires` edges between hits) are namespaced with the
+/// skill id so slices from different skills cannot collide, section titles
+/// name the skill they came from, and the utility score is blended with the
+/// normalized retrieval score so the packer favors the best matches.
+/// Dependencies on slices that were not retrieved are dropped.
+#[must_use]
+pub fn packable_slices(hits: &[SliceHit]) -> Vec<SkillSlice> {
+    let max_score = hits.iter().map(|h| h.score).fold(0.0_f32, f32::max);
+    let keys: HashSet<String> = hits.iter().map(SliceHit::key).collect();
+
+    hits.iter()
+        .map(|hit| {
+            let mut slice = hit.slice.clone();
+            slice.id = hit.key();
+            slice.requires = hit
+                .slice
+                .requires
+                .iter()
+                .map(|dep| slice_key(&hit.skill_id, dep))
+                .filter(|dep| keys.contains(dep))
+                .collect();
+            slice.section_title = Some(match &hit.slice.section_title {
+                Some(title) => format!("{} — {title}", hit.skill_id),
+                None => hit.skill_id.clone(),
+            });
+            let relevance = if max_score > 0.0 {
+                hit.score / max_score
+            } else {
+                0.0
+            };
+            slice.utility_score = (0.5 * hit.slice.utility_score + 0.5 * relevance).clamp(0.0, 1.0);
+            slice
+        })
+        .collect()
+}
+
+/// Parse a slice type name as used on the command line
+#[must_use]
+pub fn parse_slice_type(input: &str) -> Option<SliceType> {
+    match input.trim().to_lowercase().as_str() {
+        "rule" | "rules" => Some(SliceType::Rule),
+        "command" | "commands" => Some(SliceType::Command),
+        "example" | "examples" => Some(SliceType::Example),
+        "checklist" | "checklists" => Some(SliceType::Checklist),
+        "pitfall" | "pitfalls" => Some(SliceType::Pitfall),
+        "overview" => Some(SliceType::Overview),
+        "reference" | "references" => Some(SliceType::Reference),
+        "policy" | "policies" => Some(SliceType::Policy),
+        _ => None,
+    }
+}
+
+/// Lowercase name of a slice type
+#[must_use]
+pub const fn slice_type_name(slice_type: SliceType) -> &'static str {
+    match slice_type {
+        SliceType::Rule => "rule",
+        SliceType::Command => "command",
+        SliceType::Example => "example",
+        SliceType::Checklist => "checklist",
+        SliceType::Pitfall => "pitfall",
+        SliceType::Overview => "overview",
+        SliceType::Reference => "reference",
+        SliceType::Policy => "policy",
+    }
+}
+
+fn build_schema() -> Schema {
+    let mut builder = Schema::builder();
+
+    let text_options = TextOptions::default().set_indexing_options(
+        TextFieldIndexing::default()
+            .set_tokenizer("default")
+            .set_index_option(IndexRecordOption::WithFreqsAndPositions),
+    );
+
+    builder.add_text_field("key", STRING | STORED);
+    builder.add_text_field("skill_id", STRING | STORED);
+    builder.add_text_field("slice_type", STRING | STORED);
+    builder.add_text_field("section_title", text_options.clone());
+    builder.add_text_field("content", text_options.clone());
+    builder.add_text_field("tags", text_options);
+    builder.add_text_field("slice_json", STORED);
+
+    builder.build()
+}
+
+fn extract_fields(schema: &Schema) -> Result<SliceFields> {
+    let field = |name: &str| {
+        schema.get_field(name).map_err(|_| {
+            MsError::SearchIndex(tantivy::TantivyError::SchemaError(format!(
+                "missing {name} field"
+            )))
+        })
+    };
+    Ok(SliceFields {
+        key: field("key")?,
+        skill_id: field("skill_id")?,
+        slice_type: field("slice_type")?,
+        section_title: field("section_title")?,
+        content: field("content")?,
+        tags: field("tags")?,
+        slice_json: field("slice_json")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::disclosure::PackMode;
+    use crate::core::packing::{ConstrainedPacker, PackConstraints};
+
+    fn slice(id: &str, slice_type: SliceType, title: &str, content: &str) -> SkillSlice {
+        SkillSlice {
+            id: id.to_string(),
+            slice_type,
+            token_estimate: content.len() / 4 + 1,
+            utility_score: 0.8,
+            coverage_group: Some(slice_type_name(slice_type).to_string()),
+            tags: vec![slice_type_name(slice_type).to_string()],
+            requires: Vec::new(),
+            condition: None,
+            section_title: Some(title.to_string()),
+            content: content.to_string(),
+        }
+    }
+
+    fn sample_index() -> SliceIndex {
+        let index = SliceIndex::open_in_memory().unwrap();
+        index
+            .index_slices(
+                "rust-errors",
+                &[
+                    slice(
+                        "rule-1",
+                        SliceType::Rule,
+                        "Rules",
+                        "Propagate errors with ?",
+                    ),
+                    slice(
+                        "pitfall-1",
+                        SliceType::Pitfall,
+                        "Pitfalls",
+                        "Never unwrap errors in library code",
+                    ),
+                ],
+            )
+            .unwrap();
+        index
+            .index_slices(
+                "git-workflow",
+                &[
+                    slice("command-1", SliceType::Command, "Commands", "git commit -s"),
+                    slice(
+                        "pitfall-1",
+                        SliceType::Pitfall,
+                        "Pitfalls",
+                        "Resolve merge errors before pushing",
+                    ),
+                ],
+            )
+            .unwrap();
+        index.commit().unwrap();
+        index
+    }
+
+    #[test]
+    fn search_returns_slices_from_any_skill() {
+        let index = sample_index();
+        assert_eq!(index.num_docs(), 4);
+
+        let hits = index.search("errors", &[], 10).unwrap();
+        let keys: HashSet<String> = hits.iter().map(SliceHit::key).collect();
+        assert!(keys.contains("rust-errors::rule-1"));
+        assert!(keys.contains("rust-errors::pitfall-1"));
+        assert!(keys.contains("git-workflow::pitfall-1"));
+        assert_eq!(hits[0].bm25_rank, Some(1));
+    }
+
+    #[test]
+    fn search_filters_by_slice_type() {
+        let index = sample_index();
+        let hits = index.search("errors", &[SliceType::Pitfall], 10).unwrap();
+        assert_eq!(hits.len(), 2);
+        assert!(
+            hits.iter()
+                .all(|h| h.slice.slice_type == SliceType::Pitfall)
+        );
+    }
+
+    #[test]
+    fn reindexing_a_skill_replaces_its_slices() {
+        let index = sample_index();
+        index
+            .index_slices(
+                "rust-errors",
+                &[slice(
+                    "rule-1",
+                    SliceType::Rule,
+                    "Rules",
+                    "Prefer thiserror",
+                )],
+            )
+            .unwrap();
+        index.commit().unwrap();
+
+        assert_eq!(index.num_docs(), 3);
+        assert!(index.get("rust-errors::pitfall-1").unwrap().is_none());
+        let hits = index.search("thiserror", &[], 10).unwrap();
+        assert_eq!(hits.len(), 1);
+
+        index.delete_skill("rust-errors").unwrap();
+        index.commit().unwrap();
+        assert_eq!(index.num_docs(), 2);
+    }
+
+    #[test]
+    fn hybrid_search_pulls_in_semantic_only_slices() {
+        let index = sample_index();
+        let semantic = vec![("git-workflow::command-1".to_string(), 0.9)];
+        let hits = index
+            .search_hybrid("unwrap", &semantic, &[], &RrfConfig::default(), 10)
+            .unwrap();
+        let command = hits
+            .iter()
+            .find(|h| h.key() == "git-workflow::command-1")
+            .unwrap();
+        assert_eq!(command.semantic_rank, Some(1));
+        assert_eq!(command.bm25_rank, None);
+        assert_eq!(command.slice.content, "git commit -s");
+    }
+
+    #[test]
+    fn packable_slices_feed_constrained_packer() {
+        let index = sample_index();
+        let mut hits = index.search("errors", &[], 10).unwrap();
+        hits[0].slice.requires = vec!["missing".to_string()];
+        let slices = packable_slices(&hits);
+
+        assert!(slices.iter().all(|s| s.id.contains(SLICE_KEY_SEPARATOR)));
+        assert!(slices[0].requires.is_empty());
+        assert!(
+            slices
+                .iter()
+                .all(|s| s.section_title.as_deref().unwrap().contains(" — "))
+        );
+
+        let budget: usize = slices.iter().map(|s| s.token_estimate).sum();
+        let packed = ConstrainedPacker
+            .pack(
+                &slices,
+                &PackConstraints::new(budget, 5),
+                PackMode::Balanced,
+            )
+            .unwrap();
+        assert_eq!(packed.slices.len(), slices.len());
+        let skills: HashSet<&str> = packed
+            .slices
+            .iter()
+            .filter_map(|s| s.id.split(SLICE_KEY_SEPARATOR).next())
+            .collect();
+        assert_eq!(skills.len(), 2);
+    }
+
+    #[test]
+    fn parse_slice_type_accepts_plurals() {
+        assert_eq!(parse_slice_type("Pitfalls"), Some(SliceType::Pitfall));
+        assert_eq!(parse_slice_type("rule"), Some(SliceType::Rule));
+        assert_eq!(parse_slice_type("nope"), None);
+    }
+}
@@ -0,0 +1,419 @@
+//! Search result snippets
+//!
+//! Finds the section and block of a skill that best explains why it matched a
+//! query. Lexical matches use Tantivy's snippet generator (so highlighting
+//! agrees with BM25 tokenization); semantic matches score each block against
+//! the query embedding. Highlights are byte ranges into `MatchedSection::text`,
+//! so callers can style them without re-tokenizing.
+
+use std::collections::{BTreeMap, HashSet};
+
+use serde::Serialize;
+use tantivy::schema::Field;
+use tantivy::snippet::SnippetGenerator;
+use tantivy::tokenizer::{LowerCaser, RemoveLongFilter, SimpleTokenizer, TextAnalyzer};
+
+use super::embeddings::Embedder;
+use crate::core::spec_lens::parse_markdown;
+
+/// Default maximum snippet length in characters.
+pub const DEFAULT_SNIPPET_CHARS: usize = 160;
+
+/// Query words that are operators rather than terms.
+const QUERY_OPERATORS: &[&str] = &["and", "or", "not"];
+
+/// Which retriever produced a matched section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchKind {
+    Lexical,
+    Semantic,
+}
+
+/// Byte range of a highlighted term within a snippet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Highlight {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// The part of a skill that matched a query.
+#[derive(Debug, Clone, Serialize)]
+pub struct MatchedSection {
+    /// Section ID (absent when the body has no `##` sections)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub section_id: Option<String>,
+    /// Section title
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub section_title: Option<String>,
+    /// Block ID within the section
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_id: Option<String>,
+    pub kind: MatchKind,
+    /// Distinct matched terms (lexical) or cosine similarity (semantic)
+    pub score: f32,
+    /// Snippet text
+    pub text: String,
+    /// Highlighted ranges in `text`
+    pub highlights: Vec<Highlight>,
+}
+
+impl MatchedSection {
+    /// Split the snippet into `(text, highlighted)` runs.
+    #[must_use]
+    pub fn segments(&self) -> Vec<(&str, bool)> {
+        let mut out = Vec::with_capacity(self.highlights.len() * 2 + 1);
+        let mut cursor = 0;
+        for highlight in &self.highlights {
+            let (Some(before), Some(matched)) = (
+                self.text.get(cursor..highlight.start),
+                self.text.get(highlight.start..highlight.end),
+            ) else {
+                continue;
+            };
+            if !before.is_empty() {
+                out.push((before, false));
+            }
+            out.push((matched, true));
+            cursor = highlight.end;
+        }
+        if let Some(rest) = self.text.get(cursor..) {
+            if !rest.is_empty() {
+                out.push((rest, false));
+            }
+        }
+        out
+    }
+
+    /// Render the snippet with each highlight wrapped in `open`/`close`.
+    #[must_use]
+    pub fn render(&self, open: &str, close: &str) -> String {
+        self.segments()
+            .into_iter()
+            .map(|(text, highlighted)| {
+                if highlighted {
+                    format!("{open}{text}{close}")
+                } else {
+                    text.to_string()
+                }
+            })
+            .collect()
+    }
+
+    /// Snippet prefixed with its section title, for single-line display.
+    #[must_use]
+    pub fn label(&self) -> String {
+        self.section_title.as_ref().map_or_else(
+            || self.text.clone(),
+            |title| format!("{title}: {}", self.text),
+        )
+    }
+}
+
+/// Extracts matched sections from skill bodies for one query.
+pub struct SnippetExtractor<'a> {
+    generator: SnippetGenerator,
+    semantic: Option<(&'a dyn Embedder, Vec<f32>)>,
+    max_chars: usize,
+}
+
+impl<'a> SnippetExtractor<'a> {
+    /// Extractor that highlights the query's terms with equal weight.
+    ///
+    /// Uses the same tokenizer as the BM25 index, so "Errors" highlights
+    /// "errors" and punctuation is ignored.
+    #[must_use]
+    pub fn new(query: &str) -> Self {
+        let mut analyzer = default_analyzer();
+        let mut terms = BTreeMap::new();
+        let mut stream = analyzer.token_stream(query);
+        while stream.advance() {
+            let text = &stream.token().text;
+            if !QUERY_OPERATORS.contains(&text.as_str()) {
+                terms.insert(text.clone(), 1.0);
+            }
+        }
+        let generator = SnippetGenerator::new(
+            terms,
+            default_analyzer(),
+            Field::from_field_id(0),
+            DEFAULT_SNIPPET_CHARS,
+        );
+        Self::from_generator(generator)
+    }
+
+    /// Extractor backed by a generator built from an index, which weights
+    /// rare terms higher.
+    #[must_use]
+    pub fn from_generator(mut generator: SnippetGenerator) -> Self {
+        generator.set_max_num_chars(DEFAULT_SNIPPET_CHARS);
+        Self {
+            generator,
+            semantic: None,
+            max_chars: DEFAULT_SNIPPET_CHARS,
+        }
+    }
+
+    /// Also report the block most similar to `query` under `embedder`.
+    #[must_use]
+    pub fn with_embedder(mut self, embedder: &'a dyn Embedder, query: &str) -> Self {
+        self.semantic = Some((embedder, embedder.embed(query)));
+        self
+    }
+
+    /// Set the maximum snippet length in characters.
+    #[must_use]
+    pub fn with_max_chars(mut self, max_chars: usize) -> Self {
+        self.max_chars = max_chars.max(1);
+        self.generator.set_max_num_chars(self.max_chars);
+        self
+    }
+
+    /// Matched sections for a SKILL.md body, best lexical match first.
+    ///
+    /// Returns at most one lexical and one semantic match; the semantic one
+    /// is omitted when it points at the same block as the lexical one.
+    #[must_use]
+    pub fn extract(&self, body: &str) -> Vec<MatchedSection> {
+        let units = blocks_of(body);
+
+        let mut lexical: Option<MatchedSection> = None;
+        for unit in &units {
+            if let Some(found) = self.lexical_match(unit) {
+                if lexical.as_ref().is_none_or(|best| found.score > best.score) {
+                    lexical = Some(found);
+                }
+            }
+        }
+
+        let semantic = self.semantic.as_ref().and_then(|(embedder, query)| {
+            units
+                .iter()
+                .map(|unit| (unit, dot(query, &embedder.embed(&unit.content))))
+                .filter(|(_, score)| *score > 0.0)
+                .max_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(unit, score)| MatchedSection {
+                    section_id: unit.section_id.clone(),
+                    section_title: unit.section_title.clone(),
+                    block_id: unit.block_id.clone(),
+                    kind: MatchKind::Semantic,
+                    score,
+                    text: truncate_chars(&unit.content, self.max_chars),
+                    highlights: Vec::new(),
+                })
+        });
+
+        let mut out = Vec::new();
+        let lexical_block = lexical
+            .as_ref()
+            .map(|m| (m.section_id.clone(), m.block_id.clone()));
+        out.extend(lexical);
+        if let Some(semantic) = semantic {
+            if lexical_block != Some((semantic.section_id.clone(), semantic.block_id.clone())) {
+                out.push(semantic);
+            }
+        }
+        out
+    }
+
+    /// Best matched section for a body, if any.
+    #[must_use]
+    pub fn best(&self, body: &str) -> Option<MatchedSection> {
+        self.extract(body).into_iter().next()
+    }
+
+    fn lexical_match(&self, unit: &Unit) -> Option<MatchedSection> {
+        let snippet = self.generator.snippet(&unit.content);
+        if snippet.highlighted().is_empty() {
+            return None;
+        }
+        let fragment = snippet.fragment();
+        let distinct: HashSet<String> = snippet
+            .highlighted()
+            .iter()
+            .filter_map(|range| fragment.get(range.clone()))
+            .map(str::to_lowercase)
+            .collect();
+        // A matching section title is a strong hint that the block is on topic.
+        let title_bonus = unit.section_title.as_deref().map_or(0.0, |title| {
+            if self.generator.snippet(title).highlighted().is_empty() {
+                0.0
+            } else {
+                0.5
+            }
+        });
+        Some(MatchedSection {
+            section_id: unit.section_id.clone(),
+            section_title: unit.section_title.clone(),
+            block_id: unit.block_id.clone(),
+            kind: MatchKind::Lexical,
+            score: distinct.len() as f32 + title_bonus,
+            text: fragment.to_string(),
+            highlights: snippet
+                .highlighted()
+                .iter()
+                .map(|range| Highlight {
+                    start: range.start,
+                    end: range.end,
+                })
+                .collect(),
+        })
+    }
+}
+
+/// A block of text with its position in the skill.
+struct Unit {
+    section_id: Option<String>,
+    section_title: Option<String>,
+    block_id: Option<String>,
+    content: String,
+}
+
+/// Split a body into blocks; bodies without sections are one block.
+fn blocks_of(body: &str) -> Vec<Unit> {
+    let units: Vec<Unit> = parse_markdown(body)
+        .map(|spec| {
+            spec.sections
+                .into_iter()
+                .flat_map(|section| {
+                    let (id, title) = (section.id, section.title);
+                    section.blocks.into_iter().map(move |block| Unit {
+                        section_id: Some(id.clone()),
+                        section_title: Some(title.clone()),
+                        block_id: Some(block.id),
+                        content: block.content,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    if units.is_empty() {
+        return vec![Unit {
+            section_id: None,
+            section_title: None,
+            block_id: None,
+            content: body.to_string(),
+        }];
+    }
+    units
+}
+
+fn default_analyzer() -> TextAnalyzer {
+    TextAnalyzer::builder(SimpleTokenizer::default())
+        .filter(RemoveLongFilter::limit(40))
+        .filter(LowerCaser)
+        .build()
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn truncate_chars(text: &str, max_chars: usize) -> String {
+    let text = text.trim();
+    match text.char_indices().nth(max_chars) {
+        None => text.to_string(),
+        Some((cut, _)) => {
+            let head = &text[..cut];
+            let head = head
+                .rfind(char::is_whitespace)
+                .map_or(head, |space| &head[..space]);
+            format!("{}...", head.trim_end())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::HashEmbedder;
+
+    const BODY: &str = "# Rust Errors\n\nHandling errors in Rust.\n\n## Setup\n\nInstall the toolchain with rustup.\n\n## Propagation\n\nUse the `?` operator to propagate a Result to the caller.\n";
+
+    #[test]
+    fn lexical_match_points_at_block() {
+        let extractor = SnippetExtractor::new("propagate result");
+        let matched = extractor.best(BODY).unwrap();
+        assert_eq!(matched.kind, MatchKind::Lexical);
+        assert_eq!(matched.section_id.as_deref(), Some("propagation"));
+        assert_eq!(matched.section_title.as_deref(), Some("Propagation"));
+        assert!(matched.block_id.is_some());
+
+        let words: Vec<&str> = matched
+            .highlights
+            .iter()
+            .map(|h| &matched.text[h.start..h.end])
+            .collect();
+        assert_eq!(words, vec!["propagate", "Result"]);
+    }
+
+    #[test]
+    fn render_wraps_highlights() {
+        let extractor = SnippetExtractor::new("rustup");
+        let matched = extractor.best(BODY).unwrap();
+        assert_eq!(
+            matched.render("[", "]"),
+            "Install the toolchain with [rustup]"
+        );
+        assert_eq!(matched.label(), "Setup: Install the toolchain with rustup");
+    }
+
+    #[test]
+    fn no_match_yields_nothing() {
+        let extractor = SnippetExtractor::new("kubernetes");
+        assert!(extractor.extract(BODY).is_empty());
+    }
+
+    #[test]
+    fn operators_are_not_highlighted() {
+        let extractor = SnippetExtractor::new("rustup AND toolchain");
+        let matched = extractor.best(BODY).unwrap();
+        assert_eq!(matched.highlights.len(), 2);
+    }
+
+    #[test]
+    fn body_without_sections_is_one_block() {
+        let extractor = SnippetExtractor::new("deploy");
+        let matched = extractor.best("Run deploy after tests pass.").unwrap();
+        assert!(matched.section_id.is_none());
+        assert_eq!(matched.render("<", ">"), "Run <deploy> after tests pass");
+    }
+
+    #[test]
+    fn semantic_match_is_reported_separately() {
+        let embedder = HashEmbedder::new(64);
+        let extractor =
+            SnippetExtractor::new("rustup").with_embedder(&embedder, "propagate result caller");
+        let matches = extractor.extract(BODY);
+        assert_eq!(matches[0].kind, MatchKind::Lexical);
+        assert_eq!(matches[0].section_id.as_deref(), Some("setup"));
+        let semantic = matches
+            .iter()
+            .find(|m| m.kind == MatchKind::Semantic)
+            .unwrap();
+        assert_eq!(semantic.section_id.as_deref(), Some("propagation"));
+        assert!(semantic.highlights.is_empty());
+    }
+
+    #[test]
+    fn max_chars_limits_snippet() {
+        let long = format!(
+            "## Notes\n\n{} needle {}\n",
+            "word ".repeat(80),
+            "word ".repeat(80)
+        );
+        let extractor = SnippetExtractor::new("needle").with_max_chars(40);
+        let matched = extractor.best(&long).unwrap();
+        assert!(matched.text.chars().count() <= 40);
+        assert_eq!(matched.highlights.len(), 1);
+    }
+
+    #[test]
+    fn truncate_chars_breaks_on_whitespace() {
+        assert_eq!(truncate_chars("alpha beta gamma", 8), "alpha...");
+        assert_eq!(truncate_chars("short", 10), "short");
+    }
+}
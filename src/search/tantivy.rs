@@ -3,16 +3,21 @@
 //! Implements BM25 full-text search using Tantivy. Part of the hybrid search
 //! system that combines BM25 with hash embeddings via RRF fusion.
 
+use std::ops::Bound;
 use std::path::Path;
 use std::sync::RwLock;
 
 use tantivy::collector::TopDocs;
-use tantivy::query::QueryParser;
+use tantivy::query::{AllQuery, BooleanQuery, Occur, Query, QueryParser, RangeQuery, TermQuery};
 use tantivy::schema::{
-    Field, IndexRecordOption, STORED, STRING, Schema, TextFieldIndexing, TextOptions, Value,
+    FAST, Field, INDEXED, IndexRecordOption, STORED, STRING, Schema, TextFieldIndexing,
+    TextOptions, Value,
 };
-use tantivy::{Index, IndexReader, IndexWriter, ReloadPolicy, TantivyDocument};
+use tantivy::snippet::SnippetGenerator;
+use tantivy::{DateTime, Index, IndexReader, IndexWriter, ReloadPolicy, TantivyDocument, Term};
 
+use super::context::{Facet, SearchFilters, SkillFacets};
+use super::snippets::{MatchedSection, SnippetExtractor};
 use crate::error::{MsError, Result};
 use crate::storage::sqlite::SkillRecord;
 
@@ -37,6 +42,12 @@ struct BM25Fields {
     layer: Field,
     quality_score: Field,
     deprecated: Field,
+    // Raw facet values for pushed-down filters
+    tag: Field,
+    author: Field,
+    platform: Field,
+    requires: Field,
+    updated: Field,
 }
 
 /// A single BM25 search result
@@ -50,6 +61,8 @@ pub struct Bm25Result {
     pub name: String,
     /// Source layer
     pub layer: String,
+    /// Sections that explain the match (empty unless snippets were requested)
+    pub matched_sections: Vec<MatchedSection>,
 }
 
 impl Bm25Index {
@@ -61,9 +74,20 @@ impl Bm25Index {
         let schema = build_schema();
         let fields = extract_fields(&schema)?;
 
-        // Try to open existing index, or create new one
+        // Try to open existing index, or create new one. An index written
+        // with an older schema is derived data: drop it and let the next
+        // `ms index` repopulate it.
         let index = if path.join("meta.json").exists() {
-            Index::open_in_dir(path)?
+            let existing = Index::open_in_dir(path)?;
+            if existing.schema() == schema {
+                existing
+            } else {
+                tracing::warn!("search index schema changed; rebuilding {}", path.display());
+                drop(existing);
+                std::fs::remove_dir_all(path)?;
+                std::fs::create_dir_all(path)?;
+                Index::create_in_dir(path, schema)?
+            }
         } else {
             Index::create_in_dir(path, schema)?
         };
@@ -118,15 +142,32 @@ impl Bm25Index {
         doc.add_text(self.fields.tags, &tags);
         doc.add_text(self.fields.aliases, &aliases);
         doc.add_text(self.fields.layer, &skill.source_layer);
-        // Safely convert quality_score to u64, handling NaN/Inf/negative values
-        let quality_u64 = if skill.quality_score.is_nan() || skill.quality_score.is_infinite() {
-            0u64
-        } else {
-            (skill.quality_score.clamp(0.0, 100.0) * 100.0) as u64
-        };
-        doc.add_u64(self.fields.quality_score, quality_u64);
+        doc.add_u64(
+            self.fields.quality_score,
+            quality_to_u64(skill.quality_score),
+        );
         doc.add_bool(self.fields.deprecated, skill.is_deprecated);
 
+        let facets = SkillFacets::from_record(skill);
+        for tag in &facets.tags {
+            doc.add_text(self.fields.tag, tag);
+        }
+        if let Some(author) = &facets.author {
+            doc.add_text(self.fields.author, author);
+        }
+        for platform in &facets.platforms {
+            doc.add_text(self.fields.platform, platform);
+        }
+        for requirement in &facets.requires {
+            doc.add_text(self.fields.requires, requirement);
+        }
+        if let Some(updated) = facets.updated {
+            doc.add_date(
+                self.fields.updated,
+                DateTime::from_timestamp_secs(updated.timestamp()),
+            );
+        }
+
         // Delete any existing document with this ID first
         let id_term = tantivy::Term::from_field_text(self.fields.id, &skill.id);
 
@@ -204,10 +245,45 @@ impl Bm25Index {
 
     /// Search skills by query
     pub fn search(&self, query: &str, limit: usize) -> Result<Vec<Bm25Result>> {
+        self.search_inner(query, None, limit, false)
+    }
+
+    /// Search skills by query, attaching the best-matching section and block
+    /// of each hit with highlight offsets.
+    pub fn search_with_snippets(&self, query: &str, limit: usize) -> Result<Vec<Bm25Result>> {
+        self.search_inner(query, None, limit, true)
+    }
+
+    /// Search with filters applied inside Tantivy, so `limit` counts only
+    /// matching skills. An empty query returns every matching skill.
+    pub fn search_filtered(
+        &self,
+        query: &str,
+        filters: &SearchFilters,
+        limit: usize,
+        snippets: bool,
+    ) -> Result<Vec<Bm25Result>> {
+        self.search_inner(query, Some(filters), limit, snippets)
+    }
+
+    /// Snippet generator over the `body` field, weighting terms by how rare
+    /// they are in the index.
+    pub fn snippet_generator(&self, query: &str) -> Result<SnippetGenerator> {
         let searcher = self.reader.searcher();
+        let parsed_query = self
+            .query_parser()
+            .parse_query(query)
+            .map_err(|e| MsError::QueryParse(format!("Failed to parse query: {e}")))?;
+        Ok(SnippetGenerator::create(
+            &searcher,
+            &*parsed_query,
+            self.fields.body,
+        )?)
+    }
 
+    fn query_parser(&self) -> QueryParser {
         // Multi-field query parser - search name, description, body, tags
-        let query_parser = QueryParser::for_index(
+        QueryParser::for_index(
             &self.index,
             vec![
                 self.fields.name,
@@ -216,13 +292,44 @@ impl Bm25Index {
                 self.fields.tags,
                 self.fields.aliases,
             ],
-        );
+        )
+    }
 
-        let parsed_query = query_parser
-            .parse_query(query)
-            .map_err(|e| MsError::QueryParse(format!("Failed to parse query: {e}")))?;
+    fn search_inner(
+        &self,
+        query: &str,
+        filters: Option<&SearchFilters>,
+        limit: usize,
+        snippets: bool,
+    ) -> Result<Vec<Bm25Result>> {
+        let searcher = self.reader.searcher();
 
-        let top_docs = searcher.search(&parsed_query, &TopDocs::with_limit(limit))?;
+        let parsed_query: Box<dyn Query> = if query.trim().is_empty() && filters.is_some() {
+            Box::new(AllQuery)
+        } else {
+            self.query_parser()
+                .parse_query(query)
+                .map_err(|e| MsError::QueryParse(format!("Failed to parse query: {e}")))?
+        };
+
+        let top_docs = match filters {
+            Some(filters) => {
+                let mut clauses = vec![(Occur::Must, parsed_query.box_clone())];
+                clauses.extend(self.filter_clauses(filters));
+                searcher.search(&BooleanQuery::new(clauses), &TopDocs::with_limit(limit))?
+            }
+            None => searcher.search(&parsed_query, &TopDocs::with_limit(limit))?,
+        };
+
+        let extractor = if snippets {
+            Some(SnippetExtractor::from_generator(SnippetGenerator::create(
+                &searcher,
+                &*parsed_query,
+                self.fields.body,
+            )?))
+        } else {
+            None
+        };
 
         let mut results = Vec::with_capacity(top_docs.len());
         for (score, doc_address) in top_docs {
@@ -246,17 +353,118 @@ impl Bm25Index {
                 .unwrap_or_default()
                 .to_string();
 
+            // Indexes built before `body` was stored have no text to quote.
+            let matched_sections = match (&extractor, doc.get_first(self.fields.body)) {
+                (Some(extractor), Some(body)) => {
+                    extractor.extract(body.as_str().unwrap_or_default())
+                }
+                _ => Vec::new(),
+            };
+
             results.push(Bm25Result {
                 skill_id,
                 score,
                 name,
                 layer,
+                matched_sections,
             });
         }
 
         Ok(results)
     }
 
+    /// Translate filters into Tantivy clauses over the raw facet fields
+    fn filter_clauses(&self, filters: &SearchFilters) -> Vec<(Occur, Box<dyn Query>)> {
+        let term_query = |field: Field, value: &str| -> Box<dyn Query> {
+            Box::new(TermQuery::new(
+                Term::from_field_text(field, value),
+                IndexRecordOption::Basic,
+            ))
+        };
+        let mut clauses: Vec<(Occur, Box<dyn Query>)> = Vec::new();
+
+        if !filters.include_deprecated {
+            clauses.push((
+                Occur::MustNot,
+                Box::new(TermQuery::new(
+                    Term::from_field_bool(self.fields.deprecated, true),
+                    IndexRecordOption::Basic,
+                )),
+            ));
+        }
+
+        if let Some(layer) = filters.layer {
+            clauses.push((Occur::Must, term_query(self.fields.layer, layer.as_str())));
+        }
+
+        if filters.min_quality.is_some() || filters.max_quality.is_some() {
+            let bound = |q: Option<f32>| {
+                q.map_or(Bound::Unbounded, |q| {
+                    Bound::Included(Term::from_field_u64(
+                        self.fields.quality_score,
+                        quality_to_u64(f64::from(q)),
+                    ))
+                })
+            };
+            clauses.push((
+                Occur::Must,
+                Box::new(RangeQuery::new(
+                    bound(filters.min_quality),
+                    bound(filters.max_quality),
+                )),
+            ));
+        }
+
+        if filters.updated_after.is_some() || filters.updated_before.is_some() {
+            let term = |dt: chrono::DateTime<chrono::Utc>| {
+                Term::from_field_date_for_search(
+                    self.fields.updated,
+                    DateTime::from_timestamp_secs(dt.timestamp()),
+                )
+            };
+            let lower = filters
+                .updated_after
+                .map_or(Bound::Unbounded, |dt| Bound::Included(term(dt)));
+            let upper = filters
+                .updated_before
+                .map_or(Bound::Unbounded, |dt| Bound::Excluded(term(dt)));
+            clauses.push((Occur::Must, Box::new(RangeQuery::new(lower, upper))));
+        }
+
+        // `--tags` keeps its any-match semantics
+        if !filters.tags.is_empty() {
+            let any_tag = filters
+                .tags
+                .iter()
+                .map(|tag| {
+                    (
+                        Occur::Should,
+                        term_query(self.fields.tag, &tag.to_lowercase()),
+                    )
+                })
+                .collect();
+            clauses.push((Occur::Must, Box::new(BooleanQuery::new(any_tag))));
+        }
+
+        for clause in &filters.facets {
+            let field = match clause.facet {
+                Facet::Tag => self.fields.tag,
+                Facet::Author => self.fields.author,
+                Facet::Layer => self.fields.layer,
+                Facet::Platform => self.fields.platform,
+                Facet::Requires => self.fields.requires,
+            };
+            let occur = if clause.negated {
+                Occur::MustNot
+            } else {
+                Occur::Must
+            };
+            clauses.push((occur, term_query(field, &clause.value)));
+        }
+
+        clauses
+    }
+
     /// Search with layer filter
     pub fn search_with_layer(
         &self,
@@ -318,6 +526,7 @@ impl Bm25Index {
                 score,
                 name,
                 layer: layer_val,
+                matched_sections: Vec::new(),
             });
         }
 
@@ -336,6 +545,17 @@ impl Bm25Index {
     }
 }
 
+/// Quality scores are stored as fixed-point u64 (two decimals) so they can be
+/// range-filtered.
+fn quality_to_u64(quality: f64) -> u64 {
+    // Safely convert quality_score to u64, handling NaN/Inf/negative values
+    if quality.is_nan() || quality.is_infinite() {
+        0
+    } else {
+        (quality.clamp(0.0, 100.0) * 100.0).round() as u64
+    }
+}
+
 fn normalize_layer(input: &str) -> &'static str {
     // Normalize layer names to match stored values: base, org, project, user
     match input.to_lowercase().as_str() {
@@ -362,16 +582,23 @@ fn build_schema() -> Schema {
     builder.add_text_field("id", STRING | STORED);
     builder.add_text_field("name", text_options.clone() | STORED);
 
-    // Searchable content (not stored, just indexed)
+    // Searchable content (indexed; body is also stored for snippets)
     builder.add_text_field("description", text_options.clone());
-    builder.add_text_field("body", text_options.clone());
+    builder.add_text_field("body", text_options.clone() | STORED);
     builder.add_text_field("tags", text_options.clone());
     builder.add_text_field("aliases", text_options);
 
     // Metadata for filtering (stored)
     builder.add_text_field("layer", STRING | STORED);
-    builder.add_u64_field("quality_score", tantivy::schema::FAST | STORED);
-    builder.add_bool_field("deprecated", STORED);
+    builder.add_u64_field("quality_score", INDEXED | FAST | STORED);
+    builder.add_bool_field("deprecated", INDEXED | STORED);
+
+    // Raw (untokenized, lowercased) facet values for filter pushdown
+    builder.add_text_field("tag", STRING);
+    builder.add_text_field("author", STRING);
+    builder.add_text_field("platform", STRING);
+    builder.add_text_field("requires", STRING);
+    builder.add_date_field("updated", INDEXED | FAST);
 
     builder.build()
 }
@@ -424,6 +651,31 @@ fn extract_fields(schema: &Schema) -> Result<BM25Fields> {
                 "missing deprecated field".into(),
             ))
         })?,
+        tag: schema.get_field("tag").map_err(|_| {
+            MsError::SearchIndex(tantivy::TantivyError::SchemaError(
+                "missing tag field".into(),
+            ))
+        })?,
+        author: schema.get_field("author").map_err(|_| {
+            MsError::SearchIndex(tantivy::TantivyError::SchemaError(
+                "missing author field".into(),
+            ))
+        })?,
+        platform: schema.get_field("platform").map_err(|_| {
+            MsError::SearchIndex(tantivy::TantivyError::SchemaError(
+                "missing platform field".into(),
+            ))
+        })?,
+        requires: schema.get_field("requires").map_err(|_| {
+            MsError::SearchIndex(tantivy::TantivyError::SchemaError(
+                "missing requires field".into(),
+            ))
+        })?,
+        updated: schema.get_field("updated").map_err(|_| {
+            MsError::SearchIndex(tantivy::TantivyError::SchemaError(
+                "missing updated field".into(),
+            ))
+        })?,
     })
 }
 
@@ -631,6 +883,89 @@ mod tests {
         assert_eq!(results.len(), 1);
     }
 
+    #[test]
+    fn test_search_filtered_pushes_down_facets() {
+        let index = Bm25Index::open_in_memory().unwrap();
+
+        // Many matching skills that the filter excludes, so post-filtering a
+        // top-k would come up empty.
+        for i in 0..30 {
+            let mut skill = make_test_skill(
+                &format!("noise-{i}"),
+                "Errors",
+                "errors errors errors",
+                "errors",
+            );
+            skill.metadata_json = r#"{"tags": ["legacy"]}"#.to_string();
+            index.index_skill(&skill).unwrap();
+        }
+        let mut target = make_test_skill("target", "Rust", "Handling errors", "Use ? for errors");
+        target.author = Some("Alice".to_string());
+        target.metadata_json =
+            r#"{"tags": ["rust"], "platforms": ["linux"], "requires": ["git"]}"#.to_string();
+        target.quality_score = 0.9;
+        target.modified_at = "2026-03-01T00:00:00Z".to_string();
+        index.index_skill(&target).unwrap();
+        let mut deprecated = make_test_skill("old", "Rust", "Old errors", "errors");
+        deprecated.metadata_json = r#"{"tags": ["rust"]}"#.to_string();
+        deprecated.is_deprecated = true;
+        index.index_skill(&deprecated).unwrap();
+        index.commit().unwrap();
+
+        let query = crate::search::SearchQuery::parse(
+            "errors tag:rust -tag:legacy author:alice platform:linux requires:git \
+             updated:>2026-01-01 quality:>0.7 layer:project",
+        )
+        .unwrap();
+        let results = index
+            .search_filtered(&query.text, &query.filters, 5, false)
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].skill_id, "target");
+
+        // Filter-only queries match every skill that passes the filters
+        let filters = crate::search::SearchQuery::parse("tag:rust")
+            .unwrap()
+            .filters;
+        let results = index.search_filtered("", &filters, 10, false).unwrap();
+        assert_eq!(results.len(), 1);
+
+        let filters =
+            SearchFilters::new()
+                .include_deprecated(true)
+                .facet(Facet::Tag, "rust", false);
+        let results = index.search_filtered("", &filters, 10, false).unwrap();
+        assert_eq!(results.len(), 2);
+
+        let mut filters = SearchFilters::new();
+        filters.updated_before = Some("2026-01-01T00:00:00Z".parse().unwrap());
+        let results = index
+            .search_filtered("errors", &filters, 50, false)
+            .unwrap();
+        assert_eq!(results.len(), 30);
+    }
+
+    #[test]
+    fn test_search_with_snippets() {
+        let index = Bm25Index::open_in_memory().unwrap();
+        let skill = make_test_skill(
+            "rust-errors",
+            "Rust Errors",
+            "Error handling",
+            "# Rust Errors\n\n## Setup\n\nInstall rustup.\n\n## Propagation\n\nUse `?` to propagate errors.\n",
+        );
+        index.index_skill(&skill).unwrap();
+        index.commit().unwrap();
+
+        let plain = index.search("propagate", 10).unwrap();
+        assert!(plain[0].matched_sections.is_empty());
+
+        let results = index.search_with_snippets("propagate", 10).unwrap();
+        let matched = &results[0].matched_sections[0];
+        assert_eq!(matched.section_id.as_deref(), Some("propagation"));
+        assert_eq!(matched.render("[", "]"), "Use `?` to [propagate] errors");
+    }
+
     #[test]
     fn test_parse_metadata() {
         let json = r#"{"tags": ["git", "workflow"], "aliases": ["commit", "version-control"]}"#;
@@ -0,0 +1,1302 @@
+//! Native command-safety evaluator.
+//!
+//! Commands are parsed into a shell AST ([`crate::core::shell`]) and every
+//! command that would run is resolved, looking through wrappers (`sudo`,
+//! `env`, `timeout`, `xargs`, ...), nested interpreters (`bash -c`, `eval`,
+//! `ssh host cmd`, heredocs fed to a shell), `find -exec` and command
+//! substitutions. Each resolved command is then matched against rule packs
+//! whose rules carry a [`SafetyTier`]; the highest tier wins and the decision
+//! records which rule fired and where.
+//!
+//! Built-in packs live in `command_rules_default.toml`. Additional packs use
+//! the same format and are loaded from `safety.rule_paths`.
+
+use std::path::Path;
+
+use glob::{MatchOptions, Pattern};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::config::SafetyConfig;
+use crate::core::safety::{DcgDecision, SafetyTier};
+use crate::core::shell::{self, Command, MAX_DEPTH, RedirectOp, Script, SimpleCommand, Word};
+use crate::error::{MsError, Result};
+use crate::utils::format::truncate_string;
+
+const DEFAULT_RULES: &str = include_str!("command_rules_default.toml");
+
+/// Pack id used for decisions made by the evaluator itself.
+const NATIVE_PACK: &str = "native";
+
+/// Commands longer than this are not analyzed (fail closed).
+const MAX_COMMAND_LEN: usize = 128 * 1024;
+
+const SHELLS: &[&str] = &["sh", "bash", "zsh", "dash", "ksh", "mksh", "ash", "fish"];
+
+/// Wrappers that run the rest of their arguments as a command, with the
+/// options that consume a value.
+const WRAPPERS: &[(&str, &[&str])] = &[
+    (
+        "sudo",
+        &["-u", "-g", "-h", "-p", "-C", "-D", "-r", "-t", "-U", "-T"],
+    ),
+    ("doas", &["-u", "-C"]),
+    ("env", &["-u", "-C", "--unset", "--chdir"]),
+    ("nohup", &[]),
+    ("exec", &["-a"]),
+    ("builtin", &[]),
+    ("command", &[]),
+    ("time", &["-f", "-o", "--format", "--output"]),
+    ("nice", &["-n", "--adjustment"]),
+    ("ionice", &["-c", "-n", "-p", "--class", "--classdata"]),
+    ("stdbuf", &["-i", "-o", "-e"]),
+    ("setsid", &[]),
+    ("chronic", &[]),
+    ("unbuffer", &[]),
+    ("caffeinate", &["-t", "-w"]),
+];
+
+/// Options that consume a value, per program, so subcommands are found after
+/// global options (`git -C repo push`).
+const OPTIONS_WITH_VALUE: &[(&str, &[&str])] = &[
+    (
+        "git",
+        &["-C", "-c", "--git-dir", "--work-tree", "--namespace"],
+    ),
+    (
+        "kubectl",
+        &[
+            "-n",
+            "--namespace",
+            "--context",
+            "--kubeconfig",
+            "--cluster",
+            "--user",
+            "-s",
+            "--server",
+        ],
+    ),
+    ("oc", &["-n", "--namespace", "--context", "--kubeconfig"]),
+    (
+        "docker",
+        &[
+            "-H",
+            "--host",
+            "--context",
+            "--config",
+            "-c",
+            "-l",
+            "--log-level",
+        ],
+    ),
+    (
+        "podman",
+        &["--connection", "-c", "--url", "--root", "--runroot"],
+    ),
+    (
+        "helm",
+        &["-n", "--namespace", "--kube-context", "--kubeconfig"],
+    ),
+    ("systemctl", &["-H", "--host", "-M", "--machine"]),
+];
+
+const XARGS_OPTIONS_WITH_VALUE: &[&str] = &[
+    "-I",
+    "-n",
+    "-P",
+    "-L",
+    "-d",
+    "-E",
+    "-s",
+    "-a",
+    "--max-args",
+    "--max-procs",
+    "--delimiter",
+    "--arg-file",
+    "--max-lines",
+    "--eof",
+    "--max-chars",
+    "--replace",
+];
+
+const SSH_OPTIONS_WITH_VALUE: &[&str] = &[
+    "-b", "-c", "-D", "-E", "-e", "-F", "-I", "-i", "-J", "-L", "-l", "-m", "-O", "-o", "-p", "-Q",
+    "-R", "-S", "-W", "-w",
+];
+
+/// A named, versionable set of rules.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RulePack {
+    pub id: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub rules: Vec<CommandRule>,
+}
+
+/// One rule. Every condition that is set must hold; entries within a list
+/// are alternatives.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CommandRule {
+    pub id: String,
+    pub tier: SafetyTier,
+    pub reason: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remediation: Option<String>,
+    /// Program name globs (matched against the basename); empty matches any.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub programs: Vec<String>,
+    /// Leading positional arguments, e.g. `["stash", "drop|clear"]`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subcommand: Vec<String>,
+    /// At least one of these flags must be present.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub flags_any: Vec<String>,
+    /// Each entry (`-r|-R`) must be present.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub flags_all: Vec<String>,
+    /// At least one argument after the subcommand must match one of these globs.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args_any: Vec<String>,
+    /// Regex matched against all arguments (and stdin heredocs) joined by spaces.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub args_regex: Option<String>,
+    /// Output redirection targets (globs).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub writes_to: Vec<String>,
+    /// The command reads output of one of these programs (pipeline or
+    /// process substitution).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stdin_from: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RuleFile {
+    #[serde(default)]
+    packs: Vec<RulePack>,
+}
+
+/// Parse rule packs from TOML (`[[packs]]` with nested `[[packs.rules]]`).
+pub fn parse_rule_packs(raw: &str) -> Result<Vec<RulePack>> {
+    let file: RuleFile =
+        toml::from_str(raw).map_err(|err| MsError::Config(format!("parse rule pack: {err}")))?;
+    Ok(file.packs)
+}
+
+/// The built-in rule packs.
+#[must_use]
+pub fn builtin_rule_packs() -> Vec<RulePack> {
+    parse_rule_packs(DEFAULT_RULES).expect("built-in command rules are valid")
+}
+
+/// Load packs from a file or every `*.toml` in a directory.
+pub fn load_rule_packs(path: &Path) -> Result<Vec<RulePack>> {
+    let mut files = Vec::new();
+    if path.is_dir() {
+        for entry in std::fs::read_dir(path)? {
+            let entry_path = entry?.path();
+            if entry_path.extension().is_some_and(|ext| ext == "toml") {
+                files.push(entry_path);
+            }
+        }
+        files.sort();
+    } else {
+        files.push(path.to_path_buf());
+    }
+
+    let mut packs = Vec::new();
+    for file in files {
+        let raw = std::fs::read_to_string(&file)
+            .map_err(|err| MsError::Config(format!("read {}: {err}", file.display())))?;
+        packs.extend(
+            parse_rule_packs(&raw)
+                .map_err(|err| MsError::Config(format!("{}: {err}", file.display())))?,
+        );
+    }
+    Ok(packs)
+}
+
+/// One rule that fired, with where it fired.
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleMatch {
+    pub pack: String,
+    pub rule_id: String,
+    pub tier: SafetyTier,
+    pub reason: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remediation: Option<String>,
+    /// Resolved command, prefixed by the wrappers it was found in.
+    pub command: String,
+}
+
+impl RuleMatch {
+    fn describe(&self) -> String {
+        format!(
+            "{} {}/{}: {} in `{}`",
+            tier_label(self.tier),
+            self.pack,
+            self.rule_id,
+            self.reason,
+            self.command
+        )
+    }
+}
+
+/// Built-in evaluator that classifies commands without external tools.
+#[derive(Debug, Clone)]
+pub struct NativeGuard {
+    packs: Vec<CompiledPack>,
+}
+
+impl NativeGuard {
+    /// Build an evaluator from rule packs, rejecting invalid patterns.
+    pub fn new(packs: Vec<RulePack>) -> Result<Self> {
+        let packs = packs
+            .into_iter()
+            .map(CompiledPack::compile)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { packs })
+    }
+
+    /// Evaluator with every built-in pack.
+    #[must_use]
+    pub fn builtin() -> Self {
+        Self::new(builtin_rule_packs()).expect("built-in command rules compile")
+    }
+
+    /// Built-in packs selected by `native_packs` (all when empty) plus packs
+    /// loaded from `rule_paths`.
+    pub fn from_config(config: &SafetyConfig) -> Result<Self> {
+        let mut packs: Vec<RulePack> = builtin_rule_packs()
+            .into_iter()
+            .filter(|pack| config.native_packs.is_empty() || config.native_packs.contains(&pack.id))
+            .collect();
+        for path in &config.rule_paths {
+            packs.extend(load_rule_packs(path)?);
+        }
+        Self::new(packs)
+    }
+
+    /// Ids of the loaded packs.
+    #[must_use]
+    pub fn pack_ids(&self) -> Vec<String> {
+        self.packs.iter().map(|pack| pack.id.clone()).collect()
+    }
+
+    /// Every rule that fires for `command`, highest tier first.
+    pub fn matches(&self, command: &str) -> std::result::Result<Vec<RuleMatch>, String> {
+        let script = shell::parse(command).map_err(|err| err.to_string())?;
+        let mut walker = Walker::default();
+        walker.script(&script, &Frame::default())?;
+
+        let mut matches = walker.findings;
+        for invocation in &walker.invocations {
+            for pack in &self.packs {
+                // Within a pack the first matching rule wins, so packs list
+                // their most specific rules first.
+                if let Some(rule) = pack.rules.iter().find(|rule| rule.matches(invocation)) {
+                    matches.push(RuleMatch {
+                        pack: pack.id.clone(),
+                        rule_id: rule.rule.id.clone(),
+                        tier: rule.rule.tier,
+                        reason: rule.rule.reason.clone(),
+                        remediation: rule.rule.remediation.clone(),
+                        command: invocation.describe(),
+                    });
+                }
+            }
+        }
+        // Stable sort keeps pack and rule order within a tier.
+        matches.sort_by_key(|m| std::cmp::Reverse(m.tier));
+        Ok(matches)
+    }
+
+    /// Classify `command`. Unparseable commands fail closed.
+    #[must_use]
+    pub fn evaluate_command(&self, command: &str) -> DcgDecision {
+        if command.trim().is_empty() {
+            return decision(SafetyTier::Safe, "empty command".to_string(), Vec::new());
+        }
+        if command.len() > MAX_COMMAND_LEN {
+            return DcgDecision {
+                rule_id: Some("too-long".to_string()),
+                pack: Some(NATIVE_PACK.to_string()),
+                ..decision(
+                    SafetyTier::Critical,
+                    "command too long for safety analysis".to_string(),
+                    Vec::new(),
+                )
+            };
+        }
+
+        let matches = match self.matches(command) {
+            Ok(matches) => matches,
+            Err(err) => {
+                return DcgDecision {
+                    rule_id: Some("parse-error".to_string()),
+                    pack: Some(NATIVE_PACK.to_string()),
+                    remediation: Some(
+                        "Simplify the command so it can be analyzed, or approve it verbatim"
+                            .to_string(),
+                    ),
+                    ..decision(
+                        SafetyTier::Danger,
+                        format!("command could not be parsed: {err}"),
+                        Vec::new(),
+                    )
+                };
+            }
+        };
+
+        let explanation: Vec<String> = matches.iter().map(RuleMatch::describe).collect();
+        let Some(top) = matches.first() else {
+            return decision(SafetyTier::Safe, "no rule matched".to_string(), explanation);
+        };
+        DcgDecision {
+            remediation: top.remediation.clone(),
+            rule_id: Some(top.rule_id.clone()),
+            pack: Some(top.pack.clone()),
+            ..decision(top.tier, top.reason.clone(), explanation)
+        }
+    }
+}
+
+fn decision(tier: SafetyTier, reason: String, explanation: Vec<String>) -> DcgDecision {
+    DcgDecision {
+        allowed: tier <= SafetyTier::Caution,
+        tier,
+        reason,
+        remediation: None,
+        rule_id: None,
+        pack: None,
+        approved: false,
+        explanation,
+    }
+}
+
+const fn tier_label(tier: SafetyTier) -> &'static str {
+    match tier {
+        SafetyTier::Safe => "[safe]",
+        SafetyTier::Caution => "[caution]",
+        SafetyTier::Danger => "[danger]",
+        SafetyTier::Critical => "[critical]",
+    }
+}
+
+// =============================================================================
+// Rule compilation and matching
+// =============================================================================
+
+#[derive(Debug, Clone)]
+struct CompiledPack {
+    id: String,
+    rules: Vec<CompiledRule>,
+}
+
+impl CompiledPack {
+    fn compile(pack: RulePack) -> Result<Self> {
+        let rules = pack
+            .rules
+            .into_iter()
+            .map(|rule| {
+                CompiledRule::compile(rule)
+                    .map_err(|err| MsError::Config(format!("rule pack {}: {err}", pack.id)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { id: pack.id, rules })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CompiledRule {
+    rule: CommandRule,
+    programs: Vec<Pattern>,
+    subcommand: Vec<Vec<Pattern>>,
+    flags_all: Vec<Vec<String>>,
+    args_any: Vec<Pattern>,
+    args_regex: Option<Regex>,
+    writes_to: Vec<Pattern>,
+    stdin_from: Vec<Pattern>,
+}
+
+impl CompiledRule {
+    fn compile(rule: CommandRule) -> Result<Self> {
+        if rule.programs.is_empty() && rule.writes_to.is_empty() {
+            return Err(MsError::Config(format!(
+                "rule {} needs `programs` or `writes_to`",
+                rule.id
+            )));
+        }
+        let globs = |patterns: &[String]| -> Result<Vec<Pattern>> {
+            patterns
+                .iter()
+                .map(|p| {
+                    Pattern::new(p).map_err(|err| {
+                        MsError::Config(format!("rule {}: invalid pattern {p:?}: {err}", rule.id))
+                    })
+                })
+                .collect()
+        };
+        let alternatives =
+            |entry: &String| entry.split('|').map(str::to_string).collect::<Vec<_>>();
+
+        Ok(Self {
+            programs: globs(&rule.programs)?,
+            subcommand: rule
+                .subcommand
+                .iter()
+                .map(|entry| globs(&alternatives(entry)))
+                .collect::<Result<_>>()?,
+            flags_all: rule.flags_all.iter().map(alternatives).collect(),
+            args_any: globs(&rule.args_any)?,
+            args_regex: rule
+                .args_regex
+                .as_deref()
+                .map(|re| {
+                    Regex::new(re).map_err(|err| {
+                        MsError::Config(format!("rule {}: invalid args_regex: {err}", rule.id))
+                    })
+                })
+                .transpose()?,
+            writes_to: globs(&rule.writes_to)?,
+            stdin_from: globs(&rule.stdin_from)?,
+            rule,
+        })
+    }
+
+    fn matches(&self, inv: &Invocation) -> bool {
+        let options = MatchOptions {
+            require_literal_separator: true,
+            ..MatchOptions::new()
+        };
+        let glob_any = |patterns: &[Pattern], value: &str| {
+            patterns.iter().any(|p| p.matches_with(value, options))
+        };
+
+        if !self.programs.is_empty() && !glob_any(&self.programs, &inv.program) {
+            return false;
+        }
+        if self.subcommand.len() > inv.positionals.len()
+            || !self
+                .subcommand
+                .iter()
+                .zip(&inv.positionals)
+                .all(|(patterns, arg)| glob_any(patterns, arg))
+        {
+            return false;
+        }
+        if !self.rule.flags_any.is_empty()
+            && !self.rule.flags_any.iter().any(|f| inv.flags.contains(f))
+        {
+            return false;
+        }
+        if !self
+            .flags_all
+            .iter()
+            .all(|alts| alts.iter().any(|f| inv.flags.contains(f)))
+        {
+            return false;
+        }
+        if !self.args_any.is_empty()
+            && !inv.positionals[self.subcommand.len()..]
+                .iter()
+                .any(|arg| glob_any(&self.args_any, &normalize_path_arg(arg)))
+        {
+            return false;
+        }
+        if let Some(re) = &self.args_regex {
+            if !re.is_match(&inv.haystack()) {
+                return false;
+            }
+        }
+        if !self.writes_to.is_empty()
+            && !inv
+                .writes
+                .iter()
+                .any(|target| glob_any(&self.writes_to, &normalize_path_arg(target)))
+        {
+            return false;
+        }
+        if !self.stdin_from.is_empty()
+            && !inv
+                .upstream
+                .iter()
+                .any(|prog| glob_any(&self.stdin_from, prog))
+        {
+            return false;
+        }
+        true
+    }
+}
+
+/// `~/` -> `~`, `/etc/` -> `/etc`, `//` -> `/`.
+fn normalize_path_arg(arg: &str) -> String {
+    let mut out = String::with_capacity(arg.len());
+    for c in arg.chars() {
+        if c == '/' && out.ends_with('/') {
+            continue;
+        }
+        out.push(c);
+    }
+    while out.len() > 1 && out.ends_with('/') {
+        out.pop();
+    }
+    out
+}
+
+// =============================================================================
+// Resolving commands from the AST
+// =============================================================================
+
+/// A command that would run, after unwrapping.
+#[derive(Debug, Clone, Default)]
+struct Invocation {
+    program: String,
+    args: Vec<String>,
+    flags: Vec<String>,
+    positionals: Vec<String>,
+    writes: Vec<String>,
+    /// Text fed to stdin (heredocs and here-strings).
+    stdin_text: Vec<String>,
+    /// Programs whose output this command reads.
+    upstream: Vec<String>,
+    /// Wrappers and interpreters this command was found inside.
+    chain: Vec<String>,
+}
+
+impl Invocation {
+    fn new(program: &str, args: &[Word], frame: &Frame) -> Self {
+        let program = basename(program);
+        let args: Vec<String> = args.iter().map(|w| w.text.clone()).collect();
+        let (flags, positionals) = split_flags(&program, &args);
+        Self {
+            program,
+            args,
+            flags,
+            positionals,
+            upstream: frame.upstream.clone(),
+            chain: frame.chain.clone(),
+            ..Self::default()
+        }
+    }
+
+    fn haystack(&self) -> String {
+        let mut parts = self.args.clone();
+        parts.extend(self.stdin_text.iter().cloned());
+        parts.join(" ")
+    }
+
+    fn describe(&self) -> String {
+        let mut command = if self.program.is_empty() {
+            String::new()
+        } else {
+            std::iter::once(self.program.as_str())
+                .chain(self.args.iter().map(String::as_str))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        for target in &self.writes {
+            if !command.is_empty() {
+                command.push(' ');
+            }
+            command.push_str("> ");
+            command.push_str(target);
+        }
+        let command = truncate_string(&command, 80);
+        if self.chain.is_empty() {
+            command
+        } else {
+            format!("{} → {command}", self.chain.join(" → "))
+        }
+    }
+}
+
+fn basename(program: &str) -> String {
+    program.rsplit('/').next().unwrap_or(program).to_string()
+}
+
+/// Split arguments into flags (bundled short flags expanded) and positionals.
+fn split_flags(program: &str, args: &[String]) -> (Vec<String>, Vec<String>) {
+    let with_value = OPTIONS_WITH_VALUE
+        .iter()
+        .find(|(name, _)| *name == program)
+        .map_or(&[][..], |(_, opts)| *opts);
+    let mut flags = Vec::new();
+    let mut positionals = Vec::new();
+    let mut iter = args.iter();
+    let mut only_positionals = false;
+    while let Some(arg) = iter.next() {
+        if only_positionals || arg == "-" || !arg.starts_with('-') {
+            positionals.push(arg.clone());
+            continue;
+        }
+        if arg == "--" {
+            only_positionals = true;
+            continue;
+        }
+        let name = arg.split_once('=').map_or(arg.as_str(), |(name, _)| name);
+        flags.push(name.to_string());
+        if !arg.starts_with("--") {
+            let letters = &arg[1..];
+            if letters.len() > 1 && letters.chars().all(|c| c.is_ascii_alphabetic()) {
+                flags.extend(letters.chars().map(|c| format!("-{c}")));
+            }
+        }
+        if with_value.contains(&name) && !arg.contains('=') {
+            iter.next();
+        }
+    }
+    (flags, positionals)
+}
+
+#[derive(Debug, Clone, Default)]
+struct Frame {
+    chain: Vec<String>,
+    upstream: Vec<String>,
+    depth: usize,
+}
+
+impl Frame {
+    fn nested(&self, label: impl Into<String>) -> std::result::Result<Self, String> {
+        if self.depth >= MAX_DEPTH {
+            return Err("command nesting too deep".to_string());
+        }
+        let mut chain = self.chain.clone();
+        chain.push(label.into());
+        Ok(Self {
+            chain,
+            upstream: self.upstream.clone(),
+            depth: self.depth + 1,
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+struct Walker {
+    invocations: Vec<Invocation>,
+    /// Findings from the evaluator itself (dynamic commands, fork bombs).
+    findings: Vec<RuleMatch>,
+}
+
+type WalkResult = std::result::Result<(), String>;
+
+impl Walker {
+    fn finding(
+        &mut self,
+        rule_id: &str,
+        tier: SafetyTier,
+        reason: String,
+        frame: &Frame,
+        command: &str,
+    ) {
+        let command = if frame.chain.is_empty() {
+            command.to_string()
+        } else {
+            format!("{} → {command}", frame.chain.join(" → "))
+        };
+        self.findings.push(RuleMatch {
+            pack: NATIVE_PACK.to_string(),
+            rule_id: rule_id.to_string(),
+            tier,
+            reason,
+            remediation: None,
+            command,
+        });
+    }
+
+    fn script(&mut self, script: &Script, frame: &Frame) -> WalkResult {
+        for pipeline in &script.pipelines {
+            let mut upstream = frame.upstream.clone();
+            for command in &pipeline.commands {
+                let stage = Frame {
+                    upstream: upstream.clone(),
+                    ..frame.clone()
+                };
+                let before = self.invocations.len();
+                self.command(command, &stage)?;
+                upstream.extend(
+                    self.invocations[before..]
+                        .iter()
+                        .map(|inv| inv.program.clone()),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn command(&mut self, command: &Command, frame: &Frame) -> WalkResult {
+        match command {
+            Command::Simple(simple) => self.simple(simple, frame),
+            Command::Subshell { body, redirects } | Command::Group { body, redirects } => {
+                self.script(body, frame)?;
+                let writes: Vec<String> = redirects
+                    .iter()
+                    .filter(|r| r.op.writes())
+                    .map(|r| r.target.text.clone())
+                    .collect();
+                if !writes.is_empty() {
+                    self.invocations.push(Invocation {
+                        writes,
+                        chain: frame.chain.clone(),
+                        ..Invocation::default()
+                    });
+                }
+                Ok(())
+            }
+            Command::Function { name, body } => {
+                let calls_itself = body_calls(body, name);
+                if calls_itself {
+                    self.finding(
+                        "recursive-function",
+                        SafetyTier::Critical,
+                        format!("function `{name}` calls itself (fork bomb pattern)"),
+                        frame,
+                        &format!("{name}()"),
+                    );
+                }
+                self.command(body, &frame.nested(format!("{name}()"))?)
+            }
+            Command::Clause(words) => self.substitutions(words, frame),
+        }
+    }
+
+    fn substitutions(&mut self, words: &[Word], frame: &Frame) -> WalkResult {
+        for word in words {
+            for script in &word.substitutions {
+                self.script(script, &frame.nested("$(…)")?)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn simple(&mut self, simple: &SimpleCommand, frame: &Frame) -> WalkResult {
+        self.substitutions(&simple.assignments, frame)?;
+        for redirect in &simple.redirects {
+            self.substitutions(std::slice::from_ref(&redirect.target), frame)?;
+        }
+
+        // Process substitutions feed the command like a pipeline would.
+        let before = self.invocations.len();
+        self.substitutions(&simple.words, frame)?;
+        let fed_by: Vec<String> = self.invocations[before..]
+            .iter()
+            .map(|inv| inv.program.clone())
+            .collect();
+        let mut frame = frame.clone();
+        frame.upstream.extend(fed_by);
+
+        let writes: Vec<String> = simple
+            .redirects
+            .iter()
+            .filter(|r| r.op.writes())
+            .map(|r| r.target.text.clone())
+            .collect();
+        let stdin: Vec<String> = simple
+            .redirects
+            .iter()
+            .filter(|r| matches!(r.op, RedirectOp::Heredoc | RedirectOp::HereString))
+            .map(|r| r.heredoc.clone().unwrap_or_else(|| r.target.text.clone()))
+            .collect();
+
+        let start = self.invocations.len();
+        if simple.words.is_empty() {
+            if !writes.is_empty() {
+                self.invocations.push(Invocation {
+                    chain: frame.chain.clone(),
+                    ..Invocation::default()
+                });
+            }
+        } else {
+            self.words(&simple.words, &stdin, &frame)?;
+        }
+        // Redirections and stdin apply to the outermost resolved command.
+        if let Some(inv) = self.invocations.get_mut(start) {
+            inv.writes.extend(writes);
+            inv.stdin_text.extend(stdin);
+        }
+        Ok(())
+    }
+
+    /// Resolve a command given as words, unwrapping wrappers and nested
+    /// interpreters.
+    fn words(&mut self, words: &[Word], stdin: &[String], frame: &Frame) -> WalkResult {
+        let mut words = words;
+        // Leading assignments (after `env`, `sudo VAR=1`, ...).
+        while words.first().is_some_and(|w| is_assignment(&w.text)) {
+            words = &words[1..];
+        }
+        let Some(program_word) = words.first() else {
+            return Ok(());
+        };
+        let args = &words[1..];
+
+        if program_word.expanded && program_word.text.starts_with('$') {
+            self.finding(
+                "dynamic-command",
+                SafetyTier::Caution,
+                format!(
+                    "command name `{}` is only known at run time",
+                    program_word.text
+                ),
+                frame,
+                &program_word.text,
+            );
+            return Ok(());
+        }
+
+        let program = basename(&program_word.text);
+        let invocation = Invocation::new(&program, args, frame);
+
+        if let Some((_, with_value)) = WRAPPERS.iter().find(|(name, _)| *name == program) {
+            // `command -v x` only looks a command up.
+            if program == "command" && args.iter().any(|a| a.text == "-v" || a.text == "-V") {
+                return Ok(());
+            }
+            let rest = skip_options(args, with_value);
+            if rest.is_empty() {
+                self.invocations.push(invocation);
+                return Ok(());
+            }
+            // `env -S "cmd args"` splits a single string.
+            if program == "env" {
+                if let Some(split) = option_value(args, "-S") {
+                    return self.nested_script(&split, "env -S", frame);
+                }
+            }
+            return self.words(rest, stdin, &frame.nested(program)?);
+        }
+
+        match program.as_str() {
+            "timeout" => {
+                let rest = skip_options(args, &["-s", "-k", "--signal", "--kill-after"]);
+                // First positional is the duration.
+                if rest.len() > 1 {
+                    return self.words(&rest[1..], stdin, &frame.nested("timeout")?);
+                }
+            }
+            "xargs" => {
+                let rest = skip_options(args, XARGS_OPTIONS_WITH_VALUE);
+                self.invocations.push(invocation);
+                let inner_frame = frame.nested("xargs")?;
+                if rest.is_empty() {
+                    return Ok(());
+                }
+                return self.words(rest, &[], &inner_frame);
+            }
+            "watch" => {
+                let rest = skip_options(args, &["-n", "--interval", "-d", "--differences"]);
+                if !rest.is_empty() {
+                    let joined = join_words(rest);
+                    return self.nested_script(&joined, "watch", frame);
+                }
+            }
+            "eval" => {
+                self.invocations.push(invocation);
+                if args.iter().any(|w| w.expanded) {
+                    self.dynamic_script("eval", frame);
+                }
+                return self.nested_script(&join_words(args), "eval", frame);
+            }
+            "ssh" => {
+                self.invocations.push(invocation);
+                let rest = skip_options(args, SSH_OPTIONS_WITH_VALUE);
+                if rest.len() > 1 {
+                    let label = format!("ssh {}", rest[0].text);
+                    return self.nested_script(&join_words(&rest[1..]), &label, frame);
+                }
+                return Ok(());
+            }
+            "find" => {
+                self.invocations.push(invocation);
+                return self.find_exec(args, frame);
+            }
+            "flock" => {
+                if let Some(script) = option_value(args, "-c") {
+                    self.invocations.push(invocation);
+                    return self.nested_script(&script, "flock -c", frame);
+                }
+                let rest = skip_options(args, &["-w", "--timeout", "-E", "--conflict-exit-code"]);
+                if rest.len() > 1 {
+                    return self.words(&rest[1..], stdin, &frame.nested("flock")?);
+                }
+            }
+            name if SHELLS.contains(&name) => {
+                self.invocations.push(invocation);
+                return self.shell(&program, args, stdin, frame);
+            }
+            _ => {}
+        }
+
+        self.invocations.push(invocation);
+        Ok(())
+    }
+
+    /// `bash -c '...'` and scripts fed to a shell on stdin.
+    fn shell(
+        &mut self,
+        program: &str,
+        args: &[Word],
+        stdin: &[String],
+        frame: &Frame,
+    ) -> WalkResult {
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            let text = arg.text.as_str();
+            if !text.starts_with('-') || text == "-" || text == "--" {
+                // A script file: its contents are not visible here.
+                return Ok(());
+            }
+            if text.starts_with("--") {
+                continue;
+            }
+            if text.contains('c') && text[1..].chars().all(|c| c.is_ascii_alphabetic()) {
+                let Some(script) = iter.next() else {
+                    return Ok(());
+                };
+                let label = format!("{program} -c");
+                if script.expanded && script.substitutions.is_empty() {
+                    self.dynamic_script(&label, frame);
+                }
+                return self.nested_script(&script.text, &label, frame);
+            }
+            if matches!(text, "-o" | "-O" | "+o" | "+O") {
+                iter.next();
+            }
+        }
+        for body in stdin {
+            self.nested_script(body, &format!("{program} <<"), frame)?;
+        }
+        Ok(())
+    }
+
+    fn find_exec(&mut self, args: &[Word], frame: &Frame) -> WalkResult {
+        let mut index = 0;
+        while index < args.len() {
+            let text = args[index].text.as_str();
+            if matches!(text, "-exec" | "-execdir" | "-ok" | "-okdir") {
+                let start = index + 1;
+                let end = args[start..]
+                    .iter()
+                    .position(|w| w.text == ";" || w.text == "+")
+                    .map_or(args.len(), |offset| start + offset);
+                let inner: Vec<Word> = args[start..end]
+                    .iter()
+                    .filter(|w| w.text != "{}")
+                    .cloned()
+                    .collect();
+                self.words(&inner, &[], &frame.nested(format!("find {text}"))?)?;
+                index = end;
+            }
+            index += 1;
+        }
+        Ok(())
+    }
+
+    fn nested_script(&mut self, src: &str, label: &str, frame: &Frame) -> WalkResult {
+        let script = shell::parse(src).map_err(|err| format!("{label}: {err}"))?;
+        self.script(&script, &frame.nested(label)?)
+    }
+
+    fn dynamic_script(&mut self, label: &str, frame: &Frame) {
+        self.finding(
+            "dynamic-script",
+            SafetyTier::Caution,
+            format!("`{label}` runs a script that is only known at run time"),
+            frame,
+            label,
+        );
+    }
+}
+
+/// Whether a function body calls the function (directly or in a pipeline).
+fn body_calls(body: &Command, name: &str) -> bool {
+    let script = Script {
+        pipelines: vec![shell::Pipeline {
+            negated: false,
+            commands: vec![body.clone()],
+        }],
+    };
+    script
+        .simple_commands()
+        .iter()
+        .any(|cmd| cmd.program().is_some_and(|w| w.text == name))
+}
+
+fn is_assignment(text: &str) -> bool {
+    text.split_once('=').is_some_and(|(name, _)| {
+        let mut chars = name.chars();
+        chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+/// Skip leading options (and the values of `with_value` options) and
+/// assignments; returns the remaining words.
+fn skip_options<'a>(args: &'a [Word], with_value: &[&str]) -> &'a [Word] {
+    let mut index = 0;
+    while let Some(arg) = args.get(index) {
+        let text = arg.text.as_str();
+        if text == "--" {
+            return &args[index + 1..];
+        }
+        if is_assignment(text) {
+            index += 1;
+            continue;
+        }
+        if !text.starts_with('-') || text == "-" {
+            break;
+        }
+        let name = text.split_once('=').map_or(text, |(name, _)| name);
+        index += if with_value.contains(&name) && !text.contains('=') {
+            2
+        } else {
+            1
+        };
+    }
+    args.get(index..).unwrap_or_default()
+}
+
+/// Value of `-x value` or `-xvalue`.
+fn option_value(args: &[Word], option: &str) -> Option<String> {
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg.text == option {
+            return iter.next().map(|w| w.text.clone());
+        }
+        if let Some(value) = arg.text.strip_prefix(option) {
+            if !value.is_empty() && !arg.text.starts_with("--") {
+                return Some(value.to_string());
+            }
+        }
+    }
+    None
+}
+
+fn join_words(words: &[Word]) -> String {
+    words
+        .iter()
+        .map(|w| w.text.as_str())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluate(command: &str) -> DcgDecision {
+        NativeGuard::builtin().evaluate_command(command)
+    }
+
+    #[test]
+    fn builtin_packs_compile() {
+        let guard = NativeGuard::builtin();
+        assert!(guard.pack_ids().contains(&"core.filesystem".to_string()));
+        assert!(guard.pack_ids().contains(&"core.git".to_string()));
+    }
+
+    #[test]
+    fn safe_commands_are_allowed() {
+        for command in ["ls -la", "git status", "cargo test", "echo hi | grep h", ""] {
+            let decision = evaluate(command);
+            assert!(decision.allowed, "{command}: {decision:?}");
+            assert_eq!(decision.tier, SafetyTier::Safe, "{command}");
+        }
+    }
+
+    #[test]
+    fn classifies_by_tier() {
+        assert_eq!(evaluate("rm notes.txt").tier, SafetyTier::Caution);
+        assert_eq!(evaluate("rm -r build").tier, SafetyTier::Danger);
+        assert_eq!(evaluate("rm -rf /").tier, SafetyTier::Critical);
+        assert_eq!(evaluate("rm -fr ~/").tier, SafetyTier::Critical);
+        assert_eq!(
+            evaluate("git push --force origin main").tier,
+            SafetyTier::Danger
+        );
+        assert_eq!(
+            evaluate("git -C repo reset --hard").tier,
+            SafetyTier::Danger
+        );
+        assert_eq!(evaluate("git push origin main").tier, SafetyTier::Safe);
+        assert_eq!(evaluate("dd if=x of=/dev/sda").tier, SafetyTier::Critical);
+        assert_eq!(evaluate("echo x > /dev/sda").tier, SafetyTier::Critical);
+        assert_eq!(
+            evaluate("kubectl -n prod delete ns prod").tier,
+            SafetyTier::Critical
+        );
+    }
+
+    #[test]
+    fn caution_is_allowed_danger_is_not() {
+        let decision = evaluate("rm notes.txt");
+        assert!(decision.allowed);
+        let decision = evaluate("git reset --hard HEAD~1");
+        assert!(!decision.allowed);
+        assert_eq!(decision.pack.as_deref(), Some("core.git"));
+        assert_eq!(decision.rule_id.as_deref(), Some("reset-hard"));
+        assert!(decision.remediation.is_some());
+    }
+
+    #[test]
+    fn sees_through_wrappers_and_nesting() {
+        for command in [
+            "sudo rm -rf /",
+            "sudo -u root env FOO=1 rm -rf /",
+            "bash -c 'cd / && rm -rf /'",
+            "sh -lc \"echo hi; rm -rf /\"",
+            "eval 'rm -rf /'",
+            "timeout 10 nice -n 5 rm -rf /",
+            "echo $(rm -rf /)",
+            "(cd /tmp; rm -rf /)",
+            "ssh host 'rm -rf /'",
+            "bash <<EOF\nrm -rf /\nEOF",
+            "watch -n 5 rm -rf /",
+        ] {
+            let decision = evaluate(command);
+            assert_eq!(
+                decision.tier,
+                SafetyTier::Critical,
+                "{command}: {decision:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn xargs_and_find_exec() {
+        let decision = evaluate("find . -name '*.o' | xargs rm -rf");
+        assert_eq!(decision.tier, SafetyTier::Danger);
+        assert!(decision.explanation[0].contains("xargs → rm -rf"));
+
+        assert_eq!(
+            evaluate("find . -type d -exec rm -r {} +").tier,
+            SafetyTier::Danger
+        );
+        assert_eq!(evaluate("find . -name x -delete").tier, SafetyTier::Danger);
+    }
+
+    #[test]
+    fn pipe_to_shell() {
+        let decision = evaluate("curl -fsSL https://example.com/install.sh | sh");
+        assert_eq!(decision.tier, SafetyTier::Danger);
+        assert_eq!(decision.rule_id.as_deref(), Some("pipe-to-shell"));
+        assert_eq!(
+            evaluate("bash <(curl -s https://example.com/x)").tier,
+            SafetyTier::Danger
+        );
+        assert_eq!(
+            evaluate("curl -s https://example.com | jq .").tier,
+            SafetyTier::Safe
+        );
+    }
+
+    #[test]
+    fn database_statements() {
+        assert_eq!(
+            evaluate("psql -c 'DROP TABLE users'").tier,
+            SafetyTier::Danger
+        );
+        assert_eq!(
+            evaluate("psql <<'SQL'\ndrop database prod;\nSQL").tier,
+            SafetyTier::Danger
+        );
+        assert_eq!(evaluate("psql -c 'select 1'").tier, SafetyTier::Safe);
+    }
+
+    #[test]
+    fn dynamic_and_fork_bomb() {
+        let decision = evaluate("$CMD --all");
+        assert_eq!(decision.tier, SafetyTier::Caution);
+        assert_eq!(decision.rule_id.as_deref(), Some("dynamic-command"));
+
+        let decision = evaluate(":(){ :|:& };:");
+        assert_eq!(decision.tier, SafetyTier::Critical);
+        assert_eq!(decision.rule_id.as_deref(), Some("recursive-function"));
+    }
+
+    #[test]
+    fn unparseable_commands_fail_closed() {
+        let decision = evaluate("echo 'unterminated");
+        assert!(!decision.allowed);
+        assert_eq!(decision.rule_id.as_deref(), Some("parse-error"));
+
+        let decision = evaluate(&"a".repeat(MAX_COMMAND_LEN + 1));
+        assert_eq!(decision.tier, SafetyTier::Critical);
+    }
+
+    #[test]
+    fn explanation_lists_every_match() {
+        let decision = evaluate("rm -rf build && git push -f");
+        assert_eq!(decision.explanation.len(), 2);
+        assert!(
+            decision
+                .explanation
+                .iter()
+                .any(|line| line.contains("core.git/push-force"))
+        );
+    }
+
+    #[test]
+    fn custom_packs_from_toml() {
+        let packs = parse_rule_packs(
+            r#"
+[[packs]]
+id = "team"
+
+[[packs.rules]]
+id = "no-prod-deploy"
+tier = "critical"
+programs = ["deploy"]
+args_any = ["prod*"]
+reason = "production deploys go through CI"
+"#,
+        )
+        .unwrap();
+        let guard = NativeGuard::new(packs).unwrap();
+        let decision = guard.evaluate_command("sudo deploy production");
+        assert_eq!(decision.tier, SafetyTier::Critical);
+        assert_eq!(decision.pack.as_deref(), Some("team"));
+        assert_eq!(
+            guard.evaluate_command("deploy staging").tier,
+            SafetyTier::Safe
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_rules() {
+        let packs = parse_rule_packs(
+            "[[packs]]\nid = \"x\"\n[[packs.rules]]\nid = \"any\"\ntier = \"danger\"\nreason = \"r\"\n",
+        )
+        .unwrap();
+        assert!(NativeGuard::new(packs).is_err());
+        assert!(parse_rule_packs("[[packs]]\nid = \"x\"\nbogus = 1\n").is_err());
+    }
+
+    #[test]
+    fn native_packs_filter() {
+        let config = SafetyConfig {
+            native_packs: vec!["core.git".to_string()],
+            ..SafetyConfig::default()
+        };
+        let guard = NativeGuard::from_config(&config).unwrap();
+        assert_eq!(guard.pack_ids(), vec!["core.git".to_string()]);
+        assert_eq!(guard.evaluate_command("rm -rf /").tier, SafetyTier::Safe);
+    }
+
+    #[test]
+    fn split_flags_expands_bundles() {
+        let args: Vec<String> = ["-rf", "--force=yes", "x", "--", "-y"]
+            .iter()
+            .map(|s| (*s).to_string())
+            .collect();
+        let (flags, positionals) = split_flags("rm", &args);
+        assert!(flags.contains(&"-r".to_string()));
+        assert!(flags.contains(&"-f".to_string()));
+        assert!(flags.contains(&"--force".to_string()));
+        assert_eq!(positionals, vec!["x", "-y"]);
+    }
+}
@@ -1,4 +1,8 @@
-//! Command safety gate backed by DCG (Destructive Command Guard).
+//! Command safety gate.
+//!
+//! Commands are evaluated by the native shell-AST evaluator
+//! (`security::command_rules`) or by DCG (Destructive Command Guard) when
+//! configured or available, and every decision is logged.
 
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -7,8 +11,11 @@ use tracing::{info, warn};
 
 use crate::app::AppContext;
 use crate::config::Config;
-use crate::core::safety::{DcgDecision, DcgGuard, SafetyTier};
+use crate::config::SafetyConfig;
+use crate::core::safety::{DcgDecision, DcgGuard, SafetyEvaluator, SafetyTier};
 use crate::error::{MsError, Result};
+use crate::security::command_rules::NativeGuard;
+use crate::security::policy::{PolicyAction, PolicyEngine};
 use crate::storage::Database;
 
 #[derive(Debug, Clone, serde::Serialize)]
@@ -21,40 +28,68 @@ pub struct CommandSafetyEvent {
     pub created_at: String,
 }
 
+/// Which evaluator produced a decision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DecisionSource {
+    Native,
+    Dcg,
+}
+
+impl DecisionSource {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Native => "native",
+            Self::Dcg => "dcg",
+        }
+    }
+}
+
+/// A decision together with the evaluator that made it.
+#[derive(Debug, Clone)]
+pub struct SafetyEvaluation {
+    pub decision: DcgDecision,
+    pub source: DecisionSource,
+    /// True when the configured evaluator could not run at all.
+    pub unavailable: bool,
+}
+
 /// Status of the safety gate and DCG availability.
 #[derive(Debug, Clone)]
 pub struct SafetyStatus {
+    /// Configured evaluator.
+    pub evaluator: SafetyEvaluator,
+    /// Evaluator that will actually be used.
+    pub active: DecisionSource,
     /// DCG version if available.
     pub dcg_version: Option<String>,
     /// Path to dcg binary.
     pub dcg_bin: PathBuf,
-    /// Loaded packs.
+    /// Loaded DCG packs.
     pub packs: Vec<String>,
+    /// Loaded native rule packs.
+    pub native_packs: Vec<String>,
+    /// Error from loading custom rule files, if any (built-ins are used).
+    pub rules_error: Option<String>,
 }
 
 #[derive(Debug, Clone)]
 pub struct SafetyGate {
     guard: DcgGuard,
+    native: NativeGuard,
+    rules_error: Option<String>,
+    evaluator: SafetyEvaluator,
     dcg_version: Option<String>,
     require_verbatim_approval: bool,
     db: Option<Arc<Database>>,
+    policy: Option<Arc<PolicyEngine>>,
 }
 
 impl SafetyGate {
     #[must_use]
     pub fn from_context(ctx: &AppContext) -> Self {
-        let guard = DcgGuard::new(
-            ctx.config.safety.dcg_bin.clone(),
-            ctx.config.safety.dcg_packs.clone(),
-            ctx.config.safety.dcg_explain_format.clone(),
-        );
-        let dcg_version = guard.version();
-        Self {
-            guard,
-            dcg_version,
-            require_verbatim_approval: ctx.config.safety.require_verbatim_approval,
-            db: Some(ctx.db.clone()),
-        }
+        Self::from_config(&ctx.config.safety, Some(ctx.db.clone())).with_policy(ctx.policy.clone())
     }
 
     pub fn from_env() -> Result<Self> {
@@ -71,45 +106,154 @@ impl SafetyGate {
                 None
             }
         };
+        let policy = Arc::new(PolicyEngine::load(&ms_root));
+        Ok(Self::from_config(&config.safety, db).with_policy(policy))
+    }
+
+    #[must_use]
+    pub fn from_config(config: &SafetyConfig, db: Option<Arc<Database>>) -> Self {
         let guard = DcgGuard::new(
-            config.safety.dcg_bin.clone(),
-            config.safety.dcg_packs.clone(),
-            config.safety.dcg_explain_format.clone(),
+            config.dcg_bin.clone(),
+            config.dcg_packs.clone(),
+            config.dcg_explain_format.clone(),
         );
-        let dcg_version = guard.version();
-        Ok(Self {
+        let dcg_version = match config.evaluator {
+            SafetyEvaluator::Native => None,
+            SafetyEvaluator::Auto | SafetyEvaluator::Dcg => guard.version(),
+        };
+        let (native, rules_error) = match NativeGuard::from_config(config) {
+            Ok(native) => (native, None),
+            Err(err) => {
+                warn!("safety gate: custom rules not loaded, using built-in packs: {err}");
+                (NativeGuard::builtin(), Some(err.to_string()))
+            }
+        };
+        Self {
             guard,
+            native,
+            rules_error,
+            evaluator: config.evaluator,
             dcg_version,
-            require_verbatim_approval: config.safety.require_verbatim_approval,
+            require_verbatim_approval: config.require_verbatim_approval,
             db,
-        })
+            policy: None,
+        }
+    }
+
+    /// Enforce `[commands]` deny patterns from the organisation policy.
+    #[must_use]
+    pub fn with_policy(mut self, policy: Arc<PolicyEngine>) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    fn policy_denial(&self, command: &str) -> Option<DcgDecision> {
+        let decision = self
+            .policy
+            .as_ref()?
+            .check(&PolicyAction::RunCommand { command });
+        if decision.allowed {
+            return None;
+        }
+        let reason = decision.reason.unwrap_or_default();
+        Some(DcgDecision::policy_denied(match decision.source {
+            Some(path) => format!("{reason} ({})", path.display()),
+            None => reason,
+        }))
+    }
+
+    /// Evaluator that `evaluate` will use.
+    #[must_use]
+    pub const fn active_source(&self) -> DecisionSource {
+        match self.evaluator {
+            SafetyEvaluator::Native => DecisionSource::Native,
+            SafetyEvaluator::Dcg => DecisionSource::Dcg,
+            SafetyEvaluator::Auto => {
+                if self.dcg_version.is_some() {
+                    DecisionSource::Dcg
+                } else {
+                    DecisionSource::Native
+                }
+            }
+        }
     }
 
     /// Get the current status of the safety gate.
     #[must_use]
     pub fn status(&self) -> SafetyStatus {
         SafetyStatus {
+            evaluator: self.evaluator,
+            active: self.active_source(),
             dcg_version: self.dcg_version.clone(),
             dcg_bin: self.guard.dcg_bin.clone(),
             packs: self.guard.packs.clone(),
+            native_packs: self.native.pack_ids(),
+            rules_error: self.rules_error.clone(),
         }
     }
 
-    pub fn enforce(&self, command: &str, session_id: Option<&str>) -> Result<()> {
-        let (mut decision, dcg_unavailable) = match self.guard.evaluate_command(command) {
-            Ok(decision) => (decision, false),
-            Err(err) => {
-                warn!("dcg unavailable: {err}");
-                (
-                    DcgDecision::unavailable(format!("dcg unavailable: {err}")),
-                    true,
-                )
-            }
+    /// Evaluate a command without enforcing or logging the decision.
+    ///
+    /// Commands denied by the organisation policy are blocked before either
+    /// evaluator runs. In `auto` mode DCG is preferred when installed; if it
+    /// fails the native evaluator decides instead of blocking.
+    #[must_use]
+    pub fn evaluate(&self, command: &str) -> SafetyEvaluation {
+        if let Some(decision) = self.policy_denial(command) {
+            return SafetyEvaluation {
+                decision,
+                source: self.active_source(),
+                unavailable: false,
+            };
+        }
+        let native = |unavailable| SafetyEvaluation {
+            decision: self.native.evaluate_command(command),
+            source: DecisionSource::Native,
+            unavailable,
         };
+        match self.active_source() {
+            DecisionSource::Native => native(false),
+            DecisionSource::Dcg => match self.guard.evaluate_command(command) {
+                Ok(decision) => SafetyEvaluation {
+                    decision,
+                    source: DecisionSource::Dcg,
+                    unavailable: false,
+                },
+                Err(err) if self.evaluator == SafetyEvaluator::Auto => {
+                    warn!("dcg failed, falling back to native evaluator: {err}");
+                    native(false)
+                }
+                Err(err) => {
+                    warn!("dcg unavailable: {err}");
+                    SafetyEvaluation {
+                        decision: DcgDecision::unavailable(format!("dcg unavailable: {err}")),
+                        source: DecisionSource::Dcg,
+                        unavailable: true,
+                    }
+                }
+            },
+        }
+    }
+
+    pub fn enforce(&self, command: &str, session_id: Option<&str>) -> Result<()> {
+        // Policy blocks cannot be approved with MS_APPROVE_COMMAND.
+        if let Some(decision) = self.policy_denial(command) {
+            self.log_event(command, &decision, session_id)?;
+            return Err(MsError::PolicyViolation(format!(
+                "command {command}: {}",
+                decision.reason
+            )));
+        }
+
+        let SafetyEvaluation {
+            mut decision,
+            source,
+            unavailable,
+        } = self.evaluate(command);
 
         if !decision.allowed {
             // If DCG is unavailable, provide a specific error explaining the situation
-            if dcg_unavailable {
+            if unavailable {
                 self.log_event(command, &decision, session_id)?;
                 return Err(MsError::DestructiveBlocked(format!(
                     "command blocked (safety system unavailable): {}. {}",
@@ -117,7 +261,7 @@ impl SafetyGate {
                     decision
                         .remediation
                         .as_deref()
-                        .unwrap_or("Install DCG to enable command evaluation")
+                        .unwrap_or("Install DCG or set safety.evaluator = \"native\"")
                 )));
             }
 
@@ -132,7 +276,8 @@ impl SafetyGate {
             } else {
                 self.log_event(command, &decision, session_id)?;
                 return Err(MsError::DestructiveBlocked(format!(
-                    "blocked by dcg: {}",
+                    "blocked by {}: {}",
+                    source.as_str(),
                     decision.reason
                 )));
             }
@@ -290,9 +435,13 @@ mod tests {
     #[test]
     fn safety_status_default_fields() {
         let status = SafetyStatus {
+            evaluator: SafetyEvaluator::Auto,
+            active: DecisionSource::Dcg,
             dcg_version: Some("1.0.0".to_string()),
             dcg_bin: PathBuf::from("/usr/bin/dcg"),
             packs: vec!["default".to_string()],
+            native_packs: vec!["core.git".to_string()],
+            rules_error: None,
         };
 
         assert_eq!(status.dcg_version, Some("1.0.0".to_string()));
@@ -303,13 +452,78 @@ mod tests {
     #[test]
     fn safety_status_no_dcg() {
         let status = SafetyStatus {
+            evaluator: SafetyEvaluator::Auto,
+            active: DecisionSource::Native,
             dcg_version: None,
             dcg_bin: PathBuf::from("/nonexistent/dcg"),
             packs: vec![],
+            native_packs: vec![],
+            rules_error: None,
         };
 
         assert!(status.dcg_version.is_none());
         assert!(status.packs.is_empty());
+        assert_eq!(status.active, DecisionSource::Native);
+    }
+
+    // =========================================================================
+    // Evaluator selection tests
+    // =========================================================================
+
+    fn gate_with(evaluator: SafetyEvaluator) -> SafetyGate {
+        let config = SafetyConfig {
+            evaluator,
+            dcg_bin: PathBuf::from("/nonexistent/dcg"),
+            ..SafetyConfig::default()
+        };
+        SafetyGate::from_config(&config, None)
+    }
+
+    #[test]
+    fn auto_without_dcg_uses_native() {
+        let gate = gate_with(SafetyEvaluator::Auto);
+        assert_eq!(gate.active_source(), DecisionSource::Native);
+
+        let eval = gate.evaluate("git push --force origin main");
+        assert_eq!(eval.source, DecisionSource::Native);
+        assert!(!eval.unavailable);
+        assert!(!eval.decision.allowed);
+        assert!(gate.evaluate("git status").decision.allowed);
+    }
+
+    #[test]
+    fn native_gate_blocks_with_source_in_message() {
+        let config = SafetyConfig {
+            evaluator: SafetyEvaluator::Native,
+            require_verbatim_approval: false,
+            ..SafetyConfig::default()
+        };
+        let gate = SafetyGate::from_config(&config, None);
+        let err = gate.enforce("rm -rf /", None).unwrap_err();
+        assert!(err.to_string().contains("blocked by native"), "{err}");
+        assert!(gate.enforce("ls -la", None).is_ok());
+    }
+
+    #[test]
+    fn dcg_mode_without_dcg_is_unavailable() {
+        let gate = gate_with(SafetyEvaluator::Dcg);
+        let eval = gate.evaluate("ls");
+        assert_eq!(eval.source, DecisionSource::Dcg);
+        assert!(eval.unavailable);
+        assert!(!eval.decision.allowed);
+    }
+
+    #[test]
+    fn bad_rule_path_falls_back_to_builtin_packs() {
+        let config = SafetyConfig {
+            evaluator: SafetyEvaluator::Native,
+            rule_paths: vec![PathBuf::from("/nonexistent/rules.toml")],
+            ..SafetyConfig::default()
+        };
+        let gate = SafetyGate::from_config(&config, None);
+        let status = gate.status();
+        assert!(status.rules_error.is_some());
+        assert!(status.native_packs.contains(&"core.git".to_string()));
     }
 
     // =========================================================================
@@ -333,6 +547,7 @@ mod tests {
                 rule_id: Some("R001".to_string()),
                 pack: Some("default".to_string()),
                 approved: false,
+                explanation: Vec::new(),
             },
             created_at: "2024-01-01T00:00:00Z".to_string(),
         };
@@ -1,20 +1,48 @@
 //! Security features for ms (prompt injection, command safety, audits).
 
 pub mod acip;
+pub mod command_rules;
 pub mod command_safety;
+pub mod hidden_content;
 pub mod path_policy;
+pub mod policy;
+pub mod secret_history;
 pub mod secret_scanner;
+pub mod skill_signing;
 
 pub use acip::{
     AcipAnalysis, AcipClassification, AcipConfig, AcipEngine, ContentSource, QuarantineRecord,
     TrustBoundaryConfig, TrustLevel, contains_injection_patterns, contains_sensitive_data,
 };
-pub use command_safety::{CommandSafetyEvent, SafetyGate, SafetyStatus};
+pub use command_rules::{
+    CommandRule, NativeGuard, RuleMatch, RulePack, builtin_rule_packs, load_rule_packs,
+    parse_rule_packs,
+};
+pub use command_safety::{
+    CommandSafetyEvent, DecisionSource, SafetyEvaluation, SafetyGate, SafetyStatus,
+};
+pub use hidden_content::{
+    HIDDEN_CONTENT_THRESHOLD, HiddenFinding, HiddenKind, scan_hidden_content, strip_invisible,
+};
 pub use path_policy::{
     PathPolicyViolation, canonicalize_with_root, deny_symlink_escape, is_under_root,
     normalize_path, safe_join, validate_path_component,
 };
+pub use policy::{
+    LayerPolicy, POLICY_FILE, POLICY_SIGNATURE_FILE, POLICY_TRUST_FILE, Policy, PolicyAction,
+    PolicyDecision, PolicyEngine, PolicyTrust, SYSTEM_POLICY_DIR, policy_content_hash,
+    policy_signature_path, policy_sources, read_policy_signatures,
+};
+pub use secret_history::{
+    HistoryFinding, HistoryRewrite, HistoryScan, rewrite_history, scan_history,
+};
 pub use secret_scanner::{
-    SecretMatch, SecretScanSummary, SecretType, contains_secrets, redact_secrets,
-    redact_secrets_typed, scan_secrets, scan_secrets_summary,
+    CustomSecretPattern, EntropyConfig, ScanContext, SecretAllowlist, SecretMatch,
+    SecretScanConfig, SecretScanSummary, SecretScanner, SecretType, contains_secrets,
+    redact_secrets, redact_secrets_typed, scan_secrets, scan_secrets_summary,
+};
+pub use skill_signing::{
+    AssetHashes, SIGNATURE_FILE, SignatureStatus, SigningConfig, SkillSignature, SkillSignatures,
+    TrustAction, TrustDecision, TrustRule, sign_content_hash, signed_content_hash,
+    spec_content_hash, verify_signatures,
 };
@@ -0,0 +1,538 @@
+//! Detached skill signatures and load-time trust policy.
+//!
+//! Signatures are Ed25519 over a skill's content hash: the SHA-256 of its
+//! serialized `SkillSpec` (the value stored as `content_hash` in the index),
+//! combined with the hash of every asset file when the skill has any (see
+//! [`signed_content_hash`]). They live in `skill.sig.json` next to the skill
+//! in the archive, so they travel with sync and survive re-indexing. Editing
+//! a skill or one of its scripts changes the hash, which leaves the old
+//! signatures in place but no longer matching. Load-time checks recompute the
+//! hash from the body being disclosed and the files on disk rather than
+//! trusting the indexed value.
+//!
+//! The trust policy maps a skill's layer and id to an action for skills
+//! that are not signed by a trusted key.
+
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::bundler::{
+    BundleSignature, Ed25519Signer, Ed25519Verifier, SignatureVerifier,
+    parse_openssh_ed25519_public_key,
+};
+use crate::core::SkillSpec;
+use crate::core::skill::SkillAssets;
+use crate::error::{MsError, Result};
+
+/// File holding a skill's signatures inside its archive directory.
+pub const SIGNATURE_FILE: &str = "skill.sig.json";
+
+/// All signatures recorded for one skill.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillSignatures {
+    #[serde(default)]
+    pub signatures: Vec<SkillSignature>,
+}
+
+/// One detached signature over a content hash.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillSignature {
+    /// Content hash that was signed
+    pub content_hash: String,
+    #[serde(flatten)]
+    pub signature: BundleSignature,
+    /// RFC 3339 timestamp
+    pub signed_at: String,
+}
+
+impl SkillSignatures {
+    /// Add a signature, replacing any earlier one from the same key.
+    pub fn add(&mut self, signature: SkillSignature) {
+        self.signatures
+            .retain(|existing| existing.signature.key_id != signature.signature.key_id);
+        self.signatures.push(signature);
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.signatures.is_empty()
+    }
+}
+
+/// Content hash of a spec, matching the hash stored in the index.
+pub fn spec_content_hash(spec: &SkillSpec) -> Result<String> {
+    let json = serde_json::to_string(spec)?;
+    Ok(hex::encode(Sha256::digest(json.as_bytes())))
+}
+
+/// SHA-256 of a skill's asset files, keyed by path relative to the skill
+/// directory: everything under `scripts/`, `references/` and `tests/`, plus
+/// declared script entry points elsewhere in the directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct AssetHashes {
+    files: BTreeMap<String, String>,
+}
+
+impl AssetHashes {
+    /// Hash the asset files of `skill_dir`; `scripts` are extra relative
+    /// paths to include. Paths that leave the directory or do not exist are
+    /// skipped.
+    pub fn collect<'a>(
+        skill_dir: &Path,
+        scripts: impl IntoIterator<Item = &'a Path>,
+    ) -> Result<Self> {
+        let declared: Vec<&Path> = scripts
+            .into_iter()
+            .filter(|path| {
+                path.components()
+                    .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+                    && skill_dir.join(path).is_file()
+            })
+            .collect();
+        let assets = SkillAssets::collect(skill_dir)?;
+        let listed = assets
+            .scripts
+            .iter()
+            .map(|file| file.path.as_path())
+            .chain(assets.references.iter().map(|file| file.path.as_path()))
+            .chain(assets.tests.iter().map(|file| file.path.as_path()));
+        let mut files = BTreeMap::new();
+        for rel in listed.chain(declared.iter().copied()) {
+            let bytes = std::fs::read(skill_dir.join(rel))?;
+            files.insert(
+                rel.to_string_lossy().replace('\\', "/"),
+                hex::encode(Sha256::digest(&bytes)),
+            );
+        }
+        Ok(Self { files })
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Check that `dir` holds exactly these asset files with these hashes.
+    pub fn verify(&self, dir: &Path) -> Result<()> {
+        let current = Self::collect(dir, self.files.keys().map(Path::new))?;
+        if let Some(path) = self
+            .files
+            .iter()
+            .find(|(path, hash)| current.files.get(*path) != Some(*hash))
+            .map(|(path, _)| path)
+            .or_else(|| {
+                current
+                    .files
+                    .keys()
+                    .find(|path| !self.files.contains_key(*path))
+            })
+        {
+            return Err(MsError::ValidationFailed(format!(
+                "skill asset {path} changed after it was verified"
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Hash that signatures cover: the spec hash alone for a skill without
+/// assets, otherwise a hash over the spec hash and every asset's path and
+/// hash.
+#[must_use]
+pub fn signed_content_hash(spec_hash: &str, assets: &AssetHashes) -> String {
+    if assets.is_empty() {
+        return spec_hash.to_string();
+    }
+    let mut hasher = Sha256::new();
+    hasher.update(spec_hash.as_bytes());
+    for (path, hash) in &assets.files {
+        hasher.update(b"\0");
+        hasher.update(path.as_bytes());
+        hasher.update(b"\0");
+        hasher.update(hash.as_bytes());
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Sign a content hash.
+#[must_use]
+pub fn sign_content_hash(
+    content_hash: &str,
+    signer: &Ed25519Signer,
+    signer_name: &str,
+) -> SkillSignature {
+    SkillSignature {
+        content_hash: content_hash.to_string(),
+        signature: signer.sign(content_hash.as_bytes(), signer_name),
+        signed_at: chrono::Utc::now().to_rfc3339(),
+    }
+}
+
+/// Outcome of checking a skill's signatures.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum SignatureStatus {
+    /// Signed by a trusted key over the current content
+    Trusted { key_id: String, signer: String },
+    /// Signed over the current content, but only by unknown keys
+    Untrusted { key_ids: Vec<String> },
+    /// No signatures at all
+    Unsigned,
+    /// Signatures exist, but none cover the current content
+    Modified,
+    /// A trusted key's signature does not verify
+    Invalid { key_id: String },
+}
+
+impl SignatureStatus {
+    #[must_use]
+    pub const fn is_trusted(&self) -> bool {
+        matches!(self, Self::Trusted { .. })
+    }
+
+    #[must_use]
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Self::Trusted { .. } => "trusted",
+            Self::Untrusted { .. } => "untrusted",
+            Self::Unsigned => "unsigned",
+            Self::Modified => "modified",
+            Self::Invalid { .. } => "invalid",
+        }
+    }
+}
+
+/// Check `signatures` against the current `content_hash`.
+#[must_use]
+pub fn verify_signatures(
+    content_hash: &str,
+    signatures: &SkillSignatures,
+    verifier: &Ed25519Verifier,
+) -> SignatureStatus {
+    if signatures.is_empty() {
+        return SignatureStatus::Unsigned;
+    }
+    let current: Vec<&SkillSignature> = signatures
+        .signatures
+        .iter()
+        .filter(|sig| sig.content_hash == content_hash)
+        .collect();
+    if current.is_empty() {
+        return SignatureStatus::Modified;
+    }
+
+    let mut invalid = None;
+    for sig in &current {
+        if !verifier.is_trusted(&sig.signature.key_id) {
+            continue;
+        }
+        if verifier
+            .verify(content_hash.as_bytes(), &sig.signature)
+            .is_ok()
+        {
+            return SignatureStatus::Trusted {
+                key_id: sig.signature.key_id.clone(),
+                signer: sig.signature.signer.clone(),
+            };
+        }
+        invalid.get_or_insert_with(|| sig.signature.key_id.clone());
+    }
+    if let Some(key_id) = invalid {
+        return SignatureStatus::Invalid { key_id };
+    }
+    SignatureStatus::Untrusted {
+        key_ids: current
+            .iter()
+            .map(|sig| sig.signature.key_id.clone())
+            .collect(),
+    }
+}
+
+/// What to do with a skill that is not signed by a trusted key.
+///
+/// Variants are ordered from least to most restrictive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrustAction {
+    /// Load normally
+    #[default]
+    Allow,
+    /// Load normally and report a warning
+    Warn,
+    /// Load at minimal disclosure only
+    Minimal,
+    /// Refuse to load
+    Deny,
+}
+
+impl TrustAction {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Allow => "allow",
+            Self::Warn => "warn",
+            Self::Minimal => "minimal",
+            Self::Deny => "deny",
+        }
+    }
+}
+
+/// One trust policy entry. The first entry matching a skill applies.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrustRule {
+    /// Source layer to match (`base`, `org`, `project`, `user`); any when unset
+    #[serde(default)]
+    pub layer: Option<String>,
+    /// Glob over skill ids; any when unset
+    #[serde(default)]
+    pub skills: Option<String>,
+    /// Action for skills without a trusted signature
+    #[serde(default)]
+    pub untrusted: TrustAction,
+}
+
+impl TrustRule {
+    pub(crate) fn matches(&self, skill_id: &str, layer: &str) -> bool {
+        if let Some(expected) = &self.layer {
+            if !expected.eq_ignore_ascii_case(layer) {
+                return false;
+            }
+        }
+        if let Some(pattern) = &self.skills {
+            return glob::Pattern::new(pattern).is_ok_and(|p| p.matches(skill_id));
+        }
+        true
+    }
+}
+
+/// `[security.signing]` configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SigningConfig {
+    /// Trusted public keys: OpenSSH `ssh-ed25519 ...` lines or paths to `.pub` files
+    #[serde(default)]
+    pub trusted_keys: Vec<String>,
+    /// Private key used by `ms security sign` when `--key` is not given
+    #[serde(default)]
+    pub key: Option<PathBuf>,
+    /// Trust rules, checked in order
+    #[serde(default)]
+    pub policy: Vec<TrustRule>,
+}
+
+/// Result of applying the trust policy to one skill.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TrustDecision {
+    pub status: SignatureStatus,
+    pub action: TrustAction,
+    /// Index of the policy rule that applied, if any
+    pub rule: Option<usize>,
+    /// Organisation policy file that made the action stricter, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub policy: Option<String>,
+}
+
+impl TrustDecision {
+    /// Human-readable reason for a non-allow action.
+    #[must_use]
+    pub fn reason(&self, skill_id: &str) -> String {
+        let detail = match &self.status {
+            SignatureStatus::Trusted { signer, .. } => format!("signed by {signer}"),
+            SignatureStatus::Untrusted { key_ids } => {
+                format!("signed only by untrusted keys ({})", key_ids.join(", "))
+            }
+            SignatureStatus::Unsigned => "not signed".to_string(),
+            SignatureStatus::Modified => "modified since it was signed".to_string(),
+            SignatureStatus::Invalid { key_id } => {
+                format!("has an invalid signature from {key_id}")
+            }
+        };
+        match &self.policy {
+            Some(policy) => format!("skill {skill_id} is {detail} (policy {policy})"),
+            None => format!("skill {skill_id} is {detail}"),
+        }
+    }
+}
+
+impl SigningConfig {
+    /// Build a verifier from `trusted_keys`.
+    pub fn verifier(&self) -> Result<Ed25519Verifier> {
+        let mut verifier = Ed25519Verifier::new();
+        for entry in &self.trusted_keys {
+            let line = if entry.trim_start().starts_with("ssh-") {
+                entry.clone()
+            } else {
+                std::fs::read_to_string(entry)
+                    .map_err(|err| MsError::Config(format!("read trusted key {entry}: {err}")))?
+            };
+            let (key_id, public_key) = parse_openssh_ed25519_public_key(line.trim())
+                .map_err(|err| MsError::Config(format!("trusted key {entry}: {err}")))?;
+            verifier.add_key(key_id, public_key);
+        }
+        Ok(verifier)
+    }
+
+    /// Apply the policy to a skill with the given signature status.
+    #[must_use]
+    pub fn decide(&self, skill_id: &str, layer: &str, status: SignatureStatus) -> TrustDecision {
+        if status.is_trusted() {
+            return TrustDecision {
+                status,
+                action: TrustAction::Allow,
+                rule: None,
+                policy: None,
+            };
+        }
+        let matched = self
+            .policy
+            .iter()
+            .position(|rule| rule.matches(skill_id, layer));
+        let action = match matched {
+            Some(index) => self.policy[index].untrusted,
+            // Tampering is worth surfacing even without a policy.
+            None if matches!(
+                status,
+                SignatureStatus::Modified | SignatureStatus::Invalid { .. }
+            ) =>
+            {
+                TrustAction::Warn
+            }
+            None => TrustAction::Allow,
+        };
+        TrustDecision {
+            status,
+            action,
+            rule: matched,
+            policy: None,
+        }
+    }
+
+    /// Verify signatures and apply the policy in one step.
+    pub fn evaluate(
+        &self,
+        skill_id: &str,
+        layer: &str,
+        content_hash: &str,
+        signatures: &SkillSignatures,
+    ) -> Result<TrustDecision> {
+        let status = verify_signatures(content_hash, signatures, &self.verifier()?);
+        Ok(self.decide(skill_id, layer, status))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::rand::SystemRandom;
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+
+    fn keypair() -> (Ed25519KeyPair, String) {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        let pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        let key_id = crate::bundler::ed25519_key_id(pair.public_key().as_ref());
+        (pair, key_id)
+    }
+
+    fn sign(pair: &Ed25519KeyPair, key_id: &str, hash: &str) -> SkillSignature {
+        SkillSignature {
+            content_hash: hash.to_string(),
+            signature: BundleSignature {
+                signer: "Org Security".to_string(),
+                key_id: key_id.to_string(),
+                signature: hex::encode(pair.sign(hash.as_bytes()).as_ref()),
+            },
+            signed_at: "2026-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn verify_reports_each_status() {
+        let (trusted, trusted_id) = keypair();
+        let (other, other_id) = keypair();
+        let mut verifier = Ed25519Verifier::new();
+        verifier.add_key(&trusted_id, trusted.public_key().as_ref().to_vec());
+
+        let empty = SkillSignatures::default();
+        assert_eq!(
+            verify_signatures("h1", &empty, &verifier),
+            SignatureStatus::Unsigned
+        );
+
+        let mut sigs = SkillSignatures::default();
+        sigs.add(sign(&other, &other_id, "h1"));
+        assert_eq!(
+            verify_signatures("h1", &sigs, &verifier),
+            SignatureStatus::Untrusted {
+                key_ids: vec![other_id.clone()]
+            }
+        );
+        assert_eq!(
+            verify_signatures("h2", &sigs, &verifier),
+            SignatureStatus::Modified
+        );
+
+        sigs.add(sign(&trusted, &trusted_id, "h1"));
+        assert!(verify_signatures("h1", &sigs, &verifier).is_trusted());
+
+        // A signature from a trusted key made with a different key is invalid.
+        let mut forged = SkillSignatures::default();
+        forged.add(sign(&other, &trusted_id, "h1"));
+        assert_eq!(
+            verify_signatures("h1", &forged, &verifier),
+            SignatureStatus::Invalid { key_id: trusted_id }
+        );
+    }
+
+    #[test]
+    fn add_replaces_signature_from_same_key() {
+        let (pair, key_id) = keypair();
+        let mut sigs = SkillSignatures::default();
+        sigs.add(sign(&pair, &key_id, "old"));
+        sigs.add(sign(&pair, &key_id, "new"));
+        assert_eq!(sigs.signatures.len(), 1);
+        assert_eq!(sigs.signatures[0].content_hash, "new");
+    }
+
+    #[test]
+    fn policy_first_matching_rule_wins() {
+        let config = SigningConfig {
+            policy: vec![
+                TrustRule {
+                    layer: Some("org".to_string()),
+                    skills: None,
+                    untrusted: TrustAction::Deny,
+                },
+                TrustRule {
+                    layer: None,
+                    skills: Some("community-*".to_string()),
+                    untrusted: TrustAction::Minimal,
+                },
+            ],
+            ..SigningConfig::default()
+        };
+
+        let org = config.decide("style", "org", SignatureStatus::Unsigned);
+        assert_eq!(org.action, TrustAction::Deny);
+        assert_eq!(org.rule, Some(0));
+
+        let community = config.decide("community-pdf", "user", SignatureStatus::Unsigned);
+        assert_eq!(community.action, TrustAction::Minimal);
+
+        let other = config.decide("local", "project", SignatureStatus::Unsigned);
+        assert_eq!(other.action, TrustAction::Allow);
+        let tampered = config.decide("local", "project", SignatureStatus::Modified);
+        assert_eq!(tampered.action, TrustAction::Warn);
+
+        let signed = config.decide(
+            "style",
+            "org",
+            SignatureStatus::Trusted {
+                key_id: "k".to_string(),
+                signer: "s".to_string(),
+            },
+        );
+        assert_eq!(signed.action, TrustAction::Allow);
+    }
+}
@@ -15,10 +15,12 @@ use tempfile::TempDir;
 use walkdir::WalkDir;
 
 use crate::app::AppContext;
+use crate::core::script_tools::ScriptTool;
+use crate::core::shell;
 use crate::core::skill::{BlockType, SkillSpec};
 use crate::core::spec_lens::parse_markdown;
 use crate::error::{MsError, Result};
-use crate::security::SafetyGate;
+use crate::security::{AssetHashes, SafetyGate};
 use crate::storage::sqlite::SkillRecord;
 
 #[derive(Debug, Clone)]
@@ -117,6 +119,19 @@ pub struct SimulationReport {
     pub warnings: Vec<String>,
 }
 
+/// Result of running a skill script tool.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScriptRun {
+    pub tool: String,
+    pub skill_id: String,
+    pub command: String,
+    pub exit_code: i32,
+    pub duration_ms: u64,
+    pub stdout: String,
+    pub stderr: String,
+    pub fs_changes: FileSystemChanges,
+}
+
 #[derive(Debug, Clone)]
 enum SimElement {
     Command {
@@ -261,6 +276,22 @@ impl<'a> SimulationEngine<'a> {
         })
     }
 
+    /// Run a skill script tool from a temporary copy of the skill
+    /// directory, with the tool's timeout and the config's output limit.
+    /// The copy must match `assets`, the hashes the caller verified. It is
+    /// a scratch workspace, not isolation: the script runs with the
+    /// caller's privileges, so only shell scripts whose every command
+    /// clears the simulation guard and `SafetyGate` are run.
+    pub fn run_script(
+        &self,
+        tool: &ScriptTool,
+        arguments: &serde_json::Value,
+        assets: &AssetHashes,
+        config: SimulationConfig,
+    ) -> Result<ScriptRun> {
+        run_script_in_workspace(&self.safety, tool, arguments, assets, config)
+    }
+
     fn run_command(
         &self,
         sandbox: &mut SimulationSandbox,
@@ -323,6 +354,115 @@ impl<'a> SimulationEngine<'a> {
     }
 }
 
+fn run_script_in_workspace(
+    safety: &SafetyGate,
+    tool: &ScriptTool,
+    arguments: &serde_json::Value,
+    assets: &AssetHashes,
+    config: SimulationConfig,
+) -> Result<ScriptRun> {
+    let rel = tool.relative_path()?;
+    let script = rel.to_string_lossy().replace('\\', "/");
+    let argv = tool.build_args(arguments)?;
+
+    let config = SimulationConfig {
+        command_timeout: tool.timeout(),
+        ..config
+    };
+    let mut sandbox = SimulationSandbox::new(config)?;
+    sandbox.setup_fixtures(&tool.skill_dir)?;
+    // Check the copy that will run, not the source, which may change again.
+    assets.verify(sandbox.workspace.path())?;
+    let body = std::fs::read_to_string(sandbox.workspace.path().join(rel)).map_err(|err| {
+        MsError::ValidationFailed(format!("script {script} is not readable text: {err}"))
+    })?;
+    check_script_body(safety, &script, &body, tool.interpreter()?.as_deref())?;
+    sandbox.guard_command(&body)?;
+
+    let mut parts = match tool.interpreter()? {
+        Some(interpreter) => vec![shell_quote(&interpreter), shell_quote(&script)],
+        None => vec![shell_quote(&format!("./{script}"))],
+    };
+    parts.extend(argv.iter().map(|arg| shell_quote(arg)));
+    let command = parts.join(" ");
+    sandbox.guard_command(&command)?;
+    safety.enforce(&command, None)?;
+
+    let start = Instant::now();
+    let result = sandbox.execute_command(&command, None)?;
+    Ok(ScriptRun {
+        tool: tool.name().to_string(),
+        skill_id: tool.skill_id.clone(),
+        command,
+        exit_code: result.exit_code,
+        duration_ms: start.elapsed().as_millis() as u64,
+        stdout: result.stdout,
+        stderr: result.stderr,
+        fs_changes: sandbox.fs_changes()?,
+    })
+}
+
+/// Shells whose scripts the command parser understands.
+const CHECKABLE_SHELLS: &[&str] = &["sh", "bash", "dash"];
+
+/// Refuse a script unless it is a shell script that parses completely, names
+/// every program statically, and clears the `SafetyGate` as a whole (the
+/// native evaluator walks every command, including functions and
+/// substitutions).
+fn check_script_body(
+    safety: &SafetyGate,
+    script: &str,
+    body: &str,
+    interpreter: Option<&str>,
+) -> Result<()> {
+    let shell = interpreter.or_else(|| shebang_program(body));
+    if !shell.is_some_and(|shell| CHECKABLE_SHELLS.contains(&shell)) {
+        return Err(MsError::ValidationFailed(format!(
+            "script {script} cannot be checked by the safety gate: only {} scripts can run",
+            CHECKABLE_SHELLS.join("/")
+        )));
+    }
+    let parsed = shell::parse_strict(body).map_err(|err| {
+        MsError::ValidationFailed(format!("script {script} cannot be parsed: {err}"))
+    })?;
+    if let Some(dynamic) = parsed
+        .simple_commands()
+        .into_iter()
+        .filter_map(shell::SimpleCommand::program)
+        .find(|program| program.expanded)
+    {
+        return Err(MsError::ValidationFailed(format!(
+            "script {script} cannot be evaluated: command name `{}` is computed at run time",
+            dynamic.text
+        )));
+    }
+    safety.enforce(body, None)
+}
+
+/// Program named by a `#!` line, looking through `env`.
+fn shebang_program(body: &str) -> Option<&str> {
+    let line = body.lines().next()?.strip_prefix("#!")?;
+    let mut words = line.split_whitespace();
+    let mut program = words.next()?.rsplit('/').next()?;
+    if program == "env" {
+        program = words.find(|word| !word.starts_with('-'))?;
+    }
+    Some(program)
+}
+
+/// Quote an argument for `sh -c`.
+fn shell_quote(arg: &str) -> String {
+    let plain = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c));
+    if plain {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
 fn resolve_skill(ctx: &AppContext, skill_ref: &str) -> Result<SkillRecord> {
     if let Some(skill) = ctx.db.get_skill(skill_ref)? {
         return Ok(skill);
@@ -764,6 +904,7 @@ mod tests {
                     id: "setup-block-1".to_string(),
                     block_type: BlockType::Code,
                     content: "```bash\n# comment\n$ echo skip\nls\n```".to_string(),
+                    suppressions: Vec::new(),
                 }],
             }],
             ..Default::default()
@@ -776,4 +917,171 @@ mod tests {
             _ => panic!("expected command element"),
         }
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn run_script_in_workspace_copy() {
+        use crate::config::SafetyConfig;
+        use crate::core::skill::{ScriptArg, ScriptEntry};
+
+        let skill_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(skill_dir.path().join("scripts")).unwrap();
+        std::fs::write(
+            skill_dir.path().join("scripts/greet.sh"),
+            "echo \"hello $2\" > greeting.txt\ncat greeting.txt\n",
+        )
+        .unwrap();
+        let tool = ScriptTool::new(
+            "greeter",
+            skill_dir.path(),
+            ScriptEntry {
+                name: "greet".to_string(),
+                path: "scripts/greet.sh".to_string(),
+                args: vec![ScriptArg {
+                    name: "name".to_string(),
+                    required: true,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        );
+        let config = SafetyConfig {
+            evaluator: crate::core::safety::SafetyEvaluator::Native,
+            ..Default::default()
+        };
+        let safety = SafetyGate::from_config(&config, None);
+        let assets = AssetHashes::collect(skill_dir.path(), []).unwrap();
+
+        let run = run_script_in_workspace(
+            &safety,
+            &tool,
+            &serde_json::json!({"name": "it's me"}),
+            &assets,
+            SimulationConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(run.exit_code, 0, "stderr: {}", run.stderr);
+        assert_eq!(run.stdout.trim(), "hello it's me");
+        assert_eq!(run.fs_changes.created, vec!["greeting.txt".to_string()]);
+        assert!(!skill_dir.path().join("greeting.txt").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn script_tampered_after_signing_is_refused() {
+        use crate::bundler::{BundleSignature, Ed25519Verifier, ed25519_key_id};
+        use crate::config::SafetyConfig;
+        use crate::core::skill::ScriptEntry;
+        use crate::security::{
+            SignatureStatus, SigningConfig, SkillSignature, SkillSignatures, TrustAction,
+            TrustRule, signed_content_hash, verify_signatures,
+        };
+        use ring::rand::SystemRandom;
+        use ring::signature::{Ed25519KeyPair, KeyPair};
+
+        let skill_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(skill_dir.path().join("scripts")).unwrap();
+        let script = skill_dir.path().join("scripts/hello.sh");
+        std::fs::write(&script, "echo hello\n").unwrap();
+        let tool = ScriptTool::new(
+            "greeter",
+            skill_dir.path(),
+            ScriptEntry {
+                name: "hello".to_string(),
+                path: "scripts/hello.sh".to_string(),
+                ..Default::default()
+            },
+        );
+
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        let pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        let key_id = ed25519_key_id(pair.public_key().as_ref());
+        let mut verifier = Ed25519Verifier::new();
+        verifier.add_key(&key_id, pair.public_key().as_ref().to_vec());
+
+        let assets = AssetHashes::collect(skill_dir.path(), []).unwrap();
+        let signed_hash = signed_content_hash("spec-hash", &assets);
+        assert_ne!(signed_hash, "spec-hash");
+        let mut signatures = SkillSignatures::default();
+        signatures.add(SkillSignature {
+            content_hash: signed_hash.clone(),
+            signature: BundleSignature {
+                signer: "Org Security".to_string(),
+                key_id,
+                signature: hex::encode(pair.sign(signed_hash.as_bytes()).as_ref()),
+            },
+            signed_at: "2026-01-01T00:00:00Z".to_string(),
+        });
+        assert!(verify_signatures(&signed_hash, &signatures, &verifier).is_trusted());
+
+        std::fs::write(&script, "echo hello\nrm -rf \"$HOME/notes\"\n").unwrap();
+
+        // Checked after the edit: the signature no longer covers the files.
+        let current = AssetHashes::collect(skill_dir.path(), []).unwrap();
+        let status = verify_signatures(
+            &signed_content_hash("spec-hash", &current),
+            &signatures,
+            &verifier,
+        );
+        assert_eq!(status, SignatureStatus::Modified);
+        let signing = SigningConfig {
+            policy: vec![TrustRule {
+                layer: None,
+                skills: None,
+                untrusted: TrustAction::Deny,
+            }],
+            ..Default::default()
+        };
+        assert_eq!(
+            signing.decide("greeter", "project", status).action,
+            TrustAction::Deny
+        );
+
+        // Checked before the edit: the run still refuses the changed copy.
+        let config = SafetyConfig {
+            evaluator: crate::core::safety::SafetyEvaluator::Native,
+            ..Default::default()
+        };
+        let err = run_script_in_workspace(
+            &SafetyGate::from_config(&config, None),
+            &tool,
+            &serde_json::Value::Null,
+            &assets,
+            SimulationConfig::default(),
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string().contains("scripts/hello.sh changed"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn script_body_is_checked_before_running() {
+        use crate::config::SafetyConfig;
+
+        let config = SafetyConfig {
+            evaluator: crate::core::safety::SafetyEvaluator::Native,
+            ..Default::default()
+        };
+        let safety = SafetyGate::from_config(&config, None);
+        let check = |body: &str, interpreter| check_script_body(&safety, "s", body, interpreter);
+
+        assert!(check("echo hi\nls -la\n", Some("bash")).is_ok());
+        assert!(check("#!/usr/bin/env bash\necho hi\n", None).is_ok());
+        assert!(check("echo ok\nrm -rf /\n", Some("bash")).is_err());
+        assert!(check("cleanup() { rm -rf /; }\ncleanup\n", Some("sh")).is_err());
+        assert!(check("if true; then echo\n", Some("bash")).is_err());
+        assert!(check("$TOOL --go\n", Some("bash")).is_err());
+        assert!(check("print('hi')\n", Some("python3")).is_err());
+        assert!(check("echo hi\n", None).is_err());
+    }
+
+    #[test]
+    fn shell_quote_escapes_quotes() {
+        assert_eq!(shell_quote("plain-arg.txt"), "plain-arg.txt");
+        assert_eq!(shell_quote("a b"), "'a b'");
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+        assert_eq!(shell_quote(""), "''");
+    }
 }
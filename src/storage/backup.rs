@@ -0,0 +1,984 @@
+//! Content-addressed, incremental backup snapshots.
+//!
+//! Files are split into fixed-size chunks stored in a [`BlobStore`] keyed by
+//! their SHA-256 hash, so a snapshot only writes the chunks that no earlier
+//! snapshot already stored. A snapshot is a manifest listing each file and
+//! its chunk hashes.
+//!
+//! Snapshots can be encrypted with a key derived from a passphrase
+//! (PBKDF2-HMAC-SHA256, ChaCha20-Poly1305). The nonce of each chunk is an
+//! HMAC of its plaintext, so encrypted chunks are deterministic and still
+//! deduplicate across snapshots.
+
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::fs;
+use std::io::{Read, Write};
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Datelike, NaiveDateTime, Utc};
+use ring::aead::{self, Aad, LessSafeKey, Nonce, UnboundKey};
+use ring::rand::{SecureRandom, SystemRandom};
+use ring::{hmac, pbkdf2};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+use crate::bundler::BlobStore;
+use crate::bundler::blob::hash_bytes;
+use crate::error::{MsError, Result};
+
+/// Directory under the backup root holding the shared chunk store.
+pub const BLOB_DIR: &str = "blobs";
+
+/// Manifest version of content-addressed snapshots (full copies are version 1).
+pub const SNAPSHOT_VERSION: u32 = 2;
+
+const MANIFEST_FILE: &str = "manifest.json";
+const KEY_FILE: &str = "encryption.json";
+const CHUNK_SIZE: usize = 1024 * 1024;
+const NONCE_LEN: usize = 12;
+const PBKDF2_ITERATIONS: u32 = 310_000;
+const KEY_CHECK: &[u8] = b"ms-backup-key-check";
+
+/// A file or directory captured under `name` in a snapshot.
+#[derive(Debug, Clone)]
+pub struct SnapshotSource {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    pub version: u32,
+    pub id: String,
+    pub created_at: String,
+    pub ms_root: String,
+    pub config_path: Option<String>,
+    /// Most recent snapshot at creation time
+    pub parent: Option<String>,
+    pub encrypted: bool,
+    pub files: Vec<SnapshotFile>,
+    pub total_bytes: u64,
+    /// Bytes of chunks that were not already in the store
+    pub new_bytes: u64,
+}
+
+impl SnapshotManifest {
+    /// Files under `prefix` (a file name or a directory followed by `/`).
+    pub fn files_under<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = &'a SnapshotFile> {
+        self.files
+            .iter()
+            .filter(move |file| file.path == prefix || file.path.starts_with(prefix))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotFile {
+    /// Path inside the snapshot, starting with the source name
+    pub path: String,
+    pub bytes: u64,
+    pub chunks: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct VerifyReport {
+    pub id: String,
+    pub files: usize,
+    pub chunks: usize,
+    /// Chunks were decrypted and authenticated, not only hash-checked
+    pub decrypted: bool,
+    pub missing: Vec<String>,
+    pub corrupt: Vec<String>,
+}
+
+impl VerifyReport {
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.missing.is_empty() && self.corrupt.is_empty()
+    }
+}
+
+/// Which snapshots to keep when pruning. A snapshot is kept if any rule
+/// selects it.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct RetentionPolicy {
+    /// Keep the N most recent snapshots
+    pub keep_last: usize,
+    /// Keep the newest snapshot of each of the last N days with backups
+    pub keep_daily: usize,
+    /// Keep the newest snapshot of each of the last N ISO weeks with backups
+    pub keep_weekly: usize,
+    /// Keep the newest snapshot of each of the last N months with backups
+    pub keep_monthly: usize,
+}
+
+impl RetentionPolicy {
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.keep_last == 0
+            && self.keep_daily == 0
+            && self.keep_weekly == 0
+            && self.keep_monthly == 0
+    }
+
+    /// IDs to keep among `(id, created_at)` pairs.
+    #[must_use]
+    pub fn select(&self, snapshots: &[(String, DateTime<Utc>)]) -> HashSet<String> {
+        let mut sorted: Vec<&(String, DateTime<Utc>)> = snapshots.iter().collect();
+        sorted.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| b.0.cmp(&a.0)));
+
+        let mut keep: HashSet<String> = sorted
+            .iter()
+            .take(self.keep_last)
+            .map(|(id, _)| id.clone())
+            .collect();
+        keep_buckets(&sorted, self.keep_daily, &mut keep, |time| {
+            time.format("%Y-%m-%d").to_string()
+        });
+        keep_buckets(&sorted, self.keep_weekly, &mut keep, |time| {
+            let week = time.iso_week();
+            format!("{}-W{:02}", week.year(), week.week())
+        });
+        keep_buckets(&sorted, self.keep_monthly, &mut keep, |time| {
+            time.format("%Y-%m").to_string()
+        });
+        keep
+    }
+}
+
+fn keep_buckets(
+    sorted: &[&(String, DateTime<Utc>)],
+    count: usize,
+    keep: &mut HashSet<String>,
+    bucket: impl Fn(&DateTime<Utc>) -> String,
+) {
+    let mut seen = HashSet::new();
+    for (id, time) in sorted {
+        if seen.len() >= count {
+            break;
+        }
+        if seen.insert(bucket(time)) {
+            keep.insert(id.clone());
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct PruneReport {
+    pub kept: Vec<String>,
+    pub removed: Vec<String>,
+    pub blobs_removed: usize,
+    pub bytes_freed: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct KeyParams {
+    kdf: String,
+    iterations: u32,
+    salt: String,
+    check: String,
+}
+
+#[derive(Deserialize)]
+struct ManifestProbe {
+    #[serde(default)]
+    version: u32,
+    #[serde(default)]
+    created_at: String,
+}
+
+/// Key material unlocked from the store passphrase.
+pub struct BackupKey {
+    aead: LessSafeKey,
+    mac: hmac::Key,
+}
+
+impl BackupKey {
+    fn derive(passphrase: &str, params: &KeyParams) -> Result<Self> {
+        let salt = hex::decode(&params.salt)
+            .map_err(|err| MsError::Config(format!("invalid backup key salt: {err}")))?;
+        let iterations = NonZeroU32::new(params.iterations)
+            .ok_or_else(|| MsError::Config("invalid backup key iterations".to_string()))?;
+        let mut material = [0u8; 64];
+        pbkdf2::derive(
+            pbkdf2::PBKDF2_HMAC_SHA256,
+            iterations,
+            &salt,
+            passphrase.as_bytes(),
+            &mut material,
+        );
+        let unbound = UnboundKey::new(&aead::CHACHA20_POLY1305, &material[..32])
+            .map_err(|_| MsError::Config("invalid backup key".to_string()))?;
+        Ok(Self {
+            aead: LessSafeKey::new(unbound),
+            mac: hmac::Key::new(hmac::HMAC_SHA256, &material[32..]),
+        })
+    }
+
+    fn encrypt(&self, plain: &[u8]) -> Result<Vec<u8>> {
+        let tag = hmac::sign(&self.mac, plain);
+        let mut nonce = [0u8; NONCE_LEN];
+        nonce.copy_from_slice(&tag.as_ref()[..NONCE_LEN]);
+        let mut sealed = plain.to_vec();
+        self.aead
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::empty(),
+                &mut sealed,
+            )
+            .map_err(|_| MsError::Config("encrypt backup chunk".to_string()))?;
+        let mut out = Vec::with_capacity(NONCE_LEN + sealed.len());
+        out.extend_from_slice(&nonce);
+        out.extend_from_slice(&sealed);
+        Ok(out)
+    }
+
+    fn decrypt(&self, sealed: &[u8]) -> Result<Vec<u8>> {
+        let failed = || {
+            MsError::ValidationFailed(
+                "backup chunk failed authentication (wrong passphrase or corrupted data)"
+                    .to_string(),
+            )
+        };
+        if sealed.len() < NONCE_LEN + aead::CHACHA20_POLY1305.tag_len() {
+            return Err(failed());
+        }
+        let (nonce, body) = sealed.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| failed())?;
+        let mut buf = body.to_vec();
+        let len = self
+            .aead
+            .open_in_place(nonce, Aad::empty(), &mut buf)
+            .map_err(|_| failed())?
+            .len();
+        buf.truncate(len);
+        Ok(buf)
+    }
+}
+
+/// Snapshot store rooted at the backups directory.
+pub struct BackupStore {
+    root: PathBuf,
+    blobs: BlobStore,
+}
+
+impl BackupStore {
+    pub fn open(root: impl AsRef<Path>) -> Result<Self> {
+        let root = root.as_ref().to_path_buf();
+        let blobs = BlobStore::open(&root)?;
+        Ok(Self { root, blobs })
+    }
+
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Derive the store key from `passphrase`, creating the key parameters
+    /// on first use. Fails if the passphrase does not match earlier ones.
+    pub fn unlock(&self, passphrase: &str) -> Result<BackupKey> {
+        if passphrase.is_empty() {
+            return Err(MsError::ValidationFailed(
+                "backup passphrase is empty".to_string(),
+            ));
+        }
+        let path = self.root.join(KEY_FILE);
+        if path.exists() {
+            let content = fs::read_to_string(&path)
+                .map_err(|err| MsError::Config(format!("read {}: {err}", path.display())))?;
+            let params: KeyParams = serde_json::from_str(&content)?;
+            let key = BackupKey::derive(passphrase, &params)?;
+            let check = hex::decode(&params.check)
+                .map_err(|err| MsError::Config(format!("invalid backup key check: {err}")))?;
+            return match key.decrypt(&check) {
+                Ok(plain) if plain == KEY_CHECK => Ok(key),
+                _ => Err(MsError::ValidationFailed(
+                    "wrong backup passphrase".to_string(),
+                )),
+            };
+        }
+
+        let mut salt = [0u8; 16];
+        SystemRandom::new()
+            .fill(&mut salt)
+            .map_err(|_| MsError::Config("generate backup key salt".to_string()))?;
+        let mut params = KeyParams {
+            kdf: "pbkdf2-hmac-sha256".to_string(),
+            iterations: PBKDF2_ITERATIONS,
+            salt: hex::encode(salt),
+            check: String::new(),
+        };
+        let key = BackupKey::derive(passphrase, &params)?;
+        params.check = hex::encode(key.encrypt(KEY_CHECK)?);
+        let json = serde_json::to_string_pretty(&params)
+            .map_err(|err| MsError::Config(format!("serialize backup key: {err}")))?;
+        fs::write(&path, json)
+            .map_err(|err| MsError::Config(format!("write {}: {err}", path.display())))?;
+        Ok(key)
+    }
+
+    /// Chunk every source into the store and return the (unsaved) manifest.
+    pub fn snapshot(
+        &self,
+        id: &str,
+        sources: &[SnapshotSource],
+        key: Option<&BackupKey>,
+    ) -> Result<SnapshotManifest> {
+        let parent = self.snapshots()?.pop().map(|manifest| manifest.id);
+        let mut manifest = SnapshotManifest {
+            version: SNAPSHOT_VERSION,
+            id: id.to_string(),
+            created_at: Utc::now().to_rfc3339(),
+            ms_root: String::new(),
+            config_path: None,
+            parent,
+            encrypted: key.is_some(),
+            files: Vec::new(),
+            total_bytes: 0,
+            new_bytes: 0,
+        };
+        for source in sources {
+            for (path, abs) in source_files(source)? {
+                let file = self.store_file(&abs, path, key, &mut manifest.new_bytes)?;
+                manifest.total_bytes += file.bytes;
+                manifest.files.push(file);
+            }
+        }
+        Ok(manifest)
+    }
+
+    pub fn save(&self, manifest: &SnapshotManifest) -> Result<PathBuf> {
+        let dir = self.root.join(&manifest.id);
+        fs::create_dir_all(&dir)
+            .map_err(|err| MsError::Config(format!("create {}: {err}", dir.display())))?;
+        let path = dir.join(MANIFEST_FILE);
+        let json = serde_json::to_string_pretty(manifest)
+            .map_err(|err| MsError::Config(format!("serialize manifest: {err}")))?;
+        fs::write(&path, json)
+            .map_err(|err| MsError::Config(format!("write {}: {err}", path.display())))?;
+        Ok(dir)
+    }
+
+    /// Load a content-addressed snapshot; `None` for full-copy backups.
+    pub fn load(&self, id: &str) -> Result<Option<SnapshotManifest>> {
+        let path = self.root.join(id).join(MANIFEST_FILE);
+        let content = fs::read_to_string(&path)
+            .map_err(|_| MsError::NotFound(format!("backup {id} missing manifest")))?;
+        let probe: ManifestProbe = serde_json::from_str(&content)?;
+        if probe.version < SNAPSHOT_VERSION {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_str(&content)?))
+    }
+
+    /// Content-addressed snapshots, oldest first.
+    pub fn snapshots(&self) -> Result<Vec<SnapshotManifest>> {
+        let mut snapshots = Vec::new();
+        for id in self.snapshot_ids()? {
+            if let Ok(Some(manifest)) = self.load(&id) {
+                snapshots.push(manifest);
+            }
+        }
+        Ok(snapshots)
+    }
+
+    /// All backup directories (both formats), sorted by ID.
+    pub fn snapshot_ids(&self) -> Result<Vec<String>> {
+        if !self.root.exists() {
+            return Ok(Vec::new());
+        }
+        let mut ids: Vec<String> = fs::read_dir(&self.root)
+            .map_err(|err| MsError::Config(format!("read {}: {err}", self.root.display())))?
+            .filter_map(std::result::Result::ok)
+            .filter(|entry| entry.path().is_dir())
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .filter(|name| name != BLOB_DIR)
+            .collect();
+        ids.sort();
+        Ok(ids)
+    }
+
+    /// Write one snapshot file to `dest`, returning bytes written.
+    pub fn restore_file(
+        &self,
+        file: &SnapshotFile,
+        encrypted: bool,
+        key: Option<&BackupKey>,
+        dest: &Path,
+    ) -> Result<u64> {
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|err| MsError::Config(format!("create {}: {err}", parent.display())))?;
+        }
+        let mut out = fs::File::create(dest)
+            .map_err(|err| MsError::Config(format!("create {}: {err}", dest.display())))?;
+        let mut written = 0u64;
+        for hash in &file.chunks {
+            let data = self.read_chunk(hash, encrypted, key)?;
+            out.write_all(&data)
+                .map_err(|err| MsError::Config(format!("write {}: {err}", dest.display())))?;
+            written += data.len() as u64;
+        }
+        if written != file.bytes {
+            return Err(MsError::ValidationFailed(format!(
+                "restored {} has {written} bytes, expected {}",
+                file.path, file.bytes
+            )));
+        }
+        Ok(written)
+    }
+
+    /// Check that every chunk exists and matches its hash; with a key, also
+    /// decrypt and authenticate encrypted chunks.
+    pub fn verify(&self, manifest: &SnapshotManifest, key: Option<&BackupKey>) -> VerifyReport {
+        let decrypt = manifest.encrypted && key.is_some();
+        let mut report = VerifyReport {
+            id: manifest.id.clone(),
+            files: manifest.files.len(),
+            decrypted: decrypt,
+            ..VerifyReport::default()
+        };
+        let mut checked: HashSet<&str> = HashSet::new();
+        for file in &manifest.files {
+            let mut bytes = 0u64;
+            let mut complete = true;
+            for hash in &file.chunks {
+                report.chunks += 1;
+                let chunk = if !self.blobs.has_blob(hash) {
+                    if checked.insert(hash) {
+                        report.missing.push(hash.clone());
+                    }
+                    None
+                } else if !self.blobs.verify_blob(hash).unwrap_or(false) {
+                    if checked.insert(hash) {
+                        report.corrupt.push(hash.clone());
+                    }
+                    None
+                } else if decrypt {
+                    self.read_chunk(hash, true, key).ok()
+                } else {
+                    self.blobs.read_blob(hash).ok()
+                };
+                match chunk {
+                    Some(data) => bytes += data.len() as u64,
+                    None => complete = false,
+                }
+            }
+            // Encrypted chunks carry a nonce and tag, so sizes only add up
+            // once decrypted.
+            if complete && (decrypt || !manifest.encrypted) && bytes != file.bytes {
+                report.corrupt.push(file.path.clone());
+            }
+        }
+        report
+    }
+
+    /// Delete snapshots not selected by `policy`, then drop unreferenced
+    /// chunks. Backups without a readable timestamp are always kept.
+    pub fn prune(&self, policy: &RetentionPolicy, dry_run: bool) -> Result<PruneReport> {
+        let mut dated = Vec::new();
+        let mut report = PruneReport::default();
+        for id in self.snapshot_ids()? {
+            match self.created_at(&id) {
+                Some(time) => dated.push((id, time)),
+                None => report.kept.push(id),
+            }
+        }
+        let keep = policy.select(&dated);
+        for (id, _) in dated {
+            if keep.contains(&id) {
+                report.kept.push(id);
+            } else {
+                report.removed.push(id);
+            }
+        }
+        report.kept.sort();
+
+        if dry_run {
+            return Ok(report);
+        }
+        for id in &report.removed {
+            let dir = self.root.join(id);
+            fs::remove_dir_all(&dir)
+                .map_err(|err| MsError::Config(format!("remove {}: {err}", dir.display())))?;
+        }
+        let (blobs_removed, bytes_freed) = self.collect_garbage()?;
+        report.blobs_removed = blobs_removed;
+        report.bytes_freed = bytes_freed;
+        Ok(report)
+    }
+
+    /// Remove chunks no snapshot references.
+    pub fn collect_garbage(&self) -> Result<(usize, u64)> {
+        let referenced: HashSet<String> = self
+            .snapshots()?
+            .into_iter()
+            .flat_map(|manifest| manifest.files)
+            .flat_map(|file| file.chunks)
+            .collect();
+        let mut removed = 0usize;
+        let mut freed = 0u64;
+        for hash in self.blobs.blob_hashes()? {
+            if !referenced.contains(&hash) {
+                freed += self.blobs.remove_blob(&hash)?;
+                removed += 1;
+            }
+        }
+        Ok((removed, freed))
+    }
+
+    fn created_at(&self, id: &str) -> Option<DateTime<Utc>> {
+        let content = fs::read_to_string(self.root.join(id).join(MANIFEST_FILE)).ok();
+        let from_manifest = content
+            .and_then(|content| serde_json::from_str::<ManifestProbe>(&content).ok())
+            .and_then(|probe| DateTime::parse_from_rfc3339(&probe.created_at).ok())
+            .map(|time| time.with_timezone(&Utc));
+        from_manifest.or_else(|| {
+            NaiveDateTime::parse_from_str(id, "%Y%m%d%H%M%S")
+                .ok()
+                .map(|time| time.and_utc())
+        })
+    }
+
+    fn store_file(
+        &self,
+        abs: &Path,
+        path: String,
+        key: Option<&BackupKey>,
+        new_bytes: &mut u64,
+    ) -> Result<SnapshotFile> {
+        let mut reader = fs::File::open(abs)
+            .map_err(|err| MsError::Config(format!("read {}: {err}", abs.display())))?;
+        let mut buf = vec![0u8; CHUNK_SIZE];
+        let mut file = SnapshotFile {
+            path,
+            bytes: 0,
+            chunks: Vec::new(),
+        };
+        loop {
+            let len = fill_chunk(&mut reader, &mut buf)
+                .map_err(|err| MsError::Config(format!("read {}: {err}", abs.display())))?;
+            if len == 0 {
+                break;
+            }
+            let plain = &buf[..len];
+            let stored = match key {
+                Some(key) => Cow::Owned(key.encrypt(plain)?),
+                None => Cow::Borrowed(plain),
+            };
+            let hash = hash_bytes(&stored);
+            if !self.blobs.has_blob(&hash) {
+                self.blobs.write_blob(&stored)?;
+                *new_bytes += stored.len() as u64;
+            }
+            file.bytes += len as u64;
+            file.chunks.push(hash);
+            if len < CHUNK_SIZE {
+                break;
+            }
+        }
+        Ok(file)
+    }
+
+    fn read_chunk(&self, hash: &str, encrypted: bool, key: Option<&BackupKey>) -> Result<Vec<u8>> {
+        let data = self.blobs.read_blob(hash)?;
+        if !encrypted {
+            return Ok(data);
+        }
+        let key = key.ok_or_else(|| {
+            MsError::ValidationFailed("backup is encrypted; a passphrase is required".to_string())
+        })?;
+        key.decrypt(&data)
+    }
+}
+
+fn fill_chunk(reader: &mut impl Read, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..])? {
+            0 => break,
+            n => filled += n,
+        }
+    }
+    Ok(filled)
+}
+
+/// `(snapshot path, absolute path)` of every regular file in a source.
+fn source_files(source: &SnapshotSource) -> Result<Vec<(String, PathBuf)>> {
+    if source.path.is_file() {
+        return Ok(vec![(source.name.clone(), source.path.clone())]);
+    }
+    let mut files = Vec::new();
+    if !source.path.is_dir() {
+        return Ok(files);
+    }
+    for entry in walkdir::WalkDir::new(&source.path).sort_by_file_name() {
+        let entry = entry
+            .map_err(|err| MsError::Config(format!("walk {}: {err}", source.path.display())))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let rel = entry
+            .path()
+            .strip_prefix(&source.path)
+            .unwrap_or(entry.path())
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        files.push((format!("{}/{rel}", source.name), entry.path().to_path_buf()));
+    }
+    Ok(files)
+}
+
+/// Replace one skill's rows in `conn` with those in the backup database.
+/// Returns the number of rows restored.
+pub fn restore_skill_rows(conn: &Connection, backup_db: &Path, skill_id: &str) -> Result<usize> {
+    with_backup_attached(conn, backup_db, |conn| {
+        let exists: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM bak.skills WHERE id = ?1)",
+            [skill_id],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            return Err(MsError::SkillNotFound(format!(
+                "{skill_id} (not in backup)"
+            )));
+        }
+        let mut tables = Vec::new();
+        for table in user_tables(conn)? {
+            if table != "skills"
+                && table_columns(conn, "main", &table)?.contains(&"skill_id".to_string())
+                && table_columns(conn, "bak", &table)?.contains(&"skill_id".to_string())
+            {
+                tables.push(table);
+            }
+        }
+        for table in &tables {
+            conn.execute(
+                &format!(
+                    "DELETE FROM main.{} WHERE skill_id = ?1",
+                    quote_ident(table)
+                ),
+                [skill_id],
+            )?;
+        }
+        conn.execute("DELETE FROM main.skills WHERE id = ?1", [skill_id])?;
+        let mut rows = copy_rows(conn, "skills", Some(("id", skill_id)))?;
+        for table in &tables {
+            rows += copy_rows(conn, table, Some(("skill_id", skill_id)))?;
+        }
+        Ok(rows)
+    })
+}
+
+/// Replace the contents of `table` in `conn` with the backup's copy.
+/// Returns the number of rows restored.
+pub fn restore_table(conn: &Connection, backup_db: &Path, table: &str) -> Result<usize> {
+    with_backup_attached(conn, backup_db, |conn| {
+        if !user_tables(conn)?.iter().any(|name| name == table) {
+            return Err(MsError::NotFound(format!("table {table}")));
+        }
+        if table_columns(conn, "bak", table)?.is_empty() {
+            return Err(MsError::NotFound(format!("table {table} (not in backup)")));
+        }
+        conn.execute(&format!("DELETE FROM main.{}", quote_ident(table)), [])?;
+        copy_rows(conn, table, None)
+    })
+}
+
+/// Regular tables of the live database (no FTS or other virtual tables).
+pub fn user_tables(conn: &Connection) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare(
+        "SELECT name FROM main.sqlite_master
+         WHERE type = 'table' AND name NOT LIKE 'sqlite_%'
+           AND sql NOT LIKE 'CREATE VIRTUAL%'
+           AND name NOT LIKE '%_fts_%'
+         ORDER BY name",
+    )?;
+    let names = stmt
+        .query_map([], |row| row.get(0))?
+        .collect::<rusqlite::Result<Vec<String>>>()?;
+    Ok(names)
+}
+
+fn with_backup_attached<T>(
+    conn: &Connection,
+    backup_db: &Path,
+    restore: impl FnOnce(&Connection) -> Result<T>,
+) -> Result<T> {
+    conn.execute(
+        "ATTACH DATABASE ?1 AS bak",
+        [backup_db.to_string_lossy().as_ref()],
+    )?;
+    // Rows are restored as they were, even if rows they referenced are gone.
+    let result = conn
+        .execute_batch("PRAGMA foreign_keys = OFF; BEGIN IMMEDIATE;")
+        .map_err(MsError::from)
+        .and_then(|()| restore(conn))
+        .and_then(|value| {
+            conn.execute_batch("COMMIT")?;
+            Ok(value)
+        });
+    if result.is_err() {
+        let _ = conn.execute_batch("ROLLBACK");
+    }
+    let _ = conn.execute_batch("PRAGMA foreign_keys = ON; DETACH DATABASE bak;");
+    result
+}
+
+fn table_columns(conn: &Connection, schema: &str, table: &str) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare("SELECT name FROM pragma_table_info(?1, ?2)")?;
+    let columns = stmt
+        .query_map([table, schema], |row| row.get(0))?
+        .collect::<rusqlite::Result<Vec<String>>>()?;
+    Ok(columns)
+}
+
+/// Copy rows of `table` from the backup, limited to columns both schemas
+/// share so snapshots from older schema versions still restore.
+fn copy_rows(conn: &Connection, table: &str, filter: Option<(&str, &str)>) -> Result<usize> {
+    let backup_columns = table_columns(conn, "bak", table)?;
+    let columns: Vec<String> = table_columns(conn, "main", table)?
+        .into_iter()
+        .filter(|column| backup_columns.contains(column))
+        .map(|column| quote_ident(&column))
+        .collect();
+    if columns.is_empty() {
+        return Ok(0);
+    }
+    let columns = columns.join(", ");
+    let table = quote_ident(table);
+    let rows = match filter {
+        Some((column, value)) => conn.execute(
+            &format!(
+                "INSERT INTO main.{table} ({columns}) SELECT {columns} FROM bak.{table} WHERE {} = ?1",
+                quote_ident(column)
+            ),
+            [value],
+        )?,
+        None => conn.execute(
+            &format!("INSERT INTO main.{table} ({columns}) SELECT {columns} FROM bak.{table}"),
+            [],
+        )?,
+    };
+    Ok(rows)
+}
+
+fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn write(path: &Path, content: &[u8]) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn sources(root: &Path) -> Vec<SnapshotSource> {
+        vec![
+            SnapshotSource {
+                name: "ms.db".to_string(),
+                path: root.join("ms.db"),
+            },
+            SnapshotSource {
+                name: "archive".to_string(),
+                path: root.join("archive"),
+            },
+        ]
+    }
+
+    #[test]
+    fn snapshots_deduplicate_unchanged_chunks() {
+        let dir = tempdir().unwrap();
+        let data = dir.path().join("data");
+        let big: Vec<u8> = (0..CHUNK_SIZE * 2 + 10).map(|i| (i % 251) as u8).collect();
+        write(&data.join("ms.db"), &big);
+        write(&data.join("archive/skills/by-id/a/SKILL.md"), b"# A");
+
+        let store = BackupStore::open(dir.path().join("backups")).unwrap();
+        let first = store.snapshot("one", &sources(&data), None).unwrap();
+        store.save(&first).unwrap();
+        assert_eq!(first.files.len(), 2);
+        assert_eq!(first.files[0].chunks.len(), 3);
+        assert_eq!(first.new_bytes, first.total_bytes);
+
+        // Change only the last chunk of the database.
+        let mut changed = big.clone();
+        *changed.last_mut().unwrap() = 0xff;
+        write(&data.join("ms.db"), &changed);
+        let second = store.snapshot("two", &sources(&data), None).unwrap();
+        store.save(&second).unwrap();
+        assert_eq!(second.parent.as_deref(), Some("one"));
+        assert_eq!(second.new_bytes, 10);
+
+        let restored = dir.path().join("restored.db");
+        store
+            .restore_file(&second.files[0], false, None, &restored)
+            .unwrap();
+        assert_eq!(fs::read(&restored).unwrap(), changed);
+        assert!(store.verify(&second, None).is_ok());
+
+        // Corrupt a chunk on disk.
+        let hash = &second.files[1].chunks[0];
+        let blob = store
+            .root()
+            .join(BLOB_DIR)
+            .join("sha256")
+            .join(&hash["sha256:".len()..]);
+        fs::write(&blob, b"tampered").unwrap();
+        let report = store.verify(&second, None);
+        assert_eq!(report.corrupt, vec![hash.clone()]);
+    }
+
+    #[test]
+    fn encrypted_snapshots_roundtrip_and_reject_wrong_passphrase() {
+        let dir = tempdir().unwrap();
+        let data = dir.path().join("data");
+        write(&data.join("ms.db"), b"secret skill data");
+        let store = BackupStore::open(dir.path().join("backups")).unwrap();
+
+        let key = store.unlock("correct horse").unwrap();
+        let first = store.snapshot("one", &sources(&data), Some(&key)).unwrap();
+        let second = store.snapshot("two", &sources(&data), Some(&key)).unwrap();
+        assert!(first.encrypted);
+        // Deterministic nonces keep encrypted chunks deduplicated.
+        assert_eq!(first.files[0].chunks, second.files[0].chunks);
+        assert_eq!(second.new_bytes, 0);
+
+        let raw = store.blobs.read_blob(&first.files[0].chunks[0]).unwrap();
+        assert!(!raw.windows(6).any(|w| w == b"secret"));
+
+        assert!(store.unlock("wrong").is_err());
+        let key = store.unlock("correct horse").unwrap();
+        let out = dir.path().join("out.db");
+        store
+            .restore_file(&first.files[0], true, Some(&key), &out)
+            .unwrap();
+        assert_eq!(fs::read(&out).unwrap(), b"secret skill data");
+        assert!(
+            store
+                .restore_file(&first.files[0], true, None, &out)
+                .is_err()
+        );
+        assert!(store.verify(&first, Some(&key)).decrypted);
+    }
+
+    #[test]
+    fn retention_keeps_last_daily_weekly_monthly() {
+        let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        let snapshots = vec![
+            ("a".to_string(), at("2026-01-05T10:00:00Z")),
+            ("b".to_string(), at("2026-02-10T10:00:00Z")),
+            ("c".to_string(), at("2026-03-02T09:00:00Z")),
+            ("d".to_string(), at("2026-03-02T18:00:00Z")),
+            ("e".to_string(), at("2026-03-03T08:00:00Z")),
+        ];
+        let last = RetentionPolicy {
+            keep_last: 2,
+            ..RetentionPolicy::default()
+        };
+        assert_eq!(
+            last.select(&snapshots),
+            HashSet::from(["e".to_string(), "d".to_string()])
+        );
+
+        let daily = RetentionPolicy {
+            keep_daily: 2,
+            ..RetentionPolicy::default()
+        };
+        assert_eq!(
+            daily.select(&snapshots),
+            HashSet::from(["e".to_string(), "d".to_string()])
+        );
+
+        let monthly = RetentionPolicy {
+            keep_monthly: 3,
+            ..RetentionPolicy::default()
+        };
+        assert_eq!(
+            monthly.select(&snapshots),
+            HashSet::from(["e".to_string(), "b".to_string(), "a".to_string()])
+        );
+        assert!(RetentionPolicy::default().select(&snapshots).is_empty());
+    }
+
+    #[test]
+    fn prune_removes_snapshots_and_unreferenced_chunks() {
+        let dir = tempdir().unwrap();
+        let data = dir.path().join("data");
+        let store = BackupStore::open(dir.path().join("backups")).unwrap();
+        for (id, content) in [("20260101000000", "old"), ("20260102000000", "new")] {
+            write(&data.join("ms.db"), content.as_bytes());
+            let manifest = store.snapshot(id, &sources(&data), None).unwrap();
+            store.save(&manifest).unwrap();
+        }
+        let policy = RetentionPolicy {
+            keep_last: 1,
+            ..RetentionPolicy::default()
+        };
+        let preview = store.prune(&policy, true).unwrap();
+        assert_eq!(preview.removed, vec!["20260101000000".to_string()]);
+        assert_eq!(store.snapshot_ids().unwrap().len(), 2);
+
+        let report = store.prune(&policy, false).unwrap();
+        assert_eq!(report.kept, vec!["20260102000000".to_string()]);
+        assert_eq!(report.blobs_removed, 1);
+        assert_eq!(report.bytes_freed, 3);
+        assert!(store.verify(&store.snapshots().unwrap()[0], None).is_ok());
+    }
+
+    #[test]
+    fn partial_restore_of_skill_rows_and_tables() {
+        let dir = tempdir().unwrap();
+        let schema = "CREATE TABLE skills (id TEXT PRIMARY KEY, name TEXT);
+             CREATE TABLE skill_rules (skill_id TEXT NOT NULL REFERENCES skills(id), rule TEXT);
+             CREATE TABLE skill_feedback (id INTEGER PRIMARY KEY, skill_id TEXT, rating INTEGER);";
+        let backup_path = dir.path().join("backup.db");
+        let backup = Connection::open(&backup_path).unwrap();
+        backup.execute_batch(schema).unwrap();
+        backup
+            .execute_batch(
+                "INSERT INTO skills VALUES ('a', 'Alpha'), ('b', 'Beta');
+                 INSERT INTO skill_rules VALUES ('a', 'old rule'), ('b', 'beta rule');
+                 INSERT INTO skill_feedback VALUES (1, 'a', 5), (2, 'b', 4);",
+            )
+            .unwrap();
+        drop(backup);
+
+        let live = Connection::open(dir.path().join("live.db")).unwrap();
+        live.execute_batch(schema).unwrap();
+        live.execute_batch(
+            "PRAGMA foreign_keys = ON;
+             INSERT INTO skills VALUES ('a', 'Alpha edited'), ('b', 'Beta edited');
+             INSERT INTO skill_rules VALUES ('a', 'new rule'), ('b', 'beta new');
+             INSERT INTO skill_feedback VALUES (3, 'a', 1);",
+        )
+        .unwrap();
+
+        let rows = restore_skill_rows(&live, &backup_path, "a").unwrap();
+        assert_eq!(rows, 3);
+        let name: String = live
+            .query_row("SELECT name FROM skills WHERE id = 'a'", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(name, "Alpha");
+        let other: String = live
+            .query_row("SELECT name FROM skills WHERE id = 'b'", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(other, "Beta edited");
+        assert!(restore_skill_rows(&live, &backup_path, "missing").is_err());
+
+        let rows = restore_table(&live, &backup_path, "skill_feedback").unwrap();
+        assert_eq!(rows, 2);
+        let count: i64 = live
+            .query_row("SELECT COUNT(*) FROM skill_feedback", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(count, 2);
+        assert!(restore_table(&live, &backup_path, "nope").is_err());
+    }
+}
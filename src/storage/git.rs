@@ -10,6 +10,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::core::{SkillMetadata, SkillSpec};
 use crate::error::{MsError, Result};
+use crate::security::skill_signing::{SIGNATURE_FILE, SkillSignatures};
 
 /// Git archive for skill versioning and audit trail
 pub struct GitArchive {
@@ -314,6 +315,52 @@ impl GitArchive {
         Ok(spec)
     }
 
+    /// Read a skill's detached signatures; empty when it has none.
+    pub fn read_signatures(&self, skill_id: &str) -> Result<SkillSignatures> {
+        let skill_path = self.skill_path(skill_id).ok_or_else(|| {
+            MsError::ValidationFailed("skill id contains path traversal sequences".to_string())
+        })?;
+        let sig_path = skill_path.join(SIGNATURE_FILE);
+        if !sig_path.exists() {
+            return Ok(SkillSignatures::default());
+        }
+        let contents = fs::read_to_string(sig_path)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// Write a skill's detached signatures and commit.
+    ///
+    /// `write_skill` leaves this file alone, so signatures persist across
+    /// re-indexing and edits (where they stop matching the content hash).
+    pub fn write_signatures(
+        &self,
+        skill_id: &str,
+        signatures: &SkillSignatures,
+    ) -> Result<SkillCommit> {
+        let skill_path = self.skill_path(skill_id).ok_or_else(|| {
+            MsError::ValidationFailed("skill id contains path traversal sequences".to_string())
+        })?;
+        if !skill_path.exists() {
+            return Err(MsError::SkillNotFound(skill_id.to_string()));
+        }
+        let sig_path = skill_path.join(SIGNATURE_FILE);
+        write_string(&sig_path, &serde_json::to_string_pretty(signatures)?)?;
+
+        let mut index = self.repo.index()?;
+        add_path(&mut index, &self.root, &sig_path)?;
+        index.write()?;
+
+        let tree_id = index.write_tree()?;
+        let tree = self.repo.find_tree(tree_id)?;
+        let message = format!("Update signatures for skill {skill_id}");
+        let oid = commit_with_parents(&self.repo, &self.signature, &tree, &message)?;
+
+        Ok(SkillCommit {
+            oid: oid.to_string(),
+            message,
+        })
+    }
+
     /// Read skill metadata from the archive.
     pub fn read_metadata(&self, skill_id: &str) -> Result<SkillMetadata> {
         let skill_path = self.skill_path(skill_id).ok_or_else(|| {
@@ -502,6 +549,7 @@ mod tests {
                     id: "block-1".to_string(),
                     block_type: crate::core::BlockType::Text,
                     content: "Hello".to_string(),
+                    suppressions: Vec::new(),
                 }],
             }],
             ..Default::default()
@@ -542,6 +590,32 @@ mod tests {
         assert!(skill_dir.join("usage-log.jsonl").exists());
     }
 
+    #[test]
+    fn test_signatures_survive_rewrite() {
+        let dir = tempdir().unwrap();
+        let archive = GitArchive::open(dir.path()).unwrap();
+
+        let spec = sample_spec("signed-skill");
+        archive.write_skill(&spec).unwrap();
+        assert!(archive.read_signatures("signed-skill").unwrap().is_empty());
+
+        let mut sigs = SkillSignatures::default();
+        sigs.add(crate::security::SkillSignature {
+            content_hash: "abc".to_string(),
+            signature: crate::bundler::BundleSignature {
+                signer: "tester".to_string(),
+                key_id: "ed25519:00".to_string(),
+                signature: "00".to_string(),
+            },
+            signed_at: "2026-01-01T00:00:00Z".to_string(),
+        });
+        archive.write_signatures("signed-skill", &sigs).unwrap();
+
+        archive.write_skill(&spec).unwrap();
+        assert_eq!(archive.read_signatures("signed-skill").unwrap(), sigs);
+        assert!(archive.write_signatures("missing", &sigs).is_err());
+    }
+
     #[test]
     fn test_git_history() {
         let dir = tempdir().unwrap();
@@ -4,7 +4,7 @@ use rusqlite::Connection;
 
 use crate::error::{MsError, Result};
 
-const MIGRATIONS: [&str; 12] = [
+const MIGRATIONS: [&str; 17] = [
     include_str!("../../migrations/001_initial_schema.sql"),
     include_str!("../../migrations/002_add_fts.sql"),
     include_str!("../../migrations/003_add_vectors.sql"),
@@ -17,6 +17,11 @@ const MIGRATIONS: [&str; 12] = [
     include_str!("../../migrations/010_add_resolution_cache.sql"),
     include_str!("../../migrations/011_add_user_preferences.sql"),
     include_str!("../../migrations/012_add_resolution_warnings.sql"),
+    include_str!("../../migrations/013_add_skill_verification.sql"),
+    include_str!("../../migrations/014_add_slice_embeddings.sql"),
+    include_str!("../../migrations/015_add_search_impressions.sql"),
+    include_str!("../../migrations/016_add_outcome_attributions.sql"),
+    include_str!("../../migrations/017_add_quarantine_findings.sql"),
 ];
 
 pub const SCHEMA_VERSION: u32 = MIGRATIONS.len() as u32;
@@ -66,8 +71,8 @@ mod tests {
     }
 
     #[test]
-    fn schema_version_is_12() {
-        assert_eq!(SCHEMA_VERSION, 12);
+    fn schema_version_is_17() {
+        assert_eq!(SCHEMA_VERSION, 17);
     }
 
     // =========================================================================
@@ -2,12 +2,14 @@
 //!
 //! Implements dual persistence: `SQLite` for queries, Git for audit/versioning.
 
+pub mod backup;
 pub mod git;
 pub mod migrations;
 pub mod sqlite;
 pub mod tombstone;
 pub mod tx;
 
+pub use backup::{BackupStore, RetentionPolicy, SnapshotManifest};
 pub use git::GitArchive;
 pub use sqlite::{Database, SkillRecord};
 pub use tombstone::{PurgeResult, RestoreResult, TombstoneManager, TombstoneRecord};
@@ -0,0 +1,496 @@
+//! Automatic outcome attribution from CASS sessions.
+//!
+//! `ms outcome` and `ms feedback add` depend on someone remembering to record
+//! results. This module infers them instead: each recorded skill load
+//! (`skill_usage`) is joined with CASS sessions from the same project that
+//! overlap the load, and each session is assessed for success signals
+//! (tests passing at the end, commits made, no rollback) and failure signals
+//! (rollbacks and user corrections from `antipatterns::detection`, a final
+//! failing test run). The strongest verdict becomes an attributed outcome
+//! with a confidence that decays with the gap between load and session.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use chrono::{DateTime, Duration, Utc};
+use regex::Regex;
+use serde::Serialize;
+
+use crate::antipatterns::detection::AntiPatternDetector;
+use crate::cass::client::{Session, SessionMatch, ToolResult};
+use crate::storage::sqlite::{OutcomeAttributionRecord, UnattributedUsage};
+
+/// Commands treated as test runs.
+const TEST_COMMANDS: &[&str] = &[
+    "cargo test",
+    "cargo nextest",
+    "pytest",
+    "npm test",
+    "npm run test",
+    "yarn test",
+    "pnpm test",
+    "bun test",
+    "go test",
+    "jest",
+    "vitest",
+    "make test",
+    "mvn test",
+    "gradle test",
+    "rspec",
+];
+
+static FAILED_COUNT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b[1-9]\d* (failed|failing|failures?)\b").unwrap());
+
+/// Net score below which a session is considered inconclusive.
+const MIN_NET_SCORE: f32 = 0.25;
+
+/// Settings for joining loads with sessions.
+#[derive(Debug, Clone)]
+pub struct AttributionConfig {
+    /// Sessions starting up to this long after a load are considered
+    pub window: Duration,
+    /// Attributions below this confidence are dropped
+    pub min_confidence: f32,
+}
+
+impl Default for AttributionConfig {
+    fn default() -> Self {
+        Self {
+            window: Duration::hours(4),
+            min_confidence: 0.4,
+        }
+    }
+}
+
+/// Success and failure signals found in one session.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SessionAssessment {
+    /// The last test run in the session passed
+    pub tests_passed: bool,
+    /// The last test run in the session failed
+    pub tests_failed: bool,
+    /// Successful `git commit` commands
+    pub commits: usize,
+    /// Rollback commands (reset, revert, restore, ...)
+    pub rollbacks: usize,
+    /// User corrections of the assistant
+    pub corrections: usize,
+    /// Highest correction confidence
+    pub correction_confidence: f32,
+}
+
+/// Inferred outcome of a session.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Verdict {
+    pub success: bool,
+    pub confidence: f32,
+}
+
+impl SessionAssessment {
+    /// Scan a session's tool calls and messages for outcome signals.
+    #[must_use]
+    pub fn assess(session: &Session, detector: &dyn AntiPatternDetector) -> Self {
+        let results: HashMap<&str, &ToolResult> = session
+            .messages
+            .iter()
+            .flat_map(|msg| &msg.tool_results)
+            .map(|result| (result.tool_call_id.as_str(), result))
+            .collect();
+
+        let mut assessment = Self::default();
+        let mut last_test_passed = None;
+        for call in session.messages.iter().flat_map(|msg| &msg.tool_calls) {
+            if !call.name.eq_ignore_ascii_case("bash") {
+                continue;
+            }
+            let Some(command) = call.arguments.get("command").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            let command = command.to_lowercase();
+            let result = results.get(call.id.as_str()).copied();
+
+            if TEST_COMMANDS.iter().any(|test| command.contains(test)) {
+                if let Some(result) = result {
+                    last_test_passed = Some(test_run_passed(result));
+                }
+            }
+            if command.contains("git commit") && result.is_some_and(|r| !r.is_error) {
+                assessment.commits += 1;
+            }
+        }
+        assessment.tests_passed = last_test_passed == Some(true);
+        assessment.tests_failed = last_test_passed == Some(false);
+
+        assessment.rollbacks = detector.detect_rollbacks(session).len();
+        let corrections = detector.find_corrections(session);
+        assessment.corrections = corrections.len();
+        assessment.correction_confidence =
+            corrections.iter().map(|c| c.confidence).fold(0.0, f32::max);
+        assessment
+    }
+
+    /// Weigh success against failure signals. Returns None when the session
+    /// is inconclusive.
+    #[must_use]
+    pub fn verdict(&self) -> Option<Verdict> {
+        let clean = self.rollbacks == 0 && self.corrections == 0;
+        let mut success = 0.0;
+        if self.tests_passed {
+            success += 0.45;
+        }
+        if self.commits > 0 {
+            success += 0.35;
+        }
+        if clean && success > 0.0 {
+            success += 0.2;
+        }
+
+        let mut failure: f32 = 0.0;
+        if self.rollbacks > 0 {
+            failure = failure.max(0.6);
+        }
+        if self.tests_failed {
+            failure = failure.max(0.5);
+        }
+        failure = failure.max(self.correction_confidence * 0.8);
+
+        let net = success - failure;
+        if net.abs() < MIN_NET_SCORE {
+            return None;
+        }
+        Some(Verdict {
+            success: net > 0.0,
+            confidence: net.abs().min(1.0),
+        })
+    }
+
+    /// Human-readable list of the signals found.
+    #[must_use]
+    pub fn signals(&self) -> Vec<String> {
+        let mut signals = Vec::new();
+        if self.tests_passed {
+            signals.push("tests passed".to_string());
+        }
+        if self.tests_failed {
+            signals.push("tests failing".to_string());
+        }
+        if self.commits > 0 {
+            signals.push(format!("{} commit(s)", self.commits));
+        }
+        if self.rollbacks > 0 {
+            signals.push(format!("{} rollback(s)", self.rollbacks));
+        }
+        if self.corrections > 0 {
+            signals.push(format!("{} correction(s)", self.corrections));
+        }
+        if self.rollbacks == 0 && self.corrections == 0 {
+            signals.push("no rollback".to_string());
+        }
+        signals
+    }
+}
+
+fn test_run_passed(result: &ToolResult) -> bool {
+    if result.is_error {
+        return false;
+    }
+    let output = result.content.to_lowercase();
+    !(output.contains("test result: failed")
+        || output.contains("failures:")
+        || FAILED_COUNT.is_match(&output))
+}
+
+/// A CASS session with its time span and assessment.
+#[derive(Debug, Clone)]
+pub struct AssessedSession {
+    pub session_id: String,
+    pub project: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub assessment: SessionAssessment,
+}
+
+impl AssessedSession {
+    /// Assess a session. Project and start time fall back to the search
+    /// match; returns None when the session has no start time.
+    #[must_use]
+    pub fn from_session(
+        session: &Session,
+        found: Option<&SessionMatch>,
+        detector: &dyn AntiPatternDetector,
+    ) -> Option<Self> {
+        let started_at = session
+            .metadata
+            .started_at
+            .as_deref()
+            .or_else(|| found.and_then(|m| m.timestamp.as_deref()))
+            .and_then(parse_timestamp)?;
+        Some(Self {
+            session_id: session.id.clone(),
+            project: session
+                .metadata
+                .project
+                .clone()
+                .or_else(|| found.and_then(|m| m.project.clone())),
+            started_at,
+            ended_at: session
+                .metadata
+                .ended_at
+                .as_deref()
+                .and_then(parse_timestamp),
+            assessment: SessionAssessment::assess(session, detector),
+        })
+    }
+
+    /// Weight of this session for a load at `used_at`: None when the session
+    /// does not overlap `[used_at, used_at + window]`, otherwise 1.0 for a
+    /// session already running at load time, falling to 0.5 at the window end.
+    #[must_use]
+    pub fn overlap_weight(&self, used_at: DateTime<Utc>, window: Duration) -> Option<f32> {
+        let end = self.ended_at.unwrap_or(self.started_at + window);
+        if self.started_at > used_at + window || end < used_at {
+            return None;
+        }
+        let gap = (self.started_at - used_at).num_seconds().max(0) as f32;
+        let span = window.num_seconds().max(1) as f32;
+        Some(1.0 - 0.5 * (gap / span).min(1.0))
+    }
+}
+
+/// Pick the most confident outcome for a skill load among sessions from the
+/// same project that overlap it.
+#[must_use]
+pub fn attribute_usage(
+    usage: &UnattributedUsage,
+    sessions: &[AssessedSession],
+    config: &AttributionConfig,
+) -> Option<OutcomeAttributionRecord> {
+    let project = usage.project_path.as_deref()?;
+    let used_at = parse_timestamp(&usage.used_at)?;
+
+    sessions
+        .iter()
+        .filter(|s| {
+            s.project
+                .as_deref()
+                .is_some_and(|p| same_project(project, p))
+        })
+        .filter_map(|s| {
+            let weight = s.overlap_weight(used_at, config.window)?;
+            let verdict = s.assessment.verdict()?;
+            Some((s, verdict.success, verdict.confidence * weight))
+        })
+        .filter(|(_, _, confidence)| *confidence >= config.min_confidence)
+        .max_by(|a, b| a.2.total_cmp(&b.2))
+        .map(|(session, success, confidence)| OutcomeAttributionRecord {
+            usage_id: usage.usage_id,
+            skill_id: usage.skill_id.clone(),
+            session_id: session.session_id.clone(),
+            success,
+            confidence,
+            signals: session.assessment.signals(),
+            created_at: Utc::now().to_rfc3339(),
+        })
+}
+
+/// Whether two project identifiers refer to the same project: equal paths,
+/// one nested in the other, or a bare project name matching a path's last
+/// component.
+#[must_use]
+pub fn same_project(a: &str, b: &str) -> bool {
+    let a = a.trim_end_matches('/');
+    let b = b.trim_end_matches('/');
+    if a.is_empty() || b.is_empty() {
+        return false;
+    }
+    let nested = |outer: &str, inner: &str| {
+        inner
+            .strip_prefix(outer)
+            .is_some_and(|rest| rest.starts_with('/'))
+    };
+    if a == b || nested(a, b) || nested(b, a) {
+        return true;
+    }
+    let name = |path: &str| path.rsplit('/').next().unwrap_or(path).to_string();
+    (!a.contains('/') || !b.contains('/')) && name(a) == name(b)
+}
+
+/// Bandit reward for an attributed outcome: 0.5 (no information) moved
+/// towards 1.0 or 0.0 by the confidence.
+#[must_use]
+pub fn bandit_reward(record: &OutcomeAttributionRecord) -> f32 {
+    let delta = 0.5 * record.confidence.clamp(0.0, 1.0);
+    if record.success {
+        0.5 + delta
+    } else {
+        0.5 - delta
+    }
+}
+
+fn parse_timestamp(raw: &str) -> Option<DateTime<Utc>> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(raw) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    chrono::NaiveDateTime::parse_from_str(raw, "%Y-%m-%d %H:%M:%S")
+        .ok()
+        .map(|dt| dt.and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::antipatterns::detection::DefaultDetector;
+    use crate::cass::client::{SessionMessage, SessionMetadata, ToolCall};
+
+    fn bash(id: &str, command: &str) -> ToolCall {
+        ToolCall {
+            id: id.to_string(),
+            name: "Bash".to_string(),
+            arguments: serde_json::json!({ "command": command }),
+        }
+    }
+
+    fn result(id: &str, content: &str, is_error: bool) -> ToolResult {
+        ToolResult {
+            tool_call_id: id.to_string(),
+            content: content.to_string(),
+            is_error,
+        }
+    }
+
+    fn session(calls: Vec<ToolCall>, results: Vec<ToolResult>, user: &str) -> Session {
+        Session {
+            id: "s1".to_string(),
+            path: "/sessions/s1.jsonl".to_string(),
+            messages: vec![
+                SessionMessage {
+                    index: 0,
+                    role: "user".to_string(),
+                    content: user.to_string(),
+                    tool_calls: Vec::new(),
+                    tool_results: Vec::new(),
+                },
+                SessionMessage {
+                    index: 1,
+                    role: "assistant".to_string(),
+                    content: String::new(),
+                    tool_calls: calls,
+                    tool_results: results,
+                },
+            ],
+            metadata: SessionMetadata {
+                project: Some("/work/app".to_string()),
+                started_at: Some("2026-03-01T10:30:00Z".to_string()),
+                ..SessionMetadata::default()
+            },
+            content_hash: "hash".to_string(),
+        }
+    }
+
+    fn usage(used_at: &str) -> UnattributedUsage {
+        UnattributedUsage {
+            usage_id: 7,
+            skill_id: "rust-errors".to_string(),
+            project_path: Some("/work/app".to_string()),
+            used_at: used_at.to_string(),
+        }
+    }
+
+    #[test]
+    fn passing_tests_and_commit_are_success() {
+        let session = session(
+            vec![
+                bash("1", "cargo test"),
+                bash("2", "cargo test"),
+                bash("3", "git commit -m 'fix'"),
+            ],
+            vec![
+                result("1", "test result: FAILED. 1 passed; 2 failed", true),
+                result("2", "test result: ok. 3 passed; 0 failed", false),
+                result("3", "[main abc1234] fix", false),
+            ],
+            "Please fix the error handling",
+        );
+        let assessment = SessionAssessment::assess(&session, &DefaultDetector::default());
+        assert!(assessment.tests_passed);
+        assert!(!assessment.tests_failed);
+        assert_eq!(assessment.commits, 1);
+
+        let verdict = assessment.verdict().unwrap();
+        assert!(verdict.success);
+        assert!(verdict.confidence > 0.9);
+        assert!(assessment.signals().contains(&"no rollback".to_string()));
+    }
+
+    #[test]
+    fn rollback_is_failure_and_quiet_session_is_inconclusive() {
+        let rolled_back = session(
+            vec![bash("1", "git reset --hard HEAD~1")],
+            vec![result("1", "", false)],
+            "Undo that",
+        );
+        let verdict = SessionAssessment::assess(&rolled_back, &DefaultDetector::default())
+            .verdict()
+            .unwrap();
+        assert!(!verdict.success);
+
+        let quiet = session(vec![bash("1", "ls")], Vec::new(), "List files");
+        assert!(
+            SessionAssessment::assess(&quiet, &DefaultDetector::default())
+                .verdict()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn attribution_matches_project_and_window() {
+        let session = session(
+            vec![bash("1", "pytest"), bash("2", "git commit -am wip")],
+            vec![
+                result("1", "5 passed in 0.1s", false),
+                result("2", "[main 1a2b3c4] wip", false),
+            ],
+            "Add the parser",
+        );
+        let assessed =
+            AssessedSession::from_session(&session, None, &DefaultDetector::default()).unwrap();
+        let config = AttributionConfig::default();
+
+        let record = attribute_usage(
+            &usage("2026-03-01T10:00:00Z"),
+            std::slice::from_ref(&assessed),
+            &config,
+        )
+        .unwrap();
+        assert!(record.success);
+        assert_eq!(record.session_id, "s1");
+        // Started 30 minutes after the load, so slightly discounted
+        assert!(record.confidence < 1.0 && record.confidence > 0.9);
+        assert!(bandit_reward(&record) > 0.9);
+
+        // Load long after the session started
+        assert!(
+            attribute_usage(
+                &usage("2026-03-01T20:00:00Z"),
+                std::slice::from_ref(&assessed),
+                &config
+            )
+            .is_none()
+        );
+
+        // Different project
+        let mut other = usage("2026-03-01T10:00:00Z");
+        other.project_path = Some("/work/other".to_string());
+        assert!(attribute_usage(&other, &[assessed], &config).is_none());
+    }
+
+    #[test]
+    fn project_matching() {
+        assert!(same_project("/work/app", "/work/app/"));
+        assert!(same_project("/work/app", "/work/app/crates/core"));
+        assert!(same_project("/work/app", "app"));
+        assert!(!same_project("/work/app", "/work/application"));
+        assert!(!same_project("/a/app", "/b/app"));
+    }
+}
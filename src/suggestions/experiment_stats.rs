@@ -0,0 +1,788 @@
+//! Statistics for skill experiments.
+//!
+//! Every non-control variant is compared against the control arm:
+//!
+//! - binary metrics (`task_success=true`) use a two-proportion z-test;
+//!   continuous metrics (`latency_ms=840`) use Welch's t-test plus a
+//!   percentile bootstrap interval for the difference in means
+//! - p-values are Holm-corrected across comparisons
+//! - an always-valid p-value (mixture SPRT over the outcome sequence) and the
+//!   Bayesian probability to beat control support stopping early
+//!
+//! `required_sample_size` plans per-variant sample sizes for `ms experiment
+//! create`.
+
+use rand::SeedableRng;
+use rand::distr::Distribution;
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore};
+use rand_distr::Beta;
+use serde::{Deserialize, Serialize};
+
+/// Whether a metric is pass/fail or a measured quantity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MetricKind {
+    Binary,
+    Continuous,
+}
+
+impl MetricKind {
+    /// Binary when every value is a boolean, a success/failure string or a
+    /// number in {0, 1}; continuous otherwise.
+    #[must_use]
+    pub fn infer<'a>(values: impl IntoIterator<Item = &'a serde_json::Value>) -> Self {
+        let binary = values.into_iter().all(|value| match value {
+            serde_json::Value::Bool(_) | serde_json::Value::String(_) => true,
+            serde_json::Value::Number(num) => num.as_f64().is_some_and(|v| v == 0.0 || v == 1.0),
+            _ => true,
+        });
+        if binary {
+            Self::Binary
+        } else {
+            Self::Continuous
+        }
+    }
+}
+
+/// Which direction of a metric counts as an improvement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    Higher,
+    Lower,
+}
+
+impl Direction {
+    /// Lower is better for latency, durations, token counts, costs and
+    /// error counts; higher otherwise.
+    #[must_use]
+    pub fn infer(metric: &str) -> Self {
+        const LOWER: &[&str] = &[
+            "latency", "duration", "time", "elapsed", "tokens", "cost", "error", "retries", "_ms",
+            "_secs",
+        ];
+        let metric = metric.to_lowercase();
+        if LOWER.iter().any(|hint| metric.contains(hint)) {
+            Self::Lower
+        } else {
+            Self::Higher
+        }
+    }
+
+    const fn improves(self, diff: f64) -> bool {
+        match self {
+            Self::Higher => diff > 0.0,
+            Self::Lower => diff < 0.0,
+        }
+    }
+}
+
+/// Outcomes of one variant. `observations` are `(sequence, value)` pairs in
+/// the order the outcomes were recorded across the whole experiment; binary
+/// outcomes are 1.0 / 0.0.
+#[derive(Debug, Clone, Default)]
+pub struct Arm {
+    pub id: String,
+    pub observations: Vec<(usize, f64)>,
+}
+
+impl Arm {
+    #[must_use]
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            observations: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn summary(&self) -> Summary {
+        let mut summary = Summary::default();
+        for (_, value) in &self.observations {
+            summary.push(*value);
+        }
+        summary
+    }
+}
+
+/// Running count, mean and variance (Welford).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct Summary {
+    pub n: u64,
+    pub mean: f64,
+    #[serde(skip)]
+    m2: f64,
+}
+
+impl Summary {
+    pub fn push(&mut self, value: f64) {
+        self.n += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.n as f64;
+        self.m2 += delta * (value - self.mean);
+    }
+
+    /// Sample variance (n - 1 denominator)
+    #[must_use]
+    pub fn variance(&self) -> f64 {
+        if self.n < 2 {
+            0.0
+        } else {
+            self.m2 / (self.n - 1) as f64
+        }
+    }
+
+    #[must_use]
+    pub fn std_dev(&self) -> f64 {
+        self.variance().sqrt()
+    }
+}
+
+/// Settings for `compare_to_control`.
+#[derive(Debug, Clone)]
+pub struct AnalysisConfig {
+    pub kind: MetricKind,
+    pub direction: Direction,
+    /// Family-wise error rate
+    pub alpha: f64,
+    pub bootstrap_samples: usize,
+    /// Mixing scale of the sequential test, as a fraction of the pooled
+    /// standard deviation (the effect size it is most sensitive to)
+    pub sequential_tau: f64,
+    /// Seed for bootstrap and posterior sampling, so repeated status calls
+    /// agree
+    pub seed: u64,
+}
+
+impl Default for AnalysisConfig {
+    fn default() -> Self {
+        Self {
+            kind: MetricKind::Binary,
+            direction: Direction::Higher,
+            alpha: 0.05,
+            bootstrap_samples: 2000,
+            sequential_tau: 0.5,
+            seed: 0x5eed,
+        }
+    }
+}
+
+/// One variant compared against control.
+#[derive(Debug, Clone, Serialize)]
+pub struct Comparison {
+    pub variant: String,
+    pub control: String,
+    pub variant_n: u64,
+    pub control_n: u64,
+    /// Variant mean (or rate) minus control mean
+    pub diff: f64,
+    /// `diff` relative to the control mean
+    pub relative_lift: Option<f64>,
+    /// Unadjusted fixed-horizon p-value
+    pub p_value: Option<f64>,
+    /// Holm-adjusted p-value
+    pub adjusted_p_value: Option<f64>,
+    /// Confidence interval for `diff` (Wald or Welch)
+    pub confidence_interval: Option<[f64; 2]>,
+    /// Percentile bootstrap interval for `diff` (continuous metrics)
+    pub bootstrap_interval: Option<[f64; 2]>,
+    /// Always-valid p-value from the mixture SPRT; safe to check at any time
+    pub sequential_p_value: Option<f64>,
+    /// Posterior probability that the variant is better than control
+    pub prob_beat_control: Option<f64>,
+    /// The variant moves the metric in the better direction
+    pub improves: bool,
+    /// Holm-adjusted p-value is below alpha
+    pub significant: bool,
+    /// Always-valid p-value is below alpha / comparisons
+    pub sequential_significant: bool,
+}
+
+/// Compare every arm in `variants` against `control`.
+#[must_use]
+pub fn compare_to_control(
+    control: &Arm,
+    variants: &[Arm],
+    config: &AnalysisConfig,
+) -> Vec<Comparison> {
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let control_summary = control.summary();
+    let comparisons = variants.len().max(1) as f64;
+
+    let mut results: Vec<Comparison> = variants
+        .iter()
+        .map(|variant| {
+            let summary = variant.summary();
+            let diff = summary.mean - control_summary.mean;
+            let (p_value, confidence_interval) = match config.kind {
+                MetricKind::Binary => two_proportion_test(&control_summary, &summary, config.alpha),
+                MetricKind::Continuous => welch_test(&control_summary, &summary, config.alpha),
+            };
+            let bootstrap_interval = (config.kind == MetricKind::Continuous)
+                .then(|| {
+                    bootstrap_interval(
+                        control,
+                        variant,
+                        config.bootstrap_samples,
+                        config.alpha,
+                        &mut rng,
+                    )
+                })
+                .flatten();
+            let sequential_p_value = sequential_p_value(control, variant, config);
+            let prob_beat_control = prob_beat_control(&control_summary, &summary, config, &mut rng);
+
+            Comparison {
+                variant: variant.id.clone(),
+                control: control.id.clone(),
+                variant_n: summary.n,
+                control_n: control_summary.n,
+                diff,
+                relative_lift: (control_summary.mean.abs() > f64::EPSILON)
+                    .then(|| diff / control_summary.mean.abs()),
+                p_value,
+                adjusted_p_value: None,
+                confidence_interval,
+                bootstrap_interval,
+                sequential_p_value,
+                prob_beat_control,
+                improves: config.direction.improves(diff),
+                significant: false,
+                sequential_significant: sequential_p_value
+                    .is_some_and(|p| p <= config.alpha / comparisons),
+            }
+        })
+        .collect();
+
+    let raw: Vec<Option<f64>> = results.iter().map(|c| c.p_value).collect();
+    for (comparison, adjusted) in results.iter_mut().zip(holm_adjust(&raw)) {
+        comparison.adjusted_p_value = adjusted;
+        comparison.significant = adjusted.is_some_and(|p| p <= config.alpha);
+    }
+    results
+}
+
+/// Holm step-down adjustment. Missing p-values stay missing and do not count
+/// towards the number of comparisons.
+#[must_use]
+pub fn holm_adjust(p_values: &[Option<f64>]) -> Vec<Option<f64>> {
+    let mut order: Vec<(usize, f64)> = p_values
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, p)| p.map(|p| (idx, p)))
+        .collect();
+    order.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+    let m = order.len();
+    let mut adjusted = vec![None; p_values.len()];
+    let mut running_max: f64 = 0.0;
+    for (rank, (idx, p)) in order.into_iter().enumerate() {
+        running_max = running_max.max(((m - rank) as f64 * p).min(1.0));
+        adjusted[idx] = Some(running_max);
+    }
+    adjusted
+}
+
+fn two_proportion_test(
+    control: &Summary,
+    variant: &Summary,
+    alpha: f64,
+) -> (Option<f64>, Option<[f64; 2]>) {
+    if control.n == 0 || variant.n == 0 {
+        return (None, None);
+    }
+    let (n1, n2) = (variant.n as f64, control.n as f64);
+    let (p1, p2) = (variant.mean, control.mean);
+    let pooled = p1.mul_add(n1, p2 * n2) / (n1 + n2);
+    let se = (pooled * (1.0 - pooled) * (1.0 / n1 + 1.0 / n2)).sqrt();
+    if se == 0.0 {
+        return (None, None);
+    }
+    let z = (p1 - p2) / se;
+    let p_value = 2.0 * (1.0 - normal_cdf(z.abs()));
+
+    let se_diff = (p1 * (1.0 - p1) / n1 + p2 * (1.0 - p2) / n2).sqrt();
+    let z_crit = normal_quantile(1.0 - alpha / 2.0);
+    let diff = p1 - p2;
+    (
+        Some(p_value),
+        Some([
+            z_crit.mul_add(-se_diff, diff),
+            z_crit.mul_add(se_diff, diff),
+        ]),
+    )
+}
+
+fn welch_test(control: &Summary, variant: &Summary, alpha: f64) -> (Option<f64>, Option<[f64; 2]>) {
+    if control.n < 2 || variant.n < 2 {
+        return (None, None);
+    }
+    let a = variant.variance() / variant.n as f64;
+    let b = control.variance() / control.n as f64;
+    let se = (a + b).sqrt();
+    if se == 0.0 {
+        return (None, None);
+    }
+    let df = (a + b).powi(2) / (a * a / (variant.n - 1) as f64 + b * b / (control.n - 1) as f64);
+    let diff = variant.mean - control.mean;
+    let t = diff / se;
+    let p_value = 2.0 * (1.0 - student_t_cdf(t.abs(), df));
+    let t_crit = student_t_quantile(1.0 - alpha / 2.0, df);
+    (
+        Some(p_value.clamp(0.0, 1.0)),
+        Some([t_crit.mul_add(-se, diff), t_crit.mul_add(se, diff)]),
+    )
+}
+
+fn bootstrap_interval(
+    control: &Arm,
+    variant: &Arm,
+    samples: usize,
+    alpha: f64,
+    rng: &mut impl RngCore,
+) -> Option<[f64; 2]> {
+    if control.observations.len() < 2 || variant.observations.len() < 2 || samples == 0 {
+        return None;
+    }
+    let resample_mean = |arm: &Arm, rng: &mut dyn RngCore| {
+        let n = arm.observations.len();
+        (0..n)
+            .map(|_| arm.observations[rng.random_range(0..n)].1)
+            .sum::<f64>()
+            / n as f64
+    };
+    let mut diffs: Vec<f64> = (0..samples)
+        .map(|_| resample_mean(variant, rng) - resample_mean(control, rng))
+        .collect();
+    diffs.sort_by(f64::total_cmp);
+    let at = |q: f64| diffs[((q * (samples - 1) as f64).round() as usize).min(samples - 1)];
+    Some([at(alpha / 2.0), at(1.0 - alpha / 2.0)])
+}
+
+/// Always-valid p-value for the difference in means, from a normal mixture
+/// SPRT evaluated after every recorded outcome. Returns the running minimum
+/// of `1 / likelihood ratio`, which can be checked after any outcome without
+/// inflating the false-positive rate.
+#[must_use]
+pub fn sequential_p_value(control: &Arm, variant: &Arm, config: &AnalysisConfig) -> Option<f64> {
+    let mut events: Vec<(usize, bool, f64)> = control
+        .observations
+        .iter()
+        .map(|(seq, value)| (*seq, false, *value))
+        .chain(
+            variant
+                .observations
+                .iter()
+                .map(|(seq, value)| (*seq, true, *value)),
+        )
+        .collect();
+    events.sort_by_key(|(seq, _, _)| *seq);
+
+    let (mut c, mut v) = (Summary::default(), Summary::default());
+    let mut p_value: Option<f64> = None;
+    for (_, is_variant, value) in events {
+        if is_variant {
+            v.push(value);
+        } else {
+            c.push(value);
+        }
+        if c.n < 2 || v.n < 2 {
+            continue;
+        }
+
+        let (var_c, var_v) = match config.kind {
+            // Smoothed so that early all-success or all-failure runs do not
+            // produce zero variance
+            MetricKind::Binary => {
+                let smooth = |s: &Summary| {
+                    let rate = s.mean.mul_add(s.n as f64, 0.5) / (s.n as f64 + 1.0);
+                    rate * (1.0 - rate)
+                };
+                (smooth(&c), smooth(&v))
+            }
+            MetricKind::Continuous => (c.variance(), v.variance()),
+        };
+        let variance = var_c / c.n as f64 + var_v / v.n as f64;
+        if variance <= 0.0 {
+            continue;
+        }
+        let pooled = f64::midpoint(var_c, var_v);
+        let tau2 = config.sequential_tau.powi(2) * pooled;
+        let diff = v.mean - c.mean;
+        let log_ratio = 0.5 * (variance / (variance + tau2)).ln()
+            + tau2 * diff * diff / (2.0 * variance * (variance + tau2));
+        let p = (-log_ratio).exp().min(1.0);
+        p_value = Some(p_value.map_or(p, |best| best.min(p)));
+    }
+    p_value
+}
+
+fn prob_beat_control(
+    control: &Summary,
+    variant: &Summary,
+    config: &AnalysisConfig,
+    rng: &mut impl RngCore,
+) -> Option<f64> {
+    if control.n == 0 || variant.n == 0 {
+        return None;
+    }
+    match config.kind {
+        MetricKind::Binary => {
+            let posterior = |s: &Summary| {
+                let successes = (s.mean * s.n as f64).round();
+                Beta::new(1.0 + successes, 1.0 + s.n as f64 - successes).ok()
+            };
+            let (c, v) = (posterior(control)?, posterior(variant)?);
+            const DRAWS: usize = 4000;
+            let wins = (0..DRAWS)
+                .filter(|_| config.direction.improves(v.sample(rng) - c.sample(rng)))
+                .count();
+            Some(wins as f64 / DRAWS as f64)
+        }
+        MetricKind::Continuous => {
+            if control.n < 2 || variant.n < 2 {
+                return None;
+            }
+            let se = (control.variance() / control.n as f64
+                + variant.variance() / variant.n as f64)
+                .sqrt();
+            let diff = variant.mean - control.mean;
+            let oriented = match config.direction {
+                Direction::Higher => diff,
+                Direction::Lower => -diff,
+            };
+            if se == 0.0 {
+                return Some(if oriented > 0.0 { 1.0 } else { 0.0 });
+            }
+            Some(normal_cdf(oriented / se))
+        }
+    }
+}
+
+/// Per-variant sample size for a two-sided test of each variant against
+/// control at family-wise `alpha` (Bonferroni over `comparisons`) with the
+/// given `power`.
+///
+/// `baseline` is the control success rate for binary metrics and the
+/// standard deviation for continuous ones; `mde` is the smallest absolute
+/// difference worth detecting.
+#[must_use]
+pub fn required_sample_size(
+    kind: MetricKind,
+    baseline: f64,
+    mde: f64,
+    alpha: f64,
+    power: f64,
+    comparisons: usize,
+) -> Option<u64> {
+    if mde <= 0.0 || !(0.0..1.0).contains(&alpha) || !(0.0..1.0).contains(&power) {
+        return None;
+    }
+    let z_alpha = normal_quantile(1.0 - alpha / (2.0 * comparisons.max(1) as f64));
+    let z_power = normal_quantile(power);
+    let n = match kind {
+        MetricKind::Binary => {
+            if !(0.0..=1.0).contains(&baseline) {
+                return None;
+            }
+            let p1 = baseline;
+            let p2 = if baseline + mde <= 1.0 {
+                baseline + mde
+            } else {
+                baseline - mde
+            };
+            if !(0.0..=1.0).contains(&p2) {
+                return None;
+            }
+            let p_bar = f64::midpoint(p1, p2);
+            let term = z_alpha * (2.0 * p_bar * (1.0 - p_bar)).sqrt()
+                + z_power * p2.mul_add(1.0 - p2, p1 * (1.0 - p1)).sqrt();
+            term * term / (mde * mde)
+        }
+        MetricKind::Continuous => {
+            if baseline <= 0.0 {
+                return None;
+            }
+            2.0 * (z_alpha + z_power).powi(2) * baseline * baseline / (mde * mde)
+        }
+    };
+    Some(n.ceil() as u64)
+}
+
+/// Standard normal CDF.
+#[must_use]
+pub fn normal_cdf(z: f64) -> f64 {
+    0.5 * erfc(-z / std::f64::consts::SQRT_2)
+}
+
+/// Complementary error function (Numerical Recipes `erfcc`, relative error
+/// below 1.2e-7).
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let poly = t.mul_add(0.170_872_77, -0.822_152_23);
+    let poly = t.mul_add(poly, 1.488_515_87);
+    let poly = t.mul_add(poly, -1.135_203_98);
+    let poly = t.mul_add(poly, 0.278_868_07);
+    let poly = t.mul_add(poly, -0.186_288_06);
+    let poly = t.mul_add(poly, 0.096_784_18);
+    let poly = t.mul_add(poly, 0.374_091_96);
+    let poly = t.mul_add(poly, 1.000_023_68);
+    let poly = t.mul_add(poly, -1.265_512_23);
+    let r = t * (-z).mul_add(z, poly).exp();
+    if x >= 0.0 { r } else { 2.0 - r }
+}
+
+/// Inverse of the standard normal CDF (Acklam's rational approximation).
+#[must_use]
+pub fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1,
+        2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1,
+        2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1,
+        1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3,
+        -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838,
+        -2.549_732_539_343_734,
+        4.374_664_141_464_968,
+        2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3,
+        3.224_671_290_700_398e-1,
+        2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    const P_LOW: f64 = 0.024_25;
+
+    if p <= 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if p >= 1.0 {
+        return f64::INFINITY;
+    }
+    let tail = |q: f64| {
+        let num = C.iter().fold(0.0_f64, |acc, c| acc.mul_add(q, *c));
+        let den = D
+            .iter()
+            .fold(0.0_f64, |acc, d| acc.mul_add(q, *d))
+            .mul_add(q, 1.0);
+        num / den
+    };
+    if p < P_LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p <= 1.0 - P_LOW {
+        let q = p - 0.5;
+        let r = q * q;
+        let num = A.iter().fold(0.0_f64, |acc, a| acc.mul_add(r, *a));
+        let den = B
+            .iter()
+            .fold(0.0_f64, |acc, b| acc.mul_add(r, *b))
+            .mul_add(r, 1.0);
+        num * q / den
+    } else {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    }
+}
+
+/// Student's t CDF.
+#[must_use]
+pub fn student_t_cdf(t: f64, df: f64) -> f64 {
+    let tail = 0.5 * regularized_incomplete_beta(df / 2.0, 0.5, df / t.mul_add(t, df));
+    if t >= 0.0 { 1.0 - tail } else { tail }
+}
+
+fn student_t_quantile(p: f64, df: f64) -> f64 {
+    let (mut lo, mut hi) = (-1e6_f64, 1e6_f64);
+    for _ in 0..200 {
+        let mid = f64::midpoint(lo, hi);
+        if student_t_cdf(mid, df) < p {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+        if hi - lo < 1e-9 {
+            break;
+        }
+    }
+    f64::midpoint(lo, hi)
+}
+
+fn regularized_incomplete_beta(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+    let front =
+        (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp();
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_continued_fraction(a, b, x) / a
+    } else {
+        1.0 - front * beta_continued_fraction(b, a, 1.0 - x) / b
+    }
+}
+
+/// Lentz's continued fraction for the incomplete beta function.
+fn beta_continued_fraction(a: f64, b: f64, x: f64) -> f64 {
+    const TINY: f64 = 1e-300;
+    let guard = |v: f64| if v.abs() < TINY { TINY } else { v };
+    let (qab, qap, qam) = (a + b, a + 1.0, a - 1.0);
+    let mut c = 1.0;
+    let mut d = 1.0 / guard(1.0 - qab * x / qap);
+    let mut h = d;
+    for m in 1..=300 {
+        let m = f64::from(m);
+        let m2 = 2.0 * m;
+        let aa = m * (b - m) * x / ((qam + m2) * (a + m2));
+        d = 1.0 / guard(aa.mul_add(d, 1.0));
+        c = guard(1.0 + aa / c);
+        h *= d * c;
+        let aa = -(a + m) * (qab + m) * x / ((a + m2) * (qap + m2));
+        d = 1.0 / guard(aa.mul_add(d, 1.0));
+        c = guard(1.0 + aa / c);
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.0).abs() < 1e-14 {
+            break;
+        }
+    }
+    h
+}
+
+/// Lanczos approximation of ln Γ(x).
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    if x < 0.5 {
+        let pi = std::f64::consts::PI;
+        return (pi / (pi * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+    let x = x - 1.0;
+    let t = x + 7.5;
+    let series = COEFFS[1..]
+        .iter()
+        .enumerate()
+        .fold(COEFFS[0], |acc, (i, c)| acc + c / (x + (i + 1) as f64));
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + series.ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arm(id: &str, start: usize, values: &[f64]) -> Arm {
+        Arm {
+            id: id.to_string(),
+            observations: values
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (start + i * 3, *v))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn distribution_functions() {
+        assert!((normal_cdf(1.96) - 0.975).abs() < 1e-4);
+        assert!((normal_cdf(-1.0) - 0.158_655).abs() < 1e-5);
+        assert!((normal_quantile(0.975) - 1.959_964).abs() < 1e-5);
+        assert!((student_t_cdf(2.228, 10.0) - 0.975).abs() < 1e-3);
+        assert!((student_t_quantile(0.975, 10.0) - 2.228).abs() < 1e-3);
+        assert!((ln_gamma(5.0) - 24.0_f64.ln()).abs() < 1e-10);
+    }
+
+    #[test]
+    fn holm_adjustment() {
+        let adjusted = holm_adjust(&[Some(0.01), None, Some(0.04), Some(0.03)]);
+        assert_eq!(adjusted[1], None);
+        assert!((adjusted[0].unwrap() - 0.03).abs() < 1e-12);
+        assert!((adjusted[3].unwrap() - 0.06).abs() < 1e-12);
+        assert!((adjusted[2].unwrap() - 0.06).abs() < 1e-12);
+    }
+
+    #[test]
+    fn continuous_multi_arm_latency() {
+        let control: Vec<f64> = (0..40).map(|i| 1000.0 + f64::from(i % 7) * 20.0).collect();
+        let faster: Vec<f64> = control.iter().map(|v| v - 200.0).collect();
+        let same = control.clone();
+        let config = AnalysisConfig {
+            kind: MetricKind::Continuous,
+            direction: Direction::infer("latency_ms"),
+            ..AnalysisConfig::default()
+        };
+        assert_eq!(config.direction, Direction::Lower);
+
+        let results = compare_to_control(
+            &arm("control", 0, &control),
+            &[arm("faster", 1, &faster), arm("same", 2, &same)],
+            &config,
+        );
+        let faster = &results[0];
+        assert!(faster.improves && faster.significant && faster.sequential_significant);
+        assert!((faster.diff + 200.0).abs() < 1e-9);
+        let [lo, hi] = faster.bootstrap_interval.unwrap();
+        assert!(lo < -190.0 && hi > -210.0 && hi < 0.0);
+        assert!(faster.prob_beat_control.unwrap() > 0.99);
+
+        let same = &results[1];
+        assert!(!same.significant && !same.sequential_significant);
+        assert!(same.adjusted_p_value.unwrap() >= same.p_value.unwrap());
+    }
+
+    #[test]
+    fn binary_comparison_and_metric_inference() {
+        let values = [serde_json::json!(true), serde_json::json!(0)];
+        assert_eq!(MetricKind::infer(&values), MetricKind::Binary);
+        let values = [serde_json::json!(840), serde_json::json!(1)];
+        assert_eq!(MetricKind::infer(&values), MetricKind::Continuous);
+
+        let control: Vec<f64> = (0..200).map(|i| f64::from(u8::from(i % 2 == 0))).collect();
+        let better: Vec<f64> = (0..200).map(|i| f64::from(u8::from(i % 5 != 0))).collect();
+        let results = compare_to_control(
+            &arm("control", 0, &control),
+            &[arm("better", 1, &better)],
+            &AnalysisConfig::default(),
+        );
+        assert!(results[0].significant && results[0].improves);
+        assert!(results[0].prob_beat_control.unwrap() > 0.99);
+        assert!(results[0].bootstrap_interval.is_none());
+    }
+
+    #[test]
+    fn sample_size_planning() {
+        // Classic example: 0.5 -> 0.6 at alpha 0.05, power 0.8 needs ~390
+        let n = required_sample_size(MetricKind::Binary, 0.5, 0.1, 0.05, 0.8, 1).unwrap();
+        assert!((380..=400).contains(&n), "{n}");
+        // More comparisons need more samples
+        let corrected = required_sample_size(MetricKind::Binary, 0.5, 0.1, 0.05, 0.8, 3).unwrap();
+        assert!(corrected > n);
+        // Continuous: 2 * (1.96 + 0.84)^2 = ~16 per unit effect size
+        let n = required_sample_size(MetricKind::Continuous, 100.0, 100.0, 0.05, 0.8, 1).unwrap();
+        assert_eq!(n, 16);
+        assert!(required_sample_size(MetricKind::Binary, 0.5, 0.0, 0.05, 0.8, 1).is_none());
+    }
+}
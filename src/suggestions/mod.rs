@@ -1,10 +1,14 @@
-//! Suggestion utilities (cooldowns, fingerprints, tracking, bandits).
+//! Suggestion utilities (cooldowns, fingerprints, tracking, bandits, outcome
+//! attribution, experiment statistics).
 
+pub mod attribution;
 pub mod bandit;
 pub mod cooldown;
 pub mod cooldown_storage;
+pub mod experiment_stats;
 pub mod tracking;
 
+pub use attribution::{AssessedSession, AttributionConfig, SessionAssessment, Verdict};
 pub use bandit::{BanditConfig, SignalBandit};
 pub use cooldown::{CooldownStats, CooldownStatus, SuggestionCooldownCache, SuggestionResponse};
 pub use tracking::{
@@ -10,8 +10,9 @@ use sha2::{Digest, Sha256};
 use tracing::{info, warn};
 
 use crate::config::RuConfig;
-use crate::core::SkillSpec;
+use crate::core::{SkillLayer, SkillSpec};
 use crate::error::{MsError, Result};
+use crate::security::{PolicyAction, PolicyEngine};
 use crate::storage::{Database, GitArchive, TxManager};
 
 use super::SyncConfig;
@@ -80,6 +81,7 @@ pub struct SyncEngine {
     db: Arc<Database>,
     ms_root: PathBuf,
     ru_config: RuConfig,
+    policy: Option<Arc<PolicyEngine>>,
 }
 
 impl SyncEngine {
@@ -100,9 +102,18 @@ impl SyncEngine {
             db,
             ms_root,
             ru_config,
+            policy: None,
         }
     }
 
+    /// Gate pulled skills through the organisation policy before they are
+    /// written to the archive.
+    #[must_use]
+    pub fn with_policy(mut self, policy: Arc<PolicyEngine>) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
     #[must_use]
     pub const fn config(&self) -> &SyncConfig {
         &self.config
@@ -421,9 +432,10 @@ impl SyncEngine {
                             continue;
                         }
                         ConflictStrategy::PreferRemote => {
-                            self.apply_jfp_payload(remote, payload, options, &tx_mgr, report)?;
-                            report.resolved.push(skill_id.clone());
-                            total_pulled += 1;
+                            if self.apply_jfp_payload(remote, payload, options, &tx_mgr, report)? {
+                                report.resolved.push(skill_id.clone());
+                                total_pulled += 1;
+                            }
                             continue;
                         }
                         ConflictStrategy::KeepBoth => {
@@ -440,6 +452,10 @@ impl SyncEngine {
                             let fork_id = unique_fork_id(&self.git, skill_id)?;
                             spec.metadata.id = fork_id.clone();
                             spec.metadata.name = format!("{} (cloud)", spec.metadata.name);
+                            if let Some(denial) = self.pull_denied(&spec) {
+                                report.errors.push(denial);
+                                continue;
+                            }
                             if !options.dry_run {
                                 tx_mgr.write_skill_locked(&spec)?;
                             }
@@ -462,8 +478,9 @@ impl SyncEngine {
                     }
                 }
 
-                self.apply_jfp_payload(remote, payload, options, &tx_mgr, report)?;
-                total_pulled += 1;
+                if self.apply_jfp_payload(remote, payload, options, &tx_mgr, report)? {
+                    total_pulled += 1;
+                }
             }
 
             // Process tombstones (deletions)
@@ -735,6 +752,7 @@ impl SyncEngine {
         Ok(())
     }
 
+    /// Apply a pulled JFP payload; returns `false` when policy blocked it.
     fn apply_jfp_payload(
         &mut self,
         remote: &RemoteConfig,
@@ -742,10 +760,15 @@ impl SyncEngine {
         options: &SyncOptions,
         tx_mgr: &TxManager,
         report: &mut SyncReport,
-    ) -> Result<()> {
+    ) -> Result<bool> {
         let skill_id = &payload.ms_skill_id;
         let spec = payload_to_skill_spec(payload)?;
 
+        if let Some(denial) = self.pull_denied(&spec) {
+            report.errors.push(denial);
+            return Ok(false);
+        }
+
         if !options.dry_run {
             tx_mgr.write_skill_locked(&spec)?;
         }
@@ -784,7 +807,42 @@ impl SyncEngine {
             .skill_states
             .insert(skill_id.clone(), state_entry);
 
-        Ok(())
+        Ok(true)
+    }
+
+    /// Policy denial for writing a pulled skill, formatted for the report.
+    ///
+    /// Pulled skills land in the project archive, so they are checked as
+    /// project-layer skills.
+    fn pull_denied(&self, spec: &SkillSpec) -> Option<String> {
+        let policy = self.policy.as_ref()?;
+        policy
+            .lint_corpus(&self.db, &self.git)
+            .and_then(|corpus| {
+                policy.enforce(&PolicyAction::IndexSkill {
+                    spec,
+                    layer: SkillLayer::Project,
+                    corpus: &corpus,
+                })
+            })
+            .err()
+            .map(|err| format!("{}: {err}", spec.metadata.id))
+    }
+
+    /// Write a pulled skill unless policy denies it; denials are recorded
+    /// as report errors and return `false`.
+    fn write_pulled(
+        &self,
+        tx_mgr: &TxManager,
+        spec: &SkillSpec,
+        report: &mut SyncReport,
+    ) -> Result<bool> {
+        if let Some(denial) = self.pull_denied(spec) {
+            report.errors.push(denial);
+            return Ok(false);
+        }
+        tx_mgr.write_skill_locked(spec)?;
+        Ok(true)
     }
 
     fn local_skill_modified_at(&self, skill_id: &str) -> Option<DateTime<Utc>> {
@@ -848,6 +906,12 @@ impl SyncEngine {
 
             match status {
                 SkillSyncStatus::Synced => {
+                    if !options.dry_run
+                        && merge_signatures(&self.git, remote_git, &id, allow_pull, allow_push)?
+                        && remote.remote_type == RemoteType::Git
+                    {
+                        needs_git_push = true;
+                    }
                     report.skipped.push(id.clone());
                 }
                 SkillSyncStatus::LocalAhead | SkillSyncStatus::LocalOnly => {
@@ -855,6 +919,7 @@ impl SyncEngine {
                         if !options.dry_run {
                             let spec = self.git.read_skill(&id)?;
                             remote_git.write_skill(&spec)?;
+                            copy_signatures(&self.git, remote_git, &id)?;
                             if remote.remote_type == RemoteType::Git {
                                 needs_git_push = true;
                             }
@@ -867,12 +932,20 @@ impl SyncEngine {
                 }
                 SkillSyncStatus::RemoteAhead | SkillSyncStatus::RemoteOnly => {
                     if allow_pull {
-                        if !options.dry_run {
+                        let pulled = if options.dry_run {
+                            true
+                        } else {
                             let spec = remote_git.read_skill(&id)?;
-                            tx_mgr.write_skill_locked(&spec)?;
+                            let written = self.write_pulled(&tx_mgr, &spec, report)?;
+                            if written {
+                                copy_signatures(remote_git, &self.git, &id)?;
+                            }
+                            written
+                        };
+                        if pulled {
+                            report.pulled.push(id.clone());
+                            final_status = SkillSyncStatus::Synced;
                         }
-                        report.pulled.push(id.clone());
-                        final_status = SkillSyncStatus::Synced;
                     } else {
                         report.skipped.push(id.clone());
                     }
@@ -994,6 +1067,7 @@ impl SyncEngine {
                     if !options.dry_run {
                         let spec = self.git.read_skill(id)?;
                         remote_git.write_skill(&spec)?;
+                        copy_signatures(&self.git, remote_git, id)?;
                         if remote_is_git {
                             *needs_git_push = true;
                         }
@@ -1009,7 +1083,10 @@ impl SyncEngine {
                 if allow_pull {
                     if !options.dry_run {
                         let spec = remote_git.read_skill(id)?;
-                        tx_mgr.write_skill_locked(&spec)?;
+                        if !self.write_pulled(tx_mgr, &spec, report)? {
+                            return Ok(SkillSyncStatus::Conflict);
+                        }
+                        copy_signatures(remote_git, &self.git, id)?;
                     }
                     report.pulled.push(id.to_string());
                     Ok(SkillSyncStatus::Synced)
@@ -1064,7 +1141,9 @@ impl SyncEngine {
                     let mut spec = remote_git.read_skill(id)?;
                     spec.metadata.id = fork_id.clone();
                     spec.metadata.name = format!("{} (remote)", spec.metadata.name);
-                    tx_mgr.write_skill_locked(&spec)?;
+                    if !self.write_pulled(tx_mgr, &spec, report)? {
+                        return Ok(SkillSyncStatus::Conflict);
+                    }
                 }
                 report.forked.push(fork_id);
 
@@ -1072,6 +1151,7 @@ impl SyncEngine {
                     if !options.dry_run {
                         let spec = self.git.read_skill(id)?;
                         remote_git.write_skill(&spec)?;
+                        copy_signatures(&self.git, remote_git, id)?;
                         if remote_is_git {
                             *needs_git_push = true;
                         }
@@ -1153,6 +1233,50 @@ impl SyncEngine {
     }
 }
 
+/// Carry a skill's detached signatures along with its spec.
+fn copy_signatures(from: &GitArchive, to: &GitArchive, id: &str) -> Result<()> {
+    let signatures = from.read_signatures(id)?;
+    if signatures.is_empty() || to.read_signatures(id)? == signatures {
+        return Ok(());
+    }
+    to.write_signatures(id, &signatures)?;
+    Ok(())
+}
+
+/// Union signatures for a skill whose content is already in sync, so signing
+/// on one machine reaches the others. Returns true when `remote` changed.
+fn merge_signatures(
+    local: &GitArchive,
+    remote: &GitArchive,
+    id: &str,
+    allow_pull: bool,
+    allow_push: bool,
+) -> Result<bool> {
+    let local_sigs = local.read_signatures(id)?;
+    let remote_sigs = remote.read_signatures(id)?;
+    if local_sigs == remote_sigs {
+        return Ok(false);
+    }
+    let mut merged = local_sigs.clone();
+    for signature in &remote_sigs.signatures {
+        if !merged
+            .signatures
+            .iter()
+            .any(|existing| existing.signature.key_id == signature.signature.key_id)
+        {
+            merged.signatures.push(signature.clone());
+        }
+    }
+    if allow_pull && merged != local_sigs {
+        local.write_signatures(id, &merged)?;
+    }
+    if allow_push && merged != remote_sigs {
+        remote.write_signatures(id, &merged)?;
+        return Ok(true);
+    }
+    Ok(false)
+}
+
 fn unique_fork_id(archive: &GitArchive, id: &str) -> Result<String> {
     let base = format!("{id}-remote");
     if !archive.skill_exists(&base) {
@@ -2,12 +2,16 @@ use std::path::PathBuf;
 
 use proptest::prelude::*;
 
+use crate::bundler::AdvisoryConfig;
 use crate::config::{
     AgentMailConfig, CacheConfig, CassConfig, Config, DisclosureConfig, LayersConfig, RobotConfig,
     SafetyConfig, SearchConfig, SecurityConfig, SkillPathsConfig, UpdateConfig,
 };
+use crate::core::safety::SafetyEvaluator;
 use crate::core::skill::{BlockType, SkillBlock, SkillMetadata, SkillSection, SkillSpec};
-use crate::security::{AcipConfig, TrustBoundaryConfig, TrustLevel};
+use crate::security::{
+    AcipConfig, SecretScanConfig, SigningConfig, TrustBoundaryConfig, TrustLevel,
+};
 
 fn arb_block_type() -> impl Strategy<Value = BlockType> {
     prop_oneof![
@@ -26,6 +30,7 @@ fn arb_skill_block() -> impl Strategy<Value = SkillBlock> {
             id,
             block_type,
             content,
+            suppressions: Vec::new(),
         }
     })
 }
@@ -76,6 +81,7 @@ fn arb_skill_metadata() -> impl Strategy<Value = SkillMetadata> {
                 author,
                 license,
                 context: Default::default(),
+                scripts: Vec::new(),
             },
         )
 }
@@ -162,6 +168,7 @@ fn arb_search() -> impl Strategy<Value = SearchConfig> {
                     api_endpoint: "https://api.openai.com/v1/embeddings".to_string(),
                     api_model: "text-embedding-3-small".to_string(),
                     api_key_env: "OPENAI_API_KEY".to_string(),
+                    learned_rerank: true,
                 }
             },
         )
@@ -264,15 +271,23 @@ fn arb_acip() -> impl Strategy<Value = AcipConfig> {
 }
 
 fn arb_security() -> impl Strategy<Value = SecurityConfig> {
-    arb_acip().prop_map(|acip| SecurityConfig { acip })
+    arb_acip().prop_map(|acip| SecurityConfig {
+        acip,
+        signing: SigningConfig::default(),
+        secrets: SecretScanConfig::default(),
+        advisories: AdvisoryConfig::default(),
+    })
 }
 
 fn arb_safety() -> impl Strategy<Value = SafetyConfig> {
     (any::<bool>(),).prop_map(|(require_verbatim_approval,)| SafetyConfig {
+        evaluator: SafetyEvaluator::default(),
         dcg_bin: PathBuf::new(),
         dcg_packs: vec![],
         dcg_explain_format: String::new(),
         require_verbatim_approval,
+        native_packs: vec![],
+        rule_paths: vec![],
     })
 }
 
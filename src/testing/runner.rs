@@ -11,6 +11,7 @@ use super::definition::{Requirement, SkipCondition, TestDefinition};
 use super::steps::StepExecutor;
 use crate::app::AppContext;
 use crate::error::{MsError, Result};
+use crate::security::SafetyGate;
 
 /// Options for controlling test execution.
 #[derive(Debug, Clone, Default)]
@@ -102,13 +103,18 @@ impl SkillTestReport {
 pub struct SkillTestRunner<'a> {
     ctx: &'a AppContext,
     options: TestOptions,
+    safety: SafetyGate,
 }
 
 impl<'a> SkillTestRunner<'a> {
     /// Create a new test runner.
     #[must_use]
-    pub const fn new(ctx: &'a AppContext, options: TestOptions) -> Self {
-        Self { ctx, options }
+    pub fn new(ctx: &'a AppContext, options: TestOptions) -> Self {
+        Self {
+            ctx,
+            options,
+            safety: SafetyGate::from_context(ctx),
+        }
     }
 
     /// Run tests for all skills.
@@ -409,7 +415,8 @@ impl<'a> SkillTestRunner<'a> {
             eprintln!("[TEST] Running: {}", test.name);
         }
 
-        let mut executor = StepExecutor::new(self.ctx, self.options.verbose);
+        let mut executor =
+            StepExecutor::new(self.ctx, self.options.verbose).with_safety(self.safety.clone());
         let mut failures = Vec::new();
 
         // Run setup steps
@@ -40,8 +40,8 @@ impl<'a> StepExecutor<'a> {
 
     /// Enable safety gate for command execution.
     ///
-    /// When enabled, all `run` steps will be validated through DCG
-    /// before execution.
+    /// When enabled, all `run` steps will be validated through the safety
+    /// gate before execution.
     #[must_use]
     pub fn with_safety(mut self, gate: SafetyGate) -> Self {
         self.safety = Some(gate);
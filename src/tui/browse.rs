@@ -20,8 +20,12 @@ use ratatui::{
     widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
 };
 
+use crate::core::spec_lens::compile_markdown;
 use crate::error::{MsError, Result};
+use crate::search::{SearchFilters, SearchQuery, SkillFacets, SnippetExtractor};
 use crate::storage::sqlite::{Database, SkillRecord};
+use crate::tui::editor::{EditorStore, SkillEditor};
+use crate::utils::format::truncate_string;
 
 /// Focus state for TUI panels.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -39,42 +43,6 @@ impl FocusPanel {
     }
 }
 
-/// Filter configuration for skill list.
-#[derive(Debug, Default, Clone)]
-pub struct Filters {
-    /// Filter by layer (base, org, project, user)
-    pub layer: Option<String>,
-    /// Filter by tags
-    pub tags: Vec<String>,
-    /// Minimum quality score filter
-    pub min_quality: Option<f64>,
-}
-
-impl Filters {
-    /// Parse filter syntax from search query.
-    /// Supports: `layer:base`, `tag:rust`, `quality:>0.8`
-    fn parse_special_filters(query: &str) -> (String, Self) {
-        let mut filters = Self::default();
-        let mut text_parts = Vec::new();
-
-        for part in query.split_whitespace() {
-            if let Some(layer) = part.strip_prefix("layer:") {
-                filters.layer = Some(layer.to_string());
-            } else if let Some(tag) = part.strip_prefix("tag:") {
-                filters.tags.push(tag.to_string());
-            } else if let Some(quality) = part.strip_prefix("quality:>") {
-                if let Ok(q) = quality.parse::<f64>() {
-                    filters.min_quality = Some(q);
-                }
-            } else {
-                text_parts.push(part);
-            }
-        }
-
-        (text_parts.join(" "), filters)
-    }
-}
-
 /// Action to take after handling input.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Action {
@@ -82,6 +50,8 @@ pub enum Action {
     Quit,
     /// Load and output the selected skill
     Load(String),
+    /// Open the selected skill in the editor
+    Edit(String),
     /// Continue running the TUI
     Continue,
 }
@@ -96,6 +66,8 @@ pub struct SkillSummary {
     pub quality_score: f64,
     pub tags: Vec<String>,
     pub body: String,
+    /// Filterable attributes (author, platforms, requires, updated, ...)
+    pub facets: SkillFacets,
 }
 
 impl From<&SkillRecord> for SkillSummary {
@@ -119,6 +91,7 @@ impl From<&SkillRecord> for SkillSummary {
             quality_score: r.quality_score,
             tags,
             body: r.body.clone(),
+            facets: SkillFacets::from_record(r),
         }
     }
 }
@@ -133,10 +106,12 @@ pub struct BrowseTui {
     list_state: ListState,
     /// Current search query
     search_query: String,
+    /// Free-text part of the query (filters removed), used for highlighting
+    text_query: String,
     /// Whether search box is focused
     search_focused: bool,
-    /// Active filters
-    filters: Filters,
+    /// Active filters (parsed from the search query)
+    filters: SearchFilters,
     /// Currently focused panel
     focus: FocusPanel,
     /// Detail pane scroll offset
@@ -164,8 +139,9 @@ impl BrowseTui {
             filtered,
             list_state,
             search_query: String::new(),
+            text_query: String::new(),
             search_focused: false,
-            filters: Filters::default(),
+            filters: SearchFilters::default(),
             focus: FocusPanel::List,
             detail_scroll: 0,
             show_help: false,
@@ -187,8 +163,9 @@ impl BrowseTui {
             filtered,
             list_state,
             search_query: String::new(),
+            text_query: String::new(),
             search_focused: false,
-            filters: Filters::default(),
+            filters: SearchFilters::default(),
             focus: FocusPanel::List,
             detail_scroll: 0,
             show_help: false,
@@ -200,6 +177,7 @@ impl BrowseTui {
     pub fn run(
         mut self,
         terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+        store: &dyn EditorStore,
     ) -> Result<Option<String>> {
         loop {
             terminal.draw(|f| self.draw(f))?;
@@ -209,6 +187,7 @@ impl BrowseTui {
                     match self.handle_key(key.code, key.modifiers) {
                         Action::Quit => return Ok(None),
                         Action::Load(skill_id) => return Ok(Some(skill_id)),
+                        Action::Edit(skill_id) => self.edit_skill(terminal, store, &skill_id)?,
                         Action::Continue => {}
                     }
                 }
@@ -216,6 +195,35 @@ impl BrowseTui {
         }
     }
 
+    /// Run the skill editor in place, then refresh the edited summary.
+    fn edit_skill(
+        &mut self,
+        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+        store: &dyn EditorStore,
+        skill_id: &str,
+    ) -> Result<()> {
+        let spec = match store.load(skill_id) {
+            Ok(spec) => spec,
+            Err(err) => {
+                self.status_message = Some(format!("Cannot edit {skill_id}: {err}"));
+                return Ok(());
+            }
+        };
+        let editor = SkillEditor::new(spec, store.validator()?);
+        let Some(saved) = editor.run(terminal, store)? else {
+            return Ok(());
+        };
+
+        if let Some(skill) = self.skills.iter_mut().find(|skill| skill.id == skill_id) {
+            skill.name.clone_from(&saved.metadata.name);
+            skill.description.clone_from(&saved.metadata.description);
+            skill.tags.clone_from(&saved.metadata.tags);
+            skill.body = compile_markdown(&saved);
+        }
+        self.status_message = Some(format!("Saved {skill_id}"));
+        Ok(())
+    }
+
     fn draw(&mut self, f: &mut Frame) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -324,10 +332,10 @@ impl BrowseTui {
 
                 ListItem::new(Line::from(vec![
                     Span::styled(
-                        format!("[{}] ", truncate(&s.layer, 4)),
+                        format!("[{}] ", truncate_string(&s.layer, 4)),
                         Style::default().fg(layer_color),
                     ),
-                    Span::raw(truncate(&s.name, 30)),
+                    Span::raw(truncate_string(&s.name, 30)),
                 ]))
             })
             .collect();
@@ -380,7 +388,7 @@ impl BrowseTui {
         let help_text = if self.search_focused {
             "Enter: apply  Esc: cancel  Backspace: delete"
         } else {
-            "j/k: navigate  /: search  Enter/l: load  Tab: switch pane  f: favorite  ?: help  q: quit"
+            "j/k: navigate  /: search  Enter/l: load  e: edit  Tab: switch pane  f: favorite  ?: help  q: quit"
         };
 
         let paragraph = Paragraph::new(help_text).style(Style::default().fg(Color::DarkGray));
@@ -392,7 +400,7 @@ impl BrowseTui {
 
         // Center the help dialog
         let help_width = 60.min(area.width.saturating_sub(4));
-        let help_height = 20.min(area.height.saturating_sub(4));
+        let help_height = 28.min(area.height.saturating_sub(4));
         let x = (area.width - help_width) / 2;
         let y = (area.height - help_height) / 2;
         let help_area = Rect::new(x, y, help_width, help_height);
@@ -417,13 +425,17 @@ impl BrowseTui {
             Line::from("Actions:"),
             Line::from("  /            Focus search box"),
             Line::from("  Enter / l    Load selected skill"),
+            Line::from("  e            Edit selected skill"),
             Line::from("  f            Toggle favorite (not implemented)"),
             Line::from("  h            Toggle hidden (not implemented)"),
             Line::from(""),
             Line::from("Search Filters:"),
             Line::from("  layer:base   Filter by layer"),
-            Line::from("  tag:rust     Filter by tag"),
+            Line::from("  tag:rust     Filter by tag (-tag:x excludes)"),
+            Line::from("  author:alice Filter by author"),
+            Line::from("  platform:linux  requires:git"),
             Line::from("  quality:>0.8 Filter by quality score"),
+            Line::from("  updated:>2026-01-01  Filter by modification date"),
             Line::from(""),
             Line::from("Press ? or Esc to close this help"),
         ];
@@ -493,6 +505,35 @@ impl BrowseTui {
             lines.push(Line::from(""));
         }
 
+        if !self.text_query.is_empty() {
+            if let Some(matched) = SnippetExtractor::new(&self.text_query).best(&skill.body) {
+                let heading = matched.section_title.as_ref().map_or_else(
+                    || "Matched:".to_string(),
+                    |title| format!("Matched in \"{title}\":"),
+                );
+                lines.push(Line::from(Span::styled(
+                    heading,
+                    Style::default().add_modifier(Modifier::BOLD),
+                )));
+                let mut spans = vec![Span::raw("  ")];
+                for (text, highlighted) in matched.segments() {
+                    let text = text.replace('\n', " ");
+                    spans.push(if highlighted {
+                        Span::styled(
+                            text,
+                            Style::default()
+                                .fg(Color::Yellow)
+                                .add_modifier(Modifier::BOLD),
+                        )
+                    } else {
+                        Span::raw(text)
+                    });
+                }
+                lines.push(Line::from(spans));
+                lines.push(Line::from(""));
+            }
+        }
+
         // Add a preview of the body content
         lines.push(Line::from(Span::styled(
             "Content Preview:".to_string(),
@@ -549,7 +590,7 @@ impl BrowseTui {
                 // Clear search/filters
                 if !self.search_query.is_empty() {
                     self.search_query.clear();
-                    self.filters = Filters::default();
+                    self.filters = SearchFilters::default();
                     self.apply_filters();
                     self.status_message = Some("Filters cleared".to_string());
                 }
@@ -616,6 +657,9 @@ impl BrowseTui {
             KeyCode::Enter | KeyCode::Char('l') => {
                 return self.load_selected();
             }
+            KeyCode::Char('e') => {
+                return self.edit_selected();
+            }
             KeyCode::Char('f') => {
                 self.status_message = Some("Favorite toggle not yet implemented".to_string());
             }
@@ -644,6 +688,9 @@ impl BrowseTui {
             KeyCode::Enter | KeyCode::Char('l') => {
                 return self.load_selected();
             }
+            KeyCode::Char('e') => {
+                return self.edit_selected();
+            }
             _ => {}
         }
         Action::Continue
@@ -696,14 +743,30 @@ impl BrowseTui {
         Action::Continue
     }
 
-    fn apply_filters(&mut self) {
-        // Parse special filters from search query
-        let (text_query, parsed_filters) = Filters::parse_special_filters(&self.search_query);
+    fn edit_selected(&self) -> Action {
+        match self.load_selected() {
+            Action::Load(skill_id) => Action::Edit(skill_id),
+            other => other,
+        }
+    }
 
-        // Merge parsed filters with existing
-        self.filters = parsed_filters;
+    fn apply_filters(&mut self) {
+        // Same query syntax as `ms search`; browsing shows deprecated skills
+        // unless filtered otherwise.
+        let query = match SearchQuery::parse(&self.search_query) {
+            Ok(query) => query,
+            Err(err) => {
+                self.status_message = Some(err.to_string());
+                SearchQuery {
+                    text: self.search_query.clone(),
+                    ..SearchQuery::default()
+                }
+            }
+        };
+        self.filters = query.filters.clone().include_deprecated(true);
+        self.text_query = query.plain_text().trim().to_string();
 
-        let text_query_lower = text_query.to_lowercase();
+        let text_query_lower = self.text_query.to_lowercase();
 
         self.filtered = self
             .skills
@@ -720,35 +783,7 @@ impl BrowseTui {
                     }
                 }
 
-                // Layer filter
-                if let Some(ref layer) = self.filters.layer {
-                    if s.layer.to_lowercase() != layer.to_lowercase() {
-                        return false;
-                    }
-                }
-
-                // Tag filter
-                if !self.filters.tags.is_empty() {
-                    let skill_tags_lower: Vec<String> =
-                        s.tags.iter().map(|t| t.to_lowercase()).collect();
-                    let has_matching_tag = self
-                        .filters
-                        .tags
-                        .iter()
-                        .any(|t| skill_tags_lower.contains(&t.to_lowercase()));
-                    if !has_matching_tag {
-                        return false;
-                    }
-                }
-
-                // Quality filter
-                if let Some(min_quality) = self.filters.min_quality {
-                    if s.quality_score < min_quality {
-                        return false;
-                    }
-                }
-
-                true
+                self.filters.matches_facets(&s.facets)
             })
             .map(|(i, _)| i)
             .collect();
@@ -801,8 +836,8 @@ impl Drop for TerminalGuard {
     }
 }
 
-/// Run the browse TUI.
-pub fn run_browse_tui(db: &Database) -> Result<Option<String>> {
+/// Run the browse TUI, editing skills through `store`.
+pub fn run_browse_tui(db: &Database, store: &dyn EditorStore) -> Result<Option<String>> {
     // Check if stdout is a terminal
     if !io::stdout().is_terminal() {
         return Err(MsError::ValidationFailed(
@@ -814,7 +849,7 @@ pub fn run_browse_tui(db: &Database) -> Result<Option<String>> {
     let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
 
     let app = BrowseTui::new(db)?;
-    app.run(&mut terminal)
+    app.run(&mut terminal, store)
 }
 
 fn normalize_layer(input: &str) -> String {
@@ -827,14 +862,6 @@ fn normalize_layer(input: &str) -> String {
     .to_string()
 }
 
-fn truncate(s: &str, max_len: usize) -> String {
-    if s.chars().count() > max_len {
-        format!("{}...", s.chars().take(max_len - 3).collect::<String>())
-    } else {
-        s.to_string()
-    }
-}
-
 fn quality_color(quality: f64) -> Color {
     if quality >= 0.8 {
         Color::Green
@@ -857,14 +884,21 @@ mod tests {
         quality: f64,
         tags: Vec<&str>,
     ) -> SkillSummary {
+        let tags: Vec<String> = tags.into_iter().map(String::from).collect();
         SkillSummary {
             id: id.to_string(),
             name: name.to_string(),
             description: format!("Description for {}", name),
             layer: layer.to_string(),
             quality_score: quality,
-            tags: tags.into_iter().map(String::from).collect(),
+            tags: tags.clone(),
             body: format!("# {}\n\nBody content for {}.", name, name),
+            facets: SkillFacets {
+                tags,
+                layer: layer.to_string(),
+                quality: quality as f32,
+                ..SkillFacets::default()
+            },
         }
     }
 
@@ -962,6 +996,33 @@ mod tests {
         assert_eq!(app.filtered_count(), 1);
     }
 
+    #[test]
+    fn test_detail_shows_matched_section() {
+        let mut skill = make_test_skill("rust-errors", "Rust Errors", "base", 0.9, vec![]);
+        skill.body =
+            "# Rust Errors\n\n## Propagation\n\nUse `?` to propagate errors.\n".to_string();
+        let mut app = BrowseTui::with_test_skills(vec![skill]);
+
+        app.set_search_query("errors");
+        let detail = app.get_selected_detail();
+        let matched = detail
+            .lines
+            .iter()
+            .position(|line| {
+                line.spans
+                    .iter()
+                    .any(|s| s.content.contains("Matched in \"Propagation\""))
+            })
+            .expect("matched section heading");
+        let snippet = &detail.lines[matched + 1];
+        assert!(
+            snippet
+                .spans
+                .iter()
+                .any(|s| s.content == "errors" && s.style.fg == Some(Color::Yellow))
+        );
+    }
+
     #[test]
     fn test_navigation_wraps() {
         let skills = vec![
@@ -992,8 +1053,8 @@ mod tests {
 
     #[test]
     fn test_truncate() {
-        assert_eq!(truncate("hello", 10), "hello");
-        assert_eq!(truncate("hello world", 8), "hello...");
+        assert_eq!(truncate_string("hello", 10), "hello");
+        assert_eq!(truncate_string("hello world", 8), "hello...");
     }
 
     #[test]
@@ -1013,13 +1074,32 @@ mod tests {
 
     #[test]
     fn test_parse_special_filters_extracts_filters() {
-        let (query, filters) =
-            Filters::parse_special_filters("layer:base tag:rust tag:cli quality:>0.8 remaining");
+        let mut app = BrowseTui::with_test_skills(Vec::new());
+        app.set_search_query("layer:base tag:rust tag:cli quality:>0.8 remaining");
 
-        assert_eq!(query, "remaining");
-        assert_eq!(filters.layer.as_deref(), Some("base"));
-        assert_eq!(filters.tags, vec!["rust", "cli"]);
-        assert_eq!(filters.min_quality, Some(0.8));
+        assert_eq!(app.text_query, "remaining");
+        let values: Vec<&str> = app
+            .filters
+            .facets
+            .iter()
+            .map(|c| c.value.as_str())
+            .collect();
+        assert_eq!(values, vec!["base", "rust", "cli"]);
+        assert_eq!(app.filters.min_quality, Some(0.8));
+    }
+
+    #[test]
+    fn test_filter_by_excluded_tag_and_author() {
+        let mut alice = make_test_skill("s1", "Skill 1", "base", 0.9, vec!["rust"]);
+        alice.facets.author = Some("alice".to_string());
+        let legacy = make_test_skill("s2", "Skill 2", "base", 0.9, vec!["rust", "legacy"]);
+        let mut app = BrowseTui::with_test_skills(vec![alice, legacy]);
+
+        app.set_search_query("tag:rust -tag:legacy");
+        assert_eq!(app.filtered_count(), 1);
+
+        app.set_search_query("author:alice");
+        assert_eq!(app.filtered_count(), 1);
     }
 
     #[test]
@@ -1091,7 +1171,7 @@ mod tests {
         app.handle_key(KeyCode::Esc, KeyModifiers::empty());
 
         assert!(app.search_query.is_empty());
-        assert_eq!(app.filters.layer, None);
+        assert!(app.filters.facets.is_empty());
         assert_eq!(app.status_message.as_deref(), Some("Filters cleared"));
     }
 
@@ -1146,4 +1226,23 @@ mod tests {
         let mut app = BrowseTui::with_test_skills(Vec::new());
         assert_eq!(app.load_selected(), Action::Continue);
     }
+
+    #[test]
+    fn test_edit_key_opens_selected_skill() {
+        let skills = vec![
+            make_test_skill("s1", "Skill 1", "base", 0.9, vec![]),
+            make_test_skill("s2", "Skill 2", "base", 0.85, vec![]),
+        ];
+        let mut app = BrowseTui::with_test_skills(skills);
+
+        app.handle_list_key(KeyCode::Char('j'));
+        assert_eq!(
+            app.handle_list_key(KeyCode::Char('e')),
+            Action::Edit("s2".to_string())
+        );
+        assert_eq!(
+            app.handle_detail_key(KeyCode::Char('e')),
+            Action::Edit("s2".to_string())
+        );
+    }
 }
@@ -0,0 +1,1593 @@
+//! Interactive skill editor using ratatui.
+//!
+//! Edits a skill's structured spec directly instead of the YAML round-trip
+//! behind `ms edit`: a section/block tree with inline block editing, block
+//! type changes and reordering (keyboard or mouse drag), live lint
+//! diagnostics, a rendered preview at each disclosure level, and a diff
+//! against the saved version that must be reviewed before committing.
+
+use std::collections::HashMap;
+use std::io::{self, IsTerminal, Stdout};
+use std::time::Duration;
+
+use crossterm::{
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers, MouseButton,
+        MouseEvent, MouseEventKind,
+    },
+    execute,
+    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+};
+use ratatui::{
+    Frame, Terminal,
+    backend::CrosstermBackend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+use crate::core::disclosure::{DisclosureLevel, disclose_level};
+use crate::core::skill::SkillAssets;
+use crate::core::spec_lens::{compile_markdown, slugify};
+use crate::core::{BlockType, SkillBlock, SkillSection, SkillSpec};
+use crate::error::{MsError, Result};
+use crate::lint::{Diagnostic, Severity, ValidationEngine};
+use crate::utils::format::truncate_string;
+
+/// Disclosure levels the preview cycles through.
+const PREVIEW_LEVELS: [DisclosureLevel; 5] = [
+    DisclosureLevel::Minimal,
+    DisclosureLevel::Overview,
+    DisclosureLevel::Standard,
+    DisclosureLevel::Full,
+    DisclosureLevel::Complete,
+];
+
+/// Block types in the order `t` cycles through them.
+const BLOCK_TYPES: [BlockType; 6] = [
+    BlockType::Text,
+    BlockType::Code,
+    BlockType::Rule,
+    BlockType::Pitfall,
+    BlockType::Command,
+    BlockType::Checklist,
+];
+
+/// Lines of unchanged context shown around each change in the diff.
+const DIFF_CONTEXT: usize = 2;
+
+/// Where the editor loads skills from and commits them to.
+pub trait EditorStore {
+    /// Load the saved spec for a skill.
+    fn load(&self, skill_id: &str) -> Result<SkillSpec>;
+    /// Build the engine used for live diagnostics.
+    fn validator(&self) -> Result<ValidationEngine>;
+    /// Commit an edited spec.
+    fn commit(&self, spec: &SkillSpec) -> Result<()>;
+}
+
+/// A row in the section/block tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TreeNode {
+    Section(usize),
+    Block(usize, usize),
+}
+
+/// Input mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    /// Navigating and restructuring the tree
+    Tree,
+    /// Editing the selected block or section title inline
+    Insert,
+    /// Reviewing the diff before committing
+    Review,
+}
+
+/// Action to take after handling input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditorAction {
+    /// Exit the editor
+    Quit,
+    /// Commit the working spec
+    Commit,
+    /// Continue running the editor
+    Continue,
+}
+
+/// One line of a line diff.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    Same(String),
+    Removed(String),
+    Added(String),
+}
+
+/// Rendered preview at one disclosure level.
+#[derive(Debug, Clone)]
+struct Preview {
+    level: DisclosureLevel,
+    text: String,
+    tokens: usize,
+}
+
+/// Multi-line text buffer for inline editing.
+#[derive(Debug, Clone, Default)]
+struct EditBuffer {
+    lines: Vec<String>,
+    row: usize,
+    /// Cursor column in characters
+    col: usize,
+    /// Text before editing started, restored on discard
+    original: String,
+}
+
+impl EditBuffer {
+    fn new(text: &str) -> Self {
+        let lines: Vec<String> = text.split('\n').map(String::from).collect();
+        let row = lines.len() - 1;
+        let col = lines[row].chars().count();
+        Self {
+            lines,
+            row,
+            col,
+            original: text.to_string(),
+        }
+    }
+
+    fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    fn line_len(&self) -> usize {
+        self.lines[self.row].chars().count()
+    }
+
+    fn byte_col(&self) -> usize {
+        let line = &self.lines[self.row];
+        line.char_indices()
+            .nth(self.col)
+            .map_or(line.len(), |(idx, _)| idx)
+    }
+
+    fn insert(&mut self, c: char) {
+        let idx = self.byte_col();
+        self.lines[self.row].insert(idx, c);
+        self.col += 1;
+    }
+
+    fn newline(&mut self) {
+        let idx = self.byte_col();
+        let rest = self.lines[self.row].split_off(idx);
+        self.lines.insert(self.row + 1, rest);
+        self.row += 1;
+        self.col = 0;
+    }
+
+    fn backspace(&mut self) {
+        if self.col > 0 {
+            self.col -= 1;
+            let idx = self.byte_col();
+            self.lines[self.row].remove(idx);
+        } else if self.row > 0 {
+            let line = self.lines.remove(self.row);
+            self.row -= 1;
+            self.col = self.line_len();
+            self.lines[self.row].push_str(&line);
+        }
+    }
+
+    fn delete(&mut self) {
+        if self.col < self.line_len() {
+            let idx = self.byte_col();
+            self.lines[self.row].remove(idx);
+        } else if self.row + 1 < self.lines.len() {
+            let next = self.lines.remove(self.row + 1);
+            self.lines[self.row].push_str(&next);
+        }
+    }
+
+    fn left(&mut self) {
+        if self.col > 0 {
+            self.col -= 1;
+        } else if self.row > 0 {
+            self.row -= 1;
+            self.col = self.line_len();
+        }
+    }
+
+    fn right(&mut self) {
+        if self.col < self.line_len() {
+            self.col += 1;
+        } else if self.row + 1 < self.lines.len() {
+            self.row += 1;
+            self.col = 0;
+        }
+    }
+
+    fn up(&mut self) {
+        if self.row > 0 {
+            self.row -= 1;
+            self.col = self.col.min(self.line_len());
+        }
+    }
+
+    fn down(&mut self) {
+        if self.row + 1 < self.lines.len() {
+            self.row += 1;
+            self.col = self.col.min(self.line_len());
+        }
+    }
+}
+
+/// TUI skill editor state.
+pub struct SkillEditor {
+    /// Last committed (or loaded) version
+    saved: SkillSpec,
+    /// Version being edited
+    working: SkillSpec,
+    /// Engine for live diagnostics
+    validator: ValidationEngine,
+    /// Diagnostics for the working spec, most severe first
+    diagnostics: Vec<Diagnostic>,
+    /// Previews of the working spec at each level in `PREVIEW_LEVELS`
+    previews: Vec<Preview>,
+    /// Line diff of the saved SKILL.md against the working one
+    diff: Vec<DiffLine>,
+    /// Block-level changes the markdown diff cannot show (types, moves)
+    block_changes: Vec<String>,
+    /// Whether the working spec differs from the saved one
+    dirty: bool,
+    /// Tree selection state
+    tree_state: ListState,
+    /// Current input mode
+    mode: Mode,
+    /// Node being edited in insert mode
+    editing: Option<TreeNode>,
+    /// Inline edit buffer
+    buffer: EditBuffer,
+    /// Index into `PREVIEW_LEVELS`
+    preview_level: usize,
+    /// Preview pane scroll offset
+    preview_scroll: u16,
+    /// Diff overlay scroll offset
+    diff_scroll: u16,
+    /// Whether to show help overlay
+    show_help: bool,
+    /// Whether `q` was pressed once with unsaved changes
+    confirm_quit: bool,
+    /// Whether a mouse drag is reordering the selected node
+    dragging: bool,
+    /// Tree panel area from the last draw, for mouse hit-testing
+    tree_area: Rect,
+    /// Status message to display
+    status_message: Option<String>,
+}
+
+impl SkillEditor {
+    /// Create an editor for a saved spec.
+    pub fn new(spec: SkillSpec, validator: ValidationEngine) -> Self {
+        let mut tree_state = ListState::default();
+        tree_state.select(Some(0));
+
+        let mut editor = Self {
+            saved: spec.clone(),
+            working: spec,
+            validator,
+            diagnostics: Vec::new(),
+            previews: Vec::new(),
+            diff: Vec::new(),
+            block_changes: Vec::new(),
+            dirty: false,
+            tree_state,
+            mode: Mode::Tree,
+            editing: None,
+            buffer: EditBuffer::default(),
+            preview_level: 2,
+            preview_scroll: 0,
+            diff_scroll: 0,
+            show_help: false,
+            confirm_quit: false,
+            dragging: false,
+            tree_area: Rect::default(),
+            status_message: None,
+        };
+        editor.refresh();
+        editor
+    }
+
+    /// The spec being edited.
+    #[must_use]
+    pub const fn working(&self) -> &SkillSpec {
+        &self.working
+    }
+
+    /// Whether there are uncommitted changes.
+    #[must_use]
+    pub const fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Live diagnostics for the working spec.
+    #[must_use]
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Run the editor main loop.
+    ///
+    /// Returns the last committed spec, or `None` if nothing was committed.
+    pub fn run(
+        mut self,
+        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+        store: &dyn EditorStore,
+    ) -> Result<Option<SkillSpec>> {
+        let mut committed = None;
+        loop {
+            terminal.draw(|f| self.draw(f))?;
+
+            if event::poll(Duration::from_millis(100))? {
+                let action = match event::read()? {
+                    Event::Key(key) => self.handle_key(key.code, key.modifiers),
+                    Event::Mouse(mouse) => {
+                        self.handle_mouse(mouse);
+                        EditorAction::Continue
+                    }
+                    _ => EditorAction::Continue,
+                };
+                match action {
+                    EditorAction::Quit => return Ok(committed),
+                    EditorAction::Commit => match store.commit(&self.working) {
+                        Ok(()) => {
+                            self.mark_saved();
+                            committed = Some(self.saved.clone());
+                        }
+                        Err(err) => {
+                            self.mode = Mode::Tree;
+                            self.status_message = Some(format!("Commit failed: {err}"));
+                        }
+                    },
+                    EditorAction::Continue => {}
+                }
+            }
+        }
+    }
+
+    /// Record the working spec as committed.
+    fn mark_saved(&mut self) {
+        self.saved = self.working.clone();
+        self.mode = Mode::Tree;
+        self.refresh();
+        self.status_message = Some(format!("Committed {}", self.saved.metadata.id));
+    }
+
+    /// Recompute diagnostics, previews and the diff after a change.
+    fn refresh(&mut self) {
+        let mut diagnostics = self.validator.validate(&self.working).diagnostics;
+        diagnostics.sort_by_key(|diagnostic| std::cmp::Reverse(diagnostic.severity));
+        self.diagnostics = diagnostics;
+
+        self.previews = PREVIEW_LEVELS
+            .iter()
+            .map(|level| render_preview(&self.working, *level))
+            .collect();
+        self.diff = line_diff(
+            &compile_markdown(&self.saved),
+            &compile_markdown(&self.working),
+        );
+        self.block_changes = block_changes(&self.saved, &self.working);
+        self.dirty =
+            serde_json::to_value(&self.saved).ok() != serde_json::to_value(&self.working).ok();
+        self.confirm_quit = false;
+    }
+
+    // =========================================================================
+    // Tree
+    // =========================================================================
+
+    fn rows(&self) -> Vec<TreeNode> {
+        let mut rows = Vec::new();
+        for (s, section) in self.working.sections.iter().enumerate() {
+            rows.push(TreeNode::Section(s));
+            rows.extend((0..section.blocks.len()).map(|b| TreeNode::Block(s, b)));
+        }
+        rows
+    }
+
+    fn selected_node(&self) -> Option<TreeNode> {
+        self.rows().get(self.tree_state.selected()?).copied()
+    }
+
+    fn select_node(&mut self, node: TreeNode) {
+        if let Some(idx) = self.rows().iter().position(|row| *row == node) {
+            self.tree_state.select(Some(idx));
+        }
+    }
+
+    fn clamp_selection(&mut self) {
+        let len = self.rows().len();
+        let selected = self.tree_state.selected().unwrap_or(0);
+        self.tree_state
+            .select((len > 0).then(|| selected.min(len - 1)));
+    }
+
+    fn select_next(&mut self) {
+        let len = self.rows().len();
+        if len == 0 {
+            return;
+        }
+        let next = self
+            .tree_state
+            .selected()
+            .map_or(0, |i| (i + 1).min(len - 1));
+        self.tree_state.select(Some(next));
+    }
+
+    fn select_prev(&mut self) {
+        let prev = self
+            .tree_state
+            .selected()
+            .map_or(0, |i| i.saturating_sub(1));
+        self.tree_state.select(Some(prev));
+    }
+
+    /// Move the selected block or section one step, carrying blocks across
+    /// section boundaries. Returns whether anything moved.
+    fn move_selected(&mut self, down: bool) -> bool {
+        let selected = self.selected_node();
+        let sections = &mut self.working.sections;
+        let moved = match selected {
+            Some(TreeNode::Block(s, b)) => {
+                let len = sections[s].blocks.len();
+                if down && b + 1 < len {
+                    sections[s].blocks.swap(b, b + 1);
+                    Some(TreeNode::Block(s, b + 1))
+                } else if down && s + 1 < sections.len() {
+                    let block = sections[s].blocks.remove(b);
+                    sections[s + 1].blocks.insert(0, block);
+                    Some(TreeNode::Block(s + 1, 0))
+                } else if !down && b > 0 {
+                    sections[s].blocks.swap(b, b - 1);
+                    Some(TreeNode::Block(s, b - 1))
+                } else if !down && s > 0 {
+                    let block = sections[s].blocks.remove(b);
+                    sections[s - 1].blocks.push(block);
+                    Some(TreeNode::Block(s - 1, sections[s - 1].blocks.len() - 1))
+                } else {
+                    None
+                }
+            }
+            Some(TreeNode::Section(s)) => {
+                if down && s + 1 < sections.len() {
+                    sections.swap(s, s + 1);
+                    Some(TreeNode::Section(s + 1))
+                } else if !down && s > 0 {
+                    sections.swap(s, s - 1);
+                    Some(TreeNode::Section(s - 1))
+                } else {
+                    None
+                }
+            }
+            None => None,
+        };
+
+        match moved {
+            Some(node) => {
+                self.select_node(node);
+                self.refresh();
+                true
+            }
+            None => {
+                self.status_message = Some(
+                    if down {
+                        "Already at the bottom"
+                    } else {
+                        "Already at the top"
+                    }
+                    .to_string(),
+                );
+                false
+            }
+        }
+    }
+
+    fn cycle_block_type(&mut self) {
+        let Some(TreeNode::Block(s, b)) = self.selected_node() else {
+            self.status_message = Some("Select a block to change its type".to_string());
+            return;
+        };
+        let block = &mut self.working.sections[s].blocks[b];
+        let idx = BLOCK_TYPES
+            .iter()
+            .position(|block_type| *block_type == block.block_type)
+            .unwrap_or(0);
+        block.block_type = BLOCK_TYPES[(idx + 1) % BLOCK_TYPES.len()].clone();
+        self.status_message = Some(format!(
+            "{} is now {}",
+            block.id,
+            block_type_name(&block.block_type)
+        ));
+        self.refresh();
+    }
+
+    fn add_block(&mut self) {
+        let Some(s) = (match self.selected_node() {
+            Some(TreeNode::Section(s) | TreeNode::Block(s, _)) => Some(s),
+            None => None,
+        }) else {
+            self.status_message = Some("Add a section first (A)".to_string());
+            return;
+        };
+        let at = match self.selected_node() {
+            Some(TreeNode::Block(_, b)) => b + 1,
+            _ => self.working.sections[s].blocks.len(),
+        };
+        let section = &mut self.working.sections[s];
+        let id = next_block_id(section);
+        section.blocks.insert(
+            at,
+            SkillBlock {
+                id,
+                block_type: BlockType::Text,
+                content: String::new(),
+                suppressions: Vec::new(),
+            },
+        );
+        self.select_node(TreeNode::Block(s, at));
+        self.refresh();
+        self.begin_edit();
+    }
+
+    fn add_section(&mut self) {
+        let at = match self.selected_node() {
+            Some(TreeNode::Section(s) | TreeNode::Block(s, _)) => s + 1,
+            None => self.working.sections.len(),
+        };
+        let title = "New section".to_string();
+        let mut id = slugify(&title);
+        let mut n = 1;
+        while self.working.sections.iter().any(|section| section.id == id) {
+            n += 1;
+            id = format!("{}-{n}", slugify(&title));
+        }
+        self.working.sections.insert(
+            at,
+            SkillSection {
+                id,
+                title,
+                blocks: Vec::new(),
+            },
+        );
+        self.select_node(TreeNode::Section(at));
+        self.refresh();
+        self.begin_edit();
+    }
+
+    fn delete_selected(&mut self) {
+        match self.selected_node() {
+            Some(TreeNode::Block(s, b)) => {
+                let block = self.working.sections[s].blocks.remove(b);
+                self.status_message = Some(format!("Deleted {}", block.id));
+            }
+            Some(TreeNode::Section(s)) => {
+                let section = self.working.sections.remove(s);
+                self.status_message = Some(format!(
+                    "Deleted section \"{}\" ({} blocks)",
+                    section.title,
+                    section.blocks.len()
+                ));
+            }
+            None => return,
+        }
+        self.clamp_selection();
+        self.refresh();
+    }
+
+    // =========================================================================
+    // Inline editing
+    // =========================================================================
+
+    fn begin_edit(&mut self) {
+        let Some(node) = self.selected_node() else {
+            return;
+        };
+        let text = match node {
+            TreeNode::Section(s) => self.working.sections[s].title.clone(),
+            TreeNode::Block(s, b) => self.working.sections[s].blocks[b].content.clone(),
+        };
+        self.buffer = EditBuffer::new(&text);
+        self.editing = Some(node);
+        self.mode = Mode::Insert;
+    }
+
+    /// Write the buffer into the working spec so lint, preview and diff
+    /// follow every keystroke.
+    fn apply_buffer(&mut self) {
+        let text = self.buffer.text();
+        match self.editing {
+            Some(TreeNode::Section(s)) => self.working.sections[s].title = text,
+            Some(TreeNode::Block(s, b)) => self.working.sections[s].blocks[b].content = text,
+            None => return,
+        }
+        self.refresh();
+    }
+
+    fn finish_edit(&mut self, discard: bool) {
+        if discard {
+            self.buffer = EditBuffer::new(&self.buffer.original);
+            self.apply_buffer();
+            self.status_message = Some("Edit discarded".to_string());
+        }
+        self.editing = None;
+        self.mode = Mode::Tree;
+    }
+
+    // =========================================================================
+    // Input
+    // =========================================================================
+
+    fn handle_key(&mut self, key: KeyCode, modifiers: KeyModifiers) -> EditorAction {
+        if self.show_help {
+            if matches!(key, KeyCode::Char('?') | KeyCode::Esc | KeyCode::Enter) {
+                self.show_help = false;
+            }
+            return EditorAction::Continue;
+        }
+
+        match self.mode {
+            Mode::Insert => self.handle_insert_key(key, modifiers),
+            Mode::Review => self.handle_review_key(key),
+            Mode::Tree => self.handle_tree_key(key, modifiers),
+        }
+    }
+
+    fn handle_tree_key(&mut self, key: KeyCode, modifiers: KeyModifiers) -> EditorAction {
+        let shift = modifiers.contains(KeyModifiers::SHIFT);
+        match key {
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => {
+                return EditorAction::Quit;
+            }
+            KeyCode::Char('q') => {
+                if self.dirty && !self.confirm_quit {
+                    self.confirm_quit = true;
+                    self.status_message =
+                        Some("Unsaved changes: press q again to discard them".to_string());
+                    return EditorAction::Continue;
+                }
+                return EditorAction::Quit;
+            }
+            KeyCode::Char('?') => self.show_help = true,
+            KeyCode::Char('J') => {
+                self.move_selected(true);
+            }
+            KeyCode::Down if shift => {
+                self.move_selected(true);
+            }
+            KeyCode::Char('K') => {
+                self.move_selected(false);
+            }
+            KeyCode::Up if shift => {
+                self.move_selected(false);
+            }
+            KeyCode::Down | KeyCode::Char('j') => self.select_next(),
+            KeyCode::Up | KeyCode::Char('k') => self.select_prev(),
+            KeyCode::Enter | KeyCode::Char('e') => self.begin_edit(),
+            KeyCode::Char('t') => self.cycle_block_type(),
+            KeyCode::Char('a') => self.add_block(),
+            KeyCode::Char('A') => self.add_section(),
+            KeyCode::Char('x') | KeyCode::Delete => self.delete_selected(),
+            KeyCode::Char('p') => {
+                self.preview_level = (self.preview_level + 1) % PREVIEW_LEVELS.len();
+                self.preview_scroll = 0;
+            }
+            KeyCode::Char('P') => {
+                self.preview_level =
+                    (self.preview_level + PREVIEW_LEVELS.len() - 1) % PREVIEW_LEVELS.len();
+                self.preview_scroll = 0;
+            }
+            KeyCode::PageDown => self.preview_scroll = self.preview_scroll.saturating_add(5),
+            KeyCode::PageUp => self.preview_scroll = self.preview_scroll.saturating_sub(5),
+            KeyCode::Char('s' | 'd') => {
+                if self.dirty {
+                    self.mode = Mode::Review;
+                    self.diff_scroll = 0;
+                } else {
+                    self.status_message = Some("No changes to commit".to_string());
+                }
+            }
+            _ => {}
+        }
+        EditorAction::Continue
+    }
+
+    fn handle_insert_key(&mut self, key: KeyCode, modifiers: KeyModifiers) -> EditorAction {
+        let single_line = matches!(self.editing, Some(TreeNode::Section(_)));
+        match key {
+            KeyCode::Esc => self.finish_edit(false),
+            KeyCode::Char('x') if modifiers.contains(KeyModifiers::CONTROL) => {
+                self.finish_edit(true);
+            }
+            KeyCode::Enter if single_line => self.finish_edit(false),
+            KeyCode::Enter => {
+                self.buffer.newline();
+                self.apply_buffer();
+            }
+            KeyCode::Tab if !single_line => {
+                for _ in 0..4 {
+                    self.buffer.insert(' ');
+                }
+                self.apply_buffer();
+            }
+            KeyCode::Char(c) => {
+                self.buffer.insert(c);
+                self.apply_buffer();
+            }
+            KeyCode::Backspace => {
+                self.buffer.backspace();
+                self.apply_buffer();
+            }
+            KeyCode::Delete => {
+                self.buffer.delete();
+                self.apply_buffer();
+            }
+            KeyCode::Left => self.buffer.left(),
+            KeyCode::Right => self.buffer.right(),
+            KeyCode::Up => self.buffer.up(),
+            KeyCode::Down => self.buffer.down(),
+            KeyCode::Home => self.buffer.col = 0,
+            KeyCode::End => self.buffer.col = self.buffer.line_len(),
+            _ => {}
+        }
+        EditorAction::Continue
+    }
+
+    fn handle_review_key(&mut self, key: KeyCode) -> EditorAction {
+        match key {
+            KeyCode::Char('y') => return EditorAction::Commit,
+            KeyCode::Esc | KeyCode::Char('n' | 'q') => self.mode = Mode::Tree,
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.diff_scroll = self.diff_scroll.saturating_add(1);
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.diff_scroll = self.diff_scroll.saturating_sub(1);
+            }
+            KeyCode::PageDown => self.diff_scroll = self.diff_scroll.saturating_add(10),
+            KeyCode::PageUp => self.diff_scroll = self.diff_scroll.saturating_sub(10),
+            _ => {}
+        }
+        EditorAction::Continue
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        if self.mode != Mode::Tree || self.show_help {
+            return;
+        }
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if let Some(row) = self.tree_row_at(mouse.column, mouse.row) {
+                    self.tree_state.select(Some(row));
+                    self.dragging = true;
+                }
+            }
+            MouseEventKind::Drag(MouseButton::Left) if self.dragging => {
+                // One step per drag event towards the row under the pointer
+                if let (Some(target), Some(current)) = (
+                    self.tree_row_at(mouse.column, mouse.row),
+                    self.tree_state.selected(),
+                ) {
+                    if target != current {
+                        self.move_selected(target > current);
+                    }
+                }
+            }
+            MouseEventKind::Up(MouseButton::Left) => self.dragging = false,
+            MouseEventKind::ScrollDown => self.select_next(),
+            MouseEventKind::ScrollUp => self.select_prev(),
+            _ => {}
+        }
+    }
+
+    fn tree_row_at(&self, column: u16, row: u16) -> Option<usize> {
+        let area = self.tree_area;
+        let inside_x = column > area.x && column + 1 < area.x + area.width;
+        let inside_y = row > area.y && row + 1 < area.y + area.height;
+        if !inside_x || !inside_y {
+            return None;
+        }
+        let idx = usize::from(row - area.y - 1) + self.tree_state.offset();
+        (idx < self.rows().len()).then_some(idx)
+    }
+
+    // =========================================================================
+    // Drawing
+    // =========================================================================
+
+    fn draw(&mut self, f: &mut Frame) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1), // Title bar
+                Constraint::Min(10),   // Main content
+                Constraint::Length(1), // Help bar
+            ])
+            .split(f.area());
+
+        self.draw_title_bar(f, chunks[0]);
+
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+            .split(chunks[1]);
+        self.draw_tree(f, columns[0]);
+
+        let right = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+            .split(columns[1]);
+        self.draw_block_panel(f, right[0]);
+
+        let bottom = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(right[1]);
+        self.draw_preview(f, bottom[0]);
+        self.draw_diagnostics(f, bottom[1]);
+
+        self.draw_help_bar(f, chunks[2]);
+
+        if self.mode == Mode::Review {
+            self.draw_review_overlay(f);
+        }
+        if self.show_help {
+            draw_help_overlay(f);
+        }
+    }
+
+    fn draw_title_bar(&self, f: &mut Frame, area: Rect) {
+        let errors = self
+            .diagnostics
+            .iter()
+            .filter(|d| d.severity == Severity::Error)
+            .count();
+        let warnings = self
+            .diagnostics
+            .iter()
+            .filter(|d| d.severity == Severity::Warning)
+            .count();
+        let status = self
+            .status_message
+            .as_ref()
+            .map(|m| format!(" | {m}"))
+            .unwrap_or_default();
+
+        let title = Line::from(vec![
+            Span::styled("ms edit", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(format!(" | {}", self.working.metadata.id)),
+            if self.dirty {
+                Span::styled(" | modified", Style::default().fg(Color::Yellow))
+            } else {
+                Span::raw(" | saved")
+            },
+            Span::raw(format!(" | {errors} errors, {warnings} warnings{status}")),
+        ]);
+
+        let paragraph = Paragraph::new(title).style(Style::default().fg(Color::Cyan));
+        f.render_widget(paragraph, area);
+    }
+
+    fn draw_tree(&mut self, f: &mut Frame, area: Rect) {
+        self.tree_area = area;
+        let items: Vec<ListItem> = self
+            .rows()
+            .into_iter()
+            .map(|node| match node {
+                TreeNode::Section(s) => {
+                    let section = &self.working.sections[s];
+                    ListItem::new(Line::from(Span::styled(
+                        format!("▾ {}", section.title),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    )))
+                }
+                TreeNode::Block(s, b) => {
+                    let block = &self.working.sections[s].blocks[b];
+                    let first = block.content.lines().next().unwrap_or("(empty)");
+                    let width = usize::from(area.width.saturating_sub(16)).max(8);
+                    ListItem::new(Line::from(vec![
+                        Span::raw("  "),
+                        Span::styled(
+                            format!("[{}]", block_type_name(&block.block_type)),
+                            Style::default().fg(block_type_color(&block.block_type)),
+                        ),
+                        Span::raw(format!(" {}", truncate_string(first, width))),
+                    ]))
+                }
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(if self.dragging {
+                        Style::default().fg(Color::Yellow)
+                    } else {
+                        Style::default().fg(Color::Cyan)
+                    })
+                    .title(" Sections "),
+            )
+            .highlight_style(
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol("> ");
+
+        f.render_stateful_widget(list, area, &mut self.tree_state);
+    }
+
+    fn draw_block_panel(&self, f: &mut Frame, area: Rect) {
+        if self.mode == Mode::Insert {
+            let height = usize::from(area.height.saturating_sub(2)).max(1);
+            let scroll = self.buffer.row.saturating_sub(height - 1);
+            let text: Vec<Line> = self
+                .buffer
+                .lines
+                .iter()
+                .map(|line| Line::from(line.clone()))
+                .collect();
+            let paragraph = Paragraph::new(text)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(Color::Yellow))
+                        .title(" Editing [Esc: done  Ctrl-X: discard] "),
+                )
+                .scroll((u16::try_from(scroll).unwrap_or(u16::MAX), 0));
+            f.render_widget(paragraph, area);
+
+            let x = area.x + 1 + u16::try_from(self.buffer.col).unwrap_or(u16::MAX);
+            let y = area.y + 1 + u16::try_from(self.buffer.row - scroll).unwrap_or(u16::MAX);
+            f.set_cursor_position((
+                x.min(area.x + area.width.saturating_sub(2)),
+                y.min(area.y + area.height.saturating_sub(2)),
+            ));
+            return;
+        }
+
+        let (title, text) = match self.selected_node() {
+            Some(TreeNode::Block(s, b)) => {
+                let block = &self.working.sections[s].blocks[b];
+                (
+                    format!(" {} ({}) ", block.id, block_type_name(&block.block_type)),
+                    Text::from(block.content.clone()),
+                )
+            }
+            Some(TreeNode::Section(s)) => {
+                let section = &self.working.sections[s];
+                (
+                    format!(" Section {} ", section.id),
+                    Text::from(vec![
+                        Line::from(Span::styled(
+                            section.title.clone(),
+                            Style::default().add_modifier(Modifier::BOLD),
+                        )),
+                        Line::from(format!("{} blocks", section.blocks.len())),
+                    ]),
+                )
+            }
+            None => (
+                " Block ".to_string(),
+                Text::from("No sections yet: press A to add one"),
+            ),
+        };
+
+        let paragraph = Paragraph::new(text)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .wrap(Wrap { trim: false });
+        f.render_widget(paragraph, area);
+    }
+
+    fn draw_preview(&self, f: &mut Frame, area: Rect) {
+        let preview = &self.previews[self.preview_level];
+        let counts = self
+            .previews
+            .iter()
+            .map(|p| {
+                let label = format!("{} {}", p.level.name(), p.tokens);
+                if p.level == preview.level {
+                    Span::styled(label, Style::default().add_modifier(Modifier::BOLD))
+                } else {
+                    Span::styled(label, Style::default().fg(Color::DarkGray))
+                }
+            })
+            .fold(Vec::new(), |mut spans, span| {
+                if !spans.is_empty() {
+                    spans.push(Span::raw(" · "));
+                }
+                spans.push(span);
+                spans
+            });
+
+        let mut lines = vec![Line::from(counts), Line::from("")];
+        lines.extend(
+            preview
+                .text
+                .lines()
+                .map(|line| Line::from(line.to_string())),
+        );
+
+        let paragraph = Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).title(format!(
+                " Preview: {} (~{} tokens) ",
+                preview.level.name(),
+                preview.tokens
+            )))
+            .wrap(Wrap { trim: false })
+            .scroll((self.preview_scroll, 0));
+        f.render_widget(paragraph, area);
+    }
+
+    fn draw_diagnostics(&self, f: &mut Frame, area: Rect) {
+        let lines: Vec<Line> = if self.diagnostics.is_empty() {
+            vec![Line::from(Span::styled(
+                "No issues",
+                Style::default().fg(Color::Green),
+            ))]
+        } else {
+            self.diagnostics
+                .iter()
+                .map(|diagnostic| {
+                    let (label, color) = match diagnostic.severity {
+                        Severity::Error => ("error", Color::Red),
+                        Severity::Warning => ("warn", Color::Yellow),
+                        Severity::Info => ("info", Color::Blue),
+                    };
+                    Line::from(vec![
+                        Span::styled(format!("{label:<5} "), Style::default().fg(color)),
+                        Span::styled(
+                            format!("{} ", diagnostic.rule_id),
+                            Style::default().add_modifier(Modifier::BOLD),
+                        ),
+                        Span::raw(diagnostic.message.clone()),
+                    ])
+                })
+                .collect()
+        };
+
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(" Diagnostics ({}) ", self.diagnostics.len())),
+            )
+            .wrap(Wrap { trim: false });
+        f.render_widget(paragraph, area);
+    }
+
+    fn draw_help_bar(&self, f: &mut Frame, area: Rect) {
+        let help_text = match self.mode {
+            Mode::Insert => "Type to edit  Esc: done  Ctrl-X: discard  arrows: move cursor",
+            Mode::Review => "y: commit  Esc/n: back to editing  j/k: scroll",
+            Mode::Tree => {
+                "j/k: navigate  J/K: move  e: edit  t: type  a/A: add  x: delete  p: preview  s: review & commit  ?: help  q: quit"
+            }
+        };
+
+        let paragraph = Paragraph::new(help_text).style(Style::default().fg(Color::DarkGray));
+        f.render_widget(paragraph, area);
+    }
+
+    fn draw_review_overlay(&self, f: &mut Frame) {
+        let area = f.area();
+        let width = area.width.saturating_sub(8).max(20).min(area.width);
+        let height = area.height.saturating_sub(4).max(5).min(area.height);
+        let review_area = Rect::new(
+            (area.width - width) / 2,
+            (area.height - height) / 2,
+            width,
+            height,
+        );
+        f.render_widget(Clear, review_area);
+
+        let added = self
+            .diff
+            .iter()
+            .filter(|line| matches!(line, DiffLine::Added(_)))
+            .count();
+        let removed = self
+            .diff
+            .iter()
+            .filter(|line| matches!(line, DiffLine::Removed(_)))
+            .count();
+
+        let mut lines = Vec::new();
+        if !self.block_changes.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "Block changes:",
+                Style::default().add_modifier(Modifier::BOLD),
+            )));
+            for change in &self.block_changes {
+                lines.push(Line::from(format!("  {change}")));
+            }
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(
+            "SKILL.md:",
+            Style::default().add_modifier(Modifier::BOLD),
+        )));
+        for line in diff_hunks(&self.diff, DIFF_CONTEXT) {
+            lines.push(match line {
+                Some(DiffLine::Added(text)) => Line::from(Span::styled(
+                    format!("+ {text}"),
+                    Style::default().fg(Color::Green),
+                )),
+                Some(DiffLine::Removed(text)) => Line::from(Span::styled(
+                    format!("- {text}"),
+                    Style::default().fg(Color::Red),
+                )),
+                Some(DiffLine::Same(text)) => Line::from(Span::styled(
+                    format!("  {text}"),
+                    Style::default().fg(Color::DarkGray),
+                )),
+                None => Line::from(Span::styled("  ⋯", Style::default().fg(Color::DarkGray))),
+            });
+        }
+
+        let errors = self
+            .diagnostics
+            .iter()
+            .filter(|d| d.severity == Severity::Error)
+            .count();
+        let warning = if errors > 0 {
+            format!(" · {errors} lint errors")
+        } else {
+            String::new()
+        };
+
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan))
+                    .title(format!(
+                        " Review changes: +{added} -{removed}{warning} · y: commit  Esc: back "
+                    )),
+            )
+            .scroll((self.diff_scroll, 0));
+        f.render_widget(paragraph, review_area);
+    }
+}
+
+fn draw_help_overlay(f: &mut Frame) {
+    let area = f.area();
+
+    let help_width = 64.min(area.width.saturating_sub(4));
+    let help_height = 24.min(area.height.saturating_sub(4));
+    let x = (area.width - help_width) / 2;
+    let y = (area.height - help_height) / 2;
+    let help_area = Rect::new(x, y, help_width, help_height);
+
+    f.render_widget(Clear, help_area);
+
+    let help_text = vec![
+        Line::from(Span::styled(
+            "Editor Shortcuts",
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from("Tree:"),
+        Line::from("  j / Down        Next section or block"),
+        Line::from("  k / Up          Previous section or block"),
+        Line::from("  J / Shift-Down  Move selection down (blocks cross sections)"),
+        Line::from("  K / Shift-Up    Move selection up"),
+        Line::from("  mouse drag      Drag a section or block to reorder it"),
+        Line::from(""),
+        Line::from("Editing:"),
+        Line::from("  Enter / e       Edit block content or section title"),
+        Line::from("  Esc             Finish editing (Ctrl-X discards)"),
+        Line::from("  t               Cycle block type"),
+        Line::from("  a / A           Add block / add section"),
+        Line::from("  x / Delete      Delete block or section"),
+        Line::from(""),
+        Line::from("Preview and commit:"),
+        Line::from("  p / P           Next / previous disclosure level"),
+        Line::from("  PgUp/PgDn       Scroll preview"),
+        Line::from("  s / d           Review diff, then y to commit"),
+        Line::from("  q               Quit (twice with unsaved changes)"),
+        Line::from(""),
+        Line::from("Press ? or Esc to close this help"),
+    ];
+
+    let paragraph = Paragraph::new(help_text)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan))
+                .title(" Help "),
+        )
+        .wrap(Wrap { trim: false });
+    f.render_widget(paragraph, help_area);
+}
+
+/// RAII Guard to ensure terminal state is restored even on panic.
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn new() -> Result<Self> {
+        enable_raw_mode()?;
+        let mut stdout = io::stdout();
+        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+        Ok(Self)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = disable_raw_mode();
+        let _ = execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture);
+    }
+}
+
+/// Run the skill editor on a saved spec.
+///
+/// Returns the last committed spec, or `None` if nothing was committed.
+pub fn run_skill_editor(spec: SkillSpec, store: &dyn EditorStore) -> Result<Option<SkillSpec>> {
+    if !io::stdout().is_terminal() {
+        return Err(MsError::ValidationFailed(
+            "the skill editor requires an interactive terminal".to_string(),
+        ));
+    }
+
+    let editor = SkillEditor::new(spec, store.validator()?);
+    let _guard = TerminalGuard::new()?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+    editor.run(&mut terminal, store)
+}
+
+/// Line diff of `old` against `new` (longest common subsequence).
+#[must_use]
+pub fn line_diff(old: &str, new: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // lcs[i][j] = length of the LCS of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = Vec::with_capacity(old.len().max(new.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            diff.push(DiffLine::Same(old[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            diff.push(DiffLine::Removed(old[i].to_string()));
+            i += 1;
+        } else {
+            diff.push(DiffLine::Added(new[j].to_string()));
+            j += 1;
+        }
+    }
+    diff.extend(
+        old[i..]
+            .iter()
+            .map(|line| DiffLine::Removed((*line).to_string())),
+    );
+    diff.extend(
+        new[j..]
+            .iter()
+            .map(|line| DiffLine::Added((*line).to_string())),
+    );
+    diff
+}
+
+/// Changed lines with `context` unchanged lines around them; `None` marks
+/// an elided gap.
+fn diff_hunks(diff: &[DiffLine], context: usize) -> Vec<Option<&DiffLine>> {
+    let changed: Vec<usize> = diff
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, DiffLine::Same(_)))
+        .map(|(idx, _)| idx)
+        .collect();
+
+    let mut out = Vec::new();
+    let mut last_shown: Option<usize> = None;
+    for (idx, line) in diff.iter().enumerate() {
+        let near_change = changed
+            .iter()
+            .any(|changed_idx| changed_idx.abs_diff(idx) <= context);
+        if !near_change {
+            continue;
+        }
+        if last_shown.map_or(idx > 0, |last| idx > last + 1) {
+            out.push(None);
+        }
+        out.push(Some(line));
+        last_shown = Some(idx);
+    }
+    if last_shown.is_some_and(|last| last + 1 < diff.len()) {
+        out.push(None);
+    }
+    out
+}
+
+/// Block-level changes between two specs that the markdown diff does not
+/// show: type changes, moves between sections, additions and removals.
+fn block_changes(saved: &SkillSpec, working: &SkillSpec) -> Vec<String> {
+    let index = |spec: &SkillSpec| -> HashMap<String, (String, BlockType)> {
+        spec.sections
+            .iter()
+            .flat_map(|section| {
+                section.blocks.iter().map(move |block| {
+                    (
+                        block.id.clone(),
+                        (section.title.clone(), block.block_type.clone()),
+                    )
+                })
+            })
+            .collect()
+    };
+    let before = index(saved);
+    let after = index(working);
+
+    let mut changes = Vec::new();
+    for section in &working.sections {
+        for block in &section.blocks {
+            match before.get(&block.id) {
+                None => changes.push(format!("added {} in \"{}\"", block.id, section.title)),
+                Some((old_section, old_type)) => {
+                    if *old_type != block.block_type {
+                        changes.push(format!(
+                            "{}: {} -> {}",
+                            block.id,
+                            block_type_name(old_type),
+                            block_type_name(&block.block_type)
+                        ));
+                    }
+                    if *old_section != section.title {
+                        changes.push(format!(
+                            "{}: moved from \"{old_section}\" to \"{}\"",
+                            block.id, section.title
+                        ));
+                    }
+                }
+            }
+        }
+    }
+    for section in &saved.sections {
+        for block in &section.blocks {
+            if !after.contains_key(&block.id) {
+                changes.push(format!("removed {} from \"{}\"", block.id, section.title));
+            }
+        }
+    }
+    changes
+}
+
+fn render_preview(spec: &SkillSpec, level: DisclosureLevel) -> Preview {
+    let disclosed = disclose_level(spec, &SkillAssets::default(), level);
+    let mut text = format!("# {}\n", disclosed.frontmatter.name);
+    if !disclosed.frontmatter.description.is_empty() {
+        text.push('\n');
+        text.push_str(disclosed.frontmatter.description.trim_end());
+        text.push('\n');
+    }
+    if !disclosed.frontmatter.tags.is_empty() {
+        text.push_str(&format!(
+            "\nTags: {}\n",
+            disclosed.frontmatter.tags.join(", ")
+        ));
+    }
+    if let Some(body) = &disclosed.body {
+        text.push('\n');
+        text.push_str(body.trim_end());
+        text.push('\n');
+    }
+    Preview {
+        level,
+        text,
+        tokens: disclosed.token_estimate,
+    }
+}
+
+fn next_block_id(section: &SkillSection) -> String {
+    let mut n = section.blocks.len() + 1;
+    loop {
+        let id = format!("{}-block-{n}", section.id);
+        if !section.blocks.iter().any(|block| block.id == id) {
+            return id;
+        }
+        n += 1;
+    }
+}
+
+const fn block_type_name(block_type: &BlockType) -> &'static str {
+    match block_type {
+        BlockType::Text => "text",
+        BlockType::Code => "code",
+        BlockType::Rule => "rule",
+        BlockType::Pitfall => "pitfall",
+        BlockType::Command => "command",
+        BlockType::Checklist => "checklist",
+    }
+}
+
+const fn block_type_color(block_type: &BlockType) -> Color {
+    match block_type {
+        BlockType::Text => Color::Gray,
+        BlockType::Code => Color::Cyan,
+        BlockType::Rule => Color::Green,
+        BlockType::Pitfall => Color::Red,
+        BlockType::Command => Color::Magenta,
+        BlockType::Checklist => Color::Yellow,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::spec_lens::parse_markdown;
+    use crate::lint::rules::all_rules;
+
+    const SKILL: &str = "---\nid: demo\nname: Demo\ndescription: A demo skill\n---\n\n# Demo\n\nA demo skill\n\n## Rules\n\nAlways run the tests.\n\nNever force push.\n\n## Pitfalls\n\nForgetting to rebase.\n";
+
+    fn editor() -> SkillEditor {
+        let mut engine = ValidationEngine::with_defaults();
+        for rule in all_rules() {
+            engine.register(rule);
+        }
+        SkillEditor::new(parse_markdown(SKILL).unwrap(), engine)
+    }
+
+    fn key(editor: &mut SkillEditor, code: KeyCode) -> EditorAction {
+        editor.handle_key(code, KeyModifiers::NONE)
+    }
+
+    fn block_ids(editor: &SkillEditor, section: usize) -> Vec<String> {
+        editor.working().sections[section]
+            .blocks
+            .iter()
+            .map(|block| block.id.clone())
+            .collect()
+    }
+
+    #[test]
+    fn move_block_crosses_section_boundary() {
+        let mut editor = editor();
+        let last = block_ids(&editor, 0).pop().unwrap();
+        editor.select_node(TreeNode::Block(0, 1));
+
+        key(&mut editor, KeyCode::Char('J'));
+        assert_eq!(block_ids(&editor, 1)[0], last);
+        assert_eq!(editor.selected_node(), Some(TreeNode::Block(1, 0)));
+        assert!(editor.is_dirty());
+        assert!(
+            editor
+                .block_changes
+                .iter()
+                .any(|change| change.contains("moved from \"Rules\" to \"Pitfalls\""))
+        );
+
+        editor.handle_key(KeyCode::Up, KeyModifiers::SHIFT);
+        assert_eq!(block_ids(&editor, 0).last(), Some(&last));
+        assert!(!editor.is_dirty());
+    }
+
+    #[test]
+    fn type_change_shows_in_review_and_commits() {
+        let mut editor = editor();
+        editor.select_node(TreeNode::Block(0, 0));
+        let before = editor.working().sections[0].blocks[0].block_type.clone();
+
+        key(&mut editor, KeyCode::Char('t'));
+        assert_ne!(editor.working().sections[0].blocks[0].block_type, before);
+        assert_eq!(editor.block_changes.len(), 1);
+
+        key(&mut editor, KeyCode::Char('s'));
+        assert_eq!(editor.mode, Mode::Review);
+        assert_eq!(key(&mut editor, KeyCode::Char('y')), EditorAction::Commit);
+        editor.mark_saved();
+        assert!(!editor.is_dirty());
+        assert!(editor.block_changes.is_empty());
+    }
+
+    #[test]
+    fn inline_edit_updates_diagnostics_live() {
+        let mut editor = editor();
+        let empty = |editor: &SkillEditor| {
+            editor
+                .diagnostics()
+                .iter()
+                .any(|d| d.rule_id == "non-empty-blocks")
+        };
+        assert!(!empty(&editor));
+
+        editor.select_node(TreeNode::Block(1, 0));
+        key(&mut editor, KeyCode::Char('a'));
+        assert_eq!(editor.mode, Mode::Insert);
+        assert!(empty(&editor));
+
+        for c in "Skipping".chars() {
+            key(&mut editor, KeyCode::Char(c));
+        }
+        key(&mut editor, KeyCode::Enter);
+        key(&mut editor, KeyCode::Char('x'));
+        assert!(!empty(&editor));
+        assert_eq!(
+            editor.working().sections[1].blocks[1].content,
+            "Skipping\nx"
+        );
+
+        key(&mut editor, KeyCode::Esc);
+        assert_eq!(editor.mode, Mode::Tree);
+        assert!(
+            editor
+                .diff
+                .contains(&DiffLine::Added("Skipping".to_string()))
+        );
+    }
+
+    #[test]
+    fn discard_restores_original_content() {
+        let mut editor = editor();
+        editor.select_node(TreeNode::Block(0, 0));
+        key(&mut editor, KeyCode::Char('e'));
+        key(&mut editor, KeyCode::Backspace);
+        key(&mut editor, KeyCode::Char('!'));
+        assert!(editor.is_dirty());
+
+        editor.handle_key(KeyCode::Char('x'), KeyModifiers::CONTROL);
+        assert_eq!(editor.mode, Mode::Tree);
+        assert!(!editor.is_dirty());
+    }
+
+    #[test]
+    fn preview_token_counts_grow_with_level() {
+        let editor = editor();
+        let tokens: Vec<usize> = editor.previews.iter().map(|p| p.tokens).collect();
+        assert_eq!(tokens.len(), PREVIEW_LEVELS.len());
+        assert!(tokens[0] < tokens[1]);
+        assert!(tokens[1] <= tokens[3]);
+        assert!(editor.previews[3].text.contains("Never force push."));
+        assert!(!editor.previews[0].text.contains("Never force push."));
+    }
+
+    #[test]
+    fn quit_with_unsaved_changes_needs_confirmation() {
+        let mut editor = editor();
+        assert_eq!(key(&mut editor, KeyCode::Char('q')), EditorAction::Quit);
+
+        editor.select_node(TreeNode::Block(0, 0));
+        key(&mut editor, KeyCode::Char('x'));
+        assert_eq!(key(&mut editor, KeyCode::Char('q')), EditorAction::Continue);
+        assert_eq!(key(&mut editor, KeyCode::Char('q')), EditorAction::Quit);
+    }
+
+    #[test]
+    fn line_diff_marks_changes() {
+        let diff = line_diff("a\nb\nc\n", "a\nc\nd\n");
+        assert_eq!(
+            diff,
+            vec![
+                DiffLine::Same("a".to_string()),
+                DiffLine::Removed("b".to_string()),
+                DiffLine::Same("c".to_string()),
+                DiffLine::Added("d".to_string()),
+            ]
+        );
+        let hunks = diff_hunks(&diff, 0);
+        assert_eq!(hunks.len(), 4);
+        assert!(hunks[0].is_none());
+    }
+}
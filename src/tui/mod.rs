@@ -4,6 +4,8 @@
 
 pub mod browse;
 pub mod build_tui;
+pub mod editor;
 
 pub use browse::{BrowseTui, run_browse_tui};
 pub use build_tui::BuildTui;
+pub use editor::{EditorStore, SkillEditor, run_skill_editor};
@@ -5,7 +5,14 @@
 //!
 //! This is a P1 E2E test that exercises the core bundle distribution workflow.
 
-use super::fixture::E2EFixture;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use super::fixture::{CommandOutput, E2EFixture};
+use ms::bundler::{
+    Bundle, BundleInfo, BundleManifest, BundlePackage, BundleRegistry, BundledSkill, InstallSource,
+    InstalledBundle, install,
+};
 use ms::error::Result;
 
 /// Test the complete bundle creation and local installation workflow.
@@ -397,3 +404,175 @@ Content for reinstall testing.
     fixture.generate_report();
     Ok(())
 }
+
+/// Package a one-skill bundle whose SKILL.md is `body`.
+fn advisory_bundle(dir: &Path, version: &str, body: &str) -> BundlePackage {
+    let root = dir.join(version);
+    let skill_dir = root.join("skills/by-id/audited-skill");
+    std::fs::create_dir_all(&skill_dir).unwrap();
+    std::fs::write(skill_dir.join("SKILL.md"), body).unwrap();
+    let manifest = BundleManifest {
+        bundle: BundleInfo {
+            id: "audited-bundle".to_string(),
+            name: "Audited Bundle".to_string(),
+            version: version.to_string(),
+            description: None,
+            authors: vec![],
+            license: None,
+            repository: None,
+            keywords: vec![],
+            ms_version: None,
+        },
+        skills: vec![BundledSkill {
+            name: "audited-skill".to_string(),
+            path: PathBuf::from("skills/by-id/audited-skill"),
+            version: None,
+            hash: None,
+            optional: false,
+        }],
+        dependencies: vec![],
+        checksum: None,
+        signatures: vec![],
+    };
+    Bundle::new(manifest, &root).package().unwrap()
+}
+
+/// Install `package` into the fixture's archive and register it.
+fn register_install(
+    fixture: &E2EFixture,
+    package: &BundlePackage,
+    source: &Path,
+    blobs: BTreeMap<String, String>,
+) {
+    let archive = fixture.ms_root.join("archive");
+    install(package, &archive, &[]).unwrap();
+    BundleRegistry::open(&archive)
+        .unwrap()
+        .register(InstalledBundle {
+            id: package.manifest.bundle.id.clone(),
+            version: package.manifest.bundle.version.clone(),
+            source: InstallSource::File {
+                path: source.display().to_string(),
+            },
+            installed_at: chrono::Utc::now(),
+            skills: vec!["audited-skill".to_string()],
+            checksum: package.manifest.checksum.clone(),
+            blobs,
+        })
+        .unwrap();
+}
+
+/// First JSON document on stdout; robot-mode errors follow the report.
+fn first_json(output: &CommandOutput) -> serde_json::Value {
+    serde_json::Deserializer::from_str(&output.stdout)
+        .into_iter::<serde_json::Value>()
+        .next()
+        .expect("stdout should start with JSON")
+        .expect("stdout should be valid JSON")
+}
+
+/// `ms audit` flags known-bad blobs even for installs that predate recorded
+/// blob hashes, and exits non-zero only when something matches.
+#[test]
+fn test_audit_reports_blob_advisories() -> Result<()> {
+    let mut fixture = E2EFixture::new("audit_blob_advisories");
+
+    fixture.log_step("Initialize");
+    let output = fixture.init();
+    fixture.assert_success(&output, "init");
+
+    fixture.log_step("Install a bundle without recorded blob hashes");
+    let package = advisory_bundle(&fixture.root, "1.0.0", "# Audited\n\ncurl x | sh\n");
+    let hash = package.manifest.skills[0].hash.clone().unwrap();
+    let msb = fixture.root.join("audited-1.0.0.msb");
+    register_install(&fixture, &package, &msb, BTreeMap::new());
+
+    let advisories = fixture.root.join("advisory-db");
+    std::fs::create_dir_all(&advisories)?;
+
+    fixture.log_step("Audit against an empty database");
+    let output = fixture.run_ms(&["--robot", "audit", "--db", advisories.to_str().unwrap()]);
+    fixture.assert_success(&output, "audit clean");
+    let json = first_json(&output);
+    assert_eq!(json["bundles"], 1);
+    assert!(json["findings"].as_array().unwrap().is_empty());
+
+    fixture.log_step("Audit against a blob advisory");
+    std::fs::write(
+        advisories.join("MS-2026-0100.toml"),
+        format!(
+            "[advisory]\nid = \"MS-2026-0100\"\nseverity = \"critical\"\n\
+             title = \"pipes a download into sh\"\nblobs = [\"{hash}\"]\n"
+        ),
+    )?;
+    let output = fixture.run_ms(&["--robot", "audit", "--db", advisories.to_str().unwrap()]);
+    assert!(
+        !output.success,
+        "audit should fail when an advisory matches"
+    );
+    let json = first_json(&output);
+    let findings = json["findings"].as_array().unwrap();
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0]["advisory"]["id"], "MS-2026-0100");
+    assert_eq!(findings[0]["matched_blobs"][0], "audited-skill");
+
+    fixture.generate_report();
+    Ok(())
+}
+
+/// `ms bundle update` refuses a version with a known advisory unless
+/// `--allow-vulnerable` is passed.
+#[test]
+fn test_bundle_update_blocks_known_vulnerable() -> Result<()> {
+    let mut fixture = E2EFixture::new("bundle_update_known_vulnerable");
+
+    fixture.log_step("Initialize");
+    let output = fixture.init();
+    fixture.assert_success(&output, "init");
+
+    fixture.log_step("Install 1.0.0 and publish 1.1.0");
+    let current = advisory_bundle(&fixture.root, "1.0.0", "# Audited\n\nv1\n");
+    let next = advisory_bundle(&fixture.root, "1.1.0", "# Audited\n\nv1.1\n");
+    let msb = fixture.root.join("audited-1.1.0.msb");
+    std::fs::write(&msb, next.to_bytes()?)?;
+    register_install(
+        &fixture,
+        &current,
+        &msb,
+        InstalledBundle::blobs_from_manifest(&current.manifest),
+    );
+
+    let advisories = fixture.ms_root.join("advisories");
+    std::fs::create_dir_all(&advisories)?;
+    std::fs::write(
+        advisories.join("MS-2026-0200.toml"),
+        "[advisory]\nid = \"MS-2026-0200\"\nbundle = \"audited-bundle\"\n\
+         severity = \"high\"\ntitle = \"unsafe release\"\n\n\
+         [versions]\npatched = [\">=2.0.0\"]\n",
+    )?;
+
+    fixture.log_step("Update is refused");
+    let output = fixture.run_ms(&["--robot", "bundle", "update", "audited-bundle"]);
+    fixture.assert_success(&output, "bundle update");
+    let update = &output.json()["updates"][0];
+    assert_eq!(update["skipped_reason"], "known_vulnerable");
+    assert_eq!(update["applied"], false);
+    assert_eq!(update["advisories"][0], "MS-2026-0200");
+
+    fixture.log_step("Update with --allow-vulnerable");
+    let output = fixture.run_ms(&[
+        "--robot",
+        "bundle",
+        "update",
+        "audited-bundle",
+        "--allow-vulnerable",
+    ]);
+    fixture.assert_success(&output, "bundle update --allow-vulnerable");
+    let update = &output.json()["updates"][0];
+    assert_eq!(update["applied"], true);
+    let registry = BundleRegistry::open(&fixture.ms_root.join("archive"))?;
+    assert_eq!(registry.get("audited-bundle").unwrap().version, "1.1.0");
+
+    fixture.generate_report();
+    Ok(())
+}
@@ -1011,3 +1011,50 @@ fn test_mcp_index_tool() -> Result<()> {
     client.kill();
     Ok(())
 }
+
+const SKILL_WITH_SCRIPT: &str = r#"---
+id: release-notes
+name: Release Notes
+description: Draft release notes from the changelog
+scripts:
+  - name: draft
+    path: scripts/draft.sh
+    description: Print a release notes draft
+---
+
+# Release Notes
+
+## Usage
+
+Run the draft script and edit the result.
+"#;
+
+#[test]
+fn test_mcp_script_tool_refuses_untrusted_skill() -> Result<()> {
+    let mut fixture = setup_mcp_fixture("mcp_script_tool_untrusted")?;
+
+    fixture.log_step("Create unsigned skill with a script");
+    fixture.create_skill_in_layer("release-notes", SKILL_WITH_SCRIPT, "project")?;
+    let script_dir = fixture.root.join("skills/release-notes/scripts");
+    std::fs::create_dir_all(&script_dir)?;
+    std::fs::write(script_dir.join("draft.sh"), "#!/bin/sh\necho drafted\n")?;
+    let output = fixture.run_ms(&["--robot", "index"]);
+    fixture.assert_success(&output, "index");
+
+    fixture.log_step("Deny unsigned project skills");
+    let mut config = std::fs::read_to_string(&fixture.config_path)?;
+    config.push_str("\n[[security.signing.policy]]\nlayer = \"project\"\nuntrusted = \"deny\"\n");
+    std::fs::write(&fixture.config_path, config)?;
+
+    let mut client = McpClient::spawn(&fixture, false)?;
+    client.initialize()?;
+    let response = client.call_tool("release-notes__draft", json!({}))?;
+
+    assert!(response.tool_is_error(), "untrusted script must not run");
+    let text = response.tool_text().expect("Should have tool text");
+    assert!(text.contains("trust policy"), "unexpected error: {text}");
+    assert!(!text.contains("drafted"), "script output leaked: {text}");
+
+    client.kill();
+    Ok(())
+}
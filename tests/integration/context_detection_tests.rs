@@ -328,9 +328,11 @@ mod context_collector_tests {
         let ctx1 = CollectedContext {
             cwd: PathBuf::from("/test"),
             detected_projects: vec![],
+            dependencies: vec![],
             recent_files: vec![],
             detected_tools: HashSet::new(),
             git_context: None,
+            workspace: ms::context::WorkspaceLayout::default(),
             env_signals: std::collections::HashMap::new(),
             collected_at: chrono::Utc::now(),
             fingerprint: ms::context::CollectorFingerprint(0),
@@ -447,6 +449,7 @@ mod context_collector_tests {
                     marker_pattern: "marker".to_string(),
                 })
                 .collect(),
+            dependencies: vec![],
             recent_files: files
                 .iter()
                 .map(|f| ms::context::RecentFile {
@@ -460,6 +463,7 @@ mod context_collector_tests {
                 .collect(),
             detected_tools: tools.iter().map(|s| s.to_string()).collect(),
             git_context: None,
+            workspace: ms::context::WorkspaceLayout::default(),
             env_signals: std::collections::HashMap::new(),
             collected_at: chrono::Utc::now(),
             fingerprint: ms::context::CollectorFingerprint(0),
@@ -489,6 +493,7 @@ mod relevance_scorer_tests {
                 file_patterns: file_patterns.iter().map(|s| s.to_string()).collect(),
                 tools: tools.iter().map(|s| s.to_string()).collect(),
                 signals: vec![],
+                dependencies: vec![],
             },
             ..Default::default()
         }
@@ -591,6 +596,7 @@ mod relevance_scorer_tests {
                     ContextSignal::new("tokio", "tokio::.*", 1.0),
                     ContextSignal::new("async-std", "async_std::.*", 0.5),
                 ],
+                dependencies: vec![],
             },
             ..Default::default()
         };
@@ -22,6 +22,7 @@ fn arb_skill_spec() -> impl Strategy<Value = SkillSpec> {
             id,
             block_type,
             content,
+            suppressions: Vec::new(),
         },
     );
 
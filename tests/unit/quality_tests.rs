@@ -44,6 +44,7 @@ fn minimal_spec() -> SkillSpec {
                 id: "b1".to_string(),
                 block_type: BlockType::Text,
                 content: "Short content".to_string(),
+                suppressions: Vec::new(),
             }],
         }],
         ..Default::default()
@@ -59,6 +60,7 @@ fn spec_with_sections(count: usize) -> SkillSpec {
                 id: format!("b{i}"),
                 block_type: BlockType::Text,
                 content: "a".repeat(200),
+                suppressions: Vec::new(),
             }],
         })
         .collect();
@@ -91,6 +93,7 @@ fn spec_with_code_example() -> SkillSpec {
                     id: "b1".to_string(),
                     block_type: BlockType::Text,
                     content: "a".repeat(500),
+                    suppressions: Vec::new(),
                 }],
             },
             SkillSection {
@@ -100,6 +103,7 @@ fn spec_with_code_example() -> SkillSpec {
                     id: "b2".to_string(),
                     block_type: BlockType::Code,
                     content: r#"fn main() { println!("Hello"); }"#.to_string(),
+                    suppressions: Vec::new(),
                 }],
             },
         ],
@@ -125,6 +129,7 @@ fn rich_spec() -> SkillSpec {
                     id: "b1".to_string(),
                     block_type: BlockType::Text,
                     content: "a".repeat(800),
+                    suppressions: Vec::new(),
                 }],
             },
             SkillSection {
@@ -134,6 +139,7 @@ fn rich_spec() -> SkillSpec {
                     id: "b2".to_string(),
                     block_type: BlockType::Text,
                     content: "a".repeat(600),
+                    suppressions: Vec::new(),
                 }],
             },
             SkillSection {
@@ -144,11 +150,13 @@ fn rich_spec() -> SkillSpec {
                         id: "b3".to_string(),
                         block_type: BlockType::Code,
                         content: "a".repeat(400),
+                        suppressions: Vec::new(),
                     },
                     SkillBlock {
                         id: "b4".to_string(),
                         block_type: BlockType::Code,
                         content: "b".repeat(300),
+                        suppressions: Vec::new(),
                     },
                 ],
             },
@@ -188,6 +196,8 @@ fn quality_scorer_new_with_custom_weights() {
         usage_weight: 0.0,
         toolchain_weight: 0.0,
         freshness_weight: 0.0,
+        verification_weight: 0.0,
+        outcome_weight: 0.0,
     };
     let scorer = QualityScorer::new(weights.clone());
     assert_eq!(scorer.weights.structure_weight, 0.5);
@@ -235,6 +245,9 @@ fn score_spec_rich_produces_high_score() {
         evidence_count: Some(5),
         modified_at: Some(Utc::now()),
         toolchain_match: true,
+        verification_score: None,
+        stale_references: 0,
+        outcome_rate: None,
     };
     let score = scorer.score_spec(&rich_spec(), &context);
 
@@ -263,12 +276,18 @@ fn score_spec_always_in_range() {
                 evidence_count: Some(0),
                 modified_at: Some(Utc::now() - Duration::days(365)),
                 toolchain_match: false,
+                verification_score: None,
+                stale_references: 0,
+                outcome_rate: None,
             },
             QualityContext {
                 usage_count: Some(100),
                 evidence_count: Some(50),
                 modified_at: Some(Utc::now()),
                 toolchain_match: true,
+                verification_score: None,
+                stale_references: 0,
+                outcome_rate: None,
             },
         ] {
             let score = scorer.score_spec(&spec, &context);
@@ -687,6 +706,9 @@ fn no_issues_for_rich_spec_with_good_context() {
         evidence_count: Some(10),
         modified_at: Some(Utc::now()),
         toolchain_match: true,
+        verification_score: None,
+        stale_references: 0,
+        outcome_rate: None,
     };
     let score = scorer.score_spec(&rich_spec(), &context);
 
@@ -861,6 +883,8 @@ fn quality_breakdown_debug() {
         usage: 0.8,
         toolchain: 0.9,
         freshness: 1.0,
+        verification: None,
+        outcomes: None,
     };
     let debug = format!("{breakdown:?}");
     assert!(debug.contains("structure"));
@@ -876,6 +900,8 @@ fn quality_breakdown_clone() {
         usage: 0.8,
         toolchain: 0.9,
         freshness: 1.0,
+        verification: None,
+        outcomes: None,
     };
     let cloned = breakdown.clone();
     assert_eq!(cloned.structure, 0.5);